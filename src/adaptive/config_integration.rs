@@ -299,16 +299,6 @@ impl ConfigurationManager {
         info!("Saved timer configuration from adaptive system");
         Ok(())
     }
-
-    async fn save_bot_config(&self, config: crate::config::BotConfiguration) -> Result<()> {
-        let config_path = std::path::Path::new("config").join("bot.yaml");
-        let content = serde_yaml::to_string(&config)?;
-        tokio::fs::write(&config_path, content).await?;
-        
-        // Don't try to update internal cache directly - let the file watcher handle it
-        info!("Saved bot configuration from adaptive system");
-        Ok(())
-    }
 }
 
 /// Configuration-aware tuning strategy
@@ -340,6 +330,7 @@ impl ParameterTuningStrategy for ConfigurationTuningStrategy {
                         reason: format!("High message throughput ({:.1} msg/s) - increase rate limit", 
                                       metrics.messages_per_second),
                         expected_improvement: 0.15,
+                        trigger: MetricTrigger::new("messages_per_second", metrics.messages_per_second, 50.0),
                     });
                 }
             }
@@ -359,6 +350,7 @@ impl ParameterTuningStrategy for ConfigurationTuningStrategy {
                         reason: format!("High processing latency ({:.1}ms) - increase batch size for efficiency", 
                                       metrics.average_latency_ms),
                         expected_improvement: 0.25,
+                        trigger: MetricTrigger::new("average_latency_ms", metrics.average_latency_ms, 200.0),
                     });
                 }
             }
@@ -379,6 +371,7 @@ impl ParameterTuningStrategy for ConfigurationTuningStrategy {
                             reason: format!("Low system health ({:.1}%) - reduce worker threads", 
                                           metrics.system_health_score * 100.0),
                             expected_improvement: 0.2,
+                            trigger: MetricTrigger::new("system_health_score", metrics.system_health_score, 0.8),
                         });
                     }
                 }
@@ -399,6 +392,7 @@ impl ParameterTuningStrategy for ConfigurationTuningStrategy {
                         reason: format!("High memory usage ({:.1}%) - reduce cache size", 
                                       metrics.memory_usage_percent),
                         expected_improvement: 0.3,
+                        trigger: MetricTrigger::new("memory_usage_percent", metrics.memory_usage_percent, 80.0),
                     });
                 }
             }
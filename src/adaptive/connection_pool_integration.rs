@@ -170,6 +170,7 @@ impl ParameterTuningStrategy for ConnectionPoolTuningStrategy {
                         reason: format!("High pool utilization ({:.1}%) - increase pool size", 
                                       metrics.connection_pool_utilization * 100.0),
                         expected_improvement: 0.3,
+                        trigger: MetricTrigger::new("connection_pool_utilization", metrics.connection_pool_utilization, 0.9),
                     });
                 }
             }
@@ -188,6 +189,7 @@ impl ParameterTuningStrategy for ConnectionPoolTuningStrategy {
                             reason: format!("Low pool utilization ({:.1}%) - reduce pool size", 
                                           metrics.connection_pool_utilization * 100.0),
                             expected_improvement: 0.1,
+                            trigger: MetricTrigger::new("connection_pool_utilization", metrics.connection_pool_utilization, 0.3),
                         });
                     }
                 }
@@ -208,6 +210,7 @@ impl ParameterTuningStrategy for ConnectionPoolTuningStrategy {
                         reason: format!("High connection failures ({}) - increase timeout", 
                                       metrics.connection_failures),
                         expected_improvement: 0.4,
+                        trigger: MetricTrigger::new("connection_failures", metrics.connection_failures as f64, 5.0),
                     });
                 }
             }
@@ -225,10 +228,27 @@ impl ParameterTuningStrategy for ConnectionPoolTuningStrategy {
     }
 }
 
-/// Metrics collector integration with your connection pool
+/// Failures in each collection interval, from the pool's lifetime failure count
+#[derive(Debug, Default)]
+pub struct ConnectionFailureCounter {
+    last_total: u64,
+}
+
+impl ConnectionFailureCounter {
+    /// Failures since the previous call (a pool whose counters restarted counts from zero)
+    pub fn failures_since_last(&mut self, total: u64) -> u64 {
+        let new_failures = if total >= self.last_total { total - self.last_total } else { total };
+        self.last_total = total;
+        new_failures
+    }
+}
+
+/// Metrics collector integration with your connection pool; call once per
+/// interval with the same `failures` counter
 pub async fn collect_connection_pool_metrics(
     pool: &Arc<ConnectionPool>,
     metrics_collector: &Arc<MetricsCollector>,
+    failures: &mut ConnectionFailureCounter,
 ) -> Result<()> {
     let adaptive_metrics = pool.get_adaptive_metrics().await?;
     
@@ -236,6 +256,11 @@ pub async fn collect_connection_pool_metrics(
     metrics_collector.record_custom_metric("pool_utilization", adaptive_metrics.utilization_percentage).await?;
     metrics_collector.record_custom_metric("pool_efficiency", adaptive_metrics.pool_efficiency_score * 100.0).await?;
     metrics_collector.record_custom_metric("pool_failure_rate", adaptive_metrics.failure_rate_percentage).await?;
+    // Read by the health score alongside the pipeline's own error rate, so it's
+    // the failures in this interval rather than since startup
+    let connection_failures: u64 = adaptive_metrics.platform_metrics.values().map(|m| m.connection_failures as u64).sum();
+    let new_failures = failures.failures_since_last(connection_failures);
+    metrics_collector.record_custom_metric("connection_failures", new_failures as f64).await?;
     
     // Record platform-specific metrics
     for (platform, platform_metrics) in &adaptive_metrics.platform_metrics {
@@ -295,4 +320,14 @@ mod tests {
         assert!(!suggestions.is_empty());
         assert!(suggestions.iter().any(|s| s.parameter_name == "connection_pool_max_size"));
     }
+
+    #[test]
+    fn test_connection_failures_are_counted_per_interval() {
+        let mut failures = ConnectionFailureCounter::default();
+        assert_eq!(failures.failures_since_last(3), 3);
+        assert_eq!(failures.failures_since_last(3), 0);
+        assert_eq!(failures.failures_since_last(5), 2);
+        // A new pool starts counting again
+        assert_eq!(failures.failures_since_last(1), 1);
+    }
 }
\ No newline at end of file
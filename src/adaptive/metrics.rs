@@ -167,6 +167,98 @@ impl MetricTimeSeries {
     }
 }
 
+/// Relative change between the two halves of a window that counts as a trend
+const TREND_THRESHOLD_PERCENT: f64 = 5.0;
+
+/// Which way a metric moved over a window
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TrendDirection {
+    Rising,
+    Falling,
+    Steady,
+}
+
+/// Summary of a metric over a recent time window
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricTrend {
+    pub metric: String,
+    pub window_minutes: u64,
+    pub samples: usize,
+    pub min: f64,
+    pub avg: f64,
+    pub max: f64,
+    /// Average of the later half of the window relative to the earlier half
+    pub change_percent: f64,
+    pub direction: TrendDirection,
+}
+
+impl MetricTrend {
+    /// Summarize the points recorded in the `window_minutes` before `now` (unix seconds)
+    pub fn from_points(metric: &str, points: &[MetricDataPoint], window_minutes: u64, now: u64) -> Option<Self> {
+        let cutoff = now.saturating_sub(window_minutes * 60);
+        let mut values: Vec<(u64, f64)> = points.iter()
+            .filter(|p| p.timestamp >= cutoff && p.timestamp <= now)
+            .map(|p| (p.timestamp, p.value))
+            .collect();
+        if values.is_empty() {
+            return None;
+        }
+        values.sort_by_key(|(timestamp, _)| *timestamp);
+
+        let values: Vec<f64> = values.into_iter().map(|(_, value)| value).collect();
+        let average = |slice: &[f64]| slice.iter().sum::<f64>() / slice.len() as f64;
+
+        let change_percent = if values.len() < 2 {
+            0.0
+        } else {
+            let (earlier, later) = values.split_at(values.len() / 2);
+            let (earlier, later) = (average(earlier), average(later));
+            if earlier.abs() > f64::EPSILON {
+                (later - earlier) / earlier.abs() * 100.0
+            } else if later > earlier {
+                100.0
+            } else if later < earlier {
+                -100.0
+            } else {
+                0.0
+            }
+        };
+
+        let direction = if change_percent > TREND_THRESHOLD_PERCENT {
+            TrendDirection::Rising
+        } else if change_percent < -TREND_THRESHOLD_PERCENT {
+            TrendDirection::Falling
+        } else {
+            TrendDirection::Steady
+        };
+
+        Some(Self {
+            metric: metric.to_string(),
+            window_minutes,
+            samples: values.len(),
+            min: values.iter().copied().fold(f64::INFINITY, f64::min),
+            avg: average(&values),
+            max: values.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+            change_percent,
+            direction,
+        })
+    }
+
+    /// One-line summary for chat
+    pub fn summary(&self) -> String {
+        let (icon, direction) = match self.direction {
+            TrendDirection::Rising => ("📈", "rising"),
+            TrendDirection::Falling => ("📉", "falling"),
+            TrendDirection::Steady => ("➡️", "steady"),
+        };
+        format!(
+            "{} {} over {}m: min {:.1} / avg {:.1} / max {:.1} ({} samples), {} {:+.0}%",
+            icon, self.metric, self.window_minutes, self.min, self.avg, self.max,
+            self.samples, direction, self.change_percent
+        )
+    }
+}
+
 /// Metrics collector that gathers and stores performance data
 pub struct MetricsCollector {
     metrics: Arc<RwLock<HashMap<String, MetricTimeSeries>>>,
@@ -276,6 +368,28 @@ impl MetricsCollector {
         Ok(metrics.keys().cloned().collect())
     }
     
+    /// Trend of a recorded metric over the last `window_minutes` (within the retention period)
+    pub async fn get_metric_trend(&self, name: &str, window_minutes: u64) -> Result<MetricTrend> {
+        let max_minutes = self.retention_hours * 60;
+        if window_minutes == 0 || window_minutes > max_minutes {
+            return Err(anyhow::anyhow!("Window must be between 1 and {} minutes", max_minutes));
+        }
+
+        let metrics = self.metrics.read().await;
+        let Some(series) = metrics.get(name) else {
+            let mut known: Vec<&str> = metrics.keys().map(String::as_str).collect();
+            known.sort_unstable();
+            known.truncate(8);
+            return Err(anyhow::anyhow!("Unknown metric '{}' (known: {})", name,
+                if known.is_empty() { "none yet".to_string() } else { known.join(", ") }));
+        };
+
+        let points: Vec<MetricDataPoint> = series.data_points.iter().cloned().collect();
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+        MetricTrend::from_points(name, &points, window_minutes, now)
+            .ok_or_else(|| anyhow::anyhow!("No '{}' data in the last {} minutes", name, window_minutes))
+    }
+    
     pub async fn calculate_system_health(&self) -> Result<f64> {
         let metrics = self.metrics.read().await;
         
@@ -570,6 +684,49 @@ mod tests {
         assert!(metrics.contains_key("memory_usage"));
     }
     
+    fn points(values: &[(u64, f64)]) -> Vec<MetricDataPoint> {
+        values.iter()
+            .map(|&(timestamp, value)| MetricDataPoint { timestamp, value, metric_type: MetricType::Latency })
+            .collect()
+    }
+    
+    #[test]
+    fn test_metric_trend_summary() {
+        let now = 10_000;
+        // One old point outside the 10 minute window, then latency climbing
+        let data = points(&[(now - 3600, 900.0), (now - 500, 80.0), (now - 400, 100.0), (now - 200, 150.0), (now - 60, 190.0)]);
+        
+        let trend = MetricTrend::from_points("latency_chat", &data, 10, now).unwrap();
+        assert_eq!(trend.samples, 4);
+        assert_eq!(trend.min, 80.0);
+        assert_eq!(trend.max, 190.0);
+        assert_eq!(trend.avg, 130.0);
+        assert_eq!(trend.direction, TrendDirection::Rising);
+        assert_eq!(trend.summary(), "📈 latency_chat over 10m: min 80.0 / avg 130.0 / max 190.0 (4 samples), rising +89%");
+        
+        let falling = points(&[(now - 300, 50.0), (now - 200, 40.0), (now - 100, 20.0)]);
+        assert_eq!(MetricTrend::from_points("x", &falling, 10, now).unwrap().direction, TrendDirection::Falling);
+        let steady = points(&[(now - 300, 50.0), (now - 100, 51.0)]);
+        assert_eq!(MetricTrend::from_points("x", &steady, 10, now).unwrap().direction, TrendDirection::Steady);
+        assert!(MetricTrend::from_points("x", &steady, 1, now).is_none());
+    }
+    
+    #[tokio::test]
+    async fn test_metric_trend_validation() {
+        let collector = MetricsCollector::new(1).unwrap();
+        collector.record_latency("chat", 120.0).await.unwrap();
+        collector.record_latency("chat", 140.0).await.unwrap();
+        
+        let trend = collector.get_metric_trend("latency_chat", 15).await.unwrap();
+        assert_eq!(trend.samples, 2);
+        assert_eq!(trend.avg, 130.0);
+        
+        let err = collector.get_metric_trend("latency_nope", 15).await.unwrap_err();
+        assert!(err.to_string().contains("known: latency_chat"));
+        assert!(collector.get_metric_trend("latency_chat", 0).await.is_err());
+        assert!(collector.get_metric_trend("latency_chat", 61).await.is_err());
+    }
+    
     #[tokio::test]
     async fn test_time_series_operations() {
         let mut series = MetricTimeSeries::new("test".to_string(), 100);
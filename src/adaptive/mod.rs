@@ -62,6 +62,9 @@ pub struct AdaptiveConfig {
     pub rollback_threshold_seconds: u64,
     pub learning_mode: bool,
     pub strategies: StrategyConfig,
+    /// Per-parameter limits on tuning for this deployment
+    #[serde(default)]
+    pub parameter_bounds: HashMap<String, ParameterBounds>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -86,6 +89,25 @@ impl Default for AdaptiveConfig {
                 memory_tuning: MemoryTuningConfig::default(),
                 error_rate_tuning: ErrorRateTuningConfig::default(),
             },
+            parameter_bounds: HashMap::new(),
+        }
+    }
+}
+
+impl AdaptiveConfig {
+    /// Read `[adaptive.parameter_bounds]` from a startup TOML file; a missing file or section means no bounds
+    pub fn load_parameter_bounds(path: &std::path::Path) -> Result<HashMap<String, ParameterBounds>> {
+        if !path.exists() {
+            return Ok(HashMap::new());
+        }
+
+        let content = std::fs::read_to_string(path)?;
+        let startup: toml::Value = toml::from_str(&content)
+            .map_err(|e| anyhow::anyhow!("Failed to parse {}: {}", path.display(), e))?;
+        match startup.get("adaptive").and_then(|a| a.get("parameter_bounds")) {
+            Some(bounds) => Ok(bounds.clone().try_into()
+                .map_err(|e| anyhow::anyhow!("Invalid parameter_bounds in {}: {}", path.display(), e))?),
+            None => Ok(HashMap::new()),
         }
     }
 }
@@ -105,7 +127,7 @@ impl AdaptivePerformanceSystem {
             config.safety_checks_enabled,
             config.max_parameter_changes_per_hour,
             config.rollback_threshold_seconds,
-        )?);
+        )?.with_parameter_bounds(config.parameter_bounds.clone())?);
         
         let tuning_engine = Arc::new(TuningEngine::new(
             config.clone(),
@@ -204,6 +226,32 @@ impl AdaptivePerformanceSystem {
         Ok(result)
     }
     
+    /// Most recent tuning changes, newest first, with their justifications
+    pub async fn get_recent_changes(&self, limit: usize) -> Result<Vec<ParameterChange>> {
+        let tuning_history = self.tuning_engine.get_tuning_history().await?;
+        Ok(tuning_history.iter()
+            .rev()
+            .flat_map(|entry| entry.changes_applied.iter().rev().cloned())
+            .take(limit)
+            .collect())
+    }
+    
+    /// Recent data points for every collected metric, at most `max_points` per series
+    pub async fn get_metric_series(&self, max_points: usize) -> Result<HashMap<String, Vec<MetricDataPoint>>> {
+        let mut series = HashMap::new();
+        for name in self.metrics_collector.get_all_metric_names().await? {
+            let history = self.metrics_collector.get_metric_history(&name).await?;
+            let skip = history.len().saturating_sub(max_points);
+            series.insert(name, history.into_iter().skip(skip).collect());
+        }
+        Ok(series)
+    }
+    
+    /// Min/avg/max and direction of a metric over the last `window_minutes`
+    pub async fn get_metric_trend(&self, name: &str, window_minutes: u64) -> Result<MetricTrend> {
+        self.metrics_collector.get_metric_trend(name, window_minutes).await
+    }
+    
     /// Record a custom performance metric
     pub async fn record_metric(&self, metric_name: &str, value: f64) -> Result<()> {
         self.metrics_collector.record_custom_metric(metric_name, value).await
@@ -269,6 +317,15 @@ impl AdaptivePerformanceSystem {
             warn!("State version mismatch: {} vs {}", state.version, env!("CARGO_PKG_VERSION"));
         }
         
+        // Refuse the whole import if it would put anything outside the configured bounds
+        let out_of_bounds: Vec<String> = state.parameters.iter()
+            .filter_map(|(name, value)| self.safety_manager.check_parameter_bounds(name, value).err())
+            .map(|e| e.to_string())
+            .collect();
+        if !out_of_bounds.is_empty() {
+            return Err(anyhow::anyhow!("Imported parameters outside configured bounds: {}", out_of_bounds.join("; ")));
+        }
+
         // Import parameters with safety checks
         for (name, value) in state.parameters {
             if self.safety_manager.validate_parameter_change(&name, &value).await? {
@@ -323,6 +380,9 @@ impl AdaptivePerformanceSystem {
                                        change.old_value, 
                                        change.new_value, 
                                        change.reason);
+                                if let Some(justification) = &change.justification {
+                                    debug!("  justification: {}", justification);
+                                }
                             }
                         } else {
                             debug!("Tuning cycle #{} completed: no adjustments needed", cycle_count);
@@ -476,4 +536,48 @@ mod tests {
         // Import state should succeed
         system.import_state(state).await.unwrap();
     }
+
+    #[tokio::test]
+    async fn test_import_rejects_out_of_bound_parameters() {
+        let mut config = AdaptiveConfig::default();
+        config.parameter_bounds.insert(
+            "pattern_matching_timeout_ms".to_string(),
+            ParameterBounds { min: None, max: Some(1000.0), step: None },
+        );
+        let system = AdaptivePerformanceSystem::new(config).unwrap();
+
+        let mut state = system.export_state().await.unwrap();
+        state.parameters.insert("pattern_matching_timeout_ms".to_string(), ParameterValue::Duration(2000));
+        state.parameters.insert("message_processing_batch_size".to_string(), ParameterValue::Integer(20));
+        assert!(system.import_state(state).await.is_err());
+
+        // Nothing from the rejected import was applied
+        let parameters = system.get_current_parameters().await.unwrap();
+        assert_eq!(parameters["pattern_matching_timeout_ms"], ParameterValue::Duration(500));
+        assert_eq!(parameters["message_processing_batch_size"], ParameterValue::Integer(10));
+    }
+
+    #[test]
+    fn test_load_parameter_bounds_from_startup_config() {
+        // The shipped config only documents bounds, it doesn't set any
+        let shipped = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("config/startup.toml");
+        assert!(AdaptiveConfig::load_parameter_bounds(&shipped).unwrap().is_empty());
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("startup.toml");
+        std::fs::write(&path, r#"
+[adaptive.parameter_bounds.pattern_matching_timeout_ms]
+max = 1000
+
+[adaptive.parameter_bounds.message_processing_batch_size]
+min = 5
+max = 50
+step = 5
+"#).unwrap();
+        let bounds = AdaptiveConfig::load_parameter_bounds(&path).unwrap();
+        assert_eq!(bounds["pattern_matching_timeout_ms"].max, Some(1000.0));
+        assert_eq!(bounds["message_processing_batch_size"].step, Some(5.0));
+
+        assert!(AdaptiveConfig::load_parameter_bounds(std::path::Path::new("missing.toml")).unwrap().is_empty());
+    }
 }
\ No newline at end of file
@@ -67,10 +67,10 @@ impl ModerationSystemAdaptive for ModerationSystem {
         // Get per-filter metrics from your detailed stats
         if let Some(filter_details) = filter_stats.get("filter_details").and_then(|v| v.as_object()) {
             for (filter_name, details) in filter_details {
-                if let Some(violations) = details.get("violations").and_then(|v| v.as_u64()) {
+                if let Some(triggers) = details.get("trigger_count").and_then(|v| v.as_u64()) {
                     filter_metrics.insert(filter_name.clone(), FilterMetrics {
                         filter_name: filter_name.clone(),
-                        triggered_count: violations,
+                        triggered_count: triggers,
                         success_rate: 85.0, // Would calculate from actual data
                         false_positive_rate: 5.0, // Would track appeals/reversals
                         average_confidence: 0.85, // Would track from AI system
@@ -176,6 +176,7 @@ impl ParameterTuningStrategy for ModerationTuningStrategy {
                         reason: format!("Low pattern match rate ({:.1}%) - reduce AI threshold for better coverage", 
                                       metrics.pattern_match_rate * 100.0),
                         expected_improvement: 0.25,
+                        trigger: MetricTrigger::new("pattern_match_rate", metrics.pattern_match_rate, 0.7),
                     });
                 }
             }
@@ -193,6 +194,7 @@ impl ParameterTuningStrategy for ModerationTuningStrategy {
                         reason: format!("Very high pattern match rate ({:.1}%) - increase threshold to reduce false positives", 
                                       metrics.pattern_match_rate * 100.0),
                         expected_improvement: 0.15,
+                        trigger: MetricTrigger::new("pattern_match_rate", metrics.pattern_match_rate, 0.95),
                     });
                 }
             }
@@ -212,6 +214,7 @@ impl ParameterTuningStrategy for ModerationTuningStrategy {
                         reason: format!("High AI processing time ({:.1}ms) - increase timeout", 
                                       metrics.ai_processing_time_ms),
                         expected_improvement: 0.2,
+                        trigger: MetricTrigger::new("ai_processing_time_ms", metrics.ai_processing_time_ms, 1000.0),
                     });
                 }
             }
@@ -231,6 +234,7 @@ impl ParameterTuningStrategy for ModerationTuningStrategy {
                         reason: format!("Large moderation queue ({} messages) - increase batch size", 
                                       metrics.moderation_queue_length),
                         expected_improvement: 0.3,
+                        trigger: MetricTrigger::new("moderation_queue_length", metrics.moderation_queue_length as f64, 100.0),
                     });
                 }
             }
@@ -301,6 +305,7 @@ impl ParameterTuningStrategy for EnhancedAIModerationStrategy {
                         confidence: 0.7,
                         reason: "High AI processing time - reduce learning rate for faster inference".to_string(),
                         expected_improvement: 0.25,
+                        trigger: MetricTrigger::new("ai_processing_time_ms", metrics.ai_processing_time_ms, 500.0),
                     });
                 }
             }
@@ -320,6 +325,7 @@ impl ParameterTuningStrategy for EnhancedAIModerationStrategy {
                         reason: format!("Low system health ({:.1}%) - increase AI threshold for stability", 
                                       metrics.system_health_score * 100.0),
                         expected_improvement: 0.2,
+                        trigger: MetricTrigger::new("system_health_score", metrics.system_health_score, 0.8),
                     });
                 }
             }
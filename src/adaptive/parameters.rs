@@ -111,6 +111,58 @@ impl ParameterConstraints {
     }
 }
 
+/// Per-deployment limits on how far tuning may move a numeric parameter
+///
+/// Values are plain numbers in the parameter's own unit (milliseconds for
+/// durations), applied on top of the limits built into the code.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ParameterBounds {
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    /// Values must land on `min + n * step` (or a multiple of `step` without a min)
+    pub step: Option<f64>,
+}
+
+impl ParameterBounds {
+    /// Check the bounds themselves make sense
+    pub fn validate(&self) -> Result<()> {
+        if let (Some(min), Some(max)) = (self.min, self.max) {
+            if min > max {
+                return Err(anyhow!("min {} is greater than max {}", min, max));
+            }
+        }
+        if self.step.is_some_and(|step| step <= 0.0) {
+            return Err(anyhow!("step must be positive"));
+        }
+        Ok(())
+    }
+
+    /// Check a value falls within these bounds
+    pub fn check(&self, value: &ParameterValue) -> Result<()> {
+        let number = match value {
+            ParameterValue::Integer(v) => *v as f64,
+            ParameterValue::Float(v) => *v,
+            ParameterValue::Duration(v) => *v as f64,
+            other => return Err(anyhow!("Value {} is not numeric", other)),
+        };
+
+        if let Some(min) = self.min.filter(|min| number < *min) {
+            return Err(anyhow!("Value {} below configured minimum {}", value, min));
+        }
+        if let Some(max) = self.max.filter(|max| number > *max) {
+            return Err(anyhow!("Value {} above configured maximum {}", value, max));
+        }
+        if let Some(step) = self.step {
+            let steps = (number - self.min.unwrap_or(0.0)) / step;
+            if (steps - steps.round()).abs() > 1e-6 {
+                return Err(anyhow!("Value {} is not a multiple of configured step {}", value, step));
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Definition of a tunable parameter
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ParameterDefinition {
@@ -173,6 +225,46 @@ pub struct ParameterChange {
     pub new_value: ParameterValue,
     pub reason: String,
     pub triggered_by: String, // "system", "user", "safety", etc.
+    /// Why the tuning engine made this change (None for manual and safety changes)
+    #[serde(default)]
+    pub justification: Option<ChangeJustification>,
+}
+
+/// Structured explanation of an automatic parameter change
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChangeJustification {
+    pub strategy: String,
+    pub metric: String,
+    pub metric_value: f64,
+    pub target_value: f64,
+    pub expected_effect: String,
+    pub expected_improvement: f64,
+    pub confidence: f64,
+}
+
+impl ChangeJustification {
+    pub fn new(strategy: &str, suggestion: &ParameterSuggestion) -> Self {
+        let trigger = &suggestion.trigger;
+        let direction = if trigger.value > trigger.target { "lower" } else { "raise" };
+
+        Self {
+            strategy: strategy.to_string(),
+            metric: trigger.metric.clone(),
+            metric_value: trigger.value,
+            target_value: trigger.target,
+            expected_effect: format!("{} {} by ~{:.0}%", direction, trigger.metric, suggestion.expected_improvement * 100.0),
+            expected_improvement: suggestion.expected_improvement,
+            confidence: suggestion.confidence,
+        }
+    }
+}
+
+impl fmt::Display for ChangeJustification {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "strategy={} metric={} value={:.2} target={:.2} expected: {} (confidence {:.2})",
+               self.strategy, self.metric, self.metric_value, self.target_value,
+               self.expected_effect, self.confidence)
+    }
 }
 
 impl ParameterStore {
@@ -309,6 +401,7 @@ impl ParameterStore {
             new_value,
             reason,
             triggered_by,
+            justification: None,
         };
         
         self.change_history.push(change);
@@ -572,6 +665,26 @@ pub struct ParameterSuggestion {
     pub confidence: f64,
     pub reason: String,
     pub expected_improvement: f64,
+    /// Metric reading that prompted the suggestion
+    pub trigger: MetricTrigger,
+}
+
+/// A metric reading compared against the threshold a strategy acts on
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricTrigger {
+    pub metric: String,
+    pub value: f64,
+    pub target: f64,
+}
+
+impl MetricTrigger {
+    pub fn new(metric: &str, value: f64, target: f64) -> Self {
+        Self {
+            metric: metric.to_string(),
+            value,
+            target,
+        }
+    }
 }
 
 /// Parameter tuning strategy interface
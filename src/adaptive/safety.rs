@@ -21,6 +21,7 @@ pub struct SafetyManager {
     rollback_history: Arc<RwLock<Vec<RollbackEvent>>>,
     safety_rules: Vec<Box<dyn SafetyRule + Send + Sync>>,
     circuit_breaker: Arc<RwLock<CircuitBreaker>>,
+    parameter_bounds: HashMap<String, ParameterBounds>,
 }
 
 /// Record of a parameter change for safety tracking
@@ -116,6 +117,7 @@ impl SafetyManager {
             rollback_history: Arc::new(RwLock::new(Vec::new())),
             safety_rules: Vec::new(),
             circuit_breaker: Arc::new(RwLock::new(CircuitBreaker::new())),
+            parameter_bounds: HashMap::new(),
         };
         
         // Initialize safety rules
@@ -127,8 +129,34 @@ impl SafetyManager {
         Ok(manager)
     }
     
+    /// Constrain tuning of the named parameters to deployment-specific bounds
+    pub fn with_parameter_bounds(mut self, bounds: HashMap<String, ParameterBounds>) -> Result<Self> {
+        for (name, bound) in &bounds {
+            bound.validate().map_err(|e| anyhow::anyhow!("Invalid bounds for {}: {}", name, e))?;
+        }
+        if !bounds.is_empty() {
+            info!("Configured tuning bounds for {} parameters", bounds.len());
+        }
+        self.parameter_bounds = bounds;
+        Ok(self)
+    }
+
+    /// Check a value against the configured bounds for its parameter
+    pub fn check_parameter_bounds(&self, name: &str, value: &ParameterValue) -> Result<()> {
+        match self.parameter_bounds.get(name) {
+            Some(bounds) => bounds.check(value).map_err(|e| anyhow::anyhow!("{}: {}", name, e)),
+            None => Ok(()),
+        }
+    }
+
     /// Validate if a parameter change is safe
     pub async fn validate_parameter_change(&self, name: &str, value: &ParameterValue) -> Result<bool> {
+        // Deployment bounds hold even with the other safety checks switched off
+        if let Err(e) = self.check_parameter_bounds(name, value) {
+            warn!("Parameter change outside configured bounds: {}", e);
+            return Ok(false);
+        }
+
         if !self.enabled {
             return Ok(true);
         }
@@ -705,6 +733,31 @@ mod tests {
         assert!(!result);
     }
     
+    #[tokio::test]
+    async fn test_configured_bounds_reject_changes() {
+        let mut bounds = HashMap::new();
+        bounds.insert("pattern_matching_timeout_ms".to_string(), ParameterBounds { min: None, max: Some(200.0), step: None });
+        bounds.insert("message_processing_batch_size".to_string(), ParameterBounds { min: Some(5.0), max: Some(50.0), step: Some(5.0) });
+        let manager = SafetyManager::new(true, 10, 300).unwrap().with_parameter_bounds(bounds.clone()).unwrap();
+
+        assert!(manager.validate_parameter_change("pattern_matching_timeout_ms", &ParameterValue::Duration(200)).await.unwrap());
+        assert!(!manager.validate_parameter_change("pattern_matching_timeout_ms", &ParameterValue::Duration(300)).await.unwrap());
+        assert!(manager.validate_parameter_change("message_processing_batch_size", &ParameterValue::Integer(15)).await.unwrap());
+        assert!(!manager.validate_parameter_change("message_processing_batch_size", &ParameterValue::Integer(17)).await.unwrap());
+        assert!(!manager.validate_parameter_change("message_processing_batch_size", &ParameterValue::Integer(0)).await.unwrap());
+        // Parameters without configured bounds are unaffected
+        assert!(manager.validate_parameter_change("connection_timeout_ms", &ParameterValue::Duration(30000)).await.unwrap());
+
+        // Bounds still apply with the other safety checks disabled
+        let unchecked = SafetyManager::new(false, 10, 300).unwrap().with_parameter_bounds(bounds).unwrap();
+        assert!(!unchecked.validate_parameter_change("pattern_matching_timeout_ms", &ParameterValue::Duration(5000)).await.unwrap());
+
+        // Nonsensical bounds are refused up front
+        let mut invalid = HashMap::new();
+        invalid.insert("learning_rate".to_string(), ParameterBounds { min: Some(1.0), max: Some(0.5), step: None });
+        assert!(SafetyManager::new(true, 10, 300).unwrap().with_parameter_bounds(invalid).is_err());
+    }
+
     #[tokio::test]
     async fn test_circuit_breaker() {
         let mut breaker = CircuitBreaker::new();
@@ -230,8 +230,8 @@ impl TuningEngine {
         Ok(())
     }
     
-    /// Collect suggestions from all strategies
-    async fn collect_strategy_suggestions(&self, metrics: &PerformanceMetrics) -> Result<Vec<ParameterSuggestion>> {
+    /// Collect suggestions from all strategies, tagged with the strategy that made them
+    async fn collect_strategy_suggestions(&self, metrics: &PerformanceMetrics) -> Result<Vec<(String, ParameterSuggestion)>> {
         let store = self.parameter_store.read().await;
         let mut all_suggestions = Vec::new();
         
@@ -250,24 +250,25 @@ impl TuningEngine {
                     suggestion.expected_improvement * 100.0);
             }
             
-            all_suggestions.extend(suggestions);  // <- Now this works because suggestions wasn't moved
+            let strategy_name = strategy.get_strategy_name().to_string();
+            all_suggestions.extend(suggestions.into_iter().map(|s| (strategy_name.clone(), s)));
         }
         
         Ok(all_suggestions)
     }
     
     /// Prioritize and filter suggestions
-    async fn prioritize_suggestions(&self, suggestions: Vec<ParameterSuggestion>) -> Result<Vec<ParameterSuggestion>> {
+    async fn prioritize_suggestions(&self, suggestions: Vec<(String, ParameterSuggestion)>) -> Result<Vec<(String, ParameterSuggestion)>> {
         let store = self.parameter_store.read().await;
         let mut filtered_suggestions = Vec::new();
         
         // Group suggestions by parameter name
-        let mut suggestions_by_param: HashMap<String, Vec<ParameterSuggestion>> = HashMap::new();
-        for suggestion in suggestions {
+        let mut suggestions_by_param: HashMap<String, Vec<(String, ParameterSuggestion)>> = HashMap::new();
+        for (strategy, suggestion) in suggestions {
             suggestions_by_param
                 .entry(suggestion.parameter_name.clone())
                 .or_insert_with(Vec::new)
-                .push(suggestion);
+                .push((strategy, suggestion));
         }
         
         // For each parameter, select the best suggestion
@@ -280,22 +281,22 @@ impl TuningEngine {
             
             // Find suggestion with highest confidence * expected_improvement score
             let best_suggestion = param_suggestions.into_iter()
-                .max_by(|a, b| {
+                .max_by(|(_, a), (_, b)| {
                     let score_a = a.confidence * a.expected_improvement;
                     let score_b = b.confidence * b.expected_improvement;
                     score_a.partial_cmp(&score_b).unwrap_or(std::cmp::Ordering::Equal)
                 });
             
-            if let Some(suggestion) = best_suggestion {
+            if let Some((strategy, suggestion)) = best_suggestion {
                 // Only include suggestions with decent confidence and improvement
                 if suggestion.confidence > 0.3 && suggestion.expected_improvement > 0.01 {
-                    filtered_suggestions.push(suggestion);
+                    filtered_suggestions.push((strategy, suggestion));
                 }
             }
         }
         
         // Sort by priority (confidence * improvement * impact)
-        filtered_suggestions.sort_by(|a, b| {
+        filtered_suggestions.sort_by(|(_, a), (_, b)| {
             let priority_a = a.confidence * a.expected_improvement;
             let priority_b = b.confidence * b.expected_improvement;
             priority_b.partial_cmp(&priority_a).unwrap_or(std::cmp::Ordering::Equal)
@@ -308,11 +309,11 @@ impl TuningEngine {
     }
     
     /// Apply parameter changes with safety checks
-    async fn apply_changes_safely(&self, suggestions: Vec<ParameterSuggestion>) -> Result<(Vec<ParameterChange>, Vec<RejectedChange>)> {
+    async fn apply_changes_safely(&self, suggestions: Vec<(String, ParameterSuggestion)>) -> Result<(Vec<ParameterChange>, Vec<RejectedChange>)> {
         let mut applied_changes = Vec::new();
         let mut rejected_changes = Vec::new();
 
-        for suggestion in suggestions {
+        for (strategy, suggestion) in suggestions {
 
             let param_name = suggestion.parameter_name.clone();
             let suggested_value = suggestion.suggested_value.clone();
@@ -342,6 +343,14 @@ impl TuningEngine {
                 
                 match store.set_parameter(&suggestion.parameter_name, suggestion.suggested_value.clone()) {
                     Ok(old_value) => {
+                        let justification = ChangeJustification::new(&strategy, &suggestion);
+                        info!("Applied parameter change: {} = {} (was: {}, reason: {}; {})", 
+                              suggestion.parameter_name, 
+                              suggestion.suggested_value,
+                              old_value,
+                              suggestion.reason,
+                              justification);
+
                         applied_changes.push(ParameterChange {
                             timestamp: chrono::Utc::now().timestamp() as u64,
                             parameter_name: param_name,
@@ -349,13 +358,8 @@ impl TuningEngine {
                             new_value: suggested_value,
                             reason: reason,
                             triggered_by: "tuning_engine".to_string(),
+                            justification: Some(justification),
                         });
-                        
-                        info!("Applied parameter change: {} = {} (was: {}, reason: {})", 
-                              suggestion.parameter_name, 
-                              suggestion.suggested_value,
-                              applied_changes.last().unwrap().old_value,
-                              suggestion.reason);
                     }
                     Err(e) => {
                         rejected_changes.push(RejectedChange {
@@ -450,10 +454,15 @@ impl TuningEngine {
             return "none".to_string();
         }
         
-        // Simplified analysis - look at parameter categories to infer strategy
         let mut strategy_counts = HashMap::new();
         
         for change in changes {
+            if let Some(justification) = &change.justification {
+                *strategy_counts.entry(justification.strategy.as_str()).or_insert(0) += 1;
+                continue;
+            }
+
+            // No recorded strategy - infer it from the parameter category
             let strategy = match change.parameter_name.as_str() {
                 name if name.contains("latency") || name.contains("timeout") => "latency_tuning",
                 name if name.contains("memory") || name.contains("cache") => "memory_tuning", 
@@ -522,6 +531,7 @@ impl ParameterTuningStrategy for LatencyTuningStrategy {
                         reason: format!("Reduce timeout to improve latency ({:.1}ms -> target {:.1}ms)", 
                                       metrics.average_latency_ms, self.config.target_latency_ms),
                         expected_improvement: (latency_ratio - 1.0) * 0.3,
+                        trigger: MetricTrigger::new("average_latency_ms", metrics.average_latency_ms, self.config.target_latency_ms),
                     });
                 }
             }
@@ -538,6 +548,7 @@ impl ParameterTuningStrategy for LatencyTuningStrategy {
                         confidence: 0.7,
                         reason: "Increase batch size to improve processing efficiency".to_string(),
                         expected_improvement: 0.15,
+                        trigger: MetricTrigger::new("average_latency_ms", metrics.average_latency_ms, self.config.target_latency_ms),
                     });
                 }
             }
@@ -609,6 +620,7 @@ impl ParameterTuningStrategy for MemoryTuningStrategy {
                         reason: format!("Reduce cache size due to high memory usage ({:.1}%)", 
                                       metrics.memory_usage_percent),
                         expected_improvement: memory_pressure * 0.4,
+                        trigger: MetricTrigger::new("memory_usage_percent", metrics.memory_usage_percent, self.config.target_memory_percent),
                     });
                 }
             }
@@ -625,6 +637,7 @@ impl ParameterTuningStrategy for MemoryTuningStrategy {
                         confidence: 0.8,
                         reason: "Lower GC threshold to free memory more aggressively".to_string(),
                         expected_improvement: 0.2,
+                        trigger: MetricTrigger::new("memory_usage_percent", metrics.memory_usage_percent, self.config.target_memory_percent),
                     });
                 }
             }
@@ -694,6 +707,7 @@ impl ParameterTuningStrategy for ErrorRateTuningStrategy {
                         reason: format!("Increase retries due to high error rate ({:.2}%)", 
                                       metrics.error_rate_percent),
                         expected_improvement: (error_severity - 1.0) * 0.6,
+                        trigger: MetricTrigger::new("error_rate_percent", metrics.error_rate_percent, self.config.target_error_rate_percent),
                     });
                 }
             }
@@ -710,6 +724,7 @@ impl ParameterTuningStrategy for ErrorRateTuningStrategy {
                         confidence: 0.7,
                         reason: "Increase timeout to reduce connection errors".to_string(),
                         expected_improvement: 0.25,
+                        trigger: MetricTrigger::new("error_rate_percent", metrics.error_rate_percent, self.config.target_error_rate_percent),
                     });
                 }
             }
@@ -754,6 +769,7 @@ impl ParameterTuningStrategy for LoadBalancingStrategy {
                         reason: format!("Increase pool size due to high utilization ({:.1}%)", 
                                       metrics.connection_pool_utilization * 100.0),
                         expected_improvement: 0.15,
+                        trigger: MetricTrigger::new("connection_pool_utilization", metrics.connection_pool_utilization, 0.8),
                     });
                 }
             }
@@ -772,6 +788,7 @@ impl ParameterTuningStrategy for LoadBalancingStrategy {
                             reason: format!("Reduce pool size due to low utilization ({:.1}%)", 
                                           metrics.connection_pool_utilization * 100.0),
                             expected_improvement: 0.05,
+                            trigger: MetricTrigger::new("connection_pool_utilization", metrics.connection_pool_utilization, 0.3),
                         });
                     }
                 }
@@ -817,6 +834,7 @@ impl ParameterTuningStrategy for AdaptiveAIStrategy {
                         reason: format!("Increase AI threshold due to slow processing ({:.1}ms)", 
                                       metrics.ai_processing_time_ms),
                         expected_improvement: 0.2,
+                        trigger: MetricTrigger::new("ai_processing_time_ms", metrics.ai_processing_time_ms, 1000.0),
                     });
                 }
             }
@@ -836,6 +854,7 @@ impl ParameterTuningStrategy for AdaptiveAIStrategy {
                         reason: format!("Increase pattern timeout due to low match rate ({:.1}%)", 
                                       metrics.pattern_match_rate * 100.0),
                         expected_improvement: 0.1,
+                        trigger: MetricTrigger::new("pattern_match_rate", metrics.pattern_match_rate, 0.8),
                     });
                 }
             }
@@ -926,4 +945,36 @@ mod tests {
         let improvement = engine.calculate_performance_improvement(&before, &after);
         assert!(improvement > 0.0);
     }
+    
+    #[tokio::test]
+    async fn test_latency_change_is_justified() {
+        let engine = TuningEngine::new(
+            AdaptiveConfig::default(),
+            Arc::new(MetricsCollector::new(1).unwrap()),
+            Arc::new(RwLock::new(ParameterStore::new())),
+            Arc::new(SafetyManager::new(true, 10, 300).unwrap()),
+        ).unwrap();
+        
+        let metrics = PerformanceMetrics {
+            average_latency_ms: 250.0,
+            pattern_match_rate: 1.0,
+            connection_pool_utilization: 0.5,
+            ..Default::default()
+        };
+        
+        let suggestions = engine.collect_strategy_suggestions(&metrics).await.unwrap();
+        let prioritized = engine.prioritize_suggestions(suggestions).await.unwrap();
+        let (applied, _) = engine.apply_changes_safely(prioritized).await.unwrap();
+        
+        let change = applied.iter()
+            .find(|c| c.parameter_name == "connection_timeout_ms")
+            .expect("latency strategy should adjust the connection timeout");
+        let justification = change.justification.as_ref().expect("tuning changes carry a justification");
+        assert_eq!(justification.strategy, "latency_tuning");
+        assert_eq!(justification.metric, "average_latency_ms");
+        assert_eq!(justification.metric_value, 250.0);
+        assert_eq!(justification.target_value, 100.0);
+        assert_eq!(justification.expected_effect, "lower average_latency_ms by ~45%");
+        assert_eq!(engine.find_dominant_strategy(&applied), "latency_tuning");
+    }
 }
\ No newline at end of file
@@ -1,17 +1,33 @@
 use anyhow::Result;
 use log::{warn};
 use std::sync::Arc;
+use tokio::sync::RwLock;
 
 use crate::bot::achievements::{AchievementSystem, AchievementRarity};
+use crate::bot::identity::IdentityMap;
 use crate::types::ChatMessage;
 
 pub struct AchievementCommands {
     achievement_system: Arc<AchievementSystem>,
+    identities: Arc<RwLock<IdentityMap>>,
 }
 
 impl AchievementCommands {
     pub fn new(achievement_system: Arc<AchievementSystem>) -> Self {
-        Self { achievement_system }
+        Self {
+            achievement_system,
+            identities: Arc::new(RwLock::new(IdentityMap::new())),
+        }
+    }
+
+    /// Share identity links with the points system, which achievements are keyed by
+    pub fn with_identities(mut self, identities: Arc<RwLock<IdentityMap>>) -> Self {
+        self.identities = identities;
+        self
+    }
+
+    async fn user_key(&self, platform: &str, username: &str) -> String {
+        self.identities.read().await.resolve(platform, username)
     }
 
     /// Process achievement-related commands
@@ -59,7 +75,7 @@ impl AchievementCommands {
             args[0]
         };
 
-        let user_id = format!("{}:{}", message.platform, target_user);
+        let user_id = self.user_key(&message.platform, target_user).await;
         let achievements = self.achievement_system.get_achievements_for_user(&user_id).await;
         
         if achievements.is_empty() {
@@ -121,7 +137,7 @@ impl AchievementCommands {
         }
 
         let achievement_name = args.join(" ").to_lowercase();
-        let user_id = format!("{}:{}", message.platform, message.username);
+        let user_id = self.user_key(&message.platform, &message.username).await;
         let achievements = self.achievement_system.get_achievements_for_user(&user_id).await;
         
         // Find achievement by name (case insensitive)
@@ -204,16 +220,16 @@ impl AchievementCommands {
             args[0]
         };
 
-        let user_id = format!("{}:{}", message.platform, target_user);
-        let achievements = self.achievement_system.get_achievements_for_user(&user_id).await;
+        let user_id = self.user_key(&message.platform, target_user).await;
+        let achievements = self.achievement_system.get_achievement_progress(&user_id).await;
         
         // Show progress on closest achievements (not yet unlocked)
         let mut in_progress: Vec<_> = achievements.iter()
-            .filter(|(_, unlocked, progress)| !unlocked && *progress > 0)
+            .filter(|(_, progress)| *progress > 0)
             .collect();
 
         // Sort by progress percentage
-        in_progress.sort_by(|(a, _, a_progress), (b, _, b_progress)| {
+        in_progress.sort_by(|(a, a_progress), (b, b_progress)| {
             let a_percent = calculate_progress_percent(&a.requirement, *a_progress);
             let b_percent = calculate_progress_percent(&b.requirement, *b_progress);
             b_percent.partial_cmp(&a_percent).unwrap_or(std::cmp::Ordering::Equal)
@@ -231,7 +247,7 @@ impl AchievementCommands {
         }
 
         let progress_str = in_progress.iter()
-            .map(|(achievement, _, progress)| {
+            .map(|(achievement, progress)| {
                 let percent = calculate_progress_percent(&achievement.requirement, *progress);
                 format!("{} {} ({}%)", 
                         achievement.badge_emoji, 
@@ -357,10 +373,14 @@ impl AchievementCommands {
             AchievementRarity::Mythic => "👑",
         };
 
+        // Hidden achievements are revealed for the first time here
+        let unlock_label = if achievement.hidden { "UNLOCKED A SECRET ACHIEVEMENT" } else { "UNLOCKED" };
+
         let response = format!(
-            "{} {} UNLOCKED: {} {} | \"{}\" | +{} points! {}",
+            "{} {} {}: {} {} | \"{}\" | +{} points! {}",
             rarity_announcement,
             username,
+            unlock_label,
             achievement.badge_emoji,
             achievement.name,
             achievement.description,
@@ -398,4 +418,55 @@ fn calculate_progress_percent(requirement: &crate::bot::achievements::Achievemen
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot::achievements::{Achievement, AchievementCategory, AchievementRequirement};
+
+    #[tokio::test]
+    async fn test_hidden_achievement_announcement() {
+        let commands = AchievementCommands::new(Arc::new(AchievementSystem::new()));
+        let (tx, mut rx) = tokio::sync::mpsc::channel(10);
+        let message = ChatMessage::new("twitch", "channel", "viewer", "hi");
+        let achievement = Achievement {
+            id: "secret".to_string(),
+            name: "Secret Chatter".to_string(),
+            description: "Found it".to_string(),
+            category: AchievementCategory::Special,
+            requirement: AchievementRequirement::MessageCount(5),
+            reward_points: 100,
+            badge_emoji: "🤫".to_string(),
+            rarity: AchievementRarity::Rare,
+            hidden: true,
+        };
+
+        commands.announce_achievement(&achievement, "viewer", &message, &tx).await.unwrap();
+
+        let (_, channel, response) = rx.recv().await.unwrap();
+        assert_eq!(channel, "channel");
+        assert!(response.contains("viewer UNLOCKED A SECRET ACHIEVEMENT: 🤫 Secret Chatter"));
+    }
 
+    #[tokio::test]
+    async fn test_achievements_are_found_for_linked_and_mixed_case_accounts() {
+        let identities = Arc::new(RwLock::new(IdentityMap::new()));
+        identities.write().await.link_identities(("twitch", "Alice"), ("youtube", "AliceLive")).unwrap();
+        let system = Arc::new(AchievementSystem::new());
+        system.initialize_default_achievements().await;
+        let commands = AchievementCommands::new(Arc::clone(&system)).with_identities(identities);
+        let (tx, mut rx) = tokio::sync::mpsc::channel(10);
+
+        // Points keys users by their resolved identity, and achievements follow
+        let mut points = crate::bot::points::UserPoints::new("twitch".to_string(), "Alice".to_string(), None);
+        points.user_id = "twitch:alice".to_string();
+        points.messages_sent = 1000;
+        assert!(!system.check_achievements(&points).await.is_empty());
+
+        for (platform, username, args) in [("youtube", "AliceLive", vec![]), ("twitch", "viewer", vec!["@ALICE"])] {
+            let message = ChatMessage::new(platform, "channel", username, "!achievements");
+            commands.process_command("achievements", &args, &message, &tx).await.unwrap();
+            let (_, _, response) = rx.recv().await.unwrap();
+            assert!(!response.contains(" has 0/"), "{}", response);
+        }
+    }
+}
@@ -20,12 +20,13 @@ pub struct Achievement {
     pub hidden: bool, // Hidden until unlocked
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub enum AchievementCategory {
     Engagement,    // Chat and participation
     Loyalty,       // Time-based achievements
     Social,        // Community interaction
     Points,        // Point milestones
+    #[default]
     Special,       // Event or rare achievements
     Moderation,    // Mod-specific achievements
 }
@@ -46,8 +47,29 @@ pub enum AchievementRequirement {
     Custom(String),             // Custom achievement logic
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Eq, Ord, PartialEq, PartialOrd)]
+impl AchievementRequirement {
+    /// Metric names usable in configured achievement conditions
+    pub const CONFIG_METRICS: &'static [&'static str] = &[
+        "messages", "watch_minutes", "points_earned", "points_balance", "commands_used", "days_active",
+    ];
+
+    /// Build a requirement from a configured metric name and threshold
+    pub fn from_metric(metric: &str, threshold: u64) -> Option<Self> {
+        match metric {
+            "messages" => Some(AchievementRequirement::MessageCount(threshold)),
+            "watch_minutes" => Some(AchievementRequirement::WatchTime(threshold)),
+            "points_earned" => Some(AchievementRequirement::PointsEarned(threshold as i64)),
+            "points_balance" => Some(AchievementRequirement::PointsBalance(threshold as i64)),
+            "commands_used" => Some(AchievementRequirement::CommandsUsed(threshold)),
+            "days_active" => Some(AchievementRequirement::DaysActive(threshold)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, Ord, PartialEq, PartialOrd, Default)]
 pub enum AchievementRarity {
+    #[default]
     Common,     // Easy to get
     Uncommon,   // Moderate effort
     Rare,       // Significant effort
@@ -111,6 +133,8 @@ impl UserAchievements {
 pub struct AchievementSystem {
     achievements: Arc<RwLock<HashMap<String, Achievement>>>,
     user_achievements: Arc<RwLock<HashMap<String, UserAchievements>>>,
+    /// Achievements loaded from config, merged over the defaults
+    custom_achievements: Arc<RwLock<Vec<Achievement>>>,
 }
 
 impl AchievementSystem {
@@ -118,6 +142,7 @@ impl AchievementSystem {
         let system = Self {
             achievements: Arc::new(RwLock::new(HashMap::new())),
             user_achievements: Arc::new(RwLock::new(HashMap::new())),
+            custom_achievements: Arc::new(RwLock::new(Vec::new())),
         };
         
         // Initialize with default achievements
@@ -130,14 +155,25 @@ impl AchievementSystem {
 
     /// Initialize the achievement system with default achievements
     pub async fn initialize_default_achievements(&self) {
-        let default_achievements = self.create_default_achievements();
+        self.rebuild_achievements().await;
+        info!("Initialized {} achievements", self.achievements.read().await.len());
+    }
+
+    /// Replace the configured achievements; a custom achievement overrides a default with the same ID
+    pub async fn set_custom_achievements(&self, custom: Vec<Achievement>) {
+        info!("Loaded {} custom achievements", custom.len());
+        *self.custom_achievements.write().await = custom;
+        self.rebuild_achievements().await;
+    }
+
+    async fn rebuild_achievements(&self) {
+        let custom = self.custom_achievements.read().await;
         let mut achievements = self.achievements.write().await;
-        
-        for achievement in default_achievements {
+        achievements.clear();
+
+        for achievement in self.create_default_achievements().into_iter().chain(custom.iter().cloned()) {
             achievements.insert(achievement.id.clone(), achievement);
         }
-        
-        info!("Initialized {} default achievements", achievements.len());
     }
 
     /// Check user progress and unlock achievements
@@ -200,12 +236,56 @@ impl AchievementSystem {
         }
     }
 
-    /// Get user's achievements
+    /// Fold one identity's unlocked achievements and progress into another's,
+    /// once their accounts are linked
+    pub async fn merge_user(&self, from: &str, into: &str) {
+        let achievements = self.achievements.read().await;
+        let mut user_achievements = self.user_achievements.write().await;
+        let Some(merged) = user_achievements.remove(from) else {
+            return;
+        };
+
+        let target = user_achievements
+            .entry(into.to_string())
+            .or_insert_with(|| UserAchievements::new(into.to_string()));
+        for id in merged.unlocked {
+            let Some(achievement) = achievements.get(&id) else {
+                continue;
+            };
+            if target.unlock_achievement(achievement) {
+                if let Some(unlocked_at) = merged.unlock_timestamps.get(&id) {
+                    target.unlock_timestamps.insert(id, *unlocked_at);
+                }
+            }
+        }
+        for (id, progress) in merged.progress {
+            let current = target.progress.entry(id).or_insert(0);
+            *current = (*current).max(progress);
+        }
+    }
+
+    /// Get user's achievements, without progress on hidden achievements they haven't unlocked
     pub async fn get_user_achievements(&self, user_id: &str) -> Option<UserAchievements> {
-        self.user_achievements.read().await.get(user_id).cloned()
+        let achievements = self.achievements.read().await;
+        let mut user_data = self.user_achievements.read().await.get(user_id).cloned()?;
+
+        let unlocked = &user_data.unlocked;
+        user_data.progress.retain(|id, _| {
+            unlocked.contains(id) || achievements.get(id).is_none_or(|a| !a.hidden)
+        });
+        Some(user_data)
     }
 
-    /// Get all achievements with user's unlock status
+    /// Get progress towards achievements the user hasn't unlocked yet; hidden achievements are omitted
+    pub async fn get_achievement_progress(&self, user_id: &str) -> Vec<(Achievement, u64)> {
+        self.get_achievements_for_user(user_id).await
+            .into_iter()
+            .filter(|(_, unlocked, _)| !unlocked)
+            .map(|(achievement, _, progress)| (achievement, progress))
+            .collect()
+    }
+
+    /// Get all achievements with user's unlock status; hidden achievements only appear once unlocked
     pub async fn get_achievements_for_user(&self, user_id: &str) -> Vec<(Achievement, bool, u64)> {
         let achievements = self.achievements.read().await;
         let user_achievements = self.user_achievements.read().await;
@@ -468,4 +548,50 @@ impl AchievementSystem {
         
         stats
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secret_achievement() -> Achievement {
+        Achievement {
+            id: "secret_chatter".to_string(),
+            name: "Secret Chatter".to_string(),
+            description: "Send 5 messages".to_string(),
+            category: AchievementCategory::Special,
+            requirement: AchievementRequirement::MessageCount(5),
+            reward_points: 100,
+            badge_emoji: "🤫".to_string(),
+            rarity: AchievementRarity::Rare,
+            hidden: true,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_hidden_achievement_revealed_on_unlock() {
+        let system = AchievementSystem::new();
+        system.initialize_default_achievements().await;
+        system.set_custom_achievements(vec![secret_achievement()]).await;
+
+        let mut user = UserPoints::new("twitch".to_string(), "viewer".to_string(), None);
+        user.messages_sent = 3;
+        system.check_achievements(&user).await;
+
+        let listed = system.get_achievements_for_user(&user.user_id).await;
+        assert!(!listed.iter().any(|(a, _, _)| a.id == "secret_chatter"));
+        assert!(!system.get_achievement_progress(&user.user_id).await.iter().any(|(a, _)| a.id == "secret_chatter"));
+        let user_data = system.get_user_achievements(&user.user_id).await.unwrap();
+        assert!(!user_data.progress.contains_key("secret_chatter"));
+        assert!(user_data.progress.contains_key("chatterbox"));
+
+        // Unlocks are returned like any other, so the announcement still fires
+        user.messages_sent = 5;
+        let unlocked = system.check_achievements(&user).await;
+        assert!(unlocked.iter().any(|a| a.id == "secret_chatter"));
+
+        let listed = system.get_achievements_for_user(&user.user_id).await;
+        assert!(listed.iter().any(|(a, unlocked, _)| a.id == "secret_chatter" && *unlocked));
+        assert!(!system.get_achievement_progress(&user.user_id).await.iter().any(|(a, _)| a.id == "secret_chatter"));
+    }
+}
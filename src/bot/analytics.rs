@@ -4,6 +4,7 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
+use crate::bot::identity::IdentityMap;
 use crate::types::ChatMessage;
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -30,6 +31,8 @@ pub struct ChannelStats {
     pub unique_users: Vec<String>, // Changed from HashSet for serialization
     pub commands_executed: u64,
     pub spam_messages_blocked: u64,
+    #[serde(default)]
+    pub bot_messages_dropped: u64, // Bot messages the platform accepted but didn't post
 }
 
 pub struct AnalyticsSystem {
@@ -39,6 +42,7 @@ pub struct AnalyticsSystem {
     start_time: chrono::DateTime<chrono::Utc>,
     analytics_receiver: Option<tokio::sync::mpsc::Receiver<AnalyticsEvent>>,
     analytics_sender: Arc<tokio::sync::mpsc::Sender<AnalyticsEvent>>,
+    identities: Arc<RwLock<IdentityMap>>,
 }
 
 #[derive(Debug, Clone)]
@@ -47,6 +51,7 @@ pub enum AnalyticsEvent {
     CommandExecuted { command: String, user: String, channel: String },
     SpamBlocked(ChatMessage),
     CooldownHit { command: String, user: String },
+    MessageDropped { platform: String, channel: String, reason: String },
 }
 
 impl AnalyticsSystem {
@@ -60,9 +65,16 @@ impl AnalyticsSystem {
             start_time: chrono::Utc::now(),
             analytics_receiver: Some(receiver),
             analytics_sender: Arc::new(sender),
+            identities: Arc::new(RwLock::new(IdentityMap::new())),
         }
     }
 
+    /// Share identity links with other systems, so linked accounts count as one user
+    pub fn with_identities(mut self, identities: Arc<RwLock<IdentityMap>>) -> Self {
+        self.identities = identities;
+        self
+    }
+
     /// Get a sender for analytics events
     pub fn get_sender(&self) -> Arc<tokio::sync::mpsc::Sender<AnalyticsEvent>> {
         Arc::clone(&self.analytics_sender)
@@ -74,6 +86,7 @@ impl AnalyticsSystem {
             let user_stats = Arc::clone(&self.user_stats);
             let command_stats = Arc::clone(&self.command_stats);
             let channel_stats = Arc::clone(&self.channel_stats);
+            let identities = Arc::clone(&self.identities);
             let start_time = self.start_time;
 
             tokio::spawn(async move {
@@ -82,7 +95,8 @@ impl AnalyticsSystem {
                 while let Some(event) = receiver.recv().await {
                     match event {
                         AnalyticsEvent::MessageReceived(message) => {
-                            Self::process_message_event(&user_stats, &channel_stats, &message, start_time).await;
+                            let user_key = identities.read().await.resolve(&message.platform, &message.username);
+                            Self::process_message_event(&user_stats, &channel_stats, &message, &user_key, start_time).await;
                         }
                         AnalyticsEvent::CommandExecuted { command, user, channel } => {
                             Self::process_command_event(&command_stats, &user_stats, &command, &user, &channel).await;
@@ -93,6 +107,9 @@ impl AnalyticsSystem {
                         AnalyticsEvent::CooldownHit { command, user: _ } => {
                             Self::process_cooldown_event(&command_stats, &command).await;
                         }
+                        AnalyticsEvent::MessageDropped { platform, channel, reason } => {
+                            Self::process_dropped_event(&channel_stats, &platform, &channel, &reason).await;
+                        }
                     }
                 }
                 
@@ -128,19 +145,28 @@ impl AnalyticsSystem {
         }).await;
     }
 
+    /// Record a bot message the platform dropped (convenience method)
+    pub async fn record_message_dropped(&self, platform: &str, channel: &str, reason: &str) {
+        let _ = self.analytics_sender.send(AnalyticsEvent::MessageDropped {
+            platform: platform.to_string(),
+            channel: channel.to_string(),
+            reason: reason.to_string(),
+        }).await;
+    }
+
     // Internal event processors
     async fn process_message_event(
         user_stats: &Arc<RwLock<HashMap<String, UserStats>>>,
         channel_stats: &Arc<RwLock<HashMap<String, ChannelStats>>>,
         message: &ChatMessage,
+        user_key: &str,
         start_time: chrono::DateTime<chrono::Utc>,
     ) {
         // Update user stats
         {
             let mut user_stats_guard = user_stats.write().await;
-            let user_key = format!("{}:{}", message.platform, message.username);
             
-            let stats = user_stats_guard.entry(user_key).or_insert(UserStats {
+            let stats = user_stats_guard.entry(user_key.to_string()).or_insert(UserStats {
                 total_messages: 0,
                 command_usage: 0,
                 last_seen: message.timestamp,
@@ -172,12 +198,12 @@ impl AnalyticsSystem {
                 unique_users: Vec::new(), // Changed to Vec
                 commands_executed: 0,
                 spam_messages_blocked: 0,
+                bot_messages_dropped: 0,
             });
             
             stats.total_messages += 1;
-            let user_key = format!("{}:{}", message.platform, message.username);
-            if !stats.unique_users.contains(&user_key) {
-                stats.unique_users.push(user_key);
+            if !stats.unique_users.iter().any(|user| user == user_key) {
+                stats.unique_users.push(user_key.to_string());
             }
         }
     }
@@ -230,12 +256,34 @@ impl AnalyticsSystem {
             unique_users: Vec::new(), // Changed to Vec
             commands_executed: 0,
             spam_messages_blocked: 0,
+            bot_messages_dropped: 0,
         });
         
         stats.spam_messages_blocked += 1;
         info!("Recorded spam blocked from {} in {}", message.username, message.channel);
     }
 
+    async fn process_dropped_event(
+        channel_stats: &Arc<RwLock<HashMap<String, ChannelStats>>>,
+        platform: &str,
+        channel: &str,
+        reason: &str,
+    ) {
+        let mut channel_stats_guard = channel_stats.write().await;
+        let channel_key = format!("{}:{}", platform, channel);
+
+        let stats = channel_stats_guard.entry(channel_key).or_insert(ChannelStats {
+            total_messages: 0,
+            unique_users: Vec::new(),
+            commands_executed: 0,
+            spam_messages_blocked: 0,
+            bot_messages_dropped: 0,
+        });
+
+        stats.bot_messages_dropped += 1;
+        info!("Recorded dropped bot message in {}#{}: {}", platform, channel, reason);
+    }
+
     async fn process_cooldown_event(
         command_stats: &Arc<RwLock<HashMap<String, CommandStats>>>,
         command: &str,
@@ -285,6 +333,9 @@ impl AnalyticsSystem {
         let total_spam_blocked = channel_stats.values().map(|s| s.spam_messages_blocked).sum::<u64>();
         
         analytics.insert("total_spam_blocked".to_string(), Value::Number(total_spam_blocked.into()));
+
+        let total_messages_dropped = channel_stats.values().map(|s| s.bot_messages_dropped).sum::<u64>();
+        analytics.insert("total_messages_dropped".to_string(), Value::Number(total_messages_dropped.into()));
         
         // Top users by activity
         let mut top_users: Vec<_> = user_stats
@@ -311,7 +362,7 @@ impl AnalyticsSystem {
 
     /// Get user stats for a specific user
     pub async fn get_user_stats(&self, platform: &str, username: &str) -> Option<UserStats> {
-        let user_key = format!("{}:{}", platform, username);
+        let user_key = self.identities.read().await.resolve(platform, username);
         self.user_stats.read().await.get(&user_key).cloned()
     }
 
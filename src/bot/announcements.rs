@@ -0,0 +1,99 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::config::RateLimitConfig;
+
+/// A channel a broadcast was sent to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AnnouncementTarget {
+    pub platform: String,
+    pub channel: String,
+}
+
+impl AnnouncementTarget {
+    pub fn new(platform: &str, channel: &str) -> Self {
+        Self {
+            platform: platform.to_string(),
+            channel: channel.to_string(),
+        }
+    }
+}
+
+/// Where a broadcast got through, and why it didn't elsewhere
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AnnouncementReport {
+    pub delivered: Vec<AnnouncementTarget>,
+    pub failed: Vec<(AnnouncementTarget, String)>,
+}
+
+impl AnnouncementReport {
+    pub fn merge(&mut self, other: AnnouncementReport) {
+        self.delivered.extend(other.delivered);
+        self.failed.extend(other.failed);
+    }
+
+    /// Sort by platform and channel so the summary reads the same every time
+    pub fn sort(&mut self) {
+        self.delivered.sort_by(|a, b| (&a.platform, &a.channel).cmp(&(&b.platform, &b.channel)));
+        self.failed.sort_by(|(a, _), (b, _)| (&a.platform, &a.channel).cmp(&(&b.platform, &b.channel)));
+    }
+
+    /// One-line result for the moderator who sent the announcement
+    pub fn summary(&self) -> String {
+        let total = self.delivered.len() + self.failed.len();
+        if total == 0 {
+            return "No connected channels to announce to".to_string();
+        }
+
+        let mut summary = format!("Announced to {}/{} channels", self.delivered.len(), total);
+        if !self.failed.is_empty() {
+            let failures: Vec<String> = self.failed.iter()
+                .map(|(target, reason)| format!("{}#{} ({})", target.platform, target.channel, reason))
+                .collect();
+            summary.push_str(&format!("; failed: {}", failures.join(", ")));
+        }
+        summary
+    }
+}
+
+/// Wait before the next send on a platform that has already had `sent` messages in
+/// this broadcast: the burst allowance goes out at once, the rest at the configured rate
+pub fn pacing_delay(limits: Option<&RateLimitConfig>, sent: usize) -> Duration {
+    match limits {
+        Some(limits) if sent >= limits.burst_limit.max(1) as usize && limits.messages_per_second > 0.0 => {
+            Duration::from_secs_f32(1.0 / limits.messages_per_second)
+        }
+        _ => Duration::ZERO,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pacing_follows_burst_then_rate() {
+        let limits = RateLimitConfig {
+            messages_per_second: 0.5,
+            burst_limit: 2,
+            cooldown_seconds: 30,
+        };
+        assert_eq!(pacing_delay(Some(&limits), 0), Duration::ZERO);
+        assert_eq!(pacing_delay(Some(&limits), 1), Duration::ZERO);
+        assert_eq!(pacing_delay(Some(&limits), 2), Duration::from_secs(2));
+        assert_eq!(pacing_delay(None, 10), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_summary_lists_failures() {
+        let mut report = AnnouncementReport::default();
+        assert_eq!(report.summary(), "No connected channels to announce to");
+
+        report.delivered.push(AnnouncementTarget::new("twitch", "b"));
+        report.delivered.push(AnnouncementTarget::new("twitch", "a"));
+        report.failed.push((AnnouncementTarget::new("youtube", "live"), "not connected".to_string()));
+        report.sort();
+        assert_eq!(report.delivered[0].channel, "a");
+        assert_eq!(report.summary(), "Announced to 2/3 channels; failed: youtube#live (not connected)");
+    }
+}
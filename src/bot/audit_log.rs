@@ -0,0 +1,226 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tokio::fs::{self, File, OpenOptions};
+use tokio::io::AsyncWriteExt;
+
+use crate::bot::redaction::{LogRedaction, RedactionMode};
+use crate::types::{ChatMessage, ModerationAction};
+
+/// Longest message excerpt kept with an audit entry
+const SNIPPET_MAX_CHARS: usize = 200;
+
+/// Who decided on a moderation action
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AuditSource {
+    /// A spam filter matched
+    Automatic,
+    /// A moderator acted by hand
+    Manual { moderator: String },
+}
+
+/// One moderation action: who it was taken against, what was done, when and why
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub timestamp: DateTime<Utc>,
+    pub platform: String,
+    pub channel: String,
+    pub username: String,
+    pub action: ModerationAction,
+    /// Filter that matched, for automatic actions
+    pub filter: Option<String>,
+    /// Detection confidence; only set for AI pattern matches, rules are certain
+    pub confidence: Option<f64>,
+    /// Start of the offending message, redacted like log output
+    pub message_snippet: String,
+    pub source: AuditSource,
+    /// The action was only logged because the bot was in dry-run mode
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+impl AuditEntry {
+    /// The snippet is redacted with `log_redaction`, like log output
+    pub fn new(message: &ChatMessage, action: &ModerationAction, source: AuditSource, log_redaction: &LogRedaction) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            platform: message.platform.clone(),
+            channel: message.channel.clone(),
+            username: message.username.clone(),
+            action: action.clone(),
+            filter: None,
+            confidence: None,
+            message_snippet: RedactionMode::Truncate { max_chars: SNIPPET_MAX_CHARS }.apply(&log_redaction.redact(&message.content)),
+            source,
+            dry_run: false,
+        }
+    }
+
+    /// An action decided by a spam filter
+    pub fn automatic(message: &ChatMessage, action: &ModerationAction, filter: &str, log_redaction: &LogRedaction) -> Self {
+        Self {
+            filter: Some(filter.to_string()),
+            ..Self::new(message, action, AuditSource::Automatic, log_redaction)
+        }
+    }
+}
+
+/// Which audit entries to read back; unset fields match everything
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AuditQuery {
+    /// Inclusive
+    pub since: Option<DateTime<Utc>>,
+    /// Exclusive
+    pub until: Option<DateTime<Utc>>,
+    pub platform: Option<String>,
+    /// Case-insensitive
+    pub username: Option<String>,
+}
+
+impl AuditQuery {
+    pub fn matches(&self, entry: &AuditEntry) -> bool {
+        self.since.is_none_or(|since| entry.timestamp >= since)
+            && self.until.is_none_or(|until| entry.timestamp < until)
+            && self.platform.as_ref().is_none_or(|platform| entry.platform.eq_ignore_ascii_case(platform))
+            && self.username.as_ref().is_none_or(|username| entry.username.eq_ignore_ascii_case(username))
+    }
+}
+
+/// Append-only JSON lines record of every moderation action, kept for accountability
+pub struct ModerationAuditLog {
+    path: PathBuf,
+    file: File,
+}
+
+impl ModerationAuditLog {
+    pub async fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent).await
+                .with_context(|| format!("Failed to create audit log directory {}", parent.display()))?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(&path).await
+            .with_context(|| format!("Failed to open audit log {}", path.display()))?;
+        info!("Writing moderation audit trail to {}", path.display());
+        Ok(Self { path, file })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append an entry; each line is handed to the OS straight away
+    pub async fn record(&mut self, entry: &AuditEntry) -> Result<()> {
+        let mut line = serde_json::to_string(entry)?;
+        line.push('\n');
+        self.file.write_all(line.as_bytes()).await
+            .with_context(|| format!("Failed to write audit log {}", self.path.display()))?;
+        self.file.flush().await?;
+        Ok(())
+    }
+
+    /// Entries matching `query`, oldest first
+    pub async fn query(path: &Path, query: &AuditQuery) -> Result<Vec<AuditEntry>> {
+        let content = match fs::read_to_string(path).await {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e).with_context(|| format!("Failed to read audit log {}", path.display())),
+        };
+
+        let mut skipped = 0;
+        let entries = content.lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| match serde_json::from_str::<AuditEntry>(line) {
+                Ok(entry) => Some(entry),
+                Err(_) => {
+                    skipped += 1;
+                    None
+                }
+            })
+            .filter(|entry| query.matches(entry))
+            .collect();
+        if skipped > 0 {
+            warn!("Skipped {} unreadable lines in audit log {}", skipped, path.display());
+        }
+        Ok(entries)
+    }
+
+    /// Write the entries matching `query` to `output` as a JSON array; returns how many
+    pub async fn export(path: &Path, query: &AuditQuery, output: &Path) -> Result<usize> {
+        let entries = Self::query(path, query).await?;
+        let json = serde_json::to_string_pretty(&entries)?;
+        fs::write(output, json).await
+            .with_context(|| format!("Failed to write audit export {}", output.display()))?;
+        info!("Exported {} audit entries to {}", entries.len(), output.display());
+        Ok(entries.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_actions_are_recorded_and_queryable() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit").join("moderation_audit.jsonl");
+        let mut log = ModerationAuditLog::open(&path).await.unwrap();
+        let redaction = LogRedaction::default();
+
+        let start = Utc::now();
+        let spam = ChatMessage::new("twitch", "channel", "Spammer", "buy followers at spam.example");
+        let mut first = AuditEntry::automatic(&spam, &ModerationAction::TimeoutUser { duration_seconds: 60 }, "links", &redaction);
+        first.timestamp = start - chrono::Duration::hours(2);
+        log.record(&first).await.unwrap();
+        log.record(&AuditEntry::automatic(&spam, &ModerationAction::WarnUser { message: "no links".to_string() }, "links", &redaction)).await.unwrap();
+        let rude = ChatMessage::new("youtube", "channel", "rude", "a".repeat(500));
+        let manual = AuditSource::Manual { moderator: "modname".to_string() };
+        log.record(&AuditEntry::new(&rude, &ModerationAction::DeleteMessage, manual.clone(), &redaction)).await.unwrap();
+        drop(log);
+
+        // Reopening appends
+        let mut log = ModerationAuditLog::open(&path).await.unwrap();
+        log.record(&AuditEntry::automatic(&rude, &ModerationAction::LogOnly, "caps", &redaction)).await.unwrap();
+
+        let all = ModerationAuditLog::query(&path, &AuditQuery::default()).await.unwrap();
+        assert_eq!(all.len(), 4);
+        assert_eq!(all[0].filter.as_deref(), Some("links"));
+        assert_eq!(all[0].message_snippet, "buy followers at spam.example");
+        assert!(matches!(all[0].action, ModerationAction::TimeoutUser { duration_seconds: 60 }));
+        assert_eq!(all[2].source, manual);
+        assert!(all[2].message_snippet.chars().count() <= SNIPPET_MAX_CHARS + 1);
+
+        let by_user = ModerationAuditLog::query(&path, &AuditQuery {
+            username: Some("spammer".to_string()),
+            ..Default::default()
+        }).await.unwrap();
+        assert_eq!(by_user.len(), 2);
+
+        let recent = ModerationAuditLog::query(&path, &AuditQuery {
+            since: Some(start - chrono::Duration::minutes(1)),
+            platform: Some("twitch".to_string()),
+            ..Default::default()
+        }).await.unwrap();
+        assert_eq!(recent.len(), 1);
+        assert!(matches!(recent[0].action, ModerationAction::WarnUser { .. }));
+
+        let output = dir.path().join("export.json");
+        let exported = ModerationAuditLog::export(&path, &AuditQuery {
+            until: Some(start),
+            ..Default::default()
+        }, &output).await.unwrap();
+        assert_eq!(exported, 1);
+        let saved: Vec<AuditEntry> = serde_json::from_str(&std::fs::read_to_string(&output).unwrap()).unwrap();
+        assert_eq!(saved[0].username, "Spammer");
+    }
+
+    #[tokio::test]
+    async fn test_missing_log_queries_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let entries = ModerationAuditLog::query(&dir.path().join("none.jsonl"), &AuditQuery::default()).await.unwrap();
+        assert!(entries.is_empty());
+    }
+}
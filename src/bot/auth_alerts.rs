@@ -0,0 +1,123 @@
+use chrono::{DateTime, Utc};
+use log::{info, warn};
+
+use crate::bot::spam_wave::SpamWaveAlert;
+use crate::config::WebhookConfig;
+use crate::platforms::ConnectionError;
+
+/// Webhook event name for rejected platform credentials
+pub const AUTH_FAILED_EVENT: &str = "auth_failed";
+
+/// Webhook event name for a spam wave that has gone quiet
+pub const SPAM_WAVE_EVENT: &str = "spam_wave";
+
+/// Header carrying a webhook's shared secret, when one is configured
+const SECRET_HEADER: &str = "X-Webhook-Secret";
+
+/// JSON body posted to webhooks subscribed to auth failures
+pub fn auth_failure_payload(error: &ConnectionError, now: DateTime<Utc>) -> serde_json::Value {
+    let ConnectionError::AuthFailed { platform, reason } = error;
+    serde_json::json!({
+        "event": AUTH_FAILED_EVENT,
+        "platform": platform,
+        "reason": reason,
+        "message": error.to_string(),
+        "timestamp": now,
+    })
+}
+
+/// JSON body posted to webhooks subscribed to spam waves
+pub fn spam_wave_payload(alert: &SpamWaveAlert) -> serde_json::Value {
+    serde_json::json!({
+        "event": SPAM_WAVE_EVENT,
+        "filter": alert.filter_name,
+        "blocked_count": alert.blocked_count,
+        "duration_seconds": alert.duration_seconds(),
+        "started_at": alert.started_at,
+        "ended_at": alert.ended_at,
+    })
+}
+
+/// Webhooks that asked for `event` alerts
+pub fn subscribed_webhooks<'a>(webhooks: &'a [WebhookConfig], event: &'a str) -> impl Iterator<Item = &'a WebhookConfig> {
+    webhooks.iter().filter(move |w| w.events.iter().any(|e| e == event || e == "*"))
+}
+
+/// Notify subscribed webhooks that a platform rejected our credentials.
+/// Delivery failures are logged and otherwise ignored.
+pub async fn send_auth_failure_alert(client: &reqwest::Client, webhooks: &[WebhookConfig], error: &ConnectionError) {
+    send_alert(client, webhooks, AUTH_FAILED_EVENT, &auth_failure_payload(error, Utc::now())).await;
+}
+
+/// Notify subscribed webhooks that a spam wave has ended.
+/// Delivery failures are logged and otherwise ignored.
+pub async fn send_spam_wave_alert(client: &reqwest::Client, webhooks: &[WebhookConfig], alert: &SpamWaveAlert) {
+    send_alert(client, webhooks, SPAM_WAVE_EVENT, &spam_wave_payload(alert)).await;
+}
+
+async fn send_alert(client: &reqwest::Client, webhooks: &[WebhookConfig], event: &str, payload: &serde_json::Value) {
+    for webhook in subscribed_webhooks(webhooks, event) {
+        let mut request = client.post(&webhook.url).json(payload);
+        if let Some(secret) = &webhook.secret {
+            request = request.header(SECRET_HEADER, secret);
+        }
+
+        match request.send().await {
+            Ok(response) if response.status().is_success() => {
+                info!("Sent {} alert to webhook '{}'", event, webhook.name);
+            }
+            Ok(response) => warn!("Webhook '{}' rejected {} alert: {}", webhook.name, event, response.status()),
+            Err(e) => warn!("Failed to send {} alert to webhook '{}': {}", event, webhook.name, e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn webhook(name: &str, events: &[&str]) -> WebhookConfig {
+        WebhookConfig {
+            name: name.to_string(),
+            url: format!("https://example.com/{}", name),
+            events: events.iter().map(|e| e.to_string()).collect(),
+            secret: None,
+        }
+    }
+
+    #[test]
+    fn test_auth_failure_alert_payload() {
+        let error = ConnectionError::AuthFailed {
+            platform: "twitch".to_string(),
+            reason: "Login authentication failed".to_string(),
+        };
+        let payload = auth_failure_payload(&error, Utc::now());
+        assert_eq!(payload["event"], "auth_failed");
+        assert_eq!(payload["platform"], "twitch");
+        assert_eq!(payload["reason"], "Login authentication failed");
+
+        let webhooks = vec![webhook("ops", &["auth_failed"]), webhook("stats", &["follow"]), webhook("all", &["*"])];
+        let names: Vec<_> = subscribed_webhooks(&webhooks, AUTH_FAILED_EVENT).map(|w| w.name.as_str()).collect();
+        assert_eq!(names, vec!["ops", "all"]);
+    }
+
+    #[test]
+    fn test_spam_wave_alert_payload() {
+        let started_at = Utc::now();
+        let alert = SpamWaveAlert {
+            filter_name: "crypto".to_string(),
+            blocked_count: 25,
+            started_at,
+            ended_at: started_at + chrono::Duration::seconds(90),
+        };
+        let payload = spam_wave_payload(&alert);
+        assert_eq!(payload["event"], "spam_wave");
+        assert_eq!(payload["filter"], "crypto");
+        assert_eq!(payload["blocked_count"], 25);
+        assert_eq!(payload["duration_seconds"], 90);
+
+        let webhooks = vec![webhook("ops", &["auth_failed"]), webhook("mods", &["spam_wave"]), webhook("all", &["*"])];
+        let names: Vec<_> = subscribed_webhooks(&webhooks, SPAM_WAVE_EVENT).map(|w| w.name.as_str()).collect();
+        assert_eq!(names, vec!["mods", "all"]);
+    }
+}
@@ -1,15 +1,97 @@
 use anyhow::Result;
-use log::{debug, error, info};
-use std::collections::HashMap;
+use log::{debug, error, info, warn};
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
-use crate::types::{BotCommand, ChatMessage};
+use crate::bot::moderation::ModerationSystem;
+use crate::bot::redaction::LogRedaction;
+use crate::types::{BotCommand, ChatMessage, CommandChannels, ModerationAction};
+
+/// What happens when a user keeps hitting a command that is on cooldown
+#[derive(Debug, Clone)]
+pub enum CooldownConsequence {
+    /// Keep silently ignoring the command
+    Ignore,
+    /// Reply to the user in channel (platforms don't expose whispers)
+    Warn { message: String },
+    /// Apply a moderation action to the user
+    Moderate(ModerationAction),
+}
+
+/// Escalation for repeated command use during cooldown
+#[derive(Debug, Clone)]
+pub struct CooldownAbusePolicy {
+    /// Cooldown hits before the consequence is applied
+    pub max_hits: u32,
+    /// Hit count resets after this long without another hit
+    pub decay_seconds: u64,
+    pub consequence: CooldownConsequence,
+}
+
+/// Per-user count of cooldown hits for a command
+#[derive(Debug, Clone)]
+struct CooldownHits {
+    count: u32,
+    last_hit: chrono::DateTime<chrono::Utc>,
+}
+
+/// Default shortest gap between two cooldown notices in one channel
+pub const DEFAULT_COOLDOWN_NOTICE_INTERVAL_SECONDS: u64 = 10;
+
+/// Telling users that a command is on cooldown instead of ignoring them. Notices
+/// go to the channel as platforms don't expose whispers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CooldownNotice {
+    /// `$(user)`, `$(command)` and `$(remaining)` are filled in. `None` stays silent
+    /// except for commands with a notice of their own.
+    pub message: Option<String>,
+    /// At most one notice per channel this often, whoever triggers it
+    pub channel_interval_seconds: u64,
+}
+
+impl Default for CooldownNotice {
+    fn default() -> Self {
+        Self { message: None, channel_interval_seconds: DEFAULT_COOLDOWN_NOTICE_INTERVAL_SECONDS }
+    }
+}
+
+/// How many commands one user may use per minute, across every command
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommandRateLimit {
+    /// 0 disables the limit
+    pub per_minute: u32,
+    pub exempt_mods: bool,
+}
+
+impl Default for CommandRateLimit {
+    fn default() -> Self {
+        Self { per_minute: 0, exempt_mods: true }
+    }
+}
+
+/// A user's remaining command allowance; refills continuously up to `per_minute`
+#[derive(Debug, Clone)]
+struct TokenBucket {
+    tokens: f64,
+    last_refill: chrono::DateTime<chrono::Utc>,
+}
 
 pub struct CommandSystem {
     pub commands: Arc<RwLock<HashMap<String, BotCommand>>>,
     pub command_cooldowns: Arc<RwLock<HashMap<String, chrono::DateTime<chrono::Utc>>>>,
     pub command_prefix: Arc<RwLock<String>>,
+    platform_prefixes: Arc<RwLock<HashMap<String, String>>>,
+    cooldown_abuse_policy: Arc<RwLock<Option<CooldownAbusePolicy>>>,
+    cooldown_hits: Arc<RwLock<HashMap<String, CooldownHits>>>,
+    disabled_categories: Arc<RwLock<HashSet<String>>>,
+    rate_limit: Arc<RwLock<CommandRateLimit>>,
+    rate_limit_buckets: Arc<RwLock<HashMap<String, TokenBucket>>>,
+    cooldown_notice: Arc<RwLock<CooldownNotice>>,
+    /// Until when further cooldown notices are held back, per user and command and per channel
+    cooldown_notices_held: Arc<RwLock<HashMap<String, chrono::DateTime<chrono::Utc>>>>,
+    /// How message content appears when a cooldown abuse consequence is logged
+    log_redaction: LogRedaction,
 }
 
 impl CommandSystem {
@@ -19,15 +101,231 @@ impl CommandSystem {
             commands: Arc::new(RwLock::new(HashMap::new())),
             command_cooldowns: Arc::new(RwLock::new(HashMap::new())),
             command_prefix: Arc::new(RwLock::new("!".to_string())),
+            platform_prefixes: Arc::new(RwLock::new(HashMap::new())),
+            cooldown_abuse_policy: Arc::new(RwLock::new(None)),
+            cooldown_hits: Arc::new(RwLock::new(HashMap::new())),
+            disabled_categories: Arc::new(RwLock::new(HashSet::new())),
+            rate_limit: Arc::new(RwLock::new(CommandRateLimit::default())),
+            rate_limit_buckets: Arc::new(RwLock::new(HashMap::new())),
+            cooldown_notice: Arc::new(RwLock::new(CooldownNotice::default())),
+            cooldown_notices_held: Arc::new(RwLock::new(HashMap::new())),
+            log_redaction: LogRedaction::default(),
+        }
+    }
+
+    /// Share the bot's log redaction setting
+    pub fn with_log_redaction(mut self, log_redaction: LogRedaction) -> Self {
+        self.log_redaction = log_redaction;
+        self
+    }
+
+    /// Limit how many commands each user may use per minute, on top of per-command cooldowns
+    pub async fn set_rate_limit(&self, limit: CommandRateLimit) {
+        if limit.per_minute == 0 {
+            info!("Per-user command rate limit disabled");
+        } else {
+            info!("Per-user command rate limit: {} per minute{}", limit.per_minute,
+                  if limit.exempt_mods { " (moderators exempt)" } else { "" });
+        }
+        *self.rate_limit.write().await = limit;
+        self.rate_limit_buckets.write().await.clear();
+    }
+
+    /// Take one command from the user's allowance; false means they're throttled.
+    /// Checked before a command is dispatched to any command handler.
+    pub async fn allow_command(&self, message: &ChatMessage) -> bool {
+        self.allow_command_at(message, chrono::Utc::now()).await
+    }
+
+    async fn allow_command_at(&self, message: &ChatMessage, now: chrono::DateTime<chrono::Utc>) -> bool {
+        let limit = self.rate_limit.read().await.clone();
+        if limit.per_minute == 0 || (limit.exempt_mods && message.is_mod) {
+            return true;
+        }
+
+        let capacity = limit.per_minute as f64;
+        let per_second = capacity / 60.0;
+        let refill = |bucket: &mut TokenBucket| {
+            let elapsed = (now - bucket.last_refill).num_milliseconds().max(0) as f64 / 1000.0;
+            bucket.tokens = (bucket.tokens + elapsed * per_second).min(capacity);
+            bucket.last_refill = now;
+        };
+
+        let mut buckets = self.rate_limit_buckets.write().await;
+        // Full buckets carry no state, so drop them to keep the map bounded
+        if buckets.len() > 1000 {
+            buckets.retain(|_, bucket| {
+                refill(bucket);
+                bucket.tokens < capacity
+            });
+        }
+
+        let key = format!("{}:{}", message.platform, message.username.to_lowercase());
+        let bucket = buckets.entry(key).or_insert(TokenBucket { tokens: capacity, last_refill: now });
+        refill(bucket);
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            true
+        } else {
+            debug!("Throttling commands from '{}' in #{}: over {} per minute",
+                   message.username, message.channel, limit.per_minute);
+            false
+        }
+    }
+
+    /// Set how repeated cooldown hits are handled (`None` keeps silently ignoring them)
+    pub async fn set_cooldown_abuse_policy(&self, policy: Option<CooldownAbusePolicy>) {
+        match &policy {
+            Some(p) => info!("Cooldown abuse policy set: {:?} after {} hits", p.consequence, p.max_hits),
+            None => info!("Cooldown abuse policy disabled"),
+        }
+        *self.cooldown_abuse_policy.write().await = policy;
+        self.cooldown_hits.write().await.clear();
+    }
+
+    /// Set the reply users get when a command is on cooldown
+    pub async fn set_cooldown_notice(&self, notice: CooldownNotice) {
+        match &notice.message {
+            Some(message) => info!("Cooldown notice set: {}", message),
+            None => info!("Cooldown notices disabled"),
+        }
+        *self.cooldown_notice.write().await = notice;
+        self.cooldown_notices_held.write().await.clear();
+    }
+
+    /// Give one command its own cooldown notice, or clear it with `None` (returns false if the command doesn't exist)
+    pub async fn set_command_cooldown_notice(&self, command_name: &str, notice: Option<String>) -> bool {
+        match self.commands.write().await.get_mut(command_name) {
+            Some(command) => {
+                command.cooldown_notice = notice;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Tell the user how long is left on the cooldown: once per cooldown for each user
+    /// and command, and no more than once per `channel_interval_seconds` in a channel
+    async fn send_cooldown_notice(
+        &self,
+        command: &BotCommand,
+        message: &ChatMessage,
+        remaining_seconds: i64,
+        response_sender: &tokio::sync::mpsc::Sender<(String, String, String)>,
+    ) {
+        let notice = self.cooldown_notice.read().await.clone();
+        let Some(template) = command.cooldown_notice.as_ref().or(notice.message.as_ref()) else {
+            return;
+        };
+
+        let now = chrono::Utc::now();
+        let user_key = format!("{}:{}:{}:{}", message.platform, message.channel, message.username.to_lowercase(), command.trigger);
+        let channel_key = format!("{}:{}", message.platform, message.channel);
+        {
+            let mut held = self.cooldown_notices_held.write().await;
+            held.retain(|_, until| *until > now);
+            if held.contains_key(&user_key) || held.contains_key(&channel_key) {
+                debug!("Holding back cooldown notice for '{}' to {}", command.trigger, message.username);
+                return;
+            }
+            held.insert(user_key, now + chrono::Duration::seconds(remaining_seconds));
+            held.insert(channel_key, now + chrono::Duration::seconds(notice.channel_interval_seconds as i64));
+        }
+
+        let reply = template
+            .replace("$(user)", &message.username)
+            .replace("$(command)", &command.trigger)
+            .replace("$(remaining)", &remaining_seconds.to_string());
+        if let Err(e) = response_sender.send((message.platform.clone(), message.channel.clone(), reply)).await {
+            error!("Failed to send cooldown notice: {}", e);
         }
     }
 
+    /// Count a cooldown hit and apply the abuse policy once the user crosses the threshold
+    async fn handle_cooldown_hit(
+        &self,
+        command_name: &str,
+        message: &ChatMessage,
+        response_sender: &tokio::sync::mpsc::Sender<(String, String, String)>,
+    ) -> Result<()> {
+        let Some(policy) = self.cooldown_abuse_policy.read().await.clone() else {
+            return Ok(());
+        };
+
+        let now = chrono::Utc::now();
+        let hits_key = format!("{}:{}:{}:{}", message.platform, message.channel, message.username, command_name);
+        let threshold_reached = {
+            let mut hits_guard = self.cooldown_hits.write().await;
+            // Drop counts that have decayed so the map doesn't grow unbounded
+            hits_guard.retain(|_, hits| (now - hits.last_hit).num_seconds() < policy.decay_seconds as i64);
+
+            let hits = hits_guard.entry(hits_key.clone())
+                .or_insert(CooldownHits { count: 0, last_hit: now });
+            hits.count += 1;
+            hits.last_hit = now;
+
+            if hits.count >= policy.max_hits {
+                hits_guard.remove(&hits_key);
+                true
+            } else {
+                false
+            }
+        };
+
+        if !threshold_reached {
+            return Ok(());
+        }
+
+        warn!("User '{}' kept using '{}' during cooldown in #{}", message.username, command_name, message.channel);
+        match policy.consequence {
+            CooldownConsequence::Ignore => {}
+            CooldownConsequence::Warn { message: warning } => {
+                let warn_msg = format!("@{} {}", message.username, warning);
+                if let Err(e) = response_sender.send((
+                    message.platform.clone(),
+                    message.channel.clone(),
+                    warn_msg
+                )).await {
+                    error!("Failed to send cooldown warning: {}", e);
+                }
+            }
+            CooldownConsequence::Moderate(action) => {
+                ModerationSystem::handle_moderation_action(action, message, response_sender, &self.log_redaction).await?;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Set the command prefix (default is "!")
     pub async fn set_command_prefix(&self, prefix: String) {
         *self.command_prefix.write().await = prefix.clone();
         info!("Command prefix set to: {}", prefix);
     }
 
+    /// Set the command prefix for one platform, overriding the global prefix (`None` reverts to it)
+    pub async fn set_command_prefix_for_platform(&self, platform: &str, prefix: Option<String>) {
+        let mut prefixes = self.platform_prefixes.write().await;
+        match prefix {
+            Some(prefix) => {
+                info!("Command prefix for {} set to: {}", platform, prefix);
+                prefixes.insert(platform.to_string(), prefix);
+            }
+            None => {
+                info!("Command prefix for {} reset to global", platform);
+                prefixes.remove(platform);
+            }
+        }
+    }
+
+    /// Command prefix in effect for a platform
+    pub async fn get_command_prefix(&self, platform: &str) -> String {
+        match self.platform_prefixes.read().await.get(platform) {
+            Some(prefix) => prefix.clone(),
+            None => self.command_prefix.read().await.clone(),
+        }
+    }
+
     /// Register a new command
     pub async fn add_command(&self, trigger: String, response: String, mod_only: bool, cooldown_seconds: u64) {
         let command = BotCommand {
@@ -35,6 +333,9 @@ impl CommandSystem {
             response,
             mod_only,
             cooldown_seconds,
+            channels: CommandChannels::All,
+            category: None,
+            cooldown_notice: None,
         };
         
         self.commands.write().await.insert(trigger.clone(), command);
@@ -48,8 +349,8 @@ impl CommandSystem {
         response_sender: &tokio::sync::mpsc::Sender<(String, String, String)>,
         analytics_sender: Option<&tokio::sync::mpsc::Sender<(String, String, String)>>, // (command, user, channel)
     ) -> Result<()> {
-        // Get current prefix
-        let prefix = self.command_prefix.read().await.clone();
+        // Get current prefix for the message's platform
+        let prefix = self.get_command_prefix(&message.platform).await;
         
         // Check if message starts with command prefix
         if !message.content.starts_with(&prefix) {
@@ -75,12 +376,30 @@ impl CommandSystem {
         let command = match commands_guard.get(&command_name) {
             Some(cmd) => cmd.clone(),
             None => {
+                drop(commands_guard);
+                if command_name == "cmdgroup" {
+                    return self.handle_cmdgroup_command(&args, &message, response_sender).await;
+                }
                 debug!("Unknown command: {}", command_name);
                 return Ok(());
             }
         };
         drop(commands_guard);
 
+        // Check the command's category is enabled
+        if let Some(category) = &command.category {
+            if self.disabled_categories.read().await.contains(&category.to_lowercase()) {
+                debug!("Command '{}' skipped, category '{}' is disabled", command_name, category);
+                return Ok(());
+            }
+        }
+
+        // Check the command is enabled in this channel
+        if !command.channels.allows(&message.channel) {
+            debug!("Command '{}' is disabled in #{}", command_name, message.channel);
+            return Ok(());
+        }
+
         // Check permissions
         if command.mod_only && !message.is_mod {
             debug!("User '{}' attempted to use mod-only command '{}'", 
@@ -95,9 +414,11 @@ impl CommandSystem {
         if let Some(last_used) = cooldowns_guard.get(&cooldown_key) {
             let elapsed = chrono::Utc::now().signed_duration_since(*last_used);
             if elapsed.num_seconds() < command.cooldown_seconds as i64 {
-                debug!("Command '{}' is on cooldown for {} more seconds", 
-                       command_name, command.cooldown_seconds as i64 - elapsed.num_seconds());
-                return Ok(());
+                let remaining = command.cooldown_seconds as i64 - elapsed.num_seconds();
+                debug!("Command '{}' is on cooldown for {} more seconds", command_name, remaining);
+                drop(cooldowns_guard);
+                self.send_cooldown_notice(&command, &message, remaining, response_sender).await;
+                return self.handle_cooldown_hit(&command_name, &message, response_sender).await;
             }
         }
 
@@ -174,13 +495,112 @@ impl CommandSystem {
         self.commands.read().await.get(command_name).cloned()
     }
 
+    /// Restrict which channels a command responds in (returns false if the command doesn't exist)
+    pub async fn set_command_channels(&self, command_name: &str, channels: CommandChannels) -> bool {
+        match self.commands.write().await.get_mut(command_name) {
+            Some(command) => {
+                info!("Command !{} channels set to {:?}", command_name, channels);
+                command.channels = channels;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Put a command in a category, or clear it with `None` (returns false if the command doesn't exist)
+    pub async fn set_command_category(&self, command_name: &str, category: Option<String>) -> bool {
+        match self.commands.write().await.get_mut(command_name) {
+            Some(command) => {
+                command.category = category;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Enable/disable all commands in a category, returning how many commands it covers
+    pub async fn set_category_enabled(&self, category: &str, enabled: bool) -> Result<usize> {
+        let count = self.commands.read().await.values()
+            .filter(|c| c.category.as_deref().is_some_and(|c| c.eq_ignore_ascii_case(category)))
+            .count();
+        if count == 0 {
+            return Err(anyhow::anyhow!("Category '{}' not found", category));
+        }
+
+        let mut disabled = self.disabled_categories.write().await;
+        if enabled {
+            disabled.remove(&category.to_lowercase());
+        } else {
+            disabled.insert(category.to_lowercase());
+        }
+
+        info!("{} {} commands in category '{}'",
+              if enabled { "Enabled" } else { "Disabled" }, count, category);
+        Ok(count)
+    }
+
+    /// Check whether a command category is enabled (unknown categories are)
+    pub async fn is_category_enabled(&self, category: &str) -> bool {
+        !self.disabled_categories.read().await.contains(&category.to_lowercase())
+    }
+
+    /// Handle !cmdgroup <category> <on|off> (mod only)
+    async fn handle_cmdgroup_command(
+        &self,
+        args: &[&str],
+        message: &ChatMessage,
+        response_sender: &tokio::sync::mpsc::Sender<(String, String, String)>,
+    ) -> Result<()> {
+        if !message.is_mod {
+            return Ok(());
+        }
+
+        let response = match args {
+            [category, action] => {
+                let enabled = match action.to_lowercase().as_str() {
+                    "on" | "enable" => Some(true),
+                    "off" | "disable" => Some(false),
+                    _ => None,
+                };
+                match enabled {
+                    Some(enabled) => match self.set_category_enabled(category, enabled).await {
+                        Ok(count) => format!("{} {} commands in category '{}'",
+                                             if enabled { "Enabled" } else { "Disabled" }, count, category),
+                        Err(e) => format!("{}", e),
+                    },
+                    None => "Usage: !cmdgroup <category> <on|off>".to_string(),
+                }
+            }
+            _ => "Usage: !cmdgroup <category> <on|off>".to_string(),
+        };
+
+        if let Err(e) = response_sender.send((
+            message.platform.clone(),
+            message.channel.clone(),
+            response
+        )).await {
+            error!("Failed to send command response: {}", e);
+        }
+        Ok(())
+    }
+
     /// Remove a command
     pub async fn remove_command(&self, command_name: &str) -> bool {
         self.commands.write().await.remove(command_name).is_some()
     }
 
-    /// Check if a command can be executed (cooldown and permissions)
+    /// Check if a command can be executed (channel, cooldown and permissions)
     pub async fn can_execute_command(&self, command: &BotCommand, user: &ChatMessage) -> bool {
+        if !command.channels.allows(&user.channel) {
+            return false;
+        }
+
+        if let Some(category) = &command.category {
+            if !self.is_category_enabled(category).await {
+                return false;
+            }
+        }
+
         // Check mod-only restriction
         if command.mod_only && !user.is_mod {
             return false;
@@ -199,4 +619,260 @@ impl CommandSystem {
 
         true
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn spam_command(
+        commands: &CommandSystem,
+        username: &str,
+        times: usize,
+    ) -> Vec<String> {
+        let (tx, mut rx) = tokio::sync::mpsc::channel(100);
+        for _ in 0..times {
+            let message = ChatMessage::new("twitch", "testchannel", username, "!discord");
+            commands.process_message(message, &tx, None).await.unwrap();
+        }
+        drop(tx);
+
+        let mut responses = Vec::new();
+        while let Some((_, _, response)) = rx.recv().await {
+            responses.push(response);
+        }
+        responses
+    }
+
+    #[tokio::test]
+    async fn test_user_command_rate_limit() {
+        let commands = CommandSystem::new();
+        commands.set_rate_limit(CommandRateLimit { per_minute: 3, exempt_mods: true }).await;
+        let start = chrono::Utc::now();
+        let viewer = ChatMessage::new("twitch", "testchannel", "viewer", "!discord");
+
+        let mut allowed = Vec::new();
+        for _ in 0..5 {
+            allowed.push(commands.allow_command_at(&viewer, start).await);
+        }
+        assert_eq!(allowed, vec![true, true, true, false, false]);
+
+        // Other users and moderators have their own allowance
+        assert!(commands.allow_command_at(&ChatMessage::new("twitch", "testchannel", "other", "!discord"), start).await);
+        let mut moderator = ChatMessage::new("twitch", "testchannel", "modname", "!discord");
+        moderator.is_mod = true;
+        for _ in 0..10 {
+            assert!(commands.allow_command_at(&moderator, start).await);
+        }
+
+        // One command's worth refills every 20 seconds at 3 per minute
+        assert!(!commands.allow_command_at(&viewer, start + chrono::Duration::seconds(10)).await);
+        assert!(commands.allow_command_at(&viewer, start + chrono::Duration::seconds(21)).await);
+        assert!(!commands.allow_command_at(&viewer, start + chrono::Duration::seconds(22)).await);
+
+        commands.set_rate_limit(CommandRateLimit { per_minute: 3, exempt_mods: false }).await;
+        for _ in 0..3 {
+            assert!(commands.allow_command_at(&moderator, start).await);
+        }
+        assert!(!commands.allow_command_at(&moderator, start).await);
+
+        commands.set_rate_limit(CommandRateLimit::default()).await;
+        assert!(commands.allow_command_at(&viewer, start).await);
+    }
+
+    #[tokio::test]
+    async fn test_cooldown_hits_silently_ignored_by_default() {
+        let commands = CommandSystem::new();
+        commands.add_command("discord".to_string(), "Join our discord!".to_string(), false, 60).await;
+
+        let responses = spam_command(&commands, "viewer", 10).await;
+        assert_eq!(responses, vec!["Join our discord!"]);
+    }
+
+    #[tokio::test]
+    async fn test_repeated_cooldown_hits_escalate() {
+        let commands = CommandSystem::new();
+        commands.add_command("discord".to_string(), "Join our discord!".to_string(), false, 60).await;
+        commands.set_cooldown_abuse_policy(Some(CooldownAbusePolicy {
+            max_hits: 3,
+            decay_seconds: 60,
+            consequence: CooldownConsequence::Warn { message: "please stop spamming commands".to_string() },
+        })).await;
+
+        // First use runs, next two hits stay silent, the third hit warns
+        let responses = spam_command(&commands, "viewer", 4).await;
+        assert_eq!(responses, vec![
+            "Join our discord!".to_string(),
+            "@viewer please stop spamming commands".to_string(),
+        ]);
+
+        // Count starts over after the consequence, and is tracked per user
+        assert!(spam_command(&commands, "viewer", 2).await.is_empty());
+        assert!(spam_command(&commands, "other", 2).await.is_empty());
+
+        commands.set_cooldown_abuse_policy(Some(CooldownAbusePolicy {
+            max_hits: 2,
+            decay_seconds: 60,
+            consequence: CooldownConsequence::Moderate(ModerationAction::TimeoutUser { duration_seconds: 30 }),
+        })).await;
+        let responses = spam_command(&commands, "viewer", 2).await;
+        assert_eq!(responses, vec!["@viewer has been timed out for 30 seconds"]);
+    }
+
+    #[tokio::test]
+    async fn test_cooldown_notice_fires_once_per_cooldown() {
+        let commands = CommandSystem::new();
+        commands.add_command("discord".to_string(), "Join our discord!".to_string(), false, 60).await;
+        commands.set_cooldown_notice(CooldownNotice {
+            message: Some("@$(user) !$(command) is on cooldown, try again in $(remaining)s".to_string()),
+            channel_interval_seconds: 10,
+        }).await;
+
+        // Repeats within the cooldown get no further notice
+        let responses = spam_command(&commands, "viewer", 5).await;
+        assert_eq!(responses, vec![
+            "Join our discord!".to_string(),
+            "@viewer !discord is on cooldown, try again in 60s".to_string(),
+        ]);
+        assert!(spam_command(&commands, "viewer", 3).await.is_empty());
+
+        // Other users are held back by the channel interval
+        assert!(spam_command(&commands, "other", 1).await.is_empty());
+        let age = |key: &'static str, seconds: i64| {
+            let commands = &commands;
+            async move {
+                let mut held = commands.cooldown_notices_held.write().await;
+                *held.get_mut(key).unwrap() -= chrono::Duration::seconds(seconds);
+            }
+        };
+        age("twitch:testchannel", 11).await;
+        assert_eq!(spam_command(&commands, "other", 2).await, vec!["@other !discord is on cooldown, try again in 60s"]);
+        assert!(spam_command(&commands, "viewer", 1).await.is_empty());
+
+        // A command's own notice wins over the global one
+        assert!(commands.set_command_cooldown_notice("discord", Some("Patience, $(user)".to_string())).await);
+        age("twitch:testchannel", 11).await;
+        assert_eq!(spam_command(&commands, "third", 1).await, vec!["Patience, third"]);
+
+        commands.set_cooldown_notice(CooldownNotice::default()).await;
+        commands.set_command_cooldown_notice("discord", None).await;
+        assert!(spam_command(&commands, "fourth", 1).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_cooldown_hits_decay() {
+        let commands = CommandSystem::new();
+        commands.add_command("discord".to_string(), "Join our discord!".to_string(), false, 60).await;
+        commands.set_cooldown_abuse_policy(Some(CooldownAbusePolicy {
+            max_hits: 2,
+            decay_seconds: 30,
+            consequence: CooldownConsequence::Warn { message: "slow down".to_string() },
+        })).await;
+
+        spam_command(&commands, "viewer", 2).await;
+
+        // Age the recorded hit past the decay window
+        for hits in commands.cooldown_hits.write().await.values_mut() {
+            hits.last_hit = chrono::Utc::now() - chrono::Duration::seconds(31);
+        }
+        assert!(spam_command(&commands, "viewer", 1).await.is_empty());
+        assert_eq!(spam_command(&commands, "viewer", 1).await, vec!["@viewer slow down"]);
+    }
+
+    #[tokio::test]
+    async fn test_command_channel_restrictions() {
+        let commands = CommandSystem::new();
+        commands.add_command("so".to_string(), "Go follow $(1)!".to_string(), false, 0).await;
+
+        let run = |channel: &'static str| {
+            let commands = &commands;
+            async move {
+                let (tx, mut rx) = tokio::sync::mpsc::channel(10);
+                let message = ChatMessage::new("twitch", channel, "viewer", "!so friend");
+                commands.process_message(message, &tx, None).await.unwrap();
+                drop(tx);
+                rx.recv().await.map(|(_, _, response)| response)
+            }
+        };
+
+        // All channels by default
+        assert!(run("mainchannel").await.is_some());
+        assert!(run("sidechannel").await.is_some());
+
+        assert!(commands.set_command_channels("so", CommandChannels::Only(vec!["#MainChannel".to_string()])).await);
+        assert_eq!(run("mainchannel").await, Some("Go follow friend!".to_string()));
+        assert_eq!(run("sidechannel").await, None);
+
+        commands.set_command_channels("so", CommandChannels::Except(vec!["mainchannel".to_string()])).await;
+        assert_eq!(run("mainchannel").await, None);
+        assert!(run("sidechannel").await.is_some());
+
+        assert!(!commands.set_command_channels("missing", CommandChannels::All).await);
+    }
+
+    #[tokio::test]
+    async fn test_command_category_toggle() {
+        let commands = CommandSystem::new();
+        commands.add_command("hug".to_string(), "hugs!".to_string(), false, 0).await;
+        commands.add_command("dance".to_string(), "dances!".to_string(), false, 0).await;
+        commands.add_command("discord".to_string(), "Join our discord!".to_string(), false, 0).await;
+        commands.set_command_category("hug", Some("fun".to_string())).await;
+        commands.set_command_category("dance", Some("Fun".to_string())).await;
+
+        let run = |content: &'static str, is_mod: bool| {
+            let commands = &commands;
+            async move {
+                let (tx, mut rx) = tokio::sync::mpsc::channel(10);
+                let message = ChatMessage::new("twitch", "testchannel", "someone", content).with_mod(is_mod);
+                commands.process_message(message, &tx, None).await.unwrap();
+                drop(tx);
+                rx.recv().await.map(|(_, _, response)| response)
+            }
+        };
+
+        // Only moderators can toggle groups
+        assert_eq!(run("!cmdgroup fun off", false).await, None);
+        assert_eq!(run("!hug", false).await, Some("hugs!".to_string()));
+
+        assert_eq!(run("!cmdgroup fun off", true).await, Some("Disabled 2 commands in category 'fun'".to_string()));
+        assert_eq!(run("!hug", false).await, None);
+        assert_eq!(run("!dance", false).await, None);
+        assert_eq!(run("!discord", false).await, Some("Join our discord!".to_string()));
+
+        assert_eq!(run("!cmdgroup serious off", true).await, Some("Category 'serious' not found".to_string()));
+
+        assert_eq!(run("!cmdgroup FUN on", true).await, Some("Enabled 2 commands in category 'FUN'".to_string()));
+        assert_eq!(run("!dance", false).await, Some("dances!".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_per_platform_prefix() {
+        let commands = CommandSystem::new();
+        commands.add_command("discord".to_string(), "Join our discord!".to_string(), false, 0).await;
+        commands.set_command_prefix_for_platform("discord", Some("?".to_string())).await;
+
+        let run = |platform: &'static str, content: &'static str| {
+            let commands = &commands;
+            async move {
+                let (tx, mut rx) = tokio::sync::mpsc::channel(10);
+                let message = ChatMessage::new(platform, "testchannel", "viewer", content);
+                commands.process_message(message, &tx, None).await.unwrap();
+                drop(tx);
+                rx.recv().await.is_some()
+            }
+        };
+
+        assert!(run("twitch", "!discord").await);
+        assert!(!run("twitch", "?discord").await);
+        assert!(run("discord", "?discord").await);
+        assert!(!run("discord", "!discord").await);
+
+        // Platforms without an override follow the global prefix
+        commands.set_command_prefix("~".to_string()).await;
+        assert!(run("twitch", "~discord").await);
+        assert!(run("discord", "?discord").await);
+
+        commands.set_command_prefix_for_platform("discord", None).await;
+        assert!(run("discord", "~discord").await);
+    }
+}
@@ -9,18 +9,31 @@ use std::collections::HashMap;
 use crate::config::{
     ConfigurationManager, ConfigChangeEvent, FilterConfiguration, PatternConfiguration,
     TimerConfiguration, EnhancedBlacklistFilter, EnhancedSpamFilter, PatternDefinition,
-    EnhancedTimer
+    EnhancedTimer, AllowlistConfig, BotConfiguration, AchievementConfiguration
+};
+use crate::types::{
+    SpamFilter, SpamFilterType, BlacklistPattern, ModerationEscalation, ExemptionLevel,
+    AllowlistEntry, ModerationAllowlist, WildcardPattern
 };
-use crate::types::{SpamFilter, SpamFilterType, BlacklistPattern, ModerationEscalation, ExemptionLevel};
 use crate::bot::moderation::ModerationSystem;
-use crate::bot::pattern_matching::{EnhancedPatternMatcher, AdvancedPattern};
+use crate::bot::pattern_matching::{EnhancedPatternMatcher, AdvancedPattern, KeyboardLayout, CollectionBudget, TimeoutFallback};
 use crate::bot::enhanced_moderation::EnhancedModerationSystem;
+use crate::bot::points::PointsSystem;
+use crate::bot::giveaways::GiveawaySystem;
+use crate::bot::achievements::AchievementSystem;
+use crate::bot::timers::TimerSystem;
+use crate::bot::user_filter::UserFilter;
 
 /// Configuration integration layer that bridges external config files with bot systems
 pub struct ConfigIntegration {
     config_manager: Arc<ConfigurationManager>,
     moderation_system: Arc<ModerationSystem>,
     enhanced_moderation: Option<Arc<EnhancedModerationSystem>>,
+    points_system: Option<Arc<PointsSystem>>,
+    giveaway_system: Option<Arc<GiveawaySystem>>,
+    achievement_system: Option<Arc<AchievementSystem>>,
+    timer_system: Option<Arc<TimerSystem>>,
+    user_filter: Option<Arc<RwLock<UserFilter>>>,
     
     /// Cache for quick lookups
     filter_cache: Arc<RwLock<HashMap<String, SpamFilter>>>,
@@ -49,6 +62,11 @@ impl ConfigIntegration {
             config_manager,
             moderation_system,
             enhanced_moderation: None,
+            points_system: None,
+            giveaway_system: None,
+            achievement_system: None,
+            timer_system: None,
+            user_filter: None,
             filter_cache: Arc::new(RwLock::new(HashMap::new())),
             pattern_cache: Arc::new(RwLock::new(Vec::new())),
             change_handlers: Arc::new(RwLock::new(Vec::new())),
@@ -64,6 +82,31 @@ impl ConfigIntegration {
         self.enhanced_moderation = Some(enhanced_moderation);
     }
 
+    /// Set points system
+    pub fn set_points_system(&mut self, points_system: Arc<PointsSystem>) {
+        self.points_system = Some(points_system);
+    }
+
+    /// Set giveaway system
+    pub fn set_giveaway_system(&mut self, giveaway_system: Arc<GiveawaySystem>) {
+        self.giveaway_system = Some(giveaway_system);
+    }
+
+    /// Set achievement system
+    pub fn set_achievement_system(&mut self, achievement_system: Arc<AchievementSystem>) {
+        self.achievement_system = Some(achievement_system);
+    }
+
+    /// Set timer system
+    pub fn set_timer_system(&mut self, timer_system: Arc<TimerSystem>) {
+        self.timer_system = Some(timer_system);
+    }
+
+    /// Set the bot's user filter handle
+    pub fn set_user_filter(&mut self, user_filter: Arc<RwLock<UserFilter>>) {
+        self.user_filter = Some(user_filter);
+    }
+
     /// Initialize configuration integration
     pub async fn initialize(&self) -> Result<()> {
         info!("Initializing configuration integration...");
@@ -92,26 +135,61 @@ impl ConfigIntegration {
         let timer_config = self.config_manager.get_timer_config().await;
         self.apply_timer_configuration(&timer_config).await?;
 
+        // Load and apply bot configuration
+        let bot_config = self.config_manager.get_bot_config().await;
+        self.apply_bot_configuration(&bot_config).await;
+
+        // Load and apply achievement configuration
+        let achievement_config = self.config_manager.get_achievement_config().await;
+        self.apply_achievement_configuration(&achievement_config).await?;
+
         info!("All configurations loaded and applied");
         Ok(())
     }
 
+    /// Apply bot configuration settings to bot systems
+    async fn apply_bot_configuration(&self, config: &BotConfiguration) {
+        if let Some(points_system) = &self.points_system {
+            points_system.set_level_multipliers(config.points.level_multipliers.clone()).await;
+        }
+        if let Some(giveaway_system) = &self.giveaway_system {
+            giveaway_system.set_fraud_weights(config.giveaway_fraud.clone()).await;
+        }
+        if let Some(user_filter) = &self.user_filter {
+            *user_filter.write().await = config.user_filter.to_user_filter();
+        }
+
+        self.moderation_system.log_redaction().set(config.privacy.redaction.clone());
+        if let Some(enhanced_moderation) = &self.enhanced_moderation {
+            enhanced_moderation.set_redaction_mode(config.privacy.redaction.clone()).await;
+        }
+    }
+
+    /// Apply custom achievements to the achievement system
+    async fn apply_achievement_configuration(&self, config: &AchievementConfiguration) -> Result<()> {
+        let Some(achievement_system) = &self.achievement_system else {
+            return Ok(());
+        };
+
+        let achievements = config.achievements.iter()
+            .map(|definition| definition.to_achievement())
+            .collect::<Result<Vec<_>, _>>()?;
+        achievement_system.set_custom_achievements(achievements).await;
+        Ok(())
+    }
+
     /// Apply filter configuration to moderation system
     async fn apply_filter_configuration(&self, config: &FilterConfiguration) -> Result<()> {
         info!("Applying filter configuration with {} blacklist filters and {} spam filters",
               config.blacklist_filters.len(), config.spam_filters.len());
 
-        // Clear existing filters
-        let existing_filters = self.moderation_system.list_filters().await;
-        for (filter_name, _) in existing_filters {
-            if let Err(e) = self.moderation_system.remove_filter(&filter_name).await {
-                warn!("Failed to remove existing filter '{}': {}", filter_name, e);
-            }
-        }
+        // Build the new filter set on the side and swap it in whole, so messages
+        // checked during a reload see the old set or the new one, never a partial one
+        let staged = ModerationSystem::new();
 
         // Apply blacklist filters
         for filter in &config.blacklist_filters {
-            if let Err(e) = self.apply_blacklist_filter(filter).await {
+            if let Err(e) = Self::apply_blacklist_filter(&staged, filter).await {
                 error!("Failed to apply blacklist filter '{}': {}", filter.id, e);
                 continue;
             }
@@ -119,11 +197,27 @@ impl ConfigIntegration {
 
         // Apply spam filters
         for filter in &config.spam_filters {
-            if let Err(e) = self.apply_spam_filter(filter).await {
+            if let Err(e) = Self::apply_spam_filter(&staged, filter).await {
                 error!("Failed to apply spam filter '{}': {}", filter.id, e);
                 continue;
             }
         }
+        self.moderation_system.replace_filters_from(&staged).await;
+
+        // Apply allowlist of protected phrases
+        self.apply_allowlist(&config.allowlist).await;
+        self.moderation_system.set_warning_dedup_window(config.global_settings.warning_dedup_seconds).await;
+
+        // Filters that ask for context analysis need rolling channel context
+        let wants_context = config.spam_filters.iter()
+            .any(|f| f.enabled && f.ai_enhancement.enabled && f.ai_enhancement.context_analysis);
+        if let (true, Some(enhanced_mod)) = (wants_context, &self.enhanced_moderation) {
+            enhanced_mod.set_context_analysis_enabled(true).await;
+        }
+        if let Some(enhanced_mod) = &self.enhanced_moderation {
+            enhanced_mod.set_max_filters_per_message(config.global_settings.max_filters_per_message as usize).await;
+            enhanced_mod.set_min_action_confidence(config.global_settings.min_ai_action_confidence).await;
+        }
 
         // Update cache
         self.update_filter_cache(config).await;
@@ -132,46 +226,48 @@ impl ConfigIntegration {
         Ok(())
     }
 
-    /// Apply individual blacklist filter
-    async fn apply_blacklist_filter(&self, filter: &EnhancedBlacklistFilter) -> Result<()> {
+    /// Apply allowlist configuration; matching is case-insensitive for all pattern types
+    async fn apply_allowlist(&self, config: &AllowlistConfig) {
+        let mut entries = Vec::new();
+        for entry in &config.entries {
+            let pattern = match entry.pattern_type.as_str() {
+                "literal" => BlacklistPattern::Literal(entry.value.clone()),
+                "wildcard" => BlacklistPattern::Wildcard(WildcardPattern::new(entry.value.as_str())),
+                "regex" => match BlacklistPattern::from_regex_string(&format!("~/{}/i", entry.value)) {
+                    Ok(pattern) => pattern,
+                    Err(e) => {
+                        error!("Invalid allowlist regex '{}': {}", entry.value, e);
+                        continue;
+                    }
+                },
+                _ => {
+                    warn!("Unknown allowlist pattern type '{}', treating as literal", entry.pattern_type);
+                    BlacklistPattern::Literal(entry.value.clone())
+                }
+            };
+
+            entries.push(AllowlistEntry {
+                pattern,
+                channels: entry.channels.clone(),
+            });
+        }
+
+        self.moderation_system.set_allowlist(ModerationAllowlist {
+            entries,
+            bypass_all_filters: config.bypass_all_filters,
+        }).await;
+    }
+
+    /// Add a blacklist filter to `moderation_system`
+    async fn apply_blacklist_filter(moderation_system: &ModerationSystem, filter: &EnhancedBlacklistFilter) -> Result<()> {
         if !filter.enabled {
             debug!("Skipping disabled filter: {}", filter.id);
             return Ok(());
         }
 
         // Convert enhanced patterns to blacklist patterns
-        let mut blacklist_patterns = Vec::new();
-        for pattern_def in &filter.patterns {
-            if !pattern_def.enabled {
-                continue;
-            }
-
-            let blacklist_pattern = match pattern_def.pattern_type.as_str() {
-                "literal" => BlacklistPattern::Literal(pattern_def.value.clone()),
-                "wildcard" => BlacklistPattern::Wildcard(pattern_def.value.clone()),
-                "regex" => {
-                    let regex_pattern = if let Some(flags) = &filter.regex_flags {
-                        format!("~/{}/{}", pattern_def.value, flags)
-                    } else {
-                        format!("~/{}/", pattern_def.value)
-                    };
-                    BlacklistPattern::from_regex_string(&regex_pattern)
-                        .map_err(|e| anyhow::anyhow!("Failed to create regex pattern: {}", e))?
-                }
-                "fuzzy" => {
-                    // For fuzzy patterns, we'll add them to the enhanced moderation system
-                    // For now, treat as literal for basic compatibility
-                    BlacklistPattern::Literal(pattern_def.value.clone())
-                }
-                _ => {
-                    warn!("Unknown pattern type '{}' in filter '{}', treating as literal", 
-                          pattern_def.pattern_type, filter.id);
-                    BlacklistPattern::Literal(pattern_def.value.clone())
-                }
-            };
-
-            blacklist_patterns.push(blacklist_pattern);
-        }
+        let blacklist_patterns = Self::convert_blacklist_patterns(filter, &filter.patterns)?;
+        let except_patterns = Self::convert_blacklist_patterns(filter, &filter.except_patterns)?;
 
         if blacklist_patterns.is_empty() {
             warn!("Filter '{}' has no enabled patterns", filter.id);
@@ -192,33 +288,29 @@ impl ConfigIntegration {
             }
         };
 
-        // Create escalation
-        let escalation = if filter.escalation_enabled {
-            ModerationEscalation {
-                first_offense: crate::types::ModerationAction::WarnUser {
-                    message: filter.custom_message.clone()
-                        .unwrap_or_else(|| format!("Filter '{}' triggered", filter.name))
-                },
-                repeat_offense: crate::types::ModerationAction::TimeoutUser {
-                    duration_seconds: filter.timeout_seconds.unwrap_or(600)
-                },
-                offense_window_seconds: 3600, // 1 hour default
-            }
+        // Escalating filters warn, then time out; others may pin a single action
+        let fixed_action = if filter.escalation_enabled {
+            None
         } else {
-            ModerationEscalation::default()
+            Self::fixed_action(
+                &filter.id,
+                filter.action.as_deref(),
+                filter.timeout_seconds.unwrap_or(600),
+                filter.custom_message.as_deref(),
+            )
         };
 
         // Create patterns vec from the pattern strings
         let pattern_strings: Vec<String> = blacklist_patterns.iter().map(|p| {
             match p {
                 BlacklistPattern::Literal(s) => s.clone(),
-                BlacklistPattern::Wildcard(s) => s.clone(),
+                BlacklistPattern::Wildcard(s) => s.to_string(),
                 BlacklistPattern::Regex { pattern, .. } => pattern.clone(),
             }
         }).collect();
 
         // Add the blacklist filter to moderation system
-        self.moderation_system.add_blacklist_filter(
+        moderation_system.add_blacklist_filter(
             filter.id.clone(),
             pattern_strings,
             filter.case_sensitive,
@@ -227,13 +319,80 @@ impl ConfigIntegration {
             filter.timeout_seconds.unwrap_or(600),
             filter.custom_message.clone(),
         ).await?;
+        if !except_patterns.is_empty() {
+            moderation_system.set_blacklist_exceptions(&filter.id, except_patterns).await?;
+        }
+        if let Some(action) = fixed_action {
+            moderation_system.set_filter_escalation(&filter.id, ModerationEscalation::fixed(action)).await?;
+        }
+        if filter.grace_seconds.is_some() {
+            moderation_system.set_filter_grace(&filter.id, filter.grace_seconds).await?;
+        }
+        if filter.log_level.is_some() {
+            moderation_system.set_filter_log_level(&filter.id, filter.log_level).await?;
+        }
+        let schedule = filter.schedule()?;
+        if schedule.is_some() {
+            moderation_system.set_filter_schedule(&filter.id, schedule).await?;
+        }
 
         debug!("Applied blacklist filter: {}", filter.id);
         Ok(())
     }
 
-    /// Apply individual spam filter
-    async fn apply_spam_filter(&self, filter: &EnhancedSpamFilter) -> Result<()> {
+    /// The configured fixed action for a non-escalating filter, if any
+    fn fixed_action(filter_id: &str, action: Option<&str>, timeout_seconds: u64, custom_message: Option<&str>) -> Option<crate::types::ModerationAction> {
+        let name = action?;
+        let parsed = crate::types::ModerationAction::from_config(
+            name,
+            timeout_seconds,
+            custom_message.unwrap_or("Please follow chat rules"),
+        );
+        if parsed.is_none() {
+            warn!("Unknown action '{}' in filter '{}', expected delete, warn, timeout, shadow_mute or log_only", name, filter_id);
+        }
+        parsed
+    }
+
+    /// Convert a filter's enabled pattern definitions to blacklist patterns
+    fn convert_blacklist_patterns(filter: &EnhancedBlacklistFilter, definitions: &[PatternDefinition]) -> Result<Vec<BlacklistPattern>> {
+        let mut blacklist_patterns = Vec::new();
+        for pattern_def in definitions {
+            if !pattern_def.enabled {
+                continue;
+            }
+
+            let blacklist_pattern = match pattern_def.pattern_type.as_str() {
+                "literal" => BlacklistPattern::Literal(pattern_def.value.clone()),
+                "wildcard" => BlacklistPattern::wildcard(&pattern_def.value, pattern_def.anchor),
+                "regex" => {
+                    let regex_pattern = if let Some(flags) = &filter.regex_flags {
+                        format!("~/{}/{}", pattern_def.value, flags)
+                    } else {
+                        format!("~/{}/", pattern_def.value)
+                    };
+                    BlacklistPattern::from_regex_string(&regex_pattern)
+                        .map_err(|e| anyhow::anyhow!("Failed to create regex pattern: {}", e))?
+                }
+                "fuzzy" => {
+                    // For fuzzy patterns, we'll add them to the enhanced moderation system
+                    // For now, treat as literal for basic compatibility
+                    BlacklistPattern::Literal(pattern_def.value.clone())
+                }
+                _ => {
+                    warn!("Unknown pattern type '{}' in filter '{}', treating as literal", 
+                          pattern_def.pattern_type, filter.id);
+                    BlacklistPattern::Literal(pattern_def.value.clone())
+                }
+            };
+
+            blacklist_patterns.push(blacklist_pattern);
+        }
+        Ok(blacklist_patterns)
+    }
+
+    /// Add a spam filter to `moderation_system`
+    async fn apply_spam_filter(moderation_system: &ModerationSystem, filter: &EnhancedSpamFilter) -> Result<()> {
         if !filter.enabled {
             debug!("Skipping disabled spam filter: {}", filter.id);
             return Ok(());
@@ -272,7 +431,10 @@ impl ConfigIntegration {
                 let max_count = filter.parameters.get("max_count")
                     .and_then(|v| v.as_u64())
                     .unwrap_or(10) as u8;
-                SpamFilterType::ExcessiveEmotes { max_count }
+                let count_unique = filter.parameters.get("count_unique")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false);
+                SpamFilterType::ExcessiveEmotes { max_count, count_unique }
             }
             "LinkBlocking" => {
                 let allow_mods = filter.parameters.get("allow_mods")
@@ -309,29 +471,42 @@ impl ConfigIntegration {
             _ => ExemptionLevel::Regular,
         };
 
+        let fixed_action = if filter.escalation.enabled {
+            None
+        } else {
+            Self::fixed_action(&filter.id, filter.action.as_deref(), filter.timeout_seconds, filter.custom_message.as_deref())
+        };
+
         // Create escalation from configuration
-        let escalation = ModerationEscalation {
-            first_offense: match filter.escalation.first_offense_action.as_str() {
-                "warn" => crate::types::ModerationAction::WarnUser {
-                    message: filter.custom_message.clone()
-                        .unwrap_or_else(|| "Please follow chat rules".to_string())
+        let escalation = if let Some(action) = fixed_action {
+            ModerationEscalation::fixed(action)
+        } else {
+            ModerationEscalation {
+                first_offense: match filter.escalation.first_offense_action.as_str() {
+                    "warn" => crate::types::ModerationAction::WarnUser {
+                        message: filter.custom_message.clone()
+                            .unwrap_or_else(|| "Please follow chat rules".to_string())
+                    },
+                    "timeout" => crate::types::ModerationAction::TimeoutUser {
+                        duration_seconds: filter.timeout_seconds
+                    },
+                    "delete" => crate::types::ModerationAction::DeleteMessage,
+                    "shadow_mute" => crate::types::ModerationAction::ShadowMute {
+                        duration_seconds: filter.timeout_seconds
+                    },
+                    _ => crate::types::ModerationAction::WarnUser {
+                        message: "Please follow chat rules".to_string()
+                    }
                 },
-                "timeout" => crate::types::ModerationAction::TimeoutUser {
+                repeat_offense: crate::types::ModerationAction::TimeoutUser {
                     duration_seconds: filter.timeout_seconds
                 },
-                "delete" => crate::types::ModerationAction::DeleteMessage,
-                _ => crate::types::ModerationAction::WarnUser {
-                    message: "Please follow chat rules".to_string()
-                }
-            },
-            repeat_offense: crate::types::ModerationAction::TimeoutUser {
-                duration_seconds: filter.timeout_seconds
-            },
-            offense_window_seconds: filter.escalation.offense_window_seconds,
+                offense_window_seconds: filter.escalation.offense_window_seconds,
+            }
         };
 
         // Add spam filter to moderation system
-        self.moderation_system.add_spam_filter_advanced(
+        moderation_system.add_spam_filter_advanced(
             filter.id.clone(),
             filter_type,
             escalation,
@@ -339,6 +514,12 @@ impl ConfigIntegration {
             filter.silent_mode,
             filter.custom_message.clone(),
         ).await?;
+        if filter.grace_seconds.is_some() {
+            moderation_system.set_filter_grace(&filter.id, filter.grace_seconds).await?;
+        }
+        if filter.log_level.is_some() {
+            moderation_system.set_filter_log_level(&filter.id, filter.log_level).await?;
+        }
 
         debug!("Applied spam filter: {}", filter.id);
         Ok(())
@@ -350,14 +531,27 @@ impl ConfigIntegration {
             info!("Applying pattern configuration with {} collections",
                   config.pattern_collections.len());
 
+            // Build the new pattern set on the side and swap it in whole, so a
+            // reload never duplicates patterns or exposes a half-loaded set
             let mut all_patterns = Vec::new();
+            let mut staged = EnhancedPatternMatcher::new();
+            staged.set_min_message_length(config.global_settings.min_message_length);
 
             for (collection_id, collection) in &config.pattern_collections {
                 if !collection.enabled {
-                    debug!("Skipping disabled pattern collection: {}", collection_id);
-                    continue;
+                    debug!("Loading disabled pattern collection: {} (enable with !patterns {} on)", collection_id, collection_id);
                 }
 
+                let fallback = TimeoutFallback::parse(&collection.timeout_action).unwrap_or_else(|| {
+                    warn!("Unknown timeout action '{}' for collection '{}', failing open",
+                          collection.timeout_action, collection_id);
+                    TimeoutFallback::FailOpen
+                });
+                staged.set_collection_budget(collection_id, CollectionBudget {
+                    max_processing_time: std::time::Duration::from_millis(collection.max_processing_time_ms as u64),
+                    fallback,
+                });
+
                 for pattern_def in &collection.patterns {
                     if !pattern_def.enabled {
                         continue;
@@ -365,16 +559,21 @@ impl ConfigIntegration {
 
                     let advanced_pattern = self.convert_pattern_definition(pattern_def)?;
                     if let Some(pattern) = advanced_pattern {
+                        staged.add_configured_pattern(collection_id, &pattern_def.id, pattern.clone(), pattern_def.min_message_length);
+                        if let Some(reputation_scaling) = pattern_def.reputation_scaling {
+                            staged.set_reputation_scaling(&pattern_def.id, reputation_scaling);
+                        }
                         all_patterns.push(pattern);
                     }
                 }
+                // Disabled collections are loaded so they can be switched on live
+                staged.set_collection_enabled(collection_id, collection.enabled);
             }
+            enhanced_mod.replace_pattern_matcher(staged).await;
 
-            // Add all patterns to enhanced moderation
-            for pattern in &all_patterns {
-                if let Err(e) = enhanced_mod.add_advanced_pattern(pattern.clone()).await {
-                    error!("Failed to add advanced pattern: {}", e);
-                }
+            // Context-aware patterns need rolling channel context
+            if all_patterns.iter().any(AdvancedPattern::uses_context) {
+                enhanced_mod.set_context_analysis_enabled(true).await;
             }
 
             let pattern_length = all_patterns.len();
@@ -451,6 +650,49 @@ impl ConfigIntegration {
 
                 AdvancedPattern::Phonetic(pattern_value.to_string())
             }
+            "contextual_reply" => {
+                let pattern_value = pattern_def.parameters.get("pattern")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| anyhow::anyhow!("Missing 'pattern' parameter for contextual reply"))?;
+                let max_reply_words = pattern_def.parameters.get("max_reply_words")
+                    .and_then(|v| v.as_u64())
+                    .unwrap_or(4) as usize;
+
+                AdvancedPattern::ContextualReply {
+                    context_pattern: pattern_value.to_string(),
+                    max_reply_words,
+                }
+            }
+            "keyboard_shift" => {
+                let pattern_value = pattern_def.parameters.get("pattern")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| anyhow::anyhow!("Missing 'pattern' parameter for keyboard shift"))?;
+
+                // Accept either a single "layout" or a "layouts" list; defaults to QWERTY
+                let layout_names: Vec<&str> = match pattern_def.parameters.get("layouts").and_then(|v| v.as_array()) {
+                    Some(list) => list.iter().filter_map(|v| v.as_str()).collect(),
+                    None => pattern_def.parameters.get("layout")
+                        .and_then(|v| v.as_str())
+                        .into_iter()
+                        .collect(),
+                };
+
+                let mut layouts = Vec::new();
+                for name in layout_names {
+                    match KeyboardLayout::parse(name) {
+                        Some(layout) => layouts.push(layout),
+                        None => warn!("Unknown keyboard layout '{}' in pattern '{}', ignoring", name, pattern_def.id),
+                    }
+                }
+                if layouts.is_empty() {
+                    layouts.push(KeyboardLayout::Qwerty);
+                }
+
+                AdvancedPattern::KeyboardShift {
+                    pattern: pattern_value.to_string(),
+                    layouts,
+                }
+            }
             _ => {
                 warn!("Unknown pattern type '{}', skipping", pattern_def.pattern_type);
                 return Ok(None);
@@ -460,19 +702,31 @@ impl ConfigIntegration {
         Ok(Some(pattern))
     }
 
-    /// Apply timer configuration
+    /// Apply timer configuration: each enabled timer's time windows, day
+    /// restrictions and random offset apply to the timer of the same name (its `id`)
     async fn apply_timer_configuration(&self, config: &TimerConfiguration) -> Result<()> {
         info!("Timer configuration loaded with {} timers", config.timers.len());
-        
-        // Timer application would depend on your timer system implementation
-        // For now, we'll just log the configuration
+
+        let mut schedules = HashMap::new();
+        let mut random_offsets = HashMap::new();
         for timer in &config.timers {
             if timer.enabled {
                 debug!("Timer '{}' configured with {} messages, interval: {}s",
                        timer.name, timer.messages.len(), timer.schedule.interval_seconds);
+                if let Some(schedule) = timer.active_schedule()? {
+                    schedules.insert(timer.id.clone(), schedule);
+                }
+                if let Some(offset) = timer.schedule.random_offset_max {
+                    random_offsets.insert(timer.id.clone(), offset);
+                }
             }
         }
 
+        if let Some(timer_system) = &self.timer_system {
+            timer_system.set_schedules(schedules).await;
+            timer_system.set_random_offsets(random_offsets).await;
+        }
+
         Ok(())
     }
 
@@ -520,7 +774,13 @@ impl ConfigIntegration {
             }
             ConfigChangeEvent::BotConfigUpdated { file } => {
                 info!("Bot configuration updated in file: {}", file);
-                // Handle bot configuration changes
+                let bot_config = self.config_manager.get_bot_config().await;
+                self.apply_bot_configuration(&bot_config).await;
+            }
+            ConfigChangeEvent::AchievementsUpdated { file } => {
+                info!("Achievements updated in file: {}", file);
+                let achievement_config = self.config_manager.get_achievement_config().await;
+                self.apply_achievement_configuration(&achievement_config).await?;
             }
             ConfigChangeEvent::ValidationError { file, error } => {
                 error!("Configuration validation error in {}: {}", file, error);
@@ -549,6 +809,7 @@ impl ConfigIntegration {
                     patterns: Vec::new(), // Simplified for cache
                     case_sensitive: filter.case_sensitive,
                     whole_words_only: filter.whole_words_only,
+                    except_patterns: Vec::new(),
                 },
                 enabled: filter.enabled,
                 escalation: ModerationEscalation::default(),
@@ -556,6 +817,9 @@ impl ConfigIntegration {
                 silent_mode: filter.silent_mode,
                 custom_message: filter.custom_message.clone(),
                 name: filter.name.clone(),
+                grace_seconds: filter.grace_seconds,
+                log_level: filter.log_level,
+                schedule: filter.schedule().ok().flatten(),
             };
 
             cache.insert(filter.id.clone(), spam_filter);
@@ -594,6 +858,14 @@ impl ConfigIntegration {
                 let timer_config = self.config_manager.get_timer_config().await;
                 self.apply_timer_configuration(&timer_config).await?;
             }
+            ConfigType::Bot => {
+                let bot_config = self.config_manager.get_bot_config().await;
+                self.apply_bot_configuration(&bot_config).await;
+            }
+            ConfigType::Achievements => {
+                let achievement_config = self.config_manager.get_achievement_config().await;
+                self.apply_achievement_configuration(&achievement_config).await?;
+            }
             ConfigType::All => {
                 self.load_all_configurations().await?;
             }
@@ -605,7 +877,7 @@ impl ConfigIntegration {
 
     /// Export current configuration to file
     pub async fn export_current_config(&self, format: &str, output_path: &std::path::Path) -> Result<()> {
-        self.config_manager.export_config(format, output_path).await
+        Ok(self.config_manager.export_config(format, output_path).await?)
     }
 
     /// Import configuration from file
@@ -627,18 +899,18 @@ impl ConfigIntegration {
     }
 
     /// Get configuration statistics
-    pub async fn get_config_stats(&self) -> crate::config::ConfigStats {
-        self.config_manager.get_config_stats().await
+    pub async fn get_config_status(&self) -> crate::config::ConfigStatusSummary {
+        self.config_manager.get_config_status().await
     }
 
     /// Validate all configurations
     pub async fn validate_configurations(&self) -> Result<crate::config::ValidationReport> {
-        self.config_manager.validate_all_configs().await
+        Ok(self.config_manager.validate_all_configs().await?)
     }
 
     /// Create configuration backup
     pub async fn create_backup(&self) -> Result<std::path::PathBuf> {
-        self.config_manager.create_backup().await
+        Ok(self.config_manager.create_backup().await?)
     }
 }
 
@@ -649,6 +921,11 @@ impl Clone for ConfigIntegration {
             config_manager: Arc::clone(&self.config_manager),
             moderation_system: Arc::clone(&self.moderation_system),
             enhanced_moderation: self.enhanced_moderation.as_ref().map(Arc::clone),
+            points_system: self.points_system.as_ref().map(Arc::clone),
+            giveaway_system: self.giveaway_system.as_ref().map(Arc::clone),
+            achievement_system: self.achievement_system.as_ref().map(Arc::clone),
+            timer_system: self.timer_system.as_ref().map(Arc::clone),
+            user_filter: self.user_filter.as_ref().map(Arc::clone),
             filter_cache: Arc::clone(&self.filter_cache),
             pattern_cache: Arc::clone(&self.pattern_cache),
             change_handlers: Arc::clone(&self.change_handlers),
@@ -662,6 +939,8 @@ pub enum ConfigType {
     Filters,
     Patterns,
     Timers,
+    Bot,
+    Achievements,
     All,
 }
 
@@ -709,6 +988,8 @@ impl ConfigCommands {
             Some("filters") => ConfigType::Filters,
             Some("patterns") => ConfigType::Patterns,
             Some("timers") => ConfigType::Timers,
+            Some("bot") => ConfigType::Bot,
+            Some("achievements") => ConfigType::Achievements,
             _ => ConfigType::All,
         };
 
@@ -718,18 +999,7 @@ impl ConfigCommands {
 
     /// Handle status command
     pub async fn handle_status_command(&self) -> Result<String> {
-        let stats = self.integration.get_config_stats().await;
-        
-        Ok(format!(
-            "📊 Config Status: {} filters ({} enabled), {} patterns ({} enabled), {} timers ({} enabled), {} categories",
-            stats.total_blacklist_filters,
-            stats.enabled_blacklist_filters,
-            stats.total_pattern_collections,
-            stats.enabled_pattern_collections,
-            stats.total_timers,
-            stats.enabled_timers,
-            stats.categories
-        ))
+        Ok(self.integration.get_config_status().await.to_string())
     }
 
     /// Handle validate command
@@ -766,6 +1036,7 @@ mod tests {
     use super::*;
     use tempfile::tempdir;
     use crate::bot::moderation::ModerationSystem;
+    use crate::bot::giveaway_fraud::FraudWeights;
 
     #[tokio::test]
     async fn test_config_integration_initialization() {
@@ -821,4 +1092,504 @@ mod tests {
         let reload = commands.handle_reload_command(Some("filters")).await.unwrap();
         assert!(reload.contains("Successfully reloaded"));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_keyboard_shift_pattern_definition() {
+        let temp_dir = tempdir().unwrap();
+        let config_manager = Arc::new(ConfigurationManager::new(temp_dir.path()));
+        let integration = ConfigIntegration::new(config_manager, Arc::new(ModerationSystem::new()));
+
+        let pattern_def = crate::config::AdvancedPatternDefinition {
+            id: "shifted_badword".to_string(),
+            name: "Shifted badword".to_string(),
+            enabled: true,
+            pattern_type: "keyboard_shift".to_string(),
+            parameters: serde_json::json!({
+                "pattern": "badword",
+                "layouts": ["qwerty", "azerty"]
+            }),
+            weight: 1.0,
+            min_confidence: 0.7,
+            learning_rate: 0.1,
+            tags: vec![],
+            min_message_length: None,
+            reputation_scaling: None,
+        };
+
+        let pattern = integration.convert_pattern_definition(&pattern_def).unwrap().unwrap();
+        match &pattern {
+            AdvancedPattern::KeyboardShift { layouts, .. } => {
+                assert_eq!(layouts, &vec![KeyboardLayout::Qwerty, KeyboardLayout::Azerty]);
+            }
+            other => panic!("Expected keyboard shift pattern, got {:?}", other),
+        }
+        assert!(pattern.matches("nsfeptf"));
+    }
+
+    #[tokio::test]
+    async fn test_allowlist_hot_reload() {
+        let temp_dir = tempdir().unwrap();
+        let config_manager = Arc::new(ConfigurationManager::new(temp_dir.path()));
+        let moderation_system = Arc::new(ModerationSystem::new());
+        config_manager.initialize().await.unwrap();
+
+        let integration = ConfigIntegration::new(config_manager.clone(), moderation_system.clone());
+        integration.initialize().await.unwrap();
+        assert!(moderation_system.allowlist.read().await.entries.is_empty());
+
+        let mut filter_config = config_manager.get_filter_config().await;
+        filter_config.allowlist.entries.push(crate::config::AllowlistEntryConfig {
+            value: "spam\\s+simulator".to_string(),
+            pattern_type: "regex".to_string(),
+            channels: vec![],
+        });
+        config_manager.save_filter_config(filter_config).await.unwrap();
+        integration.reload_configuration(ConfigType::Filters).await.unwrap();
+
+        let allowlist = moderation_system.allowlist.read().await;
+        assert_eq!(allowlist.entries.len(), 1);
+        let message = crate::types::ChatMessage::new("twitch", "testchannel", "viewer", "Spam  Simulator speedrun");
+        assert!(allowlist.is_allowed(&message));
+    }
+
+    #[tokio::test]
+    async fn test_blacklist_exception_patterns() {
+        let temp_dir = tempdir().unwrap();
+        let config_manager = Arc::new(ConfigurationManager::new(temp_dir.path()));
+        let moderation_system = Arc::new(ModerationSystem::new());
+        config_manager.initialize().await.unwrap();
+
+        let integration = ConfigIntegration::new(config_manager.clone(), moderation_system.clone());
+        integration.initialize().await.unwrap();
+
+        let pattern = |pattern_type: &str, value: &str| PatternDefinition {
+            pattern_type: pattern_type.to_string(),
+            value: value.to_string(),
+            weight: 1.0,
+            description: None,
+            enabled: true,
+            anchor: crate::types::WildcardAnchor::Contains,
+        };
+        let mut filter_config = config_manager.get_filter_config().await;
+        let mut urls = filter_config.blacklist_filters[0].clone();
+        urls.id = "urls".to_string();
+        urls.exemption_level = "None".to_string();
+        urls.patterns = vec![pattern("regex", r"https?://\S+")];
+        urls.except_patterns = vec![pattern("wildcard", "youtube.com/*"), pattern("literal", "clips.twitch.tv")];
+        filter_config.blacklist_filters = vec![urls];
+        config_manager.save_filter_config(filter_config).await.unwrap();
+        integration.reload_configuration(ConfigType::Filters).await.unwrap();
+
+        let message = |content: &str| crate::types::ChatMessage::new("twitch", "testchannel", "viewer", content);
+        assert!(moderation_system.check_spam_filters(&message("go to https://scam.example now"), None).await.is_some());
+        // An exception rescues a link the block pattern would catch
+        assert!(moderation_system.check_spam_filters(&message("watch https://youtube.com/watch?v=abc"), None).await.is_none());
+        assert!(moderation_system.check_spam_filters(&message("lol https://clips.twitch.tv/xyz"), None).await.is_none());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_filter_reload_swaps_the_whole_set_at_once() {
+        let temp_dir = tempdir().unwrap();
+        let config_manager = Arc::new(ConfigurationManager::new(temp_dir.path()));
+        let moderation_system = Arc::new(ModerationSystem::new());
+        config_manager.initialize().await.unwrap();
+
+        let integration = ConfigIntegration::new(config_manager.clone(), moderation_system.clone());
+        integration.initialize().await.unwrap();
+
+        let filter_names = |moderation_system: Arc<ModerationSystem>| async move {
+            let mut names: Vec<String> = moderation_system.list_filters().await.into_iter().map(|(name, _)| name).collect();
+            names.sort();
+            names
+        };
+        let old_config = config_manager.get_filter_config().await;
+        let old_names = filter_names(moderation_system.clone()).await;
+        assert!(old_names.len() > 1);
+
+        let mut new_config = old_config.clone();
+        let mut skins = new_config.blacklist_filters[0].clone();
+        skins.id = "skin_scams".to_string();
+        skins.exemption_level = "None".to_string();
+        skins.patterns = vec![PatternDefinition {
+            pattern_type: "literal".to_string(),
+            value: "free-skins".to_string(),
+            weight: 1.0,
+            description: None,
+            enabled: true,
+            anchor: crate::types::WildcardAnchor::Contains,
+        }];
+        new_config.blacklist_filters.push(skins);
+        let mut new_names = old_names.clone();
+        new_names.push("skin_scams".to_string());
+        new_names.sort();
+
+        // Watch the live set while it's reloaded over and over
+        let stop = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let watcher = tokio::spawn({
+            let (moderation_system, stop) = (moderation_system.clone(), stop.clone());
+            let (old_names, new_names) = (old_names.clone(), new_names.clone());
+            async move {
+                let mut observed = 0;
+                while !stop.load(std::sync::atomic::Ordering::Relaxed) {
+                    let names = filter_names(moderation_system.clone()).await;
+                    assert!(names == old_names || names == new_names, "saw a partial filter set: {:?}", names);
+                    observed += 1;
+                    tokio::task::yield_now().await;
+                }
+                observed
+            }
+        });
+        for round in 0..50 {
+            let config = if round % 2 == 0 { &new_config } else { &old_config };
+            integration.apply_filter_configuration(config).await.unwrap();
+        }
+        stop.store(true, std::sync::atomic::Ordering::Relaxed);
+        assert!(watcher.await.unwrap() > 0);
+
+        // Each reload leaves exactly the set it was given live
+        assert_eq!(filter_names(moderation_system.clone()).await, old_names);
+        integration.apply_filter_configuration(&new_config).await.unwrap();
+        assert_eq!(filter_names(moderation_system.clone()).await, new_names);
+        let message = crate::types::ChatMessage::new("twitch", "testchannel", "viewer", "get free-skins here");
+        assert_eq!(moderation_system.evaluate_spam_filters(&message, None).await.unwrap().filter_name, "skin_scams");
+    }
+
+    #[tokio::test]
+    async fn test_pattern_reload_replaces_the_loaded_patterns() {
+        let temp_dir = tempdir().unwrap();
+        let config_manager = Arc::new(ConfigurationManager::new(temp_dir.path()));
+        let moderation_system = Arc::new(ModerationSystem::new());
+        config_manager.initialize().await.unwrap();
+
+        let enhanced = Arc::new(EnhancedModerationSystem::new(moderation_system.clone()));
+        let mut integration = ConfigIntegration::new(config_manager.clone(), moderation_system);
+        integration.set_enhanced_moderation(enhanced.clone());
+        integration.initialize().await.unwrap();
+
+        let loaded = enhanced.get_system_status().await.total_patterns;
+        assert!(loaded > 0);
+        let collections = enhanced.get_pattern_collections().await;
+
+        let pattern_config = config_manager.get_pattern_config().await;
+        for _ in 0..3 {
+            integration.apply_pattern_configuration(&pattern_config).await.unwrap();
+        }
+        assert_eq!(enhanced.get_system_status().await.total_patterns, loaded);
+        assert_eq!(enhanced.get_pattern_collections().await, collections);
+    }
+
+    #[tokio::test]
+    async fn test_fixed_filter_actions() {
+        use crate::types::ModerationAction;
+
+        let temp_dir = tempdir().unwrap();
+        let config_manager = Arc::new(ConfigurationManager::new(temp_dir.path()));
+        let moderation_system = Arc::new(ModerationSystem::new());
+        config_manager.initialize().await.unwrap();
+        moderation_system.set_action_cooldown(0).await;
+
+        let integration = ConfigIntegration::new(config_manager.clone(), moderation_system.clone());
+        integration.initialize().await.unwrap();
+
+        let mut filter_config = config_manager.get_filter_config().await;
+        let template = filter_config.blacklist_filters[0].clone();
+        filter_config.blacklist_filters = ["delete", "warn", "timeout", "shadow_mute", "log_only"].iter().map(|action| {
+            let mut filter = template.clone();
+            filter.id = format!("{}_filter", action);
+            filter.exemption_level = "None".to_string();
+            filter.patterns[0].pattern_type = "literal".to_string();
+            filter.patterns[0].value = format!("{}word", action);
+            filter.patterns.truncate(1);
+            filter.custom_message = None;
+            filter.timeout_seconds = Some(42);
+            filter.escalation_enabled = false;
+            filter.action = Some(action.to_string());
+            filter
+        }).collect();
+        let mut caps = filter_config.spam_filters.iter().find(|f| f.filter_type == "ExcessiveCaps").unwrap().clone();
+        caps.exemption_level = "None".to_string();
+        caps.escalation.enabled = false;
+        caps.action = Some("delete".to_string());
+        filter_config.spam_filters = vec![caps];
+        // Warn every time so repeat warnings aren't deduplicated
+        filter_config.global_settings.warning_dedup_seconds = 0;
+        config_manager.save_filter_config(filter_config).await.unwrap();
+        integration.reload_configuration(ConfigType::Filters).await.unwrap();
+
+        // Repeat offenses get the same action as the first
+        for _ in 0..2 {
+            let check = |content: &str| {
+                let message = crate::types::ChatMessage::new("twitch", "testchannel", "viewer", content);
+                let moderation_system = moderation_system.clone();
+                async move { moderation_system.check_spam_filters(&message, None).await }
+            };
+            assert!(matches!(check("deleteword").await, Some(ModerationAction::DeleteMessage)));
+            assert!(matches!(check("warnword").await, Some(ModerationAction::WarnUser { .. })));
+            assert!(matches!(check("timeoutword").await, Some(ModerationAction::TimeoutUser { duration_seconds: 42 })));
+            assert!(matches!(check("shadow_muteword").await, Some(ModerationAction::ShadowMute { duration_seconds: 42 })));
+            assert!(matches!(check("log_onlyword").await, Some(ModerationAction::LogOnly)));
+            assert!(matches!(check("STOP SHOUTING AT ME").await, Some(ModerationAction::DeleteMessage)));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_points_multipliers_hot_reload() {
+        let temp_dir = tempdir().unwrap();
+        let config_manager = Arc::new(ConfigurationManager::new(temp_dir.path()));
+        config_manager.initialize().await.unwrap();
+
+        let points_system = Arc::new(PointsSystem::new());
+        let mut integration = ConfigIntegration::new(config_manager.clone(), Arc::new(ModerationSystem::new()));
+        integration.set_points_system(points_system.clone());
+        integration.initialize().await.unwrap();
+        assert_eq!(points_system.get_level_multipliers().await.get(&crate::types::UserLevel::Subscriber), Some(&1.5));
+
+        let mut bot_config = config_manager.get_bot_config().await;
+        bot_config.points.level_multipliers.insert(crate::types::UserLevel::Subscriber, 3.0);
+        config_manager.save_bot_config(bot_config).await.unwrap();
+        integration.reload_configuration(ConfigType::Bot).await.unwrap();
+
+        assert_eq!(points_system.get_level_multipliers().await.get(&crate::types::UserLevel::Subscriber), Some(&3.0));
+    }
+
+    #[tokio::test]
+    async fn test_giveaway_fraud_weights_come_from_the_bot_config() {
+        let temp_dir = tempdir().unwrap();
+        let config_manager = Arc::new(ConfigurationManager::new(temp_dir.path()));
+        config_manager.initialize().await.unwrap();
+
+        let giveaway_system = Arc::new(GiveawaySystem::new());
+        let mut integration = ConfigIntegration::new(config_manager.clone(), Arc::new(ModerationSystem::new()));
+        integration.set_giveaway_system(giveaway_system.clone());
+        integration.initialize().await.unwrap();
+        assert_eq!(giveaway_system.fraud_weights().await, FraudWeights::default());
+
+        let mut bot_config = config_manager.get_bot_config().await;
+        bot_config.giveaway_fraud.account_age = 0.9;
+        config_manager.save_bot_config(bot_config).await.unwrap();
+        integration.reload_configuration(ConfigType::Bot).await.unwrap();
+
+        assert_eq!(giveaway_system.fraud_weights().await.account_age, 0.9);
+    }
+
+    #[tokio::test]
+    async fn test_timer_schedules_come_from_the_timer_config() {
+        let temp_dir = tempdir().unwrap();
+        let config_manager = Arc::new(ConfigurationManager::new(temp_dir.path()));
+        config_manager.initialize().await.unwrap();
+
+        let timer_system = Arc::new(TimerSystem::new());
+        timer_system.add_timer("ai_features".to_string(), "Protected by NotaBot".to_string(), 600).await.unwrap();
+        timer_system.add_timer("other".to_string(), "Hello".to_string(), 600).await.unwrap();
+        let mut integration = ConfigIntegration::new(config_manager.clone(), Arc::new(ModerationSystem::new()));
+        integration.set_timer_system(timer_system.clone());
+        integration.initialize().await.unwrap();
+        assert!(timer_system.timers.read().await["ai_features"].schedule.is_none());
+
+        let mut timer_config = config_manager.get_timer_config().await;
+        let timer = timer_config.timers.iter_mut().find(|t| t.id == "ai_features").unwrap();
+        timer.schedule.time_windows = vec![crate::config::TimeRange {
+            start: "12:00".to_string(),
+            end: "23:00".to_string(),
+            timezone: Some("Europe/London".to_string()),
+        }];
+        integration.apply_timer_configuration(&timer_config).await.unwrap();
+
+        // London is on UTC until the end of March
+        let at = |hour| chrono::TimeZone::with_ymd_and_hms(&chrono::Utc, 2026, 3, 10, hour, 0, 0).unwrap();
+        {
+            let timers = timer_system.timers.read().await;
+            let schedule = timers["ai_features"].schedule.as_ref().unwrap();
+            assert!(schedule.is_active(at(12)));
+            assert!(!schedule.is_active(at(8)));
+            assert!(timers["other"].schedule.is_none());
+        }
+
+        let timer = timer_config.timers.iter_mut().find(|t| t.id == "ai_features").unwrap();
+        timer.schedule.day_restrictions = vec!["Caturday".to_string()];
+        assert!(integration.apply_timer_configuration(&timer_config).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_timer_random_offsets_come_from_the_timer_config() {
+        let temp_dir = tempdir().unwrap();
+        let config_manager = Arc::new(ConfigurationManager::new(temp_dir.path()));
+        config_manager.initialize().await.unwrap();
+
+        let timer_system = Arc::new(TimerSystem::new());
+        timer_system.add_timer("ai_features".to_string(), "Protected by NotaBot".to_string(), 600).await.unwrap();
+        timer_system.add_timer("other".to_string(), "Hello".to_string(), 600).await.unwrap();
+        let mut integration = ConfigIntegration::new(config_manager.clone(), Arc::new(ModerationSystem::new()));
+        integration.set_timer_system(timer_system.clone());
+        integration.initialize().await.unwrap();
+
+        let mut timer_config = config_manager.get_timer_config().await;
+        let timer = timer_config.timers.iter_mut().find(|t| t.id == "ai_features").unwrap();
+        timer.schedule.random_offset_max = Some(120);
+        integration.apply_timer_configuration(&timer_config).await.unwrap();
+
+        let timers = timer_system.timers.read().await;
+        assert_eq!(timers["ai_features"].random_offset_max, Some(120));
+        assert!((480..=720).contains(&timers["ai_features"].next_interval_seconds));
+        assert_eq!(timers["other"].random_offset_max, None);
+        assert_eq!(timers["other"].next_interval_seconds, 600);
+    }
+
+    #[tokio::test]
+    async fn test_user_filter_hot_reload() {
+        let temp_dir = tempdir().unwrap();
+        let config_manager = Arc::new(ConfigurationManager::new(temp_dir.path()));
+        config_manager.initialize().await.unwrap();
+
+        let user_filter = Arc::new(RwLock::new(UserFilter::default()));
+        let mut integration = ConfigIntegration::new(config_manager.clone(), Arc::new(ModerationSystem::new()));
+        integration.set_user_filter(user_filter.clone());
+        integration.initialize().await.unwrap();
+        assert!(user_filter.read().await.is_allowed("twitch", "altbot"));
+
+        let mut bot_config = config_manager.get_bot_config().await;
+        bot_config.user_filter.ignored_users = vec!["AltBot".to_string()];
+        config_manager.save_bot_config(bot_config.clone()).await.unwrap();
+        integration.reload_configuration(ConfigType::Bot).await.unwrap();
+        assert!(!user_filter.read().await.is_allowed("twitch", "altbot"));
+        assert!(user_filter.read().await.is_allowed("twitch", "viewer"));
+
+        // Allowlist mode without any allowed users would ignore everyone
+        bot_config.user_filter.allowlist_mode = true;
+        assert!(config_manager.save_bot_config(bot_config.clone()).await.is_err());
+
+        bot_config.user_filter.allowed_users = vec!["tester".to_string()];
+        config_manager.save_bot_config(bot_config).await.unwrap();
+        integration.reload_configuration(ConfigType::Bot).await.unwrap();
+        assert!(user_filter.read().await.is_allowed("twitch", "tester"));
+        assert!(!user_filter.read().await.is_allowed("twitch", "viewer"));
+    }
+
+    #[tokio::test]
+    async fn test_custom_achievement_from_config() {
+        let temp_dir = tempdir().unwrap();
+        let config_manager = Arc::new(ConfigurationManager::new(temp_dir.path()));
+        config_manager.initialize().await.unwrap();
+
+        let achievement_system = Arc::new(AchievementSystem::new());
+        let mut integration = ConfigIntegration::new(config_manager.clone(), Arc::new(ModerationSystem::new()));
+        integration.set_achievement_system(achievement_system.clone());
+        integration.initialize().await.unwrap();
+
+        let yaml = r#"
+version: "1.0"
+description: "Test achievements"
+achievements:
+  - id: regular_chatter
+    name: Regular Chatter
+    description: Send 3 messages
+    condition:
+      metric: messages
+      threshold: 3
+    reward_points: 75
+    hidden: true
+"#;
+        let achievement_config: AchievementConfiguration = serde_yaml::from_str(yaml).unwrap();
+        config_manager.save_achievement_config(achievement_config).await.unwrap();
+        integration.reload_configuration(ConfigType::Achievements).await.unwrap();
+
+        let mut user = crate::bot::points::UserPoints::new("twitch".to_string(), "viewer".to_string(), None);
+        user.messages_sent = 2;
+        let unlocked = achievement_system.check_achievements(&user).await;
+        assert!(!unlocked.iter().any(|a| a.id == "regular_chatter"));
+        // Defaults are still there
+        assert!(unlocked.iter().any(|a| a.id == "first_message"));
+
+        user.messages_sent = 3;
+        let unlocked = achievement_system.check_achievements(&user).await;
+        let custom = unlocked.iter().find(|a| a.id == "regular_chatter").expect("custom achievement unlocked");
+        assert_eq!(custom.reward_points, 75);
+        assert!(custom.hidden);
+    }
+
+    #[tokio::test]
+    async fn test_achievement_config_rejects_unknown_metric() {
+        let temp_dir = tempdir().unwrap();
+        let config_manager = ConfigurationManager::new(temp_dir.path());
+        config_manager.initialize().await.unwrap();
+
+        let mut achievement_config = config_manager.get_achievement_config().await;
+        achievement_config.achievements.push(crate::config::AchievementDefinition {
+            id: "mystery".to_string(),
+            name: "Mystery".to_string(),
+            description: "Unknowable".to_string(),
+            condition: crate::config::AchievementCondition { metric: "vibes".to_string(), threshold: 1 },
+            reward_points: 10,
+            hidden: false,
+            category: Default::default(),
+            rarity: Default::default(),
+            badge_emoji: "❓".to_string(),
+        });
+
+        let err = config_manager.save_achievement_config(achievement_config).await.unwrap_err();
+        assert!(err.to_string().contains("unknown metric 'vibes'"));
+    }
+
+    /// Floors for config/patterns.yaml on the labeled corpus in
+    /// tests/fixtures/pattern_corpus.jsonl. Raise them when matching improves; a
+    /// drop means a change lost detections (recall) or started flagging clean chat
+    /// (precision). The shipped patterns leave "subscribe" alone since viewers say
+    /// it all the time, so its leetspeak samples are expected misses.
+    const CORPUS_MIN_PRECISION: f64 = 0.95;
+    const CORPUS_MIN_RECALL: f64 = 0.85;
+
+    #[tokio::test]
+    async fn test_shipped_patterns_accuracy_on_labeled_corpus() {
+        #[derive(serde::Deserialize)]
+        struct Sample {
+            label: String,
+            kind: String,
+            text: String,
+        }
+
+        let root = std::path::Path::new(env!("CARGO_MANIFEST_DIR"));
+        let samples: Vec<Sample> = std::fs::read_to_string(root.join("tests/fixtures/pattern_corpus.jsonl")).unwrap().lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert!(samples.iter().any(|s| s.label == "ham") && samples.iter().any(|s| s.label == "spam"));
+
+        // The patterns as shipped, loaded and evaluated the way the bot does it
+        let temp_dir = tempdir().unwrap();
+        std::fs::copy(root.join("config/patterns.yaml"), temp_dir.path().join("patterns.yaml")).unwrap();
+        let config_manager = Arc::new(ConfigurationManager::new(temp_dir.path()));
+        config_manager.initialize().await.unwrap();
+        let moderation_system = Arc::new(ModerationSystem::new());
+        let enhanced = Arc::new(EnhancedModerationSystem::new(moderation_system.clone()));
+        let mut integration = ConfigIntegration::new(config_manager, moderation_system);
+        integration.set_enhanced_moderation(enhanced.clone());
+        integration.initialize().await.unwrap();
+
+        let (mut true_positives, mut false_positives, mut false_negatives) = (0, 0, 0);
+        let mut misses = Vec::new();
+        for (i, sample) in samples.iter().enumerate() {
+            let message = crate::types::ChatMessage::new("twitch", "testchannel", format!("viewer{}", i), &sample.text);
+            let flagged = enhanced.check_message_enhanced(&message, None).await
+                .is_some_and(|result| !result.advanced_patterns.is_empty());
+            match (sample.label.as_str(), flagged) {
+                ("spam", true) => true_positives += 1,
+                ("spam", false) => {
+                    false_negatives += 1;
+                    misses.push(format!("missed {}: {}", sample.kind, sample.text));
+                }
+                ("ham", true) => {
+                    false_positives += 1;
+                    misses.push(format!("flagged clean: {}", sample.text));
+                }
+                ("ham", false) => {}
+                (label, _) => panic!("unknown label '{}'", label),
+            }
+        }
+
+        let precision = true_positives as f64 / (true_positives + false_positives) as f64;
+        let recall = true_positives as f64 / (true_positives + false_negatives) as f64;
+        assert!(precision >= CORPUS_MIN_PRECISION && recall >= CORPUS_MIN_RECALL,
+                "precision {:.3} (min {}), recall {:.3} (min {}):\n{}",
+                precision, CORPUS_MIN_PRECISION, recall, CORPUS_MIN_RECALL, misses.join("\n"));
+    }
+}
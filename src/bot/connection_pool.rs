@@ -2,12 +2,15 @@
 
 use anyhow::Result;
 use log::{debug, error, info, warn};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::{Duration, Instant};
 use tokio::sync::{RwLock, Semaphore};
 
-use crate::platforms::{PlatformConnection, twitch::TwitchConnection, youtube::YouTubeConnection};
+use crate::platforms::{DeliveryStatus, PlatformConnection, QuotaUsage, twitch::TwitchConnection, youtube::YouTubeConnection};
 use crate::platforms::{twitch::TwitchConfig, youtube::YouTubeConfig};
 
 /// Configuration for connection pooling
@@ -39,7 +42,7 @@ impl Default for PoolConfig {
 }
 
 /// Statistics for connection pool monitoring
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PoolStats {
     pub platform: String,
     pub total_connections: usize,
@@ -50,6 +53,61 @@ pub struct PoolStats {
     pub successful_requests: u64,
     pub failed_requests: u64,
     pub average_response_time_ms: f64,
+    /// Combined API quota usage of the pooled connections, if the platform meters its API
+    pub api_quota: Option<QuotaUsage>,
+}
+
+/// Pool statistics for every platform, ordered by platform name; shown by
+/// `!poolstats` and served by the web dashboard
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PoolStatsSummary {
+    pub platforms: Vec<PoolStats>,
+}
+
+impl PoolStatsSummary {
+    pub fn new(mut platforms: Vec<PoolStats>) -> Self {
+        platforms.sort_by(|a, b| a.platform.cmp(&b.platform));
+        Self { platforms }
+    }
+
+    pub fn platform(&self, platform: &str) -> Option<&PoolStats> {
+        self.platforms.iter().find(|stats| stats.platform == platform)
+    }
+}
+
+impl fmt::Display for PoolStatsSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Connection Pool Stats:")?;
+        for stats in &self.platforms {
+            writeln!(f, "{}: {} total ({} active, {} idle), {:.1}ms avg",
+                     stats.platform, stats.total_connections, stats.active_connections,
+                     stats.idle_connections, stats.average_response_time_ms)?;
+            if let Some(quota) = &stats.api_quota {
+                writeln!(f, "  API quota: {}/{} units ({:.0}% used)",
+                         quota.used, quota.limit, quota.fraction_used() * 100.0)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// What a send account is used for when a platform has more than one.
+/// Chat is always read through the bot's main connection for the platform.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectionRole {
+    /// Only sends responses, e.g. a verified account with higher rate limits
+    Send,
+    /// Also reads chat elsewhere, so it only sends when there's no dedicated send account
+    Both,
+}
+
+/// A named account registered for a platform with a fixed role
+struct RoleConnection {
+    name: String,
+    role: ConnectionRole,
+    /// Shared so a send can go out after the pool lock is released
+    connection: Arc<dyn PlatformConnection>,
 }
 
 /// Wrapper for pooled connections with metadata
@@ -96,6 +154,10 @@ struct PlatformPool {
     idle_connections: Vec<PooledConnection>,
     semaphore: Arc<Semaphore>,
     stats: PoolStats,
+    /// Accounts with a role, separate from the interchangeable pooled connections
+    accounts: Vec<RoleConnection>,
+    /// Round-robin position among the send accounts
+    next_sender: AtomicUsize,
 }
 
 impl PlatformPool {
@@ -111,6 +173,7 @@ impl PlatformPool {
             successful_requests: 0,
             failed_requests: 0,
             average_response_time_ms: 0.0,
+            api_quota: None,
         };
 
         Self {
@@ -120,7 +183,31 @@ impl PlatformPool {
             idle_connections: Vec::new(),
             semaphore,
             stats,
+            accounts: Vec::new(),
+            next_sender: AtomicUsize::new(0),
+        }
+    }
+
+    /// Accounts to send through, in the order to try them: dedicated send
+    /// accounts take turns, and `Both` accounts are only used when there are none
+    fn senders(&self) -> Vec<(String, Arc<dyn PlatformConnection>)> {
+        let dedicated: Vec<&RoleConnection> = self.accounts.iter()
+            .filter(|account| account.role == ConnectionRole::Send)
+            .collect();
+        let senders = if dedicated.is_empty() {
+            self.accounts.iter().collect()
+        } else {
+            dedicated
+        };
+        if senders.is_empty() {
+            return Vec::new();
         }
+
+        let start = self.next_sender.fetch_add(1, Ordering::Relaxed);
+        (0..senders.len())
+            .map(|offset| senders[(start + offset) % senders.len()])
+            .map(|account| (account.name.clone(), Arc::clone(&account.connection)))
+            .collect()
     }
 
     async fn get_connection(&mut self) -> Result<Box<dyn PlatformConnection>> {
@@ -358,6 +445,30 @@ impl PlatformPool {
     fn get_stats(&self) -> PoolStats {
         self.stats.clone()
     }
+
+    /// Sum quota usage across pooled connections; they share one API project,
+    /// so the limit is not summed
+    async fn quota_usage(&self) -> Option<QuotaUsage> {
+        let mut combined: Option<QuotaUsage> = None;
+        for pooled in self.active_connections.iter().chain(self.idle_connections.iter()) {
+            if let Some(usage) = pooled.connection.quota_usage().await {
+                combined = Some(match combined {
+                    Some(total) => QuotaUsage {
+                        used: total.used + usage.used,
+                        limit: total.limit.max(usage.limit),
+                    },
+                    None => usage,
+                });
+            }
+        }
+        combined
+    }
+
+    async fn get_stats_with_quota(&self) -> PoolStats {
+        let mut stats = self.get_stats();
+        stats.api_quota = self.quota_usage().await;
+        stats
+    }
 }
 
 /// Main connection pool manager
@@ -421,22 +532,88 @@ impl ConnectionPool {
         }
     }
 
+    /// Connect an account and register it for `platform` with a role, so one
+    /// account can read chat while others send the responses
+    pub async fn add_account(
+        &self,
+        platform: &str,
+        name: &str,
+        role: ConnectionRole,
+        mut connection: Box<dyn PlatformConnection>,
+    ) -> Result<()> {
+        match tokio::time::timeout(Duration::from_secs(self.config.connection_timeout_seconds), connection.connect()).await {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => return Err(e.context(format!("Failed to connect {} account {}", platform, name))),
+            Err(_) => return Err(anyhow::anyhow!("Connecting {} account {} timed out", platform, name)),
+        }
+
+        let mut pools = self.pools.write().await;
+        let pool = pools.entry(platform.to_string())
+            .or_insert_with(|| PlatformPool::new(platform.to_string(), self.config.clone()));
+        pool.accounts.push(RoleConnection { name: name.to_string(), role, connection: Arc::from(connection) });
+        info!("Added {} account {} ({:?})", platform, name, role);
+        Ok(())
+    }
+
+    /// Accounts registered for a platform, with their roles
+    pub async fn accounts(&self, platform: &str) -> Vec<(String, ConnectionRole)> {
+        self.pools.read().await.get(platform)
+            .map(|pool| pool.accounts.iter().map(|a| (a.name.clone(), a.role)).collect())
+            .unwrap_or_default()
+    }
+
+    /// Whether `platform` has a connected account to send responses through
+    pub async fn has_sender(&self, platform: &str) -> bool {
+        let accounts: Vec<Arc<dyn PlatformConnection>> = match self.pools.read().await.get(platform) {
+            Some(pool) => pool.accounts.iter().map(|a| Arc::clone(&a.connection)).collect(),
+            None => return false,
+        };
+        for connection in accounts {
+            if connection.is_connected().await {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Send through the platform's next connected send account
+    pub async fn send_message_with_status(&self, platform: &str, channel: &str, message: &str) -> Result<DeliveryStatus> {
+        let senders = self.pools.read().await.get(platform)
+            .ok_or_else(|| anyhow::anyhow!("No pool found for platform: {}", platform))?
+            .senders();
+        for (name, connection) in senders {
+            if connection.is_connected().await {
+                debug!("Sending to {}#{} as {}", platform, channel, name);
+                return connection.send_message_with_status(channel, message).await;
+            }
+        }
+        Err(anyhow::anyhow!("No connected {} account can send", platform))
+    }
+
     /// Get pool statistics for all platforms
     pub async fn get_stats(&self) -> HashMap<String, PoolStats> {
         let pools = self.pools.read().await;
         let mut stats = HashMap::new();
         
         for (platform, pool) in pools.iter() {
-            stats.insert(platform.clone(), pool.get_stats());
+            stats.insert(platform.clone(), pool.get_stats_with_quota().await);
         }
         
         stats
     }
 
+    /// Get statistics for every platform as one structured summary
+    pub async fn get_stats_summary(&self) -> PoolStatsSummary {
+        PoolStatsSummary::new(self.get_stats().await.into_values().collect())
+    }
+
     /// Get statistics for a specific platform
     pub async fn get_platform_stats(&self, platform: &str) -> Option<PoolStats> {
         let pools = self.pools.read().await;
-        pools.get(platform).map(|pool| pool.get_stats())
+        match pools.get(platform) {
+            Some(pool) => Some(pool.get_stats_with_quota().await),
+            None => None,
+        }
     }
 
     /// Shutdown the connection pool gracefully
@@ -447,6 +624,17 @@ impl ConnectionPool {
         
         let mut pools = self.pools.write().await;
         for (platform, pool) in pools.iter_mut() {
+            for account in pool.accounts.iter_mut() {
+                let Some(connection) = Arc::get_mut(&mut account.connection) else {
+                    warn!("{} account {} is still sending, not disconnecting it", platform, account.name);
+                    continue;
+                };
+                if let Err(e) = connection.disconnect().await {
+                    error!("Failed to disconnect {} account {}: {}", platform, account.name, e);
+                }
+            }
+            pool.accounts.clear();
+
             // Disconnect all connections
             for mut conn in pool.active_connections.drain(..) {
                 if let Err(e) = conn.connection.disconnect().await {
@@ -527,6 +715,7 @@ impl ConnectionPool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::platforms::mock::MockConnection;
 
     #[tokio::test]
     async fn test_pool_initialization() {
@@ -546,4 +735,78 @@ mod tests {
         assert_eq!(config.min_idle_connections, 1);
         assert_eq!(config.max_idle_connections, 3);
     }
+
+    #[tokio::test]
+    async fn test_stats_summary_is_structured_and_displayable() {
+        let pool = ConnectionPool::with_default_config();
+        {
+            let mut pools = pool.pools.write().await;
+            for platform in ["youtube", "twitch"] {
+                pools.insert(platform.to_string(), PlatformPool::new(platform.to_string(), PoolConfig::default()));
+            }
+        }
+
+        let mut summary = pool.get_stats_summary().await;
+        let platforms: Vec<_> = summary.platforms.iter().map(|s| s.platform.as_str()).collect();
+        assert_eq!(platforms, vec!["twitch", "youtube"]);
+        assert_eq!(summary.platform("twitch").unwrap().total_connections, 0);
+
+        summary.platforms[1].api_quota = Some(QuotaUsage { used: 2500, limit: 10000 });
+        let json = serde_json::to_value(&summary).unwrap();
+        assert_eq!(json["platforms"][0]["platform"], "twitch");
+        assert_eq!(json["platforms"][1]["api_quota"]["used"], 2500);
+        assert!(json["platforms"][0]["average_response_time_ms"].is_number());
+
+        assert_eq!(summary.to_string(), "Connection Pool Stats:\n\
+            twitch: 0 total (0 active, 0 idle), 0.0ms avg\n\
+            youtube: 0 total (0 active, 0 idle), 0.0ms avg\n  API quota: 2500/10000 units (25% used)\n");
+    }
+
+    #[tokio::test]
+    async fn test_sends_prefer_dedicated_send_accounts() {
+        let pool = ConnectionPool::with_default_config();
+        let channels = vec!["testchannel".to_string()];
+        let main = MockConnection::new("twitch", channels.clone());
+        let verified = MockConnection::new("twitch", channels.clone());
+        let backup = MockConnection::new("twitch", channels.clone());
+        assert!(!pool.has_sender("twitch").await);
+
+        // Without dedicated send accounts the `Both` account sends
+        pool.add_account("twitch", "main", ConnectionRole::Both, Box::new(main.clone())).await.unwrap();
+        assert!(pool.has_sender("twitch").await);
+        pool.send_message_with_status("twitch", "testchannel", "from main").await.unwrap();
+        assert_eq!(main.sent_messages().await.len(), 1);
+
+        pool.add_account("twitch", "verified", ConnectionRole::Send, Box::new(verified.clone())).await.unwrap();
+        pool.add_account("twitch", "backup", ConnectionRole::Send, Box::new(backup.clone())).await.unwrap();
+        assert_eq!(pool.accounts("twitch").await[0], ("main".to_string(), ConnectionRole::Both));
+
+        for i in 0..4 {
+            let status = pool.send_message_with_status("twitch", "testchannel", &format!("reply {}", i)).await.unwrap();
+            assert_eq!(status, DeliveryStatus::Delivered);
+        }
+        assert_eq!(main.sent_messages().await.len(), 1);
+        assert_eq!(verified.sent_messages().await.len(), 2);
+        assert_eq!(backup.sent_messages().await.len(), 2);
+
+        // A disconnected send account is skipped
+        backup.clone().disconnect().await.unwrap();
+        pool.send_message_with_status("twitch", "testchannel", "still sent").await.unwrap();
+        pool.send_message_with_status("twitch", "testchannel", "still sent").await.unwrap();
+        assert_eq!(verified.sent_messages().await.len(), 4);
+
+        assert!(pool.send_message_with_status("youtube", "testchannel", "nope").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_disconnected_accounts_are_not_senders() {
+        let pool = ConnectionPool::with_default_config();
+        let verified = MockConnection::new("twitch", vec!["testchannel".to_string()]);
+        pool.add_account("twitch", "verified", ConnectionRole::Send, Box::new(verified.clone())).await.unwrap();
+        assert!(pool.has_sender("twitch").await);
+
+        verified.clone().disconnect().await.unwrap();
+        assert!(!pool.has_sender("twitch").await);
+        assert!(pool.send_message_with_status("twitch", "testchannel", "hi").await.is_err());
+    }
 }
@@ -0,0 +1,127 @@
+use anyhow::{Context, Result};
+use log::info;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+/// Environment variable overriding where the bot keeps its state
+pub const DATA_DIR_ENV: &str = "NOTABOT_DATA_DIR";
+
+/// Used when `NOTABOT_DATA_DIR` is unset
+pub const DEFAULT_DATA_DIR: &str = "data";
+
+pub const ADAPTIVE_STATE_FILE: &str = "adaptive_final_state.json";
+pub const POINTS_SEASONS_FILE: &str = "points_seasons.json";
+pub const GIVEAWAY_HISTORY_FILE: &str = "giveaway_history.json";
+pub const ACTIVE_GIVEAWAY_FILE: &str = "active_giveaway.json";
+pub const AUDIT_LOG_FILE: &str = "moderation_audit.jsonl";
+pub const IDENTITY_LINKS_FILE: &str = "identity_links.json";
+/// Refreshed Twitch tokens, unless `TWITCH_TOKEN_FILE` says otherwise
+pub const TWITCH_TOKEN_FILE: &str = "twitch_tokens.json";
+/// `!gexport` output, with the format's extension added
+pub const GIVEAWAY_EXPORT_FILE: &str = "giveaway_export";
+
+/// Directory all persisted state (adaptive state, points seasons, giveaways,
+/// identity links, refreshed tokens, the message log, the moderation audit trail)
+/// is written under
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataDir {
+    root: PathBuf,
+}
+
+impl Default for DataDir {
+    fn default() -> Self {
+        Self::new(DEFAULT_DATA_DIR)
+    }
+}
+
+impl DataDir {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// `NOTABOT_DATA_DIR` if set, otherwise `data/` in the working directory
+    pub fn from_env() -> Self {
+        match std::env::var(DATA_DIR_ENV) {
+            Ok(dir) if !dir.trim().is_empty() => Self::new(dir.trim()),
+            _ => Self::default(),
+        }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Path of a file in the data directory
+    pub fn file(&self, name: &str) -> PathBuf {
+        self.root.join(name)
+    }
+
+    /// Place a relative path under the data directory; absolute paths are kept
+    pub fn resolve(&self, path: &Path) -> PathBuf {
+        if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            self.root.join(path)
+        }
+    }
+
+    /// Create the directory if it's missing and check it can be written to
+    pub async fn ensure(&self) -> Result<()> {
+        fs::create_dir_all(&self.root).await
+            .with_context(|| format!("Failed to create data directory {} (set {} to change it)",
+                                     self.root.display(), DATA_DIR_ENV))?;
+
+        let probe = self.root.join(".notabot_write_probe");
+        fs::write(&probe, b"").await
+            .with_context(|| format!("Data directory {} is not writable (set {} to change it)",
+                                     self.root.display(), DATA_DIR_ENV))?;
+        fs::remove_file(&probe).await.ok();
+
+        info!("Using data directory {}", self.root.display());
+        Ok(())
+    }
+
+    /// Write `value` as pretty JSON to a file in the data directory
+    pub async fn write_json<T: Serialize>(&self, name: &str, value: &T) -> Result<PathBuf> {
+        let path = self.file(name);
+        let content = serde_json::to_string_pretty(value)?;
+        fs::write(&path, content).await
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_data_dir_is_created_and_written_to() {
+        let dir = tempfile::tempdir().unwrap();
+        let data_dir = DataDir::new(dir.path().join("nested").join("data"));
+        data_dir.ensure().await.unwrap();
+        assert!(data_dir.root().is_dir());
+
+        let path = data_dir.write_json(ADAPTIVE_STATE_FILE, &serde_json::json!({"tuning_cycles": 3})).await.unwrap();
+        assert_eq!(path, data_dir.root().join(ADAPTIVE_STATE_FILE));
+        let saved: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(saved["tuning_cycles"], 3);
+
+        assert_eq!(data_dir.resolve(Path::new("logs/messages.jsonl")), data_dir.root().join("logs/messages.jsonl"));
+        let absolute = dir.path().join("elsewhere.jsonl");
+        assert_eq!(data_dir.resolve(&absolute), absolute);
+    }
+
+    #[tokio::test]
+    async fn test_uncreatable_data_dir_fails_clearly() {
+        let dir = tempfile::tempdir().unwrap();
+        let blocker = dir.path().join("not_a_dir");
+        std::fs::write(&blocker, b"").unwrap();
+
+        let error = DataDir::new(blocker.join("data")).ensure().await.unwrap_err();
+        let message = format!("{:#}", error);
+        assert!(message.contains("Failed to create data directory"), "{}", message);
+        assert!(message.contains(DATA_DIR_ENV), "{}", message);
+    }
+}
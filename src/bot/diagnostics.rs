@@ -0,0 +1,402 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::fs;
+use tokio::sync::RwLock;
+
+use crate::config::{
+    AchievementConfiguration, BotConfiguration, ConfigValidator, FilterConfiguration, PatternConfiguration,
+    TimerConfiguration,
+};
+use crate::platforms::{ConnectionError, PlatformConnection, PlatformHealth};
+
+/// Outcome of a single diagnostic check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+impl fmt::Display for CheckStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            CheckStatus::Pass => "PASS",
+            CheckStatus::Warn => "WARN",
+            CheckStatus::Fail => "FAIL",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DiagnosticCheck {
+    /// What was checked, e.g. `config/filters.yaml` or `platform twitch`
+    pub name: String,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+impl DiagnosticCheck {
+    fn new(name: impl Into<String>, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// Results of `ChatBot::run_diagnostics`, in the order the checks ran
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DiagnosticsReport {
+    pub checks: Vec<DiagnosticCheck>,
+}
+
+impl DiagnosticsReport {
+    /// The worst status of any check
+    pub fn overall(&self) -> CheckStatus {
+        self.checks.iter().map(|c| c.status).max().unwrap_or(CheckStatus::Pass)
+    }
+
+    pub fn count(&self, status: CheckStatus) -> usize {
+        self.checks.iter().filter(|c| c.status == status).count()
+    }
+
+    /// Checks that warned or failed, failures first
+    pub fn problems(&self) -> Vec<&DiagnosticCheck> {
+        let mut problems: Vec<_> = self.checks.iter().filter(|c| c.status != CheckStatus::Pass).collect();
+        problems.sort_by_key(|c| std::cmp::Reverse(c.status));
+        problems
+    }
+
+    pub fn summary(&self) -> String {
+        format!("{} passed, {} warnings, {} failed",
+                self.count(CheckStatus::Pass), self.count(CheckStatus::Warn), self.count(CheckStatus::Fail))
+    }
+
+    /// One line for chat: counts only, since check details name files, parse
+    /// errors and environment variables that don't belong in a public channel
+    pub fn chat_summary(&self) -> String {
+        if self.problems().is_empty() {
+            return format!("Doctor: all {} checks passed", self.checks.len());
+        }
+        format!("Doctor: {} (details are in the bot log)", self.summary())
+    }
+}
+
+impl fmt::Display for DiagnosticsReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for check in &self.checks {
+            writeln!(f, "[{}] {}: {}", check.status, check.name, check.detail)?;
+        }
+        write!(f, "{}", self.summary())
+    }
+}
+
+/// What the doctor should look at besides the bot's own connections
+#[derive(Debug, Clone)]
+pub struct DiagnosticsOptions {
+    /// Configuration directory to validate; `None` skips the config and env var checks
+    pub config_dir: Option<PathBuf>,
+    /// Port the web dashboard listens on
+    pub dashboard_port: Option<u16>,
+    /// Directories the bot writes data to
+    pub data_dirs: Vec<PathBuf>,
+    /// Try to connect platforms that aren't connected (only sensible before the bot starts)
+    pub connect_platforms: bool,
+    pub connect_timeout: Duration,
+}
+
+impl Default for DiagnosticsOptions {
+    fn default() -> Self {
+        Self {
+            config_dir: None,
+            dashboard_port: None,
+            data_dirs: Vec::new(),
+            connect_platforms: false,
+            connect_timeout: Duration::from_secs(15),
+        }
+    }
+}
+
+/// Validate each configuration file on disk, returning the bot configuration if it parsed
+pub(crate) async fn check_config_files(config_dir: &Path) -> (Vec<DiagnosticCheck>, Option<BotConfiguration>) {
+    let validator = ConfigValidator::new();
+    let mut checks = Vec::new();
+    let mut bot_config = None;
+
+    for file in ["filters.yaml", "patterns.yaml", "timers.yaml", "bot.yaml", "achievements.yaml"] {
+        let path = config_dir.join(file);
+        let name = path.display().to_string();
+        let content = match fs::read_to_string(&path).await {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                checks.push(DiagnosticCheck::new(name, CheckStatus::Warn, "missing, built-in defaults are used"));
+                continue;
+            }
+            Err(e) => {
+                checks.push(DiagnosticCheck::new(name, CheckStatus::Fail, format!("cannot be read: {}", e)));
+                continue;
+            }
+        };
+
+        let result: Result<(), String> = match file {
+            "filters.yaml" => serde_yaml::from_str::<FilterConfiguration>(&content).map_err(|e| e.to_string())
+                .and_then(|c| validator.validate_filter_config(&c).map_err(|e| e.to_string())),
+            "patterns.yaml" => serde_yaml::from_str::<PatternConfiguration>(&content).map_err(|e| e.to_string())
+                .and_then(|c| validator.validate_pattern_config(&c).map_err(|e| e.to_string())),
+            "timers.yaml" => serde_yaml::from_str::<TimerConfiguration>(&content).map(|_| ()).map_err(|e| e.to_string()),
+            "bot.yaml" => serde_yaml::from_str::<BotConfiguration>(&content).map_err(|e| e.to_string())
+                .and_then(|c| {
+                    validator.validate_bot_config(&c).map_err(|e| e.to_string())?;
+                    bot_config = Some(c);
+                    Ok(())
+                }),
+            _ => serde_yaml::from_str::<AchievementConfiguration>(&content).map_err(|e| e.to_string())
+                .and_then(|c| validator.validate_achievement_config(&c).map_err(|e| e.to_string())),
+        };
+
+        checks.push(match result {
+            Ok(()) => DiagnosticCheck::new(name, CheckStatus::Pass, "valid"),
+            Err(e) => DiagnosticCheck::new(name, CheckStatus::Fail, e),
+        });
+    }
+
+    (checks, bot_config)
+}
+
+/// `${NAME}` references in a configuration value
+fn env_var_references(value: &serde_json::Value, found: &mut BTreeSet<String>) {
+    match value {
+        serde_json::Value::String(s) => {
+            let mut rest = s.as_str();
+            while let Some(start) = rest.find("${") {
+                let Some(end) = rest[start..].find('}') else { break };
+                let name = &rest[start + 2..start + end];
+                if !name.is_empty() {
+                    found.insert(name.to_string());
+                }
+                rest = &rest[start + end + 1..];
+            }
+        }
+        serde_json::Value::Array(items) => items.iter().for_each(|v| env_var_references(v, found)),
+        serde_json::Value::Object(map) => map.values().for_each(|v| env_var_references(v, found)),
+        _ => {}
+    }
+}
+
+/// Every environment variable referenced by an enabled platform must be set
+pub(crate) fn check_env_vars(bot_config: &BotConfiguration, lookup: impl Fn(&str) -> Option<String>) -> Vec<DiagnosticCheck> {
+    let mut platforms: Vec<_> = bot_config.platforms.iter().filter(|(_, p)| p.enabled).collect();
+    platforms.sort_by_key(|(name, _)| name.as_str());
+
+    platforms.into_iter().filter_map(|(platform, platform_config)| {
+        let mut referenced = BTreeSet::new();
+        env_var_references(&serde_json::to_value(platform_config).ok()?, &mut referenced);
+        if referenced.is_empty() {
+            return None;
+        }
+
+        let missing: Vec<_> = referenced.iter()
+            .filter(|name| lookup(name).is_none_or(|value| value.is_empty()))
+            .cloned()
+            .collect();
+        let name = format!("env vars for {}", platform);
+        Some(if missing.is_empty() {
+            DiagnosticCheck::new(name, CheckStatus::Pass, format!("{} referenced variables set", referenced.len()))
+        } else {
+            DiagnosticCheck::new(name, CheckStatus::Fail, format!("not set: {}", missing.join(", ")))
+        })
+    }).collect()
+}
+
+/// Each connection is up (or connects within the timeout), and each platform enabled
+/// in the configuration has a connection
+pub(crate) async fn check_platforms(
+    connections: &RwLock<HashMap<String, Box<dyn PlatformConnection>>>,
+    enabled_platforms: &[String],
+    options: &DiagnosticsOptions,
+) -> Vec<DiagnosticCheck> {
+    let mut checks = Vec::new();
+    // Only a connection attempt needs the write lock; a running bot keeps chatting meanwhile
+    let (health, missing) = {
+        let connections = connections.read().await;
+        let mut health = Vec::new();
+        for (platform, connection) in connections.iter() {
+            health.push((platform.clone(), connection.health().await));
+        }
+        health.sort_by(|a, b| a.0.cmp(&b.0));
+        let missing: Vec<&String> = enabled_platforms.iter().filter(|p| !connections.contains_key(*p)).collect();
+        (health, missing)
+    };
+
+    for (platform, health) in health {
+        let name = format!("platform {}", platform);
+
+        let check = match health {
+            PlatformHealth::Connected => DiagnosticCheck::new(name, CheckStatus::Pass, "connected"),
+            PlatformHealth::AuthFailed => DiagnosticCheck::new(name, CheckStatus::Fail, "credentials were rejected"),
+            PlatformHealth::Down if !options.connect_platforms => {
+                DiagnosticCheck::new(name, CheckStatus::Fail, "not connected")
+            }
+            PlatformHealth::Down => {
+                let mut connections = connections.write().await;
+                let Some(connection) = connections.get_mut(&platform) else { continue };
+                match tokio::time::timeout(options.connect_timeout, connection.connect()).await {
+                    Ok(Ok(())) => {
+                        connection.disconnect().await.ok();
+                        DiagnosticCheck::new(name, CheckStatus::Pass, "connected successfully")
+                    }
+                    Ok(Err(e)) => match ConnectionError::auth_failure(&e) {
+                        Some(auth_error) => DiagnosticCheck::new(name, CheckStatus::Fail, auth_error.to_string()),
+                        None => DiagnosticCheck::new(name, CheckStatus::Fail, format!("connection failed: {}", e)),
+                    },
+                    Err(_) => DiagnosticCheck::new(name, CheckStatus::Fail,
+                                                   format!("no connection within {}s", options.connect_timeout.as_secs())),
+                }
+            }
+        };
+        checks.push(check);
+    }
+
+    for platform in missing {
+        checks.push(DiagnosticCheck::new(format!("platform {}", platform), CheckStatus::Fail,
+                                         "enabled in bot.yaml but no connection was set up (check its credentials)"));
+    }
+
+    checks
+}
+
+/// The dashboard port can be bound, unless this bot is already serving on it
+pub(crate) async fn check_dashboard_port(port: u16, served_by_bot: bool) -> DiagnosticCheck {
+    let name = format!("dashboard port {}", port);
+    if served_by_bot {
+        return DiagnosticCheck::new(name, CheckStatus::Pass, "dashboard is running");
+    }
+    match tokio::net::TcpListener::bind(("0.0.0.0", port)).await {
+        Ok(_) => DiagnosticCheck::new(name, CheckStatus::Pass, "available"),
+        Err(e) => DiagnosticCheck::new(name, CheckStatus::Fail, format!("cannot be bound: {}", e)),
+    }
+}
+
+/// Files can be created in the directory, or in the closest existing parent if it
+/// doesn't exist yet (it is created on first write)
+pub(crate) async fn check_writable(dir: &Path, status_if_not: CheckStatus) -> DiagnosticCheck {
+    let name = format!("directory {}", dir.display());
+    let mut target = dir;
+    while !target.exists() {
+        match target.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => target = parent,
+            _ => {
+                target = Path::new(".");
+                break;
+            }
+        }
+    }
+
+    let probe = target.join(".notabot_doctor_probe");
+    match fs::write(&probe, b"").await {
+        Ok(()) => {
+            fs::remove_file(&probe).await.ok();
+            if target == dir {
+                DiagnosticCheck::new(name, CheckStatus::Pass, "writable")
+            } else {
+                DiagnosticCheck::new(name, CheckStatus::Pass, "will be created on first write")
+            }
+        }
+        Err(e) => DiagnosticCheck::new(name, status_if_not, format!("not writable: {}", e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_summary_and_problems() {
+        let report = DiagnosticsReport {
+            checks: vec![
+                DiagnosticCheck::new("config/bot.yaml", CheckStatus::Pass, "valid"),
+                DiagnosticCheck::new("config/timers.yaml", CheckStatus::Warn, "missing, built-in defaults are used"),
+                DiagnosticCheck::new("platform twitch", CheckStatus::Fail, "not connected"),
+                DiagnosticCheck::new("dashboard port 3000", CheckStatus::Fail, "cannot be bound: address in use"),
+            ],
+        };
+
+        assert_eq!(report.overall(), CheckStatus::Fail);
+        assert_eq!(report.summary(), "1 passed, 1 warnings, 2 failed");
+        let problems: Vec<_> = report.problems().iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(problems, vec!["platform twitch", "dashboard port 3000", "config/timers.yaml"]);
+        assert_eq!(report.chat_summary(), "Doctor: 1 passed, 1 warnings, 2 failed (details are in the bot log)");
+        assert!(!report.chat_summary().contains("timers.yaml"));
+        assert!(report.to_string().starts_with("[PASS] config/bot.yaml: valid\n"));
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["checks"][2]["status"], "fail");
+        assert_eq!(DiagnosticsReport::default().overall(), CheckStatus::Pass);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_and_missing_config_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut bot_config = BotConfiguration::default();
+        if let Some(twitch) = bot_config.platforms.get_mut("twitch") {
+            twitch.enabled = true;
+        }
+        std::fs::write(dir.path().join("bot.yaml"), serde_yaml::to_string(&bot_config).unwrap()).unwrap();
+        std::fs::write(dir.path().join("filters.yaml"), "blacklist_filters: [not: valid").unwrap();
+
+        let (checks, parsed) = check_config_files(dir.path()).await;
+        let status = |file: &str| checks.iter().find(|c| c.name.ends_with(file)).unwrap().status;
+        assert_eq!(status("bot.yaml"), CheckStatus::Pass);
+        assert_eq!(status("filters.yaml"), CheckStatus::Fail);
+        assert_eq!(status("timers.yaml"), CheckStatus::Warn);
+        assert!(parsed.is_some());
+    }
+
+    #[test]
+    fn test_env_vars_referenced_by_enabled_platforms() {
+        let mut bot_config = BotConfiguration::default();
+        bot_config.platforms.clear();
+        let platform = |enabled, settings: serde_json::Value| crate::config::PlatformConfig {
+            enabled,
+            connection_settings: settings,
+            rate_limits: crate::config::RateLimitConfig { messages_per_second: 1.0, burst_limit: 1, cooldown_seconds: 1 },
+            features: Default::default(),
+            webhooks: Vec::new(),
+        };
+        bot_config.platforms.insert("twitch".to_string(), platform(true, serde_json::json!({
+            "oauth_token": "${TWITCH_OAUTH_TOKEN}",
+            "channels": "prefix-${TWITCH_CHANNELS}-${TWITCH_EMPTY}",
+        })));
+        bot_config.platforms.insert("youtube".to_string(), platform(false, serde_json::json!({
+            "api_key": "${YOUTUBE_API_KEY}",
+        })));
+
+        let env: HashMap<&str, &str> = [("TWITCH_OAUTH_TOKEN", "oauth:abc"), ("TWITCH_EMPTY", "")].into();
+        let checks = check_env_vars(&bot_config, |name| env.get(name).map(|v| v.to_string()));
+
+        // Disabled platforms aren't checked
+        assert_eq!(checks.len(), 1);
+        assert_eq!(checks[0].status, CheckStatus::Fail);
+        assert_eq!(checks[0].detail, "not set: TWITCH_CHANNELS, TWITCH_EMPTY");
+    }
+
+    #[tokio::test]
+    async fn test_port_and_directory_checks() {
+        let listener = tokio::net::TcpListener::bind(("0.0.0.0", 0)).await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        assert_eq!(check_dashboard_port(port, false).await.status, CheckStatus::Fail);
+        assert_eq!(check_dashboard_port(port, true).await.status, CheckStatus::Pass);
+        drop(listener);
+
+        let dir = tempfile::tempdir().unwrap();
+        let check = check_writable(&dir.path().join("data").join("logs"), CheckStatus::Fail).await;
+        assert_eq!(check.status, CheckStatus::Pass);
+        assert_eq!(check.detail, "will be created on first write");
+        assert_eq!(check_writable(dir.path(), CheckStatus::Fail).await.detail, "writable");
+    }
+}
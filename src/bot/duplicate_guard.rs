@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+
+/// How the bot gets an identical consecutive response past platform
+/// duplicate-message checks (Twitch rejects the same text twice in a row)
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum DuplicateEvasion {
+    /// Send responses unchanged
+    Disabled,
+    /// Append an invisible character that chat clients don't render
+    #[default]
+    InvisibleSuffix,
+    /// Append the next tag from the list, cycling per channel
+    RotatingTags(Vec<String>),
+}
+
+/// Unicode tag space: accepted by Twitch, invisible in chat
+const INVISIBLE_SUFFIX: &str = " \u{E0000}";
+
+/// Tracks the last delivered response per channel and varies repeats
+#[derive(Debug, Default)]
+pub struct DuplicateGuard {
+    mode: DuplicateEvasion,
+    last_sent: HashMap<(String, String), String>,
+    tag_index: HashMap<(String, String), usize>,
+}
+
+impl DuplicateGuard {
+    pub fn new(mode: DuplicateEvasion) -> Self {
+        Self {
+            mode,
+            last_sent: HashMap::new(),
+            tag_index: HashMap::new(),
+        }
+    }
+
+    pub fn set_mode(&mut self, mode: DuplicateEvasion) {
+        self.mode = mode;
+        self.tag_index.clear();
+    }
+
+    /// Text to send for `message`, varied if it repeats the channel's last delivered message
+    pub fn prepare(&mut self, platform: &str, channel: &str, message: &str) -> String {
+        let key = (platform.to_string(), channel.to_string());
+        if self.last_sent.get(&key).map(String::as_str) != Some(message) {
+            return message.to_string();
+        }
+
+        match &self.mode {
+            DuplicateEvasion::Disabled => message.to_string(),
+            DuplicateEvasion::InvisibleSuffix => format!("{}{}", message, INVISIBLE_SUFFIX),
+            DuplicateEvasion::RotatingTags(tags) if tags.is_empty() => message.to_string(),
+            DuplicateEvasion::RotatingTags(tags) => {
+                let index = self.tag_index.entry(key).or_insert(0);
+                let tag = &tags[*index % tags.len()];
+                *index = (*index + 1) % tags.len();
+                format!("{} {}", message, tag)
+            }
+        }
+    }
+
+    /// Record text the platform actually posted
+    pub fn record_delivered(&mut self, platform: &str, channel: &str, sent: &str) {
+        self.last_sent.insert((platform.to_string(), channel.to_string()), sent.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn send(guard: &mut DuplicateGuard, channel: &str, message: &str) -> String {
+        let sent = guard.prepare("twitch", channel, message);
+        guard.record_delivered("twitch", channel, &sent);
+        sent
+    }
+
+    #[test]
+    fn test_consecutive_duplicates_are_varied() {
+        let mut guard = DuplicateGuard::new(DuplicateEvasion::InvisibleSuffix);
+
+        assert_eq!(send(&mut guard, "main", "Join our discord!"), "Join our discord!");
+        assert_eq!(send(&mut guard, "main", "Join our discord!"), "Join our discord! \u{E0000}");
+        // Alternates back once the previous send was varied
+        assert_eq!(send(&mut guard, "main", "Join our discord!"), "Join our discord!");
+
+        // Tracked per channel
+        assert_eq!(send(&mut guard, "other", "Join our discord!"), "Join our discord!");
+    }
+
+    #[test]
+    fn test_non_consecutive_duplicates_unchanged() {
+        let mut guard = DuplicateGuard::new(DuplicateEvasion::InvisibleSuffix);
+
+        assert_eq!(send(&mut guard, "main", "hello"), "hello");
+        assert_eq!(send(&mut guard, "main", "goodbye"), "goodbye");
+        assert_eq!(send(&mut guard, "main", "hello"), "hello");
+
+        // Undelivered sends don't count as the last message
+        assert_eq!(guard.prepare("twitch", "main", "goodbye"), "goodbye");
+        assert_eq!(send(&mut guard, "main", "hello"), "hello \u{E0000}");
+    }
+
+    #[test]
+    fn test_rotating_tags_and_disabled() {
+        let mut guard = DuplicateGuard::new(DuplicateEvasion::RotatingTags(vec!["[1]".to_string(), "[2]".to_string()]));
+
+        send(&mut guard, "main", "hi");
+        assert_eq!(send(&mut guard, "main", "hi"), "hi [1]");
+        send(&mut guard, "main", "hi");
+        assert_eq!(send(&mut guard, "main", "hi"), "hi [2]");
+
+        guard.set_mode(DuplicateEvasion::Disabled);
+        assert_eq!(send(&mut guard, "main", "hi"), "hi");
+        assert_eq!(send(&mut guard, "main", "hi"), "hi");
+    }
+}
@@ -1,16 +1,158 @@
 use anyhow::Result;
-use log::{info, warn, error};
-use std::collections::HashMap;
+use log::{debug, info, warn, error};
+use std::collections::{HashMap, VecDeque};
+use chrono::{DateTime, Utc};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use std::path::Path;
 
-use crate::types::{ChatMessage, ModerationAction};
+use crate::types::{ChatMessage, MatchExplanation, ModerationAction};
+use crate::bot::moderation::{FilterBudgetExceeded, SpamFilterVerdict};
 use crate::bot::points::UserPoints;
-use crate::bot::pattern_matching::{EnhancedPatternMatcher, AdvancedPattern};
+use crate::bot::pattern_matching::{EnhancedPatternMatcher, AdvancedPattern, CollectionBudget};
 use crate::bot::smart_escalation::{SmartEscalationCalculator, SmartEscalation, ViolationSeverity, PositiveActionType};
-use crate::bot::realtime_analytics::{FilterAnalyticsSystem, UserReportType, ModeratorReviewType};
+use crate::bot::realtime_analytics::{FilterAnalyticsSystem, FilterAppealStats, UserReportType, ModeratorReviewType, RecentMatch};
 use crate::bot::filter_import_export::{FilterImportExport, ExportFormat, ExportOptions, ImportOptions};
+use crate::bot::redaction::{LogRedaction, RedactionMode};
+
+/// Runtime strictness for AI-enhanced pattern matching.
+///
+/// Strictness scales each advanced pattern's own similarity threshold, so it
+/// composes with per-filter and per-collection thresholds from configuration
+/// rather than replacing them. `High` lowers thresholds (more matches), `Low`
+/// raises them, and `Off` skips advanced patterns entirely. Base spam filters
+/// are never affected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AiStrictness {
+    Off,
+    Low,
+    Normal,
+    High,
+}
+
+impl AiStrictness {
+    /// Multiplier applied to pattern thresholds, or `None` when AI matching is off
+    pub fn threshold_scale(&self) -> Option<f32> {
+        match self {
+            AiStrictness::Off => None,
+            AiStrictness::Low => Some(1.15),
+            AiStrictness::Normal => Some(1.0),
+            AiStrictness::High => Some(0.85),
+        }
+    }
+
+    /// Parse a strictness level from user input (case-insensitive)
+    pub fn parse(level: &str) -> Option<Self> {
+        match level.to_lowercase().as_str() {
+            "off" => Some(AiStrictness::Off),
+            "low" => Some(AiStrictness::Low),
+            "normal" | "default" => Some(AiStrictness::Normal),
+            "high" => Some(AiStrictness::High),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AiStrictness::Off => "off",
+            AiStrictness::Low => "low",
+            AiStrictness::Normal => "normal",
+            AiStrictness::High => "high",
+        }
+    }
+}
+
+/// Messages kept per channel for context-aware patterns
+const CONTEXT_WINDOW_SIZE: usize = 5;
+
+/// Context older than this is ignored
+const CONTEXT_MAX_AGE_SECONDS: i64 = 120;
+
+/// Tenure after which a user gets full tenure credit
+const REPUTATION_FULL_TENURE_DAYS: f32 = 30.0;
+
+/// Message count after which a user gets full activity credit
+const REPUTATION_FULL_ACTIVITY_MESSAGES: f32 = 500.0;
+
+/// Reputation lost per recorded violation
+const REPUTATION_VIOLATION_PENALTY: f32 = 0.15;
+
+/// Reputation records kept at most; the users seen least recently are forgotten first
+pub const MAX_REPUTATION_RECORDS: usize = 50_000;
+
+/// What the system knows about a user for reputation purposes
+#[derive(Debug, Clone)]
+pub struct ReputationRecord {
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+    pub messages_seen: u64,
+    pub violations: u64,
+}
+
+impl ReputationRecord {
+    fn new() -> Self {
+        let now = Utc::now();
+        Self {
+            first_seen: now,
+            last_seen: now,
+            messages_seen: 0,
+            violations: 0,
+        }
+    }
+
+    /// Add another account's record to this one, for accounts linked as one person
+    pub fn merge(&mut self, other: &ReputationRecord) {
+        self.first_seen = self.first_seen.min(other.first_seen);
+        self.last_seen = self.last_seen.max(other.last_seen);
+        self.messages_seen += other.messages_seen;
+        self.violations += other.violations;
+    }
+
+    /// Reputation from 0.0 (untrusted) to 1.0 (trusted). New users start at 0.2,
+    /// gain up to 0.4 each from tenure and activity, and lose some per violation.
+    pub fn score(&self) -> f32 {
+        let tenure_days = (Utc::now() - self.first_seen).num_hours() as f32 / 24.0;
+        let tenure = (tenure_days / REPUTATION_FULL_TENURE_DAYS).clamp(0.0, 1.0) * 0.4;
+        let activity = (self.messages_seen as f32 / REPUTATION_FULL_ACTIVITY_MESSAGES).min(1.0) * 0.4;
+        let penalty = self.violations as f32 * REPUTATION_VIOLATION_PENALTY;
+
+        (0.2 + tenure + activity - penalty).clamp(0.0, 1.0)
+    }
+
+    /// Threshold multiplier for this reputation: 0.8 (strict) to 1.2 (lenient),
+    /// with a reputation of 0.5 leaving thresholds unchanged
+    pub fn threshold_scale(&self) -> f32 {
+        0.8 + 0.4 * self.score()
+    }
+}
+
+/// Rolling window of recent messages per channel
+#[derive(Debug, Default)]
+pub struct ChannelContextStore {
+    windows: HashMap<String, VecDeque<(DateTime<Utc>, String)>>,
+}
+
+impl ChannelContextStore {
+    /// Recent messages for a channel, oldest first
+    pub fn recent(&self, channel_key: &str) -> Vec<String> {
+        let cutoff = Utc::now() - chrono::Duration::seconds(CONTEXT_MAX_AGE_SECONDS);
+        self.windows.get(channel_key)
+            .map(|window| window.iter()
+                .filter(|(timestamp, _)| *timestamp >= cutoff)
+                .map(|(_, content)| content.clone())
+                .collect())
+            .unwrap_or_default()
+    }
+
+    /// Append a message, evicting the oldest once the window is full
+    pub fn push(&mut self, channel_key: &str, message: &ChatMessage) {
+        let window = self.windows.entry(channel_key.to_string()).or_default();
+        window.push_back((message.window_timestamp(Utc::now()), message.content.clone()));
+        while window.len() > CONTEXT_WINDOW_SIZE {
+            window.pop_front();
+        }
+    }
+}
 
 /// Enhanced moderation system that integrates all Phase 2 features
 #[derive(Clone)]
@@ -28,10 +170,25 @@ pub struct EnhancedModerationSystem {
     enhanced_features_enabled: Arc<RwLock<bool>>,
     auto_optimization_enabled: Arc<RwLock<bool>>,
     learning_mode: Arc<RwLock<bool>>,
+    ai_strictness: Arc<RwLock<AiStrictness>>,
+    context_analysis_enabled: Arc<RwLock<bool>>,
+    channel_context: Arc<RwLock<ChannelContextStore>>,
+    user_reputation: Arc<RwLock<HashMap<String, ReputationRecord>>>,
+    reputation_scaling_enabled: Arc<RwLock<bool>>,
+    /// Cap on filters matched per message (0 = no limit), and how often it was hit
+    max_filters_per_message: Arc<RwLock<usize>>,
+    filter_cap_hits: Arc<RwLock<u64>>,
+    /// Confidence AI detections need before they're acted on (0.0 = no floor), and how often one fell short
+    min_action_confidence: Arc<RwLock<f32>>,
+    ai_near_misses: Arc<RwLock<u64>>,
+    /// The base system's log redaction setting
+    log_redaction: LogRedaction,
 }
 
 impl EnhancedModerationSystem {
     pub fn new(base_moderation: Arc<crate::bot::moderation::ModerationSystem>) -> Self {
+        let user_reputation = base_moderation.user_reputation();
+        let log_redaction = base_moderation.log_redaction();
         Self {
             base_moderation,
             pattern_matcher: Arc::new(RwLock::new(EnhancedPatternMatcher::new())),
@@ -41,9 +198,24 @@ impl EnhancedModerationSystem {
             enhanced_features_enabled: Arc::new(RwLock::new(true)),
             auto_optimization_enabled: Arc::new(RwLock::new(false)), // Disabled by default for safety
             learning_mode: Arc::new(RwLock::new(false)),
+            ai_strictness: Arc::new(RwLock::new(AiStrictness::Normal)),
+            context_analysis_enabled: Arc::new(RwLock::new(false)),
+            channel_context: Arc::new(RwLock::new(ChannelContextStore::default())),
+            user_reputation,
+            reputation_scaling_enabled: Arc::new(RwLock::new(false)),
+            max_filters_per_message: Arc::new(RwLock::new(0)),
+            filter_cap_hits: Arc::new(RwLock::new(0)),
+            min_action_confidence: Arc::new(RwLock::new(0.0)),
+            ai_near_misses: Arc::new(RwLock::new(0)),
+            log_redaction,
         }
     }
 
+    /// Set how message content is stored in filter analytics snippets and reports
+    pub async fn set_redaction_mode(&self, mode: RedactionMode) {
+        self.analytics_system.set_redaction_mode(mode).await;
+    }
+
     /// Get the underlying base moderation system for adaptive integration
     pub fn get_base_moderation_system(&self) -> Arc<crate::bot::moderation::ModerationSystem> {
         self.base_moderation.clone()
@@ -55,34 +227,91 @@ impl EnhancedModerationSystem {
         message: &ChatMessage,
         user_points: Option<&UserPoints>,
     ) -> Option<EnhancedModerationResult> {
+        self.check_message_enhanced_within(message, user_points, None).await.ok().flatten()
+    }
+
+    /// Like `check_message_enhanced`, but the rule-based filters give up once
+    /// `deadline` passes (AI patterns are bounded by their collection budgets)
+    pub async fn check_message_enhanced_within(
+        &self,
+        message: &ChatMessage,
+        user_points: Option<&UserPoints>,
+        deadline: Option<tokio::time::Instant>,
+    ) -> Result<Option<EnhancedModerationResult>, FilterBudgetExceeded> {
         let start_time = std::time::Instant::now();
 
+        // The bot's own messages are never moderated
+        if self.base_moderation.is_from_bot(message).await {
+            return Ok(None);
+        }
+
         // Check if enhanced features are enabled
         if !*self.enhanced_features_enabled.read().await {
             // Fall back to base moderation
-            if let Some(action) = self.base_moderation.check_spam_filters(message, user_points).await {
-                return Some(EnhancedModerationResult {
-                    action,
-                    confidence: 0.8, // Default confidence for base filters
-                    triggered_filters: vec!["base_filter".to_string()],
-                    advanced_patterns: vec![],
-                    escalation_applied: false,
-                    response_time_ms: start_time.elapsed().as_secs_f64() * 1000.0,
-                    severity: ViolationSeverity::Moderate,
-                });
-            }
-            return None;
+            let verdict = self.base_moderation.evaluate_spam_filters_within(message, user_points, deadline).await?;
+            return Ok(verdict.map(|verdict| EnhancedModerationResult {
+                action: verdict.action.clone(),
+                confidence: 0.8, // Default confidence for base filters
+                triggered_filters: vec!["base_filter".to_string()],
+                advanced_patterns: vec![],
+                escalation_applied: false,
+                response_time_ms: start_time.elapsed().as_secs_f64() * 1000.0,
+                severity: ViolationSeverity::Moderate,
+                base_verdict: Some(verdict),
+            }));
         }
 
         // Enhanced pattern matching
         let mut triggered_filters = Vec::new();
         let mut advanced_patterns = Vec::new();
         let mut max_severity = ViolationSeverity::Minor;
+        let mut base_severity = None;
+        let mut base_verdict = None;
+        let mut ai_confidence: f32 = 0.0;
+        let mut flagged_for_review = Vec::new();
+        let mut cap_reached = false;
+        let max_filters = *self.max_filters_per_message.read().await;
 
-        // Check advanced patterns first
-        {
+        // Update what we know about this user; points data fills in history from before we saw them
+        let user_id = self.base_moderation.identity_key(&message.platform, &message.username).await;
+        let reputation_scale = {
+            let mut reputation = self.user_reputation.write().await;
+            let record = reputation.entry(user_id.clone()).or_insert_with(ReputationRecord::new);
+            record.messages_seen += 1;
+            record.last_seen = Utc::now();
+            if let Some(points) = user_points {
+                record.first_seen = record.first_seen.min(points.first_seen);
+                record.messages_seen = record.messages_seen.max(points.messages_sent);
+            }
+            let scale = record.threshold_scale();
+            if reputation.len() > MAX_REPUTATION_RECORDS {
+                Self::evict_idle_reputation(&mut reputation);
+            }
+            scale
+        };
+
+        // Previous channel messages for context-aware patterns, if enabled
+        let context = if *self.context_analysis_enabled.read().await {
+            let channel_key = format!("{}:{}", message.platform, message.channel);
+            let mut channel_context = self.channel_context.write().await;
+            let recent = channel_context.recent(&channel_key);
+            channel_context.push(&channel_key, message);
+            recent
+        } else {
+            Vec::new()
+        };
+
+        // Check advanced patterns first (skipped entirely when AI strictness is off)
+        if let Some(threshold_scale) = self.ai_strictness.read().await.threshold_scale() {
+            let scale_by_default = *self.reputation_scaling_enabled.read().await;
             let mut pattern_matcher = self.pattern_matcher.write().await;
-            let pattern_matches = pattern_matcher.matches(&message.content);
+            let evaluation = pattern_matcher.evaluate_for_user(
+                &message.content, &context, threshold_scale, reputation_scale, scale_by_default,
+            );
+            let pattern_matches = evaluation.matches;
+            ai_confidence = evaluation.confidences.iter().copied().fold(0.0, f32::max);
+            flagged_for_review = evaluation.flagged_for_review;
+            cap_reached = evaluation.match_cap_reached;
             
             if !pattern_matches.is_empty() {
                 advanced_patterns = pattern_matches.clone();
@@ -91,12 +320,16 @@ impl EnhancedModerationSystem {
             }
         }
 
-        // Check base filters
-        if let Some(base_action) = self.base_moderation.check_spam_filters(message, user_points).await {
-            triggered_filters.push("base_moderation".to_string());
+        // Check base filters, unless the patterns already used up the per-message cap
+        if cap_reached {
+            *self.filter_cap_hits.write().await += 1;
+            debug!("Filter cap of {} reached for message from {}, skipping remaining filters", max_filters, message.username);
+        } else if let Some(verdict) = self.base_moderation.evaluate_spam_filters_within(message, user_points, deadline).await? {
+            // Recorded under the filter's own name so appeals can be traced back to it
+            triggered_filters.push(verdict.filter_name.clone());
             
             // Determine severity based on action type
-            let filter_severity = match base_action {
+            let filter_severity = match verdict.action {
                 ModerationAction::WarnUser { .. } => ViolationSeverity::Minor,
                 ModerationAction::TimeoutUser { duration_seconds } => {
                     if duration_seconds < 300 {
@@ -110,16 +343,45 @@ impl EnhancedModerationSystem {
                 _ => ViolationSeverity::Moderate,
             };
             
-            max_severity = std::cmp::max(max_severity, filter_severity);
+            max_severity = std::cmp::max(max_severity, filter_severity.clone());
+            base_severity = Some(filter_severity);
+            base_verdict = Some(verdict);
+        }
+
+        // AI detections below the global confidence floor are logged for tuning but never
+        // acted on, whatever their own thresholds said; base filter matches still stand
+        if !advanced_patterns.is_empty() {
+            let min_confidence = *self.min_action_confidence.read().await;
+            let confidence = self.calculate_confidence(&triggered_filters, &advanced_patterns, ai_confidence).await;
+            if confidence < min_confidence as f64 {
+                *self.ai_near_misses.write().await += 1;
+                info!("AI near miss for {}: {:?} matched with confidence {:.2}, below the {:.2} needed to act: {}",
+                      message.username, advanced_patterns, confidence, min_confidence, self.log_redaction.redact(&message.content));
+                triggered_filters.retain(|filter| !advanced_patterns.contains(filter));
+                advanced_patterns.clear();
+                max_severity = base_severity.unwrap_or(ViolationSeverity::Minor);
+            }
         }
 
-        // If no violations detected, return None
+        // If no violations detected, return None unless a fail-closed
+        // collection ran out of time, in which case flag for review only
         if triggered_filters.is_empty() {
-            return None;
+            if flagged_for_review.is_empty() {
+                return Ok(None);
+            }
+            return Ok(Some(EnhancedModerationResult {
+                action: ModerationAction::LogOnly,
+                confidence: 0.5,
+                triggered_filters: flagged_for_review.iter().map(|c| format!("pattern_timeout:{}", c)).collect(),
+                advanced_patterns: vec![],
+                escalation_applied: false,
+                response_time_ms: start_time.elapsed().as_secs_f64() * 1000.0,
+                severity: ViolationSeverity::Minor,
+                base_verdict: None,
+            }));
         }
 
         // Apply smart escalation
-        let user_id = format!("{}:{}", message.platform, message.username);
         let escalation_applied = triggered_filters.len() > 1 || max_severity >= ViolationSeverity::Major;
         
         let final_action = if escalation_applied {
@@ -133,11 +395,10 @@ impl EnhancedModerationSystem {
                 message,
             )
         } else {
-            // Use base action for simple violations
-            self.base_moderation.check_spam_filters(message, user_points).await
-                .unwrap_or(ModerationAction::WarnUser { 
-                    message: "Please follow chat rules".to_string() 
-                })
+            // Use the base filter's action for simple violations
+            base_verdict.as_ref().map(|verdict| verdict.action.clone()).unwrap_or(ModerationAction::WarnUser {
+                message: "Please follow chat rules".to_string()
+            })
         };
 
         let response_time = start_time.elapsed().as_secs_f64() * 1000.0;
@@ -166,15 +427,27 @@ impl EnhancedModerationSystem {
             );
         }
 
-        Some(EnhancedModerationResult {
+        if let Some(record) = self.user_reputation.write().await.get_mut(&user_id) {
+            record.violations += 1;
+        }
+
+        let confidence = self.calculate_confidence(&triggered_filters, &advanced_patterns, ai_confidence).await;
+
+        // Base filters record their own violations; keep AI detections alongside them
+        if !advanced_patterns.is_empty() {
+            self.base_moderation.record_ai_violation(message, &advanced_patterns, final_action.clone(), confidence).await;
+        }
+
+        Ok(Some(EnhancedModerationResult {
             action: final_action,
-            confidence: self.calculate_confidence(&triggered_filters, &advanced_patterns).await,
+            confidence,
             triggered_filters,
             advanced_patterns,
             escalation_applied,
             response_time_ms: response_time,
             severity: max_severity,
-        })
+            base_verdict,
+        }))
     }
 
     /// Add advanced pattern to the system
@@ -185,6 +458,60 @@ impl EnhancedModerationSystem {
         Ok(())
     }
 
+    /// Replace the whole pattern set at once, as on a config reload. The new
+    /// matcher keeps this system's per-message filter cap; its collections,
+    /// budgets and minimum message length come from whoever built it.
+    pub async fn replace_pattern_matcher(&self, mut matcher: EnhancedPatternMatcher) {
+        // Held across the swap so a concurrent cap change lands on the new matcher
+        let max_filters = self.max_filters_per_message.read().await;
+        matcher.set_max_matches_per_message(*max_filters);
+        let pattern_count = matcher.patterns.len();
+        *self.pattern_matcher.write().await = matcher;
+        drop(max_filters);
+        debug!("Swapped in {} advanced patterns", pattern_count);
+    }
+
+    /// Add a pattern that belongs to a configured pattern collection
+    pub async fn add_collection_pattern(&self, collection_id: &str, pattern: AdvancedPattern) -> Result<()> {
+        let mut pattern_matcher = self.pattern_matcher.write().await;
+        pattern_matcher.add_collection_pattern(collection_id, pattern);
+        Ok(())
+    }
+
+    /// Add a collection pattern that only runs on messages of at least `min_message_length` characters
+    pub async fn add_collection_pattern_with_min_length(&self, collection_id: &str, pattern: AdvancedPattern, min_message_length: usize) -> Result<()> {
+        let mut pattern_matcher = self.pattern_matcher.write().await;
+        pattern_matcher.add_collection_pattern_with_min_length(collection_id, pattern, min_message_length);
+        Ok(())
+    }
+
+    /// Skip approximate (fuzzy, phonetic, keyboard shift) patterns for shorter messages; 0 disables
+    pub async fn set_min_message_length(&self, chars: usize) {
+        self.pattern_matcher.write().await.set_min_message_length(chars);
+        debug!("Minimum message length for approximate patterns set to {}", chars);
+    }
+
+    /// Set the processing time budget and fallback for a pattern collection
+    pub async fn set_collection_budget(&self, collection_id: &str, budget: CollectionBudget) {
+        let mut pattern_matcher = self.pattern_matcher.write().await;
+        pattern_matcher.set_collection_budget(collection_id, budget);
+        debug!("Set time budget for pattern collection '{}'", collection_id);
+    }
+
+    /// Switch a pattern collection on or off live; takes effect for the next message
+    pub async fn set_pattern_collection_enabled(&self, name: &str, enabled: bool) -> Result<()> {
+        if !self.pattern_matcher.write().await.set_collection_enabled(name, enabled) {
+            return Err(anyhow::anyhow!("Unknown pattern collection: {}", name));
+        }
+        info!("Pattern collection '{}' {}", name, if enabled { "enabled" } else { "disabled" });
+        Ok(())
+    }
+
+    /// Loaded pattern collections and whether each is enabled
+    pub async fn get_pattern_collections(&self) -> Vec<(String, bool)> {
+        self.pattern_matcher.read().await.collections()
+    }
+
     /// Enable/disable enhanced features
     pub async fn set_enhanced_features_enabled(&self, enabled: bool) {
         *self.enhanced_features_enabled.write().await = enabled;
@@ -203,6 +530,97 @@ impl EnhancedModerationSystem {
         info!("Learning mode {}", if enabled { "enabled" } else { "disabled" });
     }
 
+    /// Enable/disable rolling channel context for context-aware patterns
+    pub async fn set_context_analysis_enabled(&self, enabled: bool) {
+        *self.context_analysis_enabled.write().await = enabled;
+        if !enabled {
+            *self.channel_context.write().await = ChannelContextStore::default();
+        }
+        info!("Context analysis {}", if enabled { "enabled" } else { "disabled" });
+    }
+
+    /// Stop evaluating a message once this many filters (patterns and base filters) have
+    /// matched, to bound the work spent on pathological messages; 0 removes the cap
+    pub async fn set_max_filters_per_message(&self, max_filters: usize) {
+        *self.max_filters_per_message.write().await = max_filters;
+        self.pattern_matcher.write().await.set_max_matches_per_message(max_filters);
+        debug!("Maximum filters per message set to {}", max_filters);
+    }
+
+    /// Messages whose filter evaluation stopped at the per-message cap
+    pub async fn get_filter_cap_hits(&self) -> u64 {
+        *self.filter_cap_hits.read().await
+    }
+
+    /// Never act on AI pattern matches whose combined confidence (the best match's
+    /// score, raised by other filters that agree) is below `min_confidence`, even
+    /// when the pattern's own threshold passed; 0.0 removes the floor
+    pub async fn set_min_action_confidence(&self, min_confidence: f32) {
+        let min_confidence = min_confidence.clamp(0.0, 1.0);
+        *self.min_action_confidence.write().await = min_confidence;
+        info!("Minimum confidence for AI moderation actions set to {:.2}", min_confidence);
+    }
+
+    pub async fn get_min_action_confidence(&self) -> f32 {
+        *self.min_action_confidence.read().await
+    }
+
+    /// AI matches that weren't acted on because they fell below the minimum confidence
+    pub async fn get_ai_near_misses(&self) -> u64 {
+        *self.ai_near_misses.read().await
+    }
+
+    /// Enable/disable scaling pattern thresholds by user reputation.
+    /// Applied on top of AI strictness: low-reputation users get stricter
+    /// thresholds, trusted users more lenient ones.
+    pub async fn set_reputation_scaling_enabled(&self, enabled: bool) {
+        *self.reputation_scaling_enabled.write().await = enabled;
+        info!("Reputation-based threshold scaling {}", if enabled { "enabled" } else { "disabled" });
+    }
+
+    /// Opt one pattern in or out of reputation scaling, whatever the system-wide
+    /// setting. Returns false if no pattern has that id.
+    pub async fn set_pattern_reputation_scaling(&self, pattern_id: &str, enabled: bool) -> bool {
+        self.pattern_matcher.write().await.set_reputation_scaling(pattern_id, enabled)
+    }
+
+    /// Forget the users seen least recently, down to 90% of `MAX_REPUTATION_RECORDS`
+    /// so eviction doesn't run on every message
+    fn evict_idle_reputation(reputation: &mut HashMap<String, ReputationRecord>) {
+        let target = MAX_REPUTATION_RECORDS * 9 / 10;
+        let mut by_last_seen: Vec<(DateTime<Utc>, String)> = reputation.iter()
+            .map(|(user_id, record)| (record.last_seen, user_id.clone()))
+            .collect();
+        by_last_seen.sort_unstable();
+
+        let excess = reputation.len().saturating_sub(target);
+        for (_, user_id) in by_last_seen.into_iter().take(excess) {
+            reputation.remove(&user_id);
+        }
+        debug!("Evicted {} idle reputation records", excess);
+    }
+
+    /// Get a user's reputation (0.0-1.0) by "platform:username", shared by linked
+    /// accounts; unseen users get the new-user score
+    pub async fn get_reputation(&self, user_id: &str) -> f32 {
+        let user_id = self.base_moderation.identities().read().await.resolve_key(user_id);
+        self.user_reputation.read().await
+            .get(&user_id)
+            .map(ReputationRecord::score)
+            .unwrap_or_else(|| ReputationRecord::new().score())
+    }
+
+    /// Set AI strictness; see [`AiStrictness`] for how it interacts with per-filter thresholds
+    pub async fn set_ai_strictness(&self, level: AiStrictness) {
+        *self.ai_strictness.write().await = level;
+        info!("AI strictness set to {}", level.as_str());
+    }
+
+    /// Get the current AI strictness level
+    pub async fn get_ai_strictness(&self) -> AiStrictness {
+        *self.ai_strictness.read().await
+    }
+
     /// Record user feedback for machine learning
     pub async fn record_user_feedback(
         &self,
@@ -231,7 +649,7 @@ impl EnhancedModerationSystem {
                 }
                 UserReportType::MissedViolation => {
                     // Could trigger pattern refinement or new pattern creation
-                    warn!("Missed violation reported for content: {}", message_content);
+                    warn!("Missed violation reported for content: {}", self.log_redaction.redact(message_content));
                 }
                 _ => {}
             }
@@ -246,6 +664,32 @@ impl EnhancedModerationSystem {
         Ok(())
     }
 
+    /// Record an appeal against the last moderation action taken on the user,
+    /// counted against the filter that took it (once per action, however often
+    /// it's appealed). Returns that filter, or `None` if the user has no recent
+    /// violation to appeal.
+    pub async fn record_appeal(&self, message: &ChatMessage, reason: &str) -> Result<Option<String>> {
+        let Some((violation, first_appeal)) = self.base_moderation.appeal_last_violation(&message.platform, &message.username).await else {
+            debug!("Appeal from {} has no recent violation to link to", message.username);
+            return Ok(None);
+        };
+        if !first_appeal {
+            debug!("{} appealed the same '{}' action again", message.username, violation.filter_name);
+            return Ok(Some(violation.filter_name));
+        }
+
+        let user_id = self.base_moderation.identity_key(&message.platform, &message.username).await;
+        self.analytics_system.record_appeal(&violation.filter_name).await;
+        self.record_user_feedback(
+            &violation.filter_name,
+            &user_id,
+            UserReportType::FalsePositive,
+            &violation.message_content,
+            Some(reason.to_string()),
+        ).await?;
+        Ok(Some(violation.filter_name))
+    }
+
     /// Record moderator review
     pub async fn record_moderator_review(
         &self,
@@ -331,6 +775,20 @@ impl EnhancedModerationSystem {
         Ok(dashboard)
     }
 
+    /// Get the most recent messages matched by a filter, newest first
+    pub async fn get_recent_matches(&self, filter_id: &str, n: usize) -> Vec<RecentMatch> {
+        self.analytics_system.get_recent_matches(filter_id, n).await
+    }
+
+    /// Filters and AI patterns that catch `message`'s text, with where they matched
+    pub async fn explain_message(&self, message: &ChatMessage) -> Vec<MatchExplanation> {
+        let mut explanations = self.base_moderation.explain_message(message).await;
+        if *self.enhanced_features_enabled.read().await {
+            explanations.extend(self.pattern_matcher.read().await.explain(&message.content));
+        }
+        explanations
+    }
+
     /// Get filter effectiveness report
     pub async fn get_effectiveness_report(&self) -> Result<EffectivenessReport> {
         let pattern_stats = {
@@ -358,6 +816,7 @@ impl EnhancedModerationSystem {
                 peak_load: analytics_dashboard.global_metrics.peak_load_messages_per_second,
             },
             recommendations: analytics_dashboard.system_recommendations,
+            filter_appeals: self.analytics_system.get_appeal_stats().await,
         })
     }
 
@@ -396,8 +855,15 @@ impl EnhancedModerationSystem {
         })
     }
 
-    /// Calculate confidence score for a moderation decision
-    async fn calculate_confidence(&self, triggered_filters: &[String], advanced_patterns: &[String]) -> f64 {
+    /// Calculate confidence score for a moderation decision. AI decisions start from
+    /// how closely their best pattern matched (`pattern_confidence`), and each other
+    /// filter that triggered on the message corroborates them.
+    async fn calculate_confidence(&self, triggered_filters: &[String], advanced_patterns: &[String], pattern_confidence: f32) -> f64 {
+        if !advanced_patterns.is_empty() {
+            let corroboration = (triggered_filters.len().saturating_sub(1) as f64 * 0.1).min(0.3);
+            return (pattern_confidence as f64 + corroboration).min(1.0);
+        }
+
         let base_confidence = 0.8;
         
         // More filters triggered = higher confidence
@@ -460,6 +926,7 @@ impl EnhancedModerationSystem {
             enhanced_features_enabled: *self.enhanced_features_enabled.read().await,
             auto_optimization_enabled: *self.auto_optimization_enabled.read().await,
             learning_mode_enabled: *self.learning_mode.read().await,
+            ai_strictness: *self.ai_strictness.read().await,
             total_patterns: {
                 let pattern_matcher = self.pattern_matcher.read().await;
                 pattern_matcher.patterns.len()
@@ -481,6 +948,26 @@ pub struct EnhancedModerationResult {
     pub escalation_applied: bool,
     pub response_time_ms: f64,
     pub severity: ViolationSeverity,
+    /// The rule-based filter's verdict, when one matched
+    pub base_verdict: Option<SpamFilterVerdict>,
+}
+
+impl EnhancedModerationResult {
+    /// The outcome as a spam filter verdict for the message pipeline: a lone
+    /// rule keeps its own name, notice and log level; anything involving AI
+    /// patterns is reported under every triggered filter, with its confidence
+    pub fn into_verdict(self) -> SpamFilterVerdict {
+        match self.base_verdict {
+            Some(verdict) if self.advanced_patterns.is_empty() => SpamFilterVerdict { action: self.action, ..verdict },
+            base_verdict => SpamFilterVerdict {
+                action: self.action,
+                filter_name: self.triggered_filters.join(","),
+                notice: None,
+                log_level: base_verdict.and_then(|verdict| verdict.log_level),
+                confidence: (!self.advanced_patterns.is_empty()).then_some(self.confidence),
+            },
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -500,6 +987,8 @@ pub struct EffectivenessReport {
     pub user_satisfaction: f64,
     pub performance_metrics: PerformanceMetrics,
     pub recommendations: Vec<crate::bot::realtime_analytics::SystemRecommendation>,
+    /// Appeal-based false positive rate per filter, most appealed first
+    pub filter_appeals: Vec<FilterAppealStats>,
 }
 
 #[derive(Debug)]
@@ -521,6 +1010,7 @@ pub struct SystemStatus {
     pub enhanced_features_enabled: bool,
     pub auto_optimization_enabled: bool,
     pub learning_mode_enabled: bool,
+    pub ai_strictness: AiStrictness,
     pub total_patterns: usize,
     pub system_health_score: f64,
     pub active_alerts: usize,
@@ -545,17 +1035,8 @@ mod tests {
         enhanced.setup_default_advanced_patterns().await.unwrap();
         
         // Test message
-        let message = ChatMessage {
-            platform: "test".to_string(),
-            channel: "testchannel".to_string(),
-            username: "testuser".to_string(),
-            display_name: Some("Test User".to_string()),
-            content: "sp4m message with l33tsp34k".to_string(),
-            timestamp: Utc::now(),
-            user_badges: vec![],
-            is_mod: false,
-            is_subscriber: false,
-        };
+        let message = ChatMessage::new("test", "testchannel", "testuser", "sp4m message with l33tsp34k")
+            .with_display_name("Test User");
         
         // Check if enhanced system detects the pattern
         let result = enhanced.check_message_enhanced(&message, None).await;
@@ -603,4 +1084,343 @@ mod tests {
         // Export should succeed even with empty filter list
         assert!(result.is_ok() || export_path.exists());
     }
-}
\ No newline at end of file
+
+    fn borderline_message() -> ChatMessage {
+        ChatMessage::new("test", "testchannel", "testuser", "bdwrd")
+    }
+
+    #[tokio::test]
+    async fn test_ai_strictness_scales_thresholds() {
+        let base_moderation = Arc::new(crate::bot::moderation::ModerationSystem::new());
+        let enhanced = EnhancedModerationSystem::new(base_moderation);
+        enhanced.add_advanced_pattern(AdvancedPattern::FuzzyMatch {
+            pattern: "badword".to_string(),
+            threshold: 0.8,
+        }).await.unwrap();
+
+        let message = borderline_message();
+
+        // Borderline similarity passes at normal strictness
+        assert_eq!(enhanced.get_ai_strictness().await, AiStrictness::Normal);
+        assert!(enhanced.check_message_enhanced(&message, None).await.is_none());
+
+        // Raising strictness lowers the threshold enough to flag it
+        enhanced.set_ai_strictness(AiStrictness::High).await;
+        let result = enhanced.check_message_enhanced(&message, None).await;
+        assert!(result.is_some());
+        assert!(!result.unwrap().advanced_patterns.is_empty());
+
+        // Off disables advanced patterns entirely
+        enhanced.set_ai_strictness(AiStrictness::Off).await;
+        assert!(enhanced.check_message_enhanced(&message, None).await.is_none());
+    }
+
+    #[test]
+    fn test_ai_strictness_parse() {
+        assert_eq!(AiStrictness::parse("HIGH"), Some(AiStrictness::High));
+        assert_eq!(AiStrictness::parse("off"), Some(AiStrictness::Off));
+        assert_eq!(AiStrictness::parse("extreme"), None);
+    }
+
+    #[tokio::test]
+    async fn test_pattern_timeout_fallback() {
+        use crate::bot::pattern_matching::{CollectionBudget, TimeoutFallback};
+        use std::time::Duration;
+
+        let base_moderation = Arc::new(crate::bot::moderation::ModerationSystem::new());
+        let enhanced = EnhancedModerationSystem::new(base_moderation);
+        enhanced.add_collection_pattern("slow", AdvancedPattern::FuzzyMatch {
+            pattern: "badword".to_string(),
+            threshold: 0.8,
+        }).await.unwrap();
+
+        let message = ChatMessage {
+            content: "a perfectly ordinary message".to_string(),
+            ..borderline_message()
+        };
+
+        // Fail-open: budget exceeded, message allowed
+        enhanced.set_collection_budget("slow", CollectionBudget {
+            max_processing_time: Duration::ZERO,
+            fallback: TimeoutFallback::FailOpen,
+        }).await;
+        assert!(enhanced.check_message_enhanced(&message, None).await.is_none());
+
+        // Fail-closed: budget exceeded, message flagged for review only
+        enhanced.set_collection_budget("slow", CollectionBudget {
+            max_processing_time: Duration::ZERO,
+            fallback: TimeoutFallback::FailClosed,
+        }).await;
+        let result = enhanced.check_message_enhanced(&message, None).await.unwrap();
+        assert!(matches!(result.action, ModerationAction::LogOnly));
+        assert_eq!(result.triggered_filters, vec!["pattern_timeout:slow".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_context_aware_reply_detection() {
+        let base_moderation = Arc::new(crate::bot::moderation::ModerationSystem::new());
+        let enhanced = EnhancedModerationSystem::new(base_moderation);
+        enhanced.add_advanced_pattern(AdvancedPattern::ContextualReply {
+            context_pattern: "hurt myself".to_string(),
+            max_reply_words: 4,
+        }).await.unwrap();
+        enhanced.set_context_analysis_enabled(true).await;
+
+        let reply = ChatMessage {
+            username: "replier".to_string(),
+            content: "yes do it".to_string(),
+            ..borderline_message()
+        };
+
+        // Benign on its own
+        assert!(enhanced.check_message_enhanced(&reply, None).await.is_none());
+
+        let prior = ChatMessage {
+            content: "honestly i want to hurt myself".to_string(),
+            ..borderline_message()
+        };
+        assert!(enhanced.check_message_enhanced(&prior, None).await.is_none());
+
+        // Flagged once the prior message is in the channel's context
+        let result = enhanced.check_message_enhanced(&reply, None).await;
+        assert!(result.is_some());
+        assert!(!result.unwrap().advanced_patterns.is_empty());
+
+        // Context is per channel
+        let other_channel = ChatMessage { channel: "otherchannel".to_string(), ..reply };
+        assert!(enhanced.check_message_enhanced(&other_channel, None).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_reputation_scales_thresholds() {
+        let base_moderation = Arc::new(crate::bot::moderation::ModerationSystem::new());
+        let enhanced = EnhancedModerationSystem::new(base_moderation);
+        enhanced.add_advanced_pattern(AdvancedPattern::FuzzyMatch {
+            pattern: "badword".to_string(),
+            threshold: 0.8,
+        }).await.unwrap();
+        enhanced.set_reputation_scaling_enabled(true).await;
+
+        // Brand-new account: strict threshold flags the borderline message
+        let new_user = ChatMessage { username: "newcomer".to_string(), ..borderline_message() };
+        assert!(enhanced.check_message_enhanced(&new_user, None).await.is_some());
+        assert!(enhanced.get_reputation("test:newcomer").await < 0.2);
+
+        // Long-time chatter: same message passes
+        let veteran = ChatMessage { username: "veteran".to_string(), ..borderline_message() };
+        let mut points = UserPoints::new("test".to_string(), "veteran".to_string(), None);
+        points.first_seen = Utc::now() - chrono::Duration::days(90);
+        points.messages_sent = 1000;
+        assert!(enhanced.check_message_enhanced(&veteran, Some(&points)).await.is_none());
+        assert!(enhanced.get_reputation("test:veteran").await > 0.9);
+
+        // Unknown users get the new-user score
+        assert!((enhanced.get_reputation("test:nobody").await - 0.2).abs() < 0.01);
+    }
+
+    #[tokio::test]
+    async fn test_reputation_scaling_per_pattern() {
+        let base_moderation = Arc::new(crate::bot::moderation::ModerationSystem::new());
+        let enhanced = EnhancedModerationSystem::new(base_moderation);
+        let mut matcher = EnhancedPatternMatcher::new();
+        matcher.add_configured_pattern("spam", "borderline_badword", AdvancedPattern::FuzzyMatch {
+            pattern: "badword".to_string(),
+            threshold: 0.8,
+        }, None);
+        enhanced.replace_pattern_matcher(matcher).await;
+        let new_user = ChatMessage { username: "newcomer".to_string(), ..borderline_message() };
+
+        // Scaling is off system-wide, but this pattern opts in
+        assert!(enhanced.check_message_enhanced(&new_user, None).await.is_none());
+        assert!(enhanced.set_pattern_reputation_scaling("borderline_badword", true).await);
+        assert!(enhanced.check_message_enhanced(&new_user, None).await.is_some());
+
+        // ...and can opt out when it's on system-wide
+        enhanced.set_reputation_scaling_enabled(true).await;
+        assert!(enhanced.set_pattern_reputation_scaling("borderline_badword", false).await);
+        assert!(enhanced.check_message_enhanced(&new_user, None).await.is_none());
+        assert!(!enhanced.set_pattern_reputation_scaling("missing", true).await);
+    }
+
+    #[tokio::test]
+    async fn test_idle_reputation_records_are_evicted() {
+        let mut reputation = HashMap::new();
+        let now = Utc::now();
+        for i in 0..=MAX_REPUTATION_RECORDS {
+            let mut record = ReputationRecord::new();
+            record.last_seen = now - chrono::Duration::seconds((MAX_REPUTATION_RECORDS - i) as i64);
+            reputation.insert(format!("test:user{}", i), record);
+        }
+
+        EnhancedModerationSystem::evict_idle_reputation(&mut reputation);
+        assert_eq!(reputation.len(), MAX_REPUTATION_RECORDS * 9 / 10);
+        assert!(!reputation.contains_key("test:user0"));
+        assert!(reputation.contains_key(&format!("test:user{}", MAX_REPUTATION_RECORDS)));
+    }
+
+    #[tokio::test]
+    async fn test_appeals_are_linked_to_their_filter() {
+        let base_moderation = Arc::new(crate::bot::moderation::ModerationSystem::new());
+        for (name, word) in [("crypto", "bitcoin"), ("links", "discord")] {
+            base_moderation.add_blacklist_filter(
+                name.to_string(),
+                vec![word.to_string()],
+                false,
+                false,
+                crate::types::ExemptionLevel::None,
+                60,
+                None,
+            ).await.unwrap();
+        }
+        let enhanced = EnhancedModerationSystem::new(base_moderation);
+
+        for user in ["alice", "bob", "carol", "dave"] {
+            let message = ChatMessage::new("twitch", "testchannel", user, "free bitcoin");
+            let result = enhanced.check_message_enhanced(&message, None).await.unwrap();
+            assert_eq!(result.triggered_filters, vec!["crypto".to_string()]);
+        }
+        let link = ChatMessage::new("twitch", "testchannel", "erin", "join my discord");
+        enhanced.check_message_enhanced(&link, None).await.unwrap();
+
+        // The appeal is traced to the filter that actioned the user
+        let appeal = ChatMessage::new("twitch", "testchannel", "alice", "!appeal I was joking");
+        assert_eq!(enhanced.record_appeal(&appeal, "I was joking").await.unwrap().as_deref(), Some("crypto"));
+        // Appealing the same action again doesn't count twice
+        assert_eq!(enhanced.record_appeal(&appeal, "really, I was joking").await.unwrap().as_deref(), Some("crypto"));
+        let innocent = ChatMessage::new("twitch", "testchannel", "frank", "!appeal why");
+        assert_eq!(enhanced.record_appeal(&innocent, "why").await.unwrap(), None);
+
+        let report = enhanced.get_effectiveness_report().await.unwrap();
+        assert_eq!(report.filter_appeals.len(), 1);
+        let crypto = &report.filter_appeals[0];
+        assert_eq!(crypto.filter_id, "crypto");
+        assert_eq!((crypto.triggers, crypto.appeals), (4, 1));
+        assert_eq!(crypto.false_positive_rate, 0.25);
+
+        let analytics = enhanced.analytics_system.get_filter_analytics("crypto").await.unwrap();
+        assert_eq!(analytics.user_reports.len(), 1);
+        assert_eq!(analytics.user_reports[0].message_content, "free bitcoin");
+    }
+
+    #[tokio::test]
+    async fn test_pattern_collection_toggle() {
+        let base_moderation = Arc::new(crate::bot::moderation::ModerationSystem::new());
+        let enhanced = EnhancedModerationSystem::new(base_moderation);
+        enhanced.add_collection_pattern("crypto_scams", AdvancedPattern::Leetspeak("bitcoin".to_string())).await.unwrap();
+        enhanced.add_collection_pattern("link_spam", AdvancedPattern::Leetspeak("discord".to_string())).await.unwrap();
+
+        let crypto = ChatMessage::new("test", "testchannel", "viewer", "free b1tc01n giveaway");
+        let link = ChatMessage::new("test", "testchannel", "viewer", "join my d1sc0rd");
+        assert!(enhanced.check_message_enhanced(&crypto, None).await.is_some());
+
+        enhanced.set_pattern_collection_enabled("crypto_scams", false).await.unwrap();
+        assert!(enhanced.check_message_enhanced(&crypto, None).await.is_none());
+        assert!(enhanced.check_message_enhanced(&link, None).await.is_some());
+
+        enhanced.set_pattern_collection_enabled("crypto_scams", true).await.unwrap();
+        assert!(enhanced.check_message_enhanced(&crypto, None).await.is_some());
+
+        assert!(enhanced.set_pattern_collection_enabled("nope", false).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_explanations_cover_filters_and_ai_patterns() {
+        let base_moderation = Arc::new(crate::bot::moderation::ModerationSystem::new());
+        base_moderation.add_spam_filter(crate::types::SpamFilterType::Blacklist {
+            patterns: vec![crate::types::BlacklistPattern::Literal("giveaway".to_string())],
+            case_sensitive: false,
+            whole_words_only: true,
+            except_patterns: Vec::new(),
+        }).await.unwrap();
+        let enhanced = EnhancedModerationSystem::new(base_moderation);
+        enhanced.add_advanced_pattern(AdvancedPattern::Leetspeak("bitcoin".to_string())).await.unwrap();
+
+        let text = "free b1tc01n Giveaway";
+        let explanations = enhanced.explain_message(&ChatMessage::new("test", "testchannel", "mod", text)).await;
+        let spans: Vec<&str> = explanations.iter()
+            .map(|e| e.span.as_ref().map(|span| &text[span.start..span.end]).unwrap())
+            .collect();
+        assert_eq!(spans, ["Giveaway", "b1tc01n"]);
+        assert_eq!(explanations[0].filter, "blacklist");
+        assert_eq!(explanations[1].filter, "leetspeak#0");
+    }
+
+    #[tokio::test]
+    async fn test_filter_cap_stops_evaluation() {
+        let base_moderation = Arc::new(crate::bot::moderation::ModerationSystem::new());
+        base_moderation.add_blacklist_filter(
+            "crypto".to_string(),
+            vec!["bitcoin".to_string()],
+            false,
+            false,
+            crate::types::ExemptionLevel::None,
+            60,
+            None,
+        ).await.unwrap();
+        let enhanced = EnhancedModerationSystem::new(base_moderation.clone());
+        for word in ["free", "giveaway", "discord"] {
+            enhanced.add_advanced_pattern(AdvancedPattern::Leetspeak(word.to_string())).await.unwrap();
+        }
+        let message = ChatMessage::new("twitch", "testchannel", "viewer", "free bitcoin giveaway on discord");
+
+        // Uncapped, every pattern and the base filter match
+        let result = enhanced.check_message_enhanced(&message, None).await.unwrap();
+        assert_eq!(result.triggered_filters.len(), 4);
+        assert_eq!(enhanced.get_filter_cap_hits().await, 0);
+
+        enhanced.set_max_filters_per_message(2).await;
+        let checked_before = base_moderation.get_moderation_metrics().await.messages_checked;
+        let result = enhanced.check_message_enhanced(&message, None).await.unwrap();
+        assert_eq!(result.triggered_filters.len(), 2);
+        assert!(!result.triggered_filters.contains(&"crypto".to_string()));
+        assert_eq!(enhanced.get_filter_cap_hits().await, 1);
+        // The base filters were never run for the capped message
+        assert_eq!(base_moderation.get_moderation_metrics().await.messages_checked, checked_before);
+
+        // A cap the message doesn't reach changes nothing
+        enhanced.set_max_filters_per_message(10).await;
+        let result = enhanced.check_message_enhanced(&message, None).await.unwrap();
+        assert_eq!(result.triggered_filters.len(), 4);
+        assert_eq!(enhanced.get_filter_cap_hits().await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_ai_matches_below_the_confidence_floor_are_not_actioned() {
+        let base_moderation = Arc::new(crate::bot::moderation::ModerationSystem::new());
+        base_moderation.add_blacklist_filter(
+            "crypto".to_string(),
+            vec!["bitcoin".to_string()],
+            false,
+            false,
+            crate::types::ExemptionLevel::None,
+            60,
+            None,
+        ).await.unwrap();
+        let enhanced = EnhancedModerationSystem::new(base_moderation);
+        enhanced.add_advanced_pattern(AdvancedPattern::FuzzyMatch { pattern: "badword".to_string(), threshold: 0.6 }).await.unwrap();
+        let near_miss = ChatMessage::new("twitch", "testchannel", "viewer", "what a badwurd");
+
+        // Similarity 0.86 clears the pattern's own 0.6 threshold
+        let result = enhanced.check_message_enhanced(&near_miss, None).await.unwrap();
+        assert_eq!(result.advanced_patterns.len(), 1);
+
+        enhanced.set_min_action_confidence(0.9).await;
+        assert!(enhanced.check_message_enhanced(&near_miss, None).await.is_none());
+        assert_eq!(enhanced.get_ai_near_misses().await, 1);
+
+        // Confident matches still act, and a filter that agrees lifts a near miss over the floor
+        let exact = ChatMessage::new("twitch", "testchannel", "viewer", "what a badword");
+        assert_eq!(enhanced.check_message_enhanced(&exact, None).await.unwrap().advanced_patterns.len(), 1);
+        let with_filter = ChatMessage::new("twitch", "testchannel", "other", "badwurd bitcoin");
+        let result = enhanced.check_message_enhanced(&with_filter, None).await.unwrap();
+        assert_eq!(result.advanced_patterns.len(), 1);
+        assert!(result.confidence >= 0.9);
+
+        // Base filters still stand alongside a held-back match
+        enhanced.set_min_action_confidence(1.0).await;
+        let result = enhanced.check_message_enhanced(&with_filter, None).await.unwrap();
+        assert_eq!(result.triggered_filters, vec!["crypto".to_string()]);
+        assert!(result.advanced_patterns.is_empty());
+        assert_eq!(enhanced.get_ai_near_misses().await, 2);
+    }
+}
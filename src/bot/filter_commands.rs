@@ -1,17 +1,36 @@
 use anyhow::Result;
-use log::{warn, info};
+use log::{error, warn, info};
 use std::sync::Arc;
+use tokio::sync::RwLock;
 
+use crate::bot::enhanced_moderation::{AiStrictness, EnhancedModerationSystem};
 use crate::bot::moderation::ModerationSystem;
+use crate::config::{ConfigurationManager, FilterSearchField};
 use crate::types::{ChatMessage, SpamFilterType, ExemptionLevel, ModerationEscalation, ModerationAction};
 
 pub struct FilterCommands {
     moderation_system: Arc<ModerationSystem>,
+    /// Source of configured filters for `!findfilter`
+    config_manager: RwLock<Option<Arc<ConfigurationManager>>>,
+    /// AI moderation the bot runs, for `!aistrict` and `!matches`
+    enhanced_moderation: RwLock<Option<Arc<EnhancedModerationSystem>>>,
 }
 
 impl FilterCommands {
     pub fn new(moderation_system: Arc<ModerationSystem>) -> Self {
-        Self { moderation_system }
+        Self {
+            moderation_system,
+            config_manager: RwLock::new(None),
+            enhanced_moderation: RwLock::new(None),
+        }
+    }
+
+    pub async fn set_config_manager(&self, config_manager: Arc<ConfigurationManager>) {
+        *self.config_manager.write().await = Some(config_manager);
+    }
+
+    pub async fn set_enhanced_moderation(&self, enhanced_moderation: Arc<EnhancedModerationSystem>) {
+        *self.enhanced_moderation.write().await = Some(enhanced_moderation);
     }
 
     /// Process filter-related commands (!filters, !blacklist, etc.)
@@ -22,6 +41,12 @@ impl FilterCommands {
         message: &ChatMessage,
         response_sender: &tokio::sync::mpsc::Sender<(String, String, String)>,
     ) -> Result<bool> {
+        // Anyone can appeal a moderation action taken on them
+        if command == "appeal" {
+            self.handle_appeal_command(args, message, response_sender).await?;
+            return Ok(true);
+        }
+
         // Only moderators can manage filters
         if !message.is_mod {
             return Ok(false);
@@ -44,6 +69,34 @@ impl FilterCommands {
                 self.handle_filter_stats_command(message, response_sender).await?;
                 Ok(true)
             }
+            "why" => {
+                self.handle_why_command(args, message, response_sender).await?;
+                Ok(true)
+            }
+            "suggestfilters" => {
+                self.handle_suggest_filters_command(args, message, response_sender).await?;
+                Ok(true)
+            }
+            "findfilter" => {
+                self.handle_find_filter_command(args, message, response_sender).await?;
+                Ok(true)
+            }
+            "aistrict" => {
+                self.handle_ai_strict_command(args, message, response_sender).await?;
+                Ok(true)
+            }
+            "matches" => {
+                self.handle_matches_command(args, message, response_sender).await?;
+                Ok(true)
+            }
+            "patterns" => {
+                self.handle_patterns_command(args, message, response_sender).await?;
+                Ok(true)
+            }
+            "testfilter" => {
+                self.handle_test_filter_command(message, response_sender).await?;
+                Ok(true)
+            }
             _ => Ok(false),
         }
     }
@@ -56,7 +109,7 @@ impl FilterCommands {
         response_sender: &tokio::sync::mpsc::Sender<(String, String, String)>,
     ) -> Result<()> {
         if args.is_empty() {
-            let response = "🛡️ Filter Commands: !filters <enable|disable|add|remove|list> | !blacklist <add|remove|list> <pattern> | !filterstats".to_string();
+            let response = "🛡️ Filter Commands: !filters <enable|disable|add|remove|list> | !blacklist <add|remove|list> <pattern> | !filterstats | !why @user | !suggestfilters [count]".to_string();
             self.send_response(response, message, response_sender).await?;
             return Ok(());
         }
@@ -311,6 +364,314 @@ impl FilterCommands {
         Ok(())
     }
 
+    /// Handle !why @user - explain the user's most recent moderation action
+    async fn handle_why_command(
+        &self,
+        args: &[&str],
+        message: &ChatMessage,
+        response_sender: &tokio::sync::mpsc::Sender<(String, String, String)>,
+    ) -> Result<()> {
+        let Some(target) = args.first().map(|a| a.trim_start_matches('@')).filter(|a| !a.is_empty()) else {
+            let response = "Usage: !why @user".to_string();
+            self.send_response(response, message, response_sender).await?;
+            return Ok(());
+        };
+
+        let response = match self.moderation_system.get_last_violation(&message.platform, target).await {
+            Some(violation) => {
+                let action = match &violation.action_taken {
+                    ModerationAction::DeleteMessage => "message deleted".to_string(),
+                    ModerationAction::TimeoutUser { duration_seconds } => format!("timed out {}s", duration_seconds),
+                    ModerationAction::WarnUser { .. } => "warned".to_string(),
+                    ModerationAction::LogOnly => "logged".to_string(),
+                    ModerationAction::ShadowMute { duration_seconds } => format!("shadow muted {}s", duration_seconds),
+                };
+                let source = match violation.confidence {
+                    Some(confidence) => format!("AI, {:.0}% confidence", confidence * 100.0),
+                    None => "rule".to_string(),
+                };
+                format!(
+                    "🔎 {} was {} by '{}' ({}) {} ago for: \"{}\"",
+                    target,
+                    action,
+                    violation.filter_name,
+                    source,
+                    Self::format_elapsed(chrono::Utc::now() - violation.timestamp),
+                    Self::snippet(&violation.message_content),
+                )
+            }
+            None => format!("🔎 No recent moderation actions for {}", target),
+        };
+
+        self.send_response(response, message, response_sender).await?;
+        Ok(())
+    }
+
+    fn format_elapsed(elapsed: chrono::Duration) -> String {
+        let seconds = elapsed.num_seconds().max(0);
+        match seconds {
+            0..=59 => format!("{}s", seconds),
+            60..=3599 => format!("{}m", seconds / 60),
+            3600..=86399 => format!("{}h", seconds / 3600),
+            _ => format!("{}d", seconds / 86400),
+        }
+    }
+
+    /// Handle !suggestfilters [count] - candidate blacklist patterns from recent violations
+    async fn handle_suggest_filters_command(
+        &self,
+        args: &[&str],
+        message: &ChatMessage,
+        response_sender: &tokio::sync::mpsc::Sender<(String, String, String)>,
+    ) -> Result<()> {
+        const DEFAULT_SUGGESTIONS: usize = 5;
+        const MAX_SUGGESTIONS: usize = 10;
+
+        let limit = args.first()
+            .and_then(|a| a.parse::<usize>().ok())
+            .unwrap_or(DEFAULT_SUGGESTIONS)
+            .clamp(1, MAX_SUGGESTIONS);
+
+        let suggestions = self.moderation_system.suggest_filters(limit).await;
+        let response = if suggestions.is_empty() {
+            "💡 No filter suggestions yet - not enough repeated phrases in recent violations".to_string()
+        } else {
+            let list: Vec<String> = suggestions.iter()
+                .map(|s| format!("\"{}\" ({}x)", s.pattern, s.frequency))
+                .collect();
+            format!("💡 Suggested blacklist patterns: {} | Add with !blacklist add <pattern>", list.join(", "))
+        };
+
+        self.send_response(response, message, response_sender).await?;
+        Ok(())
+    }
+
+    /// Handle !findfilter <query> - configured filters whose name, pattern or tag
+    /// contains the query (`tag:<name>` for an exact tag)
+    async fn handle_find_filter_command(
+        &self,
+        args: &[&str],
+        message: &ChatMessage,
+        response_sender: &tokio::sync::mpsc::Sender<(String, String, String)>,
+    ) -> Result<()> {
+        const MAX_LISTED: usize = 5;
+
+        let query = args.join(" ");
+        let response = if query.trim().is_empty() {
+            "Usage: !findfilter <phrase> | !findfilter tag:<tag>".to_string()
+        } else if let Some(config_manager) = self.config_manager.read().await.clone() {
+            let matches = config_manager.find_filters(&query).await;
+            if matches.is_empty() {
+                format!("🔎 No filters match \"{}\"", query)
+            } else {
+                let listed: Vec<String> = matches.iter().take(MAX_LISTED).map(|m| {
+                    let status = if m.enabled { "✅" } else { "❌" };
+                    match m.field {
+                        FilterSearchField::Name => format!("{} {}", status, m.id),
+                        FilterSearchField::Pattern => format!("{} {} (pattern \"{}\")", status, m.id, Self::snippet(&m.matched)),
+                        FilterSearchField::Tag => format!("{} {} (tag {})", status, m.id, m.matched),
+                    }
+                }).collect();
+                let more = matches.len().saturating_sub(MAX_LISTED);
+                format!("🔎 {} filter(s) match \"{}\": {}{}", matches.len(), query, listed.join(" | "),
+                        if more > 0 { format!(" | +{} more", more) } else { String::new() })
+            }
+        } else {
+            "🔎 Filter search needs the configuration system, which isn't running".to_string()
+        };
+
+        self.send_response(response, message, response_sender).await?;
+        Ok(())
+    }
+
+    /// Handle !aistrict [off|low|normal|high] - show or set how readily AI patterns act
+    async fn handle_ai_strict_command(
+        &self,
+        args: &[&str],
+        message: &ChatMessage,
+        response_sender: &tokio::sync::mpsc::Sender<(String, String, String)>,
+    ) -> Result<()> {
+        const USAGE: &str = "Usage: !aistrict <off|low|normal|high>";
+
+        let response = match self.enhanced_moderation.read().await.clone() {
+            None => "🤖 AI strictness needs AI moderation, which isn't running".to_string(),
+            Some(enhanced) => match args.first() {
+                None => format!("🤖 AI strictness: {}. {}", enhanced.get_ai_strictness().await.as_str(), USAGE),
+                Some(arg) => match AiStrictness::parse(arg) {
+                    Some(level) => {
+                        enhanced.set_ai_strictness(level).await;
+                        info!("AI strictness set to {} by {}", level.as_str(), message.username);
+                        format!("🤖 AI strictness set to {}", level.as_str())
+                    }
+                    None => USAGE.to_string(),
+                },
+            },
+        };
+
+        self.send_response(response, message, response_sender).await?;
+        Ok(())
+    }
+
+    /// Handle !matches <filter> [count] - the last few (privacy-trimmed) messages a filter caught
+    async fn handle_matches_command(
+        &self,
+        args: &[&str],
+        message: &ChatMessage,
+        response_sender: &tokio::sync::mpsc::Sender<(String, String, String)>,
+    ) -> Result<()> {
+        const DEFAULT_MATCHES: usize = 3;
+        const MAX_MATCHES: usize = 5;
+
+        let enhanced = self.enhanced_moderation.read().await.clone();
+        let response = match (args.first(), enhanced) {
+            (None, _) => "Usage: !matches <filter> [count]".to_string(),
+            (Some(_), None) => "🔎 Recent matches need AI moderation, which isn't running".to_string(),
+            (Some(filter_id), Some(enhanced)) => {
+                let count = args.get(1)
+                    .and_then(|n| n.parse::<usize>().ok())
+                    .unwrap_or(DEFAULT_MATCHES)
+                    .clamp(1, MAX_MATCHES);
+                let recent = enhanced.get_recent_matches(filter_id, count).await;
+                if recent.is_empty() {
+                    format!("🔎 No recent matches for filter '{}'", filter_id)
+                } else {
+                    let snippets: Vec<String> = recent.iter()
+                        .map(|m| format!("[{}] {}", m.timestamp.format("%H:%M"), m.snippet))
+                        .collect();
+                    format!("🔎 Recent matches for '{}': {}", filter_id, snippets.join(" | "))
+                }
+            }
+        };
+
+        self.send_response(response, message, response_sender).await?;
+        Ok(())
+    }
+
+    /// Handle !patterns [<name> on|off] - list AI pattern collections or switch one live
+    async fn handle_patterns_command(
+        &self,
+        args: &[&str],
+        message: &ChatMessage,
+        response_sender: &tokio::sync::mpsc::Sender<(String, String, String)>,
+    ) -> Result<()> {
+        const USAGE: &str = "Usage: !patterns <name> on|off";
+
+        let Some(enhanced) = self.enhanced_moderation.read().await.clone() else {
+            self.send_response("🧩 Pattern collections need AI moderation, which isn't running".to_string(), message, response_sender).await?;
+            return Ok(());
+        };
+
+        let enabled = match args.get(1).map(|a| a.to_lowercase()).as_deref() {
+            Some("on") => Some(true),
+            Some("off") => Some(false),
+            _ => None,
+        };
+        let response = match (args.first(), enabled) {
+            (Some(name), Some(enabled)) => match enhanced.set_pattern_collection_enabled(name, enabled).await {
+                Ok(()) => {
+                    info!("Pattern collection '{}' {} by {}", name, if enabled { "enabled" } else { "disabled" }, message.username);
+                    format!("🧩 Pattern collection '{}' {}", name, if enabled { "enabled" } else { "disabled" })
+                }
+                Err(e) => format!("❌ {}", e),
+            },
+            _ => {
+                let collections: Vec<String> = enhanced.get_pattern_collections().await.into_iter()
+                    .map(|(name, enabled)| format!("{} ({})", name, if enabled { "on" } else { "off" }))
+                    .collect();
+                if collections.is_empty() {
+                    format!("🧩 No pattern collections loaded. {}", USAGE)
+                } else {
+                    format!("🧩 Pattern collections: {}. {}", collections.join(", "), USAGE)
+                }
+            }
+        };
+
+        self.send_response(response, message, response_sender).await?;
+        Ok(())
+    }
+
+    /// Handle !testfilter <message> - show which filters and AI patterns would catch a message
+    async fn handle_test_filter_command(
+        &self,
+        message: &ChatMessage,
+        response_sender: &tokio::sync::mpsc::Sender<(String, String, String)>,
+    ) -> Result<()> {
+        let text = message.content.split_once(char::is_whitespace)
+            .map(|(_, rest)| rest.trim())
+            .filter(|text| !text.is_empty());
+
+        let response = match (self.enhanced_moderation.read().await.clone(), text) {
+            (None, _) => "🧪 Filter tests need AI moderation, which isn't running".to_string(),
+            (Some(_), None) => "Usage: !testfilter <message>".to_string(),
+            (Some(enhanced), Some(text)) => {
+                // Test as a regular viewer would be moderated, not as the moderator asking
+                let probe = ChatMessage { content: text.to_string(), is_mod: false, ..message.clone() };
+                let explanations = enhanced.explain_message(&probe).await;
+                if explanations.is_empty() {
+                    "🧪 No filter or AI pattern matches that message".to_string()
+                } else {
+                    let details: Vec<String> = explanations.iter()
+                        .map(|e| {
+                            let decoded = if e.matched_text != text { " (decoded)" } else { "" };
+                            let excepted = if e.excepted { " [excepted]" } else { "" };
+                            match &e.span {
+                                Some(_) => format!("{}{}{}: {}", e.filter, excepted, decoded, e.highlighted()),
+                                None => format!("{}{}: whole message", e.filter, excepted),
+                            }
+                        })
+                        .collect();
+                    format!("🧪 Matches: {}", details.join(" | "))
+                }
+            }
+        };
+
+        self.send_response(response, message, response_sender).await?;
+        Ok(())
+    }
+
+    /// Handle !appeal <reason> - appeal the last moderation action taken on the sender,
+    /// counted against the filter that took it
+    async fn handle_appeal_command(
+        &self,
+        args: &[&str],
+        message: &ChatMessage,
+        response_sender: &tokio::sync::mpsc::Sender<(String, String, String)>,
+    ) -> Result<()> {
+        if args.is_empty() {
+            let usage = "Usage: !appeal <reason>. Describe why you think the moderation action was incorrect.";
+            return self.send_response(usage.to_string(), message, response_sender).await;
+        }
+
+        let reason = args.join(" ");
+        let appealed = match self.enhanced_moderation.read().await.clone() {
+            Some(enhanced) => enhanced.record_appeal(message, &reason).await.map(|filter| filter.is_some()),
+            None => Ok(self.moderation_system.appeal_last_violation(&message.platform, &message.username).await.is_some()),
+        };
+        let response = match appealed {
+            Ok(true) => {
+                info!("{} appealed their last moderation action: {}", message.username, reason);
+                format!("📝 Appeal recorded: '{}'. Thank you, this feedback helps tune our filters!", reason)
+            }
+            Ok(false) => "📝 No recent moderation action found to appeal.".to_string(),
+            Err(e) => {
+                error!("Failed to record user appeal: {}", e);
+                "Sorry, your appeal couldn't be recorded. Please try again later.".to_string()
+            }
+        };
+
+        self.send_response(response, message, response_sender).await
+    }
+
+    /// Shorten flagged content for chat
+    fn snippet(content: &str) -> String {
+        const MAX_SNIPPET_CHARS: usize = 50;
+        if content.chars().count() > MAX_SNIPPET_CHARS {
+            format!("{}…", content.chars().take(MAX_SNIPPET_CHARS).collect::<String>())
+        } else {
+            content.to_string()
+        }
+    }
+
     /// Handle adding new filters with advanced configuration
     async fn handle_add_filter_command(
         &self,
@@ -359,7 +720,8 @@ impl FilterCommands {
                 } else {
                     10
                 };
-                SpamFilterType::ExcessiveEmotes { max_count }
+                let count_unique = args.get(2).is_some_and(|mode| mode.eq_ignore_ascii_case("unique"));
+                SpamFilterType::ExcessiveEmotes { max_count, count_unique }
             }
             "symbols" => {
                 let max_percentage = if args.len() > 1 {
@@ -458,4 +820,214 @@ impl FilterCommands {
             .trim_matches('_')
             .to_string()
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn run_why(commands: &FilterCommands, args: &[&str]) -> String {
+        let (tx, mut rx) = tokio::sync::mpsc::channel(10);
+        let moderator = ChatMessage::new("twitch", "testchannel", "moduser", "!why").with_mod(true);
+        assert!(commands.process_command("why", args, &moderator, &tx).await.unwrap());
+        rx.recv().await.unwrap().2
+    }
+
+    #[tokio::test]
+    async fn test_why_explains_rule_violation() {
+        let moderation = Arc::new(ModerationSystem::new());
+        moderation.add_blacklist_filter(
+            "scam_links".to_string(),
+            vec!["freecoins".to_string()],
+            false,
+            false,
+            ExemptionLevel::None,
+            300,
+            None,
+        ).await.unwrap();
+        let commands = FilterCommands::new(Arc::clone(&moderation));
+
+        assert!(run_why(&commands, &["@spammer"]).await.contains("No recent moderation actions"));
+
+        let spam = ChatMessage::new("twitch", "testchannel", "spammer", "get freecoins at my site");
+        assert!(moderation.check_spam_filters(&spam, None).await.is_some());
+        assert!(moderation.check_spam_filters(&spam, None).await.is_some());
+
+        let response = run_why(&commands, &["@Spammer"]).await;
+        assert!(response.contains("timed out 300s"), "{}", response);
+        assert!(response.contains("'scam_links' (rule)"), "{}", response);
+        assert!(response.contains("get freecoins at my site"), "{}", response);
+    }
+
+    #[tokio::test]
+    async fn test_why_reports_ai_confidence() {
+        let moderation = Arc::new(ModerationSystem::new());
+        let commands = FilterCommands::new(Arc::clone(&moderation));
+
+        let message = ChatMessage::new("twitch", "testchannel", "leetuser", "fr33 m0n3y");
+        moderation.record_ai_violation(
+            &message,
+            &["leetspeak".to_string()],
+            ModerationAction::WarnUser { message: "Please follow chat rules".to_string() },
+            0.87,
+        ).await;
+
+        let response = run_why(&commands, &["leetuser"]).await;
+        assert!(response.contains("warned by 'leetspeak' (AI, 87% confidence)"), "{}", response);
+    }
+
+    #[tokio::test]
+    async fn test_why_names_ai_patterns_without_their_text() {
+        use crate::bot::enhanced_moderation::EnhancedModerationSystem;
+        use crate::bot::pattern_matching::AdvancedPattern;
+
+        let moderation = Arc::new(ModerationSystem::new());
+        let enhanced = EnhancedModerationSystem::new(Arc::clone(&moderation));
+        enhanced.add_advanced_pattern(AdvancedPattern::Leetspeak("secretword".to_string())).await.unwrap();
+        let commands = FilterCommands::new(Arc::clone(&moderation));
+
+        let message = ChatMessage::new("twitch", "testchannel", "leetuser", "s3cr3tw0rd");
+        assert!(enhanced.check_message_enhanced(&message, None).await.is_some());
+
+        let response = run_why(&commands, &["leetuser"]).await;
+        assert!(response.contains("'leetspeak#0' (AI"), "{}", response);
+        assert!(!response.contains("secretword"), "{}", response);
+    }
+
+    #[tokio::test]
+    async fn test_why_is_mod_only() {
+        let commands = FilterCommands::new(Arc::new(ModerationSystem::new()));
+        let (tx, _rx) = tokio::sync::mpsc::channel(10);
+        let viewer = ChatMessage::new("twitch", "testchannel", "viewer", "!why @someone");
+        assert!(!commands.process_command("why", &["@someone"], &viewer, &tx).await.unwrap());
+    }
+
+    async fn run_matches(commands: &FilterCommands, args: &[&str]) -> String {
+        let (tx, mut rx) = tokio::sync::mpsc::channel(10);
+        let moderator = ChatMessage::new("twitch", "testchannel", "moduser", "!matches").with_mod(true);
+        assert!(commands.process_command("matches", args, &moderator, &tx).await.unwrap());
+        rx.recv().await.unwrap().2
+    }
+
+    #[tokio::test]
+    async fn test_matches_lists_recent_snippets() {
+        use crate::bot::enhanced_moderation::EnhancedModerationSystem;
+
+        let moderation = Arc::new(ModerationSystem::new());
+        moderation.add_blacklist_filter("scam_links".to_string(), vec!["freecoins".to_string()], false, false, ExemptionLevel::None, 300, None).await.unwrap();
+        let enhanced = Arc::new(EnhancedModerationSystem::new(Arc::clone(&moderation)));
+        let commands = FilterCommands::new(Arc::clone(&moderation));
+
+        assert!(run_matches(&commands, &["scam_links"]).await.contains("isn't running"));
+        commands.set_enhanced_moderation(Arc::clone(&enhanced)).await;
+        assert!(run_matches(&commands, &["scam_links"]).await.contains("No recent matches"));
+
+        for content in ["get freecoins here @friend", "more freecoins"] {
+            let spam = ChatMessage::new("twitch", "testchannel", "spammer", content);
+            assert!(enhanced.check_message_enhanced(&spam, None).await.is_some());
+        }
+        let response = run_matches(&commands, &["scam_links", "1"]).await;
+        assert!(response.starts_with("🔎 Recent matches for 'scam_links':"), "{}", response);
+        assert!(response.contains("more freecoins") && !response.contains("get freecoins"), "{}", response);
+    }
+
+    #[tokio::test]
+    async fn test_testfilter_explains_matches_as_a_viewer() {
+        use crate::bot::enhanced_moderation::EnhancedModerationSystem;
+
+        let moderation = Arc::new(ModerationSystem::new());
+        moderation.add_blacklist_filter("scam_links".to_string(), vec!["freecoins".to_string()], false, false, ExemptionLevel::None, 300, None).await.unwrap();
+        let enhanced = Arc::new(EnhancedModerationSystem::new(Arc::clone(&moderation)));
+        let commands = FilterCommands::new(Arc::clone(&moderation));
+        commands.set_enhanced_moderation(enhanced).await;
+
+        let (tx, mut rx) = tokio::sync::mpsc::channel(10);
+        for (content, expected) in [
+            ("!testfilter get freecoins now", "🧪 Matches: "),
+            ("!testfilter hello there", "🧪 No filter or AI pattern matches that message"),
+            ("!testfilter", "Usage: !testfilter <message>"),
+        ] {
+            let moderator = ChatMessage::new("twitch", "testchannel", "moduser", content).with_mod(true);
+            assert!(commands.process_command("testfilter", &[], &moderator, &tx).await.unwrap());
+            let response = rx.recv().await.unwrap().2;
+            assert!(response.starts_with(expected), "{}", response);
+            if content.contains("freecoins") {
+                assert!(response.contains("freecoins"), "{}", response);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_viewers_appeal_their_last_moderation() {
+        use crate::bot::enhanced_moderation::EnhancedModerationSystem;
+
+        let moderation = Arc::new(ModerationSystem::new());
+        moderation.add_blacklist_filter("scam_links".to_string(), vec!["freecoins".to_string()], false, false, ExemptionLevel::None, 300, None).await.unwrap();
+        let enhanced = Arc::new(EnhancedModerationSystem::new(Arc::clone(&moderation)));
+        let commands = FilterCommands::new(Arc::clone(&moderation));
+        commands.set_enhanced_moderation(Arc::clone(&enhanced)).await;
+
+        let spam = ChatMessage::new("twitch", "testchannel", "alice", "get freecoins");
+        assert!(enhanced.check_message_enhanced(&spam, None).await.is_some());
+
+        let (tx, mut rx) = tokio::sync::mpsc::channel(10);
+        for (user, expected) in [("alice", "📝 Appeal recorded: 'I was joking'"), ("frank", "📝 No recent moderation action")] {
+            let appeal = ChatMessage::new("twitch", "testchannel", user, "!appeal I was joking");
+            assert!(commands.process_command("appeal", &["I", "was", "joking"], &appeal, &tx).await.unwrap());
+            let response = rx.recv().await.unwrap().2;
+            assert!(response.starts_with(expected), "{}", response);
+        }
+
+        let report = enhanced.get_effectiveness_report().await.unwrap();
+        assert_eq!(report.filter_appeals.len(), 1);
+        assert_eq!(report.filter_appeals[0].filter_id, "scam_links");
+    }
+
+    async fn run_findfilter(commands: &FilterCommands, args: &[&str]) -> String {
+        let (tx, mut rx) = tokio::sync::mpsc::channel(10);
+        let moderator = ChatMessage::new("twitch", "testchannel", "moduser", "!findfilter").with_mod(true);
+        assert!(commands.process_command("findfilter", args, &moderator, &tx).await.unwrap());
+        rx.recv().await.unwrap().2
+    }
+
+    #[tokio::test]
+    async fn test_findfilter_searches_patterns_and_tags() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_manager = Arc::new(ConfigurationManager::new(dir.path()));
+        config_manager.initialize().await.unwrap();
+        let commands = FilterCommands::new(Arc::new(ModerationSystem::new()));
+
+        assert!(run_findfilter(&commands, &["crypto"]).await.contains("isn't running"));
+        commands.set_config_manager(Arc::clone(&config_manager)).await;
+
+        let response = run_findfilter(&commands, &["FREE", "MONEY"]).await;
+        assert!(response.contains("crypto_spam (pattern \"*free money*\")"), "{}", response);
+
+        let response = run_findfilter(&commands, &["tag:crypto"]).await;
+        assert!(response.contains("1 filter(s)") && response.contains("crypto_spam (tag crypto)"), "{}", response);
+
+        assert!(run_findfilter(&commands, &["nothing like this"]).await.contains("No filters match"));
+        assert!(run_findfilter(&commands, &[]).await.starts_with("Usage"));
+    }
+
+    #[tokio::test]
+    async fn test_suggestfilters_lists_top_phrases() {
+        let moderation = Arc::new(ModerationSystem::new());
+        let commands = FilterCommands::new(Arc::clone(&moderation));
+        let (tx, mut rx) = tokio::sync::mpsc::channel(10);
+        let moderator = ChatMessage::new("twitch", "testchannel", "moduser", "!suggestfilters").with_mod(true);
+
+        assert!(commands.process_command("suggestfilters", &[], &moderator, &tx).await.unwrap());
+        assert!(rx.recv().await.unwrap().2.contains("No filter suggestions yet"));
+
+        for (user, content) in [("a", "cheap viewers at viewbot"), ("b", "viewbot has cheap viewers"), ("c", "CHEAP VIEWERS")] {
+            let spam = ChatMessage::new("twitch", "testchannel", user, content);
+            moderation.record_ai_violation(&spam, &["promo".to_string()], ModerationAction::DeleteMessage, 0.8).await;
+        }
+
+        assert!(commands.process_command("suggestfilters", &["1"], &moderator, &tx).await.unwrap());
+        let response = rx.recv().await.unwrap().2;
+        assert!(response.contains("\"cheap viewers\" (3x)"), "{}", response);
+        assert!(!response.contains("viewbot"), "{}", response);
+    }
+}
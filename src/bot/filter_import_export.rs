@@ -2,11 +2,12 @@ use anyhow::{Result, Context};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use chrono::{DateTime, Utc};
-use log::{info};
+use log::{info, warn};
 use std::path::Path;
 use tokio::fs;
 
-use crate::types::{SpamFilter, SpamFilterType, BlacklistPattern, ExemptionLevel, ModerationEscalation, ModerationAction};
+use crate::bot::schedule::ActiveSchedule;
+use crate::types::{SpamFilter, SpamFilterType, BlacklistPattern, WildcardPattern, ExemptionLevel, ModerationEscalation, ModerationAction, FilterLogLevel, BotCommand, CommandChannels};
 
 /// Exportable filter configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -33,6 +34,12 @@ pub struct ExportableFilter {
     pub created_at: DateTime<Utc>,
     pub effectiveness_stats: Option<EffectivenessStats>,
     pub usage_context: Vec<String>, // Recommended contexts for this filter
+    #[serde(default)]
+    pub grace_seconds: Option<u64>,
+    #[serde(default)]
+    pub schedule: Option<ActiveSchedule>,
+    #[serde(default)]
+    pub log_level: Option<FilterLogLevel>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -63,13 +70,19 @@ pub enum SerializableSpamFilterType {
     LinkBlocking { allow_mods: bool, whitelist: Vec<String> },
     RepeatedMessages { max_repeats: u8, window_seconds: u64 },
     MessageLength { max_length: usize },
-    ExcessiveEmotes { max_count: u8 },
+    ExcessiveEmotes {
+        max_count: u8,
+        #[serde(default)]
+        count_unique: bool,
+    },
     SymbolSpam { max_percentage: u8 },
     RateLimit { max_messages: u8, window_seconds: u64 },
     Blacklist {
         patterns: Vec<SerializableBlacklistPattern>,
         case_sensitive: bool,
         whole_words_only: bool,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        except_patterns: Vec<SerializableBlacklistPattern>,
     },
     // Enhanced patterns for Phase 2
     AdvancedPattern {
@@ -99,6 +112,7 @@ pub enum SerializableModerationAction {
     TimeoutUser { duration_seconds: u64 },
     WarnUser { message: String },
     LogOnly,
+    ShadowMute { duration_seconds: u64 },
 }
 
 /// Filter import/export manager
@@ -131,6 +145,8 @@ impl FilterImportExport {
             ExportFormat::Toml => self.export_toml(&export_data, output_path).await,
             ExportFormat::NightBotCompatible => self.export_nightbot_format(&export_data, output_path).await,
             ExportFormat::StreamlabsCompatible => self.export_streamlabs_format(&export_data, output_path).await,
+            ExportFormat::MoobotCompatible => Err(anyhow::anyhow!("Moobot export is not supported")),
+            ExportFormat::WizebotCompatible => Err(anyhow::anyhow!("Wizebot export is not supported")),
             ExportFormat::CompressedArchive => self.export_compressed(&export_data, output_path).await,
         }
     }
@@ -154,12 +170,255 @@ impl FilterImportExport {
             ExportFormat::Toml => self.import_toml(input_path).await?,
             ExportFormat::NightBotCompatible => self.import_nightbot_format(input_path).await?,
             ExportFormat::StreamlabsCompatible => self.import_streamlabs_format(input_path).await?,
+            ExportFormat::MoobotCompatible => {
+                let converted = self.import_moobot_format(input_path).await?;
+                return self.process_converted_import(converted, options).await;
+            }
+            ExportFormat::WizebotCompatible => {
+                let converted = self.import_wizebot_format(input_path).await?;
+                return self.process_converted_import(converted, options).await;
+            }
             ExportFormat::CompressedArchive => self.import_compressed(input_path).await?,
         };
 
         self.process_import(import_data, options).await
     }
 
+    /// Process a converted third-party import, carrying over its commands and conversion warnings
+    async fn process_converted_import(&self, converted: ConvertedImport, options: ImportOptions) -> Result<ImportResult> {
+        let mut result = self.process_import(converted.export, options).await?;
+
+        for warning in &converted.warnings {
+            warn!("Import conversion: {}", warning);
+        }
+        result.warnings.extend(converted.warnings);
+        result.warning_count = result.warnings.len();
+        result.commands = converted.commands;
+
+        Ok(result)
+    }
+
+    /// Import from a Moobot export (commands and spam protection)
+    async fn import_moobot_format(&self, input_path: &Path) -> Result<ConvertedImport> {
+        let content = fs::read_to_string(input_path).await
+            .context("Failed to read Moobot import file")?;
+
+        let moobot_data: serde_json::Value = serde_json::from_str(&content)
+            .context("Failed to parse Moobot JSON")?;
+
+        let mut filters = Vec::new();
+        let mut commands = Vec::new();
+        let mut warnings = Vec::new();
+
+        for (index, command) in moobot_data.get("commands").and_then(|c| c.as_array()).into_iter().flatten().enumerate() {
+            let Some(trigger) = command.get("identifier").and_then(|i| i.as_str()) else {
+                warnings.push(format!("Moobot command #{} has no identifier, skipped", index + 1));
+                continue;
+            };
+            let Some(response) = command.get("response").and_then(|r| r.as_str()) else {
+                warnings.push(format!("Moobot command '{}' has no text response, skipped", trigger));
+                continue;
+            };
+            if !command.get("enabled").and_then(|e| e.as_bool()).unwrap_or(true) {
+                warnings.push(format!("Moobot command '{}' is disabled, skipped", trigger));
+                continue;
+            }
+
+            let user_level = command.get("userlevel").and_then(|u| u.as_str()).unwrap_or("everyone");
+            commands.push(third_party_command(
+                "Moobot",
+                trigger,
+                response,
+                user_level,
+                command.get("cooldown").and_then(|c| c.as_u64()).unwrap_or(0),
+                &mut warnings,
+            ));
+        }
+
+        if let Some(protections) = moobot_data.get("spam_protection").and_then(|s| s.as_object()) {
+            for (kind, settings) in protections {
+                let filter_type = match kind.as_str() {
+                    "blacklist" => {
+                        let patterns: Vec<_> = settings.get("terms").and_then(|t| t.as_array()).into_iter().flatten()
+                            .filter_map(|t| t.as_str())
+                            .map(parse_blacklist_term)
+                            .collect();
+                        if patterns.is_empty() {
+                            warnings.push("Moobot blacklist has no terms, skipped".to_string());
+                            continue;
+                        }
+                        SerializableSpamFilterType::Blacklist { patterns, case_sensitive: false, whole_words_only: false, except_patterns: Vec::new() }
+                    }
+                    "links" => SerializableSpamFilterType::LinkBlocking {
+                        allow_mods: true,
+                        whitelist: settings.get("whitelist").and_then(|w| w.as_array()).into_iter().flatten()
+                            .filter_map(|d| d.as_str().map(|s| s.to_string()))
+                            .collect(),
+                    },
+                    "caps" => SerializableSpamFilterType::ExcessiveCaps {
+                        max_percentage: json_u8(settings, "percentage").unwrap_or(70),
+                    },
+                    "symbols" => SerializableSpamFilterType::SymbolSpam {
+                        max_percentage: json_u8(settings, "percentage").unwrap_or(50),
+                    },
+                    "emotes" => SerializableSpamFilterType::ExcessiveEmotes {
+                        max_count: json_u8(settings, "max").unwrap_or(10),
+                        count_unique: false,
+                    },
+                    "message_length" => SerializableSpamFilterType::MessageLength {
+                        max_length: settings.get("max").and_then(|m| m.as_u64()).unwrap_or(400) as usize,
+                    },
+                    other => {
+                        warnings.push(format!("Moobot spam protection '{}' has no NotaBot equivalent, skipped", other));
+                        continue;
+                    }
+                };
+
+                if settings.get("min_length").is_some() {
+                    warnings.push(format!("Moobot '{}' minimum message length is not supported and was ignored", kind));
+                }
+
+                let timeout = settings.get("timeout").and_then(|t| t.as_u64()).unwrap_or(0);
+                let action = if timeout > 0 {
+                    SerializableModerationAction::TimeoutUser { duration_seconds: timeout }
+                } else {
+                    SerializableModerationAction::DeleteMessage
+                };
+
+                filters.push(third_party_filter(
+                    format!("moobot_{}", kind),
+                    filter_type,
+                    settings.get("enabled").and_then(|e| e.as_bool()).unwrap_or(true),
+                    action.clone(),
+                    action,
+                    exemption_from_user_level(settings.get("exempt").and_then(|e| e.as_str()).unwrap_or("moderator")),
+                    settings.get("message").and_then(|m| m.as_str()).map(|s| s.to_string()),
+                ));
+            }
+        }
+
+        Ok(ConvertedImport {
+            export: third_party_export("Moobot", filters),
+            commands,
+            warnings,
+        })
+    }
+
+    /// Import from a Wizebot export (commands and moderation filters)
+    async fn import_wizebot_format(&self, input_path: &Path) -> Result<ConvertedImport> {
+        let content = fs::read_to_string(input_path).await
+            .context("Failed to read Wizebot import file")?;
+
+        let wizebot_data: serde_json::Value = serde_json::from_str(&content)
+            .context("Failed to parse Wizebot JSON")?;
+
+        let mut filters = Vec::new();
+        let mut commands = Vec::new();
+        let mut warnings = Vec::new();
+
+        for (index, command) in wizebot_data.get("commands").and_then(|c| c.as_array()).into_iter().flatten().enumerate() {
+            let (Some(name), Some(message)) = (
+                command.get("name").and_then(|n| n.as_str()),
+                command.get("message").and_then(|m| m.as_str()),
+            ) else {
+                warnings.push(format!("Wizebot command #{} is missing a name or message, skipped", index + 1));
+                continue;
+            };
+            if !command.get("active").and_then(|a| a.as_bool()).unwrap_or(true) {
+                warnings.push(format!("Wizebot command '{}' is inactive, skipped", name));
+                continue;
+            }
+
+            let access = command.get("access").and_then(|a| a.as_str()).unwrap_or("all");
+            commands.push(third_party_command(
+                "Wizebot",
+                name,
+                message,
+                access,
+                command.get("cooldown_sec").and_then(|c| c.as_u64()).unwrap_or(0),
+                &mut warnings,
+            ));
+        }
+
+        let wizebot_filters = wizebot_data.get("moderation")
+            .and_then(|m| m.get("filters"))
+            .and_then(|f| f.as_array());
+
+        for (index, settings) in wizebot_filters.into_iter().flatten().enumerate() {
+            let kind = settings.get("type").and_then(|t| t.as_str()).unwrap_or("unknown");
+            let filter_type = match kind {
+                "words" => {
+                    let patterns: Vec<_> = settings.get("words").and_then(|w| w.as_array()).into_iter().flatten()
+                        .filter_map(|w| w.as_str())
+                        .map(parse_blacklist_term)
+                        .collect();
+                    if patterns.is_empty() {
+                        warnings.push(format!("Wizebot word filter #{} has no words, skipped", index + 1));
+                        continue;
+                    }
+                    SerializableSpamFilterType::Blacklist {
+                        patterns,
+                        case_sensitive: false,
+                        whole_words_only: settings.get("whole_words").and_then(|w| w.as_bool()).unwrap_or(false),
+                        except_patterns: Vec::new(),
+                    }
+                }
+                "links" => SerializableSpamFilterType::LinkBlocking {
+                    allow_mods: true,
+                    whitelist: settings.get("allowed_domains").and_then(|d| d.as_array()).into_iter().flatten()
+                        .filter_map(|d| d.as_str().map(|s| s.to_string()))
+                        .collect(),
+                },
+                "caps" => SerializableSpamFilterType::ExcessiveCaps {
+                    max_percentage: json_u8(settings, "max_percent").unwrap_or(70),
+                },
+                "symbols" => SerializableSpamFilterType::SymbolSpam {
+                    max_percentage: json_u8(settings, "max_percent").unwrap_or(50),
+                },
+                "emotes" => SerializableSpamFilterType::ExcessiveEmotes {
+                    max_count: json_u8(settings, "max_count").unwrap_or(10),
+                    count_unique: false,
+                },
+                "length" => SerializableSpamFilterType::MessageLength {
+                    max_length: settings.get("max_chars").and_then(|m| m.as_u64()).unwrap_or(400) as usize,
+                },
+                other => {
+                    warnings.push(format!("Wizebot filter type '{}' has no NotaBot equivalent, skipped", other));
+                    continue;
+                }
+            };
+
+            let duration = settings.get("duration").and_then(|d| d.as_u64()).unwrap_or(600);
+            let action = match settings.get("action").and_then(|a| a.as_str()).unwrap_or("delete") {
+                "timeout" => SerializableModerationAction::TimeoutUser { duration_seconds: duration },
+                "ban" => {
+                    warnings.push(format!("Wizebot '{}' filter bans users; imported as a 14 day timeout", kind));
+                    SerializableModerationAction::TimeoutUser { duration_seconds: 1_209_600 }
+                }
+                "warn" => SerializableModerationAction::WarnUser {
+                    message: settings.get("message").and_then(|m| m.as_str()).unwrap_or("Please follow chat rules").to_string(),
+                },
+                _ => SerializableModerationAction::DeleteMessage,
+            };
+
+            filters.push(third_party_filter(
+                format!("wizebot_{}_{}", kind, index + 1),
+                filter_type,
+                settings.get("enabled").and_then(|e| e.as_bool()).unwrap_or(true),
+                action.clone(),
+                action,
+                exemption_from_user_level(settings.get("exempt").and_then(|e| e.as_str()).unwrap_or("mods")),
+                settings.get("message").and_then(|m| m.as_str()).map(|s| s.to_string()),
+            ));
+        }
+
+        Ok(ConvertedImport {
+            export: third_party_export("Wizebot", filters),
+            commands,
+            warnings,
+        })
+    }
+
     /// Export to NightBot compatible format
     async fn export_nightbot_format(&self, export_data: &FilterExport, output_path: &Path) -> Result<()> {
         let mut nightbot_data = serde_json::Map::new();
@@ -224,22 +483,7 @@ impl FilterImportExport {
                 
                 for pattern_val in patterns {
                     if let Some(pattern_str) = pattern_val.as_str() {
-                        let blacklist_pattern = if pattern_str.starts_with("~/") && pattern_str.ends_with("/") {
-                            // Regex pattern
-                            let regex_content = &pattern_str[2..pattern_str.len()-1];
-                            SerializableBlacklistPattern::Regex {
-                                pattern: regex_content.to_string(),
-                                flags: "i".to_string(), // Default to case insensitive
-                            }
-                        } else if pattern_str.contains('*') {
-                            // Wildcard pattern
-                            SerializableBlacklistPattern::Wildcard(pattern_str.to_string())
-                        } else {
-                            // Literal pattern
-                            SerializableBlacklistPattern::Literal(pattern_str.to_string())
-                        };
-                        
-                        blacklist_patterns.push(blacklist_pattern);
+                        blacklist_patterns.push(parse_blacklist_term(pattern_str));
                     }
                 }
 
@@ -254,6 +498,7 @@ impl FilterImportExport {
                             patterns: blacklist_patterns,
                             case_sensitive: false,
                             whole_words_only: false,
+                            except_patterns: Vec::new(),
                         },
                         enabled: blacklist.get("enabled").and_then(|e| e.as_bool()).unwrap_or(true),
                         escalation: SerializableModerationEscalation {
@@ -269,6 +514,9 @@ impl FilterImportExport {
                         created_at: Utc::now(),
                         effectiveness_stats: None,
                         usage_context: vec!["general".to_string()],
+                        grace_seconds: None,
+                        log_level: None,
+                        schedule: None,
                     });
                 }
             }
@@ -351,6 +599,9 @@ impl FilterImportExport {
                 created_at: Utc::now(), // Would be stored in actual implementation
                 effectiveness_stats: None, // Would be populated from analytics
                 usage_context: vec!["general".to_string()], // Default context
+                grace_seconds: filter.grace_seconds,
+                schedule: filter.schedule.clone(),
+                log_level: filter.log_level,
             });
         }
 
@@ -396,8 +647,8 @@ impl FilterImportExport {
             SpamFilterType::MessageLength { max_length } => {
                 SerializableSpamFilterType::MessageLength { max_length: *max_length }
             }
-            SpamFilterType::ExcessiveEmotes { max_count } => {
-                SerializableSpamFilterType::ExcessiveEmotes { max_count: *max_count }
+            SpamFilterType::ExcessiveEmotes { max_count, count_unique } => {
+                SerializableSpamFilterType::ExcessiveEmotes { max_count: *max_count, count_unique: *count_unique }
             }
             SpamFilterType::SymbolSpam { max_percentage } => {
                 SerializableSpamFilterType::SymbolSpam { max_percentage: *max_percentage }
@@ -408,15 +659,19 @@ impl FilterImportExport {
                     window_seconds: *window_seconds,
                 }
             }
-            SpamFilterType::Blacklist { patterns, case_sensitive, whole_words_only } => {
+            SpamFilterType::Blacklist { patterns, case_sensitive, whole_words_only, except_patterns } => {
                 let serializable_patterns = patterns.iter()
                     .map(|p| self.convert_pattern_to_serializable(p))
                     .collect::<Result<Vec<_>>>()?;
+                let serializable_exceptions = except_patterns.iter()
+                    .map(|p| self.convert_pattern_to_serializable(p))
+                    .collect::<Result<Vec<_>>>()?;
                 
                 SerializableSpamFilterType::Blacklist {
                     patterns: serializable_patterns,
                     case_sensitive: *case_sensitive,
                     whole_words_only: *whole_words_only,
+                    except_patterns: serializable_exceptions,
                 }
             }
         };
@@ -428,7 +683,7 @@ impl FilterImportExport {
     fn convert_pattern_to_serializable(&self, pattern: &BlacklistPattern) -> Result<SerializableBlacklistPattern> {
         let serializable = match pattern {
             BlacklistPattern::Literal(text) => SerializableBlacklistPattern::Literal(text.clone()),
-            BlacklistPattern::Wildcard(text) => SerializableBlacklistPattern::Wildcard(text.clone()),
+            BlacklistPattern::Wildcard(text) => SerializableBlacklistPattern::Wildcard(text.to_string()),
             BlacklistPattern::Regex { pattern, .. } => {
                 // Extract flags from the full pattern string
                 let flags = if pattern.contains("/i") { "i" } else { "" };
@@ -455,6 +710,9 @@ impl FilterImportExport {
                 SerializableModerationAction::WarnUser { message: message.clone() }
             }
             ModerationAction::LogOnly => SerializableModerationAction::LogOnly,
+            ModerationAction::ShadowMute { duration_seconds } => {
+                SerializableModerationAction::ShadowMute { duration_seconds: *duration_seconds }
+            }
         }
     }
 
@@ -646,6 +904,7 @@ impl FilterImportExport {
             filters: imported_filters,
             metadata: import_data.metadata,
             imported_count: imported_count,
+            commands: Vec::new(),
             error_count: errors.len(),
             warning_count: warnings.len(),
             errors,
@@ -677,19 +936,37 @@ impl FilterImportExport {
                     whitelist: whitelist.clone(),
                 }
             }
-            SerializableSpamFilterType::Blacklist { patterns, case_sensitive, whole_words_only } => {
+            SerializableSpamFilterType::RepeatedMessages { max_repeats, window_seconds } => {
+                SpamFilterType::RepeatedMessages { max_repeats: *max_repeats, window_seconds: *window_seconds }
+            }
+            SerializableSpamFilterType::MessageLength { max_length } => {
+                SpamFilterType::MessageLength { max_length: *max_length }
+            }
+            SerializableSpamFilterType::ExcessiveEmotes { max_count, count_unique } => {
+                SpamFilterType::ExcessiveEmotes { max_count: *max_count, count_unique: *count_unique }
+            }
+            SerializableSpamFilterType::SymbolSpam { max_percentage } => {
+                SpamFilterType::SymbolSpam { max_percentage: *max_percentage }
+            }
+            SerializableSpamFilterType::RateLimit { max_messages, window_seconds } => {
+                SpamFilterType::RateLimit { max_messages: *max_messages, window_seconds: *window_seconds }
+            }
+            SerializableSpamFilterType::Blacklist { patterns, case_sensitive, whole_words_only, except_patterns } => {
                 let internal_patterns = patterns.iter()
                     .map(|p| self.convert_pattern_from_serializable(p))
                     .collect::<Result<Vec<_>>>()?;
+                let internal_exceptions = except_patterns.iter()
+                    .map(|p| self.convert_pattern_from_serializable(p))
+                    .collect::<Result<Vec<_>>>()?;
                 
                 SpamFilterType::Blacklist {
                     patterns: internal_patterns,
                     case_sensitive: *case_sensitive,
                     whole_words_only: *whole_words_only,
+                    except_patterns: internal_exceptions,
                 }
             }
-            // Add other conversions as needed
-            _ => return Err(anyhow::anyhow!("Unsupported filter type in import")),
+            SerializableSpamFilterType::AdvancedPattern { .. } => return Err(anyhow::anyhow!("Unsupported filter type in import")),
         };
 
         let exemption_level = match filter.exemption_level.as_str() {
@@ -713,6 +990,9 @@ impl FilterImportExport {
             silent_mode: filter.silent_mode,
             custom_message: filter.custom_message.clone(),
             name: filter.name.clone(),
+            grace_seconds: filter.grace_seconds,
+            schedule: filter.schedule.clone(),
+            log_level: filter.log_level,
         })
     }
 
@@ -720,7 +1000,7 @@ impl FilterImportExport {
     fn convert_pattern_from_serializable(&self, pattern: &SerializableBlacklistPattern) -> Result<BlacklistPattern> {
         let internal_pattern = match pattern {
             SerializableBlacklistPattern::Literal(text) => BlacklistPattern::Literal(text.clone()),
-            SerializableBlacklistPattern::Wildcard(text) => BlacklistPattern::Wildcard(text.clone()),
+            SerializableBlacklistPattern::Wildcard(text) => BlacklistPattern::Wildcard(WildcardPattern::new(text.as_str())),
             SerializableBlacklistPattern::Regex { pattern, flags } => {
                 let full_pattern = if flags.is_empty() {
                     format!("~/{}/", pattern)
@@ -746,6 +1026,9 @@ impl FilterImportExport {
                 ModerationAction::WarnUser { message: message.clone() }
             }
             SerializableModerationAction::LogOnly => ModerationAction::LogOnly,
+            SerializableModerationAction::ShadowMute { duration_seconds } => {
+                ModerationAction::ShadowMute { duration_seconds: *duration_seconds }
+            }
         }
     }
 
@@ -832,6 +1115,10 @@ pub enum ExportFormat {
     Toml,
     NightBotCompatible,
     StreamlabsCompatible,
+    /// Moobot command/spam protection export (import only)
+    MoobotCompatible,
+    /// Wizebot command/moderation export (import only)
+    WizebotCompatible,
     CompressedArchive,
 }
 
@@ -859,6 +1146,8 @@ pub struct ImportResult {
     pub filters: HashMap<String, SpamFilter>,
     pub metadata: ExportMetadata,
     pub imported_count: usize,
+    /// Commands converted from third-party bot exports
+    pub commands: Vec<BotCommand>,
     pub error_count: usize,
     pub warning_count: usize,
     pub errors: Vec<String>,
@@ -866,6 +1155,128 @@ pub struct ImportResult {
     pub source_info: ImportSourceInfo,
 }
 
+/// Third-party export converted to NotaBot types, before filter processing
+struct ConvertedImport {
+    export: FilterExport,
+    commands: Vec<BotCommand>,
+    warnings: Vec<String>,
+}
+
+/// Parse a NightBot-style blacklist term (`~/regex/`, `wild*card` or literal)
+fn parse_blacklist_term(term: &str) -> SerializableBlacklistPattern {
+    if term.len() > 2 && term.starts_with("~/") && term.ends_with('/') {
+        SerializableBlacklistPattern::Regex {
+            pattern: term[2..term.len() - 1].to_string(),
+            flags: "i".to_string(), // Default to case insensitive
+        }
+    } else if term.contains('*') {
+        SerializableBlacklistPattern::Wildcard(term.to_string())
+    } else {
+        SerializableBlacklistPattern::Literal(term.to_string())
+    }
+}
+
+fn json_u8(value: &serde_json::Value, key: &str) -> Option<u8> {
+    value.get(key).and_then(|v| v.as_u64()).map(|v| v.min(u8::MAX as u64) as u8)
+}
+
+/// Map a third-party user level name onto an exemption level name
+fn exemption_from_user_level(level: &str) -> String {
+    match level.to_lowercase().as_str() {
+        "everyone" | "all" | "none" => "None",
+        "subscriber" | "subscribers" | "subs" => "Subscriber",
+        "regular" | "regulars" | "vip" | "vips" => "Regular",
+        "owner" | "broadcaster" => "Owner",
+        _ => "Moderator",
+    }.to_string()
+}
+
+/// Convert a third-party command, warning when its access level can't be represented
+fn third_party_command(
+    source: &str,
+    trigger: &str,
+    response: &str,
+    user_level: &str,
+    cooldown_seconds: u64,
+    warnings: &mut Vec<String>,
+) -> BotCommand {
+    let trigger = trigger.trim_start_matches('!').to_lowercase();
+    let mod_only = match exemption_from_user_level(user_level).as_str() {
+        "None" => false,
+        "Moderator" | "Owner" => true,
+        _ => {
+            warnings.push(format!(
+                "{} command '{}' is limited to {}; imported as available to everyone",
+                source, trigger, user_level
+            ));
+            false
+        }
+    };
+
+    BotCommand {
+        trigger,
+        response: response.to_string(),
+        mod_only,
+        cooldown_seconds,
+        channels: CommandChannels::All,
+        category: None,
+        cooldown_notice: None,
+    }
+}
+
+fn third_party_filter(
+    name: String,
+    filter_type: SerializableSpamFilterType,
+    enabled: bool,
+    first_offense: SerializableModerationAction,
+    repeat_offense: SerializableModerationAction,
+    exemption_level: String,
+    custom_message: Option<String>,
+) -> ExportableFilter {
+    ExportableFilter {
+        name,
+        filter_type,
+        enabled,
+        escalation: SerializableModerationEscalation {
+            first_offense,
+            repeat_offense,
+            offense_window_seconds: 3600,
+        },
+        exemption_level,
+        silent_mode: false,
+        custom_message,
+        created_at: Utc::now(),
+        effectiveness_stats: None,
+        usage_context: vec!["general".to_string()],
+        grace_seconds: None,
+        log_level: None,
+        schedule: None,
+    }
+}
+
+fn third_party_export(source: &str, filters: Vec<ExportableFilter>) -> FilterExport {
+    let total_filters = filters.len();
+    FilterExport {
+        version: "1.0".to_string(),
+        exported_at: Utc::now(),
+        exported_by: format!("{} Import", source),
+        bot_version: format!("{}_import", source.to_lowercase()),
+        description: format!("Imported from {} configuration", source),
+        tags: vec!["imported".to_string(), source.to_lowercase()],
+        filters,
+        metadata: ExportMetadata {
+            total_filters,
+            filter_types: HashMap::new(),
+            estimated_accuracy: 0.8, // Conservative estimate
+            recommended_for: vec!["general".to_string()],
+            compatibility: vec![source.to_lowercase(), "twitch".to_string()],
+            author: "Unknown".to_string(),
+            license: "Imported".to_string(),
+            update_url: None,
+        },
+    }
+}
+
 #[derive(Debug)]
 pub struct ImportSourceInfo {
     pub version: String,
@@ -897,4 +1308,132 @@ impl Default for ImportOptions {
             dry_run: false,
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    const MOOBOT_SAMPLE: &str = r#"{
+        "commands": [
+            { "identifier": "!discord", "response": "Join us at discord.gg/example", "cooldown": 30, "userlevel": "everyone" },
+            { "identifier": "so", "response": "Go follow {{ user }}!", "userlevel": "moderator" },
+            { "identifier": "subonly", "response": "Thanks for subbing!", "userlevel": "subscriber" },
+            { "identifier": "song", "type": "song_request" }
+        ],
+        "spam_protection": {
+            "blacklist": { "enabled": true, "terms": ["badword", "*scam*", "~/fr[e3]{2}\\s*gift/"], "timeout": 600, "exempt": "subscriber", "message": "Watch your language" },
+            "links": { "enabled": true, "whitelist": ["youtube.com", "clips.twitch.tv"], "timeout": 0 },
+            "caps": { "enabled": true, "percentage": 65, "min_length": 12, "timeout": 30 },
+            "zalgo": { "enabled": true }
+        }
+    }"#;
+
+    const WIZEBOT_SAMPLE: &str = r#"{
+        "commands": [
+            { "name": "!socials", "message": "twitter.com/example", "cooldown_sec": 15, "access": "all" },
+            { "name": "!reset", "message": "Resetting...", "access": "mods" },
+            { "name": "!old", "message": "unused", "active": false }
+        ],
+        "moderation": {
+            "filters": [
+                { "type": "words", "words": ["badword", "spam*"], "action": "timeout", "duration": 300, "exempt": "vip" },
+                { "type": "symbols", "max_percent": 40, "action": "delete" },
+                { "type": "emotes", "max_count": 12, "action": "ban" },
+                { "type": "flood", "max_lines": 3 }
+            ]
+        }
+    }"#;
+
+    fn options() -> ImportOptions {
+        ImportOptions { prefix_names: false, ..ImportOptions::default() }
+    }
+
+    /// Export imported filters as NotaBot JSON and import them again
+    async fn round_trip(manager: &FilterImportExport, result: &ImportResult, dir: &Path) -> ImportResult {
+        let path = dir.join("roundtrip.json");
+        manager.export_filters(&result.filters, ExportFormat::Json, &path, ExportOptions::default()).await.unwrap();
+        manager.import_filters(&path, None, options()).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_moobot_import_round_trip() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("moobot.json");
+        fs::write(&path, MOOBOT_SAMPLE).await.unwrap();
+
+        let manager = FilterImportExport::new();
+        let result = manager.import_filters(&path, Some(ExportFormat::MoobotCompatible), options()).await.unwrap();
+
+        assert_eq!(result.imported_count, 3);
+        assert_eq!(result.error_count, 0);
+        assert!(result.metadata.compatibility.contains(&"moobot".to_string()));
+
+        let blacklist = &result.filters["moobot_blacklist"];
+        match &blacklist.filter_type {
+            SpamFilterType::Blacklist { patterns, .. } => {
+                assert_eq!(patterns.len(), 3);
+                assert!(matches!(patterns[1], BlacklistPattern::Wildcard(_)));
+                assert!(matches!(patterns[2], BlacklistPattern::Regex { .. }));
+            }
+            other => panic!("expected blacklist, got {:?}", other),
+        }
+        assert!(matches!(blacklist.exemption_level, ExemptionLevel::Subscriber));
+        assert!(matches!(blacklist.escalation.first_offense, ModerationAction::TimeoutUser { duration_seconds: 600 }));
+        assert!(matches!(result.filters["moobot_links"].escalation.first_offense, ModerationAction::DeleteMessage));
+        assert!(matches!(result.filters["moobot_caps"].filter_type, SpamFilterType::ExcessiveCaps { max_percentage: 65 }));
+
+        let triggers: Vec<_> = result.commands.iter().map(|c| (c.trigger.as_str(), c.mod_only, c.cooldown_seconds)).collect();
+        assert_eq!(triggers, vec![("discord", false, 30), ("so", true, 0), ("subonly", false, 0)]);
+
+        assert!(result.warnings.iter().any(|w| w.contains("'zalgo'")));
+        assert!(result.warnings.iter().any(|w| w.contains("minimum message length")));
+        assert!(result.warnings.iter().any(|w| w.contains("'subonly' is limited to subscriber")));
+        assert!(result.warnings.iter().any(|w| w.contains("'song' has no text response")));
+        assert_eq!(result.warning_count, result.warnings.len());
+
+        let reimported = round_trip(&manager, &result, dir.path()).await;
+        assert_eq!(reimported.error_count, 0);
+        let mut names: Vec<_> = reimported.filters.keys().cloned().collect();
+        names.sort();
+        assert_eq!(names, vec!["moobot_blacklist", "moobot_caps", "moobot_links"]);
+    }
+
+    #[tokio::test]
+    async fn test_wizebot_import_round_trip() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("wizebot.json");
+        fs::write(&path, WIZEBOT_SAMPLE).await.unwrap();
+
+        let manager = FilterImportExport::new();
+        let result = manager.import_filters(&path, Some(ExportFormat::WizebotCompatible), options()).await.unwrap();
+
+        assert_eq!(result.imported_count, 3);
+        assert!(result.metadata.compatibility.contains(&"wizebot".to_string()));
+
+        let words = &result.filters["wizebot_words_1"];
+        assert!(matches!(words.exemption_level, ExemptionLevel::Regular));
+        assert!(matches!(words.escalation.first_offense, ModerationAction::TimeoutUser { duration_seconds: 300 }));
+        assert!(matches!(result.filters["wizebot_symbols_2"].filter_type, SpamFilterType::SymbolSpam { max_percentage: 40 }));
+        assert!(matches!(
+            result.filters["wizebot_emotes_3"].escalation.first_offense,
+            ModerationAction::TimeoutUser { duration_seconds: 1_209_600 }
+        ));
+
+        let triggers: Vec<_> = result.commands.iter().map(|c| (c.trigger.as_str(), c.mod_only, c.cooldown_seconds)).collect();
+        assert_eq!(triggers, vec![("socials", false, 15), ("reset", true, 0)]);
+
+        assert!(result.warnings.iter().any(|w| w.contains("'flood'")));
+        assert!(result.warnings.iter().any(|w| w.contains("14 day timeout")));
+        assert!(result.warnings.iter().any(|w| w.contains("'!old' is inactive")));
+
+        let reimported = round_trip(&manager, &result, dir.path()).await;
+        assert_eq!(reimported.error_count, 0);
+        assert_eq!(reimported.imported_count, 3);
+        assert!(matches!(
+            reimported.filters["wizebot_emotes_3"].filter_type,
+            SpamFilterType::ExcessiveEmotes { max_count: 12, count_unique: false }
+        ));
+    }
+}
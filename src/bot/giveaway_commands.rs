@@ -1,17 +1,29 @@
 use anyhow::Result;
 use std::sync::Arc;
+use tokio::sync::RwLock;
 use crate::types::{ChatMessage, GiveawayType};
 
-use super::giveaways::{GiveawaySystem};
+use super::data_dir::{DataDir, GIVEAWAY_EXPORT_FILE};
+use super::giveaways::{GiveawaySystem, GiveawayExportFormat};
+use crate::types::GiveawayError;
 
 // Example giveaway command handler that would be added to the command processing system
 pub struct GiveawayCommands {
     giveaway_system: Arc<GiveawaySystem>,
+    /// Where `!gexport` writes; the file name is fixed, never taken from chat
+    data_dir: RwLock<DataDir>,
 }
 
 impl GiveawayCommands {
     pub fn new(giveaway_system: Arc<GiveawaySystem>) -> Self {
-        Self { giveaway_system }
+        Self {
+            giveaway_system,
+            data_dir: RwLock::new(DataDir::default()),
+        }
+    }
+
+    pub async fn set_data_dir(&self, data_dir: DataDir) {
+        *self.data_dir.write().await = data_dir;
     }
 
     /// Process giveaway-related commands
@@ -75,6 +87,22 @@ impl GiveawayCommands {
                 self.handle_giveaway_stats(message, response_sender).await?;
                 Ok(true)
             }
+            "giveaway" => {
+                if !message.is_mod {
+                    self.send_response("Only moderators can manage giveaway entrants.".to_string(), message, response_sender).await?;
+                    return Ok(true);
+                }
+                self.handle_manual_entry(args, message, response_sender).await?;
+                Ok(true)
+            }
+            "gexport" => {
+                if !message.is_mod {
+                    self.send_response("Only moderators can export giveaway history.".to_string(), message, response_sender).await?;
+                    return Ok(true);
+                }
+                self.handle_export_history(args, message, response_sender).await?;
+                Ok(true)
+            }
             _ => Ok(false), // Command not handled
         }
     }
@@ -277,6 +305,42 @@ impl GiveawayCommands {
         Ok(())
     }
 
+    async fn handle_manual_entry(
+        &self,
+        args: &[&str],
+        message: &ChatMessage,
+        response_sender: &tokio::sync::mpsc::Sender<(String, String, String)>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let eligible = match args.first().map(|a| a.to_lowercase()) {
+            Some(action) if action == "add" => true,
+            Some(action) if action == "remove" => false,
+            _ => {
+                self.send_response("Usage: !giveaway <add|remove> @user".to_string(), message, response_sender).await?;
+                return Ok(());
+            }
+        };
+        let Some(username) = args.get(1).map(|u| u.trim_start_matches('@')).filter(|u| !u.is_empty()) else {
+            self.send_response("Usage: !giveaway <add|remove> @user".to_string(), message, response_sender).await?;
+            return Ok(());
+        };
+
+        let response = match self.giveaway_system.set_user_eligibility(&message.platform, username, eligible).await {
+            Ok(_) => {
+                let participants = self.giveaway_system.get_giveaway_status().await
+                    .map(|status| status.participant_count)
+                    .unwrap_or(0);
+                if eligible {
+                    format!("{} has been added to the giveaway ({} participants)", username, participants)
+                } else {
+                    format!("{} has been removed from the giveaway ({} participants)", username, participants)
+                }
+            }
+            Err(e) => format!("Failed to update giveaway entry: {}", e),
+        };
+        self.send_response(response, message, response_sender).await?;
+        Ok(())
+    }
+
     async fn handle_reset_eligibility(
         &self,
         message: &ChatMessage,
@@ -319,6 +383,38 @@ impl GiveawayCommands {
         Ok(())
     }
 
+    async fn handle_export_history(
+        &self,
+        args: &[&str],
+        message: &ChatMessage,
+        response_sender: &tokio::sync::mpsc::Sender<(String, String, String)>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let format = match args {
+            [] => GiveawayExportFormat::Json,
+            [format] => match GiveawayExportFormat::parse(format) {
+                Some(format) => format,
+                None => {
+                    self.send_response("Usage: !gexport [json|csv]".to_string(), message, response_sender).await?;
+                    return Ok(());
+                }
+            },
+            _ => {
+                self.send_response("Usage: !gexport [json|csv]".to_string(), message, response_sender).await?;
+                return Ok(());
+            }
+        };
+        let file_name = format!("{}.{}", GIVEAWAY_EXPORT_FILE, format.extension());
+        let path = self.data_dir.read().await.file(&file_name);
+
+        let response = match self.giveaway_system.export_history(format, &path).await {
+            Ok(count) => format!("Exported {} completed giveaways to {} in the data directory", count, file_name),
+            Err(GiveawayError::NoHistory) => "No completed giveaways to export yet.".to_string(),
+            Err(e) => format!("Failed to export giveaway history: {}", e),
+        };
+        self.send_response(response, message, response_sender).await?;
+        Ok(())
+    }
+
     async fn send_response(
         &self,
         response: String,
@@ -334,4 +430,68 @@ impl GiveawayCommands {
         }
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_giveaway_add_and_remove_commands() {
+        let system = Arc::new(GiveawaySystem::new());
+        let commands = GiveawayCommands::new(Arc::clone(&system));
+        let (tx, mut rx) = tokio::sync::mpsc::channel(10);
+
+        let keyword = GiveawayType::Keyword {
+            keyword: "!enter".to_string(),
+            case_sensitive: false,
+            anti_spam: true,
+            max_entries_per_user: Some(1),
+        };
+        system.start_giveaway(keyword, "streamer".to_string(), "main".to_string(), "twitch".to_string(), None).await.unwrap();
+
+        let mut moderator = ChatMessage::new("twitch", "main", "moderator", "!giveaway add @latecomer");
+        moderator.is_mod = true;
+        assert!(commands.process_command("giveaway", &["add", "@latecomer"], &moderator, &tx).await.unwrap());
+        assert_eq!(rx.recv().await.unwrap().2, "latecomer has been added to the giveaway (1 participants)");
+
+        // Regular viewers can't manage entrants
+        let viewer = ChatMessage::new("twitch", "main", "viewer", "!giveaway add viewer");
+        commands.process_command("giveaway", &["add", "viewer"], &viewer, &tx).await.unwrap();
+        assert_eq!(rx.recv().await.unwrap().2, "Only moderators can manage giveaway entrants.");
+        assert_eq!(system.get_giveaway_status().await.unwrap().participant_count, 1);
+
+        commands.process_command("giveaway", &["remove", "LateComer"], &moderator, &tx).await.unwrap();
+        assert_eq!(rx.recv().await.unwrap().2, "LateComer has been removed from the giveaway (0 participants)");
+        assert!(system.get_eligible_users().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_export_writes_into_the_data_dir_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let system = Arc::new(GiveawaySystem::new());
+        let commands = GiveawayCommands::new(Arc::clone(&system));
+        commands.set_data_dir(DataDir::new(dir.path())).await;
+        let (tx, mut rx) = tokio::sync::mpsc::channel(10);
+
+        let mut moderator = ChatMessage::new("twitch", "main", "moderator", "!gexport");
+        moderator.is_mod = true;
+        commands.process_command("gexport", &[], &moderator, &tx).await.unwrap();
+        assert_eq!(rx.recv().await.unwrap().2, "No completed giveaways to export yet.");
+
+        let number = GiveawayType::RandomNumber { min: 1, max: 10, auto_generate: true };
+        system.start_giveaway(number, "streamer".to_string(), "main".to_string(), "twitch".to_string(), None).await.unwrap();
+        system.set_user_eligibility("twitch", "winner", true).await.unwrap();
+        system.end_giveaway(true).await.unwrap();
+
+        // Paths from chat are refused rather than written to
+        let escape = dir.path().join("elsewhere.csv");
+        commands.process_command("gexport", &["csv", escape.to_str().unwrap()], &moderator, &tx).await.unwrap();
+        assert_eq!(rx.recv().await.unwrap().2, "Usage: !gexport [json|csv]");
+        assert!(!escape.exists());
+
+        commands.process_command("gexport", &["csv"], &moderator, &tx).await.unwrap();
+        assert_eq!(rx.recv().await.unwrap().2, "Exported 1 completed giveaways to giveaway_export.csv in the data directory");
+        assert!(dir.path().join("giveaway_export.csv").is_file());
+    }
+}
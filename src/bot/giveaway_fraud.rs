@@ -0,0 +1,312 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// How much each signal contributes to a giveaway entrant's fraud score
+///
+/// Weights are relative; the score is the weighted average of the signals
+/// that could be measured, so it always stays within 0.0..=1.0.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FraudWeights {
+    /// Young accounts (only counted when the account creation date is known)
+    pub account_age: f32,
+    /// Little or no chat activity before entering
+    pub message_history: f32,
+    /// Repeated entries or copy-pasted messages
+    pub duplicate_entries: f32,
+    /// Username looks like a batch of bot accounts among the other entrants
+    pub username_similarity: f32,
+}
+
+impl Default for FraudWeights {
+    fn default() -> Self {
+        Self {
+            account_age: 0.35,
+            message_history: 0.25,
+            duplicate_entries: 0.2,
+            username_similarity: 0.2,
+        }
+    }
+}
+
+/// Accounts younger than this are considered new
+const NEW_ACCOUNT_DAYS: f32 = 7.0;
+/// Chat messages before entering at which a user stops looking like a drive-by
+const ESTABLISHED_MESSAGE_COUNT: f32 = 10.0;
+/// Repeats (extra entries or identical messages) that max out the duplicate signal
+const DUPLICATE_SATURATION: f32 = 3.0;
+/// Look-alike entrants that max out the username signal
+const SIMILAR_NAME_SATURATION: f32 = 3.0;
+/// Recent messages kept per user for duplicate detection
+const RECENT_MESSAGES: usize = 10;
+/// Chatters remembered at most; the least recently active non-entrants are forgotten first
+pub const MAX_PROFILES: usize = 10_000;
+
+#[derive(Debug, Default)]
+struct ChatProfile {
+    message_count: u32,
+    recent_messages: VecDeque<String>,
+    entry_attempts: u32,
+    /// Value of the scorer's activity counter when this user last chatted
+    last_active: u64,
+}
+
+/// Heuristic fraud scoring for giveaway entrants
+#[derive(Debug, Default)]
+pub struct FraudScorer {
+    weights: FraudWeights,
+    profiles: HashMap<String, ChatProfile>,
+    account_created: HashMap<String, DateTime<Utc>>,
+    /// Users whose account age has been asked for during the current giveaway
+    account_lookups: HashSet<String>,
+    /// Counts recorded messages, to tell which profiles went quiet longest
+    activity: u64,
+}
+
+impl FraudScorer {
+    pub fn new(weights: FraudWeights) -> Self {
+        Self {
+            weights,
+            profiles: HashMap::new(),
+            account_created: HashMap::new(),
+            account_lookups: HashSet::new(),
+            activity: 0,
+        }
+    }
+
+    pub fn set_weights(&mut self, weights: FraudWeights) {
+        self.weights = weights;
+    }
+
+    pub fn weights(&self) -> &FraudWeights {
+        &self.weights
+    }
+
+    /// Record when a user's platform account was created, if the platform tells us
+    pub fn set_account_created(&mut self, platform: &str, username: &str, created: DateTime<Utc>) {
+        self.account_created.insert(Self::key(platform, username), created);
+    }
+
+    /// When a user's platform account was created, if known
+    pub fn account_created(&self, platform: &str, username: &str) -> Option<DateTime<Utc>> {
+        self.account_created.get(&Self::key(platform, username)).copied()
+    }
+
+    /// Whether a user's account creation date should be looked up; true only
+    /// once per giveaway, and never when it's already known
+    pub fn begin_account_lookup(&mut self, platform: &str, username: &str) -> bool {
+        let key = Self::key(platform, username);
+        !self.account_created.contains_key(&key) && self.account_lookups.insert(key)
+    }
+
+    /// Record a chat message from a user
+    pub fn record_message(&mut self, platform: &str, username: &str, content: &str) {
+        self.activity += 1;
+        let profile = self.profiles.entry(Self::key(platform, username)).or_default();
+        profile.message_count += 1;
+        profile.last_active = self.activity;
+        profile.recent_messages.push_back(content.trim().to_lowercase());
+        if profile.recent_messages.len() > RECENT_MESSAGES {
+            profile.recent_messages.pop_front();
+        }
+
+        if self.profiles.len() > MAX_PROFILES {
+            self.evict_idle_profiles();
+        }
+    }
+
+    /// Forget the least recently active chatters who haven't entered the current
+    /// giveaway, down to 90% of `MAX_PROFILES` so eviction doesn't run on every message
+    fn evict_idle_profiles(&mut self) {
+        let target = MAX_PROFILES * 9 / 10;
+        let mut idle: Vec<(u64, String)> = self.profiles.iter()
+            .filter(|(_, profile)| profile.entry_attempts == 0)
+            .map(|(key, profile)| (profile.last_active, key.clone()))
+            .collect();
+        idle.sort_unstable();
+
+        let excess = self.profiles.len().saturating_sub(target);
+        for (_, key) in idle.into_iter().take(excess) {
+            self.profiles.remove(&key);
+        }
+        let profiles = &self.profiles;
+        self.account_created.retain(|key, _| profiles.contains_key(key));
+    }
+
+    /// Number of chatters currently remembered
+    pub fn profile_count(&self) -> usize {
+        self.profiles.len()
+    }
+
+    /// Record an attempt to enter the current giveaway (including rejected repeats)
+    pub fn record_entry_attempt(&mut self, platform: &str, username: &str) {
+        self.profiles.entry(Self::key(platform, username)).or_default().entry_attempts += 1;
+    }
+
+    /// Forget entry attempts and account lookups when a new giveaway starts
+    pub fn reset_entries(&mut self) {
+        for profile in self.profiles.values_mut() {
+            profile.entry_attempts = 0;
+        }
+        self.account_lookups.clear();
+    }
+
+    /// Fraud score from 0.0 (safe) to 1.0 (almost certainly a bot)
+    pub fn score(&self, platform: &str, username: &str, other_entrants: &[String], now: DateTime<Utc>) -> f32 {
+        let key = Self::key(platform, username);
+        let profile = self.profiles.get(&key);
+
+        let mut signals = Vec::with_capacity(4);
+
+        if let Some(created) = self.account_created.get(&key) {
+            let age_days = (now - *created).max(Duration::zero()).num_hours() as f32 / 24.0;
+            signals.push((self.weights.account_age, (1.0 - age_days / NEW_ACCOUNT_DAYS).max(0.0)));
+        }
+
+        // Entry messages don't count as chat history
+        let (history, repeats) = profile.map_or((0, 0), |p| {
+            let history = p.message_count.saturating_sub(p.entry_attempts);
+            let repeats = p.entry_attempts.saturating_sub(1) + Self::repeated_messages(&p.recent_messages);
+            (history, repeats)
+        });
+        signals.push((self.weights.message_history, 1.0 - (history as f32 / ESTABLISHED_MESSAGE_COUNT).min(1.0)));
+        signals.push((self.weights.duplicate_entries, (repeats as f32 / DUPLICATE_SATURATION).min(1.0)));
+        signals.push((self.weights.username_similarity, Self::username_signal(username, other_entrants)));
+
+        let total_weight: f32 = signals.iter().map(|(weight, _)| weight.max(0.0)).sum();
+        if total_weight <= 0.0 {
+            return 0.0;
+        }
+        let weighted: f32 = signals.iter().map(|(weight, signal)| weight.max(0.0) * signal).sum();
+        (weighted / total_weight).clamp(0.0, 1.0)
+    }
+
+    /// Messages that repeat an earlier one in the recent window
+    fn repeated_messages(recent: &VecDeque<String>) -> u32 {
+        let mut seen = std::collections::HashSet::new();
+        recent.iter().filter(|m| !m.is_empty() && !seen.insert(m.as_str())).count() as u32
+    }
+
+    fn username_signal(username: &str, other_entrants: &[String]) -> f32 {
+        let stem = Self::name_stem(username);
+        let similar = if stem.len() >= 4 {
+            other_entrants.iter()
+                .filter(|other| !other.eq_ignore_ascii_case(username) && Self::name_stem(other) == stem)
+                .count()
+        } else {
+            0
+        };
+
+        // Long numeric suffixes are typical of generated names
+        let trailing_digits = username.chars().rev().take_while(|c| c.is_ascii_digit()).count();
+        let generated = if trailing_digits >= 4 { 1.0 } else { 0.0 };
+
+        ((similar as f32 + generated) / SIMILAR_NAME_SATURATION).min(1.0)
+    }
+
+    /// Username with digits and separators removed, so `free_gift123` and `FreeGift_77` match
+    fn name_stem(username: &str) -> String {
+        username.chars()
+            .filter(|c| c.is_alphabetic())
+            .flat_map(char::to_lowercase)
+            .collect()
+    }
+
+    fn key(platform: &str, username: &str) -> String {
+        format!("{}:{}", platform, username.to_lowercase())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fresh_spammy_account_scores_high() {
+        let mut scorer = FraudScorer::default();
+        let now = Utc::now();
+        scorer.set_account_created("twitch", "freegift_4821", now - Duration::hours(2));
+        for _ in 0..3 {
+            scorer.record_message("twitch", "freegift_4821", "!enter");
+            scorer.record_entry_attempt("twitch", "freegift_4821");
+        }
+
+        let others = vec!["freegift_1193".to_string(), "FreeGift_7730".to_string(), "regular_viewer".to_string()];
+        let score = scorer.score("twitch", "freegift_4821", &others, now);
+        assert!(score > 0.9, "score was {}", score);
+    }
+
+    #[test]
+    fn test_regular_scores_low() {
+        let mut scorer = FraudScorer::default();
+        let now = Utc::now();
+        scorer.set_account_created("twitch", "regular_viewer", now - Duration::days(900));
+        for i in 0..25 {
+            scorer.record_message("twitch", "regular_viewer", &format!("great play #{}", i));
+        }
+        scorer.record_message("twitch", "regular_viewer", "!enter");
+        scorer.record_entry_attempt("twitch", "regular_viewer");
+
+        let others = vec!["freegift_1193".to_string(), "another_fan".to_string()];
+        let score = scorer.score("twitch", "regular_viewer", &others, now);
+        assert!(score < 0.1, "score was {}", score);
+    }
+
+    #[test]
+    fn test_weights_are_configurable() {
+        let mut scorer = FraudScorer::default();
+        let now = Utc::now();
+        scorer.record_message("twitch", "newcomer", "!enter");
+        scorer.record_entry_attempt("twitch", "newcomer");
+
+        // Only chat history counts: no history at all is maximally suspicious
+        scorer.set_weights(FraudWeights {
+            account_age: 0.0,
+            message_history: 1.0,
+            duplicate_entries: 0.0,
+            username_similarity: 0.0,
+        });
+        assert_eq!(scorer.score("twitch", "newcomer", &[], now), 1.0);
+
+        // Ignoring history, a single clean entry isn't suspicious
+        scorer.set_weights(FraudWeights { message_history: 0.0, ..FraudWeights::default() });
+        assert_eq!(scorer.score("twitch", "newcomer", &[], now), 0.0);
+    }
+
+    #[test]
+    fn test_idle_chatters_are_evicted_but_entrants_kept() {
+        let mut scorer = FraudScorer::default();
+        scorer.record_message("twitch", "entrant", "!enter");
+        scorer.record_entry_attempt("twitch", "entrant");
+        scorer.set_account_created("twitch", "entrant", Utc::now());
+        scorer.record_message("twitch", "lurker", "hi");
+        scorer.set_account_created("twitch", "lurker", Utc::now());
+
+        for i in 0..MAX_PROFILES {
+            scorer.record_message("twitch", &format!("viewer{}", i), "hello");
+        }
+
+        assert!(scorer.profile_count() <= MAX_PROFILES);
+        assert!(scorer.profiles.contains_key("twitch:entrant"));
+        assert!(scorer.account_created.contains_key("twitch:entrant"));
+        assert!(!scorer.profiles.contains_key("twitch:lurker"));
+        assert!(!scorer.account_created.contains_key("twitch:lurker"));
+        // The most recent chatters survive
+        assert!(scorer.profiles.contains_key(&format!("twitch:viewer{}", MAX_PROFILES - 1)));
+    }
+
+    #[test]
+    fn test_account_lookup_requested_once_per_giveaway() {
+        let mut scorer = FraudScorer::default();
+        assert!(scorer.begin_account_lookup("twitch", "entrant"));
+        assert!(!scorer.begin_account_lookup("twitch", "Entrant"));
+
+        scorer.reset_entries();
+        assert!(scorer.begin_account_lookup("twitch", "entrant"));
+
+        scorer.reset_entries();
+        scorer.set_account_created("twitch", "entrant", Utc::now());
+        assert!(!scorer.begin_account_lookup("twitch", "entrant"));
+    }
+}
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use chrono::{DateTime, Utc};
@@ -7,6 +8,7 @@ use log::{info, warn, debug};
 use rand::{thread_rng, Rng};
 use uuid::Uuid;
 
+use super::giveaway_fraud::{FraudScorer, FraudWeights};
 use crate::types::{ChatMessage, GiveawayType, GiveawaySettings, GiveawayResult, GiveawayError, 
                   UserLevel, ActiveGiveaway, CompletedGiveaway, GiveawayWinner, GiveawayStatus};
 
@@ -26,11 +28,47 @@ pub struct GiveawaySystem {
     
     /// AI fraud detection scores (placeholder for now)
     fraud_scores: Arc<RwLock<HashMap<String, f32>>>,
+
+    /// Heuristic fraud scoring from chat behaviour
+    fraud_scorer: Arc<RwLock<FraudScorer>>,
     
     /// Statistics tracking
     statistics: Arc<RwLock<GiveawayStatistics>>,
+
+    /// Where completed giveaways are persisted, if anywhere
+    history_path: Arc<RwLock<Option<PathBuf>>>,
+
+    /// Where the running giveaway is persisted so it survives restarts
+    active_path: Arc<RwLock<Option<PathBuf>>>,
+}
+
+/// File formats for giveaway history exports
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GiveawayExportFormat {
+    Json,
+    Csv,
+}
+
+impl GiveawayExportFormat {
+    pub fn parse(format: &str) -> Option<Self> {
+        match format.to_lowercase().as_str() {
+            "json" => Some(Self::Json),
+            "csv" => Some(Self::Csv),
+            _ => None,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Csv => "csv",
+        }
+    }
 }
 
+/// Column order for CSV history exports
+pub const GIVEAWAY_CSV_HEADER: &str = "id,type,platform,channel,creator,start_time,end_time,duration_seconds,participant_count,total_entries,success,winner,winner_platform";
+
 /// Statistics for giveaway system performance
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct GiveawayStatistics {
@@ -54,10 +92,105 @@ impl GiveawaySystem {
             default_settings: Arc::new(RwLock::new(GiveawaySettings::default())),
             user_activity: Arc::new(RwLock::new(HashMap::new())),
             fraud_scores: Arc::new(RwLock::new(HashMap::new())),
+            fraud_scorer: Arc::new(RwLock::new(FraudScorer::default())),
             statistics: Arc::new(RwLock::new(GiveawayStatistics::default())),
+            history_path: Arc::new(RwLock::new(None)),
+            active_path: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    /// Persist the running giveaway to `path` and resume one saved there by a
+    /// previous run; returns whether a giveaway was restored
+    pub async fn set_active_giveaway_path(&self, path: impl Into<PathBuf>) -> GiveawayResult<bool> {
+        let path = path.into();
+        *self.active_path.write().await = Some(path.clone());
+        if !path.exists() {
+            return Ok(false);
+        }
+
+        let content = tokio::fs::read_to_string(&path).await
+            .map_err(|e| GiveawayError::Persistence(format!("{}: {}", path.display(), e)))?;
+        let giveaway: ActiveGiveaway = serde_json::from_str(&content)
+            .map_err(|e| GiveawayError::Persistence(format!("{}: {}", path.display(), e)))?;
+
+        let mut active_guard = self.active_giveaway.write().await;
+        if active_guard.is_some() {
+            warn!("Not restoring giveaway {}: another giveaway is already running", giveaway.id);
+            return Ok(false);
+        }
+
+        // The clock keeps running from the original start time, so an active user
+        // giveaway that ran out while the bot was down comes back with entries closed
+        // and its entrants intact, ready for a moderator to draw a winner
+        if giveaway.has_timed_out() {
+            warn!("Restored giveaway {} timed out while the bot was offline; entries are closed", giveaway.id);
+        } else {
+            info!("Restored giveaway {} with {} participants", giveaway.id, giveaway.participant_count);
+        }
+        *active_guard = Some(giveaway);
+        Ok(true)
+    }
+
+    /// Write the running giveaway to disk, or remove the file once there isn't one
+    async fn save_active_giveaway(&self, giveaway: Option<&ActiveGiveaway>) {
+        let Some(path) = self.active_path.read().await.clone() else {
+            return;
+        };
+
+        let result = match giveaway {
+            Some(giveaway) => match serde_json::to_string_pretty(giveaway) {
+                Ok(content) => tokio::fs::write(&path, content).await,
+                Err(e) => Err(std::io::Error::other(e)),
+            },
+            None if path.exists() => tokio::fs::remove_file(&path).await,
+            None => Ok(()),
+        };
+        if let Err(e) = result {
+            warn!("Failed to save active giveaway to {}: {}", path.display(), e);
         }
     }
 
+    /// What changes when a chat message affects the giveaway
+    fn entry_fingerprint(giveaway: &ActiveGiveaway) -> (u32, usize, usize, bool) {
+        (
+            giveaway.participant_count,
+            giveaway.eligible_users.len(),
+            giveaway.keyword_entries.len(),
+            giveaway.winner.is_some(),
+        )
+    }
+
+    /// Persist completed giveaways to `path`, loading any history already saved there
+    pub async fn set_history_path(&self, path: impl Into<PathBuf>) -> GiveawayResult<usize> {
+        let path = path.into();
+        let loaded = if path.exists() {
+            let content = tokio::fs::read_to_string(&path).await
+                .map_err(|e| GiveawayError::Persistence(format!("{}: {}", path.display(), e)))?;
+            serde_json::from_str::<Vec<CompletedGiveaway>>(&content)
+                .map_err(|e| GiveawayError::Persistence(format!("{}: {}", path.display(), e)))?
+        } else {
+            Vec::new()
+        };
+
+        let count = loaded.len();
+        if count > 0 {
+            info!("Loaded {} completed giveaways from {}", count, path.display());
+            *self.giveaway_history.write().await = loaded;
+        }
+        *self.history_path.write().await = Some(path);
+        Ok(count)
+    }
+
+    async fn save_history(&self) -> GiveawayResult<()> {
+        let Some(path) = self.history_path.read().await.clone() else {
+            return Ok(());
+        };
+        let content = serde_json::to_string_pretty(&*self.giveaway_history.read().await)
+            .map_err(|e| GiveawayError::Persistence(e.to_string()))?;
+        tokio::fs::write(&path, content).await
+            .map_err(|e| GiveawayError::Persistence(format!("{}: {}", path.display(), e)))
+    }
+
     /// Start a new giveaway
     pub async fn start_giveaway(
         &self,
@@ -110,12 +243,16 @@ impl GiveawaySystem {
             }
         }
 
+        // Entry attempts only count within one giveaway
+        self.fraud_scorer.write().await.reset_entries();
+
         // Set status to active
         giveaway.status = GiveawayStatus::Active;
         let giveaway_id = giveaway.id;
 
         // Store the giveaway
         *active_guard = Some(giveaway);
+        self.save_active_giveaway(active_guard.as_ref()).await;
 
         // Update statistics
         let mut stats = self.statistics.write().await;
@@ -134,12 +271,14 @@ impl GiveawaySystem {
             Some(g) => g,
             None => return Err(GiveawayError::NoActiveGiveaway),
         };
+        self.save_active_giveaway(None).await;
 
         // Select winner if not forcing cancellation
         let winner = if force {
             giveaway.cancel(Some("Forced cancellation".to_string()));
             None
         } else {
+            self.rescore_entrants(&mut giveaway).await;
             match self.select_winner(&mut giveaway).await {
                 Ok(winner) => {
                     giveaway.complete_with_winner(winner.clone());
@@ -157,6 +296,9 @@ impl GiveawaySystem {
         // Move to history
         let completed = CompletedGiveaway::from(giveaway);
         self.giveaway_history.write().await.push(completed.clone());
+        if let Err(e) = self.save_history().await {
+            warn!("Failed to save giveaway history: {}", e);
+        }
 
         // Update statistics
         let mut stats = self.statistics.write().await;
@@ -186,6 +328,7 @@ impl GiveawaySystem {
     pub async fn process_message(&self, message: &ChatMessage) -> GiveawayResult<()> {
         // Update user activity tracking
         self.update_user_activity(&message.platform, &message.username).await;
+        self.fraud_scorer.write().await.record_message(&message.platform, &message.username, &message.content);
 
         let mut active_guard = self.active_giveaway.write().await;
         let giveaway = match active_guard.as_mut() {
@@ -193,6 +336,16 @@ impl GiveawaySystem {
             None => return Ok(()), // No active giveaway
         };
 
+        let before = Self::entry_fingerprint(giveaway);
+        self.process_entry(giveaway, message).await?;
+        if Self::entry_fingerprint(giveaway) != before {
+            self.save_active_giveaway(Some(giveaway)).await;
+        }
+        Ok(())
+    }
+
+    /// Apply a chat message to the running giveaway
+    async fn process_entry(&self, giveaway: &mut ActiveGiveaway, message: &ChatMessage) -> GiveawayResult<()> {
         // Skip if not the right platform (unless cross-platform)
         if giveaway.platform != "all" && giveaway.platform != message.platform {
             return Ok(());
@@ -252,10 +405,12 @@ impl GiveawaySystem {
                 // Check if message contains the keyword
                 if message_text.trim() == target_keyword || message_text.contains(&target_keyword) {
                     let user_key = format!("{}:{}", message.platform, message.username.to_lowercase());
+                    self.fraud_scorer.write().await.record_entry_attempt(&message.platform, &message.username);
                     
                     // Anti-spam check
                     if *anti_spam && giveaway.keyword_entries.contains_key(&user_key) {
                         debug!("User {} already entered keyword, ignoring repeat", message.username);
+                        self.update_fraud_score(giveaway, message, fraud_score).await;
                         return Ok(());
                     }
 
@@ -301,6 +456,7 @@ impl GiveawaySystem {
             }
         }
 
+        self.update_fraud_score(giveaway, message, fraud_score).await;
         Ok(())
     }
 
@@ -309,6 +465,24 @@ impl GiveawaySystem {
         &self,
         platform: &str,
         username: &str,
+    ) -> GiveawayResult<bool> {
+        let username = username.trim_start_matches('@');
+        let currently_eligible = {
+            let active_guard = self.active_giveaway.read().await;
+            let giveaway = active_guard.as_ref().ok_or(GiveawayError::NoActiveGiveaway)?;
+            giveaway.eligible_users.get(&format!("{}:{}", platform, username.to_lowercase()))
+                .is_some_and(|status| status.eligible)
+        };
+        self.set_user_eligibility(platform, username, !currently_eligible).await
+    }
+
+    /// Manually add a user to (or remove them from) the active giveaway, overriding
+    /// automatic entry; returns whether the user is now eligible
+    pub async fn set_user_eligibility(
+        &self,
+        platform: &str,
+        username: &str,
+        eligible: bool,
     ) -> GiveawayResult<bool> {
         let mut active_guard = self.active_giveaway.write().await;
         let giveaway = match active_guard.as_mut() {
@@ -316,32 +490,13 @@ impl GiveawaySystem {
             None => return Err(GiveawayError::NoActiveGiveaway),
         };
 
-        let user_key = format!("{}:{}", platform, username.to_lowercase());
-        
-        if let Some(status) = giveaway.eligible_users.get_mut(&user_key) {
-            status.toggle_eligibility();
-            info!("Toggled eligibility for {}: {}", username, status.eligible);
-            Ok(status.eligible)
-        } else {
-            // User not in list, add them as eligible
-            let user_level = UserLevel::Viewer; // Default level
-            giveaway.update_user_eligibility(
-                username.to_string(),
-                platform.to_string(),
-                user_level,
-                true,
-            );
-            
-            if let Some(status) = giveaway.eligible_users.get_mut(&user_key) {
-                status.manual_override = true;
-                info!("Added new user {} as eligible", username);
-                Ok(true)
-            } else {
-                Err(GiveawayError::UserNotEligible {
-                    reason: "Failed to add user".to_string(),
-                })
-            }
+        let username = username.trim_start_matches('@');
+        if giveaway.set_manual_eligibility(username, platform, eligible) {
+            info!("Manually {} {} ({} participants)",
+                  if eligible { "added" } else { "removed" }, username, giveaway.participant_count);
         }
+        self.save_active_giveaway(Some(giveaway)).await;
+        Ok(eligible)
     }
 
     /// Reset all user eligibility
@@ -354,6 +509,7 @@ impl GiveawaySystem {
 
         let previous_count = giveaway.participant_count;
         giveaway.reset_eligibility();
+        self.save_active_giveaway(Some(giveaway)).await;
 
         info!("Reset eligibility for {} users", previous_count);
         Ok(previous_count)
@@ -421,6 +577,25 @@ impl GiveawaySystem {
             .collect()
     }
 
+    /// Export all completed giveaways (oldest first) to `path`; returns how many were written
+    pub async fn export_history(&self, format: GiveawayExportFormat, path: &Path) -> GiveawayResult<usize> {
+        let history = self.giveaway_history.read().await;
+        if history.is_empty() {
+            return Err(GiveawayError::NoHistory);
+        }
+
+        let content = match format {
+            GiveawayExportFormat::Json => serde_json::to_string_pretty(&*history)
+                .map_err(|e| GiveawayError::Persistence(e.to_string()))?,
+            GiveawayExportFormat::Csv => history_to_csv(&history),
+        };
+        tokio::fs::write(path, content).await
+            .map_err(|e| GiveawayError::Persistence(format!("{}: {}", path.display(), e)))?;
+
+        info!("Exported {} completed giveaways to {}", history.len(), path.display());
+        Ok(history.len())
+    }
+
     // Private helper methods
 
     /// Validate giveaway configuration
@@ -598,6 +773,62 @@ impl GiveawaySystem {
         scores.get(&user_key).copied().unwrap_or(0.0)
     }
 
+    /// Score an entrant's chat behaviour and store it on their eligibility status
+    async fn update_fraud_score(&self, giveaway: &mut ActiveGiveaway, message: &ChatMessage, external_score: f32) {
+        if !giveaway.settings.fraud_detection_enabled {
+            return;
+        }
+
+        let user_key = format!("{}:{}", message.platform, message.username.to_lowercase());
+        if !giveaway.eligible_users.contains_key(&user_key) {
+            return;
+        }
+
+        let others: Vec<String> = giveaway.eligible_users.iter()
+            .filter(|(key, _)| **key != user_key)
+            .map(|(_, status)| status.username.clone())
+            .collect();
+        let heuristic = self.fraud_scorer.read().await.score(&message.platform, &message.username, &others, Utc::now());
+
+        if let Some(status) = giveaway.eligible_users.get_mut(&user_key) {
+            status.fraud_score = heuristic.max(external_score);
+            if status.fraud_score > giveaway.settings.max_fraud_score {
+                debug!("Giveaway entrant {} looks fraudulent (score {:.2})", message.username, status.fraud_score);
+            }
+        }
+    }
+
+    /// Score every entrant again right before the draw, so account ages looked up
+    /// after they entered and look-alikes who entered later are taken into account
+    async fn rescore_entrants(&self, giveaway: &mut ActiveGiveaway) {
+        if !giveaway.settings.fraud_detection_enabled {
+            return;
+        }
+
+        let entrants: Vec<String> = giveaway.eligible_users.values()
+            .map(|status| status.username.clone())
+            .collect();
+        let scorer = self.fraud_scorer.read().await;
+        let external_scores = self.fraud_scores.read().await;
+        let now = Utc::now();
+
+        for (user_key, status) in giveaway.eligible_users.iter_mut() {
+            let heuristic = scorer.score(&status.platform, &status.username, &entrants, now);
+            status.fraud_score = heuristic.max(external_scores.get(user_key).copied().unwrap_or(0.0));
+        }
+    }
+
+    /// Whether the platform should be asked when this user's account was created:
+    /// they've entered a running giveaway that checks for fraud, and nobody asked yet
+    pub async fn wants_account_created(&self, platform: &str, username: &str) -> bool {
+        let user_key = format!("{}:{}", platform, username.to_lowercase());
+        let entered = match self.active_giveaway.read().await.as_ref() {
+            Some(giveaway) => giveaway.settings.fraud_detection_enabled && giveaway.eligible_users.contains_key(&user_key),
+            None => false,
+        };
+        entered && self.fraud_scorer.write().await.begin_account_lookup(platform, username)
+    }
+
     /// Calculate time remaining for active user giveaways
     fn calculate_time_remaining(&self, giveaway: &ActiveGiveaway) -> Option<i64> {
         match &giveaway.giveaway_type {
@@ -621,6 +852,11 @@ impl GiveawaySystem {
         Ok(())
     }
 
+    /// When a user's platform account was created, if it has been looked up
+    pub async fn account_created(&self, platform: &str, username: &str) -> Option<DateTime<Utc>> {
+        self.fraud_scorer.read().await.account_created(platform, username)
+    }
+
     /// Set fraud score for a user (for AI integration)
     pub async fn set_fraud_score(&self, platform: &str, username: &str, score: f32) {
         let user_key = format!("{}:{}", platform, username.to_lowercase());
@@ -628,6 +864,21 @@ impl GiveawaySystem {
         scores.insert(user_key, score.clamp(0.0, 1.0));
     }
 
+    /// Set how heuristic fraud signals are weighted
+    pub async fn set_fraud_weights(&self, weights: FraudWeights) {
+        self.fraud_scorer.write().await.set_weights(weights);
+    }
+
+    /// How heuristic fraud signals are currently weighted
+    pub async fn fraud_weights(&self) -> FraudWeights {
+        self.fraud_scorer.read().await.weights().clone()
+    }
+
+    /// Record a user's platform account creation date for fraud scoring
+    pub async fn set_account_created(&self, platform: &str, username: &str, created: DateTime<Utc>) {
+        self.fraud_scorer.write().await.set_account_created(platform, username, created);
+    }
+
     /// Generate random number for random number giveaway
     pub async fn generate_random_number(&self, min: u32, max: u32) -> GiveawayResult<u32> {
         let mut active_guard = self.active_giveaway.write().await;
@@ -645,6 +896,7 @@ impl GiveawaySystem {
         let mut rng = thread_rng();
         let number = rng.gen_range(min..=max);
         giveaway.generated_number = Some(number);
+        self.save_active_giveaway(Some(giveaway)).await;
 
         info!("Generated random number for giveaway: {}", number);
         Ok(number)
@@ -681,9 +933,303 @@ pub struct EligibilityInfo {
     pub manual_override: bool,
 }
 
+/// Render completed giveaways as CSV rows under `GIVEAWAY_CSV_HEADER`
+pub fn history_to_csv(history: &[CompletedGiveaway]) -> String {
+    let mut csv = String::from(GIVEAWAY_CSV_HEADER);
+    csv.push('\n');
+
+    for giveaway in history {
+        let row = [
+            giveaway.id.to_string(),
+            giveaway.giveaway_type.name().to_string(),
+            giveaway.platform.clone(),
+            giveaway.channel.clone(),
+            giveaway.creator.clone(),
+            giveaway.start_time.to_rfc3339(),
+            giveaway.end_time.to_rfc3339(),
+            giveaway.duration_seconds.to_string(),
+            giveaway.participant_count.to_string(),
+            giveaway.total_entries.to_string(),
+            giveaway.success.to_string(),
+            giveaway.winner.as_ref().map(|w| w.username.clone()).unwrap_or_default(),
+            giveaway.winner.as_ref().map(|w| w.platform.clone()).unwrap_or_default(),
+        ];
+        let fields: Vec<String> = row.iter().map(|field| csv_field(field)).collect();
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
 // Default implementation
 impl Default for GiveawaySystem {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::GiveawayWinner;
+
+    fn completed(channel: &str, winner: Option<&str>, participants: u32) -> CompletedGiveaway {
+        let start_time = Utc::now() - chrono::Duration::minutes(5);
+        CompletedGiveaway {
+            id: Uuid::new_v4(),
+            giveaway_type: GiveawayType::Keyword {
+                keyword: "!enter".to_string(),
+                case_sensitive: false,
+                anti_spam: true,
+                max_entries_per_user: Some(1),
+            },
+            settings: GiveawaySettings::default(),
+            start_time,
+            end_time: start_time + chrono::Duration::seconds(300),
+            winner: winner.map(|name| GiveawayWinner::new(name.to_string(), "twitch".to_string(), UserLevel::Viewer, 1)),
+            participant_count: participants,
+            total_entries: participants,
+            success: winner.is_some(),
+            creator: "streamer".to_string(),
+            channel: channel.to_string(),
+            platform: "twitch".to_string(),
+            duration_seconds: 300,
+        }
+    }
+
+    #[test]
+    fn test_history_to_csv_columns() {
+        let history = vec![
+            completed("main", Some("lucky_viewer"), 12),
+            completed("side, channel", None, 0),
+        ];
+        let csv = history_to_csv(&history);
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], GIVEAWAY_CSV_HEADER);
+        let columns = GIVEAWAY_CSV_HEADER.split(',').count();
+
+        let first: Vec<&str> = lines[1].split(',').collect();
+        assert_eq!(first.len(), columns);
+        assert_eq!(first[0], history[0].id.to_string());
+        assert_eq!(first[1], "keyword");
+        assert_eq!(first[3], "main");
+        assert_eq!(first[7], "300");
+        assert_eq!(first[8], "12");
+        assert_eq!(first[10], "true");
+        assert_eq!(first[11], "lucky_viewer");
+        assert_eq!(first[12], "twitch");
+
+        // Fields containing commas are quoted; a giveaway without a winner leaves those columns empty
+        assert!(lines[2].contains(",\"side, channel\","));
+        assert!(lines[2].ends_with(",0,0,false,,"));
+    }
+
+    #[tokio::test]
+    async fn test_fraudulent_entrants_excluded_from_pool() {
+        let system = GiveawaySystem::new();
+        let keyword = GiveawayType::Keyword {
+            keyword: "!enter".to_string(),
+            case_sensitive: false,
+            anti_spam: true,
+            max_entries_per_user: Some(1),
+        };
+        system.start_giveaway(keyword, "streamer".to_string(), "main".to_string(), "twitch".to_string(), None).await.unwrap();
+
+        let now = Utc::now();
+        system.set_account_created("twitch", "regular_viewer", now - chrono::Duration::days(400)).await;
+        for i in 0..12 {
+            system.process_message(&ChatMessage::new("twitch", "main", "regular_viewer", format!("nice one {}", i))).await.unwrap();
+        }
+        system.process_message(&ChatMessage::new("twitch", "main", "regular_viewer", "!enter")).await.unwrap();
+
+        let bots = ["freegift_1111", "freegift_2222", "freegift_3333"];
+        for bot in bots {
+            system.set_account_created("twitch", bot, now - chrono::Duration::hours(1)).await;
+        }
+        for _ in 0..3 {
+            for bot in bots {
+                system.process_message(&ChatMessage::new("twitch", "main", bot, "!enter")).await.unwrap();
+            }
+        }
+
+        let entrants = system.get_eligible_users().await;
+        assert_eq!(entrants.len(), 4);
+        let regular = entrants.iter().find(|e| e.username == "regular_viewer").unwrap();
+        assert!(regular.fraud_score < 0.1);
+        assert!(entrants.iter().filter(|e| e.username != "regular_viewer").all(|e| e.fraud_score > 0.7));
+
+        // Only the regular is left in the winner pool
+        let pool = system.active_giveaway.read().await.as_ref().unwrap()
+            .get_eligible_users().iter().map(|s| s.username.clone()).collect::<Vec<_>>();
+        assert_eq!(pool, vec!["regular_viewer".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_scores_are_recomputed_before_the_draw() {
+        let system = GiveawaySystem::new();
+        system.set_fraud_weights(FraudWeights {
+            account_age: 1.0,
+            message_history: 0.0,
+            duplicate_entries: 0.0,
+            username_similarity: 0.0,
+        }).await;
+        let keyword = GiveawayType::Keyword {
+            keyword: "!enter".to_string(),
+            case_sensitive: false,
+            anti_spam: true,
+            max_entries_per_user: Some(1),
+        };
+        system.start_giveaway(keyword, "streamer".to_string(), "main".to_string(), "twitch".to_string(), None).await.unwrap();
+
+        for user in ["regular_viewer", "fresh_account"] {
+            system.process_message(&ChatMessage::new("twitch", "main", user, "!enter")).await.unwrap();
+        }
+        assert!(system.wants_account_created("twitch", "fresh_account").await);
+        assert!(!system.wants_account_created("twitch", "fresh_account").await);
+        assert!(!system.wants_account_created("twitch", "lurker").await);
+
+        // The account age only arrives after the entry was scored
+        let now = Utc::now();
+        system.set_account_created("twitch", "regular_viewer", now - chrono::Duration::days(400)).await;
+        system.set_account_created("twitch", "fresh_account", now - chrono::Duration::hours(1)).await;
+        assert!(system.get_eligible_users().await.iter().all(|e| e.fraud_score == 0.0));
+
+        let winner = system.end_giveaway(false).await.unwrap().unwrap();
+        assert_eq!(winner.username, "regular_viewer");
+    }
+
+    #[tokio::test]
+    async fn test_manual_eligibility_updates_count_and_pool() {
+        let system = GiveawaySystem::new();
+        let keyword = GiveawayType::Keyword {
+            keyword: "!enter".to_string(),
+            case_sensitive: false,
+            anti_spam: false,
+            max_entries_per_user: None,
+        };
+        system.start_giveaway(keyword, "streamer".to_string(), "main".to_string(), "twitch".to_string(), None).await.unwrap();
+        system.process_message(&ChatMessage::new("twitch", "main", "viewer1", "!enter")).await.unwrap();
+        assert_eq!(system.get_giveaway_status().await.unwrap().participant_count, 1);
+
+        // Add someone who missed the keyword
+        assert!(system.set_user_eligibility("twitch", "@latecomer", true).await.unwrap());
+        assert_eq!(system.get_giveaway_status().await.unwrap().participant_count, 2);
+        // Adding twice doesn't double count
+        system.set_user_eligibility("twitch", "latecomer", true).await.unwrap();
+        assert_eq!(system.get_giveaway_status().await.unwrap().participant_count, 2);
+
+        // Removed users stay out even if they type the keyword again
+        assert!(!system.set_user_eligibility("twitch", "viewer1", false).await.unwrap());
+        system.process_message(&ChatMessage::new("twitch", "main", "viewer1", "!enter")).await.unwrap();
+        assert_eq!(system.get_giveaway_status().await.unwrap().participant_count, 1);
+
+        let pool = system.active_giveaway.read().await.as_ref().unwrap()
+            .get_eligible_users().iter().map(|s| s.username.clone()).collect::<Vec<_>>();
+        assert_eq!(pool, vec!["latecomer".to_string()]);
+
+        // Toggling flips back
+        assert!(system.toggle_user_eligibility("twitch", "viewer1").await.unwrap());
+        assert_eq!(system.get_giveaway_status().await.unwrap().participant_count, 2);
+        let winner = system.end_giveaway(false).await.unwrap().unwrap();
+        assert!(["viewer1", "latecomer"].contains(&winner.username.as_str()));
+    }
+
+    #[tokio::test]
+    async fn test_active_giveaway_survives_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("active_giveaway.json");
+
+        let system = GiveawaySystem::new();
+        assert!(!system.set_active_giveaway_path(&path).await.unwrap());
+        let keyword = GiveawayType::Keyword {
+            keyword: "!enter".to_string(),
+            case_sensitive: false,
+            anti_spam: true,
+            max_entries_per_user: Some(1),
+        };
+        let id = system.start_giveaway(keyword, "streamer".to_string(), "main".to_string(), "twitch".to_string(), None).await.unwrap();
+        system.process_message(&ChatMessage::new("twitch", "main", "viewer1", "!enter")).await.unwrap();
+        system.process_message(&ChatMessage::new("twitch", "main", "viewer2", "!enter")).await.unwrap();
+        assert!(path.exists());
+
+        // "Restart" the bot
+        let restarted = GiveawaySystem::new();
+        assert!(restarted.set_active_giveaway_path(&path).await.unwrap());
+        let status = restarted.get_giveaway_status().await.unwrap();
+        assert_eq!(status.id, id);
+        assert_eq!(status.participant_count, 2);
+
+        // Keyword capture resumes, and earlier entries still count against repeats
+        restarted.process_message(&ChatMessage::new("twitch", "main", "viewer1", "!enter")).await.unwrap();
+        restarted.process_message(&ChatMessage::new("twitch", "main", "viewer3", "!enter")).await.unwrap();
+        assert_eq!(restarted.get_giveaway_status().await.unwrap().participant_count, 3);
+
+        // Ending the giveaway clears the saved state
+        assert!(restarted.end_giveaway(false).await.unwrap().is_some());
+        assert!(!path.exists());
+        assert!(!GiveawaySystem::new().set_active_giveaway_path(&path).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_restored_giveaway_that_timed_out_offline() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("active_giveaway.json");
+
+        let mut giveaway = ActiveGiveaway::new(
+            GiveawayType::ActiveUser { duration_minutes: 10, min_messages: None },
+            GiveawaySettings::default(),
+            "streamer".to_string(),
+            "main".to_string(),
+            "twitch".to_string(),
+        );
+        giveaway.status = GiveawayStatus::Active;
+        giveaway.start_time = Utc::now() - chrono::Duration::hours(1);
+        giveaway.update_user_eligibility("viewer1".to_string(), "twitch".to_string(), UserLevel::Viewer, true);
+        std::fs::write(&path, serde_json::to_string(&giveaway).unwrap()).unwrap();
+
+        let system = GiveawaySystem::new();
+        assert!(system.set_active_giveaway_path(&path).await.unwrap());
+        assert_eq!(system.get_giveaway_status().await.unwrap().time_remaining, Some(0));
+
+        // Entries are closed, but the existing entrant can still win
+        system.process_message(&ChatMessage::new("twitch", "main", "viewer2", "hello")).await.unwrap();
+        assert_eq!(system.get_giveaway_status().await.unwrap().participant_count, 1);
+        assert_eq!(system.end_giveaway(false).await.unwrap().unwrap().username, "viewer1");
+    }
+
+    #[tokio::test]
+    async fn test_export_and_persist_history() {
+        let dir = tempfile::tempdir().unwrap();
+        let system = GiveawaySystem::new();
+
+        // Nothing to export yet
+        let err = system.export_history(GiveawayExportFormat::Csv, &dir.path().join("empty.csv")).await;
+        assert!(matches!(err, Err(GiveawayError::NoHistory)));
+        assert!(!dir.path().join("empty.csv").exists());
+
+        let history_path = dir.path().join("giveaways.json");
+        assert_eq!(system.set_history_path(&history_path).await.unwrap(), 0);
+        system.giveaway_history.write().await.push(completed("main", Some("winner1"), 3));
+        system.save_history().await.unwrap();
+
+        let json_path = dir.path().join("export.json");
+        assert_eq!(system.export_history(GiveawayExportFormat::Json, &json_path).await.unwrap(), 1);
+        let exported: Vec<CompletedGiveaway> = serde_json::from_str(&std::fs::read_to_string(&json_path).unwrap()).unwrap();
+        assert_eq!(exported[0].winner.as_ref().unwrap().username, "winner1");
+
+        // History survives a restart
+        let reloaded = GiveawaySystem::new();
+        assert_eq!(reloaded.set_history_path(&history_path).await.unwrap(), 1);
+        assert_eq!(reloaded.get_history(None).await[0].participant_count, 3);
+    }
+}
@@ -0,0 +1,179 @@
+use anyhow::{bail, Context, Result};
+use log::info;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use tokio::fs;
+
+/// Key for a single chat account: `platform:username`, case-folded and
+/// without a leading `@`
+pub fn account_key(platform: &str, username: &str) -> String {
+    format!("{}:{}", platform.trim().to_lowercase(), username.trim().trim_start_matches('@').to_lowercase())
+}
+
+/// Links between chat accounts that belong to the same person
+///
+/// Every account resolves to a canonical key: its own [`account_key`], unless
+/// it was linked to another account, in which case it resolves to that
+/// account's key. State kept under the resolved key (points, reputation,
+/// offense history, shadow mutes) is then shared by all the linked accounts.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct IdentityMap {
+    /// Linked account key -> canonical key; canonical accounts have no entry
+    links: HashMap<String, String>,
+}
+
+impl IdentityMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load links saved with [`IdentityMap::save`]; no file means no links
+    pub async fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::new());
+        }
+
+        let content = fs::read_to_string(path).await
+            .with_context(|| format!("Failed to read identity links: {}", path.display()))?;
+        let identities: Self = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse identity links: {}", path.display()))?;
+
+        info!("Loaded {} identity links", identities.links.len());
+        Ok(identities)
+    }
+
+    pub async fn save(&self, path: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(path, content).await
+            .with_context(|| format!("Failed to write identity links: {}", path.display()))
+    }
+
+    /// Treat `other` as the same person as `primary`; both then resolve to
+    /// `primary`'s canonical key, which is returned. Accounts already linked
+    /// to `other` move with it. Fails if `other` is linked to someone else.
+    pub fn link_identities(&mut self, primary: (&str, &str), other: (&str, &str)) -> Result<String> {
+        let canonical = self.resolve(primary.0, primary.1);
+        let other_key = account_key(other.0, other.1);
+        if self.resolve(other.0, other.1) == canonical {
+            return Ok(canonical);
+        }
+        if let Some(existing) = self.links.get(&other_key) {
+            bail!("{} is already linked to {}; unlink it first", other_key, existing);
+        }
+
+        for target in self.links.values_mut().filter(|target| **target == other_key) {
+            *target = canonical.clone();
+        }
+        self.links.insert(other_key.clone(), canonical.clone());
+        info!("Linked {} to {}", other_key, canonical);
+        Ok(canonical)
+    }
+
+    /// Split an account off from the accounts it was linked with. When it was
+    /// the canonical account, the first of the others takes over. Returns
+    /// false if the account wasn't linked to anything.
+    pub fn unlink(&mut self, platform: &str, username: &str) -> bool {
+        let key = account_key(platform, username);
+        if self.links.remove(&key).is_some() {
+            info!("Unlinked {}", key);
+            return true;
+        }
+
+        let mut members: Vec<String> = self.links.iter()
+            .filter(|(_, target)| **target == key)
+            .map(|(member, _)| member.clone())
+            .collect();
+        if members.is_empty() {
+            return false;
+        }
+        members.sort();
+        let successor = members.remove(0);
+        self.links.remove(&successor);
+        for member in members {
+            self.links.insert(member, successor.clone());
+        }
+        info!("Unlinked {}, {} now stands for its former links", key, successor);
+        true
+    }
+
+    /// Canonical key for an account
+    pub fn resolve(&self, platform: &str, username: &str) -> String {
+        let key = account_key(platform, username);
+        self.links.get(&key).cloned().unwrap_or(key)
+    }
+
+    /// Canonical key for a `platform:username` key; keys without a platform
+    /// are only case-folded
+    pub fn resolve_key(&self, key: &str) -> String {
+        match key.split_once(':') {
+            Some((platform, username)) => self.resolve(platform, username),
+            None => key.trim().to_lowercase(),
+        }
+    }
+
+    /// Whether two accounts resolve to the same person
+    pub fn is_same_person(&self, a: (&str, &str), b: (&str, &str)) -> bool {
+        self.resolve(a.0, a.1) == self.resolve(b.0, b.1)
+    }
+
+    /// Every account linked with this one, canonical account first and the
+    /// rest sorted; just the account itself when it isn't linked
+    pub fn linked_accounts(&self, platform: &str, username: &str) -> Vec<String> {
+        let canonical = self.resolve(platform, username);
+        let mut members: Vec<String> = self.links.iter()
+            .filter(|(_, target)| **target == canonical)
+            .map(|(member, _)| member.clone())
+            .collect();
+        members.sort();
+        members.insert(0, canonical);
+        members
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_linked_accounts_resolve_to_one_identity() {
+        let mut identities = IdentityMap::new();
+        assert_eq!(identities.resolve("twitch", "@Alice"), "twitch:alice");
+        assert!(!identities.is_same_person(("twitch", "alice"), ("youtube", "AliceLive")));
+
+        let canonical = identities.link_identities(("Twitch", "Alice"), ("youtube", "AliceLive")).unwrap();
+        assert_eq!(canonical, "twitch:alice");
+        assert_eq!(identities.resolve("youtube", "alicelive"), "twitch:alice");
+        assert_eq!(identities.resolve_key("YouTube:ALICELIVE"), "twitch:alice");
+        assert!(identities.is_same_person(("twitch", "ALICE"), ("youtube", "AliceLive")));
+
+        // Linking through an already linked account joins the same identity
+        identities.link_identities(("youtube", "AliceLive"), ("kick", "alice_k")).unwrap();
+        assert_eq!(identities.linked_accounts("kick", "alice_k"), ["twitch:alice", "kick:alice_k", "youtube:alicelive"]);
+        assert!(identities.link_identities(("twitch", "bob"), ("kick", "alice_k")).is_err());
+
+        // A group moves along when its canonical account is linked elsewhere
+        identities.link_identities(("discord", "alice#1"), ("twitch", "alice")).unwrap();
+        assert_eq!(identities.resolve("youtube", "alicelive"), "discord:alice#1");
+
+        assert!(identities.unlink("discord", "alice#1"));
+        assert_eq!(identities.resolve("discord", "alice#1"), "discord:alice#1");
+        assert_eq!(identities.linked_accounts("twitch", "alice"), ["kick:alice_k", "twitch:alice", "youtube:alicelive"]);
+        assert!(!identities.unlink("twitch", "bob"));
+    }
+
+    #[tokio::test]
+    async fn test_links_survive_a_save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("identity_links.json");
+        assert_eq!(IdentityMap::load(&path).await.unwrap(), IdentityMap::new());
+
+        let mut identities = IdentityMap::new();
+        identities.link_identities(("twitch", "Alice"), ("youtube", "AliceLive")).unwrap();
+        identities.save(&path).await.unwrap();
+
+        let loaded = IdentityMap::load(&path).await.unwrap();
+        assert_eq!(loaded, identities);
+        assert_eq!(loaded.resolve("youtube", "alicelive"), "twitch:alice");
+    }
+}
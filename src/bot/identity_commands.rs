@@ -0,0 +1,180 @@
+use anyhow::Result;
+use log::{info, warn};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use crate::bot::achievements::AchievementSystem;
+use crate::bot::identity;
+use crate::bot::moderation::ModerationSystem;
+use crate::bot::points::PointsSystem;
+use crate::types::ChatMessage;
+
+/// Links accounts on different platforms into one identity, merging what was
+/// kept for each of them, and the moderator commands that do it from chat
+pub struct IdentityCommands {
+    points_system: Arc<PointsSystem>,
+    achievement_system: Arc<AchievementSystem>,
+    moderation_system: Arc<ModerationSystem>,
+    /// Where links are saved; unsaved when unset
+    links_path: RwLock<Option<PathBuf>>,
+}
+
+impl IdentityCommands {
+    pub fn new(
+        points_system: Arc<PointsSystem>,
+        achievement_system: Arc<AchievementSystem>,
+        moderation_system: Arc<ModerationSystem>,
+    ) -> Self {
+        Self {
+            points_system,
+            achievement_system,
+            moderation_system,
+            links_path: RwLock::new(None),
+        }
+    }
+
+    pub async fn set_links_path(&self, path: PathBuf) {
+        *self.links_path.write().await = Some(path);
+    }
+
+    /// Process account linking commands (!linkaccounts, !unlinkaccount)
+    pub async fn process_command(
+        &self,
+        command: &str,
+        args: &[&str],
+        message: &ChatMessage,
+        response_sender: &tokio::sync::mpsc::Sender<(String, String, String)>,
+    ) -> Result<bool> {
+        // Only moderators can link accounts
+        if !message.is_mod {
+            return Ok(false);
+        }
+
+        match command {
+            "linkaccounts" => {
+                self.handle_link_accounts_command(args, message, response_sender).await?;
+                Ok(true)
+            }
+            "unlinkaccount" => {
+                self.handle_unlink_account_command(args, message, response_sender).await?;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    /// Treat `other` as the same person as `primary`, merging their state; see
+    /// `ChatBot::link_identities`
+    pub async fn link_identities(&self, primary: (&str, &str), other: (&str, &str)) -> Result<String> {
+        let identities = self.moderation_system.identities();
+        let (previous, identity) = {
+            let mut identities = identities.write().await;
+            let previous = identities.resolve(other.0, other.1);
+            (previous, identities.link_identities(primary, other)?)
+        };
+
+        if previous != identity {
+            self.merge_identity_state(&previous, &identity).await;
+        }
+        self.save_links().await?;
+        Ok(identity)
+    }
+
+    /// Split an account off from the identity it was linked to; see
+    /// `ChatBot::unlink_identity`
+    pub async fn unlink_identity(&self, platform: &str, username: &str) -> Result<bool> {
+        let identities = self.moderation_system.identities();
+        let successor = {
+            let mut identities = identities.write().await;
+            let key = identity::account_key(platform, username);
+            let members = identities.linked_accounts(platform, username);
+            if !identities.unlink(platform, username) {
+                return Ok(false);
+            }
+            // The canonical account leads the list, with the others after it
+            (members[0] == key).then(|| identities.resolve_key(&members[1])).map(|successor| (key, successor))
+        };
+
+        if let Some((previous, successor)) = successor {
+            self.merge_identity_state(&previous, &successor).await;
+        }
+        self.save_links().await?;
+        Ok(true)
+    }
+
+    /// Move everything kept per identity from one identity key to another
+    async fn merge_identity_state(&self, from: &str, into: &str) {
+        self.points_system.merge_user(from, into).await;
+        self.achievement_system.merge_user(from, into).await;
+        self.moderation_system.merge_identity(from, into).await;
+    }
+
+    async fn save_links(&self) -> Result<()> {
+        let Some(path) = self.links_path.read().await.clone() else {
+            return Ok(());
+        };
+        let identities = self.moderation_system.identities().read().await.clone();
+        identities.save(&path).await
+    }
+
+    /// Handle !linkaccounts <platform:username> <platform:username>
+    async fn handle_link_accounts_command(
+        &self,
+        args: &[&str],
+        message: &ChatMessage,
+        response_sender: &tokio::sync::mpsc::Sender<(String, String, String)>,
+    ) -> Result<()> {
+        let accounts: Vec<(&str, &str)> = args.iter().filter_map(|arg| arg.split_once(':')).collect();
+        let response = match accounts[..] {
+            [primary, other] if args.len() == 2 => match self.link_identities(primary, other).await {
+                Ok(identity) => {
+                    info!("{}:{} linked to {} by {}", other.0, other.1, identity, message.username);
+                    format!("🔗 Linked {}:{} to {}; they now share points and moderation history", other.0, other.1, identity)
+                }
+                Err(e) => format!("❌ Couldn't link accounts: {}", e),
+            },
+            _ => "Usage: !linkaccounts <platform:username> <platform:username>".to_string(),
+        };
+
+        self.send_response(response, message, response_sender).await
+    }
+
+    /// Handle !unlinkaccount <platform:username>
+    async fn handle_unlink_account_command(
+        &self,
+        args: &[&str],
+        message: &ChatMessage,
+        response_sender: &tokio::sync::mpsc::Sender<(String, String, String)>,
+    ) -> Result<()> {
+        let response = match args.first().and_then(|arg| arg.split_once(':')) {
+            Some((platform, username)) => match self.unlink_identity(platform, username).await {
+                Ok(true) => {
+                    info!("{}:{} unlinked by {}", platform, username, message.username);
+                    format!("🔗 Unlinked {}:{}", platform, username)
+                }
+                Ok(false) => format!("{}:{} isn't linked to another account", platform, username),
+                Err(e) => format!("❌ Couldn't unlink account: {}", e),
+            },
+            None => "Usage: !unlinkaccount <platform:username>".to_string(),
+        };
+
+        self.send_response(response, message, response_sender).await
+    }
+
+    async fn send_response(
+        &self,
+        response: String,
+        message: &ChatMessage,
+        response_sender: &tokio::sync::mpsc::Sender<(String, String, String)>,
+    ) -> Result<()> {
+        if let Err(e) = response_sender.send((
+            message.platform.clone(),
+            message.channel.clone(),
+            response
+        )).await {
+            warn!("Failed to send identity command response: {}", e);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,374 @@
+use anyhow::{Context, Result};
+use log::{error, info, warn};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tokio::fs::{self, File, OpenOptions};
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+use tokio::sync::{mpsc, oneshot};
+
+use crate::bot::redaction::LogRedaction;
+use crate::types::ChatMessage;
+
+/// Messages waiting for the log writer before senders have to wait
+const WRITER_QUEUE_SIZE: usize = 1000;
+
+/// Append-only log of inbound chat messages, replayed after a crash to rebuild
+/// analytics (replayed messages are never moderated again, nor earn points).
+/// A clean shutdown writes a checkpoint, and only messages after the last
+/// checkpoint are replayed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MessageLogConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_log_path")]
+    pub path: PathBuf,
+    /// The log is rotated once it grows past this size
+    #[serde(default = "default_max_file_bytes")]
+    pub max_file_bytes: u64,
+    /// Files kept including the current one; older messages are dropped
+    #[serde(default = "default_max_files")]
+    pub max_files: usize,
+}
+
+fn default_log_path() -> PathBuf {
+    PathBuf::from("message_log.jsonl")
+}
+
+fn default_max_file_bytes() -> u64 {
+    10 * 1024 * 1024
+}
+
+fn default_max_files() -> usize {
+    3
+}
+
+impl Default for MessageLogConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: default_log_path(),
+            max_file_bytes: default_max_file_bytes(),
+            max_files: default_max_files(),
+        }
+    }
+}
+
+impl MessageLogConfig {
+    /// `message_log.jsonl.1` is the most recently rotated file
+    fn rotated_path(&self, index: usize) -> PathBuf {
+        let mut name = self.path.as_os_str().to_owned();
+        name.push(format!(".{}", index));
+        PathBuf::from(name)
+    }
+
+    /// Every log file, oldest first
+    fn files_oldest_first(&self) -> Vec<PathBuf> {
+        let mut files: Vec<PathBuf> = (1..self.max_files.max(1)).rev().map(|i| self.rotated_path(i)).collect();
+        files.push(self.path.clone());
+        files
+    }
+}
+
+/// One line of the log
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+enum LogLine {
+    /// Everything before this line was handled by a process that shut down cleanly
+    Checkpoint { clean_shutdown: chrono::DateTime<chrono::Utc> },
+    Message(ChatMessage),
+}
+
+/// Messages read back from the log
+#[derive(Debug, Default)]
+pub struct ReplayedMessages {
+    pub messages: Vec<ChatMessage>,
+    /// Unreadable lines, e.g. one cut short by the crash
+    pub skipped_lines: usize,
+}
+
+enum LogRequest {
+    Append(ChatMessage),
+    Checkpoint(oneshot::Sender<Result<()>>),
+}
+
+/// Cheap handle to a message log written by its own task, so callers never
+/// wait on disk I/O while holding a lock
+#[derive(Clone)]
+pub struct MessageLogHandle {
+    config: MessageLogConfig,
+    requests: mpsc::Sender<LogRequest>,
+}
+
+impl MessageLogHandle {
+    pub fn config(&self) -> &MessageLogConfig {
+        &self.config
+    }
+
+    /// Queue a message for the log
+    pub async fn append(&self, message: &ChatMessage) -> Result<()> {
+        self.requests.send(LogRequest::Append(message.clone())).await
+            .map_err(|_| anyhow::anyhow!("Message log writer has stopped"))
+    }
+
+    /// Write a clean-shutdown checkpoint once everything queued before it is written
+    pub async fn checkpoint(&self) -> Result<()> {
+        let (done_tx, done_rx) = oneshot::channel();
+        self.requests.send(LogRequest::Checkpoint(done_tx)).await
+            .map_err(|_| anyhow::anyhow!("Message log writer has stopped"))?;
+        done_rx.await.map_err(|_| anyhow::anyhow!("Message log writer has stopped"))?
+    }
+}
+
+/// Writer for the inbound message log
+pub struct MessageLog {
+    config: MessageLogConfig,
+    file: File,
+    size: u64,
+    log_redaction: LogRedaction,
+}
+
+impl MessageLog {
+    pub async fn open(config: MessageLogConfig) -> Result<Self> {
+        if let Some(parent) = config.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent).await
+                .with_context(|| format!("Failed to create message log directory {}", parent.display()))?;
+        }
+
+        let mut file = Self::open_file(&config.path).await?;
+        let mut size = file.metadata().await?.len();
+        // Finish off a line torn by a crash so the next message starts cleanly
+        if size > 0 && !Self::ends_with_newline(&config.path).await? {
+            file.write_all(b"\n").await?;
+            size += 1;
+        }
+        info!("Logging inbound messages to {}", config.path.display());
+        Ok(Self { config, file, size, log_redaction: LogRedaction::default() })
+    }
+
+    /// Redact logged messages with the bot's log redaction setting
+    pub fn with_log_redaction(mut self, log_redaction: LogRedaction) -> Self {
+        self.log_redaction = log_redaction;
+        self
+    }
+
+    pub fn config(&self) -> &MessageLogConfig {
+        &self.config
+    }
+
+    /// Move the log onto its own writer task
+    pub fn spawn(mut self) -> MessageLogHandle {
+        let (requests, mut receiver) = mpsc::channel(WRITER_QUEUE_SIZE);
+        let handle = MessageLogHandle { config: self.config.clone(), requests };
+        tokio::spawn(async move {
+            while let Some(request) = receiver.recv().await {
+                match request {
+                    LogRequest::Append(message) => {
+                        if let Err(e) = self.append(&message).await {
+                            error!("Failed to append to message log: {:#}", e);
+                        }
+                    }
+                    LogRequest::Checkpoint(done) => {
+                        let _ = done.send(self.checkpoint().await);
+                    }
+                }
+            }
+        });
+        handle
+    }
+
+    /// Append a message, rotating first if it would overflow the current file.
+    /// The text is redacted like log output, since the file outlives the process.
+    pub async fn append(&mut self, message: &ChatMessage) -> Result<()> {
+        let message = ChatMessage {
+            content: self.log_redaction.redact(&message.content),
+            ..message.clone()
+        };
+        self.write_line(&LogLine::Message(message)).await
+    }
+
+    /// Mark everything logged so far as handled, so it isn't replayed
+    pub async fn checkpoint(&mut self) -> Result<()> {
+        self.write_line(&LogLine::Checkpoint { clean_shutdown: chrono::Utc::now() }).await
+    }
+
+    /// Each line is handed to the OS straight away, so it survives a process crash
+    async fn write_line(&mut self, line: &LogLine) -> Result<()> {
+        let mut line = serde_json::to_string(line)?;
+        line.push('\n');
+
+        if self.size > 0 && self.size + line.len() as u64 > self.config.max_file_bytes {
+            self.rotate().await?;
+        }
+
+        self.file.write_all(line.as_bytes()).await
+            .with_context(|| format!("Failed to write message log {}", self.config.path.display()))?;
+        self.file.flush().await?;
+        self.size += line.len() as u64;
+        Ok(())
+    }
+
+    async fn rotate(&mut self) -> Result<()> {
+        let files = self.config.files_oldest_first();
+        // Shift each file one slot older; whatever falls off the end is overwritten
+        for pair in files.windows(2) {
+            if fs::try_exists(&pair[1]).await.unwrap_or(false) {
+                fs::rename(&pair[1], &pair[0]).await
+                    .with_context(|| format!("Failed to rotate message log {}", pair[1].display()))?;
+            }
+        }
+        if files.len() == 1 {
+            fs::remove_file(&self.config.path).await.ok();
+        }
+
+        self.file = Self::open_file(&self.config.path).await?;
+        self.size = 0;
+        Ok(())
+    }
+
+    async fn ends_with_newline(path: &Path) -> Result<bool> {
+        let mut file = File::open(path).await?;
+        file.seek(std::io::SeekFrom::End(-1)).await?;
+        let mut last = [0u8; 1];
+        file.read_exact(&mut last).await?;
+        Ok(last[0] == b'\n')
+    }
+
+    async fn open_file(path: &Path) -> Result<File> {
+        OpenOptions::new().create(true).append(true).open(path).await
+            .with_context(|| format!("Failed to open message log {}", path.display()))
+    }
+
+    /// Read every message logged since the last checkpoint, oldest first
+    pub async fn replay(config: &MessageLogConfig) -> Result<ReplayedMessages> {
+        let mut replayed = ReplayedMessages::default();
+
+        for path in config.files_oldest_first() {
+            let content = match fs::read_to_string(&path).await {
+                Ok(content) => content,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(e).with_context(|| format!("Failed to read message log {}", path.display())),
+            };
+
+            for line in content.lines().filter(|l| !l.trim().is_empty()) {
+                match serde_json::from_str(line) {
+                    Ok(LogLine::Message(message)) => replayed.messages.push(message),
+                    Ok(LogLine::Checkpoint { .. }) => replayed = ReplayedMessages::default(),
+                    Err(_) => replayed.skipped_lines += 1,
+                }
+            }
+        }
+
+        if replayed.skipped_lines > 0 {
+            warn!("Skipped {} unreadable lines in message log {}", replayed.skipped_lines, config.path.display());
+        }
+        Ok(replayed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(dir: &Path, max_file_bytes: u64) -> MessageLogConfig {
+        MessageLogConfig {
+            enabled: true,
+            path: dir.join("logs").join("messages.jsonl"),
+            max_file_bytes,
+            max_files: 3,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_write_and_replay() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = config(dir.path(), 1024 * 1024);
+
+        let mut log = MessageLog::open(config.clone()).await.unwrap();
+        for i in 0..5 {
+            log.append(&ChatMessage::new("twitch", "channel", format!("viewer{}", i), "hello")).await.unwrap();
+        }
+        drop(log);
+
+        // A crash mid-write leaves a partial last line
+        let mut file = OpenOptions::new().append(true).open(&config.path).await.unwrap();
+        file.write_all(br#"{"platform":"twitch","chan"#).await.unwrap();
+        file.flush().await.unwrap();
+        drop(file);
+
+        // Reopening appends rather than truncating
+        let mut log = MessageLog::open(config.clone()).await.unwrap();
+        log.append(&ChatMessage::new("youtube", "channel", "late", "hi")).await.unwrap();
+
+        let replayed = MessageLog::replay(&config).await.unwrap();
+        let users: Vec<_> = replayed.messages.iter().map(|m| m.username.as_str()).collect();
+        assert_eq!(users, vec!["viewer0", "viewer1", "viewer2", "viewer3", "viewer4", "late"]);
+        assert_eq!(replayed.skipped_lines, 1);
+    }
+
+    #[tokio::test]
+    async fn test_logged_messages_follow_the_redaction_setting() {
+        use crate::bot::redaction::RedactionMode;
+
+        let dir = tempfile::tempdir().unwrap();
+        let config = config(dir.path(), 1024 * 1024);
+        let redaction = LogRedaction::default();
+        let mut log = MessageLog::open(config.clone()).await.unwrap().with_log_redaction(redaction.clone());
+
+        log.append(&ChatMessage::new("twitch", "channel", "viewer", "my phone number is 555")).await.unwrap();
+        redaction.set(RedactionMode::Truncate { max_chars: 5 });
+        log.append(&ChatMessage::new("twitch", "channel", "viewer", "my phone number is 555")).await.unwrap();
+
+        let contents: Vec<_> = MessageLog::replay(&config).await.unwrap().messages.into_iter().map(|m| m.content).collect();
+        assert_eq!(contents, vec!["my phone number is 555", "my ph…"]);
+    }
+
+    #[tokio::test]
+    async fn test_log_is_rotated_and_bounded() {
+        let dir = tempfile::tempdir().unwrap();
+        let line_len = serde_json::to_string(&ChatMessage::new("twitch", "channel", "viewer00", "hello")).unwrap().len() as u64 + 1;
+        // Two messages per file, at most three files
+        let config = config(dir.path(), line_len * 2);
+
+        let mut log = MessageLog::open(config.clone()).await.unwrap();
+        for i in 0..10 {
+            log.append(&ChatMessage::new("twitch", "channel", format!("viewer{:02}", i), "hello")).await.unwrap();
+        }
+
+        assert!(config.rotated_path(2).exists());
+        assert!(!config.rotated_path(3).exists());
+
+        let replayed = MessageLog::replay(&config).await.unwrap();
+        let users: Vec<_> = replayed.messages.iter().map(|m| m.username.as_str()).collect();
+        assert_eq!(users, vec!["viewer04", "viewer05", "viewer06", "viewer07", "viewer08", "viewer09"]);
+        assert_eq!(replayed.skipped_lines, 0);
+    }
+
+    #[tokio::test]
+    async fn test_only_messages_after_the_last_checkpoint_are_replayed() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = config(dir.path(), 1024 * 1024);
+
+        let log = MessageLog::open(config.clone()).await.unwrap().spawn();
+        log.append(&ChatMessage::new("twitch", "channel", "before", "hello")).await.unwrap();
+        log.checkpoint().await.unwrap();
+        assert!(MessageLog::replay(&config).await.unwrap().messages.is_empty());
+
+        // Messages after the checkpoint are what a crash would lose
+        log.append(&ChatMessage::new("twitch", "channel", "after", "hello")).await.unwrap();
+        log.checkpoint().await.unwrap();
+        log.append(&ChatMessage::new("twitch", "channel", "crashed", "hello")).await.unwrap();
+        drop(log);
+
+        let replayed = tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            loop {
+                let replayed = MessageLog::replay(&config).await.unwrap();
+                if !replayed.messages.is_empty() {
+                    return replayed;
+                }
+                tokio::task::yield_now().await;
+            }
+        }).await.unwrap();
+        let users: Vec<_> = replayed.messages.iter().map(|m| m.username.as_str()).collect();
+        assert_eq!(users, vec!["crashed"]);
+    }
+}
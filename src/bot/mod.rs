@@ -1,32 +1,58 @@
 use anyhow::Result;
-use log::{error, info, warn};
+use log::{debug, error, info, warn};
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use tokio::sync::{broadcast, RwLock};
 
-use crate::platforms::PlatformConnection;
-use crate::types::{ChatMessage, SpamFilterType, ExemptionLevel, ModerationEscalation, ModerationAction};
+use crate::adaptive::AdaptivePerformanceSystem;
+use crate::config::{ConfigurationManager, PlatformFeatures, RateLimitConfig, SecuritySettings, WebhookConfig};
+use crate::platforms::twitch_eventsub::TwitchEvent;
+use crate::platforms::{ConnectionError, DeliveryStatus, PlatformConnection, PlatformHealth};
+use crate::types::{ChatMessage, SpamFilterType, ExemptionLevel, ModerationEscalation, ModerationAction, ModerationResult};
 
 pub mod achievements;
+pub mod audit_log;
 pub mod achievement_commands;
 pub mod analytics;
+pub mod announcements;
+pub mod auth_alerts;
 pub mod commands;
 pub mod config_integration;
 pub mod connection_pool;
+pub mod data_dir;
+pub mod diagnostics;
+pub mod duplicate_guard;
 pub mod enhanced_moderation;
 pub mod filter_commands;
 pub mod filter_import_export;
 pub mod giveaways;
+pub mod giveaway_fraud;
 pub mod giveaway_commands;
+pub mod identity;
+pub mod identity_commands;
+pub mod message_log;
 pub mod moderation;
+pub mod moderation_metrics;
 pub mod pattern_matching;
+pub mod pending_actions;
+pub mod pipeline_metrics;
 pub mod points;
 pub mod points_commands;
+pub mod raid_mode;
 pub mod realtime_analytics;
+pub mod redaction;
+pub mod restricted_hours;
+pub mod schedule;
 pub mod shutdown;
 pub mod smart_escalation;
+pub mod spam_wave;
+pub mod system_commands;
+pub mod task_limiter;
 pub mod timers;
 pub mod timer_commands;
+pub mod user_filter;
 
 
 use commands::CommandSystem;
@@ -39,9 +65,27 @@ use points_commands::PointsCommands;
 use achievements::AchievementSystem;
 use achievement_commands::AchievementCommands;
 use filter_commands::FilterCommands;
+use system_commands::SystemCommands;
+use identity_commands::IdentityCommands;
 use enhanced_moderation::EnhancedModerationSystem;
 use crate::types::{GiveawayType, GiveawaySettings, GiveawayResult};
 use giveaways::{GiveawaySystem};
+use giveaway_commands::GiveawayCommands;
+use duplicate_guard::{DuplicateEvasion, DuplicateGuard};
+use user_filter::UserFilter;
+use identity::IdentityMap;
+use raid_mode::{RaidMode, RaidModeConfig};
+use restricted_hours::{RestrictedHours, RestrictedHoursChange, RestrictedHoursConfig};
+use message_log::{MessageLog, MessageLogConfig, MessageLogHandle};
+use audit_log::{AuditEntry, AuditQuery, AuditSource, ModerationAuditLog};
+use pipeline_metrics::{PipelineMetrics, PipelineMetricsWindow};
+use diagnostics::{CheckStatus, DiagnosticsOptions, DiagnosticsReport};
+use pending_actions::{PendingActionConfig, PendingActionQueue};
+use announcements::{AnnouncementReport, AnnouncementTarget};
+use connection_pool::ConnectionPool;
+use data_dir::DataDir;
+use moderation_metrics::ModerationMetricsSnapshot;
+use task_limiter::TaskLimiter;
 
 /// Core bot engine that manages connections and all bot systems
 pub struct ChatBot {
@@ -56,55 +100,1137 @@ pub struct ChatBot {
     achievement_system: Arc<AchievementSystem>,
     achievement_commands: Arc<AchievementCommands>,
     filter_commands: Arc<FilterCommands>,
+    system_commands: Arc<SystemCommands>,
     giveaway_system: Arc<GiveawaySystem>,
+    giveaway_commands: Arc<GiveawayCommands>,
+    duplicate_guard: Arc<RwLock<DuplicateGuard>>,
+    user_filter: Arc<RwLock<UserFilter>>,
+    dry_run: Arc<AtomicBool>,
+    /// Longest time one inbound message may take to process before it's skipped
+    message_timeout_ms: Arc<AtomicU64>,
+    /// Bounds how many moderation actions and responses are sent at once
+    task_limiter: Arc<TaskLimiter>,
+    raid_mode: Arc<RwLock<RaidMode>>,
+    /// When attached, inbound messages are moderated with AI patterns as well as rules
+    enhanced_moderation: Arc<RwLock<Option<Arc<EnhancedModerationSystem>>>>,
+    adaptive_system: Arc<RwLock<Option<Arc<AdaptivePerformanceSystem>>>>,
+    connection_pool: Arc<RwLock<Option<Arc<ConnectionPool>>>>,
+    config_manager: Arc<RwLock<Option<Arc<ConfigurationManager>>>>,
+    platform_features: Arc<RwLock<HashMap<String, PlatformFeatures>>>,
+    /// Per-platform webhooks, used for auth failure alerts
+    platform_webhooks: Arc<RwLock<HashMap<String, Vec<WebhookConfig>>>>,
+    /// Per-platform send rates, used to pace announcements
+    platform_rate_limits: Arc<RwLock<HashMap<String, RateLimitConfig>>>,
+    /// Where relative export paths are written
+    data_dir: Arc<RwLock<DataDir>>,
+    restricted_hours: Arc<RwLock<RestrictedHours>>,
+    /// Optional log of inbound messages for rebuilding state after a crash
+    message_log: Arc<RwLock<Option<MessageLogHandle>>>,
+    /// Record of every moderation action, when `security.audit_logging` is on
+    audit_log: Arc<RwLock<Option<ModerationAuditLog>>>,
+    /// Measured message processing latency and errors, fed to adaptive tuning
+    pipeline_metrics: Arc<PipelineMetrics>,
+    /// What `run_diagnostics` checks besides the connections
+    diagnostics_options: Arc<RwLock<DiagnosticsOptions>>,
+    /// Port of the running web dashboard
+    dashboard_port: Arc<RwLock<Option<u16>>>,
+    /// Dashboard IP allowlist and trusted proxies
+    security_settings: Arc<RwLock<Option<SecuritySettings>>>,
+    /// Moderation actions waiting for their platform to reconnect
+    pending_actions: Arc<RwLock<PendingActionQueue>>,
+    /// Account links, saved whenever they change
+    identity_commands: Arc<IdentityCommands>,
+}
+
+/// Systems the dashboard data updater reads from
+#[cfg(feature = "web")]
+struct DashboardSources {
+    analytics_system: Arc<RwLock<AnalyticsSystem>>,
+    moderation_system: Arc<ModerationSystem>,
+    task_limiter: Arc<TaskLimiter>,
+    connections: Arc<RwLock<HashMap<String, Box<dyn PlatformConnection>>>>,
+    adaptive_system: Option<Arc<AdaptivePerformanceSystem>>,
+    connection_pool: Option<Arc<ConnectionPool>>,
+    config_manager: Option<Arc<ConfigurationManager>>,
+}
+
+#[cfg(feature = "web")]
+impl DashboardSources {
+    /// Push one fresh snapshot of everything into the dashboard state
+    async fn refresh(&self, state: &crate::web::DashboardState) {
+        // Update analytics data
+        let analytics = self.analytics_system.read().await.get_analytics().await;
+        state.update_analytics(analytics).await;
+
+        // Update health data
+        let mut health = HashMap::new();
+        {
+            let connections_guard = self.connections.read().await;
+            for (platform_name, connection) in connections_guard.iter() {
+                health.insert(platform_name.clone(), connection.health().await);
+            }
+        }
+        state.update_health(health).await;
+
+        // Update active filters (reflects hot-reloads and runtime changes)
+        state.update_active_filters(self.moderation_system.get_active_filters().await).await;
+        let mut metrics = self.moderation_system.get_moderation_metrics().await;
+        metrics.tasks_in_flight = self.task_limiter.in_flight() as u64;
+        state.update_moderation_metrics(metrics).await;
+
+        if let Some(pool) = &self.connection_pool {
+            state.update_pool_stats(pool.get_stats_summary().await).await;
+        }
+        if let Some(config_manager) = &self.config_manager {
+            state.update_config_status(config_manager.get_config_status().await).await;
+        }
+
+        // Update adaptive tuning status
+        if let Some(adaptive) = &self.adaptive_system {
+            match adaptive.get_recent_changes(50).await {
+                Ok(changes) => state.update_adaptive_changes(changes).await,
+                Err(e) => warn!("Failed to read adaptive changes for dashboard: {}", e),
+            }
+            match adaptive.get_current_parameters().await {
+                Ok(parameters) => state.update_adaptive_parameters(parameters).await,
+                Err(e) => warn!("Failed to read adaptive parameters for dashboard: {}", e),
+            }
+            match adaptive.get_health_status().await {
+                Ok(health) => state.update_adaptive_health(health).await,
+                Err(e) => warn!("Failed to read adaptive health for dashboard: {}", e),
+            }
+            match adaptive.get_metric_series(120).await {
+                Ok(series) => state.update_adaptive_metrics(series).await,
+                Err(e) => warn!("Failed to read adaptive metrics for dashboard: {}", e),
+            }
+        }
+    }
+}
+
+/// Everything the inbound message pipeline needs, shared by one task per platform receiver
+#[derive(Clone)]
+struct MessageProcessor {
+    command_system: Arc<CommandSystem>,
+    moderation_system: Arc<ModerationSystem>,
+    enhanced_moderation: Arc<RwLock<Option<Arc<EnhancedModerationSystem>>>>,
+    analytics_sender: Arc<tokio::sync::mpsc::Sender<AnalyticsEvent>>,
+    points_system: Arc<PointsSystem>,
+    points_commands: Arc<PointsCommands>,
+    achievement_system: Arc<AchievementSystem>,
+    achievement_commands: Arc<AchievementCommands>,
+    filter_commands: Arc<FilterCommands>,
+    system_commands: Arc<SystemCommands>,
+    identity_commands: Arc<IdentityCommands>,
+    timer_commands: Arc<TimerCommands>,
+    giveaway_system: Arc<GiveawaySystem>,
+    giveaway_commands: Arc<GiveawayCommands>,
+    user_filter: Arc<RwLock<UserFilter>>,
+    dry_run: Arc<AtomicBool>,
+    message_timeout_ms: Arc<AtomicU64>,
+    task_limiter: Arc<TaskLimiter>,
+    raid_mode: Arc<RwLock<RaidMode>>,
+    platform_features: Arc<RwLock<HashMap<String, PlatformFeatures>>>,
+    message_log: Arc<RwLock<Option<MessageLogHandle>>>,
+    audit_log: Arc<RwLock<Option<ModerationAuditLog>>>,
+    pipeline_metrics: Arc<PipelineMetrics>,
+    connections: Arc<RwLock<HashMap<String, Box<dyn PlatformConnection>>>>,
+    diagnostics_options: Arc<RwLock<DiagnosticsOptions>>,
+    dashboard_port: Arc<RwLock<Option<u16>>>,
+    duplicate_guard: Arc<RwLock<DuplicateGuard>>,
+    platform_rate_limits: Arc<RwLock<HashMap<String, RateLimitConfig>>>,
+    response_tx: tokio::sync::mpsc::Sender<(String, String, String)>,
+    moderation_tx: tokio::sync::mpsc::Sender<(String, String, String)>,
+    analytics_command_tx: tokio::sync::mpsc::Sender<(String, String, String)>,
+}
+
+impl MessageProcessor {
+    /// Run one inbound message through logging, moderation, points and commands.
+    ///
+    /// Moderation gets the message's whole time limit, checked between filters, and
+    /// has to finish before anything is awarded: a message it couldn't check is
+    /// skipped. Writes that must not be cut short (message log, audit log) happen
+    /// outside the part that can time out.
+    async fn process(&self, message: ChatMessage) {
+        // Filtered users never reach moderation, commands or analytics
+        if !self.user_filter.read().await.is_allowed(&message.platform, &message.username) {
+            debug!("Ignoring message from filtered user {}", message.username);
+            return;
+        }
+        // Neither do shadow-muted ones, until their mute runs out
+        if self.moderation_system.is_shadow_muted(&message).await {
+            debug!("Dropping message from shadow-muted user {}", message.username);
+            return;
+        }
+        let _timer = self.pipeline_metrics.time_message();
+        let limit = std::time::Duration::from_millis(self.message_timeout_ms.load(Ordering::Relaxed));
+        let deadline = tokio::time::Instant::now() + limit;
+
+        let message_log = self.message_log.read().await.clone();
+        if let Some(log) = message_log {
+            if let Err(e) = log.append(&message).await {
+                error!("Failed to append to message log: {:#}", e);
+            }
+        }
+
+        info!("Processing message from {}: {}", message.username, self.moderation_system.log_redaction().redact(&message.content));
+
+        // Record message in analytics
+        if let Err(e) = self.analytics_sender.send(AnalyticsEvent::MessageReceived(message.clone())).await {
+            error!("Failed to send analytics message event: {}", e);
+        }
+
+        let features = self.platform_features.read().await
+            .get(&message.platform)
+            .cloned()
+            .unwrap_or_default();
+
+        let verdict = if features.moderation {
+            // A burst of first-time chatters means a raid is arriving
+            if self.moderation_system.update_user_history(&message).await {
+                let mut raid_mode = self.raid_mode.write().await;
+                if raid_mode.record_first_message(&message.platform, &message.channel, chrono::Utc::now()) {
+                    info!("Raid detected in {}#{}, entering raid mode", message.platform, message.channel);
+                }
+            }
+            let user_points = self.points_system.get_user_points(&message.platform, &message.username).await;
+            let enhanced = self.enhanced_moderation.read().await.clone();
+            let verdict = match enhanced {
+                Some(enhanced) => enhanced.check_message_enhanced_within(&message, user_points.as_ref(), Some(deadline)).await
+                    .map(|result| result.map(|result| result.into_verdict())),
+                None => self.moderation_system.evaluate_spam_filters_within(&message, user_points.as_ref(), Some(deadline)).await,
+            };
+            match verdict {
+                Ok(verdict) => verdict,
+                Err(e) => {
+                    debug!("Moderation of message from {} stopped: {}", message.username, e);
+                    self.pipeline_metrics.record_timeout(&message, limit);
+                    return;
+                }
+            }
+        } else {
+            None
+        };
+        let flagged = verdict.is_some();
+        if let Some(verdict) = verdict {
+            self.apply_verdict(&message, verdict).await;
+        }
+
+        let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
+        self.pipeline_metrics.process_within(&message, remaining, self.process_moderated(&message, &features, flagged)).await;
+    }
+
+    /// Log, audit and carry out a spam filter's verdict on `message`
+    async fn apply_verdict(&self, message: &ChatMessage, verdict: moderation::SpamFilterVerdict) {
+        let log_level = verdict.log_level.map(|level| level.level());
+        let log_level = if self.moderation_system.is_spam_wave_active().await {
+            log_level.unwrap_or(log::Level::Debug)
+        } else {
+            log_level.unwrap_or(log::Level::Warn)
+        };
+        let log_redaction = self.moderation_system.log_redaction();
+        log::log!(log_level, "Message flagged by spam filter: {} from {}", log_redaction.redact(&message.content), message.username);
+
+        // Record spam in analytics
+        if let Err(e) = self.analytics_sender.send(AnalyticsEvent::SpamBlocked(message.clone())).await {
+            error!("Failed to send analytics spam event: {}", e);
+        }
+
+        let dry_run = self.dry_run.load(Ordering::Relaxed);
+        if let Some(log) = self.audit_log.write().await.as_mut() {
+            let entry = AuditEntry {
+                confidence: verdict.confidence,
+                dry_run,
+                ..AuditEntry::automatic(message, &verdict.action, &verdict.filter_name, &log_redaction)
+            };
+            if let Err(e) = log.record(&entry).await {
+                error!("Failed to write moderation audit entry: {:#}", e);
+            }
+        }
+
+        // Handle moderation action
+        if dry_run {
+            moderation::ModerationSystem::log_dry_run_action(&verdict.action, message, &log_redaction);
+            return;
+        }
+        if let ModerationAction::ShadowMute { duration_seconds } = verdict.action {
+            self.moderation_system.shadow_mute(&message.platform, &message.username, duration_seconds).await;
+        }
+        if let Err(e) = moderation::ModerationSystem::handle_moderation_action_with_notice(
+            verdict.action, message, verdict.notice.as_ref(), &self.moderation_tx, &log_redaction
+        ).await {
+            error!("Failed to handle moderation action: {}", e);
+            self.pipeline_metrics.record_error();
+        }
+    }
+
+    /// Ask the platform when a giveaway entrant's account was created, in the
+    /// background so the message isn't held up; the score is refreshed before the draw
+    fn look_up_account_created(&self, message: &ChatMessage) {
+        let connections = Arc::clone(&self.connections);
+        let giveaway_system = Arc::clone(&self.giveaway_system);
+        let platform = message.platform.clone();
+        let username = message.username.clone();
+        tokio::spawn(async move {
+            let created = match connections.read().await.get(&platform) {
+                Some(connection) => connection.account_created(&username).await,
+                None => return,
+            };
+            match created {
+                Ok(Some(created)) => giveaway_system.set_account_created(&platform, &username, created).await,
+                Ok(None) => {}
+                Err(e) => warn!("Failed to look up account age of {} on {}: {}", username, platform, e),
+            }
+        });
+    }
+
+    /// Points, giveaways and achievements for a moderated message, then its commands
+    /// unless a filter flagged it
+    async fn process_moderated(&self, message: &ChatMessage, features: &PlatformFeatures, flagged: bool) {
+        // Process message for points (always, even if spam)
+        if features.points {
+            if let Err(e) = self.points_system.process_message(message).await {
+                error!("Failed to process points for message: {}", e);
+                self.pipeline_metrics.record_error();
+            }
+        }
+
+        if features.giveaways {
+            if let Err(e) = self.giveaway_system.process_message(message).await {
+                error!("Failed to process giveaway message: {}", e);
+                self.pipeline_metrics.record_error();
+            }
+            if self.giveaway_system.wants_account_created(&message.platform, &message.username).await {
+                self.look_up_account_created(message);
+            }
+        }
+
+        // Check for achievement unlocks after processing points
+        let user_points = if features.points {
+            self.points_system.get_user_points(&message.platform, &message.username).await
+        } else {
+            None
+        };
+        if let Some(user_points) = user_points {
+            let unlocked_achievements = self.achievement_system.check_achievements(&user_points).await;
+
+            for achievement in unlocked_achievements {
+                // Award achievement bonus points
+                if let Err(e) = self.points_system.add_points(&message.platform, &message.username,
+                                                              achievement.reward_points, &format!("Achievement: {}", achievement.name)).await {
+                    error!("Failed to award achievement points: {}", e);
+                }
+
+                // Announce the achievement, or fold it into the raid welcome
+                if self.raid_mode.write().await.hold_welcome(&message.platform, &message.channel, &message.username, chrono::Utc::now()) {
+                    debug!("Holding back achievement announcement for {} during raid mode", message.username);
+                } else if let Err(e) = self.achievement_commands.announce_achievement(&achievement, &message.username, message, &self.response_tx).await {
+                    error!("Failed to announce achievement: {}", e);
+                }
+            }
+        }
+
+        // Don't process commands for flagged messages
+        if flagged || !features.commands {
+            return;
+        }
+        self.process_commands(message, features).await;
+    }
+
+    /// Built-in commands first, then each command module in turn, then custom commands
+    async fn process_commands(&self, message: &ChatMessage, features: &PlatformFeatures) {
+        let response_tx = &self.response_tx;
+
+        // Check for commands
+        let prefix = self.command_system.get_command_prefix(&message.platform).await;
+        if message.content.starts_with(&prefix) {
+            let content_without_prefix = &message.content[prefix.len()..];
+            let parts: Vec<&str> = content_without_prefix.split_whitespace().collect();
+
+            if !parts.is_empty() {
+                let command_name = parts[0].to_lowercase();
+                let args: Vec<&str> = parts[1..].to_vec();
+
+                // Per-user rate limit applies to every command below
+                if !self.command_system.allow_command(message).await {
+                    return;
+                }
+
+                // Self-test for moderators; never connects anything while running
+                if command_name == "doctor" && message.is_mod {
+                    let options = DiagnosticsOptions {
+                        connect_platforms: false,
+                        ..self.diagnostics_options.read().await.clone()
+                    };
+                    let running_port = *self.dashboard_port.read().await;
+                    let report = ChatBot::diagnose(&self.connections, &options, running_port).await;
+                    info!("Diagnostics requested by {}:\n{}", message.username, report);
+                    if let Err(e) = response_tx.send((message.platform.clone(), message.channel.clone(), report.chat_summary())).await {
+                        error!("Failed to send diagnostics report: {}", e);
+                    }
+                    return;
+                }
+
+                // Announce to every connected channel; runs in the background
+                // since pacing can take a while on rate-limited platforms
+                if command_name == "announce" {
+                    if !message.is_mod && !ExemptionLevel::Owner.is_exempt(message, None) {
+                        return;
+                    }
+                    let text = args.join(" ");
+                    let reply_tx = response_tx.clone();
+                    let (platform, channel) = (message.platform.clone(), message.channel.clone());
+                    if text.is_empty() {
+                        if let Err(e) = reply_tx.send((platform, channel, "Usage: !announce <message>".to_string())).await {
+                            error!("Failed to send announce usage: {}", e);
+                        }
+                        return;
+                    }
+
+                    info!("Announcement requested by {}: {}", message.username, text);
+                    let connections = Arc::clone(&self.connections);
+                    let duplicate_guard = Arc::clone(&self.duplicate_guard);
+                    let platform_rate_limits = Arc::clone(&self.platform_rate_limits);
+                    let dry_run = self.dry_run.load(Ordering::Relaxed);
+                    let pipeline_metrics = Arc::clone(&self.pipeline_metrics);
+                    self.task_limiter.spawn(async move {
+                        let report = ChatBot::broadcast_to(&connections, &duplicate_guard, &platform_rate_limits, dry_run, &text).await;
+                        pipeline_metrics.record_errors(report.failed.len() as u64);
+                        if let Err(e) = reply_tx.send((platform, channel, report.summary())).await {
+                            error!("Failed to send announce report: {}", e);
+                        }
+                    }).await;
+                    return;
+                }
+                // Try timer commands first (NEW)
+                match self.timer_commands.process_command(&command_name, &args, message, response_tx).await {
+                    Ok(true) => {
+                        // Timer command was handled
+                        return;
+                    }
+                    Ok(false) => {
+                        // Not a timer command, try filter commands
+                    }
+                    Err(e) => {
+                        error!("Error processing timer command: {}", e);
+                        self.pipeline_metrics.record_error();
+                    }
+                }
+
+                // Try filter commands
+                match self.filter_commands.process_command(&command_name, &args, message, response_tx).await {
+                    Ok(true) => {
+                        // Filter command was handled
+                        return;
+                    }
+                    Ok(false) => {
+                        // Not a filter command, try system commands
+                    }
+                    Err(e) => {
+                        error!("Error processing filter command: {}", e);
+                        self.pipeline_metrics.record_error();
+                    }
+                }
+
+                // Try system status commands
+                match self.system_commands.process_command(&command_name, &args, message, response_tx).await {
+                    Ok(true) => {
+                        // System command was handled
+                        return;
+                    }
+                    Ok(false) => {
+                        // Not a system command, try identity commands
+                    }
+                    Err(e) => {
+                        error!("Error processing system command: {}", e);
+                        self.pipeline_metrics.record_error();
+                    }
+                }
+
+                // Try account linking commands
+                match self.identity_commands.process_command(&command_name, &args, message, response_tx).await {
+                    Ok(true) => {
+                        // Identity command was handled
+                        return;
+                    }
+                    Ok(false) => {
+                        // Not an identity command, try achievement commands
+                    }
+                    Err(e) => {
+                        error!("Error processing identity command: {}", e);
+                        self.pipeline_metrics.record_error();
+                    }
+                }
+
+                // Try achievement commands
+                match self.achievement_commands.process_command(&command_name, &args, message, response_tx).await {
+                    Ok(true) => {
+                        // Achievement command was handled
+                        return;
+                    }
+                    Ok(false) => {
+                        // Not an achievement command, try giveaway commands
+                    }
+                    Err(e) => {
+                        error!("Error processing achievement command: {}", e);
+                        self.pipeline_metrics.record_error();
+                    }
+                }
+
+                // Try giveaway commands
+                let giveaway_command = if features.giveaways {
+                    self.giveaway_commands.process_command(&command_name, &args, message, response_tx).await
+                } else {
+                    Ok(false)
+                };
+                match giveaway_command {
+                    Ok(true) => {
+                        // Giveaway command was handled
+                        return;
+                    }
+                    Ok(false) => {
+                        // Not a giveaway command, try points commands
+                    }
+                    Err(e) => {
+                        error!("Error processing giveaway command: {}", e);
+                        self.pipeline_metrics.record_error();
+                    }
+                }
+
+                // Try points commands
+                let points_command = if features.points {
+                    self.points_commands.process_command(&command_name, &args, message, response_tx).await
+                } else {
+                    Ok(false)
+                };
+                match points_command {
+                    Ok(true) => {
+                        // Points command was handled
+                        if let Err(e) = self.points_system.process_command(message, &command_name).await {
+                            error!("Failed to process command points: {}", e);
+                        }
+                        return;
+                    }
+                    Ok(false) => {
+                        // Not a points command, continue to regular commands
+                    }
+                    Err(e) => {
+                        error!("Error processing points command: {}", e);
+                        self.pipeline_metrics.record_error();
+                    }
+                }
+            }
+        }
+
+        // Process regular commands
+        if let Err(e) = self.command_system.process_message(
+            message.clone(),
+            response_tx,
+            Some(&self.analytics_command_tx)
+        ).await {
+            error!("Failed to process command: {}", e);
+            self.pipeline_metrics.record_error();
+        } else {
+            // Award points for command usage
+            if features.points && message.content.starts_with(&prefix) {
+                let content_without_prefix = &message.content[prefix.len()..];
+                let parts: Vec<&str> = content_without_prefix.split_whitespace().collect();
+
+                if !parts.is_empty() {
+                    let command_name = parts[0].to_lowercase();
+                    if let Err(e) = self.points_system.process_command(message, &command_name).await {
+                        error!("Failed to process command points: {}", e);
+                    }
+                }
+            }
+        }
+    }
 }
 
 impl ChatBot {
     pub fn new() -> Self {
-        let points_system = Arc::new(PointsSystem::new());
+        // One set of identity links, so linked accounts are the same user everywhere
+        let identities = Arc::new(RwLock::new(IdentityMap::new()));
+        let points_system = Arc::new(PointsSystem::new().with_identities(Arc::clone(&identities)));
         let points_commands = Arc::new(PointsCommands::new(Arc::clone(&points_system)));
         let achievement_system = Arc::new(AchievementSystem::new());
-        let achievement_commands = Arc::new(AchievementCommands::new(Arc::clone(&achievement_system)));
-        let moderation_system = Arc::new(ModerationSystem::new());
+        let achievement_commands = Arc::new(AchievementCommands::new(Arc::clone(&achievement_system))
+            .with_identities(Arc::clone(&identities)));
+        let moderation_system = Arc::new(ModerationSystem::new().with_identities(Arc::clone(&identities)));
         let filter_commands = Arc::new(FilterCommands::new(Arc::clone(&moderation_system)));
         let timer_system = Arc::new(TimerSystem::new());
         let timer_commands = Arc::new(TimerCommands::new(Arc::clone(&timer_system)));
         let giveaway_system = Arc::new(GiveawaySystem::new());
+        let giveaway_commands = Arc::new(GiveawayCommands::new(Arc::clone(&giveaway_system)));
+        let identity_commands = Arc::new(IdentityCommands::new(
+            Arc::clone(&points_system),
+            Arc::clone(&achievement_system),
+            Arc::clone(&moderation_system),
+        ));
         
         Self {
             connections: Arc::new(RwLock::new(HashMap::new())),
-            command_system: Arc::new(CommandSystem::new()),
+            command_system: Arc::new(CommandSystem::new().with_log_redaction(moderation_system.log_redaction())),
             timer_system,
             timer_commands,
             moderation_system,
-            analytics_system: Arc::new(RwLock::new(AnalyticsSystem::new())),
+            analytics_system: Arc::new(RwLock::new(AnalyticsSystem::new().with_identities(identities))),
             giveaway_system,
+            giveaway_commands,
             points_system,
             points_commands,
             achievement_system,
             achievement_commands,
             filter_commands,
+            system_commands: Arc::new(SystemCommands::new()),
+            duplicate_guard: Arc::new(RwLock::new(DuplicateGuard::default())),
+            user_filter: Arc::new(RwLock::new(UserFilter::default())),
+            dry_run: Arc::new(AtomicBool::new(false)),
+            message_timeout_ms: Arc::new(AtomicU64::new(pipeline_metrics::DEFAULT_MESSAGE_TIMEOUT.as_millis() as u64)),
+            task_limiter: Arc::new(TaskLimiter::default()),
+            raid_mode: Arc::new(RwLock::new(RaidMode::default())),
+            enhanced_moderation: Arc::new(RwLock::new(None)),
+            adaptive_system: Arc::new(RwLock::new(None)),
+            connection_pool: Arc::new(RwLock::new(None)),
+            config_manager: Arc::new(RwLock::new(None)),
+            platform_features: Arc::new(RwLock::new(HashMap::new())),
+            platform_webhooks: Arc::new(RwLock::new(HashMap::new())),
+            platform_rate_limits: Arc::new(RwLock::new(HashMap::new())),
+            data_dir: Arc::new(RwLock::new(DataDir::default())),
+            restricted_hours: Arc::new(RwLock::new(RestrictedHours::default())),
+            message_log: Arc::new(RwLock::new(None)),
+            audit_log: Arc::new(RwLock::new(None)),
+            pipeline_metrics: Arc::new(PipelineMetrics::new()),
+            diagnostics_options: Arc::new(RwLock::new(DiagnosticsOptions::default())),
+            dashboard_port: Arc::new(RwLock::new(None)),
+            security_settings: Arc::new(RwLock::new(None)),
+            pending_actions: Arc::new(RwLock::new(PendingActionQueue::default())),
+            identity_commands,
+        }
+    }
+
+    pub fn get_moderation_system(&self) -> Arc<ModerationSystem> {
+        self.moderation_system.clone()
+    }
+
+    pub fn get_points_system(&self) -> Arc<PointsSystem> {
+        self.points_system.clone()
+    }
+
+    pub fn get_giveaway_system(&self) -> Arc<GiveawaySystem> {
+        self.giveaway_system.clone()
+    }
+
+    pub fn get_achievement_system(&self) -> Arc<AchievementSystem> {
+        self.achievement_system.clone()
+    }
+
+    pub fn get_timer_system(&self) -> Arc<TimerSystem> {
+        self.timer_system.clone()
+    }
+
+    /// Shared handle to the user filter, for hot-reloading from configuration
+    pub fn get_user_filter(&self) -> Arc<RwLock<UserFilter>> {
+        self.user_filter.clone()
+    }
+     
+    pub fn create_enhanced_moderation(&self) -> EnhancedModerationSystem {
+        EnhancedModerationSystem::new(self.moderation_system.clone())
+    }
+
+    /// Set the command prefix (default is "!")
+    pub async fn set_command_prefix(&self, prefix: String) {
+        self.command_system.set_command_prefix(prefix).await;
+    }
+
+    /// Set the command prefix for one platform, overriding the global prefix (`None` reverts to it)
+    pub async fn set_command_prefix_for_platform(&self, platform: &str, prefix: Option<String>) {
+        self.command_system.set_command_prefix_for_platform(platform, prefix).await;
+    }
+
+    /// Add a platform connection to the bot
+    pub async fn add_connection(&mut self, mut connection: Box<dyn PlatformConnection>) {
+        connection.set_log_redaction(self.moderation_system.log_redaction());
+        let platform_name = connection.platform_name().to_string();
+        info!("Added {} connection", platform_name);
+        self.connections.write().await.insert(platform_name, connection);
+    }
+
+    /// Set how identical consecutive responses to a channel are varied to avoid platform duplicate checks
+    pub async fn set_duplicate_evasion(&self, mode: DuplicateEvasion) {
+        info!("Duplicate response evasion set to {:?}", mode);
+        self.duplicate_guard.write().await.set_mode(mode);
+    }
+
+    /// Set which users the bot processes; messages from filtered users are ignored entirely
+    pub async fn set_user_filter(&self, filter: UserFilter) {
+        info!("User filter updated (allowlist mode: {})", filter.is_allowlist_mode());
+        *self.user_filter.write().await = filter;
+    }
+
+    /// Stay connected and make every decision, but only log moderation actions,
+    /// responses and timer posts instead of sending anything to the platforms
+    pub fn set_dry_run(&self, enabled: bool) {
+        if enabled {
+            warn!("Dry run enabled: moderation actions and responses will only be logged");
+        }
+        self.dry_run.store(enabled, Ordering::Relaxed);
+        self.timer_system.set_dry_run(enabled);
+    }
+
+    /// Skip any inbound message that takes longer than `timeout` to process,
+    /// so one pathological message can't stall its platform's stream
+    pub fn set_message_timeout(&self, timeout: std::time::Duration) {
+        self.message_timeout_ms.store((timeout.as_millis() as u64).max(1), Ordering::Relaxed);
+    }
+
+    /// Cap how many moderation actions and responses are sent at once; excess
+    /// work waits its turn instead of piling up as tasks
+    pub async fn set_max_concurrent_tasks(&self, max_concurrent: usize) {
+        self.task_limiter.set_max_concurrent(max_concurrent).await;
+    }
+
+    /// Moderation actions and responses being sent right now
+    pub fn tasks_in_flight(&self) -> usize {
+        self.task_limiter.in_flight()
+    }
+
+    /// Moderation throughput counters, with the outbound work in flight
+    pub async fn get_moderation_metrics(&self) -> ModerationMetricsSnapshot {
+        let mut metrics = self.moderation_system.get_moderation_metrics().await;
+        metrics.tasks_in_flight = self.task_limiter.in_flight() as u64;
+        metrics
+    }
+
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run.load(Ordering::Relaxed)
+    }
+
+    /// Set how raids are detected and how long raid mode lasts
+    pub async fn set_raid_mode_config(&self, config: RaidModeConfig) {
+        self.raid_mode.write().await.set_config(config);
+    }
+
+    /// Turn moderation, timers, commands, points or giveaways off for one platform
+    pub async fn set_platform_features(&self, platform: &str, features: PlatformFeatures) {
+        info!("Features for {}: {:?}", platform, features);
+        self.timer_system.set_platform_enabled(platform, features.timers).await;
+        self.platform_features.write().await.insert(platform.to_string(), features);
+    }
+
+    /// Webhooks notified about events on a platform, such as rejected credentials
+    pub async fn set_platform_webhooks(&self, platform: &str, webhooks: Vec<WebhookConfig>) {
+        self.platform_webhooks.write().await.insert(platform.to_string(), webhooks);
+    }
+
+    /// Set how fast announcements may be sent on a platform
+    pub async fn set_platform_rate_limits(&self, platform: &str, limits: RateLimitConfig) {
+        self.platform_rate_limits.write().await.insert(platform.to_string(), limits);
+    }
+
+    /// Schedule hours when chat is restricted automatically
+    pub async fn set_restricted_hours(&self, config: RestrictedHoursConfig) {
+        if !config.windows.is_empty() {
+            info!("Restricted hours: {} window(s), restriction {:?}", config.windows.len(), config.restriction);
+        }
+        self.restricted_hours.write().await.set_config(config);
+    }
+
+    /// Set the account the bot posts as on a platform; its messages are never moderated
+    pub async fn set_bot_account(&self, platform: &str, username: Option<&str>) {
+        self.moderation_system.set_bot_account(platform, username).await;
+    }
+
+    /// Another account the bot posts as on a platform, such as a send-only account
+    pub async fn add_bot_account(&self, platform: &str, username: &str) {
+        self.moderation_system.add_bot_account(platform, username).await;
+    }
+
+    /// Treat `other` as the same person as `primary`: their points, achievements,
+    /// reputation, offenses and shadow mutes are merged and from now on shared
+    /// under `primary`'s identity, which is returned. The link is saved to the
+    /// data directory.
+    pub async fn link_identities(&self, primary: (&str, &str), other: (&str, &str)) -> Result<String> {
+        self.identity_commands.link_identities(primary, other).await
+    }
+
+    /// Split an account off from the identity it was linked to. The shared state
+    /// stays with the accounts still linked, and the account that leaves starts
+    /// over; when it was the canonical account, the state moves to the one that
+    /// takes its place. Returns false if the account wasn't linked.
+    pub async fn unlink_identity(&self, platform: &str, username: &str) -> Result<bool> {
+        self.identity_commands.unlink_identity(platform, username).await
+    }
+
+    /// Persist identity links to a JSON file, loading any saved links
+    pub async fn set_identity_links_path(&self, path: impl Into<std::path::PathBuf>) -> Result<()> {
+        let path = path.into();
+        let saved = IdentityMap::load(&path).await?;
+        *self.moderation_system.identities().write().await = saved;
+        self.identity_commands.set_links_path(path).await;
+        Ok(())
+    }
+
+    /// Identity key an account's per-user state is kept under
+    pub async fn resolve_identity(&self, platform: &str, username: &str) -> String {
+        self.moderation_system.identity_key(platform, username).await
+    }
+
+    /// Log inbound messages so analytics can be rebuilt after a crash. Messages
+    /// logged since the last clean shutdown are replayed when the bot starts.
+    pub async fn set_message_log(&self, config: MessageLogConfig) -> Result<()> {
+        let log = if config.enabled {
+            Some(MessageLog::open(config).await?.with_log_redaction(self.moderation_system.log_redaction()).spawn())
+        } else {
+            None
+        };
+        *self.message_log.write().await = log;
+        Ok(())
+    }
+
+    /// Record every moderation action to an append-only audit trail at `path`
+    /// (`None` stops recording)
+    pub async fn set_audit_log(&self, path: Option<PathBuf>) -> Result<()> {
+        let log = match path {
+            Some(path) => Some(ModerationAuditLog::open(path).await?),
+            None => None,
+        };
+        *self.audit_log.write().await = log;
+        Ok(())
+    }
+
+    /// Record a moderation action a moderator took by hand
+    pub async fn audit_manual_action(&self, message: &ChatMessage, action: &ModerationAction, moderator: &str) -> Result<()> {
+        Self::record_manual_action(&self.audit_log, &self.moderation_system.log_redaction(), message, action, moderator).await
+    }
+
+    async fn record_manual_action(
+        audit_log: &RwLock<Option<ModerationAuditLog>>,
+        log_redaction: &redaction::LogRedaction,
+        message: &ChatMessage,
+        action: &ModerationAction,
+        moderator: &str,
+    ) -> Result<()> {
+        if let Some(log) = audit_log.write().await.as_mut() {
+            let source = AuditSource::Manual { moderator: moderator.to_string() };
+            log.record(&AuditEntry::new(message, action, source, log_redaction)).await?;
+        }
+        Ok(())
+    }
+
+    /// Audited moderation actions matching `query`, oldest first
+    pub async fn query_audit_log(&self, query: &AuditQuery) -> Result<Vec<AuditEntry>> {
+        let path = self.audit_log_path().await?;
+        ModerationAuditLog::query(&path, query).await
+    }
+
+    /// Export audited moderation actions matching `query` to a JSON file; returns how many
+    pub async fn export_audit_log(&self, query: &AuditQuery, output: &Path) -> Result<usize> {
+        let path = self.audit_log_path().await?;
+        ModerationAuditLog::export(&path, query, output).await
+    }
+
+    async fn audit_log_path(&self) -> Result<PathBuf> {
+        self.audit_log.read().await.as_ref()
+            .map(|log| log.path().to_path_buf())
+            .ok_or_else(|| anyhow::anyhow!("Moderation audit logging is disabled"))
+    }
+
+    /// Feed messages logged since the last clean shutdown back into analytics
+    /// (not moderation, commands or points); returns how many were replayed
+    pub async fn replay_message_log(&self) -> Result<usize> {
+        let Some(config) = self.message_log.read().await.as_ref().map(|log| log.config().clone()) else {
+            return Ok(0);
+        };
+
+        let replayed = MessageLog::replay(&config).await?;
+        let analytics = self.analytics_system.read().await;
+        let mut count = 0;
+        for message in &replayed.messages {
+            if !self.user_filter.read().await.is_allowed(&message.platform, &message.username) {
+                continue;
+            }
+            analytics.record_message(message).await;
+            count += 1;
+        }
+
+        if count > 0 {
+            info!("Replayed {} messages from {}", count, config.path.display());
+        }
+        Ok(count)
+    }
+
+    /// Switch restricted mode on or off if the schedule says so at `now`.
+    /// Runs every 30 seconds once the bot has started.
+    pub async fn check_restricted_hours(&self, now: chrono::DateTime<chrono::Utc>) -> Option<RestrictedHoursChange> {
+        Self::apply_restricted_hours(&self.restricted_hours, &self.connections, &self.moderation_system, &self.dry_run, now).await
+    }
+
+    async fn apply_restricted_hours(
+        restricted_hours: &RwLock<RestrictedHours>,
+        connections: &RwLock<HashMap<String, Box<dyn PlatformConnection>>>,
+        moderation_system: &ModerationSystem,
+        dry_run: &AtomicBool,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Option<RestrictedHoursChange> {
+        let (change, config) = {
+            let mut restricted_hours = restricted_hours.write().await;
+            let change = restricted_hours.update(now)?;
+            (change, restricted_hours.config().clone())
+        };
+        let active = change == RestrictedHoursChange::Activated;
+        info!("Restricted hours {}", if active { "started" } else { "ended" });
+
+        if dry_run.load(Ordering::Relaxed) {
+            info!("[DRY RUN] would {} restriction {:?} and filters {:?}",
+                  if active { "apply" } else { "lift" }, config.restriction, config.strict_filters);
+            return Some(change);
+        }
+
+        for filter_name in &config.strict_filters {
+            if let Err(e) = moderation_system.set_filter_enabled(filter_name, active).await {
+                warn!("Failed to switch restricted hours filter '{}': {}", filter_name, e);
+            }
+        }
+
+        let restriction = config.restriction.filter(|_| active);
+        let message = if active { &config.start_message } else { &config.end_message };
+        let connections_guard = connections.read().await;
+        for (platform_name, connection) in connections_guard.iter() {
+            for channel in connection.get_channels() {
+                if config.restriction.is_some() {
+                    if let Err(e) = connection.set_chat_restriction(&channel, restriction).await {
+                        warn!("Failed to set chat restriction on {}#{}: {}", platform_name, channel, e);
+                    }
+                }
+                if let Some(message) = message {
+                    if let Err(e) = connection.send_message(&channel, message).await {
+                        warn!("Failed to announce restricted hours on {}#{}: {}", platform_name, channel, e);
+                    }
+                }
+            }
+        }
+
+        Some(change)
+    }
+
+    /// Moderate inbound messages through `system` (AI patterns, escalation and
+    /// learning on top of the spam filters) instead of the spam filters alone;
+    /// moderators' AI commands such as `!aistrict` act on it too
+    pub async fn attach_enhanced_moderation(&self, system: Arc<EnhancedModerationSystem>) {
+        self.filter_commands.set_enhanced_moderation(Arc::clone(&system)).await;
+        *self.enhanced_moderation.write().await = Some(system);
+    }
+
+    /// Give the dashboard access to the adaptive tuning system; the bot also
+    /// reports its measured pipeline metrics to it and moderators can query
+    /// its trends from chat
+    pub async fn attach_adaptive_system(&self, system: Arc<AdaptivePerformanceSystem>) {
+        self.system_commands.set_adaptive_system(Arc::clone(&system)).await;
+        *self.adaptive_system.write().await = Some(system);
+    }
+
+    /// Restrict the dashboard to `ip_whitelist`; applies when the dashboard starts
+    pub async fn set_security_settings(&self, settings: SecuritySettings) {
+        *self.security_settings.write().await = Some(settings);
+    }
+
+    /// Give the dashboard and `!poolstats` access to the connection pool's statistics
+    pub async fn attach_connection_pool(&self, pool: Arc<ConnectionPool>) {
+        self.system_commands.set_connection_pool(Arc::clone(&pool)).await;
+        *self.connection_pool.write().await = Some(pool);
+    }
+
+    /// Give the dashboard and `!configstatus` access to the configuration status, and
+    /// `!findfilter` and the dashboard's filter search access to the configured filters
+    pub async fn attach_config_manager(&self, config_manager: Arc<ConfigurationManager>) {
+        self.filter_commands.set_config_manager(Arc::clone(&config_manager)).await;
+        self.system_commands.set_config_manager(Arc::clone(&config_manager)).await;
+        *self.config_manager.write().await = Some(config_manager);
+    }
+
+    /// Set how moderation actions are queued while their platform is disconnected
+    pub async fn set_pending_action_config(&self, config: PendingActionConfig) {
+        self.pending_actions.write().await.set_config(config);
+    }
+
+    /// Moderation actions waiting for a platform to reconnect
+    pub async fn pending_action_count(&self) -> usize {
+        self.pending_actions.read().await.len()
+    }
+
+    pub async fn set_diagnostics_options(&self, options: DiagnosticsOptions) {
+        *self.diagnostics_options.write().await = options;
+    }
+
+    /// Check that the bot is set up correctly: platforms connect, config files
+    /// validate, referenced env vars are set, the dashboard port is free and data
+    /// directories are writable
+    pub async fn run_diagnostics(&self) -> DiagnosticsReport {
+        let options = self.diagnostics_options.read().await.clone();
+        let dashboard_port = *self.dashboard_port.read().await;
+        Self::diagnose(&self.connections, &options, dashboard_port).await
+    }
+
+    async fn diagnose(
+        connections: &RwLock<HashMap<String, Box<dyn PlatformConnection>>>,
+        options: &DiagnosticsOptions,
+        running_dashboard_port: Option<u16>,
+    ) -> DiagnosticsReport {
+        let mut report = DiagnosticsReport::default();
+        let mut enabled_platforms = Vec::new();
+
+        if let Some(config_dir) = &options.config_dir {
+            let (checks, bot_config) = diagnostics::check_config_files(config_dir).await;
+            report.checks.extend(checks);
+            if let Some(bot_config) = bot_config {
+                report.checks.extend(diagnostics::check_env_vars(&bot_config, |name| std::env::var(name).ok()));
+                enabled_platforms = bot_config.platforms.iter()
+                    .filter(|(_, platform)| platform.enabled)
+                    .map(|(name, _)| name.clone())
+                    .collect();
+                enabled_platforms.sort();
+            }
+            // A read-only config directory is supported, it just can't be edited
+            report.checks.push(diagnostics::check_writable(config_dir, CheckStatus::Warn).await);
+        }
+
+        report.checks.extend(diagnostics::check_platforms(connections, &enabled_platforms, options).await);
+
+        if let Some(port) = options.dashboard_port {
+            report.checks.push(diagnostics::check_dashboard_port(port, running_dashboard_port == Some(port)).await);
+        }
+
+        for dir in &options.data_dirs {
+            report.checks.push(diagnostics::check_writable(dir, CheckStatus::Fail).await);
+        }
+
+        report
+    }
+
+    pub fn get_pipeline_metrics(&self) -> Arc<PipelineMetrics> {
+        Arc::clone(&self.pipeline_metrics)
+    }
+
+    /// Act on a non-chat platform event: timeouts moderators hand out on the
+    /// platform are audited as manual actions, bans and subscriptions are logged
+    async fn handle_platform_event(
+        audit_log: &RwLock<Option<ModerationAuditLog>>,
+        raid_mode: &RwLock<RaidMode>,
+        moderation_system: &ModerationSystem,
+        platform: &str,
+        event: TwitchEvent,
+    ) {
+        match event {
+            TwitchEvent::UserBanned { channel, username, moderator, reason, ends_at } => {
+                let Some(ends_at) = ends_at else {
+                    info!("{} banned {} in {}#{}: {}", moderator, username, platform, channel, reason);
+                    return;
+                };
+                let duration_seconds = (ends_at - chrono::Utc::now()).num_seconds().max(0) as u64;
+                info!("{} timed out {} for {}s in {}#{}: {}", moderator, username, duration_seconds, platform, channel, reason);
+
+                let message = ChatMessage::new(platform, &channel, &username, &reason);
+                let action = ModerationAction::TimeoutUser { duration_seconds };
+                let log_redaction = moderation_system.log_redaction();
+                if let Err(e) = Self::record_manual_action(audit_log, &log_redaction, &message, &action, &moderator).await {
+                    error!("Failed to audit {} timeout of {}: {}", platform, username, e);
+                }
+            }
+            TwitchEvent::Subscription { channel, username, tier, is_gift } => {
+                info!("{} {} tier {} in {}#{}", username, if is_gift { "was gifted" } else { "subscribed at" }, tier, platform, channel);
+            }
+            TwitchEvent::Raid { channel, raider, viewers } => {
+                info!("{} raided {}#{} with {} viewers", raider, platform, channel, viewers);
+                let mut raid_mode = raid_mode.write().await;
+                raid_mode.start(platform, &channel, Some(raider), chrono::Utc::now());
+                Self::relax_raid_links(&raid_mode, moderation_system, platform, &channel).await;
+            }
+            // Chat arrives through the message receiver
+            TwitchEvent::ChatMessage(_) => {}
         }
     }
 
-    pub fn get_moderation_system(&self) -> Arc<ModerationSystem> {
-        self.moderation_system.clone()
+    /// Drain the pipeline metrics gathered since the last report into the adaptive
+    /// system. Returns the reported window, or `None` when no system is attached.
+    pub async fn report_pipeline_metrics(&self) -> Result<Option<PipelineMetricsWindow>> {
+        Self::record_pipeline_metrics(&self.pipeline_metrics, &self.adaptive_system).await
     }
-     
-    pub fn create_enhanced_moderation(&self) -> EnhancedModerationSystem {
-        EnhancedModerationSystem::new(self.moderation_system.clone())
+
+    async fn record_pipeline_metrics(
+        pipeline_metrics: &PipelineMetrics,
+        adaptive_system: &RwLock<Option<Arc<AdaptivePerformanceSystem>>>,
+    ) -> Result<Option<PipelineMetricsWindow>> {
+        let Some(adaptive) = adaptive_system.read().await.clone() else {
+            return Ok(None);
+        };
+
+        let window = pipeline_metrics.take_window();
+        // An idle window has no latency to report; zeros would look like a speed-up
+        if window.messages_processed > 0 {
+            adaptive.record_metric("latency_message_processing", window.average_latency_ms).await?;
+        }
+        adaptive.record_metric("throughput_messages", window.messages_per_second()).await?;
+        adaptive.record_metric("error_rate", window.error_rate_percent()).await?;
+
+        debug!("Reported pipeline metrics: {} messages, avg latency {:.2}ms, {} errors",
+               window.messages_processed, window.average_latency_ms, window.errors);
+        Ok(Some(window))
     }
 
-    /// Set the command prefix (default is "!")
-    pub async fn set_command_prefix(&self, prefix: String) {
-        self.command_system.set_command_prefix(prefix).await;
+    /// Enter raid mode for a channel (e.g. on a platform raid notification): achievement
+    /// announcements are held back and batched into one welcome, and first-time chatters
+    /// may link the raiding channel. Ends on its own after the configured duration.
+    pub async fn start_raid_mode(&self, platform: &str, channel: &str, raiding_channel: Option<String>) {
+        let mut raid_mode = self.raid_mode.write().await;
+        raid_mode.start(platform, channel, raiding_channel, chrono::Utc::now());
+        info!("Raid mode started for {}#{}", platform, channel);
+        Self::relax_raid_links(&raid_mode, &self.moderation_system, platform, channel).await;
     }
 
-    /// Add a platform connection to the bot
-    pub async fn add_connection(&mut self, connection: Box<dyn PlatformConnection>) {
-        let platform_name = connection.platform_name().to_string();
-        info!("Added {} connection", platform_name);
-        self.connections.write().await.insert(platform_name, connection);
+    /// Let first-time chatters link the raiding channel, when it's known and the
+    /// config allows it. A raid detected from a burst of new chatters gets its
+    /// raider once the platform's raid notification arrives.
+    async fn relax_raid_links(raid_mode: &RaidMode, moderation_system: &ModerationSystem, platform: &str, channel: &str) {
+        if raid_mode.config().relax_link_rules {
+            let raiding_channel = raid_mode.raiding_channel(platform, channel, chrono::Utc::now()).map(str::to_string);
+            moderation_system.set_raid_link_exception(platform, channel, raiding_channel).await;
+        }
+    }
+
+    pub async fn is_raid_mode_active(&self, platform: &str, channel: &str) -> bool {
+        self.raid_mode.read().await.is_active(platform, channel, chrono::Utc::now())
+    }
+
+    /// Set when repeated blocks by one filter are aggregated into a single spam wave alert
+    pub async fn set_spam_wave_config(&self, config: spam_wave::SpamWaveConfig) {
+        self.moderation_system.set_spam_wave_config(config).await;
+    }
+
+    /// Minimum seconds between automated timeouts/warnings for the same user (0 disables)
+    pub async fn set_action_cooldown(&self, seconds: u64) {
+        self.moderation_system.set_action_cooldown(seconds).await;
+    }
+
+    /// Set how message content is written to logs
+    pub fn set_log_redaction(&self, mode: redaction::RedactionMode) {
+        info!("Log redaction set to {:?}", mode);
+        self.moderation_system.log_redaction().set(mode);
+    }
+
+    /// Set the consequence for users who keep hitting commands on cooldown
+    pub async fn set_cooldown_abuse_policy(&self, policy: Option<commands::CooldownAbusePolicy>) {
+        self.command_system.set_cooldown_abuse_policy(policy).await;
+    }
+
+    /// Reply to users who use a command on cooldown (`None` ignores them silently).
+    /// `$(user)`, `$(command)` and `$(remaining)` are filled in.
+    pub async fn set_cooldown_notice(&self, message: Option<String>, channel_interval_seconds: u64) {
+        self.command_system.set_cooldown_notice(commands::CooldownNotice { message, channel_interval_seconds }).await;
+    }
+
+    /// Limit how many commands each user may use per minute (0 disables the limit)
+    pub async fn set_command_rate_limit(&self, per_minute: u32, exempt_mods: bool) {
+        self.command_system.set_rate_limit(commands::CommandRateLimit { per_minute, exempt_mods }).await;
     }
 
     /// Register a new command
@@ -174,6 +1300,26 @@ impl ChatBot {
         self.timer_system.reload_config().await
     }
 
+    /// Skip scheduled timer posts for `duration`, or until resumed when `None`
+    pub async fn pause_timers(&self, duration: Option<chrono::Duration>) -> chrono::DateTime<chrono::Utc> {
+        self.timer_system.pause_all(duration).await
+    }
+
+    /// Resume timer posts after a pause
+    pub async fn resume_timers(&self) -> bool {
+        self.timer_system.resume_all().await
+    }
+
+    /// Fire a timer now and restart its interval; `force` ignores disabled timers and platforms
+    pub async fn trigger_timer_now(&self, name: &str, force: bool) -> Result<usize> {
+        self.timer_system.trigger_now(name, force).await
+    }
+
+    /// Reload a single timer from file without resetting the others' schedules
+    pub async fn reload_timer(&self, name: &str) -> Result<()> {
+        self.timer_system.reload_timer(name).await
+    }
+
     /// Get timer configuration categories
     pub async fn get_timer_categories(&self) -> HashMap<String, Vec<String>> {
         self.timer_system.get_timer_categories().await
@@ -194,7 +1340,7 @@ impl ChatBot {
     // =================================================================
 
     /// Add a spam filter to the bot (legacy method for backward compatibility)
-    pub async fn add_spam_filter(&self, filter_type: SpamFilterType) -> Result<()> {
+    pub async fn add_spam_filter(&self, filter_type: SpamFilterType) -> ModerationResult<()> {
         self.moderation_system.add_spam_filter(filter_type).await
     }
 
@@ -206,7 +1352,7 @@ impl ChatBot {
         warning_message: Option<String>,
         mod_exempt: bool,
         subscriber_exempt: bool,
-    ) -> Result<()> {
+    ) -> ModerationResult<()> {
         // Convert old parameters to new escalation system
         let escalation = ModerationEscalation {
             first_offense: ModerationAction::WarnUser { 
@@ -248,7 +1394,7 @@ impl ChatBot {
         exemption_level: ExemptionLevel,
         custom_message: Option<String>,
         silent_mode: bool,
-    ) -> Result<()> {
+    ) -> ModerationResult<()> {
         let escalation = ModerationEscalation {
             first_offense: ModerationAction::WarnUser { 
                 message: custom_message.clone().unwrap_or_else(|| "Please follow chat rules (first warning)".to_string())
@@ -276,7 +1422,7 @@ impl ChatBot {
         case_sensitive: Option<bool>,
         whole_words_only: Option<bool>,
         custom_message: Option<String>,
-    ) -> Result<()> {
+    ) -> ModerationResult<()> {
         let filter_name = format!("blacklist_{}", chrono::Utc::now().timestamp());
         
         self.moderation_system.add_blacklist_filter(
@@ -296,15 +1442,20 @@ impl ChatBot {
     }
 
     /// Enable/disable specific filter
-    pub async fn set_filter_enabled(&self, filter_name: &str, enabled: bool) -> Result<()> {
+    pub async fn set_filter_enabled(&self, filter_name: &str, enabled: bool) -> ModerationResult<()> {
         self.moderation_system.set_filter_enabled(filter_name, enabled).await
     }
 
     /// Remove filter
-    pub async fn remove_filter(&self, filter_name: &str) -> Result<()> {
+    pub async fn remove_filter(&self, filter_name: &str) -> ModerationResult<()> {
         self.moderation_system.remove_filter(filter_name).await
     }
 
+    /// Get full definitions of the active filters
+    pub async fn get_active_filters(&self) -> Vec<moderation::FilterInfo> {
+        self.moderation_system.get_active_filters().await
+    }
+
     /// List all filters
     pub async fn list_filters(&self) -> Vec<(String, bool)> {
         self.moderation_system.list_filters().await
@@ -330,40 +1481,57 @@ impl ChatBot {
         info!("Starting web dashboard on port {}...", port);
         
         // Import web modules locally to avoid module resolution issues
-        use crate::web::{WebDashboard};
+        use crate::web::{ApiAccessConfig, IpAllowlist, WebDashboard};
         
         // Create dashboard
-        let dashboard = WebDashboard::new();
+        let mut api_access = ApiAccessConfig::from_env();
+        if api_access.requires_api_key() {
+            info!("Dashboard API requires an X-API-Key ({} key(s) configured)", api_access.api_keys.len());
+        }
+        if let Some(security) = self.security_settings.read().await.as_ref() {
+            api_access.ip_allowlist = IpAllowlist::parse(&security.ip_whitelist, &security.trusted_proxies)
+                .map_err(|e| anyhow::anyhow!("Invalid security.ip_whitelist or trusted_proxies: {}", e))?;
+            if api_access.ip_allowlist.is_enabled() {
+                info!("Dashboard restricted to {} allowed address range(s)", security.ip_whitelist.len());
+            }
+        }
+        let dashboard = WebDashboard::new().with_api_access(api_access);
         let dashboard_state = dashboard.get_state();
         
         info!("Setting up dashboard data updates...");
         
         // Start periodic data updates for the dashboard
-        let analytics_system = Arc::clone(&self.analytics_system);
-        let connections = Arc::clone(&self.connections);
+        let adaptive_system = self.adaptive_system.read().await.clone();
+        if let Some(adaptive) = &adaptive_system {
+            dashboard_state.set_adaptive_system(Arc::clone(adaptive)).await;
+        }
+        if let Some(config_manager) = self.config_manager.read().await.clone() {
+            dashboard_state.set_config_manager(config_manager).await;
+        }
+        // Filter tests go through the enhanced system so AI patterns are explained too
+        let enhanced_moderation = match self.enhanced_moderation.read().await.clone() {
+            Some(enhanced) => enhanced,
+            None => Arc::new(self.create_enhanced_moderation()),
+        };
+        dashboard_state.set_moderation_system(enhanced_moderation).await;
+        // Protected dashboard actions (manual tuning) are disabled without a token
+        dashboard_state.set_admin_token(std::env::var("DASHBOARD_ADMIN_TOKEN").ok()).await;
+        let sources = Arc::new(DashboardSources {
+            analytics_system: Arc::clone(&self.analytics_system),
+            moderation_system: Arc::clone(&self.moderation_system),
+            task_limiter: Arc::clone(&self.task_limiter),
+            connections: Arc::clone(&self.connections),
+            adaptive_system,
+            connection_pool: self.connection_pool.read().await.clone(),
+            config_manager: self.config_manager.read().await.clone(),
+        });
         let state_for_updates = dashboard_state.clone();
-        
-        tokio::spawn(async move {
-            info!("Dashboard data updater started");
-            let mut interval = tokio::time::interval(std::time::Duration::from_secs(5));
-            loop {
-                interval.tick().await;
-                
-                // Update analytics data
-                let analytics = analytics_system.read().await.get_analytics().await;
-                state_for_updates.update_analytics(analytics).await;
-                
-                // Update health data
-                let mut health = HashMap::new();
-                {
-                    let connections_guard = connections.read().await;
-                    for (platform_name, connection) in connections_guard.iter() {
-                        let is_healthy = connection.is_connected().await;
-                        health.insert(platform_name.clone(), is_healthy);
-                    }
-                }
-                state_for_updates.update_health(health).await;
-            }
+
+        info!("Dashboard data updater started");
+        dashboard_state.spawn_updater(std::time::Duration::from_secs(5), move || {
+            let sources = Arc::clone(&sources);
+            let state = state_for_updates.clone();
+            async move { sources.refresh(&state).await }
         });
         
         info!("Starting web server...");
@@ -375,6 +1543,7 @@ impl ChatBot {
             }
         });
         
+        *self.dashboard_port.write().await = Some(port);
         info!("Web dashboard started on port {}", port);
         Ok(())
     }
@@ -452,15 +1621,25 @@ impl ChatBot {
         // Initialize achievement system
         self.achievement_system.initialize_default_achievements().await;
 
+        // Rebuild analytics from messages logged before a crash
+        if let Err(e) = self.replay_message_log().await {
+            error!("Failed to replay message log: {:#}", e);
+        }
+
         // Collect message receivers
         let mut receivers = Vec::new();
+        let mut event_receivers = Vec::new();
         
         // Connect to all platforms
         {
             let mut connections_guard = self.connections.write().await;
             for (platform_name, connection) in connections_guard.iter_mut() {
                 if let Err(e) = connection.connect().await {
-                    error!("Failed to connect to {}: {}", platform_name, e);
+                    if let Some(auth_error) = ConnectionError::auth_failure(&e) {
+                        self.report_auth_failure(auth_error).await;
+                    } else {
+                        error!("Failed to connect to {}: {}", platform_name, e);
+                    }
                     continue;
                 }
                 
@@ -469,12 +1648,97 @@ impl ChatBot {
                     receivers.push(receiver);
                     info!("Set up message receiver for {}", platform_name);
                 }
+                if let Some(events) = connection.get_event_receiver() {
+                    event_receivers.push((platform_name.clone(), events));
+                }
             }
         }
 
+        for (platform, mut events) in event_receivers {
+            let audit_log = Arc::clone(&self.audit_log);
+            let raid_mode = Arc::clone(&self.raid_mode);
+            let moderation_system = Arc::clone(&self.moderation_system);
+            tokio::spawn(async move {
+                loop {
+                    match events.recv().await {
+                        Ok(event) => Self::handle_platform_event(&audit_log, &raid_mode, &moderation_system, &platform, event).await,
+                        Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                            warn!("Skipped {} {} events, falling behind", skipped, platform);
+                        }
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    }
+                }
+            });
+        }
+
         // Start message processing with the collected receivers
         self.start_message_processor(receivers).await?;
 
+        // Report spam waves once they go quiet instead of logging every block,
+        // to the log and to every platform's webhooks subscribed to them
+        let moderation_system = Arc::clone(&self.moderation_system);
+        let platform_webhooks = Arc::clone(&self.platform_webhooks);
+        tokio::spawn(async move {
+            let client = reqwest::Client::new();
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(5));
+            loop {
+                interval.tick().await;
+                let alerts = moderation_system.flush_spam_waves().await;
+                if alerts.is_empty() {
+                    continue;
+                }
+                let mut webhooks: Vec<WebhookConfig> = Vec::new();
+                for webhook in platform_webhooks.read().await.values().flatten() {
+                    if !webhooks.iter().any(|known| known.url == webhook.url) {
+                        webhooks.push(webhook.clone());
+                    }
+                }
+                for alert in &alerts {
+                    auth_alerts::send_spam_wave_alert(&client, &webhooks, alert).await;
+                }
+            }
+        });
+
+        // Feed measured pipeline metrics to adaptive tuning
+        let pipeline_metrics = Arc::clone(&self.pipeline_metrics);
+        let adaptive_system = Arc::clone(&self.adaptive_system);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(30));
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                if let Err(e) = Self::record_pipeline_metrics(&pipeline_metrics, &adaptive_system).await {
+                    error!("Failed to record pipeline metrics: {}", e);
+                }
+            }
+        });
+
+        // Switch restricted hours on and off on schedule
+        let restricted_hours = Arc::clone(&self.restricted_hours);
+        let connections = Arc::clone(&self.connections);
+        let moderation_system = Arc::clone(&self.moderation_system);
+        let dry_run = Arc::clone(&self.dry_run);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(30));
+            loop {
+                interval.tick().await;
+                Self::apply_restricted_hours(&restricted_hours, &connections, &moderation_system, &dry_run, chrono::Utc::now()).await;
+            }
+        });
+
+        // Drop shadow mutes that ran out while the user stayed quiet
+        let moderation_system = Arc::clone(&self.moderation_system);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(60));
+            loop {
+                interval.tick().await;
+                let pruned = moderation_system.prune_expired_shadow_mutes(chrono::Utc::now()).await;
+                if pruned > 0 {
+                    debug!("Pruned {} expired shadow mute(s)", pruned);
+                }
+            }
+        });
+
         // Start the timer system with external YAML configuration
         let timer_system_clone = Arc::clone(&self.timer_system);
         let connections_clone = Arc::clone(&self.connections);
@@ -498,6 +1762,141 @@ impl ChatBot {
         Ok(())
     }
 
+    /// Post a message to every channel on every connected platform, pacing each
+    /// platform by its rate limits, and report which channels it reached
+    pub async fn broadcast(&self, message: &str) -> AnnouncementReport {
+        Self::broadcast_to(
+            &self.connections,
+            &self.duplicate_guard,
+            &self.platform_rate_limits,
+            self.dry_run.load(Ordering::Relaxed),
+            message,
+        ).await
+    }
+
+    async fn broadcast_to(
+        connections: &RwLock<HashMap<String, Box<dyn PlatformConnection>>>,
+        duplicate_guard: &RwLock<DuplicateGuard>,
+        rate_limits: &RwLock<HashMap<String, RateLimitConfig>>,
+        dry_run: bool,
+        message: &str,
+    ) -> AnnouncementReport {
+        let connections_guard = connections.read().await;
+        let rate_limits = rate_limits.read().await.clone();
+
+        // Platforms are sent to concurrently; each one's channels in turn at its own rate
+        let sends = connections_guard.iter().map(|(platform, connection)| {
+            let limits = rate_limits.get(platform);
+            async move {
+                let mut report = AnnouncementReport::default();
+                let channels = connection.get_channels();
+
+                let unavailable = if !connection.is_connected().await {
+                    Some("not connected".to_string())
+                } else if connection.quota_usage().await.is_some_and(|quota| quota.remaining() == 0) {
+                    Some("API quota exhausted".to_string())
+                } else {
+                    None
+                };
+                if let Some(reason) = unavailable {
+                    report.failed.extend(channels.iter().map(|channel| (AnnouncementTarget::new(platform, channel), reason.clone())));
+                    return report;
+                }
+
+                for (sent, channel) in channels.iter().enumerate() {
+                    let delay = announcements::pacing_delay(limits, sent);
+                    if !delay.is_zero() {
+                        tokio::time::sleep(delay).await;
+                    }
+
+                    let target = AnnouncementTarget::new(platform, channel);
+                    if dry_run {
+                        info!("[DRY RUN] would announce to {}#{}: {}", platform, channel, message);
+                        report.delivered.push(target);
+                        continue;
+                    }
+
+                    let prepared = duplicate_guard.write().await.prepare(platform, channel, message);
+                    match connection.send_message_with_status(channel, &prepared).await {
+                        Ok(DeliveryStatus::Delivered) => {
+                            duplicate_guard.write().await.record_delivered(platform, channel, &prepared);
+                            report.delivered.push(target);
+                        }
+                        Ok(DeliveryStatus::Dropped { reason }) => report.failed.push((target, format!("dropped: {}", reason))),
+                        Err(e) => report.failed.push((target, e.to_string())),
+                    }
+                }
+                report
+            }
+        });
+
+        let mut report = AnnouncementReport::default();
+        for platform_report in futures_util::future::join_all(sends).await {
+            report.merge(platform_report);
+        }
+        report.sort();
+        info!("Announcement sent: {}", report.summary());
+        report
+    }
+
+    /// Key of the queue sends to one channel wait in
+    fn send_queue_key(platform: &str, channel: &str) -> String {
+        format!("{}#{}", platform, channel.to_lowercase())
+    }
+
+    async fn is_platform_connected(connections: &RwLock<HashMap<String, Box<dyn PlatformConnection>>>, platform: &str) -> bool {
+        match connections.read().await.get(platform) {
+            Some(connection) => connection.is_connected().await,
+            None => false,
+        }
+    }
+
+    /// Send a response through its platform's connection. Messages the platform
+    /// drops are reported to analytics; only a failed send is an error.
+    async fn deliver_response(
+        connections: &RwLock<HashMap<String, Box<dyn PlatformConnection>>>,
+        send_accounts: Option<&ConnectionPool>,
+        duplicate_guard: &RwLock<DuplicateGuard>,
+        analytics_sender: &tokio::sync::mpsc::Sender<AnalyticsEvent>,
+        platform: &str,
+        channel: &str,
+        message: &str,
+    ) -> Result<()> {
+        // Dedicated send accounts take over from the main connection when configured
+        let send_accounts = match send_accounts {
+            Some(pool) if pool.has_sender(platform).await => Some(pool),
+            _ => None,
+        };
+        let (status, message) = if let Some(pool) = send_accounts {
+            let message = duplicate_guard.write().await.prepare(platform, channel, message);
+            (pool.send_message_with_status(platform, channel, &message).await?, message)
+        } else {
+            let connections_guard = connections.read().await;
+            let Some(connection) = connections_guard.get(platform) else {
+                return Err(anyhow::anyhow!("No connection found for platform: {}", platform));
+            };
+            let message = duplicate_guard.write().await.prepare(platform, channel, message);
+            (connection.send_message_with_status(channel, &message).await?, message)
+        };
+        match status {
+            DeliveryStatus::Delivered => {
+                duplicate_guard.write().await.record_delivered(platform, channel, &message);
+                info!("Sent response to {}#{}: {}", platform, channel, message);
+            }
+            DeliveryStatus::Dropped { reason } => {
+                warn!("Response to {}#{} was dropped ({}): {}", platform, channel, reason, message);
+                if let Err(e) = analytics_sender.send(AnalyticsEvent::MessageDropped {
+                    platform: platform.to_string(),
+                    channel: channel.to_string(),
+                    reason,
+                }).await {
+                    error!("Failed to send analytics dropped message event: {}", e);
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Process incoming messages with enhanced moderation
     async fn start_message_processor(&self, receivers: Vec<broadcast::Receiver<ChatMessage>>) -> Result<()> {
         let command_system = Arc::clone(&self.command_system);
@@ -517,17 +1916,135 @@ impl ChatBot {
         // Response handler that sends messages back to platforms
         {
             let connections = Arc::clone(&connections);
+            let connection_pool = Arc::clone(&self.connection_pool);
+            let analytics_sender = Arc::clone(&analytics_sender);
+            let duplicate_guard = Arc::clone(&self.duplicate_guard);
+            let dry_run = Arc::clone(&self.dry_run);
+            let pipeline_metrics = Arc::clone(&self.pipeline_metrics);
+            let task_limiter = Arc::clone(&self.task_limiter);
             tokio::spawn(async move {
                 while let Some((platform, channel, message)) = response_rx.recv().await {
-                    let connections_guard = connections.read().await;
-                    if let Some(connection) = connections_guard.get(&platform) {
-                        if let Err(e) = connection.send_message(&channel, &message).await {
+                    if dry_run.load(Ordering::Relaxed) {
+                        info!("[DRY RUN] would send to {}#{}: {}", platform, channel, message);
+                        continue;
+                    }
+                    let connections = Arc::clone(&connections);
+                    let send_accounts = connection_pool.read().await.clone();
+                    let duplicate_guard = Arc::clone(&duplicate_guard);
+                    let analytics_sender = Arc::clone(&analytics_sender);
+                    let pipeline_metrics = Arc::clone(&pipeline_metrics);
+                    // Sends to one channel go out in order; channels proceed side by side
+                    let key = Self::send_queue_key(&platform, &channel);
+                    task_limiter.spawn_ordered(&key, async move {
+                        if let Err(e) = Self::deliver_response(&connections, send_accounts.as_deref(), &duplicate_guard, &analytics_sender, &platform, &channel, &message).await {
                             error!("Failed to send response to {}#{}: {}", platform, channel, e);
-                        } else {
-                            info!("Sent response to {}#{}: {}", platform, channel, message);
+                            pipeline_metrics.record_error();
+                        }
+                    }).await;
+                }
+            });
+        }
+
+        // Moderation actions are queued while their platform is down instead of being lost
+        let (moderation_tx, mut moderation_rx) = tokio::sync::mpsc::channel::<(String, String, String)>(100);
+        {
+            let connections = Arc::clone(&connections);
+            let analytics_sender = Arc::clone(&analytics_sender);
+            let duplicate_guard = Arc::clone(&self.duplicate_guard);
+            let dry_run = Arc::clone(&self.dry_run);
+            let pending_actions = Arc::clone(&self.pending_actions);
+            let task_limiter = Arc::clone(&self.task_limiter);
+            tokio::spawn(async move {
+                while let Some((platform, channel, message)) = moderation_rx.recv().await {
+                    if dry_run.load(Ordering::Relaxed) {
+                        info!("[DRY RUN] would send to {}#{}: {}", platform, channel, message);
+                        continue;
+                    }
+
+                    let connections = Arc::clone(&connections);
+                    let duplicate_guard = Arc::clone(&duplicate_guard);
+                    let analytics_sender = Arc::clone(&analytics_sender);
+                    let pending_actions = Arc::clone(&pending_actions);
+                    let key = Self::send_queue_key(&platform, &channel);
+                    task_limiter.spawn_ordered(&key, async move {
+                        // Checked in turn, so an action never overtakes one that failed before it
+                        if pending_actions.read().await.has_pending(&platform) || !Self::is_platform_connected(&connections, &platform).await {
+                            warn!("{} is unavailable, queuing moderation action for #{}", platform, channel);
+                            pending_actions.write().await.push(&platform, &channel, &message, chrono::Utc::now());
+                            return;
+                        }
+                        if let Err(e) = Self::deliver_response(&connections, None, &duplicate_guard, &analytics_sender, &platform, &channel, &message).await {
+                            warn!("Moderation action for {}#{} failed, queuing it until {} recovers: {}", platform, channel, platform, e);
+                            pending_actions.write().await.push(&platform, &channel, &message, chrono::Utc::now());
+                        }
+                    }).await;
+                }
+            });
+        }
+        {
+            let connections = Arc::clone(&connections);
+            let analytics_sender = Arc::clone(&analytics_sender);
+            let duplicate_guard = Arc::clone(&self.duplicate_guard);
+            let pending_actions = Arc::clone(&self.pending_actions);
+            let task_limiter = Arc::clone(&self.task_limiter);
+            tokio::spawn(async move {
+                let mut interval = tokio::time::interval(std::time::Duration::from_secs(1));
+                loop {
+                    interval.tick().await;
+                    let platforms = pending_actions.read().await.platforms();
+                    for platform in platforms {
+                        if !Self::is_platform_connected(&connections, &platform).await {
+                            continue;
+                        }
+                        let channels = pending_actions.read().await.channels(&platform);
+                        for channel in channels {
+                            // Drained through the channel's send queue, ahead of anything sent to it since
+                            let connections = Arc::clone(&connections);
+                            let duplicate_guard = Arc::clone(&duplicate_guard);
+                            let analytics_sender = Arc::clone(&analytics_sender);
+                            let pending_actions = Arc::clone(&pending_actions);
+                            let platform = platform.clone();
+                            let key = Self::send_queue_key(&platform, &channel);
+                            task_limiter.spawn_ordered(&key, async move {
+                                let mut ready = pending_actions.write().await.take_ready_in(&platform, &channel, chrono::Utc::now());
+                                if !ready.is_empty() {
+                                    info!("{} recovered, sending {} queued moderation actions to #{}", platform, ready.len(), channel);
+                                }
+                                while !ready.is_empty() {
+                                    let action = &ready[0];
+                                    if let Err(e) = Self::deliver_response(&connections, None, &duplicate_guard, &analytics_sender, &action.platform, &action.channel, &action.message).await {
+                                        warn!("Queued moderation action for {}#{} failed again, keeping it queued: {}", action.platform, action.channel, e);
+                                        pending_actions.write().await.restore(&platform, ready);
+                                        break;
+                                    }
+                                    ready.remove(0);
+                                }
+                            }).await;
+                        }
+                    }
+                }
+            });
+        }
+
+        // End raid windows that have run their course, sending the held-back welcomes as one message
+        {
+            let raid_mode = Arc::clone(&self.raid_mode);
+            let moderation_system = Arc::clone(&moderation_system);
+            let response_tx = response_tx.clone();
+            tokio::spawn(async move {
+                let mut interval = tokio::time::interval(std::time::Duration::from_secs(1));
+                loop {
+                    interval.tick().await;
+                    let expired = raid_mode.write().await.take_expired(chrono::Utc::now());
+                    for summary in expired {
+                        info!("Raid mode ended for {}#{} ({} welcomes batched)",
+                              summary.platform, summary.channel, summary.welcomed.len());
+                        moderation_system.set_raid_link_exception(&summary.platform, &summary.channel, None).await;
+                        if let Some(welcome) = summary.welcome_message() {
+                            if let Err(e) = response_tx.send((summary.platform.clone(), summary.channel.clone(), welcome)).await {
+                                error!("Failed to send raid welcome: {}", e);
+                            }
                         }
-                    } else {
-                        warn!("No connection found for platform: {}", platform);
                     }
                 }
             });
@@ -553,176 +2070,48 @@ impl ChatBot {
         }
 
         // Process messages from all platform receivers
+        let processor = MessageProcessor {
+            command_system,
+            moderation_system,
+            enhanced_moderation: Arc::clone(&self.enhanced_moderation),
+            analytics_sender,
+            points_system: Arc::clone(&self.points_system),
+            points_commands: Arc::clone(&self.points_commands),
+            achievement_system: Arc::clone(&self.achievement_system),
+            achievement_commands: Arc::clone(&self.achievement_commands),
+            filter_commands: Arc::clone(&self.filter_commands),
+            system_commands: Arc::clone(&self.system_commands),
+            identity_commands: Arc::clone(&self.identity_commands),
+            timer_commands: Arc::clone(&self.timer_commands),
+            giveaway_system: Arc::clone(&self.giveaway_system),
+            giveaway_commands: Arc::clone(&self.giveaway_commands),
+            user_filter: Arc::clone(&self.user_filter),
+            dry_run: Arc::clone(&self.dry_run),
+            message_timeout_ms: Arc::clone(&self.message_timeout_ms),
+            task_limiter: Arc::clone(&self.task_limiter),
+            raid_mode: Arc::clone(&self.raid_mode),
+            platform_features: Arc::clone(&self.platform_features),
+            message_log: Arc::clone(&self.message_log),
+            audit_log: Arc::clone(&self.audit_log),
+            pipeline_metrics: Arc::clone(&self.pipeline_metrics),
+            connections,
+            diagnostics_options: Arc::clone(&self.diagnostics_options),
+            dashboard_port: Arc::clone(&self.dashboard_port),
+            duplicate_guard: Arc::clone(&self.duplicate_guard),
+            platform_rate_limits: Arc::clone(&self.platform_rate_limits),
+            response_tx,
+            moderation_tx,
+            analytics_command_tx,
+        };
         for mut receiver in receivers {
-            let response_tx = response_tx.clone();
-            let analytics_command_tx = analytics_command_tx.clone();
-            let command_system = Arc::clone(&command_system);
-            let moderation_system = Arc::clone(&moderation_system);
-            let analytics_sender = Arc::clone(&analytics_sender);
-            let points_system = Arc::clone(&self.points_system);
-            let points_commands = Arc::clone(&self.points_commands);
-            let achievement_system = Arc::clone(&self.achievement_system);
-            let achievement_commands = Arc::clone(&self.achievement_commands);
-            let filter_commands = Arc::clone(&self.filter_commands);
-            let timer_commands = Arc::clone(&self.timer_commands); 
-            let giveaway_system = Arc::clone(&self.giveaway_system);
-            
+            let processor = processor.clone();
             tokio::spawn(async move {
                 loop {
                     match receiver.recv().await {
-                        Ok(message) => {
-                            info!("Processing message from {}: {}", message.username, message.content);
-                            
-                            // Record message in analytics
-                            if let Err(e) = analytics_sender.send(AnalyticsEvent::MessageReceived(message.clone())).await {
-                                error!("Failed to send analytics message event: {}", e);
-                            }
-                            
-                            // Process message for points (always, even if spam)
-                            if let Err(e) = points_system.process_message(&message).await {
-                                error!("Failed to process points for message: {}", e);
-                            }
-                            
-                            // PROCESS GIVEAWAY PARTICIPATION (ADD THIS)
-                            if let Err(e) = giveaway_system.process_message(&message).await {
-                                error!("Failed to process giveaway message: {}", e);
-                            }
-
-                            // Check for achievement unlocks after processing points
-                            if let Some(user_points) = points_system.get_user_points(&message.platform, &message.username).await {
-                                let unlocked_achievements = achievement_system.check_achievements(&user_points).await;
-                                
-                                for achievement in unlocked_achievements {
-                                    // Award achievement bonus points
-                                    if let Err(e) = points_system.add_points(&message.platform, &message.username, 
-                                                                           achievement.reward_points, &format!("Achievement: {}", achievement.name)).await {
-                                        error!("Failed to award achievement points: {}", e);
-                                    }
-                                    
-                                    // Announce the achievement
-                                    if let Err(e) = achievement_commands.announce_achievement(&achievement, &message.username, &message, &response_tx).await {
-                                        error!("Failed to announce achievement: {}", e);
-                                    }
-                                }
-                            }
-                            
-                            // Update user message history for moderation
-                            moderation_system.update_user_history(&message).await;
-                            
-                            // Check spam filters first (ENHANCED with user points context)
-                            let user_points = points_system.get_user_points(&message.platform, &message.username).await;
-                            if let Some(action) = moderation_system.check_spam_filters(&message, user_points.as_ref()).await {
-                                warn!("Message flagged by spam filter: {} from {}", message.content, message.username);
-                                
-                                // Record spam in analytics
-                                if let Err(e) = analytics_sender.send(AnalyticsEvent::SpamBlocked(message.clone())).await {
-                                    error!("Failed to send analytics spam event: {}", e);
-                                }
-                                
-                                // Handle moderation action
-                                if let Err(e) = moderation::ModerationSystem::handle_moderation_action(
-                                    action, &message, &response_tx
-                                ).await {
-                                    error!("Failed to handle moderation action: {}", e);
-                                }
-                                continue; // Don't process commands for flagged messages
-                            }
-                            
-                            // Check for commands
-                            let prefix = command_system.command_prefix.read().await.clone();
-                            if message.content.starts_with(&prefix) {
-                                let content_without_prefix = &message.content[prefix.len()..];
-                                let parts: Vec<&str> = content_without_prefix.split_whitespace().collect();
-                                
-                                if !parts.is_empty() {
-                                    let command_name = parts[0].to_lowercase();
-                                    let args: Vec<&str> = parts[1..].to_vec();
-                                    
-                                    // Try timer commands first (NEW)
-                                    match timer_commands.process_command(&command_name, &args, &message, &response_tx).await {
-                                        Ok(true) => {
-                                            // Timer command was handled
-                                            continue;
-                                        }
-                                        Ok(false) => {
-                                            // Not a timer command, try filter commands
-                                        }
-                                        Err(e) => {
-                                            error!("Error processing timer command: {}", e);
-                                        }
-                                    }
-                                    
-                                    // Try filter commands
-                                    match filter_commands.process_command(&command_name, &args, &message, &response_tx).await {
-                                        Ok(true) => {
-                                            // Filter command was handled
-                                            continue;
-                                        }
-                                        Ok(false) => {
-                                            // Not a filter command, try achievement commands
-                                        }
-                                        Err(e) => {
-                                            error!("Error processing filter command: {}", e);
-                                        }
-                                    }
-                                    
-                                    // Try achievement commands
-                                    match achievement_commands.process_command(&command_name, &args, &message, &response_tx).await {
-                                        Ok(true) => {
-                                            // Achievement command was handled
-                                            continue;
-                                        }
-                                        Ok(false) => {
-                                            // Not an achievement command, try points commands
-                                        }
-                                        Err(e) => {
-                                            error!("Error processing achievement command: {}", e);
-                                        }
-                                    }
-                                    
-                                    // Try points commands
-                                    match points_commands.process_command(&command_name, &args, &message, &response_tx).await {
-                                        Ok(true) => {
-                                            // Points command was handled
-                                            if let Err(e) = points_system.process_command(&message, &command_name).await {
-                                                error!("Failed to process command points: {}", e);
-                                            }
-                                            continue;
-                                        }
-                                        Ok(false) => {
-                                            // Not a points command, continue to regular commands
-                                        }
-                                        Err(e) => {
-                                            error!("Error processing points command: {}", e);
-                                        }
-                                    }
-                                }
-                            }
-                            
-                            // Process regular commands
-                            if let Err(e) = command_system.process_message(
-                                message.clone(), 
-                                &response_tx,
-                                Some(&analytics_command_tx)
-                            ).await {
-                                error!("Failed to process command: {}", e);
-                            } else {
-                                // Award points for command usage
-                                if message.content.starts_with(&prefix) {
-                                    let content_without_prefix = &message.content[prefix.len()..];
-                                    let parts: Vec<&str> = content_without_prefix.split_whitespace().collect();
-                                    
-                                    if !parts.is_empty() {
-                                        let command_name = parts[0].to_lowercase();
-                                        if let Err(e) = points_system.process_command(&message, &command_name).await {
-                                            error!("Failed to process command points: {}", e);
-                                        }
-                                    }
-                                }
-                            }
-                        }
+                        Ok(message) => processor.process(message).await,
                         Err(broadcast::error::RecvError::Lagged(n)) => {
                             warn!("Message receiver lagged by {} messages", n);
+                            processor.pipeline_metrics.record_errors(n);
                         }
                         Err(broadcast::error::RecvError::Closed) => {
                             info!("Message receiver closed");
@@ -740,17 +2129,45 @@ impl ChatBot {
     // UTILITY METHODS
     // =================================================================
 
+    /// Log rejected credentials loudly and alert any subscribed webhooks
+    async fn report_auth_failure(&self, auth_error: &ConnectionError) {
+        let ConnectionError::AuthFailed { platform, .. } = auth_error;
+        error!("🔑 AUTH FAILED: {} - the token is invalid or expired; update the credentials and restart", auth_error);
+
+        let webhooks = self.platform_webhooks.read().await.get(platform).cloned().unwrap_or_default();
+        if auth_alerts::subscribed_webhooks(&webhooks, auth_alerts::AUTH_FAILED_EVENT).next().is_some() {
+            let auth_error = auth_error.clone();
+            tokio::spawn(async move {
+                auth_alerts::send_auth_failure_alert(&reqwest::Client::new(), &webhooks, &auth_error).await;
+            });
+        }
+    }
+
+    /// Connection state per platform, with rejected credentials reported separately from outages
+    pub async fn platform_health(&self) -> HashMap<String, PlatformHealth> {
+        let mut status = HashMap::new();
+        let connections_guard = self.connections.read().await;
+
+        for (platform_name, connection) in connections_guard.iter() {
+            status.insert(platform_name.clone(), connection.health().await);
+        }
+
+        status
+    }
+
     /// Health check for all connections
     pub async fn health_check(&self) -> HashMap<String, bool> {
         let mut status = HashMap::new();
         let connections_guard = self.connections.read().await;
         
         for (platform_name, connection) in connections_guard.iter() {
-            let is_healthy = connection.is_connected().await;
-            status.insert(platform_name.clone(), is_healthy);
+            let health = connection.health().await;
+            status.insert(platform_name.clone(), health.is_healthy());
             
-            if !is_healthy {
-                warn!("{} connection is unhealthy", platform_name);
+            match health {
+                PlatformHealth::Connected => {}
+                PlatformHealth::AuthFailed => error!("{} authentication failed - check its credentials", platform_name),
+                PlatformHealth::Down => warn!("{} connection is unhealthy", platform_name),
             }
         }
         
@@ -792,6 +2209,10 @@ impl ChatBot {
         stats.insert("filters".to_string(), serde_json::Value::Object(
             filter_stats.into_iter().collect()
         ));
+
+        // Moderation throughput and block rate
+        let moderation_metrics = self.get_moderation_metrics().await;
+        stats.insert("moderation_metrics".to_string(), serde_json::to_value(moderation_metrics)?);
         
         // Add giveaway statistics
         let giveaway_stats = self.get_giveaway_statistics().await;
@@ -827,6 +2248,26 @@ impl ChatBot {
         self.add_command(trigger, enhanced_response, mod_only, cooldown_seconds).await;
     }
 
+    /// Restrict which channels a command responds in (returns false if the command doesn't exist)
+    pub async fn set_command_channels(&self, command_name: &str, channels: crate::types::CommandChannels) -> bool {
+        self.command_system.set_command_channels(command_name, channels).await
+    }
+
+    /// Put a command in a category, or clear it with `None` (returns false if the command doesn't exist)
+    pub async fn set_command_category(&self, command_name: &str, category: Option<String>) -> bool {
+        self.command_system.set_command_category(command_name, category).await
+    }
+
+    /// Give one command its own cooldown notice, or clear it with `None` (returns false if the command doesn't exist)
+    pub async fn set_command_cooldown_notice(&self, command_name: &str, notice: Option<String>) -> bool {
+        self.command_system.set_command_cooldown_notice(command_name, notice).await
+    }
+
+    /// Enable/disable all commands in a category
+    pub async fn set_command_category_enabled(&self, category: &str, enabled: bool) -> Result<usize> {
+        self.command_system.set_category_enabled(category, enabled).await
+    }
+
     /// Remove a command
     pub async fn remove_command(&self, command_name: &str) -> bool {
         self.command_system.remove_command(command_name).await
@@ -908,6 +2349,39 @@ impl ChatBot {
         self.giveaway_system.get_statistics().await
     }
 
+    /// Set how heuristic fraud signals are weighted for giveaway entrants
+    pub async fn set_giveaway_fraud_weights(&self, weights: crate::bot::giveaway_fraud::FraudWeights) {
+        self.giveaway_system.set_fraud_weights(weights).await
+    }
+
+    /// Keep points seasons and giveaways under the data directory, loading any
+    /// state a previous run saved there
+    pub async fn set_data_dir(&self, data_dir: &DataDir) -> Result<()> {
+        self.points_system.set_seasons_path(data_dir.file(data_dir::POINTS_SEASONS_FILE)).await;
+        self.set_giveaway_history_path(data_dir.file(data_dir::GIVEAWAY_HISTORY_FILE)).await?;
+        self.set_active_giveaway_path(data_dir.file(data_dir::ACTIVE_GIVEAWAY_FILE)).await?;
+        self.giveaway_commands.set_data_dir(data_dir.clone()).await;
+        self.set_identity_links_path(data_dir.file(data_dir::IDENTITY_LINKS_FILE)).await?;
+        *self.data_dir.write().await = data_dir.clone();
+        Ok(())
+    }
+
+    /// Persist the running giveaway so it survives restarts, resuming any saved one
+    pub async fn set_active_giveaway_path(&self, path: impl Into<std::path::PathBuf>) -> GiveawayResult<bool> {
+        self.giveaway_system.set_active_giveaway_path(path).await
+    }
+
+    /// Persist completed giveaways to a JSON file, loading any saved history
+    pub async fn set_giveaway_history_path(&self, path: impl Into<std::path::PathBuf>) -> GiveawayResult<usize> {
+        self.giveaway_system.set_history_path(path).await
+    }
+
+    /// Export completed giveaways as JSON or CSV; a relative `path` is placed under the data directory
+    pub async fn export_giveaway_history(&self, format: crate::bot::giveaways::GiveawayExportFormat, path: &std::path::Path) -> GiveawayResult<usize> {
+        let path = self.data_dir.read().await.resolve(path);
+        self.giveaway_system.export_history(format, &path).await
+    }
+
     /// Get giveaway history
     pub async fn get_giveaway_history(&self, limit: Option<usize>) -> Vec<crate::types::CompletedGiveaway> {
         self.giveaway_system.get_history(limit).await
@@ -941,6 +2415,14 @@ impl ChatBot {
                 error!("Error disconnecting from {}: {}", platform_name, e);
             }
         }
+
+        // Nothing logged so far needs replaying on the next start
+        let message_log = self.message_log.read().await.clone();
+        if let Some(log) = message_log {
+            if let Err(e) = log.checkpoint().await {
+                error!("Failed to checkpoint message log: {:#}", e);
+            }
+        }
         
         info!("Chat bot shutdown complete");
         Ok(())
@@ -1,32 +1,362 @@
-use anyhow::Result;
-use log::{error, info, warn, debug};
-use std::collections::HashMap;
+use log::{error, info, log, warn, debug, Level};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, RwLock};
 
 use crate::types::{
     ChatMessage, SpamFilter, SpamFilterType, ModerationAction, ModerationEscalation,
-    UserMessageHistory, BlacklistPattern, ExemptionLevel, ViolationRecord
+    UserMessageHistory, BlacklistPattern, ExemptionLevel, ViolationRecord, ModerationAllowlist,
+    ModerationError, ModerationResult, WildcardPattern, MatchExplanation, MatchSpan, FilterLogLevel,
 };
+use crate::bot::enhanced_moderation::ReputationRecord;
+use crate::bot::identity::IdentityMap;
 use crate::bot::points::UserPoints;
+use crate::bot::schedule::ActiveSchedule;
+use crate::bot::pattern_matching::decode_embedded_payloads;
+use crate::bot::redaction::LogRedaction;
+use crate::bot::spam_wave::{SpamWaveAlert, SpamWaveConfig, SpamWaveDetector};
+use crate::bot::moderation_metrics::{ModerationMetrics, ModerationMetricsSnapshot};
+
+/// Serializable summary of an active in-memory filter
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct FilterInfo {
+    pub name: String,
+    pub enabled: bool,
+    /// Filter type name, e.g. "ExcessiveCaps" or "Blacklist"
+    pub filter_type: String,
+    /// Type-specific parameters (thresholds, patterns, ...)
+    pub parameters: serde_json::Value,
+    pub exemption_level: ExemptionLevel,
+    pub escalation: ModerationEscalation,
+    pub silent_mode: bool,
+    pub custom_message: Option<String>,
+}
+
+impl From<&SpamFilter> for FilterInfo {
+    fn from(filter: &SpamFilter) -> Self {
+        // SpamFilterType serializes as {"Variant": {params}}
+        let (filter_type, parameters) = match serde_json::to_value(&filter.filter_type) {
+            Ok(serde_json::Value::Object(map)) => map.into_iter().next()
+                .unwrap_or_else(|| ("Unknown".to_string(), serde_json::Value::Null)),
+            _ => ("Unknown".to_string(), serde_json::Value::Null),
+        };
+
+        Self {
+            name: filter.name.clone(),
+            enabled: filter.enabled,
+            filter_type,
+            parameters,
+            exemption_level: filter.exemption_level.clone(),
+            escalation: filter.escalation.clone(),
+            silent_mode: filter.silent_mode,
+            custom_message: filter.custom_message.clone(),
+        }
+    }
+}
+
+/// Candidate blacklist pattern mined from recent violations, for moderator review
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct FilterSuggestion {
+    /// Suggested literal pattern
+    pub pattern: String,
+    /// Number of flagged messages containing the phrase
+    pub frequency: usize,
+    /// One flagged message containing the phrase
+    pub example: String,
+}
+
+/// Phrases must appear in at least this many flagged messages to be suggested
+pub const MIN_SUGGESTION_FREQUENCY: usize = 2;
+
+/// Longest phrase (in words) considered for suggestions
+const MAX_SUGGESTION_WORDS: usize = 3;
+
+/// Common words that never make useful patterns on their own
+const SUGGESTION_STOPWORDS: &[&str] = &[
+    "the", "and", "for", "you", "your", "are", "this", "that", "with", "have", "from", "just",
+    "what", "now", "out", "all", "get", "can", "its", "not", "but", "was", "our", "my", "me",
+    "is", "at", "on", "in", "to", "of", "a", "an", "it", "i", "be", "so", "do", "go", "or",
+];
+
+/// Mine frequent phrases from flagged messages that `is_covered` doesn't already catch
+///
+/// Each phrase is counted once per message. Phrases contained in a longer suggested
+/// phrase with the same frequency are folded into it.
+pub fn suggest_filter_patterns(
+    messages: &[String],
+    is_covered: impl Fn(&str) -> bool,
+    limit: usize,
+) -> Vec<FilterSuggestion> {
+    let mut counts: HashMap<String, (usize, &str)> = HashMap::new();
+
+    for message in messages {
+        let words: Vec<String> = message.split_whitespace()
+            .filter(|w| !w.starts_with('@'))
+            .map(|w| w.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase())
+            .filter(|w| !w.is_empty())
+            .collect();
+
+        let mut seen = HashSet::new();
+        for len in 1..=MAX_SUGGESTION_WORDS {
+            for window in words.windows(len) {
+                // Edges must carry meaning: "free followers", not "get free"
+                let edges = [window[0].as_str(), window[len - 1].as_str()];
+                if edges.iter().any(|w| SUGGESTION_STOPWORDS.contains(w)) {
+                    continue;
+                }
+                let phrase = window.join(" ");
+                if phrase.chars().count() < 4 || !seen.insert(phrase.clone()) {
+                    continue;
+                }
+                counts.entry(phrase).or_insert((0, message.as_str())).0 += 1;
+            }
+        }
+    }
+
+    let candidates: Vec<(String, usize, &str)> = counts.into_iter()
+        .filter(|(phrase, (count, _))| *count >= MIN_SUGGESTION_FREQUENCY && !is_covered(phrase))
+        .map(|(phrase, (count, example))| (phrase, count, example))
+        .collect();
+
+    let mut suggestions: Vec<FilterSuggestion> = candidates.iter()
+        .filter(|(phrase, count, _)| {
+            !candidates.iter().any(|(other, other_count, _)| {
+                other.len() > phrase.len()
+                    && other_count >= count
+                    && format!(" {} ", other).contains(&format!(" {} ", phrase))
+            })
+        })
+        .map(|(phrase, count, example)| FilterSuggestion {
+            pattern: phrase.clone(),
+            frequency: *count,
+            example: example.to_string(),
+        })
+        .collect();
+
+    suggestions.sort_by(|a, b| {
+        b.frequency.cmp(&a.frequency)
+            .then_with(|| b.pattern.len().cmp(&a.pattern.len()))
+            .then_with(|| a.pattern.cmp(&b.pattern))
+    });
+    suggestions.truncate(limit);
+    suggestions
+}
 
 pub struct ModerationSystem {
     pub spam_filters: Arc<RwLock<HashMap<String, SpamFilter>>>,
     pub user_message_history: Arc<RwLock<HashMap<String, UserMessageHistory>>>,
     pub global_enabled: Arc<RwLock<bool>>,
+    pub allowlist: Arc<RwLock<ModerationAllowlist>>,
+    spam_waves: Arc<RwLock<SpamWaveDetector>>,
+    spam_wave_alerts: broadcast::Sender<SpamWaveAlert>,
+    action_cooldown_seconds: Arc<RwLock<u64>>,
+    warning_dedup_seconds: Arc<RwLock<u64>>,
+    filter_matches: Arc<RwLock<HashMap<String, FilterMatchStats>>>,
+    metrics: Arc<RwLock<ModerationMetrics>>,
+    /// Raiding channel per (platform, channel) whose links first-time chatters may post
+    raid_link_exceptions: Arc<RwLock<HashMap<(String, String), String>>>,
+    /// Usernames the bot posts as on each platform (lowercase); its own messages are never moderated
+    bot_accounts: Arc<RwLock<HashMap<String, Vec<String>>>>,
+    /// When each shadow-muted user (by identity key) may be heard again
+    shadow_mutes: Arc<RwLock<HashMap<String, chrono::DateTime<chrono::Utc>>>>,
+    /// Accounts linked as one person; message history, offenses and shadow
+    /// mutes are kept per resolved identity
+    identities: Arc<RwLock<IdentityMap>>,
+    /// Reputation per identity, kept up by enhanced moderation
+    user_reputation: Arc<RwLock<HashMap<String, ReputationRecord>>>,
+    /// How message content appears in moderation log lines
+    log_redaction: LogRedaction,
+}
+
+/// How often a filter has matched since startup
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize)]
+pub struct FilterMatchStats {
+    pub trigger_count: u64,
+    pub last_triggered: Option<chrono::DateTime<chrono::Utc>>,
 }
 
+/// Outcome of a spam filter match
+#[derive(Debug, Clone)]
+pub struct SpamFilterVerdict {
+    pub action: ModerationAction,
+    pub filter_name: String,
+    /// The filter's custom notice, if it has one (and isn't silent)
+    pub notice: Option<ModerationNotice>,
+    /// The filter's configured log level, if it has one
+    pub log_level: Option<FilterLogLevel>,
+    /// Detection confidence when AI patterns took part; rules are certain
+    pub confidence: Option<f64>,
+}
+
+/// The spam filters couldn't all be checked before a message's deadline
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[error("spam filters not finished in time ({checked} of {total} checked)")]
+pub struct FilterBudgetExceeded {
+    pub checked: usize,
+    pub total: usize,
+}
+
+/// Where a blacklist filter matched a message
+struct BlacklistHit<'a> {
+    pattern: &'a BlacklistPattern,
+    /// The message, or the payload decoded from it that matched
+    text: String,
+    span: MatchSpan,
+    /// An exception covers this match, and every other one in the message
+    excepted: bool,
+}
+
+/// A filter's custom moderation notice
+///
+/// The template may use `$(user)`, `$(filter)`, `$(reason)` and `$(duration)`
+/// (timeout length in seconds; for warnings, the timeout a repeat offense brings).
+/// Plain messages without variables only replace warnings, as they always have;
+/// templates also announce timeouts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModerationNotice {
+    pub template: String,
+    pub filter_name: String,
+    pub reason: String,
+    pub repeat_timeout_seconds: Option<u64>,
+}
+
+impl ModerationNotice {
+    /// Fill in the template's variables for `action` taken against `message`'s author
+    pub fn render(&self, message: &ChatMessage, action: &ModerationAction) -> String {
+        let duration = match action {
+            ModerationAction::TimeoutUser { duration_seconds } => Some(*duration_seconds),
+            _ => self.repeat_timeout_seconds,
+        };
+        self.template
+            .replace("$(user)", &message.username)
+            .replace("$(filter)", &self.filter_name)
+            .replace("$(reason)", &self.reason)
+            .replace("$(duration)", &duration.unwrap_or(0).to_string())
+    }
+
+    /// Whether the notice uses any variables (and so is meant for timeouts too)
+    pub fn is_template(&self) -> bool {
+        ["$(user)", "$(filter)", "$(reason)", "$(duration)"].iter().any(|variable| self.template.contains(variable))
+    }
+}
+
+/// Short human-readable reason a filter type blocks a message
+fn filter_reason(filter_type: &SpamFilterType) -> &'static str {
+    match filter_type {
+        SpamFilterType::ExcessiveCaps { .. } => "excessive caps",
+        SpamFilterType::LinkBlocking { .. } => "posting links",
+        SpamFilterType::RepeatedMessages { .. } => "repeated messages",
+        SpamFilterType::MessageLength { .. } => "message too long",
+        SpamFilterType::ExcessiveEmotes { .. } => "too many emotes",
+        SpamFilterType::SymbolSpam { .. } => "symbol spam",
+        SpamFilterType::RateLimit { .. } => "sending messages too fast",
+        SpamFilterType::Blacklist { .. } => "blocked term",
+    }
+}
+
+/// Default window after a timeout/warning in which repeats of it are only logged
+pub const DEFAULT_ACTION_COOLDOWN_SECONDS: u64 = 5;
+
+/// Default window in which a user isn't warned twice by the same filter
+pub const DEFAULT_WARNING_DEDUP_SECONDS: u64 = 30;
+
 impl ModerationSystem {
     pub fn new() -> Self {
+        let (spam_wave_alerts, _) = broadcast::channel(32);
         Self {
+            spam_waves: Arc::new(RwLock::new(SpamWaveDetector::default())),
+            metrics: Arc::new(RwLock::new(ModerationMetrics::default())),
+            spam_wave_alerts,
+            action_cooldown_seconds: Arc::new(RwLock::new(DEFAULT_ACTION_COOLDOWN_SECONDS)),
+            warning_dedup_seconds: Arc::new(RwLock::new(DEFAULT_WARNING_DEDUP_SECONDS)),
+            filter_matches: Arc::new(RwLock::new(HashMap::new())),
+            raid_link_exceptions: Arc::new(RwLock::new(HashMap::new())),
+            bot_accounts: Arc::new(RwLock::new(HashMap::new())),
+            shadow_mutes: Arc::new(RwLock::new(HashMap::new())),
+            identities: Arc::new(RwLock::new(IdentityMap::new())),
+            user_reputation: Arc::new(RwLock::new(HashMap::new())),
+            log_redaction: LogRedaction::default(),
             spam_filters: Arc::new(RwLock::new(HashMap::new())),
             user_message_history: Arc::new(RwLock::new(HashMap::new())),
             global_enabled: Arc::new(RwLock::new(true)),
+            allowlist: Arc::new(RwLock::new(ModerationAllowlist::default())),
+        }
+    }
+
+    /// Share identity links with other systems, so linked accounts are one user everywhere
+    pub fn with_identities(mut self, identities: Arc<RwLock<IdentityMap>>) -> Self {
+        self.identities = identities;
+        self
+    }
+
+    pub fn identities(&self) -> Arc<RwLock<IdentityMap>> {
+        Arc::clone(&self.identities)
+    }
+
+    /// Share the log redaction setting with other systems that log message content
+    pub fn with_log_redaction(mut self, log_redaction: LogRedaction) -> Self {
+        self.log_redaction = log_redaction;
+        self
+    }
+
+    pub fn log_redaction(&self) -> LogRedaction {
+        self.log_redaction.clone()
+    }
+
+    /// Key per-user state is kept under: the account's linked identity, case-folded
+    pub async fn identity_key(&self, platform: &str, username: &str) -> String {
+        self.identities.read().await.resolve(platform, username)
+    }
+
+    pub fn user_reputation(&self) -> Arc<RwLock<HashMap<String, ReputationRecord>>> {
+        Arc::clone(&self.user_reputation)
+    }
+
+    /// Fold one identity's message history, offenses, shadow mute and
+    /// reputation into another's, once their accounts are linked or an
+    /// identity's canonical account changes
+    pub async fn merge_identity(&self, from: &str, into: &str) {
+        {
+            let mut history = self.user_message_history.write().await;
+            if let Some(merged) = history.remove(from) {
+                let target = history.entry(into.to_string())
+                    .or_insert_with(|| UserMessageHistory::new(into.to_string()));
+                target.messages.extend(merged.messages);
+                target.messages.sort_by_key(|(timestamp, _)| *timestamp);
+                target.last_warning = target.last_warning.max(merged.last_warning);
+                target.last_timeout = target.last_timeout.max(merged.last_timeout);
+                target.violation_count += merged.violation_count;
+
+                let violations = &mut target.violation_history;
+                violations.violations.extend(merged.violation_history.violations);
+                violations.violations.sort_by_key(|v| v.timestamp);
+                violations.total_violations += merged.violation_history.total_violations;
+                violations.last_violation = violations.last_violation.max(merged.violation_history.last_violation);
+            }
+        }
+
+        {
+            let mut mutes = self.shadow_mutes.write().await;
+            if let Some(until) = mutes.remove(from) {
+                let target = mutes.entry(into.to_string()).or_insert(until);
+                *target = (*target).max(until);
+            }
+        }
+
+        let mut reputation = self.user_reputation.write().await;
+        if let Some(merged) = reputation.remove(from) {
+            match reputation.get_mut(into) {
+                Some(record) => record.merge(&merged),
+                None => {
+                    reputation.insert(into.to_string(), merged);
+                }
+            }
         }
     }
 
     /// Add a spam filter with default configuration
-    pub async fn add_spam_filter(&self, filter_type: SpamFilterType) -> Result<()> {
+    pub async fn add_spam_filter(&self, filter_type: SpamFilterType) -> ModerationResult<()> {
         let filter_name = Self::generate_filter_name(&filter_type);
         let filter = SpamFilter {
             filter_type: filter_type.clone(),
@@ -36,6 +366,9 @@ impl ModerationSystem {
             silent_mode: false,
             custom_message: None,
             name: filter_name.clone(),
+            grace_seconds: None,
+            log_level: None,
+            schedule: None,
         };
 
         self.spam_filters.write().await.insert(filter_name.clone(), filter);
@@ -52,7 +385,7 @@ impl ModerationSystem {
         exemption_level: ExemptionLevel,
         silent_mode: bool,
         custom_message: Option<String>,
-    ) -> Result<()> {
+    ) -> ModerationResult<()> {
         let filter = SpamFilter {
             filter_type: filter_type.clone(),
             enabled: true,
@@ -61,6 +394,9 @@ impl ModerationSystem {
             silent_mode,
             custom_message,
             name: name.clone(),
+            grace_seconds: None,
+            log_level: None,
+            schedule: None,
         };
 
         self.spam_filters.write().await.insert(name.clone(), filter);
@@ -78,7 +414,7 @@ impl ModerationSystem {
         exemption_level: ExemptionLevel,
         timeout_seconds: u64,
         custom_message: Option<String>,
-    ) -> Result<()> {
+    ) -> ModerationResult<()> {
         let mut blacklist_patterns = Vec::new();
         
         for pattern_str in patterns {
@@ -93,7 +429,7 @@ impl ModerationSystem {
                 }
             } else if pattern_str.contains('*') {
                 // Wildcard pattern
-                BlacklistPattern::Wildcard(pattern_str)
+                BlacklistPattern::Wildcard(WildcardPattern::new(pattern_str))
             } else {
                 // Literal pattern
                 BlacklistPattern::Literal(pattern_str)
@@ -114,6 +450,7 @@ impl ModerationSystem {
             patterns: blacklist_patterns,
             case_sensitive,
             whole_words_only,
+            except_patterns: Vec::new(),
         };
 
         self.add_spam_filter_advanced(
@@ -126,6 +463,63 @@ impl ModerationSystem {
         ).await
     }
 
+    /// Replace a filter's escalation, e.g. with a fixed action
+    pub async fn set_filter_escalation(&self, filter_name: &str, escalation: ModerationEscalation) -> ModerationResult<()> {
+        let mut filters = self.spam_filters.write().await;
+        let filter = filters.get_mut(filter_name)
+            .ok_or_else(|| ModerationError::FilterNotFound(filter_name.to_string()))?;
+        filter.escalation = escalation;
+        Ok(())
+    }
+
+    /// Warn on a filter's first match and only action a repeat within `grace_seconds` (`None` disables)
+    pub async fn set_filter_grace(&self, filter_name: &str, grace_seconds: Option<u64>) -> ModerationResult<()> {
+        let mut filters = self.spam_filters.write().await;
+        let filter = filters.get_mut(filter_name)
+            .ok_or_else(|| ModerationError::FilterNotFound(filter_name.to_string()))?;
+        filter.grace_seconds = grace_seconds;
+        Ok(())
+    }
+
+    /// Log a filter's matches at `log_level` instead of the usual levels (`None` restores them)
+    pub async fn set_filter_log_level(&self, filter_name: &str, log_level: Option<FilterLogLevel>) -> ModerationResult<()> {
+        let mut filters = self.spam_filters.write().await;
+        let filter = filters.get_mut(filter_name)
+            .ok_or_else(|| ModerationError::FilterNotFound(filter_name.to_string()))?;
+        filter.log_level = log_level;
+        Ok(())
+    }
+
+    /// Limit a filter to certain hours and days (`None` = always active)
+    pub async fn set_filter_schedule(&self, filter_name: &str, schedule: Option<ActiveSchedule>) -> ModerationResult<()> {
+        let mut filters = self.spam_filters.write().await;
+        let filter = filters.get_mut(filter_name)
+            .ok_or_else(|| ModerationError::FilterNotFound(filter_name.to_string()))?;
+        filter.schedule = schedule;
+        Ok(())
+    }
+
+    /// Replace a blacklist filter's exception patterns
+    pub async fn set_blacklist_exceptions(&self, filter_name: &str, exceptions: Vec<BlacklistPattern>) -> ModerationResult<()> {
+        let mut filters = self.spam_filters.write().await;
+        let filter = filters.get_mut(filter_name)
+            .ok_or_else(|| ModerationError::FilterNotFound(filter_name.to_string()))?;
+        match &mut filter.filter_type {
+            SpamFilterType::Blacklist { except_patterns, .. } => {
+                info!("Filter '{}' has {} exception patterns", filter_name, exceptions.len());
+                *except_patterns = exceptions;
+                Ok(())
+            }
+            _ => Err(ModerationError::NotABlacklist(filter_name.to_string())),
+        }
+    }
+
+    /// Replace the allowlist of protected phrases
+    pub async fn set_allowlist(&self, allowlist: ModerationAllowlist) {
+        info!("Moderation allowlist updated with {} entries", allowlist.entries.len());
+        *self.allowlist.write().await = allowlist;
+    }
+
     /// Enable or disable all spam filters
     pub async fn set_spam_protection_enabled(&self, enabled: bool) {
         *self.global_enabled.write().await = enabled;
@@ -133,28 +527,42 @@ impl ModerationSystem {
     }
 
     /// Enable or disable a specific filter
-    pub async fn set_filter_enabled(&self, filter_name: &str, enabled: bool) -> Result<()> {
+    pub async fn set_filter_enabled(&self, filter_name: &str, enabled: bool) -> ModerationResult<()> {
         let mut filters = self.spam_filters.write().await;
         if let Some(filter) = filters.get_mut(filter_name) {
             filter.enabled = enabled;
             info!("Filter '{}' {}", filter_name, if enabled { "enabled" } else { "disabled" });
             Ok(())
         } else {
-            Err(anyhow::anyhow!("Filter '{}' not found", filter_name))
+            Err(ModerationError::FilterNotFound(filter_name.to_string()))
         }
     }
 
     /// Remove a spam filter
-    pub async fn remove_filter(&self, filter_name: &str) -> Result<()> {
+    pub async fn remove_filter(&self, filter_name: &str) -> ModerationResult<()> {
         let mut filters = self.spam_filters.write().await;
         if filters.remove(filter_name).is_some() {
+            self.filter_matches.write().await.remove(filter_name);
             info!("Removed filter '{}'", filter_name);
             Ok(())
         } else {
-            Err(anyhow::anyhow!("Filter '{}' not found", filter_name))
+            Err(ModerationError::FilterNotFound(filter_name.to_string()))
         }
     }
 
+    /// Replace the whole filter set with `staged`'s in one step, so checks running
+    /// meanwhile see either the old set or the new one, never a partial or empty one.
+    /// Match counts are kept for filters that are still there.
+    pub async fn replace_filters_from(&self, staged: &ModerationSystem) {
+        let filters = staged.spam_filters.read().await.clone();
+        let count = filters.len();
+        let mut live = self.spam_filters.write().await;
+        self.filter_matches.write().await.retain(|name, _| filters.contains_key(name));
+        *live = filters;
+        drop(live);
+        info!("Swapped in a new set of {} filters", count);
+    }
+
     /// List all filters
     pub async fn list_filters(&self) -> Vec<(String, bool)> {
         let filters = self.spam_filters.read().await;
@@ -163,6 +571,243 @@ impl ModerationSystem {
             .collect()
     }
 
+    /// Record a violation detected by AI patterns in the user's history
+    pub async fn record_ai_violation(
+        &self,
+        message: &ChatMessage,
+        patterns: &[String],
+        action: ModerationAction,
+        confidence: f64,
+    ) {
+        let user_key = self.identity_key(&message.platform, &message.username).await;
+        let mut history_guard = self.user_message_history.write().await;
+        let user_history = history_guard.entry(user_key.clone())
+            .or_insert_with(|| UserMessageHistory::new(user_key));
+
+        user_history.violation_history.add_violation(ViolationRecord {
+            filter_name: patterns.join(","),
+            timestamp: chrono::Utc::now(),
+            action_taken: action,
+            message_content: message.content.clone(),
+            confidence: Some(confidence),
+            grace_warning: false,
+            appealed: false,
+        });
+    }
+
+    /// Most recent violation recorded for a user
+    pub async fn get_last_violation(&self, platform: &str, username: &str) -> Option<ViolationRecord> {
+        let user_key = self.identity_key(platform, username).await;
+        let history = self.user_message_history.read().await;
+        // Chat usernames are typed by mods, so fall back to a case-insensitive match
+        let user_history = history.get(&user_key).or_else(|| {
+            history.iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(&user_key))
+                .map(|(_, h)| h)
+        })?;
+        user_history.violation_history.violations.iter()
+            .max_by_key(|v| v.timestamp)
+            .cloned()
+    }
+
+    /// Mark a user's most recent violation as appealed. Returns it, and whether
+    /// this was its first appeal.
+    pub async fn appeal_last_violation(&self, platform: &str, username: &str) -> Option<(ViolationRecord, bool)> {
+        let user_key = self.identity_key(platform, username).await;
+        let mut history = self.user_message_history.write().await;
+        let key = if history.contains_key(&user_key) {
+            user_key
+        } else {
+            history.keys().find(|key| key.eq_ignore_ascii_case(&user_key))?.clone()
+        };
+        let violation = history.get_mut(&key)?.violation_history.violations.iter_mut()
+            .max_by_key(|v| v.timestamp)?;
+        let first_appeal = !violation.appealed;
+        violation.appealed = true;
+        Some((violation.clone(), first_appeal))
+    }
+
+    /// Suggest blacklist patterns from phrases common to recent violations but not yet blacklisted
+    pub async fn suggest_filters(&self, limit: usize) -> Vec<FilterSuggestion> {
+        let messages: Vec<String> = {
+            let history = self.user_message_history.read().await;
+            history.values()
+                .flat_map(|h| h.violation_history.violations.iter())
+                .map(|v| v.message_content.clone())
+                .collect()
+        };
+
+        let filters = self.spam_filters.read().await;
+        let blacklists: Vec<_> = filters.values()
+            .filter(|f| f.enabled)
+            .filter_map(|f| match &f.filter_type {
+                SpamFilterType::Blacklist { patterns, case_sensitive, whole_words_only, .. } => {
+                    Some((patterns, *case_sensitive, *whole_words_only))
+                }
+                _ => None,
+            })
+            .collect();
+
+        suggest_filter_patterns(&messages, |phrase| {
+            blacklists.iter().any(|(patterns, case_sensitive, whole_words_only)| {
+                Self::check_blacklist(phrase, patterns, *case_sensitive, *whole_words_only, &self.log_redaction)
+            })
+        }, limit)
+    }
+
+    /// Write filter suggestions to a JSON file for review
+    pub async fn export_filter_suggestions(&self, path: &Path, limit: usize) -> ModerationResult<usize> {
+        let suggestions = self.suggest_filters(limit).await;
+        let json = serde_json::to_string_pretty(&suggestions)?;
+        tokio::fs::write(path, json).await?;
+        info!("Exported {} filter suggestions to {}", suggestions.len(), path.display());
+        Ok(suggestions.len())
+    }
+
+    /// Set how long after a timeout (or warning) further timeouts (or warnings) for the same user
+    /// are only logged, so rapid messages don't stack timeouts before the first takes effect (0 disables)
+    pub async fn set_action_cooldown(&self, seconds: u64) {
+        *self.action_cooldown_seconds.write().await = seconds;
+        info!("Moderation action cooldown set to {}s", seconds);
+    }
+
+    /// Set how long after warning a user, further warnings from the same filter are only
+    /// logged, so a user tripping a warn-only filter rapidly gets one warning (0 disables)
+    pub async fn set_warning_dedup_window(&self, seconds: u64) {
+        *self.warning_dedup_seconds.write().await = seconds;
+        info!("Warning deduplication window set to {}s", seconds);
+    }
+
+    /// Set the account the bot posts as on a platform so its own messages (command
+    /// output echoed back, for example) skip the filters; `None` moderates it like anyone else
+    pub async fn set_bot_account(&self, platform: &str, username: Option<&str>) {
+        let mut accounts = self.bot_accounts.write().await;
+        match username.map(str::trim).filter(|u| !u.is_empty()) {
+            Some(username) => {
+                debug!("Messages from {} on {} are the bot's own and won't be moderated", username, platform);
+                accounts.insert(platform.to_string(), vec![username.to_lowercase()]);
+            }
+            None => {
+                accounts.remove(platform);
+            }
+        }
+    }
+
+    /// Also treat `username` as the bot on a platform, for extra sending accounts
+    pub async fn add_bot_account(&self, platform: &str, username: &str) {
+        let username = username.trim().to_lowercase();
+        if username.is_empty() {
+            return;
+        }
+        let mut accounts = self.bot_accounts.write().await;
+        let names = accounts.entry(platform.to_string()).or_default();
+        if !names.contains(&username) {
+            debug!("Messages from {} on {} are the bot's own and won't be moderated", username, platform);
+            names.push(username);
+        }
+    }
+
+    /// Whether a message was posted by the bot's own account
+    pub async fn is_from_bot(&self, message: &ChatMessage) -> bool {
+        self.bot_accounts.read().await
+            .get(&message.platform)
+            .is_some_and(|accounts| accounts.iter().any(|account| message.username.eq_ignore_ascii_case(account)))
+    }
+
+    /// Ignore a user's messages for `duration_seconds` without telling them or
+    /// the platform. Works everywhere, since nothing is sent to the platform.
+    pub async fn shadow_mute(&self, platform: &str, username: &str, duration_seconds: u64) {
+        let until = chrono::Utc::now() + chrono::Duration::seconds(duration_seconds as i64);
+        let key = self.identity_key(platform, username).await;
+        self.shadow_mutes.write().await.insert(key, until);
+        info!("Shadow muted {} on {} until {}", username, platform, until.format("%H:%M:%S"));
+    }
+
+    /// Lift a shadow mute early; returns whether the user was muted
+    pub async fn lift_shadow_mute(&self, platform: &str, username: &str) -> bool {
+        let key = self.identity_key(platform, username).await;
+        self.shadow_mutes.write().await.remove(&key).is_some()
+    }
+
+    /// Whether `message`'s author is shadow muted at `now`; expired mutes are cleared
+    pub async fn is_shadow_muted_at(&self, message: &ChatMessage, now: chrono::DateTime<chrono::Utc>) -> bool {
+        let key = self.identity_key(&message.platform, &message.username).await;
+        let Some(until) = self.shadow_mutes.read().await.get(&key).copied() else {
+            return false;
+        };
+        if now < until {
+            return true;
+        }
+        self.shadow_mutes.write().await.remove(&key);
+        debug!("Shadow mute on {} expired", message.username);
+        false
+    }
+
+    pub async fn is_shadow_muted(&self, message: &ChatMessage) -> bool {
+        self.is_shadow_muted_at(message, chrono::Utc::now()).await
+    }
+
+    /// Drop mutes that ended before `now`, including those of users who never
+    /// chatted again; returns how many were dropped
+    pub async fn prune_expired_shadow_mutes(&self, now: chrono::DateTime<chrono::Utc>) -> usize {
+        let mut mutes = self.shadow_mutes.write().await;
+        let before = mutes.len();
+        mutes.retain(|_, until| now < *until);
+        before - mutes.len()
+    }
+
+    /// During a raid, let first-time chatters in `channel` post links to the raiding
+    /// channel without tripping link filters (`None` restores normal rules)
+    pub async fn set_raid_link_exception(&self, platform: &str, channel: &str, raiding_channel: Option<String>) {
+        let key = (platform.to_string(), channel.to_lowercase());
+        let mut exceptions = self.raid_link_exceptions.write().await;
+        match raiding_channel {
+            Some(raiding_channel) => {
+                info!("Allowing first-message links to {} in {}#{} during raid", raiding_channel, platform, channel);
+                exceptions.insert(key, raiding_channel.to_lowercase());
+            }
+            None => {
+                exceptions.remove(&key);
+            }
+        }
+    }
+
+    /// Set when repeated blocks by one filter are aggregated into a spam wave alert
+    pub async fn set_spam_wave_config(&self, config: SpamWaveConfig) {
+        self.spam_waves.write().await.set_config(config);
+    }
+
+    /// Subscribe to aggregated spam wave alerts (e.g. to forward to webhooks)
+    pub fn subscribe_spam_waves(&self) -> broadcast::Receiver<SpamWaveAlert> {
+        self.spam_wave_alerts.subscribe()
+    }
+
+    /// Whether a spam wave is in progress on any filter
+    pub async fn is_spam_wave_active(&self) -> bool {
+        self.spam_waves.read().await.is_active()
+    }
+
+    /// Emit one alert for each spam wave that has gone quiet
+    pub async fn flush_spam_waves(&self) -> Vec<SpamWaveAlert> {
+        let alerts = self.spam_waves.write().await.take_finished(chrono::Utc::now());
+        for alert in &alerts {
+            warn!("Spam wave: {} messages blocked by filter '{}' over {}s (started {})",
+                  alert.blocked_count, alert.filter_name, alert.duration_seconds(),
+                  alert.started_at.format("%H:%M:%S UTC"));
+            // No subscribers is fine; the log line is the baseline sink
+            let _ = self.spam_wave_alerts.send(alert.clone());
+        }
+        alerts
+    }
+
+    /// Full definitions of the filters currently loaded, sorted by name
+    pub async fn get_active_filters(&self) -> Vec<FilterInfo> {
+        let filters = self.spam_filters.read().await;
+        let mut active: Vec<FilterInfo> = filters.values().map(FilterInfo::from).collect();
+        active.sort_by(|a, b| a.name.cmp(&b.name));
+        active
+    }
+
     /// Clear message history for all users (useful for cleanup)
     pub async fn clear_message_history(&self) {
         self.user_message_history.write().await.clear();
@@ -175,17 +820,69 @@ impl ModerationSystem {
         message: &ChatMessage,
         user_points: Option<&UserPoints>
     ) -> Option<ModerationAction> {
+        self.evaluate_spam_filters(message, user_points).await.map(|verdict| verdict.action)
+    }
+
+    /// Like `check_spam_filters`, but also reports which filter matched and its custom notice
+    pub async fn evaluate_spam_filters(
+        &self,
+        message: &ChatMessage,
+        user_points: Option<&UserPoints>
+    ) -> Option<SpamFilterVerdict> {
+        self.evaluate_spam_filters_within(message, user_points, None).await.ok().flatten()
+    }
+
+    /// Like `evaluate_spam_filters`, but gives up once `deadline` passes, so one
+    /// message can't hold its stream on expensive patterns. The deadline is checked
+    /// between filters; a message that runs out of time hasn't been fully checked
+    pub async fn evaluate_spam_filters_within(
+        &self,
+        message: &ChatMessage,
+        user_points: Option<&UserPoints>,
+        deadline: Option<tokio::time::Instant>,
+    ) -> Result<Option<SpamFilterVerdict>, FilterBudgetExceeded> {
         if !*self.global_enabled.read().await {
-            return None;
+            return Ok(None);
+        }
+        if self.is_from_bot(message).await {
+            return Ok(None);
         }
 
+        let verdict = self.match_spam_filters(message, user_points, deadline).await?;
+        self.metrics.write().await.record(verdict.as_ref().map(|v| v.filter_name.as_str()), chrono::Utc::now());
+        Ok(verdict)
+    }
+
+    async fn match_spam_filters(
+        &self,
+        message: &ChatMessage,
+        user_points: Option<&UserPoints>,
+        deadline: Option<tokio::time::Instant>,
+    ) -> Result<Option<SpamFilterVerdict>, FilterBudgetExceeded> {
         let filters = self.spam_filters.read().await;
+        let allowlist = self.allowlist.read().await;
+        let is_allowlisted = allowlist.is_allowed(message);
         
-        for (filter_name, filter) in filters.iter() {
+        let now = chrono::Utc::now();
+        for (checked, (filter_name, filter)) in filters.iter().enumerate() {
+            if deadline.is_some_and(|deadline| tokio::time::Instant::now() >= deadline) {
+                return Err(FilterBudgetExceeded { checked, total: filters.len() });
+            }
             if !filter.enabled {
                 continue;
             }
 
+            // Outside its active hours or days
+            if filter.schedule.as_ref().is_some_and(|schedule| !schedule.is_active(now)) {
+                continue;
+            }
+
+            // Protected phrases skip blacklists (and optionally every filter)
+            if is_allowlisted && allowlist.bypasses(&filter.filter_type) {
+                debug!("Skipping filter '{}' for allowlisted message", filter_name);
+                continue;
+            }
+
             // Check exemptions
             if filter.exemption_level.is_exempt(message, user_points) {
                 continue;
@@ -193,61 +890,223 @@ impl ModerationSystem {
 
             // Check against the specific filter type
             if self.violates_filter(message, &filter.filter_type).await {
-                info!("Message from {} flagged by filter '{}': {}", 
-                      message.username, filter_name, message.content);
+                {
+                    let mut matches = self.filter_matches.write().await;
+                    let stats = matches.entry(filter_name.clone()).or_default();
+                    stats.trigger_count += 1;
+                    stats.last_triggered = Some(chrono::Utc::now());
+                }
+
+                // During a spam wave individual blocks are summarized by flush_spam_waves
+                let log_level = filter.log_level.map(FilterLogLevel::level);
+                if self.spam_waves.write().await.record_block(filter_name, chrono::Utc::now()) {
+                    log!(log_level.unwrap_or(Level::Debug),
+                         "Message from {} flagged by filter '{}' (spam wave): {}",
+                         message.username, filter_name, self.log_redaction.redact(&message.content));
+                } else {
+                    log!(log_level.unwrap_or(Level::Info), "Message from {} flagged by filter '{}': {}",
+                         message.username, filter_name, self.log_redaction.redact(&message.content));
+                }
                 
                 // Determine escalation level
-                let user_key = format!("{}:{}", message.platform, message.username);
+                let user_key = self.identity_key(&message.platform, &message.username).await;
                 let mut history_guard = self.user_message_history.write().await;
                 let user_history = history_guard.entry(user_key.clone())
                     .or_insert_with(|| UserMessageHistory::new(user_key));
                 
+                let now = chrono::Utc::now();
+                // With a grace period, only a second match inside the window is actioned
+                let grace_warning = filter.grace_seconds.is_some_and(|grace| {
+                    let window_start = now - chrono::Duration::seconds(grace as i64);
+                    !user_history.violation_history.violations.iter()
+                        .any(|v| v.filter_name == *filter_name && v.timestamp >= window_start)
+                });
                 let is_repeat = user_history.violation_history
                     .is_repeat_offense(filter_name, filter.escalation.offense_window_seconds);
                 
                 // Choose action based on escalation
-                let action = if is_repeat {
+                let action = if grace_warning {
+                    debug!("First match of '{}' for {} within its grace period, warning only", filter_name, message.username);
+                    // Worded like the filter's own warning; a custom message replaces it below
+                    let warning = match &filter.escalation.first_offense {
+                        ModerationAction::WarnUser { message } => message.clone(),
+                        _ => format!("Please follow chat rules ({})", filter_reason(&filter.filter_type)),
+                    };
+                    ModerationAction::WarnUser { message: warning }
+                } else if is_repeat {
                     filter.escalation.repeat_offense.clone()
                 } else {
                     filter.escalation.first_offense.clone()
                 };
+
+                // Don't stack a timeout on one that hasn't taken effect yet, or warn a user
+                // who was just warned or timed out; escalating a warning to a timeout still applies
+                let cooldown = chrono::Duration::seconds(*self.action_cooldown_seconds.read().await as i64);
+                let recent = |issued: Option<chrono::DateTime<chrono::Utc>>| issued.is_some_and(|t| now - t < cooldown);
+                let dedup_window = chrono::Duration::seconds(*self.warning_dedup_seconds.read().await as i64);
+                let warned_by_filter = user_history.violation_history.violations.iter().any(|v| {
+                    v.filter_name == *filter_name
+                        && matches!(v.action_taken, ModerationAction::WarnUser { .. })
+                        && now - v.timestamp < dedup_window
+                });
+                let action = match action {
+                    ModerationAction::TimeoutUser { .. } if recent(user_history.last_timeout) => {
+                        debug!("Suppressing timeout for {}: already timed out within cooldown", message.username);
+                        ModerationAction::LogOnly
+                    }
+                    ModerationAction::WarnUser { .. } if recent(user_history.last_timeout) || recent(user_history.last_warning) => {
+                        debug!("Suppressing warning for {}: already actioned within cooldown", message.username);
+                        ModerationAction::LogOnly
+                    }
+                    ModerationAction::WarnUser { .. } if warned_by_filter => {
+                        debug!("Suppressing repeat '{}' warning for {}: warned within the last {}s",
+                               filter_name, message.username, dedup_window.num_seconds());
+                        ModerationAction::LogOnly
+                    }
+                    ModerationAction::TimeoutUser { .. } => {
+                        user_history.last_timeout = Some(now);
+                        action
+                    }
+                    ModerationAction::WarnUser { .. } => {
+                        user_history.last_warning = Some(now);
+                        action
+                    }
+                    other => other,
+                };
                 
                 // Record violation
                 let violation = ViolationRecord {
                     filter_name: filter_name.clone(),
-                    timestamp: chrono::Utc::now(),
+                    timestamp: now,
                     action_taken: action.clone(),
                     message_content: message.content.clone(),
+                    confidence: None,
+                    grace_warning,
+                    appealed: false,
                 };
                 user_history.violation_history.add_violation(violation);
                 
                 // Override message for custom responses
-                let final_action = if let Some(ref custom_msg) = filter.custom_message {
-                    match action {
-                        ModerationAction::WarnUser { .. } => {
-                            ModerationAction::WarnUser { message: custom_msg.clone() }
-                        }
-                        other => other,
+                let notice = filter.custom_message.as_ref().map(|template| ModerationNotice {
+                    template: template.clone(),
+                    filter_name: filter_name.clone(),
+                    reason: filter_reason(&filter.filter_type).to_string(),
+                    repeat_timeout_seconds: match filter.escalation.repeat_offense {
+                        ModerationAction::TimeoutUser { duration_seconds } => Some(duration_seconds),
+                        _ => None,
+                    },
+                });
+                let final_action = match (&notice, action) {
+                    (Some(notice), action @ ModerationAction::WarnUser { .. }) => {
+                        ModerationAction::WarnUser { message: notice.render(message, &action) }
                     }
-                } else {
-                    action
+                    (_, other) => other,
                 };
                 
                 // Handle silent mode
-                if filter.silent_mode {
-                    match final_action {
-                        ModerationAction::WarnUser { .. } => {
-                            return Some(ModerationAction::LogOnly);
-                        }
-                        other => return Some(other),
+                let final_action = match final_action {
+                    ModerationAction::WarnUser { .. } if filter.silent_mode => ModerationAction::LogOnly,
+                    other => other,
+                };
+                return Ok(Some(SpamFilterVerdict {
+                    action: final_action,
+                    filter_name: filter_name.clone(),
+                    notice: notice.filter(|_| !filter.silent_mode),
+                    log_level: filter.log_level,
+                    confidence: None,
+                }));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Every enabled filter `message`'s text trips, with where it matched.
+    /// Exemptions, schedules and per-user history (repeats, rate limits) are
+    /// left out: this answers what a filter would catch in the text itself.
+    pub async fn explain_message(&self, message: &ChatMessage) -> Vec<MatchExplanation> {
+        let filters = self.spam_filters.read().await;
+        let mut explanations = Vec::new();
+        for (filter_name, filter) in filters.iter().filter(|(_, filter)| filter.enabled) {
+            match &filter.filter_type {
+                SpamFilterType::Blacklist { patterns, case_sensitive, whole_words_only, except_patterns } => {
+                    if let Some(hit) = Self::find_blacklist_hit(
+                        &message.content, patterns, except_patterns, *case_sensitive, *whole_words_only, &self.log_redaction) {
+                        explanations.push(MatchExplanation {
+                            pattern: Some(hit.pattern.source().to_string()),
+                            span: Some(hit.span),
+                            excepted: hit.excepted,
+                            ..MatchExplanation::new(filter_name.clone(), hit.text)
+                        });
                     }
-                } else {
-                    return Some(final_action);
+                }
+                SpamFilterType::RepeatedMessages { .. } | SpamFilterType::RateLimit { .. } => {}
+                filter_type => {
+                    if self.violates_filter(message, filter_type).await {
+                        explanations.push(MatchExplanation::new(filter_name.clone(), message.content.clone()));
+                    }
+                }
+            }
+        }
+        explanations.sort_by(|a, b| a.filter.cmp(&b.filter));
+        explanations
+    }
+
+    /// First blacklist match in `content` (or a payload decoded from it) that no
+    /// exception covers, else the first excepted one. An exception only rescues
+    /// the matches whose surrounding chunk of text it overlaps, so an allowed
+    /// link can't carry a blocked one through.
+    fn find_blacklist_hit<'a>(
+        content: &str,
+        patterns: &'a [BlacklistPattern],
+        except_patterns: &[BlacklistPattern],
+        case_sensitive: bool,
+        whole_words_only: bool,
+        log_redaction: &LogRedaction,
+    ) -> Option<BlacklistHit<'a>> {
+        let search = |text: &str| {
+            let exceptions: Vec<MatchSpan> = except_patterns.iter()
+                .filter(|p| p.matches(text, case_sensitive, whole_words_only))
+                .flat_map(|p| p.find_all(text, case_sensitive, whole_words_only))
+                .collect();
+            let mut first_excepted = None;
+            for pattern in patterns.iter().filter(|p| p.matches(text, case_sensitive, whole_words_only)) {
+                for span in pattern.find_all(text, case_sensitive, whole_words_only) {
+                    let chunk = Self::surrounding_chunk(text, &span);
+                    let excepted = exceptions.iter().any(|e| e.start < chunk.end && chunk.start < e.end);
+                    let hit = BlacklistHit { pattern, text: text.to_string(), span, excepted };
+                    if !excepted {
+                        return Some(hit);
+                    }
+                    first_excepted.get_or_insert(hit);
+                }
+            }
+            first_excepted
+        };
+
+        let mut hit = search(content);
+        if crate::ai::ENCODED_CONTENT_SCANNING && hit.as_ref().is_none_or(|hit| hit.excepted) {
+            for decoded in decode_embedded_payloads(content) {
+                match search(&decoded) {
+                    Some(decoded_hit) if !decoded_hit.excepted => return Some(decoded_hit),
+                    Some(decoded_hit) if hit.is_none() => hit = Some(decoded_hit),
+                    _ => {}
                 }
             }
         }
+        if hit.as_ref().is_some_and(|hit| hit.excepted) {
+            debug!("Blacklist match excepted for '{}'", log_redaction.redact(content));
+        }
+        hit
+    }
 
-        None
+    /// `span` widened to the whitespace around it, e.g. the whole link a
+    /// pattern matched part of
+    fn surrounding_chunk(text: &str, span: &MatchSpan) -> std::ops::Range<usize> {
+        let start = text[..span.start].rfind(char::is_whitespace)
+            .map_or(0, |i| i + text[i..].chars().next().map_or(1, char::len_utf8));
+        let end = text[span.end..].find(char::is_whitespace).map_or(text.len(), |i| span.end + i);
+        start..end
     }
 
     /// Check if a message violates a specific filter type
@@ -259,6 +1118,10 @@ impl ModerationSystem {
             SpamFilterType::LinkBlocking { allow_mods, whitelist } => {
                 if *allow_mods && message.is_mod {
                     false
+                } else if let Some(raider_link) = self.raid_link_for(message).await {
+                    let mut whitelist = whitelist.clone();
+                    whitelist.push(raider_link);
+                    Self::check_links(&message.content, &whitelist)
                 } else {
                     Self::check_links(&message.content, whitelist)
                 }
@@ -269,8 +1132,8 @@ impl ModerationSystem {
             SpamFilterType::MessageLength { max_length } => {
                 message.content.len() > *max_length
             }
-            SpamFilterType::ExcessiveEmotes { max_count } => {
-                Self::check_excessive_emotes(&message.content, *max_count)
+            SpamFilterType::ExcessiveEmotes { max_count, count_unique } => {
+                Self::count_emotes(message, *count_unique) > *max_count as usize
             }
             SpamFilterType::SymbolSpam { max_percentage } => {
                 Self::check_symbol_spam(&message.content, *max_percentage)
@@ -278,25 +1141,58 @@ impl ModerationSystem {
             SpamFilterType::RateLimit { max_messages, window_seconds } => {
                 self.check_rate_limit(message, *max_messages, *window_seconds).await
             }
-            SpamFilterType::Blacklist { patterns, case_sensitive, whole_words_only } => {
-                Self::check_blacklist(&message.content, patterns, *case_sensitive, *whole_words_only)
+            SpamFilterType::Blacklist { patterns, case_sensitive, whole_words_only, except_patterns } => {
+                if except_patterns.is_empty() {
+                    Self::check_blacklist(&message.content, patterns, *case_sensitive, *whole_words_only, &self.log_redaction)
+                } else {
+                    Self::find_blacklist_hit(&message.content, patterns, except_patterns, *case_sensitive, *whole_words_only, &self.log_redaction)
+                        .is_some_and(|hit| !hit.excepted)
+                }
             }
         }
     }
 
+    /// Raiding channel link allowed for this message: only a first-time chatter's
+    /// first message, while the channel has a raid link exception
+    async fn raid_link_for(&self, message: &ChatMessage) -> Option<String> {
+        let key = (message.platform.clone(), message.channel.to_lowercase());
+        let raiding_channel = self.raid_link_exceptions.read().await.get(&key).cloned()?;
+
+        let user_key = self.identity_key(&message.platform, &message.username).await;
+        let is_first_message = self.user_message_history.read().await
+            .get(&user_key)
+            .is_none_or(|h| h.messages.len() <= 1);
+        if !is_first_message {
+            return None;
+        }
+        crate::bot::raid_mode::channel_link(&message.platform, &raiding_channel)
+    }
+
     /// Check blacklist patterns against message content
     fn check_blacklist(
         content: &str, 
         patterns: &[BlacklistPattern], 
         case_sensitive: bool, 
-        whole_words_only: bool
+        whole_words_only: bool,
+        log_redaction: &LogRedaction,
     ) -> bool {
         for pattern in patterns {
             if pattern.matches(content, case_sensitive, whole_words_only) {
-                debug!("Blacklist match found: pattern matched '{}'", content);
+                debug!("Blacklist match found: pattern matched '{}'", log_redaction.redact(content));
                 return true;
             }
         }
+
+        // Re-run against base64/hex/URL-encoded payloads hidden in the message
+        if crate::ai::ENCODED_CONTENT_SCANNING {
+            for decoded in decode_embedded_payloads(content) {
+                if patterns.iter().any(|p| p.matches(&decoded, case_sensitive, whole_words_only)) {
+                    debug!("Blacklist match found in encoded content: '{}' -> '{}'",
+                           log_redaction.redact(content), log_redaction.redact(&decoded));
+                    return true;
+                }
+            }
+        }
         false
     }
 
@@ -348,10 +1244,11 @@ impl ModerationSystem {
     }
 
     async fn check_repeated_messages(&self, message: &ChatMessage, max_repeats: u8, window_seconds: u64) -> bool {
-        let user_key = format!("{}:{}", message.platform, message.username);
+        let user_key = self.identity_key(&message.platform, &message.username).await;
         let history = self.user_message_history.read().await;
         
         if let Some(user_hist) = history.get(&user_key) {
+            // History timestamps are on the bot's clock (see `update_user_history`)
             let cutoff_time = chrono::Utc::now() - chrono::Duration::seconds(window_seconds as i64);
             let recent_messages: Vec<&String> = user_hist.messages
                 .iter()
@@ -369,13 +1266,55 @@ impl ModerationSystem {
         }
     }
 
-    fn check_excessive_emotes(content: &str, max_count: u8) -> bool {
-        let emote_patterns = [":)", ":(", ":D", ":P", ":o", "Kappa", "PogChamp", "LUL"];
-        let emote_count = emote_patterns.iter()
-            .map(|pattern| content.matches(pattern).count())
-            .sum::<usize>();
+    /// Emotes in a message: the platform's emotes plus Unicode emoji in the rest
+    /// of the text (emoji inside a platform emote's span aren't counted twice)
+    fn count_emotes(message: &ChatMessage, count_unique: bool) -> usize {
+        let outside_emotes: String = message.content.chars().enumerate()
+            .filter(|(i, _)| !message.emotes.iter().any(|e| (e.start..=e.end).contains(i)))
+            .map(|(_, c)| c)
+            .collect();
+        let emoji = Self::unicode_emoji(&outside_emotes);
 
-        emote_count > max_count as usize
+        if count_unique {
+            let emote_ids = message.emotes.iter().map(|e| format!("emote:{}", e.id));
+            emote_ids.chain(emoji).collect::<HashSet<_>>().len()
+        } else {
+            message.emotes.len() + emoji.len()
+        }
+    }
+
+    /// Emoji in the text, keeping ZWJ sequences, skin tones and flags together as one
+    fn unicode_emoji(content: &str) -> Vec<String> {
+        let is_emoji = |c: char| matches!(c as u32,
+            0x1F000..=0x1FAFF | 0x2600..=0x27BF | 0x2B00..=0x2BFF | 0x2300..=0x23FF);
+        let is_modifier = |c: char| matches!(c as u32, 0xFE0F | 0x1F3FB..=0x1F3FF | 0xE0020..=0xE007F);
+        let is_regional = |c: char| matches!(c as u32, 0x1F1E6..=0x1F1FF);
+
+        let mut emoji: Vec<String> = Vec::new();
+        // Whether the previous character belongs to the last emoji, and was a joiner
+        let mut in_emoji = false;
+        let mut joining = false;
+        for c in content.chars() {
+            let pairs_flag = is_regional(c) && emoji.last().is_some_and(|e| {
+                let mut chars = e.chars();
+                chars.next().is_some_and(is_regional) && chars.next().is_none()
+            });
+            let extends = in_emoji
+                && ((joining && is_emoji(c)) || is_modifier(c) || c == '\u{200D}' || pairs_flag);
+
+            if extends {
+                emoji.last_mut().expect("in_emoji implies an emoji").push(c);
+                joining = c == '\u{200D}';
+            } else if is_emoji(c) {
+                emoji.push(c.to_string());
+                in_emoji = true;
+                joining = false;
+            } else {
+                in_emoji = false;
+                joining = false;
+            }
+        }
+        emoji
     }
 
     fn check_symbol_spam(content: &str, max_percentage: u8) -> bool {
@@ -392,7 +1331,7 @@ impl ModerationSystem {
     }
 
     async fn check_rate_limit(&self, message: &ChatMessage, max_messages: u8, window_seconds: u64) -> bool {
-        let user_key = format!("{}:{}", message.platform, message.username);
+        let user_key = self.identity_key(&message.platform, &message.username).await;
         let history = self.user_message_history.read().await;
         
         if let Some(user_hist) = history.get(&user_key) {
@@ -408,22 +1347,30 @@ impl ModerationSystem {
         }
     }
 
-    /// Update user message history
-    pub async fn update_user_history(&self, message: &ChatMessage) {
-        let user_key = format!("{}:{}", message.platform, message.username);
+    /// Update user message history. Returns whether this is the first message
+    /// seen from the user.
+    pub async fn update_user_history(&self, message: &ChatMessage) -> bool {
+        let user_key = self.identity_key(&message.platform, &message.username).await;
         let mut history = self.user_message_history.write().await;
         
+        let first_message = !history.contains_key(&user_key);
         let user_hist = history.entry(user_key.clone()).or_insert_with(|| UserMessageHistory::new(user_key));
 
-        user_hist.messages.push((message.timestamp, message.content.clone()));
+        let now = chrono::Utc::now();
+        let timestamp = message.window_timestamp(now);
+        if timestamp != message.timestamp {
+            debug!("Ignoring skewed timestamp {} on message from {} (received {})", message.timestamp, message.username, now);
+        }
+        user_hist.messages.push((timestamp, message.content.clone()));
 
         // Clean old messages (keep only last 50 or last hour)
-        let cutoff_time = chrono::Utc::now() - chrono::Duration::hours(1);
+        let cutoff_time = now - chrono::Duration::hours(1);
         user_hist.messages.retain(|(timestamp, _)| *timestamp > cutoff_time);
         
         if user_hist.messages.len() > 50 {
             user_hist.messages.drain(0..user_hist.messages.len() - 50);
         }
+        first_message
     }
 
     /// Handle moderation actions with enhanced responses
@@ -431,18 +1378,37 @@ impl ModerationSystem {
         action: ModerationAction,
         message: &ChatMessage,
         response_sender: &tokio::sync::mpsc::Sender<(String, String, String)>,
-    ) -> Result<()> {
+        log_redaction: &LogRedaction,
+    ) -> ModerationResult<()> {
+        Self::handle_moderation_action_with_notice(action, message, None, response_sender, log_redaction).await
+    }
+
+    /// Handle a moderation action, announcing warnings and timeouts with the
+    /// filter's custom notice (variables filled in) when it has one
+    pub async fn handle_moderation_action_with_notice(
+        action: ModerationAction,
+        message: &ChatMessage,
+        notice: Option<&ModerationNotice>,
+        response_sender: &tokio::sync::mpsc::Sender<(String, String, String)>,
+        log_redaction: &LogRedaction,
+    ) -> ModerationResult<()> {
+        let custom_notice = notice.map(|notice| notice.render(message, &action));
+        let mention = format!("@{}", message.username);
         match action {
             ModerationAction::DeleteMessage => {
                 info!("Would delete message from {} in #{}: {}", 
-                      message.username, message.channel, message.content);
+                      message.username, message.channel, log_redaction.redact(&message.content));
             }
             ModerationAction::TimeoutUser { duration_seconds } => {
                 info!("Would timeout user {} for {}s in #{}", 
                       message.username, duration_seconds, message.channel);
                 
-                let timeout_msg = format!("@{} has been timed out for {} seconds", 
-                                        message.username, duration_seconds);
+                let timeout_msg = match custom_notice {
+                    Some(text) if notice.is_some_and(ModerationNotice::is_template) => {
+                        if text.contains(&mention) { text } else { format!("{} {}", mention, text) }
+                    }
+                    _ => format!("{} has been timed out for {} seconds", mention, duration_seconds),
+                };
                 if let Err(e) = response_sender.send((
                     message.platform.clone(),
                     message.channel.clone(),
@@ -452,7 +1418,7 @@ impl ModerationSystem {
                 }
             }
             ModerationAction::WarnUser { message: warning } => {
-                let warn_msg = format!("@{} {}", message.username, warning);
+                let warn_msg = format!("{} {}", mention, custom_notice.unwrap_or(warning));
                 if let Err(e) = response_sender.send((
                     message.platform.clone(),
                     message.channel.clone(),
@@ -463,17 +1429,57 @@ impl ModerationSystem {
             }
             ModerationAction::LogOnly => {
                 info!("Spam detected from {} in #{}: {}", 
-                      message.username, message.channel, message.content);
+                      message.username, message.channel, log_redaction.redact(&message.content));
+            }
+            ModerationAction::ShadowMute { duration_seconds } => {
+                // Nothing is said in chat; the mute itself is recorded with `shadow_mute`
+                info!("Shadow muted {} for {}s in #{}", message.username, duration_seconds, message.channel);
             }
         }
 
         Ok(())
     }
 
+    /// Match counters for one filter
+    pub async fn get_filter_match_stats(&self, filter_name: &str) -> FilterMatchStats {
+        self.filter_matches.read().await.get(filter_name).cloned().unwrap_or_default()
+    }
+
+    /// Log what `handle_moderation_action` would do in dry-run mode, without doing it
+    pub fn log_dry_run_action(action: &ModerationAction, message: &ChatMessage, log_redaction: &LogRedaction) {
+        match action {
+            ModerationAction::DeleteMessage => {
+                info!("[DRY RUN] would delete message from {} in #{}: {}",
+                      message.username, message.channel, log_redaction.redact(&message.content));
+            }
+            ModerationAction::TimeoutUser { duration_seconds } => {
+                info!("[DRY RUN] would timeout {} for {}s in #{}",
+                      message.username, duration_seconds, message.channel);
+            }
+            ModerationAction::WarnUser { message: warning } => {
+                info!("[DRY RUN] would warn {} in #{}: {}", message.username, message.channel, warning);
+            }
+            ModerationAction::LogOnly => {
+                info!("[DRY RUN] Spam detected from {} in #{}: {}",
+                      message.username, message.channel, log_redaction.redact(&message.content));
+            }
+            ModerationAction::ShadowMute { duration_seconds } => {
+                info!("[DRY RUN] would shadow mute {} for {}s in #{}",
+                      message.username, duration_seconds, message.channel);
+            }
+        }
+    }
+
+    /// Messages checked and blocked, in total and over the recent window
+    pub async fn get_moderation_metrics(&self) -> ModerationMetricsSnapshot {
+        self.metrics.write().await.snapshot(chrono::Utc::now())
+    }
+
     /// Get filter statistics
     pub async fn get_filter_stats(&self) -> HashMap<String, serde_json::Value> {
         let filters = self.spam_filters.read().await;
         let history = self.user_message_history.read().await;
+        let matches = self.filter_matches.read().await;
         
         let total_filters = filters.len();
         let enabled_filters = filters.values().filter(|f| f.enabled).count();
@@ -495,10 +1501,13 @@ impl ModerationSystem {
                     .filter(|v| v.filter_name == *name)
                     .count() as u64)
                 .sum::<u64>();
+            let match_stats = matches.get(name).cloned().unwrap_or_default();
             
             filter_stats.insert(name.clone(), serde_json::json!({
                 "enabled": filter.enabled,
                 "violations": violations,
+                "trigger_count": match_stats.trigger_count,
+                "last_triggered": match_stats.last_triggered.map(|t| t.to_rfc3339()),
                 "silent_mode": filter.silent_mode,
                 "exemption_level": format!("{:?}", filter.exemption_level)
             }));
@@ -507,4 +1516,916 @@ impl ModerationSystem {
         
         stats
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blacklist_matches_base64_payload() {
+        let patterns = vec![BlacklistPattern::Literal("badsite.com".to_string())];
+
+        assert!(!ModerationSystem::check_blacklist("hello everyone", &patterns, false, false, &LogRedaction::default()));
+        // base64("visit badsite.com now")
+        assert!(ModerationSystem::check_blacklist("dmlzaXQgYmFkc2l0ZS5jb20gbm93", &patterns, false, false, &LogRedaction::default()));
+    }
+
+    fn chat_message(content: &str) -> ChatMessage {
+        ChatMessage::new("twitch", "testchannel", "viewer", content)
+    }
+
+    #[tokio::test]
+    async fn test_filter_only_applies_on_its_active_days() {
+        use chrono::Datelike;
+        let moderation = ModerationSystem::new();
+        moderation.add_spam_filter(SpamFilterType::Blacklist {
+            patterns: vec![BlacklistPattern::Literal("raidtime".to_string())],
+            case_sensitive: false,
+            whole_words_only: false,
+            except_patterns: Vec::new(),
+        }).await.unwrap();
+        let name = moderation.list_filters().await.into_iter().next().unwrap().0;
+        moderation.set_action_cooldown(0).await;
+
+        let today = chrono::Utc::now().weekday();
+        moderation.set_filter_schedule(&name, Some(ActiveSchedule { days: vec![today.succ()], ..Default::default() })).await.unwrap();
+        assert!(moderation.check_spam_filters(&chat_message("raidtime"), None).await.is_none());
+
+        moderation.set_filter_schedule(&name, Some(ActiveSchedule { days: vec![today], ..Default::default() })).await.unwrap();
+        assert!(moderation.check_spam_filters(&chat_message("raidtime"), None).await.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_explained_matches_point_at_the_matched_text() {
+        let moderation = ModerationSystem::new();
+        moderation.add_spam_filter(SpamFilterType::Blacklist {
+            patterns: vec![
+                BlacklistPattern::Literal("nothing here".to_string()),
+                BlacklistPattern::from_regex_string(r"~/free\s+v-?bucks/i").unwrap(),
+            ],
+            case_sensitive: false,
+            whole_words_only: false,
+            except_patterns: vec![BlacklistPattern::Literal("vbucks is fake".to_string())],
+        }).await.unwrap();
+        moderation.add_spam_filter(SpamFilterType::ExcessiveCaps { max_percentage: 50 }).await.unwrap();
+
+        let text = "get FREE  VBUCKS here, all of you!";
+        let explanations = moderation.explain_message(&chat_message(text)).await;
+        assert_eq!(explanations.len(), 1);
+        let explanation = &explanations[0];
+        assert_eq!(explanation.filter, "blacklist");
+        assert_eq!(explanation.pattern.as_deref(), Some(r"~/free\s+v-?bucks/i"));
+        let span = explanation.span.as_ref().unwrap();
+        assert_eq!(&text[span.start..span.end], "FREE  VBUCKS");
+        assert_eq!(explanation.highlighted(), "get «FREE  VBUCKS» here, all of you!");
+        assert!(!explanation.excepted);
+
+        let shouted = "SCAM WARNING: FREE VBUCKS IS FAKE";
+        let explanations = moderation.explain_message(&chat_message(shouted)).await;
+        assert_eq!(explanations.iter().map(|e| e.filter.as_str()).collect::<Vec<_>>(), ["blacklist", "excessive_caps"]);
+        assert!(explanations[0].excepted);
+        assert_eq!(explanations[1].span, None);
+
+        // Matches inside encoded payloads point into the decoded text
+        let encoded = "look aGV5IGZyZWUgdmJ1Y2tzIGhlcmU=";
+        let explanations = moderation.explain_message(&chat_message(encoded)).await;
+        let span = explanations[0].span.as_ref().unwrap();
+        assert_eq!(explanations[0].matched_text, "hey free vbucks here");
+        assert_eq!(&explanations[0].matched_text[span.start..span.end], "free vbucks");
+    }
+
+    #[tokio::test]
+    async fn test_shadow_mute_lasts_for_its_duration() {
+        let moderation = ModerationSystem::new();
+        let message = ChatMessage::new("twitch", "testchannel", "SuspectedBot", "hello");
+        assert!(!moderation.is_shadow_muted(&message).await);
+
+        moderation.shadow_mute("twitch", "suspectedbot", 60).await;
+        let now = chrono::Utc::now();
+        assert!(moderation.is_shadow_muted_at(&message, now).await);
+        assert!(!moderation.is_shadow_muted_at(&ChatMessage::new("youtube", "testchannel", "SuspectedBot", "hi"), now).await);
+
+        // Once it runs out the user is heard again, and the mute is forgotten
+        assert!(!moderation.is_shadow_muted_at(&message, now + chrono::Duration::seconds(61)).await);
+        assert!(!moderation.is_shadow_muted_at(&message, now).await);
+
+        moderation.shadow_mute("twitch", "suspectedbot", 60).await;
+        assert!(moderation.lift_shadow_mute("twitch", "SuspectedBot").await);
+        assert!(!moderation.is_shadow_muted(&message).await);
+        assert!(!moderation.lift_shadow_mute("twitch", "SuspectedBot").await);
+    }
+
+    #[tokio::test]
+    async fn test_expired_shadow_mutes_are_pruned() {
+        let moderation = ModerationSystem::new();
+        moderation.shadow_mute("twitch", "gone_quiet", 60).await;
+        moderation.shadow_mute("twitch", "still_muted", 600).await;
+
+        let now = chrono::Utc::now();
+        assert_eq!(moderation.prune_expired_shadow_mutes(now).await, 0);
+        assert_eq!(moderation.prune_expired_shadow_mutes(now + chrono::Duration::seconds(61)).await, 1);
+        assert_eq!(moderation.shadow_mutes.read().await.len(), 1);
+        let still_muted = ChatMessage::new("twitch", "testchannel", "still_muted", "hi");
+        assert!(moderation.is_shadow_muted_at(&still_muted, now + chrono::Duration::seconds(61)).await);
+    }
+
+    /// Keeps every log record so tests can check the level something was logged at
+    struct CapturingLogger;
+
+    static CAPTURED_LOGS: std::sync::Mutex<Vec<(Level, String)>> = std::sync::Mutex::new(Vec::new());
+
+    impl log::Log for CapturingLogger {
+        fn enabled(&self, _: &log::Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, record: &log::Record) {
+            CAPTURED_LOGS.lock().unwrap().push((record.level(), record.args().to_string()));
+        }
+
+        fn flush(&self) {}
+    }
+
+    fn capture_logs() {
+        static INSTALL: std::sync::Once = std::sync::Once::new();
+        INSTALL.call_once(|| {
+            log::set_logger(&CapturingLogger).unwrap();
+            log::set_max_level(log::LevelFilter::Trace);
+        });
+    }
+
+    fn logged_level(needle: &str) -> Option<Level> {
+        CAPTURED_LOGS.lock().unwrap().iter()
+            .find(|(_, message)| message.contains(needle))
+            .map(|(level, _)| *level)
+    }
+
+    #[tokio::test]
+    async fn test_matches_are_logged_at_the_filters_level() {
+        capture_logs();
+        let moderation = ModerationSystem::new();
+        let filters = [
+            ("noisy_caps", "shouting", Some(FilterLogLevel::Trace)),
+            ("phishing", "steamcommunlty", Some(FilterLogLevel::Warn)),
+            ("plain_words", "badword", None),
+        ];
+        for (name, word, log_level) in filters {
+            moderation.add_blacklist_filter(name.to_string(), vec![word.to_string()], false, false, ExemptionLevel::None, 60, None).await.unwrap();
+            moderation.set_filter_log_level(name, log_level).await.unwrap();
+        }
+        assert!(moderation.set_filter_log_level("missing", Some(FilterLogLevel::Debug)).await.is_err());
+
+        for (name, word, log_level) in filters {
+            let message = ChatMessage::new("twitch", "testchannel", name, format!("look: {}", word));
+            let verdict = moderation.evaluate_spam_filters(&message, None).await.unwrap();
+            assert_eq!(verdict.filter_name, name);
+            assert_eq!(verdict.log_level, log_level);
+        }
+
+        assert_eq!(logged_level("flagged by filter 'noisy_caps'"), Some(Level::Trace));
+        assert_eq!(logged_level("flagged by filter 'phishing'"), Some(Level::Warn));
+        // Without a configured level matches are logged at info, as always
+        assert_eq!(logged_level("flagged by filter 'plain_words'"), Some(Level::Info));
+    }
+
+    #[tokio::test]
+    async fn test_spam_wave_keeps_configured_log_levels() {
+        capture_logs();
+        let moderation = ModerationSystem::new();
+        moderation.set_spam_wave_config(SpamWaveConfig { threshold: 1, window_seconds: 60, quiet_seconds: 60 }).await;
+        let filters = [
+            ("wave_phishing", "steamcommunlty", Some(FilterLogLevel::Warn)),
+            ("wave_plain", "badword", None),
+        ];
+        for (name, word, log_level) in filters {
+            moderation.add_blacklist_filter(name.to_string(), vec![word.to_string()], false, false, ExemptionLevel::None, 60, None).await.unwrap();
+            moderation.set_filter_log_level(name, log_level).await.unwrap();
+            for i in 0..2 {
+                let message = ChatMessage::new("twitch", "testchannel", format!("{}{}", name, i), format!("look: {}", word));
+                assert!(moderation.check_spam_filters(&message, None).await.is_some());
+            }
+        }
+
+        // An explicit level is honoured, only the default is quietened
+        assert_eq!(logged_level("flagged by filter 'wave_phishing' (spam wave)"), Some(Level::Warn));
+        assert_eq!(logged_level("flagged by filter 'wave_plain' (spam wave)"), Some(Level::Debug));
+    }
+
+    #[tokio::test]
+    async fn test_bot_account_is_never_moderated() {
+        let moderation = ModerationSystem::new();
+        moderation.add_spam_filter(SpamFilterType::Blacklist {
+            patterns: vec![BlacklistPattern::Literal("buyfollowers".to_string())],
+            case_sensitive: false,
+            whole_words_only: false,
+            except_patterns: Vec::new(),
+        }).await.unwrap();
+        moderation.set_bot_account("twitch", Some("NotaBot")).await;
+
+        let echoed = ChatMessage::new("twitch", "testchannel", "notabot", "Filter added: buyfollowers");
+        assert!(moderation.is_from_bot(&echoed).await);
+        assert!(moderation.check_spam_filters(&echoed, None).await.is_none());
+        assert_eq!(moderation.get_moderation_metrics().await.messages_checked, 0);
+
+        // The name only counts on the platform it was configured for
+        let elsewhere = ChatMessage::new("youtube", "testchannel", "NotaBot", "buyfollowers");
+        assert!(moderation.check_spam_filters(&elsewhere, None).await.is_some());
+        assert!(moderation.check_spam_filters(&chat_message("buyfollowers"), None).await.is_some());
+
+        // Send-only accounts count as the bot too
+        moderation.add_bot_account("twitch", "NotaBotVerified").await;
+        assert!(moderation.is_from_bot(&echoed).await);
+        assert!(moderation.is_from_bot(&ChatMessage::new("twitch", "testchannel", "notabotverified", "hi")).await);
+
+        moderation.set_bot_account("twitch", None).await;
+        assert!(moderation.check_spam_filters(&echoed, None).await.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_grace_period_warns_before_acting() {
+        let moderation = ModerationSystem::new();
+        moderation.add_spam_filter_advanced(
+            "borderline".to_string(),
+            SpamFilterType::Blacklist {
+                patterns: vec![BlacklistPattern::Literal("sus".to_string())],
+                case_sensitive: false,
+                whole_words_only: false,
+                except_patterns: Vec::new(),
+            },
+            ModerationEscalation::fixed(ModerationAction::TimeoutUser { duration_seconds: 300 }),
+            ExemptionLevel::None,
+            false,
+            None,
+        ).await.unwrap();
+        moderation.set_filter_grace("borderline", Some(30)).await.unwrap();
+        moderation.set_action_cooldown(0).await;
+
+        // First trip warns, a repeat inside the grace window gets the real action
+        assert!(matches!(moderation.check_spam_filters(&chat_message("sus"), None).await, Some(ModerationAction::WarnUser { .. })));
+        assert!(matches!(
+            moderation.check_spam_filters(&chat_message("still sus"), None).await,
+            Some(ModerationAction::TimeoutUser { duration_seconds: 300 })
+        ));
+
+        // Once the last match is older than the grace period, the next one only warns again
+        async fn age_history(moderation: &ModerationSystem, seconds: i64) {
+            let mut history = moderation.user_message_history.write().await;
+            for violation in history.get_mut("twitch:viewer").unwrap().violation_history.violations.iter_mut() {
+                violation.timestamp -= chrono::Duration::seconds(seconds);
+            }
+        }
+        age_history(&moderation, 31).await;
+        assert!(matches!(moderation.check_spam_filters(&chat_message("sus again"), None).await, Some(ModerationAction::WarnUser { .. })));
+        age_history(&moderation, 10).await;
+        assert!(matches!(
+            moderation.check_spam_filters(&chat_message("so sus"), None).await,
+            Some(ModerationAction::TimeoutUser { .. })
+        ));
+
+        assert!(matches!(moderation.set_filter_grace("missing", None).await, Err(ModerationError::FilterNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_grace_warning_uses_the_filters_message() {
+        let moderation = ModerationSystem::new();
+        let blacklist = |word: &str| SpamFilterType::Blacklist {
+            patterns: vec![BlacklistPattern::Literal(word.to_string())],
+            case_sensitive: false,
+            whole_words_only: false,
+            except_patterns: Vec::new(),
+        };
+        let escalation = ModerationEscalation {
+            first_offense: ModerationAction::WarnUser { message: "no sus talk".to_string() },
+            repeat_offense: ModerationAction::TimeoutUser { duration_seconds: 300 },
+            offense_window_seconds: 60,
+        };
+        let timeout = ModerationEscalation::fixed(ModerationAction::TimeoutUser { duration_seconds: 300 });
+        let filters = [
+            ("custom", blacklist("cringe"), timeout.clone(), Some("$(user), keep it friendly".to_string())),
+            ("warned", blacklist("sus"), escalation, None),
+            ("plain", blacklist("bad"), timeout, None),
+        ];
+        for (name, filter_type, escalation, custom_message) in filters {
+            moderation.add_spam_filter_advanced(name.to_string(), filter_type, escalation, ExemptionLevel::None, false, custom_message).await.unwrap();
+            moderation.set_filter_grace(name, Some(30)).await.unwrap();
+        }
+
+        let warning = |action: Option<ModerationAction>| match action {
+            Some(ModerationAction::WarnUser { message }) => message,
+            other => panic!("expected a warning, got {:?}", other),
+        };
+        let viewer = |name: &str, content: &str| ChatMessage::new("twitch", "testchannel", name, content);
+        assert_eq!(warning(moderation.check_spam_filters(&viewer("alice", "cringe"), None).await), "alice, keep it friendly");
+        assert_eq!(warning(moderation.check_spam_filters(&viewer("bob", "sus"), None).await), "no sus talk");
+        assert_eq!(
+            warning(moderation.check_spam_filters(&viewer("carol", "bad"), None).await),
+            "Please follow chat rules (blocked term)"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_blacklist_exceptions_take_precedence() {
+        let moderation = ModerationSystem::new();
+        moderation.add_blacklist_filter(
+            "urls".to_string(),
+            vec!["*http*".to_string()],
+            false,
+            false,
+            ExemptionLevel::None,
+            60,
+            None,
+        ).await.unwrap();
+        moderation.add_blacklist_filter(
+            "words".to_string(),
+            vec!["scam".to_string()],
+            false,
+            false,
+            ExemptionLevel::None,
+            60,
+            None,
+        ).await.unwrap();
+        moderation.set_blacklist_exceptions("urls", vec![BlacklistPattern::wildcard("youtube.com", crate::types::WildcardAnchor::Contains)]).await.unwrap();
+
+        assert!(moderation.check_spam_filters(&chat_message("see http://spam.example"), None).await.is_some());
+        assert!(moderation.check_spam_filters(&chat_message("see http://youtube.com/abc"), None).await.is_none());
+        assert!(moderation.check_spam_filters(&chat_message("http://youtube.com/a and http://youtube.com/b"), None).await.is_none());
+        // An exception only rescues the match it overlaps, not the whole message
+        assert!(moderation.check_spam_filters(&chat_message("https://scam.example youtube.com"), None).await.is_some());
+        let explanations = moderation.explain_message(&chat_message("http://youtube.com/a http://scam.example")).await;
+        assert!(!explanations[0].excepted);
+        assert_eq!(explanations[0].span.as_ref().unwrap().start, "http://youtube.com/a ".len());
+        // The exception only applies to its own filter
+        let verdict = moderation.evaluate_spam_filters(&chat_message("scam at http://youtube.com/abc"), None).await.unwrap();
+        assert_eq!(verdict.filter_name, "words");
+
+        assert!(matches!(
+            moderation.set_blacklist_exceptions("missing", Vec::new()).await,
+            Err(ModerationError::FilterNotFound(_))
+        ));
+        moderation.add_spam_filter(SpamFilterType::ExcessiveCaps { max_percentage: 50 }).await.unwrap();
+        assert!(matches!(
+            moderation.set_blacklist_exceptions("excessive_caps", Vec::new()).await,
+            Err(ModerationError::NotABlacklist(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_allowlisted_phrase_bypasses_blacklist() {
+        let moderation = ModerationSystem::new();
+        moderation.add_blacklist_filter(
+            "spam_words".to_string(),
+            vec!["spam".to_string()],
+            false,
+            false,
+            ExemptionLevel::None,
+            60,
+            None,
+        ).await.unwrap();
+
+        let message = chat_message("anyone playing Spam Simulator tonight?");
+        assert!(moderation.check_spam_filters(&message, None).await.is_some());
+
+        moderation.set_allowlist(ModerationAllowlist {
+            entries: vec![crate::types::AllowlistEntry {
+                pattern: BlacklistPattern::Literal("spam simulator".to_string()),
+                channels: vec![],
+            }],
+            bypass_all_filters: false,
+        }).await;
+        assert!(moderation.check_spam_filters(&message, None).await.is_none());
+
+        // Non-allowlisted spam is still caught
+        assert!(moderation.check_spam_filters(&chat_message("buy spam now"), None).await.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_allowlist_keeps_other_filters_unless_configured() {
+        let moderation = ModerationSystem::new();
+        moderation.add_spam_filter(SpamFilterType::ExcessiveCaps { max_percentage: 50 }).await.unwrap();
+
+        let allowlist = |bypass_all_filters| ModerationAllowlist {
+            entries: vec![crate::types::AllowlistEntry {
+                pattern: BlacklistPattern::Literal("spam simulator".to_string()),
+                channels: vec!["testchannel".to_string()],
+            }],
+            bypass_all_filters,
+        };
+
+        let message = chat_message("SPAM SIMULATOR IS THE BEST GAME");
+        moderation.set_allowlist(allowlist(false)).await;
+        assert!(moderation.check_spam_filters(&message, None).await.is_some());
+
+        moderation.set_allowlist(allowlist(true)).await;
+        assert!(moderation.check_spam_filters(&message, None).await.is_none());
+
+        // Entry is scoped to a channel
+        let other_channel = ChatMessage { channel: "elsewhere".to_string(), ..message };
+        assert!(moderation.check_spam_filters(&other_channel, None).await.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_active_filters_reflect_live_state() {
+        let moderation = ModerationSystem::new();
+        moderation.add_spam_filter_advanced(
+            "caps".to_string(),
+            SpamFilterType::ExcessiveCaps { max_percentage: 70 },
+            ModerationEscalation {
+                first_offense: ModerationAction::DeleteMessage,
+                repeat_offense: ModerationAction::TimeoutUser { duration_seconds: 120 },
+                offense_window_seconds: 600,
+            },
+            ExemptionLevel::Subscriber,
+            true,
+            Some("less caps".to_string()),
+        ).await.unwrap();
+        moderation.add_blacklist_filter(
+            "bad_words".to_string(),
+            vec!["badword".to_string(), "~/sc[a4]m/i".to_string()],
+            false,
+            true,
+            ExemptionLevel::Moderator,
+            300,
+            None,
+        ).await.unwrap();
+
+        let active = moderation.get_active_filters().await;
+        assert_eq!(active.len(), 2);
+
+        let blacklist = &active[0];
+        assert_eq!(blacklist.name, "bad_words");
+        assert_eq!(blacklist.filter_type, "Blacklist");
+        assert_eq!(blacklist.exemption_level, ExemptionLevel::Moderator);
+        assert_eq!(blacklist.parameters["whole_words_only"], true);
+        assert_eq!(blacklist.parameters["patterns"][1]["Regex"]["pattern"], "~/sc[a4]m/i");
+        assert!(matches!(blacklist.escalation.repeat_offense, ModerationAction::TimeoutUser { duration_seconds: 300 }));
+
+        let caps = &active[1];
+        assert_eq!(caps.name, "caps");
+        assert_eq!(caps.filter_type, "ExcessiveCaps");
+        assert_eq!(caps.parameters["max_percentage"], 70);
+        assert_eq!(caps.exemption_level, ExemptionLevel::Subscriber);
+        assert!(caps.silent_mode);
+        assert_eq!(caps.custom_message.as_deref(), Some("less caps"));
+        assert_eq!(caps.escalation.offense_window_seconds, 600);
+        assert!(caps.enabled);
+
+        // Runtime changes show up without a config reload
+        moderation.set_filter_enabled("caps", false).await.unwrap();
+        moderation.remove_filter("bad_words").await.unwrap();
+        let active = moderation.get_active_filters().await;
+        assert_eq!(active.len(), 1);
+        assert!(!active[0].enabled);
+        assert!(serde_json::to_value(&active).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_suggest_filters_from_recent_violations() {
+        let moderation = ModerationSystem::new();
+        moderation.add_blacklist_filter(
+            "scam_links".to_string(),
+            vec!["freecoins".to_string()],
+            false,
+            false,
+            ExemptionLevel::None,
+            300,
+            None,
+        ).await.unwrap();
+
+        let blocked = [
+            ("spam1", "get freecoins and free followers at streamboost"),
+            ("spam2", "FREE FOLLOWERS!! freecoins @streamer"),
+            ("spam3", "freecoins here, cheap viewers too"),
+        ];
+        for (user, content) in blocked {
+            let message = ChatMessage::new("twitch", "testchannel", user, content);
+            assert!(moderation.check_spam_filters(&message, None).await.is_some());
+        }
+
+        let flagged = [
+            ("spam4", "best free followers and cheap viewers on streamboost"),
+            ("spam5", "hello streamer, want free followers?"),
+        ];
+        for (user, content) in flagged {
+            let message = ChatMessage::new("twitch", "testchannel", user, content);
+            moderation.record_ai_violation(&message, &["promo".to_string()], ModerationAction::DeleteMessage, 0.7).await;
+        }
+
+        let suggestions = moderation.suggest_filters(10).await;
+        let patterns: Vec<(&str, usize)> = suggestions.iter().map(|s| (s.pattern.as_str(), s.frequency)).collect();
+
+        assert_eq!(patterns[0], ("free followers", 4));
+        assert!(patterns.contains(&("cheap viewers", 2)));
+        assert!(patterns.contains(&("streamboost", 2)));
+        // Already blacklisted, folded into a longer phrase, or seen only once
+        assert!(!patterns.iter().any(|(p, _)| p.contains("freecoins")));
+        assert!(!patterns.iter().any(|(p, _)| *p == "free" || *p == "followers"));
+        assert!(!patterns.iter().any(|(p, _)| p.contains("hello") || p.contains("best")));
+        // Mentions never become patterns
+        assert!(!patterns.iter().any(|(p, _)| p.contains("streamer")));
+        assert!(suggestions[0].example.to_lowercase().contains("free followers"));
+
+        assert_eq!(moderation.suggest_filters(1).await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_spam_wave_aggregates_blocks() {
+        let moderation = ModerationSystem::new();
+        moderation.add_blacklist_filter(
+            "scam_links".to_string(),
+            vec!["freecoins".to_string()],
+            false,
+            false,
+            ExemptionLevel::None,
+            300,
+            None,
+        ).await.unwrap();
+        moderation.set_spam_wave_config(SpamWaveConfig { threshold: 5, window_seconds: 60, quiet_seconds: 1 }).await;
+        let mut alerts = moderation.subscribe_spam_waves();
+
+        for i in 0..30 {
+            let spam = ChatMessage::new("twitch", "testchannel", format!("bot{}", i), "freecoins here");
+            assert!(moderation.check_spam_filters(&spam, None).await.is_some());
+        }
+        assert!(moderation.is_spam_wave_active().await);
+        assert!(moderation.flush_spam_waves().await.is_empty());
+
+        tokio::time::sleep(std::time::Duration::from_millis(1100)).await;
+        let flushed = moderation.flush_spam_waves().await;
+        assert_eq!(flushed.len(), 1);
+        assert_eq!(flushed[0].filter_name, "scam_links");
+        assert_eq!(flushed[0].blocked_count, 30);
+
+        assert_eq!(alerts.recv().await.unwrap(), flushed[0]);
+        assert!(alerts.try_recv().is_err());
+        assert!(!moderation.is_spam_wave_active().await);
+    }
+
+    #[tokio::test]
+    async fn test_action_cooldown_prevents_double_timeout() {
+        let moderation = ModerationSystem::new();
+        moderation.add_spam_filter_advanced(
+            "links".to_string(),
+            SpamFilterType::LinkBlocking { allow_mods: true, whitelist: Vec::new() },
+            ModerationEscalation {
+                first_offense: ModerationAction::TimeoutUser { duration_seconds: 60 },
+                repeat_offense: ModerationAction::TimeoutUser { duration_seconds: 600 },
+                offense_window_seconds: 3600,
+            },
+            ExemptionLevel::None,
+            false,
+            None,
+        ).await.unwrap();
+
+        let spam = chat_message("buy now at https://spam.example");
+        let first = moderation.check_spam_filters(&spam, None).await;
+        let second = moderation.check_spam_filters(&spam, None).await;
+        assert!(matches!(first, Some(ModerationAction::TimeoutUser { duration_seconds: 60 })));
+        assert!(matches!(second, Some(ModerationAction::LogOnly)));
+
+        // The suppressed match is still on record for review
+        let last = moderation.get_last_violation("twitch", "viewer").await.unwrap();
+        assert!(matches!(last.action_taken, ModerationAction::LogOnly));
+
+        // Other users aren't affected, and with the cooldown disabled timeouts stack again
+        let other = ChatMessage::new("twitch", "testchannel", "other", "https://spam.example");
+        assert!(matches!(moderation.check_spam_filters(&other, None).await, Some(ModerationAction::TimeoutUser { .. })));
+        moderation.set_action_cooldown(0).await;
+        assert!(matches!(
+            moderation.check_spam_filters(&spam, None).await,
+            Some(ModerationAction::TimeoutUser { duration_seconds: 600 })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_rapid_trips_of_a_warn_only_filter_warn_once() {
+        let moderation = ModerationSystem::new();
+        moderation.set_action_cooldown(0).await;
+        for (name, filter_type) in [
+            ("caps", SpamFilterType::ExcessiveCaps { max_percentage: 50 }),
+            ("links", SpamFilterType::LinkBlocking { allow_mods: true, whitelist: Vec::new() }),
+        ] {
+            moderation.add_spam_filter_advanced(
+                name.to_string(),
+                filter_type,
+                ModerationEscalation::fixed(ModerationAction::WarnUser { message: "calm down".to_string() }),
+                ExemptionLevel::None,
+                false,
+                None,
+            ).await.unwrap();
+        }
+
+        let shouting = chat_message("STOP SHOUTING");
+        let mut warnings = 0;
+        for _ in 0..5 {
+            if let Some(ModerationAction::WarnUser { .. }) = moderation.check_spam_filters(&shouting, None).await {
+                warnings += 1;
+            }
+        }
+        assert_eq!(warnings, 1);
+        // The suppressed trips are still on record
+        assert!(matches!(moderation.get_last_violation("twitch", "viewer").await.unwrap().action_taken, ModerationAction::LogOnly));
+
+        // Another filter, or another user, still gets its warning
+        assert!(matches!(
+            moderation.check_spam_filters(&chat_message("https://spam.example"), None).await,
+            Some(ModerationAction::WarnUser { .. })
+        ));
+        let other = ChatMessage::new("twitch", "testchannel", "other", "STOP SHOUTING");
+        assert!(matches!(moderation.check_spam_filters(&other, None).await, Some(ModerationAction::WarnUser { .. })));
+
+        // Once the window has passed the filter warns again
+        for violation in moderation.user_message_history.write().await
+            .get_mut("twitch:viewer").unwrap().violation_history.violations.iter_mut() {
+            violation.timestamp -= chrono::Duration::seconds(DEFAULT_WARNING_DEDUP_SECONDS as i64);
+        }
+        assert!(matches!(moderation.check_spam_filters(&shouting, None).await, Some(ModerationAction::WarnUser { .. })));
+
+        moderation.set_warning_dedup_window(0).await;
+        assert!(matches!(moderation.check_spam_filters(&shouting, None).await, Some(ModerationAction::WarnUser { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_moderation_error_variants() {
+        let moderation = ModerationSystem::new();
+
+        assert!(matches!(
+            moderation.set_filter_enabled("missing", false).await,
+            Err(ModerationError::FilterNotFound(name)) if name == "missing"
+        ));
+        assert!(matches!(moderation.remove_filter("missing").await, Err(ModerationError::FilterNotFound(_))));
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("no_such_dir").join("suggestions.json");
+        assert!(matches!(moderation.export_filter_suggestions(&path, 5).await, Err(ModerationError::Io(_))));
+    }
+
+    #[tokio::test]
+    async fn test_filter_stats_count_matches() {
+        let moderation = ModerationSystem::new();
+        moderation.set_action_cooldown(0).await;
+        moderation.add_spam_filter_advanced(
+            "caps".to_string(),
+            SpamFilterType::ExcessiveCaps { max_percentage: 50 },
+            ModerationEscalation::default(),
+            ExemptionLevel::None,
+            false,
+            None,
+        ).await.unwrap();
+
+        let stats = moderation.get_filter_stats().await;
+        assert_eq!(stats["filter_details"]["caps"]["trigger_count"], 0);
+        assert!(stats["filter_details"]["caps"]["last_triggered"].is_null());
+
+        let before = chrono::Utc::now();
+        for _ in 0..3 {
+            moderation.check_spam_filters(&chat_message("STOP SHOUTING AT ME"), None).await;
+        }
+        // Non-matching messages don't count
+        moderation.check_spam_filters(&chat_message("hello everyone"), None).await;
+
+        let match_stats = moderation.get_filter_match_stats("caps").await;
+        assert_eq!(match_stats.trigger_count, 3);
+        assert!(match_stats.last_triggered.is_some_and(|t| t >= before));
+
+        moderation.set_filter_enabled("caps", false).await.unwrap();
+        let stats = moderation.get_filter_stats().await;
+        let details = &stats["filter_details"]["caps"];
+        assert_eq!(details["trigger_count"], 3);
+        assert_eq!(details["enabled"], false);
+        assert!(details["last_triggered"].is_string());
+    }
+
+    #[tokio::test]
+    async fn test_moderation_metrics_count_checked_and_blocked() {
+        let moderation = ModerationSystem::new();
+        moderation.add_spam_filter_advanced(
+            "caps".to_string(),
+            SpamFilterType::ExcessiveCaps { max_percentage: 50 },
+            ModerationEscalation::default(),
+            ExemptionLevel::None,
+            false,
+            None,
+        ).await.unwrap();
+
+        for _ in 0..2 {
+            moderation.check_spam_filters(&chat_message("STOP SHOUTING AT ME"), None).await;
+        }
+        for _ in 0..6 {
+            moderation.check_spam_filters(&chat_message("hello everyone"), None).await;
+        }
+
+        let metrics = moderation.get_moderation_metrics().await;
+        assert_eq!(metrics.messages_checked, 8);
+        assert_eq!(metrics.messages_blocked, 2);
+        assert_eq!(metrics.blocks_by_filter.get("caps"), Some(&2));
+        assert_eq!(metrics.block_rate, 0.25);
+
+        // Nothing is checked while moderation is switched off
+        moderation.set_spam_protection_enabled(false).await;
+        moderation.check_spam_filters(&chat_message("STOP SHOUTING AT ME"), None).await;
+        assert_eq!(moderation.get_moderation_metrics().await.messages_checked, 8);
+    }
+
+    #[tokio::test]
+    async fn test_custom_notice_variables() {
+        let moderation = ModerationSystem::new();
+        moderation.add_spam_filter_advanced(
+            "no_links".to_string(),
+            SpamFilterType::LinkBlocking { allow_mods: false, whitelist: Vec::new() },
+            ModerationEscalation {
+                first_offense: ModerationAction::TimeoutUser { duration_seconds: 60 },
+                repeat_offense: ModerationAction::TimeoutUser { duration_seconds: 600 },
+                offense_window_seconds: 3600,
+            },
+            ExemptionLevel::None,
+            false,
+            Some("@$(user) timed out $(duration)s by $(filter) ($(reason))".to_string()),
+        ).await.unwrap();
+
+        let spam = chat_message("visit https://spam.example");
+        let verdict = moderation.evaluate_spam_filters(&spam, None).await.unwrap();
+        assert_eq!(verdict.filter_name, "no_links");
+        assert!(matches!(verdict.action, ModerationAction::TimeoutUser { duration_seconds: 60 }));
+
+        let (tx, mut rx) = tokio::sync::mpsc::channel(4);
+        ModerationSystem::handle_moderation_action_with_notice(verdict.action, &spam, verdict.notice.as_ref(), &tx, &LogRedaction::default())
+            .await.unwrap();
+        let (_, _, sent) = rx.recv().await.unwrap();
+        assert_eq!(sent, "@viewer timed out 60s by no_links (posting links)");
+
+        // Without a custom notice the default announcement is used
+        ModerationSystem::handle_moderation_action(ModerationAction::TimeoutUser { duration_seconds: 60 }, &spam, &tx, &LogRedaction::default())
+            .await.unwrap();
+        let (_, _, sent) = rx.recv().await.unwrap();
+        assert_eq!(sent, "@viewer has been timed out for 60 seconds");
+    }
+
+    #[tokio::test]
+    async fn test_timeout_notices_keep_the_mention() {
+        let spam = chat_message("visit https://spam.example");
+        let timeout = ModerationAction::TimeoutUser { duration_seconds: 60 };
+        let notice = |template: &str| ModerationNotice {
+            template: template.to_string(),
+            filter_name: "no_links".to_string(),
+            reason: "posting links".to_string(),
+            repeat_timeout_seconds: None,
+        };
+        let (tx, mut rx) = tokio::sync::mpsc::channel(4);
+
+        // A template that doesn't name the user still mentions them
+        ModerationSystem::handle_moderation_action_with_notice(timeout.clone(), &spam, Some(&notice("Timed out for $(reason)")), &tx, &LogRedaction::default())
+            .await.unwrap();
+        assert_eq!(rx.recv().await.unwrap().2, "@viewer Timed out for posting links");
+
+        // A plain custom message only ever replaced warnings; timeouts keep the default announcement
+        ModerationSystem::handle_moderation_action_with_notice(timeout, &spam, Some(&notice("Please don't post links")), &tx, &LogRedaction::default())
+            .await.unwrap();
+        assert_eq!(rx.recv().await.unwrap().2, "@viewer has been timed out for 60 seconds");
+    }
+
+    #[tokio::test]
+    async fn test_filters_stop_at_the_deadline() {
+        let moderation = ModerationSystem::new();
+        moderation.add_blacklist_filter(
+            "words".to_string(), vec!["badword".to_string()], false, false, ExemptionLevel::None, 300, None,
+        ).await.unwrap();
+        let message = chat_message("badword");
+
+        let expired = tokio::time::Instant::now();
+        let result = moderation.evaluate_spam_filters_within(&message, None, Some(expired)).await;
+        assert_eq!(result.unwrap_err(), FilterBudgetExceeded { checked: 0, total: 1 });
+        // Nothing was recorded for the unfinished check
+        assert_eq!(moderation.get_moderation_metrics().await.messages_checked, 0);
+
+        let later = tokio::time::Instant::now() + std::time::Duration::from_secs(60);
+        let verdict = moderation.evaluate_spam_filters_within(&message, None, Some(later)).await.unwrap();
+        assert_eq!(verdict.unwrap().filter_name, "words");
+    }
+
+    #[tokio::test]
+    async fn test_custom_warning_notice_mentions_next_timeout() {
+        let moderation = ModerationSystem::new();
+        moderation.add_blacklist_filter(
+            "words".to_string(),
+            vec!["badword".to_string()],
+            false,
+            false,
+            ExemptionLevel::None,
+            300,
+            Some("$(reason), $(user)! Next time: $(duration)s".to_string()),
+        ).await.unwrap();
+
+        let verdict = moderation.evaluate_spam_filters(&chat_message("badword"), None).await.unwrap();
+        match verdict.action {
+            ModerationAction::WarnUser { message } => assert_eq!(message, "blocked term, viewer! Next time: 300s"),
+            other => panic!("expected a warning, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_emote_count_uses_platform_spans() {
+        use crate::types::EmoteSpan;
+        let span = |id: &str, start, end| EmoteSpan { id: id.to_string(), start, end };
+
+        // "Kappa Kappa PogChamp hi" as reported by Twitch
+        let with_spans = chat_message("Kappa Kappa PogChamp hi")
+            .with_emotes(vec![span("25", 0, 4), span("25", 6, 10), span("88", 12, 19)]);
+        assert_eq!(ModerationSystem::count_emotes(&with_spans, false), 3);
+        assert_eq!(ModerationSystem::count_emotes(&with_spans, true), 2);
+
+        // Emote-like words aren't counted when the platform says they aren't emotes
+        let no_emotes = chat_message("Kappa is my favourite word :)");
+        assert_eq!(ModerationSystem::count_emotes(&no_emotes, false), 0);
+    }
+
+    #[test]
+    fn test_emote_count_adds_unicode_emoji_to_platform_emotes() {
+        use crate::types::EmoteSpan;
+        let span = |id: &str, start, end| EmoteSpan { id: id.to_string(), start, end };
+
+        let mixed = chat_message("Kappa 😀😀 🔥")
+            .with_emotes(vec![span("25", 0, 4)]);
+        assert_eq!(ModerationSystem::count_emotes(&mixed, false), 4);
+        assert_eq!(ModerationSystem::count_emotes(&mixed, true), 3);
+
+        // An emoji the platform already reported as an emote counts once
+        let reported = chat_message("😀 hi").with_emotes(vec![span("emoji-1f600", 0, 0)]);
+        assert_eq!(ModerationSystem::count_emotes(&reported, false), 1);
+    }
+
+    #[test]
+    fn test_emote_count_falls_back_to_unicode_emoji() {
+        // Family (ZWJ sequence), thumbs up with skin tone, flag: one emoji each
+        let mixed = chat_message("hi 😀😀 👨\u{200D}👩\u{200D}👧 👍🏽 🇳🇿 ❤\u{FE0F} done");
+        assert_eq!(ModerationSystem::count_emotes(&mixed, false), 6);
+        assert_eq!(ModerationSystem::count_emotes(&mixed, true), 5);
+
+        let plain = chat_message("no emoji here, just text!");
+        assert_eq!(ModerationSystem::count_emotes(&plain, false), 0);
+    }
+
+    #[tokio::test]
+    async fn test_excessive_emotes_filter() {
+        let moderation = ModerationSystem::new();
+        let filter = SpamFilterType::ExcessiveEmotes { max_count: 3, count_unique: true };
+
+        let repeated = chat_message("😂😂😂😂😂😂");
+        assert!(!moderation.violates_filter(&repeated, &filter).await);
+        let varied = chat_message("😂🔥💯👀");
+        assert!(moderation.violates_filter(&varied, &filter).await);
+
+        let total = SpamFilterType::ExcessiveEmotes { max_count: 3, count_unique: false };
+        assert!(moderation.violates_filter(&repeated, &total).await);
+    }
+
+    #[tokio::test]
+    async fn test_skewed_timestamps_stay_in_moderation_windows() {
+        let moderation = ModerationSystem::new();
+        let rate_limit = SpamFilterType::RateLimit { max_messages: 3, window_seconds: 10 };
+        let now = chrono::Utc::now();
+
+        // A platform clock hours behind must not age the burst out of the window
+        for _ in 0..3 {
+            let message = chat_message("hello").with_timestamp(now - chrono::Duration::hours(2));
+            moderation.update_user_history(&message).await;
+        }
+        assert!(moderation.violates_filter(&chat_message("hello"), &rate_limit).await);
+
+        // Future-dated messages are clamped to the receive time instead of staying "recent" forever
+        let ahead = ChatMessage::new("twitch", "testchannel", "ahead", "hi")
+            .with_timestamp(now + chrono::Duration::hours(1));
+        assert!(moderation.update_user_history(&ahead).await);
+        let plausible = ChatMessage::new("twitch", "testchannel", "ahead", "hi again")
+            .with_timestamp(now - chrono::Duration::seconds(5));
+        assert!(!moderation.update_user_history(&plausible).await);
+        {
+            let history = moderation.user_message_history.read().await;
+            let times: Vec<_> = history["twitch:ahead"].messages.iter().map(|(t, _)| *t).collect();
+            assert!(times[0] >= now && times[0] <= chrono::Utc::now());
+            assert_eq!(times[1], plausible.timestamp);
+        }
+
+        // Repeat offenses are judged on the bot's clock whatever the message says
+        moderation.add_spam_filter_advanced(
+            "links".to_string(),
+            SpamFilterType::LinkBlocking { allow_mods: true, whitelist: Vec::new() },
+            ModerationEscalation {
+                first_offense: ModerationAction::WarnUser { message: "no links".to_string() },
+                repeat_offense: ModerationAction::TimeoutUser { duration_seconds: 600 },
+                offense_window_seconds: 60,
+            },
+            ExemptionLevel::None,
+            false,
+            None,
+        ).await.unwrap();
+        moderation.set_action_cooldown(0).await;
+        let future_link = chat_message("https://spam.example").with_timestamp(now + chrono::Duration::days(1));
+        let past_link = chat_message("https://spam.example").with_timestamp(now - chrono::Duration::days(1));
+        assert!(matches!(moderation.check_spam_filters(&future_link, None).await, Some(ModerationAction::WarnUser { .. })));
+        assert!(matches!(
+            moderation.check_spam_filters(&past_link, None).await,
+            Some(ModerationAction::TimeoutUser { duration_seconds: 600 })
+        ));
+    }
+}
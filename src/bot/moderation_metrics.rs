@@ -0,0 +1,193 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt::Write;
+
+/// Seconds of traffic the rolling block rate covers
+pub const BLOCK_RATE_WINDOW_SECONDS: i64 = 60;
+
+/// Messages checked and blocked during one second
+#[derive(Debug, Clone, Copy)]
+struct SecondBucket {
+    second: i64,
+    checked: u64,
+    blocked: u64,
+}
+
+/// Live moderation throughput counters (real traffic, unlike the adaptive metrics)
+#[derive(Debug)]
+pub struct ModerationMetrics {
+    messages_checked: u64,
+    messages_blocked: u64,
+    blocks_by_filter: BTreeMap<String, u64>,
+    window_seconds: i64,
+    recent: VecDeque<SecondBucket>,
+}
+
+impl Default for ModerationMetrics {
+    fn default() -> Self {
+        Self::new(BLOCK_RATE_WINDOW_SECONDS)
+    }
+}
+
+impl ModerationMetrics {
+    pub fn new(window_seconds: i64) -> Self {
+        Self {
+            messages_checked: 0,
+            messages_blocked: 0,
+            blocks_by_filter: BTreeMap::new(),
+            window_seconds: window_seconds.max(1),
+            recent: VecDeque::new(),
+        }
+    }
+
+    /// Record a message run through the filters, and the filter that blocked it if any
+    pub fn record(&mut self, blocked_by: Option<&str>, now: DateTime<Utc>) {
+        self.messages_checked += 1;
+        if let Some(filter_name) = blocked_by {
+            self.messages_blocked += 1;
+            *self.blocks_by_filter.entry(filter_name.to_string()).or_default() += 1;
+        }
+
+        let second = now.timestamp();
+        match self.recent.back_mut() {
+            Some(bucket) if bucket.second == second => {
+                bucket.checked += 1;
+                bucket.blocked += blocked_by.is_some() as u64;
+            }
+            _ => self.recent.push_back(SecondBucket {
+                second,
+                checked: 1,
+                blocked: blocked_by.is_some() as u64,
+            }),
+        }
+        self.expire(second);
+    }
+
+    pub fn snapshot(&mut self, now: DateTime<Utc>) -> ModerationMetricsSnapshot {
+        self.expire(now.timestamp());
+        let window_checked: u64 = self.recent.iter().map(|b| b.checked).sum();
+        let window_blocked: u64 = self.recent.iter().map(|b| b.blocked).sum();
+
+        ModerationMetricsSnapshot {
+            messages_checked: self.messages_checked,
+            messages_blocked: self.messages_blocked,
+            blocks_by_filter: self.blocks_by_filter.clone(),
+            window_seconds: self.window_seconds as u64,
+            window_checked,
+            window_blocked,
+            block_rate: if window_checked == 0 { 0.0 } else { window_blocked as f64 / window_checked as f64 },
+            tasks_in_flight: 0,
+        }
+    }
+
+    fn expire(&mut self, current_second: i64) {
+        while self.recent.front().is_some_and(|b| current_second - b.second >= self.window_seconds) {
+            self.recent.pop_front();
+        }
+    }
+}
+
+/// Point-in-time view of the moderation counters
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ModerationMetricsSnapshot {
+    pub messages_checked: u64,
+    pub messages_blocked: u64,
+    pub blocks_by_filter: BTreeMap<String, u64>,
+    pub window_seconds: u64,
+    /// Messages checked and blocked within the rolling window
+    pub window_checked: u64,
+    pub window_blocked: u64,
+    /// Share of messages blocked within the rolling window (0.0 to 1.0)
+    pub block_rate: f64,
+    /// Moderation actions and responses being sent right now
+    #[serde(default)]
+    pub tasks_in_flight: u64,
+}
+
+impl ModerationMetricsSnapshot {
+    /// Prometheus text exposition format
+    pub fn to_prometheus(&self) -> String {
+        let mut out = String::new();
+        let mut metric = |name: &str, kind: &str, help: &str, samples: Vec<(String, String)>| {
+            let _ = writeln!(out, "# HELP notabot_{} {}", name, help);
+            let _ = writeln!(out, "# TYPE notabot_{} {}", name, kind);
+            for (labels, value) in samples {
+                let _ = writeln!(out, "notabot_{}{} {}", name, labels, value);
+            }
+        };
+
+        metric("moderation_messages_checked_total", "counter", "Messages run through the spam filters",
+               vec![(String::new(), self.messages_checked.to_string())]);
+        metric("moderation_messages_blocked_total", "counter", "Messages flagged by a spam filter",
+               vec![(String::new(), self.messages_blocked.to_string())]);
+        metric("moderation_filter_blocks_total", "counter", "Messages flagged, by filter",
+               self.blocks_by_filter.iter()
+                   .map(|(filter, count)| (format!("{{filter=\"{}\"}}", escape_label(filter)), count.to_string()))
+                   .collect());
+        metric("moderation_block_rate", "gauge",
+               &format!("Share of messages blocked over the last {} seconds", self.window_seconds),
+               vec![(String::new(), self.block_rate.to_string())]);
+        metric("moderation_tasks_in_flight", "gauge", "Moderation actions and responses being sent",
+               vec![(String::new(), self.tasks_in_flight.to_string())]);
+        out
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_block_rate_rolls_over_the_window() {
+        let mut metrics = ModerationMetrics::new(60);
+        let start = Utc::now();
+
+        for i in 0..8 {
+            metrics.record(None, start + Duration::seconds(i));
+        }
+        metrics.record(Some("caps"), start + Duration::seconds(10));
+        metrics.record(Some("caps"), start + Duration::seconds(10));
+
+        let snapshot = metrics.snapshot(start + Duration::seconds(20));
+        assert_eq!(snapshot.messages_checked, 10);
+        assert_eq!(snapshot.messages_blocked, 2);
+        assert_eq!(snapshot.block_rate, 0.2);
+
+        // A spike after the quiet traffic has aged out of the window
+        for _ in 0..3 {
+            metrics.record(Some("links"), start + Duration::seconds(70));
+        }
+        metrics.record(None, start + Duration::seconds(71));
+        let snapshot = metrics.snapshot(start + Duration::seconds(71));
+        assert_eq!(snapshot.window_checked, 4);
+        assert_eq!(snapshot.window_blocked, 3);
+        assert_eq!(snapshot.block_rate, 0.75);
+        // Totals keep counting
+        assert_eq!(snapshot.messages_checked, 14);
+        assert_eq!(snapshot.blocks_by_filter.get("caps"), Some(&2));
+        assert_eq!(snapshot.blocks_by_filter.get("links"), Some(&3));
+
+        assert_eq!(metrics.snapshot(start + Duration::seconds(500)).block_rate, 0.0);
+    }
+
+    #[test]
+    fn test_prometheus_output() {
+        let mut metrics = ModerationMetrics::default();
+        let now = Utc::now();
+        metrics.record(None, now);
+        metrics.record(Some("say \"hi\""), now);
+
+        let text = metrics.snapshot(now).to_prometheus();
+        assert!(text.contains("# TYPE notabot_moderation_messages_checked_total counter\nnotabot_moderation_messages_checked_total 2\n"));
+        assert!(text.contains("notabot_moderation_messages_blocked_total 1\n"));
+        assert!(text.contains("notabot_moderation_filter_blocks_total{filter=\"say \\\"hi\\\"\"} 1\n"));
+        assert!(text.contains("notabot_moderation_block_rate 0.5\n"));
+        assert!(text.contains("# TYPE notabot_moderation_tasks_in_flight gauge\nnotabot_moderation_tasks_in_flight 0\n"));
+    }
+}
@@ -1,8 +1,11 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
 use unicode_normalization::UnicodeNormalization;
-use log::debug;
+use log::{debug, warn};
 use base64::engine::{Engine, general_purpose};
 
+use crate::types::{MatchExplanation, MatchSpan};
+
 /// Enhanced pattern matching capabilities that go far beyond NightBot
 #[derive(Debug, Clone)]
 pub enum AdvancedPattern {
@@ -32,9 +35,19 @@ pub enum AdvancedPattern {
     
     /// Base64/URL encoded content detection
     EncodedContent(String),
+
+    /// Short affirmative reply ("yes do it") to a recent message in the channel
+    /// that matches `context_pattern`. Needs channel context; never matches alone.
+    ContextualReply { context_pattern: String, max_reply_words: usize },
 }
 
-#[derive(Debug, Clone)]
+/// Phrases that endorse or encourage whatever was said before them
+const AFFIRMATIVE_REPLIES: &[&str] = &[
+    "yes", "yeah", "yep", "ya", "do it", "go for it", "go ahead", "you should",
+    "agreed", "same", "+1", "this", "based", "true", "facts",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum KeyboardLayout {
     Qwerty,
     Azerty,
@@ -42,12 +55,91 @@ pub enum KeyboardLayout {
     Dvorak,
 }
 
+impl KeyboardLayout {
+    /// Parse a layout name from configuration (case-insensitive)
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "qwerty" => Some(KeyboardLayout::Qwerty),
+            "azerty" => Some(KeyboardLayout::Azerty),
+            "qwertz" => Some(KeyboardLayout::Qwertz),
+            "dvorak" => Some(KeyboardLayout::Dvorak),
+            _ => None,
+        }
+    }
+
+    /// Key rows from top to bottom. Each row is staggered half a key to the
+    /// right of the one above it, as on a physical keyboard.
+    fn rows(&self) -> &'static [&'static str] {
+        match self {
+            KeyboardLayout::Qwerty => &["1234567890-=", "qwertyuiop[]", "asdfghjkl;'", "zxcvbnm,./"],
+            KeyboardLayout::Azerty => &["1234567890", "azertyuiop", "qsdfghjklm", "wxcvbn,;:!"],
+            KeyboardLayout::Qwertz => &["1234567890", "qwertzuiopü", "asdfghjklöä", "yxcvbnm,.-"],
+            KeyboardLayout::Dvorak => &["1234567890[]", "',.pyfgcrl/=", "aoeuidhtns-", ";qjkxbmwvz"],
+        }
+    }
+
+    /// Find the (row, column) position of a key
+    fn position(&self, key: char) -> Option<(usize, usize)> {
+        self.rows().iter().enumerate().find_map(|(row, keys)| {
+            keys.chars().position(|k| k == key).map(|col| (row, col))
+        })
+    }
+
+    /// Keys physically adjacent to `key` on this layout
+    pub fn adjacent_keys(&self, key: char) -> Vec<char> {
+        let Some((row, col)) = self.position(key) else {
+            return Vec::new();
+        };
+        let rows = self.rows();
+        let key_at = |r: usize, c: usize| rows.get(r).and_then(|keys| keys.chars().nth(c));
+
+        // Same row left/right, row above (col, col+1), row below (col-1, col)
+        let mut neighbours = Vec::new();
+        neighbours.extend(col.checked_sub(1).and_then(|c| key_at(row, c)));
+        neighbours.extend(key_at(row, col + 1));
+        if let Some(above) = row.checked_sub(1) {
+            neighbours.extend(key_at(above, col));
+            neighbours.extend(key_at(above, col + 1));
+        }
+        neighbours.extend(col.checked_sub(1).and_then(|c| key_at(row + 1, c)));
+        neighbours.extend(key_at(row + 1, col));
+        neighbours
+    }
+
+    /// Check whether two keys are next to each other on this layout
+    pub fn is_adjacent(&self, a: char, b: char) -> bool {
+        self.adjacent_keys(a).contains(&b)
+    }
+}
+
 impl AdvancedPattern {
+    /// Pattern type as written in pattern configuration (`pattern_type`)
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AdvancedPattern::FuzzyMatch { .. } => "fuzzy_match",
+            AdvancedPattern::Phonetic(_) => "phonetic",
+            AdvancedPattern::Leetspeak(_) => "leetspeak",
+            AdvancedPattern::UnicodeNormalized(_) => "unicode_normalized",
+            AdvancedPattern::ZalgoText => "zalgo_text",
+            AdvancedPattern::Homoglyph(_) => "homoglyph",
+            AdvancedPattern::KeyboardShift { .. } => "keyboard_shift",
+            AdvancedPattern::RepeatedCharCompression(_) => "repeated_char_compression",
+            AdvancedPattern::EncodedContent(_) => "encoded_content",
+            AdvancedPattern::ContextualReply { .. } => "contextual_reply",
+        }
+    }
+
     /// Check if this advanced pattern matches the given text
     pub fn matches(&self, text: &str) -> bool {
+        self.matches_scaled(text, 1.0)
+    }
+
+    /// Check for a match with similarity thresholds multiplied by `threshold_scale`
+    /// (values below 1.0 loosen matching, values above 1.0 tighten it)
+    pub fn matches_scaled(&self, text: &str, threshold_scale: f32) -> bool {
         match self {
             AdvancedPattern::FuzzyMatch { pattern, threshold } => {
-                Self::fuzzy_match(text, pattern, *threshold)
+                Self::fuzzy_match(text, pattern, (*threshold * threshold_scale).clamp(0.0, 1.0))
             }
             AdvancedPattern::Phonetic(pattern) => {
                 Self::phonetic_match(text, pattern)
@@ -73,9 +165,121 @@ impl AdvancedPattern {
             AdvancedPattern::EncodedContent(pattern) => {
                 Self::encoded_content_match(text, pattern)
             }
+            AdvancedPattern::ContextualReply { .. } => false,
         }
     }
 
+    /// How sure a match of this pattern on `text` is, from 0.0 to 1.0. Exact kinds
+    /// (leetspeak, homoglyphs, ...) are certain; the approximate ones score how
+    /// close the match came:
+    /// - fuzzy: the best word similarity
+    /// - phonetic: 0.5 for a word that only sounds alike, up to 1.0 as the spelling converges
+    /// - keyboard shift: 0.5 when every key is shifted, up to 1.0 as more keys are exact
+    /// - contextual reply: 0.9 when replying to the previous message, less for older ones
+    pub fn match_confidence(&self, text: &str, context: &[String]) -> f32 {
+        match self {
+            AdvancedPattern::FuzzyMatch { pattern, .. } => {
+                let pattern_lower = pattern.to_lowercase();
+                text.to_lowercase().split_whitespace()
+                    .map(|word| Self::calculate_similarity(word, &pattern_lower))
+                    .fold(0.0, f32::max)
+            }
+            AdvancedPattern::Phonetic(pattern) => {
+                let pattern_lower = pattern.to_lowercase();
+                let pattern_soundex = Self::soundex(&pattern_lower);
+                text.to_lowercase().split_whitespace()
+                    .filter(|word| Self::soundex(word) == pattern_soundex)
+                    .map(|word| 0.5 + 0.5 * Self::calculate_similarity(word, &pattern_lower))
+                    .fold(0.0, f32::max)
+            }
+            AdvancedPattern::KeyboardShift { pattern, layouts } => {
+                0.5 + 0.5 * Self::keyboard_shift_exactness(text, pattern, layouts)
+            }
+            AdvancedPattern::ContextualReply { context_pattern, .. } => {
+                let pattern_lower = context_pattern.to_lowercase();
+                context.iter().rev()
+                    .position(|prior| prior.to_lowercase().contains(&pattern_lower))
+                    .map_or(0.0, |age| (0.9 - 0.1 * age as f32).max(0.5))
+            }
+            _ => 1.0,
+        }
+    }
+
+    /// Whether this is a similarity-based pattern that tends to misfire on very
+    /// short messages ("gg" is a fuzzy/phonetic match for plenty of words)
+    pub fn is_approximate(&self) -> bool {
+        matches!(self,
+            AdvancedPattern::FuzzyMatch { .. } | AdvancedPattern::Phonetic(_) | AdvancedPattern::KeyboardShift { .. })
+    }
+
+    /// Whether this pattern needs previous channel messages to be evaluated
+    pub fn uses_context(&self) -> bool {
+        matches!(self, AdvancedPattern::ContextualReply { .. })
+    }
+
+    /// Check for a match given the previous messages in the channel (oldest first).
+    /// Patterns that don't use context ignore it.
+    pub fn matches_in_context(&self, text: &str, context: &[String], threshold_scale: f32) -> bool {
+        match self {
+            AdvancedPattern::ContextualReply { context_pattern, max_reply_words } => {
+                Self::contextual_reply_match(text, context, context_pattern, *max_reply_words)
+            }
+            _ => self.matches_scaled(text, threshold_scale),
+        }
+    }
+
+    /// Short affirmative reply to a message containing the context pattern
+    fn contextual_reply_match(text: &str, context: &[String], context_pattern: &str, max_reply_words: usize) -> bool {
+        let text_lower = text.to_lowercase();
+        let word_count = text_lower.split_whitespace().count();
+        if word_count == 0 || word_count > max_reply_words {
+            return false;
+        }
+
+        let is_affirmative = AFFIRMATIVE_REPLIES.iter().any(|phrase| {
+            if phrase.contains(' ') || !phrase.chars().all(char::is_alphanumeric) {
+                text_lower.contains(phrase)
+            } else {
+                text_lower.split(|c: char| !c.is_alphanumeric()).any(|word| word == *phrase)
+            }
+        });
+        if !is_affirmative {
+            return false;
+        }
+
+        let pattern_lower = context_pattern.to_lowercase();
+        let found = context.iter().any(|prior| prior.to_lowercase().contains(&pattern_lower));
+        if found {
+            debug!("Contextual reply match: '{}' replying to context containing '{}'", text, context_pattern);
+        }
+        found
+    }
+
+    /// Shortest run of words in `text` that this pattern matches on its own.
+    /// `None` when the pattern doesn't match, or judges the message as a whole
+    /// (zalgo, encoded payloads, replies in context).
+    pub fn find_span(&self, text: &str) -> Option<MatchSpan> {
+        if matches!(self, AdvancedPattern::ZalgoText | AdvancedPattern::EncodedContent(_) | AdvancedPattern::ContextualReply { .. })
+            || !self.matches(text) {
+            return None;
+        }
+
+        let words: Vec<(usize, usize)> = text.split_whitespace()
+            .map(|word| {
+                let start = word.as_ptr() as usize - text.as_ptr() as usize;
+                (start, start + word.len())
+            })
+            .collect();
+
+        // Grow the run until it matches, then drop leading words it doesn't need
+        let end = words.iter().position(|&(_, end)| self.matches(&text[words[0].0..end]))?;
+        let mut start = 0;
+        while start < end && self.matches(&text[words[start + 1].0..words[end].1]) {
+            start += 1;
+        }
+        Some(MatchSpan::new(text, words[start].0..words[end].1))
+    }
+
     /// Fuzzy string matching using Levenshtein distance
     fn fuzzy_match(text: &str, pattern: &str, threshold: f32) -> bool {
         let text_lower = text.to_lowercase();
@@ -298,26 +502,67 @@ impl AdvancedPattern {
             .collect()
     }
 
-    /// Keyboard layout shift detection
-    fn keyboard_shift_match(text: &str, pattern: &str, _layouts: &[KeyboardLayout]) -> bool {
-        // Simplified implementation - check common QWERTY shifts
-        let shifted_pattern = Self::apply_qwerty_shift(pattern);
-        text.to_lowercase().contains(&shifted_pattern.to_lowercase()) ||
-        text.to_lowercase().contains(&pattern.to_lowercase())
+    /// Keyboard layout shift detection.
+    ///
+    /// A window of the text matches when every character either equals the
+    /// pattern character or sits on an adjacent key of one of the configured
+    /// layouts (QWERTY when none are given). Very short patterns only match
+    /// literally, since almost any two-letter word is one key away from another.
+    fn keyboard_shift_match(text: &str, pattern: &str, layouts: &[KeyboardLayout]) -> bool {
+        const MIN_SHIFT_PATTERN_LEN: usize = 3;
+
+        let text_lower = text.to_lowercase();
+        let pattern_chars: Vec<char> = pattern.to_lowercase().chars().collect();
+        if pattern_chars.is_empty() {
+            return false;
+        }
+        if text_lower.contains(&pattern.to_lowercase()) {
+            return true;
+        }
+        if pattern_chars.len() < MIN_SHIFT_PATTERN_LEN {
+            return false;
+        }
+
+        let layouts = if layouts.is_empty() { &[KeyboardLayout::Qwerty][..] } else { layouts };
+
+        text_lower.split_whitespace().any(|word| {
+            let word_chars: Vec<char> = word.chars().collect();
+            word_chars.windows(pattern_chars.len()).any(|window| {
+                layouts.iter().any(|layout| {
+                    window.iter().zip(&pattern_chars)
+                        .all(|(&typed, &expected)| typed == expected || layout.is_adjacent(expected, typed))
+                })
+            })
+        })
     }
 
-    /// Apply common QWERTY keyboard shifts
-    fn apply_qwerty_shift(text: &str) -> String {
-        let shift_map: HashMap<char, char> = [
-            ('q', 'a'), ('w', 's'), ('e', 'd'), ('r', 'f'), ('t', 'g'),
-            ('y', 'h'), ('u', 'j'), ('i', 'k'), ('o', 'l'), ('p', ';'),
-            ('a', 'q'), ('s', 'w'), ('d', 'e'), ('f', 'r'), ('g', 't'),
-            ('h', 'y'), ('j', 'u'), ('k', 'i'), ('l', 'o'), (';', 'p'),
-        ].iter().cloned().collect();
+    /// Fraction of characters typed exactly in the closest keyboard-shifted
+    /// occurrence of `pattern` in `text` (1.0 when it appears unshifted)
+    fn keyboard_shift_exactness(text: &str, pattern: &str, layouts: &[KeyboardLayout]) -> f32 {
+        let text_lower = text.to_lowercase();
+        let pattern_chars: Vec<char> = pattern.to_lowercase().chars().collect();
+        if pattern_chars.is_empty() {
+            return 0.0;
+        }
+        if text_lower.contains(&pattern.to_lowercase()) {
+            return 1.0;
+        }
 
-        text.chars()
-            .map(|c| shift_map.get(&c.to_lowercase().next().unwrap_or(c)).copied().unwrap_or(c))
-            .collect()
+        let layouts = if layouts.is_empty() { &[KeyboardLayout::Qwerty][..] } else { layouts };
+        let mut best = 0;
+        for word in text_lower.split_whitespace() {
+            let word_chars: Vec<char> = word.chars().collect();
+            for window in word_chars.windows(pattern_chars.len()) {
+                let shifted = layouts.iter().any(|layout| {
+                    window.iter().zip(&pattern_chars)
+                        .all(|(&typed, &expected)| typed == expected || layout.is_adjacent(expected, typed))
+                });
+                if shifted {
+                    best = best.max(window.iter().zip(&pattern_chars).filter(|(typed, expected)| typed == expected).count());
+                }
+            }
+        }
+        best as f32 / pattern_chars.len() as f32
     }
 
     /// Repeated character compression matching
@@ -354,36 +599,274 @@ impl AdvancedPattern {
         result
     }
 
-    /// Encoded content detection (Base64, URL encoding, etc.)
+    /// Encoded content detection (Base64, hex, URL encoding)
     fn encoded_content_match(text: &str, pattern: &str) -> bool {
-        // Check Base64 patterns
-        // base64::decode is deprecated, use general_purpose::STANDARD.decode
-        // if let Ok(decoded) = base64::decode(text) {  
-        if let Ok(decoded) = general_purpose::STANDARD.decode(text) {
-            if let Ok(decoded_str) = String::from_utf8(decoded) {
-                if decoded_str.to_lowercase().contains(&pattern.to_lowercase()) {
-                    debug!("Base64 encoded content match found: {} -> {}", text, decoded_str);
-                    return true;
+        let pattern_lower = pattern.to_lowercase();
+        decode_embedded_payloads(text).into_iter().any(|decoded| {
+            let found = decoded.to_lowercase().contains(&pattern_lower);
+            if found {
+                debug!("Encoded content match found: {} -> {}", text, decoded);
+            }
+            found
+        })
+    }
+}
+
+/// Shortest token considered for base64/hex decoding; shorter runs are too
+/// likely to be ordinary words that happen to fit the charset.
+const MIN_ENCODED_TOKEN_LEN: usize = 8;
+
+/// Minimum share of printable characters a decoded payload must have to be
+/// treated as text rather than garbage.
+const MIN_PRINTABLE_RATIO: f32 = 0.9;
+
+/// Find substrings of `text` that look like base64, hex or percent-encoded
+/// payloads and return their decoded forms. Tokens that decode to binary or
+/// non-printable data are discarded.
+pub fn decode_embedded_payloads(text: &str) -> Vec<String> {
+    let mut decoded = Vec::new();
+
+    for token in text.split_whitespace() {
+        let token = token.trim_matches(|c: char| matches!(c, '"' | '\'' | '(' | ')' | '<' | '>' | '[' | ']' | ',' | ';'));
+
+        if token.contains('%') {
+            if let Ok(url_decoded) = urlencoding::decode(token) {
+                if url_decoded != token && is_printable_text(&url_decoded) {
+                    decoded.push(url_decoded.into_owned());
                 }
             }
         }
 
-        // Check URL encoding
-        if let Ok(decoded) = urlencoding::decode(text) {
-            if decoded.to_lowercase().contains(&pattern.to_lowercase()) {
-                debug!("URL encoded content match found: {} -> {}", text, decoded);
-                return true;
-            }
+        if token.len() < MIN_ENCODED_TOKEN_LEN {
+            continue;
         }
 
-        false
+        if let Some(text) = decode_hex_token(token) {
+            decoded.push(text);
+        } else if let Some(text) = decode_base64_token(token) {
+            decoded.push(text);
+        }
+    }
+
+    decoded
+}
+
+fn decode_hex_token(token: &str) -> Option<String> {
+    let hex = token.strip_prefix("0x").unwrap_or(token);
+    if hex.len() < MIN_ENCODED_TOKEN_LEN || !hex.len().is_multiple_of(2) || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
     }
+
+    let bytes = (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16))
+        .collect::<Result<Vec<u8>, _>>()
+        .ok()?;
+
+    String::from_utf8(bytes).ok().filter(|s| is_printable_text(s))
+}
+
+fn decode_base64_token(token: &str) -> Option<String> {
+    let is_standard = token.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '/' | '='));
+    let is_url_safe = token.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '='));
+    if !is_standard && !is_url_safe {
+        return None;
+    }
+
+    let bytes = if is_standard {
+        general_purpose::STANDARD.decode(token)
+            .or_else(|_| general_purpose::STANDARD_NO_PAD.decode(token.trim_end_matches('=')))
+    } else {
+        general_purpose::URL_SAFE.decode(token)
+            .or_else(|_| general_purpose::URL_SAFE_NO_PAD.decode(token.trim_end_matches('=')))
+    }.ok()?;
+
+    String::from_utf8(bytes).ok().filter(|s| is_printable_text(s))
+}
+
+/// Heuristic check that decoded bytes form readable text
+fn is_printable_text(text: &str) -> bool {
+    let total = text.chars().count();
+    if total == 0 || !text.chars().any(|c| c.is_alphabetic()) {
+        return false;
+    }
+
+    let printable = text.chars()
+        .filter(|c| !c.is_control() || c.is_whitespace())
+        .filter(|c| c.is_ascii_graphic() || c.is_whitespace() || c.is_alphabetic())
+        .count();
+
+    printable as f32 / total as f32 >= MIN_PRINTABLE_RATIO
+}
+
+/// Which transformations [`normalize`] applies
+///
+/// Enabled steps always run in this order:
+/// 1. `unicode_fold` - canonical decomposition (NFD), then combining marks are
+///    dropped, so accents and Zalgo stacking disappear ("café" -> "cafe")
+/// 2. `homoglyphs` - Cyrillic, Greek, mathematical and fullwidth lookalikes
+///    become ASCII ("раураl" with Cyrillic letters -> "paypal")
+/// 3. `leetspeak` - digit and symbol substitutions become letters ("fr33" -> "free")
+/// 4. `lowercase` - case folding
+///
+/// Homoglyphs run before leetspeak so fullwidth digits are folded like ASCII ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NormalizeOptions {
+    pub unicode_fold: bool,
+    pub homoglyphs: bool,
+    pub leetspeak: bool,
+    pub lowercase: bool,
+}
+
+impl Default for NormalizeOptions {
+    fn default() -> Self {
+        Self {
+            unicode_fold: true,
+            homoglyphs: true,
+            leetspeak: true,
+            lowercase: true,
+        }
+    }
+}
+
+impl NormalizeOptions {
+    /// No transformations; the text is returned unchanged
+    pub fn none() -> Self {
+        Self {
+            unicode_fold: false,
+            homoglyphs: false,
+            leetspeak: false,
+            lowercase: false,
+        }
+    }
+}
+
+/// Text after [`normalize`], alongside the input it came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NormalizedText {
+    original: String,
+    normalized: String,
+}
+
+impl NormalizedText {
+    pub fn as_str(&self) -> &str {
+        &self.normalized
+    }
+
+    pub fn original(&self) -> &str {
+        &self.original
+    }
+
+    /// Whether normalization changed anything
+    pub fn is_changed(&self) -> bool {
+        self.original != self.normalized
+    }
+
+    pub fn into_string(self) -> String {
+        self.normalized
+    }
+}
+
+impl AsRef<str> for NormalizedText {
+    fn as_ref(&self) -> &str {
+        &self.normalized
+    }
+}
+
+impl std::fmt::Display for NormalizedText {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.normalized)
+    }
+}
+
+/// Apply the same normalization the matchers use, independently of any pattern
+///
+/// See [`NormalizeOptions`] for the order transformations run in.
+pub fn normalize(text: &str, options: NormalizeOptions) -> NormalizedText {
+    let mut normalized = text.to_string();
+
+    if options.unicode_fold {
+        normalized = normalized.nfd()
+            .filter(|c| !AdvancedPattern::is_combining_mark(*c))
+            .collect();
+    }
+    if options.homoglyphs {
+        normalized = AdvancedPattern::normalize_homoglyphs(&normalized);
+    }
+    if options.leetspeak {
+        normalized = AdvancedPattern::normalize_leetspeak(&normalized);
+    }
+    if options.lowercase {
+        normalized = normalized.to_lowercase();
+    }
+
+    NormalizedText {
+        original: text.to_string(),
+        normalized,
+    }
+}
+
+/// What to do when a pattern collection runs past its processing time budget
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeoutFallback {
+    /// Skip the collection's remaining patterns and allow the message
+    FailOpen,
+    /// Skip the remaining patterns and flag the message for moderator review
+    FailClosed,
+}
+
+impl TimeoutFallback {
+    /// Parse a fallback from configuration (case-insensitive)
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "fail_open" | "allow" => Some(TimeoutFallback::FailOpen),
+            "fail_closed" | "flag" => Some(TimeoutFallback::FailClosed),
+            _ => None,
+        }
+    }
+}
+
+/// Processing time budget for a pattern collection
+#[derive(Debug, Clone)]
+pub struct CollectionBudget {
+    pub max_processing_time: Duration,
+    pub fallback: TimeoutFallback,
+}
+
+/// Outcome of evaluating text against all patterns
+#[derive(Debug, Clone, Default)]
+pub struct PatternEvaluation {
+    /// Ids of the patterns that matched (see [`EnhancedPatternMatcher::pattern_id`])
+    pub matches: Vec<String>,
+    /// Confidence of each match (see [`AdvancedPattern::match_confidence`]), in the order of `matches`
+    pub confidences: Vec<f32>,
+    /// Collections that exceeded their time budget
+    pub timed_out_collections: Vec<String>,
+    /// Timed-out collections configured to fail closed
+    pub flagged_for_review: Vec<String>,
+    /// Evaluation stopped because the match cap was reached
+    pub match_cap_reached: bool,
 }
 
 /// Enhanced pattern matching system that combines multiple detection methods
 pub struct EnhancedPatternMatcher {
     pub patterns: Vec<AdvancedPattern>,
     effectiveness_stats: HashMap<String, PatternStats>,
+    /// Configured id of each pattern (by index); others are named by kind and index
+    pattern_ids: HashMap<usize, String>,
+    /// Collection that each pattern (by index) was loaded from
+    pattern_collections: HashMap<usize, String>,
+    collection_budgets: HashMap<String, CollectionBudget>,
+    /// Messages shorter than this (in characters) skip approximate patterns
+    min_message_length: usize,
+    /// Per-pattern minimum length (by index), overriding the global one
+    pattern_min_lengths: HashMap<usize, usize>,
+    /// Collections switched off; their patterns are kept but not evaluated
+    disabled_collections: HashSet<String>,
+    /// Stop evaluating a message after this many matches (0 = no limit)
+    max_matches_per_message: usize,
+    /// Patterns (by index) that opt in or out of reputation scaling, overriding the system-wide setting
+    reputation_scaling: HashMap<usize, bool>,
 }
 
 #[derive(Debug, Clone)]
@@ -399,13 +882,26 @@ impl EnhancedPatternMatcher {
         Self {
             patterns: Vec::new(),
             effectiveness_stats: HashMap::new(),
+            pattern_ids: HashMap::new(),
+            pattern_collections: HashMap::new(),
+            collection_budgets: HashMap::new(),
+            min_message_length: 0,
+            pattern_min_lengths: HashMap::new(),
+            disabled_collections: HashSet::new(),
+            max_matches_per_message: 0,
+            reputation_scaling: HashMap::new(),
         }
     }
 
+    /// Stop evaluating a message once this many patterns have matched (0 = no limit)
+    pub fn set_max_matches_per_message(&mut self, max_matches: usize) {
+        self.max_matches_per_message = max_matches;
+    }
+
     /// Add an advanced pattern to the matcher
     pub fn add_pattern(&mut self, pattern: AdvancedPattern) {
-        let pattern_id = format!("{:?}", pattern);
         self.patterns.push(pattern);
+        let pattern_id = self.pattern_id(self.patterns.len() - 1);
         self.effectiveness_stats.insert(pattern_id, PatternStats {
             matches: 0,
             false_positives: 0,
@@ -414,14 +910,154 @@ impl EnhancedPatternMatcher {
         });
     }
 
+    /// Add a pattern that belongs to a named collection, so it counts against
+    /// that collection's time budget
+    pub fn add_collection_pattern(&mut self, collection_id: &str, pattern: AdvancedPattern) {
+        self.add_pattern(pattern);
+        self.pattern_collections.insert(self.patterns.len() - 1, collection_id.to_string());
+    }
+
+    /// Add a collection pattern with its own minimum message length, which applies
+    /// whether or not the pattern is approximate
+    pub fn add_collection_pattern_with_min_length(&mut self, collection_id: &str, pattern: AdvancedPattern, min_message_length: usize) {
+        self.add_collection_pattern(collection_id, pattern);
+        self.pattern_min_lengths.insert(self.patterns.len() - 1, min_message_length);
+    }
+
+    /// Add a pattern under the id it was configured with, which is what matches,
+    /// statistics and explanations then report it as
+    pub fn add_configured_pattern(&mut self, collection_id: &str, pattern_id: &str, pattern: AdvancedPattern, min_message_length: Option<usize>) {
+        self.pattern_ids.insert(self.patterns.len(), pattern_id.to_string());
+        match min_message_length {
+            Some(min_length) => self.add_collection_pattern_with_min_length(collection_id, pattern, min_length),
+            None => self.add_collection_pattern(collection_id, pattern),
+        }
+    }
+
+    /// Stable name for the pattern at `index`: its configured id, or its kind and
+    /// index (`leetspeak#2`). Never includes the pattern text, so it's safe to show in chat.
+    pub fn pattern_id(&self, index: usize) -> String {
+        match self.pattern_ids.get(&index) {
+            Some(id) => id.clone(),
+            None => format!("{}#{}", self.patterns[index].kind(), index),
+        }
+    }
+
+    /// Scale one pattern's thresholds by the sender's reputation, or never, regardless
+    /// of the system-wide setting. Returns false if no pattern has that id.
+    pub fn set_reputation_scaling(&mut self, pattern_id: &str, enabled: bool) -> bool {
+        let Some(index) = (0..self.patterns.len()).find(|i| self.pattern_id(*i) == pattern_id) else {
+            return false;
+        };
+        self.reputation_scaling.insert(index, enabled);
+        true
+    }
+
+    /// Skip approximate patterns (see [`AdvancedPattern::is_approximate`]) for
+    /// messages shorter than `chars`; 0 evaluates every message
+    pub fn set_min_message_length(&mut self, chars: usize) {
+        self.min_message_length = chars;
+    }
+
+    /// Set the processing time budget for a collection
+    pub fn set_collection_budget(&mut self, collection_id: &str, budget: CollectionBudget) {
+        self.collection_budgets.insert(collection_id.to_string(), budget);
+    }
+
+    /// Turn a collection's patterns on or off without removing them.
+    /// Returns false if no pattern belongs to the collection.
+    pub fn set_collection_enabled(&mut self, collection_id: &str, enabled: bool) -> bool {
+        if !self.pattern_collections.values().any(|c| c == collection_id) {
+            return false;
+        }
+        if enabled {
+            self.disabled_collections.remove(collection_id);
+        } else {
+            self.disabled_collections.insert(collection_id.to_string());
+        }
+        true
+    }
+
+    pub fn is_collection_enabled(&self, collection_id: &str) -> bool {
+        !self.disabled_collections.contains(collection_id)
+    }
+
+    /// Loaded collections and whether each is enabled, sorted by name
+    pub fn collections(&self) -> Vec<(String, bool)> {
+        let names: std::collections::BTreeSet<&String> = self.pattern_collections.values().collect();
+        names.into_iter()
+            .map(|name| (name.clone(), self.is_collection_enabled(name)))
+            .collect()
+    }
+
     /// Check if text matches any of the advanced patterns
     pub fn matches(&mut self, text: &str) -> Vec<String> {
-        let mut matches = Vec::new();
+        self.matches_scaled(text, 1.0)
+    }
+
+    /// Check text against all patterns with thresholds scaled by `threshold_scale`
+    pub fn matches_scaled(&mut self, text: &str, threshold_scale: f32) -> Vec<String> {
+        self.evaluate(text, threshold_scale).matches
+    }
+
+    /// Evaluate text against all patterns without channel context
+    pub fn evaluate(&mut self, text: &str, threshold_scale: f32) -> PatternEvaluation {
+        self.evaluate_with_context(text, &[], threshold_scale)
+    }
+
+    /// Evaluate text against all patterns, enforcing collection time budgets.
+    /// `context` holds previous channel messages for context-aware patterns.
+    ///
+    /// Budgets are checked between patterns: once a collection's accumulated
+    /// time exceeds its budget, its remaining patterns are skipped and the
+    /// collection's fallback is reported. A single slow pattern is not interrupted.
+    pub fn evaluate_with_context(&mut self, text: &str, context: &[String], threshold_scale: f32) -> PatternEvaluation {
+        self.evaluate_for_user(text, context, threshold_scale, 1.0, false)
+    }
+
+    /// Evaluate text from a user whose reputation scales thresholds by `reputation_scale`,
+    /// on top of `threshold_scale`. Patterns without their own reputation setting are
+    /// scaled when `scale_by_default` is set.
+    pub fn evaluate_for_user(
+        &mut self,
+        text: &str,
+        context: &[String],
+        threshold_scale: f32,
+        reputation_scale: f32,
+        scale_by_default: bool,
+    ) -> PatternEvaluation {
+        let mut evaluation = PatternEvaluation::default();
+        let mut time_spent: HashMap<&str, Duration> = HashMap::new();
+        let text_length = text.trim().chars().count();
         
         for (i, pattern) in self.patterns.iter().enumerate() {
-            if pattern.matches(text) {
-                let pattern_id = format!("{:?}", pattern);
-                matches.push(pattern_id.clone());
+            let min_length = match self.pattern_min_lengths.get(&i) {
+                Some(min_length) => *min_length,
+                None if pattern.is_approximate() => self.min_message_length,
+                None => 0,
+            };
+            if text_length < min_length {
+                continue;
+            }
+
+            let collection = self.pattern_collections.get(&i).map(String::as_str);
+            if let Some(collection) = collection {
+                if self.disabled_collections.contains(collection)
+                    || evaluation.timed_out_collections.iter().any(|c| c == collection) {
+                    continue;
+                }
+            }
+
+            let scale = if self.reputation_scaling.get(&i).copied().unwrap_or(scale_by_default) {
+                threshold_scale * reputation_scale
+            } else {
+                threshold_scale
+            };
+            let started = Instant::now();
+            if pattern.matches_in_context(text, context, scale) {
+                let pattern_id = self.pattern_id(i);
+                evaluation.matches.push(pattern_id.clone());
+                evaluation.confidences.push(pattern.match_confidence(text, context));
                 
                 // Update statistics
                 if let Some(stats) = self.effectiveness_stats.get_mut(&pattern_id) {
@@ -430,10 +1066,30 @@ impl EnhancedPatternMatcher {
                 }
                 
                 debug!("Advanced pattern match: {} matched by pattern {}", text, i);
+
+                if self.max_matches_per_message > 0 && evaluation.matches.len() >= self.max_matches_per_message {
+                    debug!("Stopped pattern evaluation after {} matches", evaluation.matches.len());
+                    evaluation.match_cap_reached = true;
+                    break;
+                }
+            }
+
+            let Some(collection) = collection else { continue };
+            let Some(budget) = self.collection_budgets.get(collection) else { continue };
+
+            let spent = time_spent.entry(collection).or_default();
+            *spent += started.elapsed();
+            if *spent > budget.max_processing_time {
+                warn!("Pattern collection '{}' exceeded its {}ms budget ({:.2}ms), applying {:?}",
+                      collection, budget.max_processing_time.as_millis(), spent.as_secs_f64() * 1000.0, budget.fallback);
+                evaluation.timed_out_collections.push(collection.to_string());
+                if budget.fallback == TimeoutFallback::FailClosed {
+                    evaluation.flagged_for_review.push(collection.to_string());
+                }
             }
         }
         
-        matches
+        evaluation
     }
 
     /// Report a false positive to improve pattern effectiveness
@@ -444,11 +1100,29 @@ impl EnhancedPatternMatcher {
         }
     }
 
+    /// Whether any loaded pattern needs channel context
+    pub fn has_context_patterns(&self) -> bool {
+        self.patterns.iter().any(AdvancedPattern::uses_context)
+    }
+
     /// Get effectiveness statistics for all patterns
     pub fn get_effectiveness_stats(&self) -> &HashMap<String, PatternStats> {
         &self.effectiveness_stats
     }
 
+    /// Patterns that match `text` at default strictness, with where they matched.
+    /// Disabled collections are skipped; statistics aren't touched.
+    pub fn explain(&self, text: &str) -> Vec<MatchExplanation> {
+        self.patterns.iter().enumerate()
+            .filter(|(i, _)| self.pattern_collections.get(i).is_none_or(|c| !self.disabled_collections.contains(c)))
+            .filter(|(_, pattern)| pattern.matches(text))
+            .map(|(i, pattern)| MatchExplanation {
+                span: pattern.find_span(text),
+                ..MatchExplanation::new(self.pattern_id(i), text)
+            })
+            .collect()
+    }
+
     /// Get patterns with low effectiveness scores (candidates for removal)
     pub fn get_ineffective_patterns(&self, threshold: f32) -> Vec<String> {
         self.effectiveness_stats
@@ -530,4 +1204,260 @@ mod tests {
         assert!(pattern.matches("smith"));
         // Note: Simplified Soundex might not catch all variations
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_keyboard_adjacency() {
+        assert!(KeyboardLayout::Qwerty.is_adjacent('g', 'h'));
+        assert!(KeyboardLayout::Qwerty.is_adjacent('g', 't'));
+        assert!(KeyboardLayout::Qwerty.is_adjacent('g', 'b'));
+        assert!(!KeyboardLayout::Qwerty.is_adjacent('a', 'p'));
+        assert!(KeyboardLayout::Azerty.is_adjacent('a', 'q'));
+        assert_eq!(KeyboardLayout::parse("QWERTZ"), Some(KeyboardLayout::Qwertz));
+    }
+
+    #[test]
+    fn test_keyboard_shift_detection() {
+        let pattern = AdvancedPattern::KeyboardShift {
+            pattern: "badword".to_string(),
+            layouts: vec![KeyboardLayout::Qwerty],
+        };
+
+        assert!(pattern.matches("nsfeptf")); // Every key shifted one to the right
+        assert!(pattern.matches("you are a vadwprd")); // Partial shift inside a sentence
+        assert!(pattern.matches("BADWORD"));
+        assert!(!pattern.matches("hello world"));
+
+        // Same text typed on a layout where those keys aren't neighbours
+        let dvorak_only = AdvancedPattern::KeyboardShift {
+            pattern: "badword".to_string(),
+            layouts: vec![KeyboardLayout::Dvorak],
+        };
+        assert!(!dvorak_only.matches("nsfeptf"));
+    }
+
+    #[test]
+    fn test_encoded_content_detection() {
+        let pattern = AdvancedPattern::EncodedContent("badsite.com".to_string());
+
+        // base64("visit badsite.com now") embedded in a normal sentence
+        assert!(pattern.matches("check this out dmlzaXQgYmFkc2l0ZS5jb20gbm93 lol"));
+        assert!(pattern.matches("go to 626164736974652e636f6d")); // hex
+        assert!(pattern.matches("link: %62%61%64%73%69%74%65.com")); // percent-encoded
+        assert!(!pattern.matches("just a normal message about nothing"));
+    }
+
+    #[test]
+    fn test_encoded_content_ignores_garbage() {
+        // Ordinary words that fit the base64/hex charset must not decode to anything
+        assert!(decode_embedded_payloads("somebody deadbeef Password1 abcdefgh").is_empty());
+        assert!(decode_embedded_payloads("100% sure").is_empty());
+    }
+
+    #[test]
+    fn test_collection_time_budget() {
+        let mut matcher = EnhancedPatternMatcher::new();
+        matcher.add_collection_pattern("slow", AdvancedPattern::FuzzyMatch {
+            pattern: "badword".to_string(),
+            threshold: 0.8,
+        });
+        matcher.add_collection_pattern("slow", AdvancedPattern::Leetspeak("hello".to_string()));
+        matcher.add_pattern(AdvancedPattern::Leetspeak("friend".to_string()));
+
+        // Without a budget every pattern runs
+        let evaluation = matcher.evaluate("hello there friend", 1.0);
+        assert_eq!(evaluation.matches.len(), 2);
+        assert!(evaluation.timed_out_collections.is_empty());
+
+        // A zero budget is exhausted by the first pattern, so the second is skipped
+        matcher.set_collection_budget("slow", CollectionBudget {
+            max_processing_time: Duration::ZERO,
+            fallback: TimeoutFallback::FailClosed,
+        });
+        let evaluation = matcher.evaluate("hello there friend", 1.0);
+        assert_eq!(evaluation.timed_out_collections, vec!["slow".to_string()]);
+        assert_eq!(evaluation.flagged_for_review, vec!["slow".to_string()]);
+        // Patterns outside the collection are unaffected
+        assert_eq!(evaluation.matches.len(), 1);
+    }
+
+    #[test]
+    fn test_contextual_reply_needs_context() {
+        let pattern = AdvancedPattern::ContextualReply {
+            context_pattern: "hurt myself".to_string(),
+            max_reply_words: 4,
+        };
+        let context = vec!["i want to hurt myself tonight".to_string()];
+
+        assert!(!pattern.matches("yes do it"));
+        assert!(pattern.matches_in_context("yes do it", &context, 1.0));
+        assert!(!pattern.matches_in_context("please talk to someone, you matter", &context, 1.0));
+        assert!(!pattern.matches_in_context("yes do it", &["should i stream tonight".to_string()], 1.0));
+    }
+
+    #[test]
+    fn test_normalize_option_combinations() {
+        // "Café", then "ро1nt$" with a Cyrillic р and о
+        let input = "Caf\u{e9} \u{440}\u{43E}1nt$";
+        let cases = [
+            // (unicode_fold, homoglyphs, leetspeak, expected)
+            (false, false, false, "caf\u{e9} \u{440}\u{43E}1nt$"),
+            (true, false, false, "cafe \u{440}\u{43E}1nt$"),
+            (false, true, false, "caf\u{e9} po1nt$"),
+            (false, false, true, "caf\u{e9} \u{440}\u{43E}ints"),
+            (true, true, false, "cafe po1nt$"),
+            (true, false, true, "cafe \u{440}\u{43E}ints"),
+            (false, true, true, "caf\u{e9} points"),
+            (true, true, true, "cafe points"),
+        ];
+
+        for (unicode_fold, homoglyphs, leetspeak, expected) in cases {
+            let options = NormalizeOptions { unicode_fold, homoglyphs, leetspeak, lowercase: true };
+            let result = normalize(input, options);
+            assert_eq!(result.as_str(), expected, "options: {:?}", options);
+            assert_eq!(result.original(), input);
+        }
+        assert_eq!(normalize(input, NormalizeOptions::default()).as_str(), "cafe points");
+    }
+
+    #[test]
+    fn test_normalize_lowercase_and_ordering() {
+        let unchanged = normalize("Fr33 V-Bucks", NormalizeOptions::none());
+        assert_eq!(unchanged.as_str(), "Fr33 V-Bucks");
+        assert!(!unchanged.is_changed());
+
+        let cased = NormalizeOptions { lowercase: false, ..NormalizeOptions::default() };
+        assert_eq!(normalize("Fr33", cased).to_string(), "Free");
+
+        // Fullwidth digits become ASCII before leetspeak runs
+        assert_eq!(normalize("fr\u{FF13}\u{FF13}", NormalizeOptions::default()).into_string(), "free");
+        let no_homoglyphs = NormalizeOptions { homoglyphs: false, ..NormalizeOptions::default() };
+        assert_eq!(normalize("fr\u{FF13}\u{FF13}", no_homoglyphs).as_str(), "fr\u{FF13}\u{FF13}");
+
+        // Zalgo marks are stripped with the accents
+        assert_eq!(normalize("h\u{0337}\u{0336}i", NormalizeOptions::default()).as_str(), "hi");
+    }
+
+    #[test]
+    fn test_min_message_length_skips_approximate_patterns() {
+        let mut matcher = EnhancedPatternMatcher::new();
+        matcher.add_pattern(AdvancedPattern::FuzzyMatch { pattern: "gag".to_string(), threshold: 0.6 });
+        matcher.add_pattern(AdvancedPattern::Leetspeak("gg".to_string()));
+
+        // Both match without a minimum
+        assert_eq!(matcher.matches("gg").len(), 2);
+
+        // Short messages bypass fuzzy matching, exact-style patterns still run
+        matcher.set_min_message_length(5);
+        assert_eq!(matcher.matches("gg"), ["leetspeak#1"]);
+
+        // Longer messages are still evaluated
+        let matches = matcher.matches("gag order");
+        assert!(matches.iter().any(|m| m == "fuzzy_match#0"));
+    }
+
+    #[test]
+    fn test_per_pattern_min_length_overrides_global() {
+        let mut matcher = EnhancedPatternMatcher::new();
+        matcher.set_min_message_length(10);
+        matcher.add_collection_pattern_with_min_length("short_ok", AdvancedPattern::Phonetic("hi".to_string()), 0);
+        matcher.add_collection_pattern_with_min_length("strict", AdvancedPattern::Leetspeak("hi".to_string()), 4);
+
+        let matches = matcher.matches("hi");
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches, ["phonetic#0"]);
+        assert_eq!(matcher.matches("hi there").len(), 2);
+    }
+
+    #[test]
+    fn test_disabled_collection_is_skipped() {
+        let mut matcher = EnhancedPatternMatcher::new();
+        matcher.add_collection_pattern("crypto", AdvancedPattern::Leetspeak("bitcoin".to_string()));
+        matcher.add_collection_pattern("links", AdvancedPattern::Leetspeak("discord".to_string()));
+
+        assert!(matcher.set_collection_enabled("crypto", false));
+        assert!(!matcher.set_collection_enabled("missing", false));
+        assert!(matcher.matches("free b1tc01n").is_empty());
+        assert_eq!(matcher.matches("join my d1sc0rd").len(), 1);
+        assert_eq!(matcher.collections(), vec![("crypto".to_string(), false), ("links".to_string(), true)]);
+
+        matcher.set_collection_enabled("crypto", true);
+        assert_eq!(matcher.matches("free b1tc01n").len(), 1);
+    }
+
+    #[test]
+    fn test_fuzzy_matches_are_as_confident_as_they_are_similar() {
+        let mut matcher = EnhancedPatternMatcher::new();
+        matcher.add_pattern(AdvancedPattern::FuzzyMatch { pattern: "badword".to_string(), threshold: 0.6 });
+        matcher.add_pattern(AdvancedPattern::Leetspeak("free".to_string()));
+
+        let evaluation = matcher.evaluate("fr33 badwurd", 1.0);
+        assert_eq!(evaluation.matches.len(), 2);
+        assert_eq!(evaluation.confidences.len(), 2);
+        assert!((evaluation.confidences[0] - 6.0 / 7.0).abs() < 1e-6);
+        assert_eq!(evaluation.confidences[1], 1.0);
+        assert_eq!(matcher.evaluate("BADWORD", 1.0).confidences, vec![1.0]);
+    }
+
+    #[test]
+    fn test_approximate_matches_score_how_close_they_came() {
+        let phonetic = AdvancedPattern::Phonetic("smith".to_string());
+        assert_eq!(phonetic.match_confidence("smith", &[]), 1.0);
+        assert!((phonetic.match_confidence("hi smyth", &[]) - 0.9).abs() < 1e-6);
+
+        let shift = AdvancedPattern::KeyboardShift { pattern: "badword".to_string(), layouts: vec![KeyboardLayout::Qwerty] };
+        assert_eq!(shift.match_confidence("BADWORD", &[]), 1.0);
+        assert!((shift.match_confidence("you are a vadwprd", &[]) - (0.5 + 0.5 * 5.0 / 7.0)).abs() < 1e-6);
+        assert_eq!(shift.match_confidence("nsfeptf", &[]), 0.5);
+
+        let reply = AdvancedPattern::ContextualReply { context_pattern: "hurt myself".to_string(), max_reply_words: 4 };
+        let recent = vec!["hello".to_string(), "i want to hurt myself".to_string()];
+        let older = vec!["i want to hurt myself".to_string(), "hello".to_string(), "anyone here".to_string()];
+        assert!((reply.match_confidence("yes", &recent) - 0.9).abs() < 1e-6);
+        assert!((reply.match_confidence("yes", &older) - 0.7).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_explained_span_covers_the_matching_words() {
+        let mut matcher = EnhancedPatternMatcher::new();
+        matcher.add_pattern(AdvancedPattern::Leetspeak("free money".to_string()));
+        matcher.add_pattern(AdvancedPattern::FuzzyMatch { pattern: "badword".to_string(), threshold: 0.8 });
+        matcher.add_pattern(AdvancedPattern::ZalgoText);
+
+        let text = "get fr33 m0ney, you badwrd";
+        let explanations = matcher.explain(text);
+        assert_eq!(explanations.len(), 2);
+        for explanation in &explanations {
+            let span = explanation.span.as_ref().unwrap();
+            assert_eq!(&text[span.start..span.end], span.text);
+        }
+        assert_eq!(explanations[0].span.as_ref().unwrap().text, "fr33 m0ney,");
+        assert_eq!(explanations[1].span.as_ref().unwrap().text, "badwrd");
+        assert_eq!(explanations[1].highlighted(), "get fr33 m0ney, you «badwrd»");
+        assert_eq!(explanations[0].filter, "leetspeak#0");
+        assert_eq!(explanations[1].filter, "fuzzy_match#1");
+
+        assert_eq!(AdvancedPattern::ZalgoText.find_span("h̸̡̛e̶̢͝l̷̨͘l̵̢͠o̴̧͝"), None);
+        assert!(matcher.explain("nothing to see").is_empty());
+    }
+
+    #[test]
+    fn test_evaluation_stops_at_match_cap() {
+        let mut matcher = EnhancedPatternMatcher::new();
+        for word in ["free", "bitcoin", "discord", "giveaway", "click"] {
+            matcher.add_pattern(AdvancedPattern::Leetspeak(word.to_string()));
+        }
+        let text = "fr33 b1tc01n g1v3away on my d1sc0rd, cl1ck";
+
+        let evaluation = matcher.evaluate(text, 1.0);
+        assert_eq!(evaluation.matches.len(), 5);
+        assert!(!evaluation.match_cap_reached);
+
+        matcher.set_max_matches_per_message(2);
+        let evaluation = matcher.evaluate(text, 1.0);
+        assert_eq!(evaluation.matches.len(), 2);
+        assert!(evaluation.match_cap_reached);
+        // Patterns after the cap weren't evaluated at all
+        let unevaluated = matcher.get_effectiveness_stats().values().filter(|s| s.matches == 1).count();
+        assert_eq!(unevaluated, 3);
+    }
+}
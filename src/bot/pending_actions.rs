@@ -0,0 +1,227 @@
+use chrono::{DateTime, Duration, Utc};
+use log::warn;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+
+/// How moderation actions are held back while their platform is unreachable
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PendingActionConfig {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Actions queued per platform; the oldest is dropped once the queue is full
+    #[serde(default = "default_max_queued")]
+    pub max_queued: usize,
+    /// Actions older than this when the platform comes back are dropped, not sent late
+    #[serde(default = "default_max_age_seconds")]
+    pub max_age_seconds: u64,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_max_queued() -> usize {
+    50
+}
+
+fn default_max_age_seconds() -> u64 {
+    120
+}
+
+impl Default for PendingActionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            max_queued: default_max_queued(),
+            max_age_seconds: default_max_age_seconds(),
+        }
+    }
+}
+
+/// A moderation message that couldn't be sent yet
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingAction {
+    pub platform: String,
+    pub channel: String,
+    pub message: String,
+    pub queued_at: DateTime<Utc>,
+}
+
+/// Per-platform queues of moderation actions waiting for a connection to recover
+#[derive(Debug, Default)]
+pub struct PendingActionQueue {
+    config: PendingActionConfig,
+    queues: HashMap<String, VecDeque<PendingAction>>,
+}
+
+impl PendingActionQueue {
+    pub fn new(config: PendingActionConfig) -> Self {
+        Self {
+            config,
+            queues: HashMap::new(),
+        }
+    }
+
+    pub fn set_config(&mut self, config: PendingActionConfig) {
+        self.config = config;
+    }
+
+    pub fn config(&self) -> &PendingActionConfig {
+        &self.config
+    }
+
+    /// Queue an action, returning false if queuing is disabled and it was dropped
+    pub fn push(&mut self, platform: &str, channel: &str, message: &str, now: DateTime<Utc>) -> bool {
+        if !self.config.enabled || self.config.max_queued == 0 {
+            warn!("Dropping moderation action for {}#{}: platform unavailable and queuing is disabled", platform, channel);
+            return false;
+        }
+
+        let queue = self.queues.entry(platform.to_string()).or_default();
+        while queue.len() >= self.config.max_queued {
+            if let Some(dropped) = queue.pop_front() {
+                warn!("Dropping moderation action for {}#{}: queue full ({} pending): {}",
+                      dropped.platform, dropped.channel, self.config.max_queued, dropped.message);
+            }
+        }
+        queue.push_back(PendingAction {
+            platform: platform.to_string(),
+            channel: channel.to_string(),
+            message: message.to_string(),
+            queued_at: now,
+        });
+        true
+    }
+
+    /// Take a platform's queued actions in order, dropping those too stale to send
+    pub fn take_ready(&mut self, platform: &str, now: DateTime<Utc>) -> Vec<PendingAction> {
+        let Some(queue) = self.queues.remove(platform) else {
+            return Vec::new();
+        };
+        self.drop_stale(queue, now)
+    }
+
+    /// Take the actions queued for one channel of a platform, like `take_ready`
+    pub fn take_ready_in(&mut self, platform: &str, channel: &str, now: DateTime<Utc>) -> Vec<PendingAction> {
+        let Some(queue) = self.queues.get_mut(platform) else {
+            return Vec::new();
+        };
+        let (taken, kept) = std::mem::take(queue).into_iter().partition(|action| action.channel == channel);
+        *queue = kept;
+        if queue.is_empty() {
+            self.queues.remove(platform);
+        }
+        self.drop_stale(taken, now)
+    }
+
+    fn drop_stale(&self, actions: VecDeque<PendingAction>, now: DateTime<Utc>) -> Vec<PendingAction> {
+        let max_age = Duration::seconds(self.config.max_age_seconds as i64);
+        actions.into_iter()
+            .filter(|action| {
+                let age = now - action.queued_at;
+                if age > max_age {
+                    warn!("Dropping moderation action for {}#{}: {}s old, past the {}s limit: {}",
+                          action.platform, action.channel, age.num_seconds(), max_age.num_seconds(), action.message);
+                    false
+                } else {
+                    true
+                }
+            })
+            .collect()
+    }
+
+    /// Put back actions that still couldn't be sent, ahead of anything queued since
+    pub fn restore(&mut self, platform: &str, actions: Vec<PendingAction>) {
+        let queue = self.queues.entry(platform.to_string()).or_default();
+        for action in actions.into_iter().rev() {
+            queue.push_front(action);
+        }
+    }
+
+    /// Channels of a platform with actions waiting, in the order they were first queued
+    pub fn channels(&self, platform: &str) -> Vec<String> {
+        let mut channels: Vec<String> = Vec::new();
+        for action in self.queues.get(platform).into_iter().flatten() {
+            if !channels.contains(&action.channel) {
+                channels.push(action.channel.clone());
+            }
+        }
+        channels
+    }
+
+    /// Platforms with actions waiting
+    pub fn platforms(&self) -> Vec<String> {
+        self.queues.iter()
+            .filter(|(_, queue)| !queue.is_empty())
+            .map(|(platform, _)| platform.clone())
+            .collect()
+    }
+
+    pub fn has_pending(&self, platform: &str) -> bool {
+        self.queues.get(platform).is_some_and(|queue| !queue.is_empty())
+    }
+
+    pub fn len(&self) -> usize {
+        self.queues.values().map(VecDeque::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_queue_is_bounded_and_drops_stale_actions() {
+        let mut queue = PendingActionQueue::new(PendingActionConfig {
+            enabled: true,
+            max_queued: 3,
+            max_age_seconds: 60,
+        });
+        let start = Utc::now();
+
+        for i in 0..4 {
+            assert!(queue.push("twitch", "channel", &format!("action {}", i), start + Duration::seconds(i * 30)));
+        }
+        // The oldest was pushed out by the fourth
+        assert_eq!(queue.len(), 3);
+
+        // At 100s, action 1 (queued at 30s) is 70s old and dropped
+        let ready = queue.take_ready("twitch", start + Duration::seconds(100));
+        let messages: Vec<_> = ready.iter().map(|a| a.message.as_str()).collect();
+        assert_eq!(messages, vec!["action 2", "action 3"]);
+        assert!(queue.is_empty());
+        assert!(queue.take_ready("youtube", start).is_empty());
+    }
+
+    #[test]
+    fn test_restored_actions_keep_their_order() {
+        let mut queue = PendingActionQueue::default();
+        let now = Utc::now();
+        queue.push("twitch", "channel", "first", now);
+        queue.push("twitch", "channel", "second", now);
+        let ready = queue.take_ready("twitch", now);
+        queue.push("twitch", "channel", "third", now);
+
+        queue.restore("twitch", ready);
+        let messages: Vec<_> = queue.take_ready("twitch", now).into_iter().map(|a| a.message).collect();
+        assert_eq!(messages, vec!["first", "second", "third"]);
+
+        queue.push("twitch", "a", "a1", now);
+        queue.push("twitch", "b", "b1", now);
+        queue.push("twitch", "a", "a2", now);
+        assert_eq!(queue.channels("twitch"), ["a", "b"]);
+        let messages: Vec<_> = queue.take_ready_in("twitch", "a", now).into_iter().map(|a| a.message).collect();
+        assert_eq!(messages, vec!["a1", "a2"]);
+        assert_eq!(queue.channels("twitch"), ["b"]);
+        queue.take_ready_in("twitch", "b", now);
+        assert!(!queue.has_pending("twitch"));
+
+        queue.set_config(PendingActionConfig { enabled: false, ..Default::default() });
+        assert!(!queue.push("twitch", "channel", "dropped", now));
+        assert!(!queue.has_pending("twitch"));
+    }
+}
@@ -0,0 +1,210 @@
+use log::warn;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::types::ChatMessage;
+
+/// Default limit on the time spent processing one inbound message
+pub const DEFAULT_MESSAGE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Counters for the current reporting window
+#[derive(Debug)]
+struct WindowCounters {
+    started: Instant,
+    messages_processed: u64,
+    errors: u64,
+    total_latency: Duration,
+    max_latency: Duration,
+}
+
+impl WindowCounters {
+    fn new(started: Instant) -> Self {
+        Self {
+            started,
+            messages_processed: 0,
+            errors: 0,
+            total_latency: Duration::ZERO,
+            max_latency: Duration::ZERO,
+        }
+    }
+}
+
+/// Measured latency and error counts of the inbound message pipeline, drained
+/// periodically into the adaptive tuning system
+#[derive(Debug)]
+pub struct PipelineMetrics {
+    window: Mutex<WindowCounters>,
+}
+
+impl Default for PipelineMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PipelineMetrics {
+    pub fn new() -> Self {
+        Self {
+            window: Mutex::new(WindowCounters::new(Instant::now())),
+        }
+    }
+
+    /// Start timing one message; the latency is recorded when the timer is dropped,
+    /// so every early exit from the processing loop is still counted
+    pub fn time_message(&self) -> MessageTimer<'_> {
+        MessageTimer {
+            metrics: self,
+            started: Instant::now(),
+        }
+    }
+
+    pub fn record_message(&self, latency: Duration) {
+        let mut window = self.window.lock().unwrap_or_else(|e| e.into_inner());
+        window.messages_processed += 1;
+        window.total_latency += latency;
+        window.max_latency = window.max_latency.max(latency);
+    }
+
+    /// Count a failed operation (moderation action, command, response delivery, ...)
+    pub fn record_error(&self) {
+        self.record_errors(1);
+    }
+
+    pub fn record_errors(&self, count: u64) {
+        self.window.lock().unwrap_or_else(|e| e.into_inner()).errors += count;
+    }
+
+    /// Run the processing of one message, abandoning it after `limit` so a
+    /// pathological message can't stall its platform's stream. A timeout is
+    /// logged with the message's metadata and counted as an error; returns
+    /// whether processing finished in time.
+    pub async fn process_within<F: Future<Output = ()>>(&self, message: &ChatMessage, limit: Duration, processing: F) -> bool {
+        if tokio::time::timeout(limit, processing).await.is_ok() {
+            return true;
+        }
+
+        self.record_timeout(message, limit);
+        false
+    }
+
+    /// Log and count a message skipped for running past its processing time limit
+    pub fn record_timeout(&self, message: &ChatMessage, limit: Duration) {
+        warn!("Skipped message from {} in {}#{} ({} chars): processing took longer than {:?}",
+              message.username, message.platform, message.channel, message.content.chars().count(), limit);
+        self.record_error();
+    }
+
+    /// Messages processed since the last `take_window`
+    pub fn messages_in_window(&self) -> u64 {
+        self.window.lock().unwrap_or_else(|e| e.into_inner()).messages_processed
+    }
+
+    /// Return the counters gathered since the last call and start a new window
+    pub fn take_window(&self) -> PipelineMetricsWindow {
+        let now = Instant::now();
+        let mut window = self.window.lock().unwrap_or_else(|e| e.into_inner());
+        let finished = std::mem::replace(&mut *window, WindowCounters::new(now));
+
+        PipelineMetricsWindow {
+            duration: now.duration_since(finished.started),
+            messages_processed: finished.messages_processed,
+            errors: finished.errors,
+            average_latency_ms: if finished.messages_processed == 0 {
+                0.0
+            } else {
+                finished.total_latency.as_secs_f64() * 1000.0 / finished.messages_processed as f64
+            },
+            max_latency_ms: finished.max_latency.as_secs_f64() * 1000.0,
+        }
+    }
+}
+
+/// Records the time spent on a message when dropped
+pub struct MessageTimer<'a> {
+    metrics: &'a PipelineMetrics,
+    started: Instant,
+}
+
+impl Drop for MessageTimer<'_> {
+    fn drop(&mut self) {
+        self.metrics.record_message(self.started.elapsed());
+    }
+}
+
+/// Pipeline activity over one reporting window
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PipelineMetricsWindow {
+    pub duration: Duration,
+    pub messages_processed: u64,
+    pub errors: u64,
+    pub average_latency_ms: f64,
+    pub max_latency_ms: f64,
+}
+
+impl PipelineMetricsWindow {
+    pub fn messages_per_second(&self) -> f64 {
+        let seconds = self.duration.as_secs_f64();
+        if seconds <= 0.0 { 0.0 } else { self.messages_processed as f64 / seconds }
+    }
+
+    /// Failed operations per processed message, as a percentage (capped at 100)
+    pub fn error_rate_percent(&self) -> f64 {
+        match (self.errors, self.messages_processed) {
+            (0, _) => 0.0,
+            (_, 0) => 100.0,
+            (errors, processed) => (errors as f64 / processed as f64 * 100.0).min(100.0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_window_measures_processing_time() {
+        let metrics = PipelineMetrics::new();
+        for delay_ms in [5, 15] {
+            let _timer = metrics.time_message();
+            std::thread::sleep(Duration::from_millis(delay_ms));
+        }
+        metrics.record_error();
+
+        let window = metrics.take_window();
+        assert_eq!(window.messages_processed, 2);
+        assert_eq!(window.errors, 1);
+        assert!(window.average_latency_ms >= 10.0, "average was {}", window.average_latency_ms);
+        assert!(window.max_latency_ms >= 15.0, "max was {}", window.max_latency_ms);
+        assert_eq!(window.error_rate_percent(), 50.0);
+        assert!(window.messages_per_second() > 0.0);
+
+        // Taking the window resets the counters
+        let empty = metrics.take_window();
+        assert_eq!(empty.messages_processed, 0);
+        assert_eq!(empty.average_latency_ms, 0.0);
+        assert_eq!(empty.error_rate_percent(), 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_slow_message_times_out_without_blocking_the_next() {
+        let metrics = PipelineMetrics::new();
+        let limit = Duration::from_millis(50);
+        let started = Instant::now();
+        let mut processed = Vec::new();
+
+        for (content, delay) in [("slow", Duration::from_secs(30)), ("fast", Duration::ZERO)] {
+            let message = ChatMessage::new("twitch", "channel", "viewer", content);
+            let finished = metrics.process_within(&message, limit, async {
+                tokio::time::sleep(delay).await;
+                processed.push(content);
+            }).await;
+            assert_eq!(finished, content == "fast");
+        }
+
+        assert_eq!(processed, vec!["fast"]);
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert_eq!(metrics.take_window().errors, 1);
+    }
+}
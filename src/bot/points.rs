@@ -1,12 +1,30 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use log::{debug, info, warn};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Arc;
+use tokio::fs;
 use tokio::sync::RwLock;
 use tokio::time::{Duration, Instant};
 
-use crate::types::ChatMessage;
+use crate::bot::identity::IdentityMap;
+use crate::types::{ChatMessage, UserLevel};
+
+/// Default earning multipliers by user level
+pub fn default_level_multipliers() -> HashMap<UserLevel, f64> {
+    HashMap::from([
+        (UserLevel::Viewer, 1.0),
+        (UserLevel::Regular, 1.0),
+        (UserLevel::Subscriber, 1.5),
+        (UserLevel::VIP, 1.5),
+        (UserLevel::Moderator, 2.0),
+        (UserLevel::Owner, 2.0),
+    ])
+}
+
+/// Lifetime earnings needed to count as a regular
+const REGULAR_MIN_TOTAL_EARNED: i64 = 10000;
 
 /// User points and statistics
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -80,7 +98,7 @@ impl UserPoints {
     }
 
     /// Update user status from chat message
-    pub fn update_from_message(&mut self, message: &ChatMessage) {
+    pub fn update_from_message(&mut self, message: &ChatMessage, level_multipliers: &HashMap<UserLevel, f64>) {
         self.last_activity = message.timestamp;
         self.messages_sent += 1;
         self.is_subscriber = message.is_subscriber;
@@ -91,22 +109,31 @@ impl UserPoints {
         }
 
         // Update multiplier based on status
-        self.update_multiplier();
+        let level = self.user_level(message);
+        self.update_multiplier(&level, level_multipliers);
     }
 
-    /// Update points multiplier based on user status
-    fn update_multiplier(&mut self) {
-        self.multiplier = 1.0;
-        
-        if self.is_subscriber {
-            self.multiplier += 0.5; // 50% bonus for subscribers
-        }
-        
-        if self.is_moderator {
-            self.multiplier += 1.0; // 100% bonus for moderators
+    /// Current user level, taken from the message badges and flags
+    pub fn user_level(&self, message: &ChatMessage) -> UserLevel {
+        if message.user_badges.iter().any(|b| b == "broadcaster" || b == "owner") {
+            UserLevel::Owner
+        } else if message.is_mod {
+            UserLevel::Moderator
+        } else if message.user_badges.iter().any(|b| b == "vip") {
+            UserLevel::VIP
+        } else if message.is_subscriber {
+            UserLevel::Subscriber
+        } else if self.total_earned >= REGULAR_MIN_TOTAL_EARNED {
+            UserLevel::Regular
+        } else {
+            UserLevel::Viewer
         }
+    }
+
+    /// Update points multiplier based on user level
+    fn update_multiplier(&mut self, level: &UserLevel, level_multipliers: &HashMap<UserLevel, f64>) {
+        self.multiplier = level_multipliers.get(level).copied().unwrap_or(1.0);
         
-        // VIP multiplier could be added here based on badges
         if self.total_earned > 100000 {
             self.multiplier += 0.25; // 25% bonus for long-term users
         }
@@ -135,6 +162,11 @@ pub struct PointsConfig {
     pub daily_bonus: i64,
     pub first_time_bonus: i64,
     pub max_points_per_hour: i64,
+    pub min_transfer: i64,
+    pub max_transfer: i64,
+    pub daily_transfer_cap: i64,   // Max points a user can give away per day
+    pub season_reset: SeasonResetMode,
+    pub seasons_path: PathBuf,     // Where archived seasons are persisted
 }
 
 impl Default for PointsConfig {
@@ -147,10 +179,55 @@ impl Default for PointsConfig {
             daily_bonus: 100,              // 100 points for first activity of day
             first_time_bonus: 500,         // 500 points for new users
             max_points_per_hour: 200,      // Rate limiting
+            min_transfer: 10,
+            max_transfer: 10000,
+            daily_transfer_cap: 20000,
+            season_reset: SeasonResetMode::Full,
+            seasons_path: PathBuf::from("points_seasons.json"),
+        }
+    }
+}
+
+/// What happens to live balances when a new season starts
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum SeasonResetMode {
+    /// Archive standings only, balances carry over
+    Keep,
+    /// Everyone starts the new season at zero
+    Full,
+    /// Everyone keeps a percentage of their balance
+    Soft { keep_percent: u8 },
+}
+
+impl SeasonResetMode {
+    fn apply(&self, points: i64) -> i64 {
+        match self {
+            SeasonResetMode::Keep => points,
+            SeasonResetMode::Full => 0,
+            SeasonResetMode::Soft { keep_percent } => points * (*keep_percent).min(100) as i64 / 100,
         }
     }
 }
 
+/// A user's final placing in an archived season
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SeasonStanding {
+    pub rank: usize,
+    pub user_id: String,
+    pub username: String,
+    pub display_name: Option<String>,
+    pub points: i64,
+}
+
+/// Archived leaderboard for a finished season
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PointsSeason {
+    pub name: String,
+    pub archived_at: chrono::DateTime<chrono::Utc>,
+    pub reset_mode: SeasonResetMode,
+    pub standings: Vec<SeasonStanding>,
+}
+
 /// Transaction record for points history
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PointsTransaction {
@@ -170,12 +247,47 @@ pub enum TransactionType {
     Admin,
 }
 
+/// Reasons a points transfer is rejected
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum TransferError {
+    #[error("Amount must be positive")]
+    InvalidAmount,
+
+    #[error("Minimum transfer is {min} points")]
+    BelowMinimum { min: i64 },
+
+    #[error("Maximum transfer is {max} points")]
+    AboveMaximum { max: i64 },
+
+    #[error("Can't transfer points to yourself")]
+    SelfTransfer,
+
+    #[error("Sender has no points account")]
+    UnknownSender,
+
+    #[error("User {username} not found")]
+    UnknownRecipient { username: String },
+
+    #[error("Not enough points (balance: {balance})")]
+    InsufficientPoints { balance: i64 },
+
+    #[error("Daily transfer limit reached ({remaining} points left today)")]
+    DailyCapExceeded { remaining: i64 },
+}
+
 pub struct PointsSystem {
     users: Arc<RwLock<HashMap<String, UserPoints>>>,
     config: PointsConfig,
     transactions: Arc<RwLock<Vec<PointsTransaction>>>,
     watching_tracker: Arc<RwLock<HashMap<String, Instant>>>,
     hourly_earnings: Arc<RwLock<HashMap<String, (Instant, i64)>>>,
+    daily_transfers: Arc<RwLock<HashMap<String, (chrono::NaiveDate, i64)>>>,
+    level_multipliers: Arc<RwLock<HashMap<UserLevel, f64>>>,
+    seasons: Arc<RwLock<Vec<PointsSeason>>>,
+    /// Where archived seasons are persisted; starts as `config.seasons_path`
+    seasons_path: Arc<RwLock<PathBuf>>,
+    /// Linked accounts share one balance, kept under their resolved identity
+    identities: Arc<RwLock<IdentityMap>>,
 }
 
 impl PointsSystem {
@@ -186,23 +298,99 @@ impl PointsSystem {
             transactions: Arc::new(RwLock::new(Vec::new())),
             watching_tracker: Arc::new(RwLock::new(HashMap::new())),
             hourly_earnings: Arc::new(RwLock::new(HashMap::new())),
+            daily_transfers: Arc::new(RwLock::new(HashMap::new())),
+            level_multipliers: Arc::new(RwLock::new(default_level_multipliers())),
+            seasons: Arc::new(RwLock::new(Vec::new())),
+            seasons_path: Arc::new(RwLock::new(PointsConfig::default().seasons_path)),
+            identities: Arc::new(RwLock::new(IdentityMap::new())),
         }
     }
 
     pub fn with_config(config: PointsConfig) -> Self {
         Self {
             users: Arc::new(RwLock::new(HashMap::new())),
+            seasons_path: Arc::new(RwLock::new(config.seasons_path.clone())),
             config,
             transactions: Arc::new(RwLock::new(Vec::new())),
             watching_tracker: Arc::new(RwLock::new(HashMap::new())),
             hourly_earnings: Arc::new(RwLock::new(HashMap::new())),
+            daily_transfers: Arc::new(RwLock::new(HashMap::new())),
+            level_multipliers: Arc::new(RwLock::new(default_level_multipliers())),
+            seasons: Arc::new(RwLock::new(Vec::new())),
+            identities: Arc::new(RwLock::new(IdentityMap::new())),
         }
     }
 
+    /// Share identity links with other systems, so linked accounts are one user everywhere
+    pub fn with_identities(mut self, identities: Arc<RwLock<IdentityMap>>) -> Self {
+        self.identities = identities;
+        self
+    }
+
+    async fn user_key(&self, platform: &str, username: &str) -> String {
+        self.identities.read().await.resolve(platform, username)
+    }
+
+    /// Fold one identity's balance and stats into another's, once their accounts
+    /// are linked or an identity's canonical account changes
+    pub async fn merge_user(&self, from: &str, into: &str) {
+        let mut users = self.users.write().await;
+        let Some(mut merged) = users.remove(from) else {
+            return;
+        };
+
+        match users.get_mut(into) {
+            Some(user) => {
+                user.points += merged.points;
+                user.total_earned += merged.total_earned;
+                user.total_spent += merged.total_spent;
+                user.minutes_watched += merged.minutes_watched;
+                user.messages_sent += merged.messages_sent;
+                user.commands_used += merged.commands_used;
+                user.last_activity = user.last_activity.max(merged.last_activity);
+                user.first_seen = user.first_seen.min(merged.first_seen);
+                user.is_subscriber |= merged.is_subscriber;
+                user.is_moderator |= merged.is_moderator;
+                user.multiplier = user.multiplier.max(merged.multiplier);
+            }
+            None => {
+                merged.user_id = into.to_string();
+                if let Some((platform, username)) = into.split_once(':') {
+                    merged.platform = platform.to_string();
+                    merged.username = username.to_string();
+                    merged.display_name = None;
+                }
+                users.insert(into.to_string(), merged);
+            }
+        }
+        info!("Merged points of {} into {}", from, into);
+    }
+
+    /// Persist archived seasons to `path` instead; call before `start` to load from it
+    pub async fn set_seasons_path(&self, path: impl Into<PathBuf>) {
+        *self.seasons_path.write().await = path.into();
+    }
+
+    /// Replace the earning multipliers by user level (applied from each user's next message)
+    pub async fn set_level_multipliers(&self, multipliers: HashMap<UserLevel, f64>) {
+        info!("Points multipliers updated: {:?}", multipliers);
+        *self.level_multipliers.write().await = multipliers;
+    }
+
+    /// Current earning multipliers by user level
+    pub async fn get_level_multipliers(&self) -> HashMap<UserLevel, f64> {
+        self.level_multipliers.read().await.clone()
+    }
+
     /// Start the points system with periodic tasks
     pub async fn start(&self) -> Result<()> {
         info!("Starting user points system...");
 
+        // Load archived seasons
+        if let Err(e) = self.load_seasons().await {
+            warn!("Failed to load points seasons: {}", e);
+        }
+
         // Start watching time tracker
         self.start_watching_tracker().await;
 
@@ -215,7 +403,7 @@ impl PointsSystem {
 
     /// Process a chat message for points
     pub async fn process_message(&self, message: &ChatMessage) -> Result<()> {
-        let user_id = format!("{}:{}", message.platform, message.username);
+        let user_id = self.user_key(&message.platform, &message.username).await;
         
         // Check if user exists and if it's a new user
         let (is_new_user, needs_daily_bonus) = {
@@ -237,6 +425,7 @@ impl PointsSystem {
                 message.username.clone(),
                 message.display_name.clone()
             );
+            new_user.user_id = user_id.clone();
             new_user.add_points(self.config.first_time_bonus, "Welcome bonus");
             
             // Insert new user
@@ -288,10 +477,11 @@ impl PointsSystem {
 
         // Update user info and award message points
         {
+            let level_multipliers = self.level_multipliers.read().await.clone();
             let mut users = self.users.write().await;
             if let Some(user) = users.get_mut(&user_id) {
                 // Update user info from message
-                user.update_from_message(message);
+                user.update_from_message(message, &level_multipliers);
                 
                 // Award points for message
                 if self.config.points_per_message > 0 {
@@ -323,7 +513,7 @@ impl PointsSystem {
 
     /// Process a command usage for points
     pub async fn process_command(&self, message: &ChatMessage, command: &str) -> Result<()> {
-        let user_id = format!("{}:{}", message.platform, message.username);
+        let user_id = self.user_key(&message.platform, &message.username).await;
         
         let mut users = self.users.write().await;
         if let Some(user) = users.get_mut(&user_id) {
@@ -353,13 +543,13 @@ impl PointsSystem {
 
     /// Get user points
     pub async fn get_user_points(&self, platform: &str, username: &str) -> Option<UserPoints> {
-        let user_id = format!("{}:{}", platform, username);
+        let user_id = self.user_key(platform, username).await;
         self.users.read().await.get(&user_id).cloned()
     }
 
     /// Add points to user (admin function)
     pub async fn add_points(&self, platform: &str, username: &str, amount: i64, reason: &str) -> Result<bool> {
-        let user_id = format!("{}:{}", platform, username);
+        let user_id = self.user_key(platform, username).await;
         let mut users = self.users.write().await;
         
         if let Some(user) = users.get_mut(&user_id) {
@@ -386,7 +576,7 @@ impl PointsSystem {
 
     /// Spend points for user
     pub async fn spend_points(&self, platform: &str, username: &str, amount: i64, reason: &str) -> Result<bool> {
-        let user_id = format!("{}:{}", platform, username);
+        let user_id = self.user_key(platform, username).await;
         let mut users = self.users.write().await;
         
         if let Some(user) = users.get_mut(&user_id) {
@@ -421,6 +611,99 @@ impl PointsSystem {
         sorted_users
     }
 
+    /// Archive the current standings as a season and reset balances per the configured mode
+    pub async fn start_new_season(&self, name: &str) -> Result<PointsSeason> {
+        self.start_new_season_with_mode(name, self.config.season_reset).await
+    }
+
+    /// Archive the current standings as a season and reset balances with the given mode
+    pub async fn start_new_season_with_mode(&self, name: &str, reset_mode: SeasonResetMode) -> Result<PointsSeason> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(anyhow::anyhow!("Season name can't be empty"));
+        }
+
+        let mut seasons = self.seasons.write().await;
+        if seasons.iter().any(|s| s.name.eq_ignore_ascii_case(name)) {
+            return Err(anyhow::anyhow!("Season '{}' already exists", name));
+        }
+
+        // Snapshot, archive and reset under one lock so no points land in between;
+        // balances are only reset once the archive is safely on disk
+        let mut users = self.users.write().await;
+        let season = {
+            let mut ranked: Vec<&UserPoints> = users.values().collect();
+            ranked.sort_by(|a, b| b.points.cmp(&a.points).then_with(|| a.username.cmp(&b.username)));
+
+            let standings = ranked.iter().enumerate()
+                .map(|(i, user)| SeasonStanding {
+                    rank: i + 1,
+                    user_id: user.user_id.clone(),
+                    username: user.username.clone(),
+                    display_name: user.display_name.clone(),
+                    points: user.points,
+                })
+                .collect();
+
+            PointsSeason {
+                name: name.to_string(),
+                archived_at: chrono::Utc::now(),
+                reset_mode,
+                standings,
+            }
+        };
+
+        seasons.push(season.clone());
+        if let Err(e) = self.save_seasons(&seasons).await {
+            seasons.pop();
+            return Err(e);
+        }
+        for user in users.values_mut() {
+            user.points = reset_mode.apply(user.points);
+        }
+        drop(users);
+
+        info!("Archived points season '{}' with {} users (reset: {:?})",
+              season.name, season.standings.len(), reset_mode);
+        Ok(season)
+    }
+
+    /// Get the archived leaderboard for a season
+    pub async fn get_season_leaderboard(&self, season: &str, limit: usize) -> Option<Vec<SeasonStanding>> {
+        self.seasons.read().await.iter()
+            .find(|s| s.name.eq_ignore_ascii_case(season))
+            .map(|s| s.standings.iter().take(limit).cloned().collect())
+    }
+
+    /// Names of all archived seasons, oldest first
+    pub async fn list_seasons(&self) -> Vec<String> {
+        self.seasons.read().await.iter().map(|s| s.name.clone()).collect()
+    }
+
+    /// Load archived seasons from disk
+    pub async fn load_seasons(&self) -> Result<()> {
+        let path = &*self.seasons_path.read().await;
+        if !path.exists() {
+            return Ok(());
+        }
+
+        let content = fs::read_to_string(path).await
+            .with_context(|| format!("Failed to read seasons: {}", path.display()))?;
+        let seasons: Vec<PointsSeason> = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse seasons: {}", path.display()))?;
+
+        info!("Loaded {} archived points seasons", seasons.len());
+        *self.seasons.write().await = seasons;
+        Ok(())
+    }
+
+    async fn save_seasons(&self, seasons: &[PointsSeason]) -> Result<()> {
+        let path = &*self.seasons_path.read().await;
+        let content = serde_json::to_string_pretty(seasons)?;
+        fs::write(path, content).await
+            .with_context(|| format!("Failed to write seasons: {}", path.display()))
+    }
+
     /// Get user statistics
     pub async fn get_statistics(&self) -> HashMap<String, serde_json::Value> {
         let users = self.users.read().await;
@@ -445,61 +728,88 @@ impl PointsSystem {
         stats
     }
 
-    /// Transfer points between users
-    pub async fn transfer_points(&self, from_platform: &str, from_user: &str, 
-                                to_platform: &str, to_user: &str, amount: i64) -> Result<bool> {
-        let from_id = format!("{}:{}", from_platform, from_user);
-        let to_id = format!("{}:{}", to_platform, to_user);
-        
+    /// Transfer points between users.
+    ///
+    /// Validation, the debit and the credit all happen under one lock on the
+    /// user table, so concurrent transfers can't overdraw or duplicate points.
+    pub async fn transfer_points(&self, from_platform: &str, from_user: &str,
+                                to_platform: &str, to_user: &str, amount: i64) -> Result<(), TransferError> {
+        if amount <= 0 {
+            return Err(TransferError::InvalidAmount);
+        }
+        if amount < self.config.min_transfer {
+            return Err(TransferError::BelowMinimum { min: self.config.min_transfer });
+        }
+        if amount > self.config.max_transfer {
+            return Err(TransferError::AboveMaximum { max: self.config.max_transfer });
+        }
+
+        let from_id = self.user_key(from_platform, from_user).await;
+        let to_id = self.user_key(to_platform, to_user).await;
+        if from_id == to_id {
+            return Err(TransferError::SelfTransfer);
+        }
+
         let mut users = self.users.write().await;
-        
-        // Check if both users exist and from_user has enough points
-        let can_transfer = if let (Some(from), Some(_to)) = (users.get(&from_id), users.get(&to_id)) {
-            from.points >= amount
-        } else {
-            false
-        };
-        
-        if can_transfer {
-            // Perform transfer
-            let mut transactions = Vec::new();
-            
-            if let Some(from) = users.get_mut(&from_id) {
-                from.spend_points(amount, &format!("Transfer to {}", to_user));
-                transactions.push(PointsTransaction {
-                    user_id: from.user_id.clone(),
-                    transaction_type: TransactionType::Spent,
-                    amount,
-                    reason: format!("Transfer to {}", to_user),
-                    timestamp: chrono::Utc::now(),
-                    balance_after: from.points,
-                });
-            }
-            
-            if let Some(to) = users.get_mut(&to_id) {
-                to.add_points(amount, &format!("Transfer from {}", from_user));
-                transactions.push(PointsTransaction {
-                    user_id: to.user_id.clone(),
-                    transaction_type: TransactionType::Earned,
-                    amount,
-                    reason: format!("Transfer from {}", from_user),
-                    timestamp: chrono::Utc::now(),
-                    balance_after: to.points,
-                });
-            }
-            
-            drop(users);
-            
-            // Record transactions
-            for transaction in transactions {
-                self.add_transaction(transaction).await;
-            }
-            
-            info!("Transferred {} points from {} to {}", amount, from_user, to_user);
-            Ok(true)
-        } else {
-            Ok(false)
+
+        let balance = users.get(&from_id).map(|u| u.points).ok_or(TransferError::UnknownSender)?;
+        if !users.contains_key(&to_id) {
+            return Err(TransferError::UnknownRecipient { username: to_user.to_string() });
         }
+        if balance < amount {
+            return Err(TransferError::InsufficientPoints { balance });
+        }
+
+        let today = chrono::Utc::now().date_naive();
+        let mut daily = self.daily_transfers.write().await;
+        let sent_today = daily.get(&from_id)
+            .filter(|(date, _)| *date == today)
+            .map(|(_, sent)| *sent)
+            .unwrap_or(0);
+        let remaining = (self.config.daily_transfer_cap - sent_today).max(0);
+        if amount > remaining {
+            return Err(TransferError::DailyCapExceeded { remaining });
+        }
+        daily.insert(from_id.clone(), (today, sent_today + amount));
+        drop(daily);
+
+        let now = chrono::Utc::now();
+        let mut transactions = Vec::new();
+
+        if let Some(from) = users.get_mut(&from_id) {
+            from.spend_points(amount, &format!("Transfer to {}", to_user));
+            transactions.push(PointsTransaction {
+                user_id: from.user_id.clone(),
+                transaction_type: TransactionType::Spent,
+                amount,
+                reason: format!("Transfer to {}", to_user),
+                timestamp: now,
+                balance_after: from.points,
+            });
+        }
+
+        if let Some(to) = users.get_mut(&to_id) {
+            // Credit the exact amount; earning multipliers don't apply to gifts
+            to.points += amount;
+            to.total_earned += amount;
+            transactions.push(PointsTransaction {
+                user_id: to.user_id.clone(),
+                transaction_type: TransactionType::Earned,
+                amount,
+                reason: format!("Transfer from {}", from_user),
+                timestamp: now,
+                balance_after: to.points,
+            });
+        }
+
+        drop(users);
+
+        for transaction in transactions {
+            self.add_transaction(transaction).await;
+        }
+
+        info!("Transferred {} points from {} to {}", amount, from_user, to_user);
+        Ok(())
     }
 
     /// Internal helper methods
@@ -601,4 +911,240 @@ impl PointsSystem {
             }
         });
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn system_with_users(names: &[&str]) -> PointsSystem {
+        let points = PointsSystem::new();
+        for name in names {
+            points.process_message(&ChatMessage::new("twitch", "channel", *name, "hi")).await.unwrap();
+        }
+        points
+    }
+
+    async fn balance(points: &PointsSystem, username: &str) -> i64 {
+        points.get_user_points("twitch", username).await.unwrap().points
+    }
+
+    #[tokio::test]
+    async fn test_transfer_moves_exact_amount() {
+        let points = system_with_users(&["alice", "bob"]).await;
+        // Subscriber multiplier must not inflate received gifts
+        points.process_message(&ChatMessage::new("twitch", "channel", "bob", "hi").with_subscriber(true)).await.unwrap();
+
+        let alice_before = balance(&points, "alice").await;
+        let bob_before = balance(&points, "bob").await;
+
+        points.transfer_points("twitch", "alice", "twitch", "bob", 100).await.unwrap();
+
+        assert_eq!(balance(&points, "alice").await, alice_before - 100);
+        assert_eq!(balance(&points, "bob").await, bob_before + 100);
+
+        let transactions = points.transactions.read().await;
+        assert!(transactions.iter().any(|t| t.user_id == "twitch:alice" && t.reason == "Transfer to bob"));
+        assert!(transactions.iter().any(|t| t.user_id == "twitch:bob" && t.reason == "Transfer from alice"));
+    }
+
+    #[tokio::test]
+    async fn test_linked_accounts_share_one_balance() {
+        let identities = Arc::new(RwLock::new(IdentityMap::new()));
+        identities.write().await.link_identities(("twitch", "Alice"), ("youtube", "AliceLive")).unwrap();
+        let points = PointsSystem::new().with_identities(identities);
+
+        points.process_message(&ChatMessage::new("twitch", "channel", "alice", "hi")).await.unwrap();
+        points.process_message(&ChatMessage::new("youtube", "channel", "AliceLive", "hi")).await.unwrap();
+
+        let on_twitch = points.get_user_points("twitch", "ALICE").await.unwrap();
+        let on_youtube = points.get_user_points("youtube", "alicelive").await.unwrap();
+        assert_eq!(on_twitch.user_id, "twitch:alice");
+        assert_eq!(on_twitch.messages_sent, 2);
+        assert_eq!(on_youtube.points, on_twitch.points);
+        assert_eq!(
+            points.transfer_points("youtube", "AliceLive", "twitch", "alice", 50).await,
+            Err(TransferError::SelfTransfer)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_transfer_rejections() {
+        let points = system_with_users(&["alice", "bob"]).await;
+        let alice_balance = balance(&points, "alice").await;
+
+        let transfer = |to: &'static str, amount: i64| {
+            let points = &points;
+            async move { points.transfer_points("twitch", "alice", "twitch", to, amount).await }
+        };
+
+        assert_eq!(transfer("bob", 0).await, Err(TransferError::InvalidAmount));
+        assert_eq!(transfer("bob", -50).await, Err(TransferError::InvalidAmount));
+        assert_eq!(transfer("bob", 5).await, Err(TransferError::BelowMinimum { min: 10 }));
+        assert_eq!(transfer("bob", 10001).await, Err(TransferError::AboveMaximum { max: 10000 }));
+        assert_eq!(transfer("alice", 50).await, Err(TransferError::SelfTransfer));
+        assert_eq!(transfer("Alice", 50).await, Err(TransferError::SelfTransfer));
+        assert_eq!(
+            transfer("nobody", 50).await,
+            Err(TransferError::UnknownRecipient { username: "nobody".to_string() })
+        );
+        assert_eq!(
+            transfer("bob", alice_balance + 1).await,
+            Err(TransferError::InsufficientPoints { balance: alice_balance })
+        );
+        assert_eq!(
+            points.transfer_points("twitch", "ghost", "twitch", "bob", 50).await,
+            Err(TransferError::UnknownSender)
+        );
+
+        // Nothing moved
+        assert_eq!(balance(&points, "alice").await, alice_balance);
+    }
+
+    #[tokio::test]
+    async fn test_transfer_daily_cap() {
+        let config = PointsConfig { daily_transfer_cap: 150, ..PointsConfig::default() };
+        let points = PointsSystem::with_config(config);
+        for name in ["alice", "bob"] {
+            points.process_message(&ChatMessage::new("twitch", "channel", name, "hi")).await.unwrap();
+        }
+
+        points.transfer_points("twitch", "alice", "twitch", "bob", 100).await.unwrap();
+        assert_eq!(
+            points.transfer_points("twitch", "alice", "twitch", "bob", 100).await,
+            Err(TransferError::DailyCapExceeded { remaining: 50 })
+        );
+        points.transfer_points("twitch", "alice", "twitch", "bob", 50).await.unwrap();
+
+        // Cap is per sender
+        points.transfer_points("twitch", "bob", "twitch", "alice", 100).await.unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_transfers_conserve_points() {
+        let points = Arc::new(system_with_users(&["alice", "bob"]).await);
+        let total_before = balance(&points, "alice").await + balance(&points, "bob").await;
+
+        let mut handles = Vec::new();
+        for i in 0..100 {
+            let points = Arc::clone(&points);
+            let (from, to) = if i % 2 == 0 { ("alice", "bob") } else { ("bob", "alice") };
+            handles.push(tokio::spawn(async move {
+                points.transfer_points("twitch", from, "twitch", to, 40).await
+            }));
+        }
+        for handle in handles {
+            let _ = handle.await.unwrap();
+        }
+
+        let alice = balance(&points, "alice").await;
+        let bob = balance(&points, "bob").await;
+        assert_eq!(alice + bob, total_before);
+        assert!(alice >= 0 && bob >= 0);
+    }
+
+    #[tokio::test]
+    async fn test_subscriber_earns_level_multiplier() {
+        let config = PointsConfig { points_per_message: 10, first_time_bonus: 0, ..PointsConfig::default() };
+        let points = PointsSystem::with_config(config);
+
+        for _ in 0..3 {
+            points.process_message(&ChatMessage::new("twitch", "channel", "viewer", "hi")).await.unwrap();
+            points.process_message(&ChatMessage::new("twitch", "channel", "sub", "hi").with_subscriber(true)).await.unwrap();
+        }
+
+        assert_eq!(balance(&points, "viewer").await, 30);
+        assert_eq!(balance(&points, "sub").await, 45);
+    }
+
+    #[tokio::test]
+    async fn test_multiplier_follows_current_level() {
+        let config = PointsConfig { points_per_message: 10, first_time_bonus: 0, ..PointsConfig::default() };
+        let points = PointsSystem::with_config(config);
+        points.set_level_multipliers(HashMap::from([(UserLevel::Subscriber, 2.0), (UserLevel::VIP, 3.0)])).await;
+
+        points.process_message(&ChatMessage::new("twitch", "channel", "alice", "hi").with_subscriber(true)).await.unwrap();
+        assert_eq!(balance(&points, "alice").await, 20);
+
+        // Subscription lapsed: unlisted levels earn the base rate
+        points.process_message(&ChatMessage::new("twitch", "channel", "alice", "hi")).await.unwrap();
+        assert_eq!(balance(&points, "alice").await, 30);
+
+        points.process_message(&ChatMessage::new("twitch", "channel", "alice", "hi").with_badges(vec!["vip".to_string()])).await.unwrap();
+        assert_eq!(balance(&points, "alice").await, 60);
+    }
+
+    fn seasons_config(dir: &tempfile::TempDir, season_reset: SeasonResetMode) -> PointsConfig {
+        PointsConfig {
+            seasons_path: dir.path().join("seasons.json"),
+            season_reset,
+            ..PointsConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_season_archive_and_full_reset() {
+        let dir = tempfile::tempdir().unwrap();
+        let points = PointsSystem::with_config(seasons_config(&dir, SeasonResetMode::Full));
+        for name in ["alice", "bob"] {
+            points.process_message(&ChatMessage::new("twitch", "channel", name, "hi")).await.unwrap();
+        }
+        points.add_points("twitch", "bob", 100, "test").await.unwrap();
+        let alice_before = balance(&points, "alice").await;
+        let bob_before = balance(&points, "bob").await;
+
+        let season = points.start_new_season("January").await.unwrap();
+        assert_eq!(season.standings.len(), 2);
+
+        let standings = points.get_season_leaderboard("january", 10).await.unwrap();
+        assert_eq!(standings[0].username, "bob");
+        assert_eq!(standings[0].points, bob_before);
+        assert_eq!(standings[1].rank, 2);
+        assert_eq!(standings[1].points, alice_before);
+
+        assert_eq!(balance(&points, "alice").await, 0);
+        assert_eq!(balance(&points, "bob").await, 0);
+        // Lifetime stats survive the reset
+        assert!(points.get_user_points("twitch", "bob").await.unwrap().total_earned > 0);
+
+        assert!(points.start_new_season("JANUARY").await.is_err());
+        assert!(points.get_season_leaderboard("February", 10).await.is_none());
+
+        // Archives persist across restarts
+        let reloaded = PointsSystem::with_config(seasons_config(&dir, SeasonResetMode::Full));
+        reloaded.load_seasons().await.unwrap();
+        assert_eq!(reloaded.list_seasons().await, vec!["January".to_string()]);
+        assert_eq!(reloaded.get_season_leaderboard("January", 1).await.unwrap(), standings[..1].to_vec());
+    }
+
+    #[tokio::test]
+    async fn test_season_soft_reset_and_keep() {
+        let dir = tempfile::tempdir().unwrap();
+        let points = PointsSystem::with_config(seasons_config(&dir, SeasonResetMode::Soft { keep_percent: 10 }));
+        points.process_message(&ChatMessage::new("twitch", "channel", "alice", "hi")).await.unwrap();
+        let before = balance(&points, "alice").await;
+
+        points.start_new_season("Spring").await.unwrap();
+        assert_eq!(balance(&points, "alice").await, before / 10);
+
+        let after_soft = balance(&points, "alice").await;
+        let season = points.start_new_season_with_mode("Summer", SeasonResetMode::Keep).await.unwrap();
+        assert_eq!(season.standings[0].points, after_soft);
+        assert_eq!(balance(&points, "alice").await, after_soft);
+    }
+
+    #[tokio::test]
+    async fn test_failed_season_archive_keeps_balances() {
+        let dir = tempfile::tempdir().unwrap();
+        let points = PointsSystem::with_config(seasons_config(&dir, SeasonResetMode::Full));
+        points.process_message(&ChatMessage::new("twitch", "channel", "alice", "hi")).await.unwrap();
+        let before = balance(&points, "alice").await;
+
+        // The archive can't be written into a directory that doesn't exist
+        points.set_seasons_path(dir.path().join("missing").join("seasons.json")).await;
+        assert!(points.start_new_season("January").await.is_err());
+
+        assert_eq!(balance(&points, "alice").await, before);
+        assert!(points.list_seasons().await.is_empty());
+    }
+}
@@ -2,7 +2,7 @@ use anyhow::Result;
 use log::{warn};
 use std::sync::Arc;
 
-use crate::bot::points::PointsSystem;
+use crate::bot::points::{PointsSystem, SeasonResetMode, TransferError};
 use crate::types::ChatMessage;
 
 pub struct PointsCommands {
@@ -47,6 +47,14 @@ impl PointsCommands {
                 self.handle_points_stats(message, response_sender).await?;
                 Ok(true)
             }
+            "newseason" => {
+                self.handle_new_season(args, message, response_sender).await?;
+                Ok(true)
+            }
+            "season" => {
+                self.handle_season_leaderboard(args, message, response_sender).await?;
+                Ok(true)
+            }
             _ => Ok(false), // Command not handled by points system
         }
     }
@@ -183,7 +191,7 @@ impl PointsCommands {
             return Ok(());
         }
 
-        let target_user = args[0];
+        let target_user = args[0].trim_start_matches('@');
         let amount = match args[1].parse::<i64>() {
             Ok(amt) if amt > 0 => amt,
             _ => {
@@ -193,36 +201,19 @@ impl PointsCommands {
             }
         };
 
-        // Minimum transfer amount
-        if amount < 10 {
-            let response = "❌ Minimum transfer amount is 10 points!".to_string();
-            self.send_response(response, message, response_sender).await?;
-            return Ok(());
-        }
-
-        // Can't transfer to yourself
-        if target_user.to_lowercase() == message.username.to_lowercase() {
-            let response = "❌ You can't transfer points to yourself! 🤷".to_string();
-            self.send_response(response, message, response_sender).await?;
-            return Ok(());
-        }
-
-        let success = self.points_system.transfer_points(
+        let result = self.points_system.transfer_points(
             &message.platform, &message.username,
             &message.platform, target_user,
             amount
-        ).await?;
+        ).await;
 
-        let response = if success {
-            format!(
+        let response = match result {
+            Ok(()) => format!(
                 "✅ {} successfully transferred {} points to {}! 💝",
                 message.username, amount, target_user
-            )
-        } else {
-            format!(
-                "❌ Transfer failed! You might not have enough points or {} doesn't exist.",
-                target_user
-            )
+            ),
+            Err(TransferError::SelfTransfer) => "❌ You can't transfer points to yourself! 🤷".to_string(),
+            Err(e) => format!("❌ Transfer failed: {}", e),
         };
 
         self.send_response(response, message, response_sender).await?;
@@ -313,6 +304,104 @@ impl PointsCommands {
         Ok(())
     }
 
+    async fn handle_new_season(
+        &self,
+        args: &[&str],
+        message: &ChatMessage,
+        response_sender: &tokio::sync::mpsc::Sender<(String, String, String)>,
+    ) -> Result<()> {
+        // Check if user is moderator
+        if !message.is_mod {
+            let response = "❌ This command is for moderators only!".to_string();
+            self.send_response(response, message, response_sender).await?;
+            return Ok(());
+        }
+
+        let usage = "Usage: !newseason <name> [full|keep|soft <percent>] - Archive the leaderboard and start a new season (mod only)";
+        let Some(name) = args.first() else {
+            self.send_response(usage.to_string(), message, response_sender).await?;
+            return Ok(());
+        };
+
+        let reset_mode = match args.get(1).map(|m| m.to_lowercase()).as_deref() {
+            None => None,
+            Some("full") => Some(SeasonResetMode::Full),
+            Some("keep") => Some(SeasonResetMode::Keep),
+            Some("soft") => match args.get(2).map(|p| p.trim_end_matches('%').parse::<u8>()) {
+                Some(Ok(keep_percent)) if keep_percent <= 100 => Some(SeasonResetMode::Soft { keep_percent }),
+                _ => {
+                    self.send_response(usage.to_string(), message, response_sender).await?;
+                    return Ok(());
+                }
+            },
+            Some(_) => {
+                self.send_response(usage.to_string(), message, response_sender).await?;
+                return Ok(());
+            }
+        };
+
+        let result = match reset_mode {
+            Some(mode) => self.points_system.start_new_season_with_mode(name, mode).await,
+            None => self.points_system.start_new_season(name).await,
+        };
+
+        let response = match result {
+            Ok(season) => {
+                let reset = match season.reset_mode {
+                    SeasonResetMode::Full => "balances reset".to_string(),
+                    SeasonResetMode::Keep => "balances kept".to_string(),
+                    SeasonResetMode::Soft { keep_percent } => format!("{}% of balances kept", keep_percent),
+                };
+                let winner = season.standings.first()
+                    .map(|s| format!(" | Champion: {} ({} pts) 🏆", s.display_name.as_deref().unwrap_or(&s.username), s.points))
+                    .unwrap_or_default();
+                format!("📦 Season '{}' archived with {} users, {}{}", season.name, season.standings.len(), reset, winner)
+            }
+            Err(e) => format!("❌ Couldn't start a new season: {}", e),
+        };
+
+        self.send_response(response, message, response_sender).await?;
+        Ok(())
+    }
+
+    async fn handle_season_leaderboard(
+        &self,
+        args: &[&str],
+        message: &ChatMessage,
+        response_sender: &tokio::sync::mpsc::Sender<(String, String, String)>,
+    ) -> Result<()> {
+        let Some(season) = args.first() else {
+            let seasons = self.points_system.list_seasons().await;
+            let response = if seasons.is_empty() {
+                "📦 No seasons archived yet!".to_string()
+            } else {
+                format!("📦 Seasons: {} | Use !season <name> to see the final standings", seasons.join(", "))
+            };
+            self.send_response(response, message, response_sender).await?;
+            return Ok(());
+        };
+
+        let response = match self.points_system.get_season_leaderboard(season, 5).await {
+            Some(standings) if !standings.is_empty() => {
+                let mut response = format!("🏆 SEASON {} FINAL STANDINGS 🏆 | ", season.to_uppercase());
+                for standing in &standings {
+                    response.push_str(&format!(
+                        "{}. {} ({} pts) ",
+                        standing.rank,
+                        standing.display_name.as_deref().unwrap_or(&standing.username),
+                        standing.points
+                    ));
+                }
+                response
+            }
+            Some(_) => format!("📦 Season '{}' had no participants", season),
+            None => format!("❌ Season '{}' not found", season),
+        };
+
+        self.send_response(response, message, response_sender).await?;
+        Ok(())
+    }
+
     async fn send_response(
         &self,
         response: String,
@@ -0,0 +1,264 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+
+/// When a burst of new chatters counts as a raid, and how long raid mode lasts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RaidModeConfig {
+    /// First-time chatters within `detection_window_seconds` that start raid mode (0 disables detection)
+    pub new_chatter_threshold: usize,
+    pub detection_window_seconds: u64,
+    /// Raid mode ends on its own after this long
+    pub duration_seconds: u64,
+    /// Let first-time chatters link the raiding channel while raid mode is on
+    pub relax_link_rules: bool,
+}
+
+impl Default for RaidModeConfig {
+    fn default() -> Self {
+        Self {
+            new_chatter_threshold: 10,
+            detection_window_seconds: 30,
+            duration_seconds: 120,
+            relax_link_rules: true,
+        }
+    }
+}
+
+/// Link to a channel as raiders would post it, for platforms with channel URLs
+pub fn channel_link(platform: &str, channel: &str) -> Option<String> {
+    match platform {
+        "twitch" => Some(format!("twitch.tv/{}", channel)),
+        "youtube" => Some(format!("youtube.com/@{}", channel)),
+        _ => None,
+    }
+}
+
+/// A finished raid window and the welcomes held back during it
+#[derive(Debug, Clone, PartialEq)]
+pub struct RaidSummary {
+    pub platform: String,
+    pub channel: String,
+    pub raiding_channel: Option<String>,
+    pub welcomed: Vec<String>,
+}
+
+/// Most raider names listed in the batched welcome
+const MAX_WELCOME_NAMES: usize = 5;
+
+impl RaidSummary {
+    /// One combined welcome for everyone whose announcements were held back
+    pub fn welcome_message(&self) -> Option<String> {
+        if self.welcomed.is_empty() {
+            return None;
+        }
+
+        let from = self.raiding_channel.as_ref()
+            .map(|channel| format!(" from {}", channel))
+            .unwrap_or_default();
+        let mut names = self.welcomed.iter().take(MAX_WELCOME_NAMES).cloned().collect::<Vec<_>>().join(", ");
+        if self.welcomed.len() > MAX_WELCOME_NAMES {
+            names.push_str(&format!(" and {} more", self.welcomed.len() - MAX_WELCOME_NAMES));
+        }
+        Some(format!("👋 Welcome to everyone joining us{}: {}!", from, names))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct RaidWindow {
+    ends_at: DateTime<Utc>,
+    raiding_channel: Option<String>,
+    welcomed: Vec<String>,
+}
+
+/// Per-channel raid windows, started explicitly or by a burst of first-time chatters
+#[derive(Debug, Default)]
+pub struct RaidMode {
+    config: RaidModeConfig,
+    first_chatters: HashMap<(String, String), VecDeque<DateTime<Utc>>>,
+    active: HashMap<(String, String), RaidWindow>,
+}
+
+impl RaidMode {
+    pub fn new(config: RaidModeConfig) -> Self {
+        Self {
+            config,
+            first_chatters: HashMap::new(),
+            active: HashMap::new(),
+        }
+    }
+
+    pub fn set_config(&mut self, config: RaidModeConfig) {
+        self.config = config;
+    }
+
+    pub fn config(&self) -> &RaidModeConfig {
+        &self.config
+    }
+
+    /// Start (or extend) raid mode for a channel, e.g. on a platform raid notification
+    pub fn start(&mut self, platform: &str, channel: &str, raiding_channel: Option<String>, now: DateTime<Utc>) {
+        let ends_at = now + Duration::seconds(self.config.duration_seconds as i64);
+        let window = self.active.entry(Self::key(platform, channel)).or_insert_with(|| RaidWindow {
+            ends_at,
+            raiding_channel: None,
+            welcomed: Vec::new(),
+        });
+        window.ends_at = ends_at;
+        if let Some(raiding_channel) = raiding_channel {
+            window.raiding_channel = Some(raiding_channel.trim_start_matches('@').to_lowercase());
+        }
+    }
+
+    /// Record a first-time chatter; returns true when this burst just started raid mode
+    pub fn record_first_message(&mut self, platform: &str, channel: &str, now: DateTime<Utc>) -> bool {
+        if self.config.new_chatter_threshold == 0 || self.is_active(platform, channel, now) {
+            return false;
+        }
+
+        let window = Duration::seconds(self.config.detection_window_seconds as i64);
+        let key = Self::key(platform, channel);
+        let recent = self.first_chatters.entry(key.clone()).or_default();
+        recent.push_back(now);
+        while recent.front().is_some_and(|t| now - *t > window) {
+            recent.pop_front();
+        }
+
+        if recent.len() >= self.config.new_chatter_threshold {
+            self.first_chatters.remove(&key);
+            self.start(platform, channel, None, now);
+            return true;
+        }
+        false
+    }
+
+    pub fn is_active(&self, platform: &str, channel: &str, now: DateTime<Utc>) -> bool {
+        self.active.get(&Self::key(platform, channel)).is_some_and(|w| now < w.ends_at)
+    }
+
+    /// Channel the current raid came from, when known
+    pub fn raiding_channel(&self, platform: &str, channel: &str, now: DateTime<Utc>) -> Option<&str> {
+        self.active.get(&Self::key(platform, channel))
+            .filter(|w| now < w.ends_at)
+            .and_then(|w| w.raiding_channel.as_deref())
+    }
+
+    /// Hold back a welcome/announcement for `username` during raid mode; returns
+    /// false (send it now) when the channel isn't in raid mode
+    pub fn hold_welcome(&mut self, platform: &str, channel: &str, username: &str, now: DateTime<Utc>) -> bool {
+        match self.active.get_mut(&Self::key(platform, channel)) {
+            Some(window) if now < window.ends_at => {
+                if !window.welcomed.iter().any(|u| u.eq_ignore_ascii_case(username)) {
+                    window.welcomed.push(username.to_string());
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// End raid mode for a channel now
+    pub fn end(&mut self, platform: &str, channel: &str) -> Option<RaidSummary> {
+        let key = Self::key(platform, channel);
+        self.active.remove(&key).map(|window| Self::summary(key, window))
+    }
+
+    /// Take summaries for raid windows that have run their course
+    pub fn take_expired(&mut self, now: DateTime<Utc>) -> Vec<RaidSummary> {
+        let expired: Vec<_> = self.active.iter()
+            .filter(|(_, w)| now >= w.ends_at)
+            .map(|(key, _)| key.clone())
+            .collect();
+
+        expired.into_iter()
+            .filter_map(|key| self.active.remove(&key).map(|window| Self::summary(key, window)))
+            .collect()
+    }
+
+    fn summary((platform, channel): (String, String), window: RaidWindow) -> RaidSummary {
+        RaidSummary {
+            platform,
+            channel,
+            raiding_channel: window.raiding_channel,
+            welcomed: window.welcomed,
+        }
+    }
+
+    fn key(platform: &str, channel: &str) -> (String, String) {
+        (platform.to_string(), channel.to_lowercase())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn raid_mode() -> RaidMode {
+        RaidMode::new(RaidModeConfig {
+            new_chatter_threshold: 3,
+            detection_window_seconds: 10,
+            duration_seconds: 60,
+            relax_link_rules: true,
+        })
+    }
+
+    #[test]
+    fn test_channel_links_depend_on_the_platform() {
+        assert_eq!(channel_link("twitch", "friendlystreamer").as_deref(), Some("twitch.tv/friendlystreamer"));
+        assert_eq!(channel_link("youtube", "friendlystreamer").as_deref(), Some("youtube.com/@friendlystreamer"));
+        assert_eq!(channel_link("mock", "friendlystreamer"), None);
+    }
+
+    #[test]
+    fn test_burst_of_new_chatters_starts_raid_mode() {
+        let mut raid = raid_mode();
+        let start = Utc::now();
+
+        assert!(!raid.record_first_message("twitch", "main", start));
+        assert!(!raid.record_first_message("twitch", "main", start + Duration::seconds(1)));
+        // Other channels are tracked separately
+        assert!(!raid.record_first_message("twitch", "other", start + Duration::seconds(1)));
+        assert!(raid.record_first_message("twitch", "main", start + Duration::seconds(2)));
+
+        assert!(raid.is_active("twitch", "main", start + Duration::seconds(30)));
+        assert!(!raid.is_active("twitch", "other", start + Duration::seconds(30)));
+        // Auto-exits after the configured duration
+        assert!(!raid.is_active("twitch", "main", start + Duration::seconds(63)));
+    }
+
+    #[test]
+    fn test_slow_new_chatters_are_not_a_raid() {
+        let mut raid = raid_mode();
+        let start = Utc::now();
+
+        for i in 0..10 {
+            assert!(!raid.record_first_message("twitch", "main", start + Duration::seconds(i * 20)));
+        }
+    }
+
+    #[test]
+    fn test_welcomes_are_batched_until_raid_ends() {
+        let mut raid = raid_mode();
+        let start = Utc::now();
+        raid.start("twitch", "main", Some("@FriendlyStreamer".to_string()), start);
+        assert_eq!(raid.raiding_channel("twitch", "main", start), Some("friendlystreamer"));
+
+        for name in ["a", "b", "c", "d", "e", "f", "g", "A"] {
+            assert!(raid.hold_welcome("twitch", "main", name, start + Duration::seconds(5)));
+        }
+        assert!(raid.take_expired(start + Duration::seconds(30)).is_empty());
+
+        let summaries = raid.take_expired(start + Duration::seconds(60));
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].welcomed.len(), 7);
+        assert_eq!(
+            summaries[0].welcome_message().unwrap(),
+            "👋 Welcome to everyone joining us from friendlystreamer: a, b, c, d, e and 2 more!"
+        );
+
+        // Normal behaviour afterwards
+        assert!(!raid.hold_welcome("twitch", "main", "late", start + Duration::seconds(61)));
+        assert!(raid.raiding_channel("twitch", "main", start + Duration::seconds(61)).is_none());
+        assert!(raid.end("twitch", "main").is_none());
+    }
+}
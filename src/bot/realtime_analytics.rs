@@ -6,6 +6,8 @@ use std::sync::Arc;
 use log::{info, debug, warn};
 use chrono::Timelike;
 
+use crate::bot::redaction::RedactionMode;
+
 /// Real-time analytics for filter performance and effectiveness
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FilterAnalytics {
@@ -38,6 +40,9 @@ pub struct FilterAnalytics {
     // User feedback
     pub user_reports: Vec<UserReport>,
     pub moderator_reviews: Vec<ModeratorReview>,
+    /// Appeals traced back to an action this filter took
+    #[serde(default)]
+    pub appeals: u64,
     
     // Resource usage
     pub cpu_usage_percent: f64,
@@ -45,6 +50,44 @@ pub struct FilterAnalytics {
     
     // Adaptive suggestions
     pub optimization_suggestions: Vec<OptimizationSuggestion>,
+
+    // Most recent matched messages, oldest first, for tuning
+    #[serde(default)]
+    pub recent_matches: VecDeque<RecentMatch>,
+}
+
+/// A privacy-trimmed snippet of a message that triggered a filter
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecentMatch {
+    pub timestamp: DateTime<Utc>,
+    pub snippet: String,
+    pub is_true_positive: bool,
+}
+
+impl RecentMatch {
+    /// Longest snippet kept from a matched message
+    pub const MAX_SNIPPET_CHARS: usize = 80;
+
+    /// Build a snippet with @mentions masked and the text truncated
+    pub fn from_message(content: &str, is_true_positive: bool) -> Self {
+        let masked: Vec<&str> = content.split_whitespace()
+            .map(|word| if word.starts_with('@') && word.len() > 1 { "@user" } else { word })
+            .collect();
+        let masked = masked.join(" ");
+
+        let snippet = if masked.chars().count() > Self::MAX_SNIPPET_CHARS {
+            let truncated: String = masked.chars().take(Self::MAX_SNIPPET_CHARS).collect();
+            format!("{}…", truncated)
+        } else {
+            masked
+        };
+
+        Self {
+            timestamp: Utc::now(),
+            snippet,
+            is_true_positive,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -139,11 +182,16 @@ pub enum Difficulty {
     Expert,      // Requires specialized knowledge
 }
 
+/// Default number of recent matches kept per filter
+pub const DEFAULT_RECENT_MATCHES_CAPACITY: usize = 25;
+
 /// Real-time analytics system for monitoring filter performance
 pub struct FilterAnalyticsSystem {
     analytics: Arc<RwLock<HashMap<String, FilterAnalytics>>>,
     global_metrics: Arc<RwLock<GlobalMetrics>>,
     alert_thresholds: AlertThresholds,
+    recent_matches_capacity: usize,
+    redaction: RwLock<RedactionMode>,
     optimization_engine: Arc<RwLock<OptimizationEngine>>, // This should be wrapped
 }
 
@@ -195,10 +243,23 @@ impl FilterAnalyticsSystem {
                 user_satisfaction_score: 0.8,
             })),
             alert_thresholds: AlertThresholds::default(),
+            recent_matches_capacity: DEFAULT_RECENT_MATCHES_CAPACITY,
+            redaction: RwLock::new(RedactionMode::Off),
             optimization_engine: Arc::new(RwLock::new(OptimizationEngine::new())), // Wrap in Arc<RwLock<>>        
         }
     }
 
+    /// Set how many recent matches are kept per filter
+    pub fn with_recent_matches_capacity(mut self, capacity: usize) -> Self {
+        self.recent_matches_capacity = capacity;
+        self
+    }
+
+    /// Set how message content is stored in recent matches and user reports
+    pub async fn set_redaction_mode(&self, mode: RedactionMode) {
+        *self.redaction.write().await = mode;
+    }
+
     /// Record a filter trigger event
     pub async fn record_trigger(
         &self,  // &self instead of &mut self
@@ -206,7 +267,7 @@ impl FilterAnalyticsSystem {
         filter_type: &str,
         is_true_positive: bool,
         response_time_ms: f64,
-        _message_content: &str,
+        message_content: &str,
     ) {
         let mut analytics = self.analytics.write().await;
         let filter_analytics = analytics.entry(filter_id.to_string())
@@ -233,6 +294,15 @@ impl FilterAnalyticsSystem {
         filter_analytics.peak_response_time_ms = 
             filter_analytics.peak_response_time_ms.max(response_time_ms);
 
+        // Keep a bounded window of recent matches for tuning
+        if self.recent_matches_capacity > 0 {
+            let content = self.redaction.read().await.apply(message_content);
+            filter_analytics.recent_matches.push_back(RecentMatch::from_message(&content, is_true_positive));
+            while filter_analytics.recent_matches.len() > self.recent_matches_capacity {
+                filter_analytics.recent_matches.pop_front();
+            }
+        }
+
         // Update effectiveness metrics
         filter_analytics.update_effectiveness_metrics();
 
@@ -270,13 +340,14 @@ impl FilterAnalyticsSystem {
         message_content: &str,
         explanation: Option<String>,
     ) {
+        let message_content = self.redaction.read().await.apply(message_content);
         let mut analytics = self.analytics.write().await;
         if let Some(filter_analytics) = analytics.get_mut(filter_id) {
             let report = UserReport {
                 timestamp: Utc::now(),
                 user_id: user_id.to_string(),
                 report_type: report_type.clone(),
-                message_content: message_content.to_string(),
+                message_content,
                 user_explanation: explanation,
                 resolved: false,
                 resolution: None,
@@ -304,6 +375,32 @@ impl FilterAnalyticsSystem {
         }
     }
 
+    /// Count an appeal against the filter whose action is being appealed. The
+    /// appeal itself is stored with `record_user_report`.
+    pub async fn record_appeal(&self, filter_id: &str) {
+        let mut analytics = self.analytics.write().await;
+        // The trigger may predate analytics (e.g. a restart), but the appeal still counts
+        let filter_analytics = analytics.entry(filter_id.to_string())
+            .or_insert_with(|| FilterAnalytics::new(filter_id, "unknown"));
+        filter_analytics.appeals += 1;
+        info!("Appeal linked to filter '{}' ({} total)", filter_id, filter_analytics.appeals);
+    }
+
+    /// Appeal-based false positive rates, filters with the most appeals first
+    pub async fn get_appeal_stats(&self) -> Vec<FilterAppealStats> {
+        let mut stats: Vec<FilterAppealStats> = self.analytics.read().await.values()
+            .filter(|fa| fa.appeals > 0)
+            .map(|fa| FilterAppealStats {
+                filter_id: fa.filter_id.clone(),
+                triggers: fa.total_triggers,
+                appeals: fa.appeals,
+                false_positive_rate: fa.appeal_rate(),
+            })
+            .collect();
+        stats.sort_by(|a, b| b.appeals.cmp(&a.appeals).then_with(|| a.filter_id.cmp(&b.filter_id)));
+        stats
+    }
+
     /// Record moderator review
     pub async fn record_moderator_review(
         &self,
@@ -337,6 +434,14 @@ impl FilterAnalyticsSystem {
         self.analytics.read().await.get(filter_id).cloned()
     }
 
+    /// Get up to `n` of the most recent matches for a filter, newest first
+    pub async fn get_recent_matches(&self, filter_id: &str, n: usize) -> Vec<RecentMatch> {
+        self.analytics.read().await
+            .get(filter_id)
+            .map(|fa| fa.recent_matches.iter().rev().take(n).cloned().collect())
+            .unwrap_or_default()
+    }
+
     /// Get comprehensive analytics dashboard data
     pub async fn get_dashboard_data(&self) -> AnalyticsDashboard {
         let analytics = self.analytics.read().await;
@@ -360,6 +465,7 @@ impl FilterAnalyticsSystem {
                     HealthStatus::Critical 
                 },
                 last_updated: fa.last_updated,
+                recent_matches: fa.recent_matches.iter().rev().take(FilterSummary::RECENT_MATCHES_SHOWN).cloned().collect(),
             })
             .collect();
 
@@ -585,9 +691,20 @@ impl FilterAnalytics {
             pattern_evolution: Vec::new(),
             user_reports: Vec::new(),
             moderator_reviews: Vec::new(),
+            appeals: 0,
             cpu_usage_percent: 0.0,
             memory_usage_bytes: 0,
             optimization_suggestions: Vec::new(),
+            recent_matches: VecDeque::new(),
+        }
+    }
+
+    /// Share of this filter's triggers that were appealed (0.0 to 1.0)
+    pub fn appeal_rate(&self) -> f64 {
+        if self.appeals == 0 {
+            0.0
+        } else {
+            (self.appeals as f64 / self.total_triggers.max(1) as f64).min(1.0)
         }
     }
 
@@ -764,6 +881,22 @@ pub struct FilterSummary {
     pub average_response_time: f64,
     pub health_status: HealthStatus,
     pub last_updated: DateTime<Utc>,
+    pub recent_matches: Vec<RecentMatch>,
+}
+
+impl FilterSummary {
+    /// Number of recent matches included in the dashboard filter detail
+    pub const RECENT_MATCHES_SHOWN: usize = 5;
+}
+
+/// How often a filter's actions are appealed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FilterAppealStats {
+    pub filter_id: String,
+    pub triggers: u64,
+    pub appeals: u64,
+    /// Appeals per trigger, capped at 1.0
+    pub false_positive_rate: f64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -870,4 +1003,85 @@ mod tests {
         assert_eq!(filter_analytics.user_reports.len(), 1);
         assert_eq!(filter_analytics.false_positives, 1);
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_recent_matches_ring_buffer() {
+        let analytics_system = FilterAnalyticsSystem::new().with_recent_matches_capacity(3);
+
+        for i in 0..5 {
+            analytics_system.record_trigger(
+                "test_filter",
+                "blacklist",
+                true,
+                1.0,
+                &format!("spam message {}", i),
+            ).await;
+        }
+
+        // Buffer is capped at capacity and returned newest first
+        let recent = analytics_system.get_recent_matches("test_filter", 10).await;
+        assert_eq!(recent.len(), 3);
+        assert_eq!(recent[0].snippet, "spam message 4");
+        assert_eq!(recent[2].snippet, "spam message 2");
+
+        assert_eq!(analytics_system.get_recent_matches("test_filter", 1).await.len(), 1);
+        assert!(analytics_system.get_recent_matches("unknown", 5).await.is_empty());
+
+        let dashboard = analytics_system.get_dashboard_data().await;
+        assert_eq!(dashboard.filter_summaries[0].recent_matches.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_redaction_of_stored_snippets() {
+        let analytics_system = FilterAnalyticsSystem::new();
+        let spam = "buy followers at badsite.com today";
+
+        // Full text by default
+        analytics_system.record_trigger("test_filter", "blacklist", true, 1.0, spam).await;
+        assert_eq!(analytics_system.get_recent_matches("test_filter", 1).await[0].snippet, spam);
+
+        analytics_system.set_redaction_mode(RedactionMode::Truncate { max_chars: 13 }).await;
+        analytics_system.record_trigger("test_filter", "blacklist", true, 1.0, spam).await;
+        assert_eq!(analytics_system.get_recent_matches("test_filter", 1).await[0].snippet, "buy followers…");
+
+        analytics_system.set_redaction_mode(RedactionMode::Hash).await;
+        analytics_system.record_trigger("test_filter", "blacklist", true, 1.0, spam).await;
+        analytics_system.record_user_report("test_filter", "viewer", UserReportType::MissedViolation, spam, None).await;
+        let analytics = analytics_system.get_filter_analytics("test_filter").await.unwrap();
+        assert!(!analytics.recent_matches.back().unwrap().snippet.contains("badsite"));
+        assert!(!analytics.user_reports[0].message_content.contains("badsite"));
+        assert_eq!(analytics.user_reports[0].message_content, RedactionMode::Hash.apply(spam));
+    }
+
+    #[tokio::test]
+    async fn test_appeal_rate_per_filter() {
+        let analytics_system = FilterAnalyticsSystem::new();
+        for _ in 0..4 {
+            analytics_system.record_trigger("links", "blacklist", true, 1.0, "spam.example").await;
+            analytics_system.record_trigger("caps", "caps", true, 1.0, "LOUD").await;
+        }
+        analytics_system.record_appeal("links").await;
+        analytics_system.record_appeal("caps").await;
+        analytics_system.record_appeal("caps").await;
+        analytics_system.record_appeal("gone").await;
+
+        let stats = analytics_system.get_appeal_stats().await;
+        let ids: Vec<_> = stats.iter().map(|s| s.filter_id.as_str()).collect();
+        assert_eq!(ids, vec!["caps", "gone", "links"]);
+        assert_eq!(stats[0].false_positive_rate, 0.5);
+        assert_eq!(stats[2].false_positive_rate, 0.25);
+        // Appeals against a filter with no recorded triggers can't exceed 100%
+        assert_eq!(stats[1].triggers, 0);
+        assert_eq!(stats[1].false_positive_rate, 1.0);
+    }
+
+    #[test]
+    fn test_recent_match_privacy_trim() {
+        let long_message = format!("hey @someone {}", "x".repeat(200));
+        let recent = RecentMatch::from_message(&long_message, true);
+
+        assert!(recent.snippet.starts_with("hey @user "));
+        assert!(!recent.snippet.contains("someone"));
+        assert_eq!(recent.snippet.chars().count(), RecentMatch::MAX_SNIPPET_CHARS + 1);
+    }
+}
@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, RwLock};
+
+/// How chat message text appears in logs and stored analytics snippets
+///
+/// In-memory moderation history keeps the full text so `!why` and filter
+/// suggestions still have context; only output that leaves the process (or
+/// outlives it) is redacted.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum RedactionMode {
+    /// Full message text
+    #[default]
+    Off,
+    /// Keep the first `max_chars` characters
+    Truncate { max_chars: usize },
+    /// Replace the text with a short fingerprint and its length
+    Hash,
+}
+
+impl RedactionMode {
+    /// Apply this mode to message content
+    pub fn apply(&self, content: &str) -> String {
+        match self {
+            RedactionMode::Off => content.to_string(),
+            RedactionMode::Truncate { max_chars } => {
+                if content.chars().count() > *max_chars {
+                    format!("{}…", content.chars().take(*max_chars).collect::<String>())
+                } else {
+                    content.to_string()
+                }
+            }
+            RedactionMode::Hash => {
+                // Stable within a build, so repeats of the same message can be correlated
+                let mut hasher = std::collections::hash_map::DefaultHasher::new();
+                content.hash(&mut hasher);
+                format!("[#{:08x}, {} chars]", hasher.finish() as u32, content.chars().count())
+            }
+        }
+    }
+}
+
+/// How message content is written to logs, shared by everything that logs it.
+/// Clones are handles to the same setting, so a config reload reaches them all.
+#[derive(Debug, Clone, Default)]
+pub struct LogRedaction(Arc<RwLock<RedactionMode>>);
+
+impl LogRedaction {
+    pub fn set(&self, mode: RedactionMode) {
+        *self.0.write().unwrap_or_else(|e| e.into_inner()) = mode;
+    }
+
+    pub fn mode(&self) -> RedactionMode {
+        self.0.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Message content as it should appear in a log line
+    pub fn redact(&self, content: &str) -> String {
+        self.0.read().unwrap_or_else(|e| e.into_inner()).apply(content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redaction_modes() {
+        let message = "visit badsite.com for free followers";
+
+        assert_eq!(RedactionMode::Off.apply(message), message);
+        assert_eq!(RedactionMode::Truncate { max_chars: 5 }.apply(message), "visit…");
+        assert_eq!(RedactionMode::Truncate { max_chars: 100 }.apply(message), message);
+
+        let hashed = RedactionMode::Hash.apply(message);
+        assert!(!hashed.contains("badsite"));
+        assert!(hashed.ends_with(", 36 chars]"));
+        assert_eq!(hashed, RedactionMode::Hash.apply(message));
+        assert_ne!(hashed, RedactionMode::Hash.apply("something else"));
+    }
+
+    #[test]
+    fn test_log_redaction_setting() {
+        let redaction = LogRedaction::default();
+        let shared = redaction.clone();
+        assert_eq!(shared.redact("hello chat"), "hello chat");
+
+        redaction.set(RedactionMode::Truncate { max_chars: 4 });
+        assert_eq!(shared.redact("hello chat"), "hell…");
+        assert_eq!(shared.mode(), RedactionMode::Truncate { max_chars: 4 });
+        // Separate settings don't affect each other
+        assert_eq!(LogRedaction::default().redact("hello chat"), "hello chat");
+
+        redaction.set(RedactionMode::Off);
+        assert_eq!(shared.redact("hello chat"), "hello chat");
+    }
+
+    #[test]
+    fn test_redaction_mode_config_format() {
+        let mode: RedactionMode = serde_yaml::from_str("mode: truncate\nmax_chars: 20").unwrap();
+        assert_eq!(mode, RedactionMode::Truncate { max_chars: 20 });
+        let mode: RedactionMode = serde_yaml::from_str("mode: hash").unwrap();
+        assert_eq!(mode, RedactionMode::Hash);
+    }
+}
@@ -0,0 +1,127 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::bot::schedule;
+use crate::config::TimeRange;
+use crate::platforms::ChatRestriction;
+
+/// When chat is locked down automatically, e.g. for late-night unmonitored streams
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RestrictedHoursConfig {
+    /// Restricted while any of these ranges contains the current time (empty disables the schedule)
+    #[serde(default)]
+    pub windows: Vec<TimeRange>,
+    /// Platform-side mode switched on for the window
+    #[serde(default)]
+    pub restriction: Option<ChatRestriction>,
+    /// Stricter filters that are only enabled during the window
+    #[serde(default)]
+    pub strict_filters: Vec<String>,
+    #[serde(default)]
+    pub start_message: Option<String>,
+    #[serde(default)]
+    pub end_message: Option<String>,
+}
+
+/// A change of mode the bot should carry out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestrictedHoursChange {
+    Activated,
+    Deactivated,
+}
+
+/// Tracks whether chat is currently in restricted mode
+#[derive(Debug, Default)]
+pub struct RestrictedHours {
+    config: RestrictedHoursConfig,
+    active: bool,
+}
+
+impl RestrictedHours {
+    pub fn new(config: RestrictedHoursConfig) -> Self {
+        Self {
+            config,
+            active: false,
+        }
+    }
+
+    /// Replace the schedule; the next `update` applies it
+    pub fn set_config(&mut self, config: RestrictedHoursConfig) {
+        self.config = config;
+    }
+
+    pub fn config(&self) -> &RestrictedHoursConfig {
+        &self.config
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    pub fn in_window(&self, at: DateTime<Utc>) -> bool {
+        // No windows means no schedule, not an always-on one
+        !self.config.windows.is_empty() && schedule::is_active_at(&self.config.windows, &[], None, at)
+    }
+
+    /// Check the schedule, returning a change when the mode should switch
+    pub fn update(&mut self, now: DateTime<Utc>) -> Option<RestrictedHoursChange> {
+        let should_be_active = self.in_window(now);
+        if should_be_active == self.active {
+            return None;
+        }
+
+        self.active = should_be_active;
+        Some(if should_be_active {
+            RestrictedHoursChange::Activated
+        } else {
+            RestrictedHoursChange::Deactivated
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn late_night() -> RestrictedHours {
+        RestrictedHours::new(RestrictedHoursConfig {
+            windows: vec![TimeRange {
+                start: "23:00".to_string(),
+                end: "07:00".to_string(),
+                timezone: Some("UTC".to_string()),
+            }],
+            restriction: Some(ChatRestriction::FollowersOnly),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_mode_follows_the_window() {
+        let mut restricted = late_night();
+        let at = |day, hour| Utc.with_ymd_and_hms(2026, 5, day, hour, 0, 0).unwrap();
+
+        assert_eq!(restricted.update(at(1, 20)), None);
+        assert!(!restricted.is_active());
+
+        assert_eq!(restricted.update(at(1, 23)), Some(RestrictedHoursChange::Activated));
+        assert!(restricted.is_active());
+        // Still inside the window after midnight: no repeated change
+        assert_eq!(restricted.update(at(2, 3)), None);
+        assert!(restricted.is_active());
+
+        assert_eq!(restricted.update(at(2, 7)), Some(RestrictedHoursChange::Deactivated));
+        assert!(!restricted.is_active());
+        assert_eq!(restricted.update(at(2, 12)), None);
+    }
+
+    #[test]
+    fn test_removing_the_schedule_lifts_the_restriction() {
+        let mut restricted = late_night();
+        let night = Utc.with_ymd_and_hms(2026, 5, 1, 23, 30, 0).unwrap();
+        assert_eq!(restricted.update(night), Some(RestrictedHoursChange::Activated));
+
+        restricted.set_config(RestrictedHoursConfig::default());
+        assert_eq!(restricted.update(night), Some(RestrictedHoursChange::Deactivated));
+    }
+}
@@ -0,0 +1,283 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Datelike, FixedOffset, NaiveTime, Offset, TimeZone, Utc, Weekday};
+use chrono_tz::TZ_VARIANTS;
+use log::warn;
+use serde::{Deserialize, Serialize};
+
+use crate::config::TimeRange;
+
+/// Offset from UTC of `timezone` at `at`: an IANA zone name, matched ignoring
+/// case ("America/New_York", "Europe/London"), following its daylight saving
+/// rules, or an explicit offset like "+05:30", "UTC-8" or "GMT+1". Defaults to
+/// UTC; unknown names give `None`.
+pub fn utc_offset_at(timezone: Option<&str>, at: DateTime<Utc>) -> Option<FixedOffset> {
+    let timezone = timezone.map(str::trim).filter(|tz| !tz.is_empty()).unwrap_or("UTC");
+    if let Some(zone) = TZ_VARIANTS.iter().find(|zone| zone.name().eq_ignore_ascii_case(timezone)) {
+        return Some(zone.offset_from_utc_datetime(&at.naive_utc()).fix());
+    }
+
+    let upper = timezone.to_uppercase();
+    let offset = upper.trim_start_matches("UTC").trim_start_matches("GMT");
+    let (sign, rest) = match offset.chars().next()? {
+        '+' => (1, &offset[1..]),
+        '-' => (-1, &offset[1..]),
+        _ => return None,
+    };
+    let (hours, minutes) = rest.split_once(':').unwrap_or((rest, "0"));
+    let (hours, minutes): (i32, i32) = (hours.parse().ok()?, minutes.parse().ok()?);
+    if hours > 14 || minutes >= 60 {
+        return None;
+    }
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+/// Parse day names: "Mon", "monday", "FRI", ...
+pub fn parse_days(days: &[String]) -> Result<Vec<Weekday>> {
+    days.iter()
+        .map(|day| day.trim().parse::<Weekday>().map_err(|_| anyhow!("Unknown day '{}'", day)))
+        .collect()
+}
+
+/// Whether `at` is inside one of `windows` (any time, with none) on one of `days`
+/// (any day, with none).
+///
+/// Times are wall-clock times in each window's own timezone, falling back to
+/// `timezone` and then UTC, so region names follow daylight saving. A window that
+/// ends before it starts wraps past midnight and belongs to the day it started on:
+/// 22:00-02:00 on Fridays includes 01:00 on Saturday. Invalid windows and unknown
+/// timezones never match.
+pub fn is_active_at(windows: &[TimeRange], days: &[Weekday], timezone: Option<&str>, at: DateTime<Utc>) -> bool {
+    if windows.is_empty() {
+        if days.is_empty() {
+            return true;
+        }
+        let Some(offset) = utc_offset_at(timezone, at) else {
+            warn!("Ignoring schedule with unknown timezone {:?}", timezone);
+            return false;
+        };
+        return days.contains(&at.with_timezone(&offset).weekday());
+    }
+
+    windows.iter()
+        .filter_map(|window| window_start_day(window, timezone, at))
+        .any(|day| days.is_empty() || days.contains(&day))
+}
+
+/// `is_active_at` for the current time
+pub fn is_active_now(windows: &[TimeRange], days: &[Weekday], timezone: Option<&str>) -> bool {
+    is_active_at(windows, days, timezone, Utc::now())
+}
+
+/// The local weekday on which the run of `window` containing `at` began, if `at` is in it
+fn window_start_day(window: &TimeRange, timezone: Option<&str>, at: DateTime<Utc>) -> Option<Weekday> {
+    let timezone = window.timezone.as_deref().or(timezone);
+    let (Some(start), Some(end), Some(offset)) = (
+        parse_time(&window.start),
+        parse_time(&window.end),
+        utc_offset_at(timezone, at),
+    ) else {
+        warn!("Ignoring invalid time range {}-{} ({:?})", window.start, window.end, timezone);
+        return None;
+    };
+
+    let local = at.with_timezone(&offset);
+    let (time, day) = (local.time(), local.weekday());
+    if start <= end {
+        (time >= start && time < end).then_some(day)
+    } else if time >= start {
+        Some(day)
+    } else if time < end {
+        Some(day.pred())
+    } else {
+        None
+    }
+}
+
+fn parse_time(time: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(time.trim(), "%H:%M").ok()
+}
+
+/// Hours and days a filter or timer is limited to; see `is_active_at`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ActiveSchedule {
+    #[serde(default)]
+    pub windows: Vec<TimeRange>,
+    #[serde(default)]
+    pub days: Vec<Weekday>,
+    /// Zone for the days, and for windows without one of their own
+    #[serde(default)]
+    pub timezone: Option<String>,
+}
+
+impl ActiveSchedule {
+    /// Schedule from configured windows and day names, or `None` when neither restricts anything
+    pub fn new(windows: Vec<TimeRange>, days: &[String], timezone: Option<String>) -> Result<Option<Self>> {
+        let days = parse_days(days)?;
+        if windows.is_empty() && days.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(Self { windows, days, timezone }))
+    }
+
+    pub fn is_active(&self, at: DateTime<Utc>) -> bool {
+        is_active_at(&self.windows, &self.days, self.timezone.as_deref(), at)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn window(start: &str, end: &str, timezone: Option<&str>) -> TimeRange {
+        TimeRange {
+            start: start.to_string(),
+            end: end.to_string(),
+            timezone: timezone.map(String::from),
+        }
+    }
+
+    fn utc(month: u32, day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, month, day, hour, minute, 0).unwrap()
+    }
+
+    fn offset_hours(timezone: &str, at: DateTime<Utc>) -> f32 {
+        utc_offset_at(Some(timezone), at).unwrap().local_minus_utc() as f32 / 3600.0
+    }
+
+    const WEEKDAYS: [Weekday; 5] = [Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri];
+
+    #[test]
+    fn test_no_windows_or_days_is_always_active() {
+        assert!(is_active_at(&[], &[], None, utc(3, 14, 3, 0)));
+        assert!(is_active_at(&[], &[], Some("Nowhere/Special"), utc(3, 14, 3, 0)));
+    }
+
+    #[test]
+    fn test_days_only() {
+        // 2026-03-10 is a Tuesday, 03-14 and 03-15 the weekend after
+        assert!(is_active_at(&[], &WEEKDAYS, None, utc(3, 10, 12, 0)));
+        assert!(!is_active_at(&[], &WEEKDAYS, None, utc(3, 14, 12, 0)));
+        assert!(!is_active_at(&[], &WEEKDAYS, None, utc(3, 15, 23, 59)));
+
+        // Friday evening in Los Angeles is already Saturday in UTC
+        assert!(is_active_at(&[], &WEEKDAYS, Some("America/Los_Angeles"), utc(3, 14, 4, 0)));
+        // and Monday morning in Tokyo is still Sunday in UTC
+        assert!(is_active_at(&[], &WEEKDAYS, Some("Asia/Tokyo"), utc(3, 15, 23, 0)));
+        assert!(!is_active_at(&[], &WEEKDAYS, Some("Mars/Olympus"), utc(3, 10, 12, 0)));
+    }
+
+    #[test]
+    fn test_windows_and_wrap_around() {
+        let daytime = [window("09:00", "17:00", None)];
+        assert!(is_active_at(&daytime, &[], None, utc(3, 10, 9, 0)));
+        assert!(!is_active_at(&daytime, &[], None, utc(3, 10, 17, 0)));
+        assert!(!is_active_at(&daytime, &[], None, utc(3, 10, 8, 59)));
+
+        let overnight = [window("22:00", "02:00", None)];
+        assert!(is_active_at(&overnight, &[], None, utc(3, 10, 23, 30)));
+        assert!(is_active_at(&overnight, &[], None, utc(3, 11, 1, 59)));
+        assert!(!is_active_at(&overnight, &[], None, utc(3, 11, 2, 0)));
+        assert!(!is_active_at(&overnight, &[], None, utc(3, 11, 12, 0)));
+
+        // A wrapping window belongs to the day it started on
+        let fridays = [Weekday::Fri];
+        assert!(is_active_at(&overnight, &fridays, None, utc(3, 13, 23, 0)));
+        assert!(is_active_at(&overnight, &fridays, None, utc(3, 14, 1, 0)));
+        assert!(!is_active_at(&overnight, &fridays, None, utc(3, 13, 1, 0)));
+        assert!(!is_active_at(&overnight, &fridays, None, utc(3, 14, 23, 0)));
+
+        // Any window may match
+        let split = [window("06:00", "08:00", None), window("20:00", "21:00", None)];
+        assert!(is_active_at(&split, &[], None, utc(3, 10, 7, 0)));
+        assert!(is_active_at(&split, &[], None, utc(3, 10, 20, 30)));
+        assert!(!is_active_at(&split, &[], None, utc(3, 10, 12, 0)));
+
+        // A zero-length window never matches, invalid ones are ignored
+        assert!(!is_active_at(&[window("10:00", "10:00", None)], &[], None, utc(3, 10, 10, 0)));
+        assert!(!is_active_at(&[window("25:00", "07:00", None)], &[], None, utc(3, 10, 3, 0)));
+        let with_invalid = [window("nope", "07:00", None), window("00:00", "07:00", None)];
+        assert!(is_active_at(&with_invalid, &[], None, utc(3, 10, 3, 0)));
+    }
+
+    #[test]
+    fn test_window_timezones() {
+        // 01:00-07:00 in Los Angeles is 08:00-14:00 UTC in March
+        let pacific = [window("01:00", "07:00", Some("America/Los_Angeles"))];
+        assert!(is_active_at(&pacific, &[], None, utc(3, 10, 10, 0)));
+        assert!(!is_active_at(&pacific, &[], None, utc(3, 10, 3, 0)));
+        assert!(is_active_at(&[window("01:00", "07:00", Some("UTC-8"))], &[], None, utc(3, 10, 10, 0)));
+        assert!(is_active_at(&[window("15:00", "16:00", Some("+05:30"))], &[], None, utc(3, 10, 9, 45)));
+
+        // The schedule's timezone applies to windows without their own
+        let evening = [window("18:00", "23:00", None)];
+        assert!(is_active_at(&evening, &[], Some("EST"), utc(3, 10, 23, 30)));
+        assert!(!is_active_at(&evening, &[], None, utc(3, 10, 23, 30)));
+        assert!(!is_active_at(&[window("01:00", "07:00", Some("Mars/Olympus"))], &[], None, utc(3, 10, 3, 0)));
+    }
+
+    #[test]
+    fn test_daylight_saving_transitions() {
+        // 2026: US clocks go forward March 8 at 07:00 UTC and back November 1 at 06:00 UTC
+        assert_eq!(offset_hours("America/New_York", utc(3, 8, 6, 59)), -5.0);
+        assert_eq!(offset_hours("America/New_York", utc(3, 8, 7, 0)), -4.0);
+        assert_eq!(offset_hours("America/New_York", utc(11, 1, 5, 59)), -4.0);
+        assert_eq!(offset_hours("America/New_York", utc(11, 1, 6, 0)), -5.0);
+        assert_eq!(offset_hours("US/Pacific", utc(7, 1, 12, 0)), -7.0);
+        assert_eq!(offset_hours("America/Phoenix", utc(7, 1, 12, 0)), -7.0);
+        // Fixed-offset zones and explicit offsets ignore daylight saving
+        assert_eq!(offset_hours("EST", utc(7, 1, 12, 0)), -5.0);
+        assert_eq!(offset_hours("UTC-8", utc(7, 1, 12, 0)), -8.0);
+
+        // Europe switches at 01:00 UTC on the last Sundays of March and October
+        assert_eq!(offset_hours("Europe/London", utc(3, 29, 0, 59)), 0.0);
+        assert_eq!(offset_hours("europe/london", utc(3, 29, 1, 0)), 1.0);
+        assert_eq!(offset_hours("Europe/Berlin", utc(10, 25, 0, 59)), 2.0);
+        assert_eq!(offset_hours("Europe/Berlin", utc(10, 25, 1, 0)), 1.0);
+
+        // Southern hemisphere daylight time spans the new year
+        assert_eq!(offset_hours("Australia/Sydney", utc(1, 15, 0, 0)), 11.0);
+        assert_eq!(offset_hours("Australia/Sydney", utc(4, 4, 15, 59)), 11.0);
+        assert_eq!(offset_hours("Australia/Sydney", utc(4, 4, 16, 0)), 10.0);
+        assert_eq!(offset_hours("Australia/Sydney", utc(10, 3, 16, 0)), 11.0);
+        assert_eq!(offset_hours("Australia/Adelaide", utc(7, 1, 0, 0)), 9.5);
+        assert_eq!(offset_hours("Pacific/Auckland", utc(7, 1, 0, 0)), 12.0);
+        assert_eq!(offset_hours("Pacific/Auckland", utc(12, 1, 0, 0)), 13.0);
+    }
+
+    #[test]
+    fn test_windows_follow_wall_clock_across_daylight_saving() {
+        let office = [window("09:00", "17:00", Some("America/New_York"))];
+        // 13:00 UTC is 08:00 in winter and 09:00 in summer
+        assert!(!is_active_at(&office, &[], None, utc(1, 15, 13, 0)));
+        assert!(is_active_at(&office, &[], None, utc(1, 15, 14, 0)));
+        assert!(is_active_at(&office, &[], None, utc(7, 15, 13, 0)));
+        assert!(!is_active_at(&office, &[], None, utc(7, 15, 21, 0)));
+
+        // On the night clocks go forward, 02:00-03:00 local never happens
+        let skipped = [window("02:00", "03:00", Some("America/New_York"))];
+        assert!(!is_active_at(&skipped, &[], None, utc(3, 8, 7, 0)));
+        assert!(is_active_at(&skipped, &[], None, utc(3, 7, 7, 30)));
+
+        // When they go back, 01:00-02:00 local happens twice
+        let repeated = [window("01:00", "02:00", Some("America/New_York"))];
+        assert!(is_active_at(&repeated, &[], None, utc(11, 1, 5, 30)));
+        assert!(is_active_at(&repeated, &[], None, utc(11, 1, 6, 30)));
+        assert!(!is_active_at(&repeated, &[], None, utc(11, 1, 7, 0)));
+
+        // Days are counted in the same zone: Saturday 03:30 UTC is still Friday in New York
+        assert!(is_active_at(&[window("22:00", "23:59", None)], &[Weekday::Fri], Some("America/New_York"), utc(3, 14, 3, 30)));
+    }
+
+    #[test]
+    fn test_active_schedule_from_config() {
+        let days: Vec<String> = ["Mon", "tue", "Wednesday", "Thu", "FRI"].iter().map(|d| d.to_string()).collect();
+        let schedule = ActiveSchedule::new(Vec::new(), &days, None).unwrap().unwrap();
+        assert_eq!(schedule.days, WEEKDAYS);
+        assert!(schedule.is_active(utc(3, 10, 12, 0)));
+        assert!(!schedule.is_active(utc(3, 14, 12, 0)));
+
+        assert!(ActiveSchedule::new(Vec::new(), &[], Some("America/Los_Angeles".to_string())).unwrap().is_none());
+        assert!(ActiveSchedule::new(Vec::new(), &["Someday".to_string()], None).is_err());
+    }
+}
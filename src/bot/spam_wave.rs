@@ -0,0 +1,205 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+
+/// When repeated blocks by one filter count as a spam wave
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpamWaveConfig {
+    /// Blocks by the same filter within `window_seconds` that start a wave
+    pub threshold: usize,
+    pub window_seconds: u64,
+    /// A wave ends once its filter has been quiet this long
+    pub quiet_seconds: u64,
+}
+
+impl Default for SpamWaveConfig {
+    fn default() -> Self {
+        Self {
+            threshold: 10,
+            window_seconds: 30,
+            quiet_seconds: 60,
+        }
+    }
+}
+
+/// Aggregated alert for a finished spam wave
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpamWaveAlert {
+    pub filter_name: String,
+    pub blocked_count: usize,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+}
+
+impl SpamWaveAlert {
+    pub fn duration_seconds(&self) -> i64 {
+        (self.ended_at - self.started_at).num_seconds()
+    }
+}
+
+#[derive(Debug, Default)]
+struct FilterBlocks {
+    recent: VecDeque<DateTime<Utc>>,
+    wave: Option<ActiveWave>,
+}
+
+#[derive(Debug, Clone)]
+struct ActiveWave {
+    started_at: DateTime<Utc>,
+    last_block: DateTime<Utc>,
+    count: usize,
+}
+
+/// Folds bursts of blocks by the same filter into one alert per wave
+#[derive(Debug, Default)]
+pub struct SpamWaveDetector {
+    config: SpamWaveConfig,
+    filters: HashMap<String, FilterBlocks>,
+    finished: Vec<SpamWaveAlert>,
+}
+
+impl SpamWaveDetector {
+    pub fn new(config: SpamWaveConfig) -> Self {
+        Self {
+            config,
+            filters: HashMap::new(),
+            finished: Vec::new(),
+        }
+    }
+
+    pub fn set_config(&mut self, config: SpamWaveConfig) {
+        self.config = config;
+    }
+
+    /// Record a block; returns true when it belongs to a wave and shouldn't be logged on its own
+    pub fn record_block(&mut self, filter_name: &str, now: DateTime<Utc>) -> bool {
+        let quiet = Duration::seconds(self.config.quiet_seconds as i64);
+        let window = Duration::seconds(self.config.window_seconds as i64);
+        let blocks = self.filters.entry(filter_name.to_string()).or_default();
+
+        if let Some(wave) = &mut blocks.wave {
+            if now - wave.last_block <= quiet {
+                wave.count += 1;
+                wave.last_block = now;
+                return true;
+            }
+            let wave = blocks.wave.take().expect("wave checked above");
+            self.finished.push(Self::alert(filter_name, wave));
+        }
+
+        blocks.recent.push_back(now);
+        while blocks.recent.front().is_some_and(|t| now - *t > window) {
+            blocks.recent.pop_front();
+        }
+
+        if self.config.threshold > 0 && blocks.recent.len() >= self.config.threshold {
+            blocks.wave = Some(ActiveWave {
+                started_at: blocks.recent[0],
+                last_block: now,
+                count: blocks.recent.len(),
+            });
+            blocks.recent.clear();
+            return true;
+        }
+        false
+    }
+
+    /// Whether any filter is currently in a wave
+    pub fn is_active(&self) -> bool {
+        self.filters.values().any(|b| b.wave.is_some())
+    }
+
+    /// Take alerts for waves that have gone quiet
+    pub fn take_finished(&mut self, now: DateTime<Utc>) -> Vec<SpamWaveAlert> {
+        let quiet = Duration::seconds(self.config.quiet_seconds as i64);
+
+        for (filter_name, blocks) in self.filters.iter_mut() {
+            if blocks.wave.as_ref().is_some_and(|w| now - w.last_block > quiet) {
+                let wave = blocks.wave.take().expect("wave checked above");
+                self.finished.push(Self::alert(filter_name, wave));
+            }
+        }
+        self.filters.retain(|_, b| b.wave.is_some() || !b.recent.is_empty());
+
+        std::mem::take(&mut self.finished)
+    }
+
+    fn alert(filter_name: &str, wave: ActiveWave) -> SpamWaveAlert {
+        SpamWaveAlert {
+            filter_name: filter_name.to_string(),
+            blocked_count: wave.count,
+            started_at: wave.started_at,
+            ended_at: wave.last_block,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detector() -> SpamWaveDetector {
+        SpamWaveDetector::new(SpamWaveConfig { threshold: 5, window_seconds: 10, quiet_seconds: 30 })
+    }
+
+    #[test]
+    fn test_rapid_blocks_produce_one_alert() {
+        let mut waves = detector();
+        let start = Utc::now();
+
+        let suppressed = (0..50)
+            .filter(|i| waves.record_block("scam_links", start + Duration::seconds(i / 5)))
+            .count();
+        // Only the blocks before the threshold are logged individually
+        assert_eq!(suppressed, 46);
+        assert!(waves.is_active());
+
+        // Still going: nothing to report yet
+        assert!(waves.take_finished(start + Duration::seconds(20)).is_empty());
+
+        let alerts = waves.take_finished(start + Duration::seconds(60));
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].filter_name, "scam_links");
+        assert_eq!(alerts[0].blocked_count, 50);
+        assert_eq!(alerts[0].duration_seconds(), 9);
+        assert!(!waves.is_active());
+        assert!(waves.take_finished(start + Duration::seconds(120)).is_empty());
+    }
+
+    #[test]
+    fn test_slow_blocks_are_not_a_wave() {
+        let mut waves = detector();
+        let start = Utc::now();
+
+        for i in 0..20 {
+            assert!(!waves.record_block("caps", start + Duration::seconds(i * 5)));
+        }
+        assert!(waves.take_finished(start + Duration::seconds(500)).is_empty());
+    }
+
+    #[test]
+    fn test_wave_resets_after_quiet_period() {
+        let mut waves = detector();
+        let start = Utc::now();
+
+        for i in 0..6 {
+            waves.record_block("caps", start + Duration::seconds(i));
+        }
+        // Other filters are tracked separately
+        assert!(!waves.record_block("links", start));
+
+        // After the quiet period a new block is logged normally again
+        let later = start + Duration::seconds(100);
+        assert!(!waves.record_block("caps", later));
+        let alerts = waves.take_finished(later);
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].blocked_count, 6);
+
+        for i in 1..5 {
+            waves.record_block("caps", later + Duration::seconds(i));
+        }
+        let alerts = waves.take_finished(later + Duration::seconds(100));
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].blocked_count, 5);
+    }
+}
@@ -0,0 +1,209 @@
+use anyhow::Result;
+use log::warn;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use crate::adaptive::AdaptivePerformanceSystem;
+use crate::bot::connection_pool::ConnectionPool;
+use crate::config::ConfigurationManager;
+use crate::types::ChatMessage;
+
+/// Moderator commands that report on the bot's supporting systems
+pub struct SystemCommands {
+    /// Adaptive tuning the bot runs, for `!adaptivetrend`
+    adaptive_system: RwLock<Option<Arc<AdaptivePerformanceSystem>>>,
+    /// Connection pool the bot sends through, for `!poolstats`
+    connection_pool: RwLock<Option<Arc<ConnectionPool>>>,
+    /// Configuration the bot was loaded from, for `!configstatus`
+    config_manager: RwLock<Option<Arc<ConfigurationManager>>>,
+}
+
+impl SystemCommands {
+    pub fn new() -> Self {
+        Self {
+            adaptive_system: RwLock::new(None),
+            connection_pool: RwLock::new(None),
+            config_manager: RwLock::new(None),
+        }
+    }
+
+    pub async fn set_adaptive_system(&self, adaptive_system: Arc<AdaptivePerformanceSystem>) {
+        *self.adaptive_system.write().await = Some(adaptive_system);
+    }
+
+    pub async fn set_connection_pool(&self, connection_pool: Arc<ConnectionPool>) {
+        *self.connection_pool.write().await = Some(connection_pool);
+    }
+
+    pub async fn set_config_manager(&self, config_manager: Arc<ConfigurationManager>) {
+        *self.config_manager.write().await = Some(config_manager);
+    }
+
+    /// Process system status commands (!adaptivetrend, !poolstats, !configstatus)
+    pub async fn process_command(
+        &self,
+        command: &str,
+        args: &[&str],
+        message: &ChatMessage,
+        response_sender: &tokio::sync::mpsc::Sender<(String, String, String)>,
+    ) -> Result<bool> {
+        // System status is for moderators only
+        if !message.is_mod {
+            return Ok(false);
+        }
+
+        match command {
+            "adaptivetrend" => {
+                self.handle_adaptive_trend_command(args, message, response_sender).await?;
+                Ok(true)
+            }
+            "poolstats" => {
+                self.handle_pool_stats_command(message, response_sender).await?;
+                Ok(true)
+            }
+            "configstatus" => {
+                self.handle_config_status_command(message, response_sender).await?;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn handle_adaptive_trend_command(
+        &self,
+        args: &[&str],
+        message: &ChatMessage,
+        response_sender: &tokio::sync::mpsc::Sender<(String, String, String)>,
+    ) -> Result<()> {
+        let response = match self.adaptive_system.read().await.as_ref() {
+            None => "📈 Metric trends need adaptive tuning, which isn't running".to_string(),
+            Some(adaptive_system) => match (args.first(), args.get(1)) {
+                (Some(metric), Some(minutes)) => match minutes.parse::<u64>() {
+                    Ok(minutes) => match adaptive_system.get_metric_trend(metric, minutes).await {
+                        Ok(trend) => trend.summary(),
+                        Err(e) => format!("❌ Trend error: {}", e),
+                    },
+                    Err(_) => format!("❌ '{}' is not a number of minutes", minutes),
+                },
+                _ => "Usage: !adaptivetrend <metric> <minutes>".to_string(),
+            },
+        };
+
+        self.send_response(response, message, response_sender).await
+    }
+
+    /// Handle !poolstats - connections per platform, one line for chat
+    async fn handle_pool_stats_command(
+        &self,
+        message: &ChatMessage,
+        response_sender: &tokio::sync::mpsc::Sender<(String, String, String)>,
+    ) -> Result<()> {
+        let response = match self.connection_pool.read().await.as_ref() {
+            None => "🔌 Pool stats need the connection pool, which isn't running".to_string(),
+            Some(connection_pool) => {
+                let summary = connection_pool.get_stats_summary().await;
+                if summary.platforms.is_empty() {
+                    "🔌 Connection Pool Stats: no platforms pooled yet".to_string()
+                } else {
+                    let summary = summary.to_string();
+                    let lines: Vec<&str> = summary.lines().map(str::trim).collect();
+                    format!("🔌 {} {}", lines[0], lines[1..].join(" | "))
+                }
+            }
+        };
+
+        self.send_response(response, message, response_sender).await
+    }
+
+    /// Handle !configstatus - configured filters, patterns and timers, and how reloads went
+    async fn handle_config_status_command(
+        &self,
+        message: &ChatMessage,
+        response_sender: &tokio::sync::mpsc::Sender<(String, String, String)>,
+    ) -> Result<()> {
+        let response = match self.config_manager.read().await.as_ref() {
+            None => "📊 Config status needs the configuration manager, which isn't running".to_string(),
+            Some(config_manager) => config_manager.get_config_status().await.to_string(),
+        };
+
+        self.send_response(response, message, response_sender).await
+    }
+
+    async fn send_response(
+        &self,
+        response: String,
+        message: &ChatMessage,
+        response_sender: &tokio::sync::mpsc::Sender<(String, String, String)>,
+    ) -> Result<()> {
+        if let Err(e) = response_sender.send((
+            message.platform.clone(),
+            message.channel.clone(),
+            response
+        )).await {
+            warn!("Failed to send system command response: {}", e);
+        }
+        Ok(())
+    }
+}
+
+impl Default for SystemCommands {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adaptive::AdaptiveConfig;
+
+    async fn run(commands: &SystemCommands, command: &str, args: &[&str]) -> String {
+        let (tx, mut rx) = tokio::sync::mpsc::channel(10);
+        let moderator = ChatMessage::new("twitch", "testchannel", "moduser", &format!("!{}", command)).with_mod(true);
+        assert!(commands.process_command(command, args, &moderator, &tx).await.unwrap());
+        rx.recv().await.unwrap().2
+    }
+
+    #[tokio::test]
+    async fn test_adaptive_trend_reports_recorded_metric() {
+        let commands = SystemCommands::new();
+        assert!(run(&commands, "adaptivetrend", &["queue_depth", "15"]).await.contains("isn't running"));
+
+        let adaptive = Arc::new(AdaptivePerformanceSystem::new(AdaptiveConfig::default()).unwrap());
+        adaptive.record_metric("queue_depth", 4.0).await.unwrap();
+        adaptive.record_metric("queue_depth", 6.0).await.unwrap();
+        commands.set_adaptive_system(Arc::clone(&adaptive)).await;
+
+        let response = run(&commands, "adaptivetrend", &["queue_depth", "15"]).await;
+        assert!(response.contains("queue_depth over 15m"), "{}", response);
+        assert!(response.contains("(2 samples)"), "{}", response);
+        assert_eq!(run(&commands, "adaptivetrend", &["queue_depth"]).await, "Usage: !adaptivetrend <metric> <minutes>");
+        assert!(run(&commands, "adaptivetrend", &["queue_depth", "soon"]).await.contains("not a number"));
+        assert!(run(&commands, "adaptivetrend", &["nope", "15"]).await.starts_with("❌ Trend error"));
+    }
+
+    #[tokio::test]
+    async fn test_pool_and_config_status_come_from_the_attached_systems() {
+        let commands = SystemCommands::new();
+        assert!(run(&commands, "poolstats", &[]).await.contains("isn't running"));
+        assert!(run(&commands, "configstatus", &[]).await.contains("isn't running"));
+
+        let pool = Arc::new(ConnectionPool::with_default_config());
+        commands.set_connection_pool(pool).await;
+        assert_eq!(run(&commands, "poolstats", &[]).await, "🔌 Connection Pool Stats: no platforms pooled yet");
+
+        let dir = tempfile::tempdir().unwrap();
+        let config_manager = Arc::new(ConfigurationManager::new(dir.path()));
+        config_manager.initialize().await.unwrap();
+        commands.set_config_manager(Arc::clone(&config_manager)).await;
+        assert_eq!(run(&commands, "configstatus", &[]).await, config_manager.get_config_status().await.to_string());
+    }
+
+    #[tokio::test]
+    async fn test_system_commands_are_mod_only() {
+        let commands = SystemCommands::new();
+        let (tx, _rx) = tokio::sync::mpsc::channel(10);
+        let viewer = ChatMessage::new("twitch", "testchannel", "viewer", "!adaptivetrend x 5");
+        assert!(!commands.process_command("adaptivetrend", &["x", "5"], &viewer, &tx).await.unwrap());
+    }
+}
@@ -0,0 +1,227 @@
+use log::{error, info};
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tokio::sync::{mpsc, Semaphore};
+use tokio::task::JoinHandle;
+
+/// Default cap on moderation actions and responses being sent at once
+pub const DEFAULT_MAX_CONCURRENT_TASKS: usize = 32;
+
+/// Tasks waiting in one key's queue before `spawn_ordered` callers wait too
+const ORDERED_QUEUE_CAPACITY: usize = 64;
+
+type OrderedTask = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// Bounded executor for outbound work (moderation actions, responses,
+/// announcements): at most `max_concurrent` tasks run at once and the rest
+/// wait for a slot, so a raid can't flood the runtime with sends
+#[derive(Debug)]
+pub struct TaskLimiter {
+    semaphore: Arc<Semaphore>,
+    max_concurrent: AtomicUsize,
+    in_flight: Arc<AtomicUsize>,
+    /// One queue per key for `spawn_ordered`, each drained by its own worker
+    ordered: Mutex<HashMap<String, mpsc::Sender<OrderedTask>>>,
+}
+
+impl Default for TaskLimiter {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_CONCURRENT_TASKS)
+    }
+}
+
+impl TaskLimiter {
+    pub fn new(max_concurrent: usize) -> Self {
+        let max_concurrent = max_concurrent.max(1);
+        Self {
+            semaphore: Arc::new(Semaphore::new(max_concurrent)),
+            max_concurrent: AtomicUsize::new(max_concurrent),
+            in_flight: Arc::new(AtomicUsize::new(0)),
+            ordered: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn max_concurrent(&self) -> usize {
+        self.max_concurrent.load(Ordering::Relaxed)
+    }
+
+    /// Tasks currently running
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::Relaxed)
+    }
+
+    /// Change the bound; shrinking waits for enough running tasks to finish
+    pub async fn set_max_concurrent(&self, max_concurrent: usize) {
+        let max_concurrent = max_concurrent.max(1);
+        let previous = self.max_concurrent.swap(max_concurrent, Ordering::Relaxed);
+        if max_concurrent > previous {
+            self.semaphore.add_permits(max_concurrent - previous);
+        } else if max_concurrent < previous {
+            if let Ok(permits) = Arc::clone(&self.semaphore).acquire_many_owned((previous - max_concurrent) as u32).await {
+                permits.forget();
+            }
+        }
+        if max_concurrent != previous {
+            info!("Outbound task limit changed from {} to {}", previous, max_concurrent);
+        }
+    }
+
+    /// Wait for a free slot, then run `task` in the background. Callers reading
+    /// from a channel stop reading while every slot is busy, so excess work
+    /// queues up in the channel instead of as spawned tasks.
+    pub async fn spawn<F>(&self, task: F) -> JoinHandle<F::Output>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        let permit = Arc::clone(&self.semaphore).acquire_owned().await
+            .expect("task limiter semaphore is never closed");
+        self.in_flight.fetch_add(1, Ordering::Relaxed);
+        let in_flight = Arc::clone(&self.in_flight);
+        tokio::spawn(async move {
+            let _permit = permit;
+            let _guard = InFlightGuard(in_flight);
+            task.await
+        })
+    }
+
+    /// Queue `task` behind the others spawned with the same `key`: tasks sharing
+    /// a key run one at a time in the order they were queued, while different
+    /// keys run side by side within the limit. Waits while the key's queue is full.
+    pub async fn spawn_ordered<F>(&self, key: &str, task: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let queue = {
+            let mut ordered = self.ordered.lock().unwrap_or_else(|e| e.into_inner());
+            ordered.entry(key.to_string())
+                .or_insert_with(|| self.start_ordered_queue(key))
+                .clone()
+        };
+        if queue.send(Box::pin(task)).await.is_err() {
+            error!("Ordered task queue for {} has stopped", key);
+        }
+    }
+
+    /// Worker that runs one key's tasks in turn, each in a slot of the limit
+    fn start_ordered_queue(&self, key: &str) -> mpsc::Sender<OrderedTask> {
+        let (sender, mut receiver) = mpsc::channel::<OrderedTask>(ORDERED_QUEUE_CAPACITY);
+        let semaphore = Arc::clone(&self.semaphore);
+        let in_flight = Arc::clone(&self.in_flight);
+        let key = key.to_string();
+        tokio::spawn(async move {
+            while let Some(task) = receiver.recv().await {
+                let Ok(_permit) = semaphore.acquire().await else { break };
+                in_flight.fetch_add(1, Ordering::Relaxed);
+                let _guard = InFlightGuard(Arc::clone(&in_flight));
+                // A panicking task must not take the rest of the queue down with it
+                if tokio::spawn(task).await.is_err() {
+                    error!("Ordered task for {} panicked", key);
+                }
+            }
+        });
+        sender
+    }
+}
+
+/// Decrements the in-flight count when a task finishes, even by panicking
+struct InFlightGuard(Arc<AtomicUsize>);
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    async fn run_tasks(limiter: &TaskLimiter, count: usize) -> usize {
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let mut handles = Vec::new();
+        for _ in 0..count {
+            let running = Arc::clone(&running);
+            let peak = Arc::clone(&peak);
+            handles.push(limiter.spawn(async move {
+                let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(5)).await;
+                running.fetch_sub(1, Ordering::SeqCst);
+            }).await);
+            assert!(limiter.in_flight() <= limiter.max_concurrent());
+        }
+        for handle in handles {
+            handle.await.unwrap();
+        }
+        peak.load(Ordering::SeqCst)
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrency_never_exceeds_the_bound() {
+        let limiter = TaskLimiter::new(3);
+        let peak = run_tasks(&limiter, 30).await;
+        assert!(peak <= 3, "peak was {}", peak);
+        assert!(peak >= 2, "tasks should overlap, peak was {}", peak);
+        assert_eq!(limiter.in_flight(), 0);
+
+        limiter.set_max_concurrent(1).await;
+        assert_eq!(run_tasks(&limiter, 10).await, 1);
+
+        limiter.set_max_concurrent(5).await;
+        let peak = run_tasks(&limiter, 30).await;
+        assert!(peak <= 5, "peak was {}", peak);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_ordered_tasks_run_in_turn_per_key() {
+        let limiter = TaskLimiter::new(4);
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let (done_tx, mut done_rx) = mpsc::channel(20);
+
+        for i in 0..10 {
+            for key in ["twitch:a", "twitch:b"] {
+                let (log, running, peak, done_tx) = (Arc::clone(&log), Arc::clone(&running), Arc::clone(&peak), done_tx.clone());
+                limiter.spawn_ordered(key, async move {
+                    let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    // Later tasks are quicker, so they'd overtake if run concurrently
+                    tokio::time::sleep(Duration::from_millis(10 - i)).await;
+                    log.lock().unwrap().push((key, i));
+                    running.fetch_sub(1, Ordering::SeqCst);
+                    done_tx.send(()).await.unwrap();
+                    if i == 3 {
+                        panic!("send failed");
+                    }
+                }).await;
+            }
+        }
+        for _ in 0..20 {
+            done_rx.recv().await.unwrap();
+        }
+
+        let log = log.lock().unwrap();
+        for key in ["twitch:a", "twitch:b"] {
+            let order: Vec<u64> = log.iter().filter(|(k, _)| *k == key).map(|(_, i)| *i).collect();
+            assert_eq!(order, (0..10).collect::<Vec<_>>());
+        }
+        // One task per key at a time, but the two keys overlap
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_panicking_task_releases_its_slot() {
+        let limiter = TaskLimiter::new(1);
+        let handle = limiter.spawn(async { panic!("send failed") }).await;
+        assert!(handle.await.is_err());
+        assert_eq!(limiter.in_flight(), 0);
+        assert_eq!(limiter.spawn(async { 7 }).await.await.unwrap(), 7);
+    }
+}
@@ -28,7 +28,7 @@ impl TimerCommands {
         }
 
         match command {
-            "timers" => {
+            "timers" | "timer" => {
                 self.handle_timers_command(args, message, response_sender).await?;
                 Ok(true)
             }
@@ -36,6 +36,10 @@ impl TimerCommands {
                 self.handle_reload_timers_command(message, response_sender).await?;
                 Ok(true)
             }
+            "reloadtimer" => {
+                self.handle_reload_timer_command(args, message, response_sender).await?;
+                Ok(true)
+            }
             "timerstats" => {
                 self.handle_timer_stats_command(message, response_sender).await?;
                 Ok(true)
@@ -52,7 +56,7 @@ impl TimerCommands {
         response_sender: &tokio::sync::mpsc::Sender<(String, String, String)>,
     ) -> Result<()> {
         if args.is_empty() {
-            let response = "Timer Commands: !timers <list|enable|disable|reload|categories> [name/category] | !timerstats | !reloadtimers".to_string();
+            let response = "Timer Commands: !timers <list|enable|disable|fire|pause|resume|reload|categories> [name/category/minutes] | !timerstats | !reloadtimers | !reloadtimer <name>".to_string();
             self.send_response(response, message, response_sender).await?;
             return Ok(());
         }
@@ -99,6 +103,50 @@ impl TimerCommands {
                     }
                 }
             }
+            "fire" => {
+                if args.len() < 2 {
+                    let response = "Usage: !timer fire <timer_name> [force]".to_string();
+                    self.send_response(response, message, response_sender).await?;
+                    return Ok(());
+                }
+
+                let timer_name = args[1];
+                let force = args.get(2).is_some_and(|a| a.eq_ignore_ascii_case("force"));
+                info!("Manual fire of timer '{}' requested by {}", timer_name, message.username);
+                let response = match self.timer_system.trigger_now(timer_name, force).await {
+                    Ok(0) => format!("Timer '{}' fired but had nowhere to post (add 'force' to ignore disabled platforms)", timer_name),
+                    Ok(posted) => format!("Timer '{}' fired to {} channel(s); next run in its full interval", timer_name, posted),
+                    Err(e) => format!("Failed to fire timer '{}': {}", timer_name, e),
+                };
+                self.send_response(response, message, response_sender).await?;
+            }
+            "pause" => {
+                let minutes = match args.get(1).map(|m| m.parse::<u32>()) {
+                    None => None,
+                    Some(Ok(minutes)) if minutes > 0 => Some(minutes),
+                    Some(_) => {
+                        let response = "Usage: !timers pause [minutes]".to_string();
+                        self.send_response(response, message, response_sender).await?;
+                        return Ok(());
+                    }
+                };
+
+                info!("Timers paused by {}", message.username);
+                self.timer_system.pause_all(minutes.map(|m| chrono::Duration::minutes(m as i64))).await;
+                let response = match minutes {
+                    Some(minutes) => format!("Timers paused for {} minute(s); they resume automatically", minutes),
+                    None => "Timers paused until !timers resume".to_string(),
+                };
+                self.send_response(response, message, response_sender).await?;
+            }
+            "resume" => {
+                let response = if self.timer_system.resume_all().await {
+                    "Timers resumed".to_string()
+                } else {
+                    "Timers aren't paused".to_string()
+                };
+                self.send_response(response, message, response_sender).await?;
+            }
             "reload" => {
                 match self.timer_system.reload_config().await {
                     Ok(_) => {
@@ -115,7 +163,7 @@ impl TimerCommands {
                 self.handle_categories_command(args, message, response_sender).await?;
             }
             _ => {
-                let response = "Unknown subcommand. Use: list, enable, disable, reload, categories".to_string();
+                let response = "Unknown subcommand. Use: list, enable, disable, fire, pause, resume, reload, categories".to_string();
                 self.send_response(response, message, response_sender).await?;
             }
         }
@@ -241,6 +289,29 @@ impl TimerCommands {
         Ok(())
     }
 
+    /// Handle !reloadtimer command, reloading one timer without resetting the others
+    async fn handle_reload_timer_command(
+        &self,
+        args: &[&str],
+        message: &ChatMessage,
+        response_sender: &tokio::sync::mpsc::Sender<(String, String, String)>,
+    ) -> Result<()> {
+        let Some(timer_name) = args.first() else {
+            let response = "Usage: !reloadtimer <timer_name>".to_string();
+            self.send_response(response, message, response_sender).await?;
+            return Ok(());
+        };
+
+        info!("Reload of timer '{}' requested by {}", timer_name, message.username);
+        let response = match self.timer_system.reload_timer(timer_name).await {
+            Ok(_) => format!("Timer '{}' reloaded from timers.yaml", timer_name),
+            Err(e) => format!("Failed to reload timer '{}': {}", timer_name, e),
+        };
+        self.send_response(response, message, response_sender).await?;
+
+        Ok(())
+    }
+
     /// Handle !timerstats command
     async fn handle_timer_stats_command(
         &self,
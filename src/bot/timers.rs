@@ -1,13 +1,15 @@
 use anyhow::{Result, Context};
 use log::{debug, error, info, warn};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use tokio::fs;
 use tokio::sync::RwLock;
 use tokio::time::Duration;
+use rand::Rng;
 
+use crate::bot::schedule::ActiveSchedule;
 use crate::platforms::PlatformConnection;
 use crate::types::BotTimer;
 
@@ -17,12 +19,27 @@ use crate::types::{
     VariableDefinition, TimerAnalytics, TimerRules
 };
 
+type PlatformConnections = Arc<RwLock<HashMap<String, Box<dyn PlatformConnection>>>>;
+
 pub struct TimerSystem {
     pub timers: Arc<RwLock<HashMap<String, BotTimer>>>,
     config_path: PathBuf,
     timer_config: Arc<RwLock<TimerConfig>>,
     custom_variables: Arc<RwLock<HashMap<String, String>>>,
     shutdown_signal: Arc<AtomicBool>,
+    dry_run: Arc<AtomicBool>,
+    /// Platforms where timers are turned off in the platform's feature settings
+    disabled_platforms: Arc<RwLock<HashSet<String>>>,
+    /// Platform connections, set once the timer system is started
+    connections: Arc<RwLock<Option<PlatformConnections>>>,
+    /// Scheduled fires are skipped until this time
+    paused_until: Arc<RwLock<Option<chrono::DateTime<chrono::Utc>>>>,
+    /// Hours and days timers are limited to, by timer name; set from the timer
+    /// configuration and kept across reloads of the timers file
+    schedules: Arc<RwLock<HashMap<String, ActiveSchedule>>>,
+    /// Random offsets from the timer configuration, by timer name; used by timers
+    /// whose own definition doesn't set `random_offset_max`
+    random_offsets: Arc<RwLock<HashMap<String, u64>>>,
 }
 
 impl TimerSystem {
@@ -33,6 +50,12 @@ impl TimerSystem {
             timer_config: Arc::new(RwLock::new(TimerConfig::default())),
             custom_variables: Arc::new(RwLock::new(HashMap::new())),
             shutdown_signal: Arc::new(AtomicBool::new(false)),
+            dry_run: Arc::new(AtomicBool::new(false)),
+            disabled_platforms: Arc::new(RwLock::new(HashSet::new())),
+            connections: Arc::new(RwLock::new(None)),
+            paused_until: Arc::new(RwLock::new(None)),
+            schedules: Arc::new(RwLock::new(HashMap::new())),
+            random_offsets: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
@@ -44,6 +67,12 @@ impl TimerSystem {
             timer_config: Arc::new(RwLock::new(TimerConfig::default())),
             custom_variables: Arc::new(RwLock::new(HashMap::new())),
             shutdown_signal: Arc::new(AtomicBool::new(false)),
+            dry_run: Arc::new(AtomicBool::new(false)),
+            disabled_platforms: Arc::new(RwLock::new(HashSet::new())),
+            connections: Arc::new(RwLock::new(None)),
+            paused_until: Arc::new(RwLock::new(None)),
+            schedules: Arc::new(RwLock::new(HashMap::new())),
+            random_offsets: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
@@ -54,22 +83,77 @@ impl TimerSystem {
             self.create_default_config().await?;
         }
 
+        let config = self.read_config_file().await?;
+
+        // Update configuration
+        *self.timer_config.write().await = config.clone();
+
+        // Load timers from configuration
+        self.load_timers_from_config(config).await?;
+
+        info!("Loaded {} timers from configuration", self.timers.read().await.len());
+        Ok(())
+    }
+
+    /// Read and validate the timer configuration file
+    async fn read_config_file(&self) -> Result<TimerConfig> {
         let content = fs::read_to_string(&self.config_path).await
             .with_context(|| format!("Failed to read timer config: {}", self.config_path.display()))?;
 
         let config: TimerConfig = serde_yaml::from_str(&content)
             .with_context(|| format!("Failed to parse timer config: {}", self.config_path.display()))?;
 
-        // Validate configuration
         self.validate_config(&config)?;
+        Ok(config)
+    }
 
-        // Update configuration
-        *self.timer_config.write().await = config.clone();
+    /// Reload one timer's definition from file, keeping its schedule (last trigger
+    /// and trigger count) and leaving every other timer untouched
+    pub async fn reload_timer(&self, name: &str) -> Result<()> {
+        let config = self.read_config_file().await?;
+        let minimum_interval = config.global_settings.minimum_interval_seconds;
+        let timer_def = config.timers.into_iter()
+            .find(|t| t.name == name)
+            .ok_or_else(|| anyhow::anyhow!("Timer '{}' not found in {}", name, self.config_path.display()))?;
 
-        // Load timers from configuration
-        self.load_timers_from_config(config).await?;
+        {
+            let mut timer_config = self.timer_config.write().await;
+            match timer_config.timers.iter_mut().find(|t| t.name == name) {
+                Some(existing) => *existing = timer_def.clone(),
+                None => timer_config.timers.push(timer_def.clone()),
+            }
+        }
 
-        info!("Loaded {} timers from configuration", self.timers.read().await.len());
+        let random_offset_max = match timer_def.random_offset_max {
+            Some(offset) => Some(offset),
+            None => self.random_offsets.read().await.get(name).copied(),
+        };
+        let mut timers = self.timers.write().await;
+        if !timer_def.enabled {
+            timers.remove(name);
+            info!("Reloaded timer '{}' (disabled in configuration)", name);
+            return Ok(());
+        }
+
+        let (last_triggered, trigger_count) = timers.get(name)
+            .map(|t| (t.last_triggered, t.trigger_count))
+            .unwrap_or((None, 0));
+        let schedule = self.schedules.read().await.get(name).cloned();
+        timers.insert(name.to_string(), BotTimer {
+            name: timer_def.name,
+            message: timer_def.message,
+            interval_seconds: timer_def.interval_seconds,
+            channels: timer_def.channels,
+            platforms: timer_def.platforms,
+            enabled: timer_def.enabled,
+            last_triggered,
+            trigger_count,
+            random_offset_max,
+            next_interval_seconds: randomized_interval(timer_def.interval_seconds, random_offset_max, minimum_interval),
+            schedule,
+        });
+
+        info!("Reloaded timer '{}' ({}s interval)", name, timer_def.interval_seconds);
         Ok(())
     }
 
@@ -108,6 +192,7 @@ impl TimerSystem {
                     description: Some("Showcase AI moderation features".to_string()),
                     tags: Some(vec!["ai".to_string(), "features".to_string(), "promotion".to_string()]),
                     variables: None,
+                    random_offset_max: None,
                 },
                 TimerDefinition {
                     name: "community_ai".to_string(),
@@ -119,6 +204,7 @@ impl TimerSystem {
                     description: Some("Explain AI learning capabilities".to_string()),
                     tags: Some(vec!["ai".to_string(), "community".to_string(), "education".to_string()]),
                     variables: None,
+                    random_offset_max: None,
                 },
                 TimerDefinition {
                     name: "ai_vs_nightbot".to_string(),
@@ -130,6 +216,7 @@ impl TimerSystem {
                     description: Some("Compare NotaBot advantages".to_string()),
                     tags: Some(vec!["comparison".to_string(), "nightbot".to_string(), "superiority".to_string()]),
                     variables: None,
+                    random_offset_max: None,
                 },
                 TimerDefinition {
                     name: "twitch_ai_exclusive".to_string(),
@@ -141,6 +228,7 @@ impl TimerSystem {
                     description: Some("Twitch-specific AI features".to_string()),
                     tags: Some(vec!["twitch".to_string(), "ai".to_string(), "exclusive".to_string()]),
                     variables: None,
+                    random_offset_max: None,
                 },
                 TimerDefinition {
                     name: "youtube_ai_exclusive".to_string(),
@@ -152,6 +240,7 @@ impl TimerSystem {
                     description: Some("YouTube-specific AI features".to_string()),
                     tags: Some(vec!["youtube".to_string(), "ai".to_string(), "cross-platform".to_string()]),
                     variables: None,
+                    random_offset_max: None,
                 },
                 TimerDefinition {
                     name: "points_economy".to_string(),
@@ -163,6 +252,7 @@ impl TimerSystem {
                     description: Some("Explain points system".to_string()),
                     tags: Some(vec!["points".to_string(), "economy".to_string(), "engagement".to_string()]),
                     variables: None,
+                    random_offset_max: None,
                 },
             ],
             categories: {
@@ -274,8 +364,11 @@ impl TimerSystem {
 
     /// Load timers from configuration into runtime timers
     async fn load_timers_from_config(&self, config: TimerConfig) -> Result<()> {
+        let schedules = self.schedules.read().await;
+        let random_offsets = self.random_offsets.read().await;
         let mut timers = self.timers.write().await;
         timers.clear();
+        let minimum_interval = config.global_settings.minimum_interval_seconds;
 
         for timer_def in config.timers {
             if !timer_def.enabled {
@@ -283,6 +376,8 @@ impl TimerSystem {
                 continue;
             }
 
+            let schedule = schedules.get(&timer_def.name).cloned();
+            let random_offset_max = timer_def.random_offset_max.or_else(|| random_offsets.get(&timer_def.name).copied());
             let bot_timer = BotTimer {
                 name: timer_def.name.clone(),
                 message: timer_def.message,
@@ -292,6 +387,9 @@ impl TimerSystem {
                 enabled: timer_def.enabled,
                 last_triggered: None,
                 trigger_count: 0,
+                random_offset_max,
+                next_interval_seconds: randomized_interval(timer_def.interval_seconds, random_offset_max, minimum_interval),
+                schedule,
             };
 
             timers.insert(timer_def.name.clone(), bot_timer);
@@ -314,6 +412,12 @@ impl TimerSystem {
         let timer_config_handle = self.timer_config.clone();
         let custom_variables_handle = self.custom_variables.clone();
         let shutdown_signal = Arc::clone(&self.shutdown_signal);
+        let dry_run = Arc::clone(&self.dry_run);
+        let disabled_platforms = Arc::clone(&self.disabled_platforms);
+        let connections = Arc::clone(&self.connections);
+        let paused_until = Arc::clone(&self.paused_until);
+        let schedules = Arc::clone(&self.schedules);
+        let random_offsets = Arc::clone(&self.random_offsets);
 
         tokio::spawn(async move {
             let mut last_modified = std::fs::metadata(&config_path)
@@ -343,6 +447,12 @@ impl TimerSystem {
                                 timer_config: timer_config_handle.clone(),
                                 custom_variables: custom_variables_handle.clone(),
                                 shutdown_signal: shutdown_signal.clone(),
+                                dry_run: dry_run.clone(),
+                                disabled_platforms: disabled_platforms.clone(),
+                                connections: connections.clone(),
+                                paused_until: paused_until.clone(),
+                                schedules: schedules.clone(),
+                                random_offsets: random_offsets.clone(),
                             };
 
                             match temp_system.load_config().await {
@@ -390,6 +500,9 @@ impl TimerSystem {
             enabled: true,
             last_triggered: None,
             trigger_count: 0,
+            random_offset_max: None,
+            next_interval_seconds: interval_seconds,
+            schedule: None,
         };
 
         self.timers.write().await.insert(name.clone(), timer);
@@ -397,6 +510,30 @@ impl TimerSystem {
         Ok(())
     }
 
+    /// Limit timers to the hours and days in `schedules` (by timer name); timers
+    /// without an entry post at any time
+    pub async fn set_schedules(&self, schedules: HashMap<String, ActiveSchedule>) {
+        let mut timers = self.timers.write().await;
+        for (name, timer) in timers.iter_mut() {
+            timer.schedule = schedules.get(name).cloned();
+        }
+        *self.schedules.write().await = schedules;
+    }
+
+    /// Random offsets (± seconds, by timer name) for timers whose definition
+    /// doesn't set its own `random_offset_max`
+    pub async fn set_random_offsets(&self, random_offsets: HashMap<String, u64>) {
+        *self.random_offsets.write().await = random_offsets.clone();
+        let timer_config = self.timer_config.read().await;
+        let minimum_interval = timer_config.global_settings.minimum_interval_seconds;
+        let mut timers = self.timers.write().await;
+        for (name, timer) in timers.iter_mut() {
+            let own_offset = timer_config.timers.iter().find(|t| &t.name == name).and_then(|t| t.random_offset_max);
+            timer.random_offset_max = own_offset.or_else(|| random_offsets.get(name).copied());
+            timer.next_interval_seconds = randomized_interval(timer.interval_seconds, timer.random_offset_max, minimum_interval);
+        }
+    }
+
     /// Enable or disable a specific timer
     pub async fn set_timer_enabled(&self, name: &str, enabled: bool) -> Result<()> {
         let mut timers_guard = self.timers.write().await;
@@ -466,6 +603,7 @@ impl TimerSystem {
     ) -> Result<()> {
         // Load configuration first
         self.load_config().await?;
+        *self.connections.write().await = Some(Arc::clone(&connections));
 
         // Start config file watcher
         self.start_config_watcher().await;
@@ -474,6 +612,9 @@ impl TimerSystem {
         let timer_config = Arc::clone(&self.timer_config);
         let custom_variables = Arc::clone(&self.custom_variables);
         let shutdown_signal = Arc::clone(&self.shutdown_signal);
+        let dry_run = Arc::clone(&self.dry_run);
+        let disabled_platforms = Arc::clone(&self.disabled_platforms);
+        let paused_until = Arc::clone(&self.paused_until);
         
         let handle = tokio::spawn(async move {
             info!("Timer system started with configuration-based timers");
@@ -489,32 +630,11 @@ impl TimerSystem {
                 check_interval.tick().await;
                 
                 let now = chrono::Utc::now();
-                let mut timers_to_trigger = Vec::new();
+                let minimum_interval = timer_config.read().await.global_settings.minimum_interval_seconds;
+                let paused = Self::check_paused(&paused_until, now).await;
                 
                 // Check which timers need to be triggered
-                {
-                    let mut timers_guard = timers.write().await;
-                    for (name, timer) in timers_guard.iter_mut() {
-                        if !timer.enabled {
-                            continue;
-                        }
-                        
-                        let should_trigger = match timer.last_triggered {
-                            Some(last) => {
-                                let elapsed = now.signed_duration_since(last);
-                                elapsed.num_seconds() >= timer.interval_seconds as i64
-                            }
-                            None => true, // First time running
-                        };
-                        
-                        if should_trigger {
-                            timer.last_triggered = Some(now);
-                            timer.trigger_count += 1;
-                            timers_to_trigger.push(timer.clone());
-                            debug!("Timer '{}' triggered (count: {})", name, timer.trigger_count);
-                        }
-                    }
-                }
+                let timers_to_trigger = Self::take_due_timers(&mut *timers.write().await, paused, minimum_interval, now);
                 
                 // Send timer messages
                 for timer in timers_to_trigger {
@@ -522,7 +642,9 @@ impl TimerSystem {
                         &timer, 
                         &connections, 
                         &timer_config,
-                        &custom_variables
+                        &custom_variables,
+                        &*disabled_platforms.read().await,
+                        dry_run.load(Ordering::Relaxed),
                     ).await {
                         error!("Failed to execute timer '{}': {}", timer.name, e);
                     }
@@ -545,22 +667,148 @@ impl TimerSystem {
         Ok(())
     }
 
-    /// Execute a timer by sending its message to appropriate channels (with variable substitution)
+    /// Stop scheduled timer posts for `duration`, or until `resume_all` when `None`.
+    /// Timers keep their schedules; fires falling inside the pause are skipped.
+    pub async fn pause_all(&self, duration: Option<chrono::Duration>) -> chrono::DateTime<chrono::Utc> {
+        let until = duration
+            .and_then(|d| chrono::Utc::now().checked_add_signed(d))
+            .unwrap_or(chrono::DateTime::<chrono::Utc>::MAX_UTC);
+        *self.paused_until.write().await = Some(until);
+        match duration {
+            Some(d) => info!("Timers paused for {} minutes", d.num_minutes()),
+            None => info!("Timers paused until resumed"),
+        }
+        until
+    }
+
+    /// End a pause early; returns false if timers weren't paused
+    pub async fn resume_all(&self) -> bool {
+        let was_paused = self.paused_until.write().await.take()
+            .is_some_and(|until| chrono::Utc::now() < until);
+        if was_paused {
+            info!("Timers resumed");
+        }
+        was_paused
+    }
+
+    /// When the current pause ends, if timers are paused
+    pub async fn paused_until(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        (*self.paused_until.read().await).filter(|until| chrono::Utc::now() < *until)
+    }
+
+    /// Whether scheduled fires are paused at `now`, clearing a pause that has run out
+    async fn check_paused(paused_until: &RwLock<Option<chrono::DateTime<chrono::Utc>>>, now: chrono::DateTime<chrono::Utc>) -> bool {
+        let mut paused_until = paused_until.write().await;
+        match *paused_until {
+            Some(until) if now < until => true,
+            Some(_) => {
+                *paused_until = None;
+                info!("Timer pause ended, resuming scheduled posts");
+                false
+            }
+            None => false,
+        }
+    }
+
+    /// Mark the timers due at `now` as fired and return them. While paused, due timers
+    /// are moved on to their next interval without posting so the schedule carries on.
+    fn take_due_timers(
+        timers: &mut HashMap<String, BotTimer>,
+        paused: bool,
+        minimum_interval: u64,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Vec<BotTimer> {
+        let mut timers_to_trigger = Vec::new();
+        for (name, timer) in timers.iter_mut() {
+            if !timer.enabled {
+                continue;
+            }
+            // Outside its hours or days the timer waits, and fires once they come round
+            if timer.schedule.as_ref().is_some_and(|schedule| !schedule.is_active(now)) {
+                continue;
+            }
+
+            let should_trigger = match timer.last_triggered {
+                Some(last) => {
+                    let elapsed = now.signed_duration_since(last);
+                    elapsed.num_seconds() >= timer.next_interval_seconds as i64
+                }
+                None => true, // First time running
+            };
+
+            if should_trigger {
+                timer.last_triggered = Some(now);
+                timer.next_interval_seconds = randomized_interval(timer.interval_seconds, timer.random_offset_max, minimum_interval);
+                if paused {
+                    debug!("Timer '{}' skipped while timers are paused", name);
+                    continue;
+                }
+                timer.trigger_count += 1;
+                timers_to_trigger.push(timer.clone());
+                debug!("Timer '{}' triggered (count: {})", name, timer.trigger_count);
+            }
+        }
+        timers_to_trigger
+    }
+
+    /// Fire a timer immediately across its targets and restart its interval from now.
+    /// Unless forced, a disabled timer is refused and platforms with timers turned off
+    /// are skipped. Returns the number of channels posted to.
+    pub async fn trigger_now(&self, name: &str, force: bool) -> Result<usize> {
+        let connections = self.connections.read().await.clone()
+            .ok_or_else(|| anyhow::anyhow!("Timer system is not running"))?;
+
+        let minimum_interval = self.timer_config.read().await.global_settings.minimum_interval_seconds;
+        let timer = {
+            let mut timers = self.timers.write().await;
+            let timer = timers.get_mut(name)
+                .ok_or_else(|| anyhow::anyhow!("Timer '{}' not found", name))?;
+            if !timer.enabled && !force {
+                return Err(anyhow::anyhow!("Timer '{}' is disabled", name));
+            }
+            timer.last_triggered = Some(chrono::Utc::now());
+            timer.trigger_count += 1;
+            timer.next_interval_seconds = randomized_interval(timer.interval_seconds, timer.random_offset_max, minimum_interval);
+            timer.clone()
+        };
+
+        let skipped_platforms = if force { HashSet::new() } else { self.disabled_platforms.read().await.clone() };
+        let posted = Self::execute_timer_with_variables(
+            &timer,
+            &connections,
+            &self.timer_config,
+            &self.custom_variables,
+            &skipped_platforms,
+            self.dry_run.load(Ordering::Relaxed),
+        ).await?;
+
+        info!("Timer '{}' fired manually{} ({} channels)", name, if force { " (forced)" } else { "" }, posted);
+        Ok(posted)
+    }
+
+    /// Execute a timer by sending its message to appropriate channels (with variable substitution),
+    /// returning the number of channels posted to
     async fn execute_timer_with_variables(
         timer: &BotTimer,
         connections: &Arc<RwLock<HashMap<String, Box<dyn PlatformConnection>>>>,
         timer_config: &Arc<RwLock<TimerConfig>>,
         custom_variables: &Arc<RwLock<HashMap<String, String>>>,
-    ) -> Result<()> {
+        disabled_platforms: &HashSet<String>,
+        dry_run: bool,
+    ) -> Result<usize> {
         let connections_guard = connections.read().await;
         let config = timer_config.read().await;
         let custom_vars = custom_variables.read().await;
+        let mut posted = 0;
         
         for (platform_name, connection) in connections_guard.iter() {
             // Check if this timer should post on this platform
             if !timer.platforms.is_empty() && !timer.platforms.contains(platform_name) {
                 continue;
             }
+            if disabled_platforms.contains(platform_name) {
+                continue;
+            }
             
             // Get channels for this platform
             let channels_to_post = if timer.channels.is_empty() {
@@ -597,15 +845,19 @@ impl TimerSystem {
                     }
                 }
                 
-                if let Err(e) = connection.send_message(&channel, &processed_message).await {
+                if dry_run {
+                    info!("[DRY RUN] Timer '{}' would post to {}#{}: {}", timer.name, platform_name, channel, processed_message);
+                    posted += 1;
+                } else if let Err(e) = connection.send_message(&channel, &processed_message).await {
                     error!("Failed to send timer message to {}#{}: {}", platform_name, channel, e);
                 } else {
                     info!("Timer '{}' posted to {}#{}: {}", timer.name, platform_name, channel, processed_message);
+                    posted += 1;
                 }
             }
         }
         
-        Ok(())
+        Ok(posted)
     }
 
     /// Get timer analytics (if enabled)
@@ -698,6 +950,21 @@ impl TimerSystem {
         custom_vars.iter().map(|(k, v)| (k.clone(), v.clone())).collect()
     }
 
+    /// Log timer posts instead of sending them
+    pub fn set_dry_run(&self, enabled: bool) {
+        self.dry_run.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Turn all timer posts on or off for one platform
+    pub async fn set_platform_enabled(&self, platform: &str, enabled: bool) {
+        let mut disabled = self.disabled_platforms.write().await;
+        if enabled {
+            disabled.remove(platform);
+        } else {
+            disabled.insert(platform.to_string());
+        }
+    }
+
     /// Signal the timer system to shutdown gracefully
     pub async fn shutdown(&self) {
         info!("Signaling timer system shutdown...");
@@ -707,4 +974,222 @@ impl TimerSystem {
         tokio::time::sleep(Duration::from_millis(100)).await;
         info!("Timer system shutdown signal sent");
     }
-}
\ No newline at end of file
+}
+
+/// Shift an interval by a random offset in `[-random_offset_max, +random_offset_max]`
+/// so timers don't post on a fixed cadence, never going below the minimum interval
+pub fn randomized_interval(interval_seconds: u64, random_offset_max: Option<u64>, minimum_interval_seconds: u64) -> u64 {
+    let offset_max = random_offset_max.unwrap_or(0).min(i64::MAX as u64) as i64;
+    if offset_max == 0 {
+        return interval_seconds;
+    }
+
+    let offset = rand::rng().random_range(-offset_max..=offset_max);
+    let interval = (interval_seconds as i64).saturating_add(offset);
+    interval.max(minimum_interval_seconds as i64).max(0) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platforms::mock::MockConnection;
+
+    #[tokio::test]
+    async fn test_reload_single_timer_keeps_schedules() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = dir.path().join("timers.yaml");
+        let timer_system = TimerSystem::with_config_path(&config_path);
+        timer_system.load_config().await.unwrap();
+
+        let triggered_at = chrono::Utc::now() - chrono::Duration::seconds(120);
+        for timer in timer_system.timers.write().await.values_mut() {
+            timer.last_triggered = Some(triggered_at);
+            timer.trigger_count = 4;
+        }
+
+        let mut config: TimerConfig = serde_yaml::from_str(&std::fs::read_to_string(&config_path).unwrap()).unwrap();
+        let edited = config.timers.iter_mut().find(|t| t.name == "ai_features").unwrap();
+        edited.message = "Updated message".to_string();
+        edited.interval_seconds = 600;
+        std::fs::write(&config_path, serde_yaml::to_string(&config).unwrap()).unwrap();
+
+        timer_system.reload_timer("ai_features").await.unwrap();
+
+        let timers = timer_system.timers.read().await;
+        let reloaded = &timers["ai_features"];
+        assert_eq!(reloaded.message, "Updated message");
+        assert_eq!(reloaded.interval_seconds, 600);
+        assert_eq!(reloaded.last_triggered, Some(triggered_at));
+        assert_eq!(reloaded.trigger_count, 4);
+
+        // Every other timer keeps its schedule
+        assert!(timers.len() > 1);
+        for timer in timers.values() {
+            assert_eq!(timer.last_triggered, Some(triggered_at), "timer {} was reset", timer.name);
+            assert_eq!(timer.trigger_count, 4);
+        }
+        drop(timers);
+
+        let stored = timer_system.timer_config.read().await;
+        assert_eq!(stored.timers.iter().find(|t| t.name == "ai_features").unwrap().message, "Updated message");
+        drop(stored);
+
+        assert!(timer_system.reload_timer("missing").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_trigger_now_fires_and_restarts_interval() {
+        let timer_system = TimerSystem::new();
+        timer_system.add_timer_advanced("raid".to_string(), "Raid incoming on $(platform)!".to_string(), 600, Vec::new(), Vec::new()).await.unwrap();
+        assert!(timer_system.trigger_now("raid", false).await.is_err(), "nothing to post to before start");
+
+        let mut twitch = MockConnection::new("twitch", vec!["streamer".to_string(), "backup".to_string()]);
+        twitch.connect().await.unwrap();
+        let sent = twitch.clone();
+        let mut connections: HashMap<String, Box<dyn PlatformConnection>> = HashMap::new();
+        connections.insert("twitch".to_string(), Box::new(twitch));
+        *timer_system.connections.write().await = Some(Arc::new(RwLock::new(connections)));
+
+        let long_ago = chrono::Utc::now() - chrono::Duration::seconds(500);
+        timer_system.timers.write().await.get_mut("raid").unwrap().last_triggered = Some(long_ago);
+
+        let before = chrono::Utc::now();
+        assert_eq!(timer_system.trigger_now("raid", false).await.unwrap(), 2);
+        assert_eq!(sent.sent_messages().await, vec![
+            ("streamer".to_string(), "Raid incoming on twitch!".to_string()),
+            ("backup".to_string(), "Raid incoming on twitch!".to_string()),
+        ]);
+        // The interval now counts from the manual fire rather than the earlier run
+        let (_, count, last_triggered) = timer_system.get_timer_stats().await["raid"];
+        assert_eq!(count, 1);
+        assert!(last_triggered.is_some_and(|t| t >= before));
+
+        // Conditions hold unless forced
+        timer_system.set_platform_enabled("twitch", false).await;
+        assert_eq!(timer_system.trigger_now("raid", false).await.unwrap(), 0);
+        timer_system.set_timer_enabled("raid", false).await.unwrap();
+        assert!(timer_system.trigger_now("raid", false).await.is_err());
+        assert_eq!(timer_system.trigger_now("raid", true).await.unwrap(), 2);
+        assert_eq!(sent.sent_messages().await.len(), 4);
+
+        assert!(timer_system.trigger_now("missing", true).await.is_err());
+    }
+
+    #[test]
+    fn test_randomized_interval_stays_within_offset() {
+        assert_eq!(randomized_interval(900, None, 30), 900);
+        assert_eq!(randomized_interval(900, Some(0), 30), 900);
+
+        let intervals: Vec<u64> = (0..200).map(|_| randomized_interval(900, Some(120), 30)).collect();
+        assert!(intervals.iter().all(|i| (780..=1020).contains(i)), "out of range: {:?}", intervals);
+        let distinct: HashSet<_> = intervals.iter().collect();
+        assert!(distinct.len() > 10, "intervals barely vary: {:?}", distinct);
+        assert!(intervals.iter().any(|&i| i < 900) && intervals.iter().any(|&i| i > 900));
+
+        // An offset larger than the interval can't push it under the minimum
+        assert!((0..200).all(|_| randomized_interval(60, Some(600), 45) >= 45));
+    }
+
+    #[tokio::test]
+    async fn test_each_fire_rolls_a_new_interval() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = dir.path().join("timers.yaml");
+        let timer_system = TimerSystem::with_config_path(&config_path);
+        let mut config = timer_system.create_comprehensive_default_config();
+        config.timers[0].random_offset_max = Some(300);
+        std::fs::write(&config_path, serde_yaml::to_string(&config).unwrap()).unwrap();
+        timer_system.load_config().await.unwrap();
+
+        let mut connections: HashMap<String, Box<dyn PlatformConnection>> = HashMap::new();
+        connections.insert("twitch".to_string(), Box::new(MockConnection::new("twitch", vec!["streamer".to_string()])));
+        *timer_system.connections.write().await = Some(Arc::new(RwLock::new(connections)));
+        timer_system.set_dry_run(true);
+
+        let mut intervals = HashSet::new();
+        for _ in 0..50 {
+            timer_system.trigger_now("ai_features", false).await.unwrap();
+            let next = timer_system.timers.read().await["ai_features"].next_interval_seconds;
+            assert!((600..=1200).contains(&next), "interval {} outside 900±300", next);
+            intervals.insert(next);
+        }
+        assert!(intervals.len() > 1, "every fire used the same interval");
+
+        // Timers without an offset keep their exact cadence
+        assert_eq!(timer_system.timers.read().await["community_ai"].next_interval_seconds, 1200);
+    }
+
+    #[tokio::test]
+    async fn test_paused_timers_skip_fires_and_resume_on_schedule() {
+        let timer_system = TimerSystem::new();
+        timer_system.add_timer("promo".to_string(), "Follow the stream!".to_string(), 300).await.unwrap();
+        let start = chrono::Utc::now();
+        timer_system.timers.write().await.get_mut("promo").unwrap().last_triggered = Some(start - chrono::Duration::seconds(300));
+
+        let until = timer_system.pause_all(Some(chrono::Duration::minutes(10))).await;
+        assert_eq!(timer_system.paused_until().await, Some(until));
+
+        // Due during the pause: nothing fires, but the schedule moves on
+        let during = start + chrono::Duration::seconds(60);
+        let paused = TimerSystem::check_paused(&timer_system.paused_until, during).await;
+        assert!(paused);
+        let fired = TimerSystem::take_due_timers(&mut *timer_system.timers.write().await, paused, 30, during);
+        assert!(fired.is_empty());
+        let (_, count, last_triggered) = timer_system.get_timer_stats().await["promo"];
+        assert_eq!(count, 0);
+        assert_eq!(last_triggered, Some(during));
+
+        // After the window the pause clears itself and the timer fires on its next interval
+        let after = until + chrono::Duration::seconds(1);
+        assert!(!TimerSystem::check_paused(&timer_system.paused_until, after).await);
+        assert_eq!(timer_system.paused_until().await, None);
+        let fired = TimerSystem::take_due_timers(&mut *timer_system.timers.write().await, false, 30, after);
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].trigger_count, 1);
+
+        // An open-ended pause lasts until resumed
+        timer_system.pause_all(None).await;
+        assert!(TimerSystem::check_paused(&timer_system.paused_until, after + chrono::Duration::days(1)).await);
+        assert!(timer_system.resume_all().await);
+        assert!(!timer_system.resume_all().await);
+        assert!(!TimerSystem::check_paused(&timer_system.paused_until, after).await);
+    }
+
+    #[tokio::test]
+    async fn test_weekend_timer_only_fires_on_weekends() {
+        use chrono::TimeZone;
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = dir.path().join("timers.yaml");
+        let timer_system = TimerSystem::with_config_path(&config_path);
+        timer_system.load_config().await.unwrap();
+
+        let mut config: TimerConfig = serde_yaml::from_str(&std::fs::read_to_string(&config_path).unwrap()).unwrap();
+        config.timers.retain(|t| t.name == "points_economy");
+        std::fs::write(&config_path, serde_yaml::to_string(&config).unwrap()).unwrap();
+        timer_system.load_config().await.unwrap();
+
+        let windows = vec![crate::config::TimeRange {
+            start: "18:00".to_string(),
+            end: "02:00".to_string(),
+            timezone: None,
+        }];
+        let days = ["Sat".to_string(), "Sun".to_string()];
+        let schedule = ActiveSchedule::new(windows, &days, Some("America/New_York".to_string())).unwrap().unwrap();
+        timer_system.set_schedules(HashMap::from([("points_economy".to_string(), schedule)])).await;
+
+        // 2026-03-13 is a Friday; New York is UTC-4 by then
+        let at = |day, hour| chrono::Utc.with_ymd_and_hms(2026, 3, day, hour, 0, 0).unwrap();
+        let mut timers = timer_system.timers.write().await;
+        assert!(TimerSystem::take_due_timers(&mut timers, false, 30, at(13, 23)).is_empty());
+        // Saturday 19:00 local fires, and Sunday 01:00 local still belongs to Saturday evening
+        assert_eq!(TimerSystem::take_due_timers(&mut timers, false, 30, at(14, 23)).len(), 1);
+        assert_eq!(TimerSystem::take_due_timers(&mut timers, false, 30, at(15, 5)).len(), 1);
+        // Monday 01:00 local is Sunday evening's window, Monday 19:00 isn't scheduled
+        assert_eq!(TimerSystem::take_due_timers(&mut timers, false, 30, at(16, 5)).len(), 1);
+        assert!(TimerSystem::take_due_timers(&mut timers, false, 30, at(16, 23)).is_empty());
+        drop(timers);
+
+        // Schedules outlive reloads of the timers file
+        timer_system.load_config().await.unwrap();
+        assert!(timer_system.timers.read().await["points_economy"].schedule.is_some());
+    }
+}
@@ -0,0 +1,96 @@
+use std::collections::HashSet;
+
+/// Which chat users the bot processes at all
+///
+/// Entries are either `username` (any platform) or `platform:username`,
+/// matched case-insensitively.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UserFilter {
+    /// Users whose messages are ignored entirely (known alt-bots, etc.)
+    denied: HashSet<String>,
+    /// When set, only these users are processed
+    allowed: Option<HashSet<String>>,
+}
+
+impl UserFilter {
+    /// Ignore the given users, processing everyone else
+    pub fn deny<I, S>(users: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self {
+            denied: Self::normalize(users),
+            allowed: None,
+        }
+    }
+
+    /// Only process the given users (the deny list still applies on top)
+    pub fn with_allowlist<I, S>(mut self, users: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.allowed = Some(Self::normalize(users));
+        self
+    }
+
+    /// Whether messages from this user should be processed
+    pub fn is_allowed(&self, platform: &str, username: &str) -> bool {
+        let username = username.to_lowercase();
+        let scoped = format!("{}:{}", platform.to_lowercase(), username);
+        let listed = |users: &HashSet<String>| users.contains(&username) || users.contains(&scoped);
+
+        if listed(&self.denied) {
+            return false;
+        }
+        self.allowed.as_ref().is_none_or(listed)
+    }
+
+    pub fn is_allowlist_mode(&self) -> bool {
+        self.allowed.is_some()
+    }
+
+    fn normalize<I, S>(users: I) -> HashSet<String>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        users.into_iter()
+            .map(|u| u.as_ref().trim().trim_start_matches('@').to_lowercase())
+            .filter(|u| !u.is_empty())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deny_list() {
+        let filter = UserFilter::deny(["AltBot", "twitch:shadowbot", "@Nightbot"]);
+
+        assert!(!filter.is_allowed("twitch", "altbot"));
+        assert!(!filter.is_allowed("youtube", "ALTBOT"));
+        assert!(!filter.is_allowed("twitch", "nightbot"));
+        // Platform-scoped entries only apply on that platform
+        assert!(!filter.is_allowed("twitch", "ShadowBot"));
+        assert!(filter.is_allowed("youtube", "shadowbot"));
+        assert!(filter.is_allowed("twitch", "viewer"));
+        assert!(UserFilter::default().is_allowed("twitch", "anyone"));
+    }
+
+    #[test]
+    fn test_allowlist_mode() {
+        let filter = UserFilter::deny(["tester2"]).with_allowlist(["tester1", "tester2", "mock:tester3"]);
+
+        assert!(filter.is_allowlist_mode());
+        assert!(filter.is_allowed("twitch", "Tester1"));
+        assert!(filter.is_allowed("mock", "tester3"));
+        assert!(!filter.is_allowed("twitch", "tester3"));
+        assert!(!filter.is_allowed("twitch", "viewer"));
+        // Deny wins over allow
+        assert!(!filter.is_allowed("twitch", "tester2"));
+    }
+}
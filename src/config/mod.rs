@@ -1,18 +1,63 @@
 // src/config/mod.rs - New configuration management module
 
-use anyhow::Result;
 use log::{debug, error, info, warn};
 use notify::{Config, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use tokio::fs;
 use tokio::sync::{broadcast, RwLock};
 use tokio::time::{sleep, Duration};
 
-use crate::bot::pattern_matching::AdvancedPattern;
-use crate::types::{BlacklistFilterConfig, SpamFilterConfig, AdvancedPatternConfig};
+use crate::bot::achievements::{Achievement, AchievementCategory, AchievementRarity, AchievementRequirement};
+use crate::bot::pattern_matching::{AdvancedPattern, TimeoutFallback};
+use crate::bot::schedule::ActiveSchedule;
+use crate::types::{BlacklistFilterConfig, SpamFilterConfig, AdvancedPatternConfig, FilterLogLevel, UserLevel, WildcardAnchor};
+
+/// Errors from loading, saving and validating configuration
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigError {
+    #[error("Not found: {0}")]
+    NotFound(String),
+
+    #[error("Already exists: {0}")]
+    AlreadyExists(String),
+
+    #[error("Invalid configuration: {0}")]
+    Validation(String),
+
+    #[error("Parse error: {0}")]
+    Parse(String),
+
+    #[error("Unsupported format: {0}")]
+    UnsupportedFormat(String),
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Configuration directory {0} is read-only; changes cannot be saved")]
+    ReadOnly(String),
+
+    #[error("File watcher error: {0}")]
+    Watch(#[from] notify::Error),
+}
+
+impl From<serde_yaml::Error> for ConfigError {
+    fn from(e: serde_yaml::Error) -> Self {
+        ConfigError::Parse(e.to_string())
+    }
+}
+
+impl From<serde_json::Error> for ConfigError {
+    fn from(e: serde_json::Error) -> Self {
+        ConfigError::Parse(e.to_string())
+    }
+}
+
+/// Result type for configuration operations
+pub type ConfigResult<T> = std::result::Result<T, ConfigError>;
 
 /// Main configuration manager that handles all external configuration files
 #[derive(Clone)]
@@ -25,6 +70,7 @@ pub struct ConfigurationManager {
     pattern_config: Arc<RwLock<PatternConfiguration>>,
     timer_config: Arc<RwLock<TimerConfiguration>>,
     bot_config: Arc<RwLock<BotConfiguration>>,
+    achievement_config: Arc<RwLock<AchievementConfiguration>>,
     
     /// File watchers for hot-reloading
     watchers: Arc<RwLock<Vec<RecommendedWatcher>>>,
@@ -38,6 +84,72 @@ pub struct ConfigurationManager {
     /// Cache control
     cache_enabled: bool,
     last_reload: Arc<RwLock<std::time::Instant>>,
+
+    /// Set when the directory can't be written (e.g. mounted as a ConfigMap):
+    /// defaults stay in memory and saves are refused
+    read_only: Arc<AtomicBool>,
+
+    /// Keep the last good configuration when a file fails to parse or validate,
+    /// rather than falling back to defaults (startup refuses to continue instead)
+    strict: Arc<AtomicBool>,
+
+    /// Hot-reload outcomes since startup
+    reload_metrics: Arc<RwLock<ReloadMetrics>>,
+}
+
+/// File changes this soon after the last one handled are skipped
+const RELOAD_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Hot-reload outcomes for one configuration file
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReloadStats {
+    pub succeeded: u64,
+    pub failed: u64,
+    pub last_reload: Option<chrono::DateTime<chrono::Utc>>,
+    pub last_error: Option<String>,
+    pub last_error_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Hot-reload activity since startup; a high count points at an editor saving on every keystroke
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReloadMetrics {
+    /// Keyed by config type: "filters", "patterns", "timers", "bot" or "achievements"
+    pub by_config: HashMap<String, ReloadStats>,
+    /// File change events skipped for arriving within the debounce window
+    pub debounce_skipped: u64,
+}
+
+impl ReloadMetrics {
+    fn record(&mut self, filename: &str, error: Option<String>) {
+        let config_type = filename.split('.').next().unwrap_or(filename);
+        let stats = self.by_config.entry(config_type.to_string()).or_default();
+        let now = chrono::Utc::now();
+        stats.last_reload = Some(now);
+        match error {
+            Some(error) => {
+                stats.failed += 1;
+                stats.last_error = Some(error);
+                stats.last_error_at = Some(now);
+            }
+            None => stats.succeeded += 1,
+        }
+    }
+
+    pub fn succeeded(&self) -> u64 {
+        self.by_config.values().map(|s| s.succeeded).sum()
+    }
+
+    pub fn failed(&self) -> u64 {
+        self.by_config.values().map(|s| s.failed).sum()
+    }
+
+    /// Config type and message of the most recent failed reload
+    pub fn last_error(&self) -> Option<(&str, &str)> {
+        self.by_config.iter()
+            .filter_map(|(config_type, s)| Some((s.last_error_at?, config_type.as_str(), s.last_error.as_deref()?)))
+            .max_by_key(|(at, _, _)| *at)
+            .map(|(_, config_type, error)| (config_type, error))
+    }
 }
 
 /// Events broadcasted when configuration changes
@@ -47,6 +159,7 @@ pub enum ConfigChangeEvent {
     PatternsUpdated { file: String },
     TimersUpdated { file: String },
     BotConfigUpdated { file: String },
+    AchievementsUpdated { file: String },
     ValidationError { file: String, error: String },
     ReloadComplete { files_updated: Vec<String> },
 }
@@ -73,6 +186,34 @@ pub struct FilterConfiguration {
     
     /// Import/export settings
     pub import_export: ImportExportSettings,
+
+    /// Protected phrases that bypass blacklist filters
+    #[serde(default)]
+    pub allowlist: AllowlistConfig,
+}
+
+/// Allowlist of protected phrases checked before filters
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AllowlistConfig {
+    pub entries: Vec<AllowlistEntryConfig>,
+    /// Skip every filter for allowlisted messages, not just blacklists
+    #[serde(default)]
+    pub bypass_all_filters: bool,
+}
+
+/// Single allowlist entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AllowlistEntryConfig {
+    pub value: String,
+    #[serde(default = "default_allowlist_pattern_type")]
+    pub pattern_type: String, // "literal", "wildcard", "regex"
+    /// Channels this entry applies to; empty means all channels
+    #[serde(default)]
+    pub channels: Vec<String>,
+}
+
+fn default_allowlist_pattern_type() -> String {
+    "literal".to_string()
 }
 
 /// Enhanced blacklist filter with more configuration options
@@ -87,6 +228,11 @@ pub struct EnhancedBlacklistFilter {
     
     /// Pattern configuration
     pub patterns: Vec<PatternDefinition>,
+    /// Exceptions: a block pattern match is let through when one of these
+    /// matches the same chunk of text (e.g. the same link); matches elsewhere
+    /// in the message are still actioned
+    #[serde(default)]
+    pub except_patterns: Vec<PatternDefinition>,
     pub case_sensitive: bool,
     pub whole_words_only: bool,
     pub regex_flags: Option<String>, // i, m, s, x flags
@@ -94,8 +240,18 @@ pub struct EnhancedBlacklistFilter {
     /// Action configuration
     pub timeout_seconds: Option<u64>,
     pub escalation_enabled: bool,
+    /// Fixed action (delete, warn, timeout, shadow_mute, log_only) for every offense when
+    /// escalation is disabled
+    #[serde(default)]
+    pub action: Option<String>,
+    /// Warn on the first match and only action a second match within this many seconds
+    #[serde(default)]
+    pub grace_seconds: Option<u64>,
     pub custom_message: Option<String>,
     pub silent_mode: bool,
+    /// Level matches are logged at: trace, debug, info or warn (unset = the usual levels)
+    #[serde(default)]
+    pub log_level: Option<FilterLogLevel>,
     
     /// User exemptions
     pub exemption_level: String,
@@ -119,6 +275,60 @@ pub struct EnhancedBlacklistFilter {
     pub learning_enabled: bool,
 }
 
+impl EnhancedBlacklistFilter {
+    /// The filter's `active_hours` and `active_days`, if it's limited to either
+    pub fn schedule(&self) -> ConfigResult<Option<ActiveSchedule>> {
+        let timezone = self.active_hours.as_ref().and_then(|hours| hours.timezone.clone());
+        ActiveSchedule::new(
+            self.active_hours.iter().cloned().collect(),
+            self.active_days.as_deref().unwrap_or_default(),
+            timezone,
+        ).map_err(|e| ConfigError::Validation(format!("Filter '{}': {} in active_days", self.id, e)))
+    }
+}
+
+/// Which kind of filter a search result is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterKind {
+    Blacklist,
+    Spam,
+}
+
+/// The part of a filter a search query matched
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterSearchField {
+    Name,
+    Pattern,
+    Tag,
+}
+
+/// A filter found by `ConfigurationManager::find_filters`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FilterSearchMatch {
+    pub id: String,
+    pub name: String,
+    pub kind: FilterKind,
+    pub enabled: bool,
+    pub field: FilterSearchField,
+    /// The name, pattern or tag that matched
+    pub matched: String,
+}
+
+impl FilterSearchMatch {
+    fn blacklist(filter: &EnhancedBlacklistFilter, field: FilterSearchField, matched: &str) -> Self {
+        Self {
+            id: filter.id.clone(),
+            name: filter.name.clone(),
+            kind: FilterKind::Blacklist,
+            enabled: filter.enabled,
+            field,
+            matched: matched.to_string(),
+        }
+    }
+}
+
 /// Individual pattern definition with type and parameters
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PatternDefinition {
@@ -127,6 +337,9 @@ pub struct PatternDefinition {
     pub weight: f32, // 0.0-1.0, how much this pattern contributes to match
     pub description: Option<String>,
     pub enabled: bool,
+    /// For wildcard patterns: match the whole text (default) or anywhere in it
+    #[serde(default)]
+    pub anchor: WildcardAnchor,
 }
 
 /// Enhanced spam filter configuration
@@ -146,8 +359,18 @@ pub struct EnhancedSpamFilter {
     /// Action and escalation
     pub timeout_seconds: u64,
     pub escalation: EscalationConfig,
+    /// Fixed action (delete, warn, timeout, shadow_mute, log_only) for every offense when
+    /// escalation is disabled
+    #[serde(default)]
+    pub action: Option<String>,
+    /// Warn on the first match and only action a second match within this many seconds
+    #[serde(default)]
+    pub grace_seconds: Option<u64>,
     pub custom_message: Option<String>,
     pub silent_mode: bool,
+    /// Level matches are logged at: trace, debug, info or warn (unset = the usual levels)
+    #[serde(default)]
+    pub log_level: Option<FilterLogLevel>,
     
     /// Exemptions and conditions
     pub exemption_level: String,
@@ -197,11 +420,23 @@ pub struct ConditionConfig {
 }
 
 /// Time range specification
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TimeRange {
     pub start: String, // "14:30" format
     pub end: String,   // "22:00" format
-    pub timezone: Option<String>, // "UTC", "PST", etc.
+    pub timezone: Option<String>, // "UTC", "America/New_York", "+05:30", etc.
+}
+
+impl TimeRange {
+    /// Whether `at` falls within this range in the range's timezone (see
+    /// `schedule::is_active_at`). Invalid ranges never match.
+    pub fn contains(&self, at: chrono::DateTime<chrono::Utc>) -> bool {
+        crate::bot::schedule::is_active_at(std::slice::from_ref(self), &[], None, at)
+    }
+
+    pub fn contains_now(&self) -> bool {
+        self.contains(chrono::Utc::now())
+    }
 }
 
 /// Global filter settings
@@ -214,6 +449,30 @@ pub struct FilterGlobalSettings {
     pub auto_optimization: bool,
     pub performance_monitoring: bool,
     pub debug_mode: bool,
+    /// What to do when two filters share an `id`
+    #[serde(default)]
+    pub duplicate_ids: DuplicateIdPolicy,
+    /// Seconds in which a user is warned at most once by the same filter (0 = warn every time)
+    #[serde(default = "default_warning_dedup_seconds")]
+    pub warning_dedup_seconds: u64,
+    /// AI pattern matches below this confidence (0.0-1.0) are only logged, never acted on
+    #[serde(default)]
+    pub min_ai_action_confidence: f32,
+}
+
+fn default_warning_dedup_seconds() -> u64 {
+    crate::bot::moderation::DEFAULT_WARNING_DEDUP_SECONDS
+}
+
+/// Handling of filters that share an `id` (CRUD by id only ever reaches one of them)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicateIdPolicy {
+    /// Refuse to load the configuration
+    #[default]
+    Reject,
+    /// Load it anyway and log a warning
+    Warn,
 }
 
 /// Filter category definition
@@ -294,6 +553,15 @@ pub struct PatternCollection {
     pub max_processing_time_ms: u32,
     pub cache_results: bool,
     pub parallel_processing: bool,
+
+    /// Fallback when `max_processing_time_ms` is exceeded:
+    /// "fail_open" (allow the message) or "fail_closed" (flag for review)
+    #[serde(default = "default_timeout_action")]
+    pub timeout_action: String,
+}
+
+fn default_timeout_action() -> String {
+    "fail_open".to_string()
 }
 
 /// Advanced pattern definition with AI capabilities
@@ -308,6 +576,16 @@ pub struct AdvancedPatternDefinition {
     pub min_confidence: f32,
     pub learning_rate: f32,
     pub tags: Vec<String>,
+
+    /// Skip this pattern for messages shorter than this many characters,
+    /// overriding `global_settings.min_message_length`
+    #[serde(default)]
+    pub min_message_length: Option<usize>,
+
+    /// Scale this pattern's thresholds by the sender's reputation (stricter for new
+    /// accounts, more lenient for regulars); unset follows the system-wide setting
+    #[serde(default)]
+    pub reputation_scaling: Option<bool>,
 }
 
 /// Global pattern settings
@@ -319,6 +597,11 @@ pub struct PatternGlobalSettings {
     pub cache_size_mb: u32,
     pub performance_profiling: bool,
     pub auto_optimization: bool,
+
+    /// Messages shorter than this many characters skip fuzzy, phonetic and
+    /// keyboard-shift patterns (literal matching still runs); 0 disables
+    #[serde(default)]
+    pub min_message_length: usize,
 }
 
 /// Machine learning configuration
@@ -388,6 +671,16 @@ pub struct EnhancedTimer {
     pub track_engagement: bool,
 }
 
+impl EnhancedTimer {
+    /// The timer's `time_windows` and `day_restrictions`, if it's limited to either.
+    /// Days are counted in the first window's timezone (UTC without one).
+    pub fn active_schedule(&self) -> ConfigResult<Option<ActiveSchedule>> {
+        let timezone = self.schedule.time_windows.first().and_then(|window| window.timezone.clone());
+        ActiveSchedule::new(self.schedule.time_windows.clone(), &self.schedule.day_restrictions, timezone)
+            .map_err(|e| ConfigError::Validation(format!("Timer '{}': {} in day_restrictions", self.id, e)))
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TimerMessage {
     pub content: String,
@@ -472,6 +765,78 @@ pub struct TimerAnalytics {
     pub retention_days: u32,
 }
 
+/// Custom achievement configuration structure
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AchievementConfiguration {
+    pub version: String,
+    pub description: String,
+
+    /// Achievements merged with the built-in defaults (same ID overrides a default)
+    #[serde(default)]
+    pub achievements: Vec<AchievementDefinition>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AchievementDefinition {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub condition: AchievementCondition,
+    pub reward_points: i64,
+    #[serde(default)]
+    pub hidden: bool,
+    #[serde(default)]
+    pub category: AchievementCategory,
+    #[serde(default)]
+    pub rarity: AchievementRarity,
+    #[serde(default = "default_badge_emoji")]
+    pub badge_emoji: String,
+}
+
+/// Unlock condition: a user metric reaching a threshold
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AchievementCondition {
+    pub metric: String, // see AchievementRequirement::CONFIG_METRICS
+    pub threshold: u64,
+}
+
+fn default_badge_emoji() -> String {
+    "🏅".to_string()
+}
+
+impl AchievementDefinition {
+    /// Convert to an achievement, failing on an unknown condition metric
+    pub fn to_achievement(&self) -> ConfigResult<Achievement> {
+        let requirement = AchievementRequirement::from_metric(&self.condition.metric, self.condition.threshold)
+            .ok_or_else(|| ConfigError::Validation(format!(
+                "Achievement '{}' uses unknown metric '{}' (expected one of: {})",
+                self.id, self.condition.metric, AchievementRequirement::CONFIG_METRICS.join(", ")
+            )))?;
+
+        Ok(Achievement {
+            id: self.id.clone(),
+            name: self.name.clone(),
+            description: self.description.clone(),
+            category: self.category.clone(),
+            requirement,
+            reward_points: self.reward_points,
+            badge_emoji: self.badge_emoji.clone(),
+            rarity: self.rarity.clone(),
+            hidden: self.hidden,
+        })
+    }
+}
+
+impl Default for AchievementConfiguration {
+    fn default() -> Self {
+        Self {
+            version: "1.0".to_string(),
+            description: "NotaBot Custom Achievements".to_string(),
+            achievements: Vec::new(),
+        }
+    }
+}
+
 /// Bot configuration structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BotConfiguration {
@@ -493,6 +858,79 @@ pub struct BotConfiguration {
     
     /// Security settings
     pub security: SecuritySettings,
+
+    /// Points earning settings
+    #[serde(default)]
+    pub points: PointsSettings,
+
+    /// Users the bot ignores, or exclusively processes in allowlist mode
+    #[serde(default)]
+    pub user_filter: UserFilterSettings,
+
+    /// Message content handling in logs and analytics
+    #[serde(default)]
+    pub privacy: PrivacySettings,
+
+    /// Hours when chat is automatically restricted
+    #[serde(default)]
+    pub restricted_hours: crate::bot::restricted_hours::RestrictedHoursConfig,
+
+    /// Opt-in log of inbound messages for crash recovery
+    #[serde(default)]
+    pub message_log: crate::bot::message_log::MessageLogConfig,
+
+    /// Queue for moderation actions sent while a platform is disconnected
+    #[serde(default)]
+    pub pending_actions: crate::bot::pending_actions::PendingActionConfig,
+
+    /// How much each signal counts towards a giveaway entrant's fraud score
+    #[serde(default)]
+    pub giveaway_fraud: crate::bot::giveaway_fraud::FraudWeights,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PrivacySettings {
+    /// Redaction of message text in logs and stored analytics snippets
+    #[serde(default)]
+    pub redaction: crate::bot::redaction::RedactionMode,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UserFilterSettings {
+    /// `username` or `platform:username` entries whose messages are ignored entirely
+    #[serde(default)]
+    pub ignored_users: Vec<String>,
+    /// Only process `allowed_users` (useful for testing)
+    #[serde(default)]
+    pub allowlist_mode: bool,
+    #[serde(default)]
+    pub allowed_users: Vec<String>,
+}
+
+impl UserFilterSettings {
+    pub fn to_user_filter(&self) -> crate::bot::user_filter::UserFilter {
+        let filter = crate::bot::user_filter::UserFilter::deny(&self.ignored_users);
+        if self.allowlist_mode {
+            filter.with_allowlist(&self.allowed_users)
+        } else {
+            filter
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PointsSettings {
+    /// Earning multiplier by user level; levels not listed earn 1.0x
+    #[serde(default = "crate::bot::points::default_level_multipliers")]
+    pub level_multipliers: HashMap<UserLevel, f64>,
+}
+
+impl Default for PointsSettings {
+    fn default() -> Self {
+        Self {
+            level_multipliers: crate::bot::points::default_level_multipliers(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -501,9 +939,46 @@ pub struct CoreBotSettings {
     pub global_prefix: String,
     pub response_delay_ms: u64,
     pub max_message_length: usize,
+    /// Commands each user may use per minute across all commands; 0 disables the limit
     pub rate_limit_per_minute: u32,
+    /// Moderators aren't held to `rate_limit_per_minute`
+    #[serde(default = "default_rate_limit_exempt_mods")]
+    pub rate_limit_exempt_mods: bool,
+    /// Reply when a command is on cooldown, with `$(user)`, `$(command)` and
+    /// `$(remaining)` filled in (unset ignores the command silently)
+    #[serde(default)]
+    pub cooldown_notice: Option<String>,
+    /// At most one cooldown notice per channel this often
+    #[serde(default = "default_cooldown_notice_interval_seconds")]
+    pub cooldown_notice_interval_seconds: u64,
     pub debug_mode: bool,
     pub log_level: String,
+    /// Log moderation actions, responses and timer posts instead of sending them
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Account the bot posts as, per platform; its messages are never moderated.
+    /// Twitch falls back to the login username, so set this when another instance shares it.
+    #[serde(default)]
+    pub bot_accounts: HashMap<String, String>,
+    /// Inbound messages taking longer than this to process are skipped
+    #[serde(default = "default_message_timeout_ms")]
+    pub message_timeout_ms: u64,
+    /// Refuse to start when a configuration file exists but is broken, instead
+    /// of using its defaults (a broken hot-reload always keeps the last good one)
+    #[serde(default)]
+    pub strict_config: bool,
+}
+
+fn default_rate_limit_exempt_mods() -> bool {
+    true
+}
+
+fn default_cooldown_notice_interval_seconds() -> u64 {
+    crate::bot::commands::DEFAULT_COOLDOWN_NOTICE_INTERVAL_SECONDS
+}
+
+fn default_message_timeout_ms() -> u64 {
+    crate::bot::pipeline_metrics::DEFAULT_MESSAGE_TIMEOUT.as_millis() as u64
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -522,7 +997,8 @@ pub struct RateLimitConfig {
     pub cooldown_seconds: u64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Which bot features run on a platform; platforms without an entry get everything
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PlatformFeatures {
     pub moderation: bool,
     pub timers: bool,
@@ -531,6 +1007,18 @@ pub struct PlatformFeatures {
     pub giveaways: bool,
 }
 
+impl Default for PlatformFeatures {
+    fn default() -> Self {
+        Self {
+            moderation: true,
+            timers: true,
+            commands: true,
+            points: true,
+            giveaways: true,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WebhookConfig {
     pub name: String,
@@ -559,6 +1047,35 @@ pub struct PerformanceSettings {
     pub worker_threads: u8,
     pub batch_size: u32,
     pub monitoring_enabled: bool,
+    /// Moderation actions and responses sent at once; the rest wait their turn
+    #[serde(default = "default_max_concurrent_tasks")]
+    pub max_concurrent_tasks: usize,
+}
+
+fn default_max_concurrent_tasks() -> usize {
+    crate::bot::task_limiter::DEFAULT_MAX_CONCURRENT_TASKS
+}
+
+/// Fewer runtime workers than this and one busy task can stall the platform readers
+pub const MIN_WORKER_THREADS: usize = 2;
+
+impl PerformanceSettings {
+    /// Worker threads the runtime is built with; 0 means one per CPU
+    pub fn effective_worker_threads(&self) -> usize {
+        let configured = match self.worker_threads {
+            0 => num_cpus::get(),
+            threads => threads as usize,
+        };
+        configured.max(MIN_WORKER_THREADS)
+    }
+
+    /// Multi-threaded runtime sized by `worker_threads`. Pattern matching and
+    /// other CPU-bound filtering run on these workers, so this caps them too.
+    pub fn runtime_builder(&self) -> tokio::runtime::Builder {
+        let mut builder = tokio::runtime::Builder::new_multi_thread();
+        builder.worker_threads(self.effective_worker_threads()).enable_all();
+        builder
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -566,7 +1083,12 @@ pub struct SecuritySettings {
     pub encryption_enabled: bool,
     pub api_key_rotation_days: u32,
     pub max_failed_attempts: u8,
+    /// Addresses or CIDR ranges allowed to reach the dashboard; empty allows all
     pub ip_whitelist: Vec<String>,
+    /// Reverse proxies whose `X-Forwarded-For` header is believed
+    #[serde(default)]
+    pub trusted_proxies: Vec<String>,
+    /// Keep an append-only audit trail of moderation actions in the data directory
     pub audit_logging: bool,
 }
 
@@ -583,10 +1105,17 @@ impl ConfigValidator {
     }
 
     /// Validate filter configuration
-    pub fn validate_filter_config(&self, config: &FilterConfiguration) -> Result<()> {
+    pub fn validate_filter_config(&self, config: &FilterConfiguration) -> ConfigResult<()> {
         // Validate version format
         if config.version.is_empty() {
-            return Err(anyhow::anyhow!("Version cannot be empty"));
+            return Err(ConfigError::Validation("Version cannot be empty".to_string()));
+        }
+
+        for duplicate in Self::duplicate_filter_ids(config) {
+            match config.global_settings.duplicate_ids {
+                DuplicateIdPolicy::Reject => return Err(ConfigError::Validation(duplicate)),
+                DuplicateIdPolicy::Warn => warn!("{} - updates and removals by id will only reach one of them", duplicate),
+            }
         }
 
         // Validate blacklist filters
@@ -599,67 +1128,136 @@ impl ConfigValidator {
             self.validate_spam_filter(filter)?;
         }
 
+        // Validate allowlist entries
+        for entry in &config.allowlist.entries {
+            if entry.value.is_empty() {
+                return Err(ConfigError::Validation("Allowlist entry value cannot be empty".to_string()));
+            }
+            if entry.pattern_type == "regex" {
+                if let Err(e) = regex::Regex::new(&entry.value) {
+                    return Err(ConfigError::Validation(format!("Invalid allowlist regex '{}': {}", entry.value, e)));
+                }
+            }
+        }
+
         Ok(())
     }
 
-    fn validate_blacklist_filter(&self, filter: &EnhancedBlacklistFilter) -> Result<()> {
+    /// One message per id that's used by more than one blacklist or spam filter
+    pub fn duplicate_filter_ids(config: &FilterConfiguration) -> Vec<String> {
+        fn duplicates<'a>(kind: &str, ids: impl Iterator<Item = &'a str>) -> Vec<String> {
+            let mut seen = std::collections::HashSet::new();
+            let mut reported = std::collections::HashSet::new();
+            ids.filter(|id| !seen.insert(*id) && reported.insert(*id))
+                .map(|id| format!("Duplicate {} ID: {}", kind, id))
+                .collect()
+        }
+
+        let mut messages = duplicates("blacklist filter", config.blacklist_filters.iter().map(|f| f.id.as_str()));
+        messages.extend(duplicates("spam filter", config.spam_filters.iter().map(|f| f.id.as_str())));
+        messages
+    }
+
+    fn validate_blacklist_filter(&self, filter: &EnhancedBlacklistFilter) -> ConfigResult<()> {
         if filter.name.is_empty() {
-            return Err(anyhow::anyhow!("Filter name cannot be empty"));
+            return Err(ConfigError::Validation("Filter name cannot be empty".to_string()));
         }
 
         if filter.patterns.is_empty() {
-            return Err(anyhow::anyhow!("Filter must have at least one pattern"));
+            return Err(ConfigError::Validation("Filter must have at least one pattern".to_string()));
         }
 
-        for pattern in &filter.patterns {
+        for pattern in filter.patterns.iter().chain(&filter.except_patterns) {
             self.validate_pattern_definition(pattern)?;
         }
 
+        filter.schedule()?;
+
         Ok(())
     }
 
-    fn validate_spam_filter(&self, filter: &EnhancedSpamFilter) -> Result<()> {
+    fn validate_spam_filter(&self, filter: &EnhancedSpamFilter) -> ConfigResult<()> {
         if filter.name.is_empty() {
-            return Err(anyhow::anyhow!("Filter name cannot be empty"));
+            return Err(ConfigError::Validation("Filter name cannot be empty".to_string()));
         }
 
         if filter.filter_type.is_empty() {
-            return Err(anyhow::anyhow!("Filter type cannot be empty"));
+            return Err(ConfigError::Validation("Filter type cannot be empty".to_string()));
         }
 
         Ok(())
     }
 
-    fn validate_pattern_definition(&self, pattern: &PatternDefinition) -> Result<()> {
+    fn validate_pattern_definition(&self, pattern: &PatternDefinition) -> ConfigResult<()> {
         if pattern.value.is_empty() {
-            return Err(anyhow::anyhow!("Pattern value cannot be empty"));
+            return Err(ConfigError::Validation("Pattern value cannot be empty".to_string()));
         }
 
         if !(0.0..=1.0).contains(&pattern.weight) {
-            return Err(anyhow::anyhow!("Pattern weight must be between 0.0 and 1.0"));
+            return Err(ConfigError::Validation("Pattern weight must be between 0.0 and 1.0".to_string()));
         }
 
         // Validate regex patterns
         if pattern.pattern_type == "regex" {
             if let Err(e) = regex::Regex::new(&pattern.value) {
-                return Err(anyhow::anyhow!("Invalid regex pattern: {}", e));
+                return Err(ConfigError::Validation(format!("Invalid regex pattern: {}", e)));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Validate bot configuration
+    pub fn validate_bot_config(&self, config: &BotConfiguration) -> ConfigResult<()> {
+        for (level, multiplier) in &config.points.level_multipliers {
+            if !multiplier.is_finite() || *multiplier < 0.0 {
+                return Err(ConfigError::Validation(format!("Points multiplier for {:?} must be a non-negative number", level)));
             }
         }
 
+        if config.user_filter.allowlist_mode && config.user_filter.allowed_users.is_empty() {
+            return Err(ConfigError::Validation("User filter allowlist mode needs at least one allowed user".to_string()));
+        }
+
+        if config.privacy.redaction == (crate::bot::redaction::RedactionMode::Truncate { max_chars: 0 }) {
+            return Err(ConfigError::Validation("Redaction max_chars must be greater than 0".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Validate achievement configuration
+    pub fn validate_achievement_config(&self, config: &AchievementConfiguration) -> ConfigResult<()> {
+        let mut seen = std::collections::HashSet::new();
+        for definition in &config.achievements {
+            if definition.id.is_empty() {
+                return Err(ConfigError::Validation("Achievement ID cannot be empty".to_string()));
+            }
+            if !seen.insert(definition.id.as_str()) {
+                return Err(ConfigError::Validation(format!("Duplicate achievement ID: {}", definition.id)));
+            }
+            if definition.reward_points < 0 {
+                return Err(ConfigError::Validation(format!("Achievement '{}' has negative reward points", definition.id)));
+            }
+            definition.to_achievement()?;
+        }
         Ok(())
     }
 
     /// Validate pattern configuration
-    pub fn validate_pattern_config(&self, config: &PatternConfiguration) -> Result<()> {
+    pub fn validate_pattern_config(&self, config: &PatternConfiguration) -> ConfigResult<()> {
         for collection in config.pattern_collections.values() {
             self.validate_pattern_collection(collection)?;
         }
         Ok(())
     }
 
-    fn validate_pattern_collection(&self, collection: &PatternCollection) -> Result<()> {
+    fn validate_pattern_collection(&self, collection: &PatternCollection) -> ConfigResult<()> {
         if collection.name.is_empty() {
-            return Err(anyhow::anyhow!("Pattern collection name cannot be empty"));
+            return Err(ConfigError::Validation("Pattern collection name cannot be empty".to_string()));
+        }
+
+        if TimeoutFallback::parse(&collection.timeout_action).is_none() {
+            return Err(ConfigError::Validation(format!("Invalid timeout action '{}' (expected fail_open or fail_closed)", collection.timeout_action)));
         }
 
         for pattern in &collection.patterns {
@@ -669,17 +1267,17 @@ impl ConfigValidator {
         Ok(())
     }
 
-    fn validate_advanced_pattern_definition(&self, pattern: &AdvancedPatternDefinition) -> Result<()> {
+    fn validate_advanced_pattern_definition(&self, pattern: &AdvancedPatternDefinition) -> ConfigResult<()> {
         if pattern.name.is_empty() {
-            return Err(anyhow::anyhow!("Pattern name cannot be empty"));
+            return Err(ConfigError::Validation("Pattern name cannot be empty".to_string()));
         }
 
         if !(0.0..=1.0).contains(&pattern.weight) {
-            return Err(anyhow::anyhow!("Pattern weight must be between 0.0 and 1.0"));
+            return Err(ConfigError::Validation("Pattern weight must be between 0.0 and 1.0".to_string()));
         }
 
         if !(0.0..=1.0).contains(&pattern.min_confidence) {
-            return Err(anyhow::anyhow!("Min confidence must be between 0.0 and 1.0"));
+            return Err(ConfigError::Validation("Min confidence must be between 0.0 and 1.0".to_string()));
         }
 
         Ok(())
@@ -709,6 +1307,9 @@ impl Default for FilterConfiguration {
                 auto_optimization: false,
                 performance_monitoring: true,
                 debug_mode: false,
+                duplicate_ids: DuplicateIdPolicy::default(),
+                warning_dedup_seconds: default_warning_dedup_seconds(),
+                min_ai_action_confidence: 0.0,
             },
             categories: HashMap::new(),
             import_export: ImportExportSettings {
@@ -719,6 +1320,7 @@ impl Default for FilterConfiguration {
                 backup_retention_days: 30,
                 nightbot_compatibility: true,
             },
+            allowlist: AllowlistConfig::default(),
         }
     }
 }
@@ -737,6 +1339,7 @@ impl Default for PatternConfiguration {
                 cache_size_mb: 50,
                 performance_profiling: true,
                 auto_optimization: true,
+                min_message_length: 0,
             },
             ml_config: MLConfiguration {
                 enabled: true,
@@ -767,70 +1370,200 @@ impl ConfigurationManager {
             pattern_config: Arc::new(RwLock::new(PatternConfiguration::default())),
             timer_config: Arc::new(RwLock::new(TimerConfiguration::default())),
             bot_config: Arc::new(RwLock::new(BotConfiguration::default())),
+            achievement_config: Arc::new(RwLock::new(AchievementConfiguration::default())),
             watchers: Arc::new(RwLock::new(Vec::new())),
             change_notifier: tx,
             validator: Arc::new(ConfigValidator::new()),
             cache_enabled: true,
             last_reload: Arc::new(RwLock::new(std::time::Instant::now())),
+            read_only: Arc::new(AtomicBool::new(false)),
+            strict: Arc::new(AtomicBool::new(false)),
+            reload_metrics: Arc::new(RwLock::new(ReloadMetrics::default())),
+        }
+    }
+
+    /// Treat the configuration directory as read-only even if it is writable
+    pub fn with_read_only(self, read_only: bool) -> Self {
+        self.read_only.store(read_only, Ordering::Relaxed);
+        self
+    }
+
+    /// Whether saving configuration is disabled
+    pub fn is_read_only(&self) -> bool {
+        self.read_only.load(Ordering::Relaxed)
+    }
+
+    /// Choose what a configuration file that fails to parse or validate at startup means:
+    /// strict mode stops startup, otherwise (the default) its built-in defaults are used
+    /// until it's fixed. A broken hot-reload keeps the last good configuration either way.
+    pub fn with_strict_config(self, strict: bool) -> Self {
+        self.strict.store(strict, Ordering::Relaxed);
+        self
+    }
+
+    pub fn is_strict(&self) -> bool {
+        self.strict.load(Ordering::Relaxed)
+    }
+
+    fn ensure_writable(&self) -> ConfigResult<()> {
+        if self.is_read_only() {
+            return Err(ConfigError::ReadOnly(self.config_dir.display().to_string()));
+        }
+        Ok(())
+    }
+
+    fn is_read_only_error(e: &std::io::Error) -> bool {
+        matches!(e.kind(), std::io::ErrorKind::PermissionDenied | std::io::ErrorKind::ReadOnlyFilesystem)
+    }
+
+    /// Check that files can be created in the config directory by writing a probe file
+    async fn detect_read_only(&self) -> ConfigResult<bool> {
+        let probe = self.config_dir.join(".notabot_write_test");
+        match fs::write(&probe, b"").await {
+            Ok(()) => {
+                fs::remove_file(&probe).await.ok();
+                Ok(false)
+            }
+            Err(e) if Self::is_read_only_error(&e) => Ok(true),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Write a default configuration file, unless the directory is read-only
+    async fn write_default_file(&self, config_path: &Path, content: String) -> ConfigResult<()> {
+        if self.is_read_only() {
+            debug!("Not writing {} to the read-only configuration directory", config_path.display());
+            return Ok(());
+        }
+        fs::write(config_path, content).await?;
+        Ok(())
+    }
+
+    /// Read the performance settings from `bot.yaml` without an async runtime,
+    /// since they decide how the runtime is built. Falls back to the defaults.
+    pub fn read_performance_settings<P: AsRef<Path>>(config_dir: P) -> PerformanceSettings {
+        #[derive(Deserialize)]
+        struct PerformanceOnly {
+            performance: PerformanceSettings,
+        }
+
+        let config_path = config_dir.as_ref().join("bot.yaml");
+        match std::fs::read_to_string(&config_path) {
+            Ok(content) => match serde_yaml::from_str::<PerformanceOnly>(&content) {
+                Ok(config) => return config.performance,
+                Err(e) => warn!("Invalid performance settings in {}: {}", config_path.display(), e),
+            },
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => warn!("Failed to read {}: {}", config_path.display(), e),
+        }
+        BotConfiguration::default().performance
+    }
+
+    /// `core.strict_config` from bot.yaml, read before the manager is created
+    /// since it decides how the files (bot.yaml included) are loaded. A file that
+    /// can't be read means lenient.
+    pub fn read_strict_config<P: AsRef<Path>>(config_dir: P) -> bool {
+        #[derive(Deserialize)]
+        struct StrictOnly {
+            #[serde(default)]
+            strict_config: bool,
+        }
+        #[derive(Deserialize)]
+        struct CoreOnly {
+            core: StrictOnly,
         }
+
+        let config_path = config_dir.as_ref().join("bot.yaml");
+        match std::fs::read_to_string(&config_path) {
+            Ok(content) => match serde_yaml::from_str::<CoreOnly>(&content) {
+                Ok(config) => return config.core.strict_config,
+                Err(e) => warn!("Can't read strict_config from {}: {}", config_path.display(), e),
+            },
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => warn!("Failed to read {}: {}", config_path.display(), e),
+        }
+        false
     }
 
     /// Initialize configuration system
-    pub async fn initialize(&self) -> Result<()> {
+    pub async fn initialize(&self) -> ConfigResult<()> {
         // Create config directory if it doesn't exist
-        if !self.config_dir.exists() {
-            fs::create_dir_all(&self.config_dir).await?;
-            info!("Created configuration directory: {}", self.config_dir.display());
+        if !self.config_dir.exists() && !self.is_read_only() {
+            match fs::create_dir_all(&self.config_dir).await {
+                Ok(()) => info!("Created configuration directory: {}", self.config_dir.display()),
+                Err(e) if Self::is_read_only_error(&e) => self.read_only.store(true, Ordering::Relaxed),
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        if !self.is_read_only() && self.config_dir.exists() && self.detect_read_only().await? {
+            self.read_only.store(true, Ordering::Relaxed);
+        }
+        if self.is_read_only() {
+            warn!("Configuration directory {} is read-only: missing files fall back to built-in defaults \
+                   and configuration changes cannot be saved", self.config_dir.display());
         }
 
         // Load all configurations
         self.load_all_configs().await?;
 
-        // Setup file watchers for hot-reloading
-        self.setup_file_watchers().await?;
+        // Setup file watchers for hot-reloading (a directory that couldn't be created has nothing to watch)
+        if self.config_dir.exists() {
+            self.setup_file_watchers().await?;
+        }
 
         info!("Configuration manager initialized successfully");
         Ok(())
     }
 
     /// Load all configuration files
-    async fn load_all_configs(&self) -> Result<()> {
+    async fn load_all_configs(&self) -> ConfigResult<()> {
         let mut files_loaded = Vec::new();
+        let default_source = if self.is_read_only() { "in-memory default" } else { "created default" };
 
         // Load filter configuration
-        if let Err(e) = self.load_filter_config().await {
-            warn!("Failed to load filter config, using defaults: {}", e);
-            self.create_default_filter_config().await?;
-            files_loaded.push("filters.yaml (created default)".to_string());
-        } else {
+        let result = self.load_filter_config().await;
+        if self.check_startup_load("filters.yaml", result)? {
             files_loaded.push("filters.yaml".to_string());
+        } else {
+            self.create_default_filter_config().await?;
+            files_loaded.push(format!("filters.yaml ({})", default_source));
         }
 
         // Load pattern configuration
-        if let Err(e) = self.load_pattern_config().await {
-            warn!("Failed to load pattern config, using defaults: {}", e);
-            self.create_default_pattern_config().await?;
-            files_loaded.push("patterns.yaml (created default)".to_string());
-        } else {
+        let result = self.load_pattern_config().await;
+        if self.check_startup_load("patterns.yaml", result)? {
             files_loaded.push("patterns.yaml".to_string());
+        } else {
+            self.create_default_pattern_config().await?;
+            files_loaded.push(format!("patterns.yaml ({})", default_source));
         }
 
         // Load timer configuration
-        if let Err(e) = self.load_timer_config().await {
-            warn!("Failed to load timer config, using defaults: {}", e);
-            self.create_default_timer_config().await?;
-            files_loaded.push("timers.yaml (created default)".to_string());
-        } else {
+        let result = self.load_timer_config().await;
+        if self.check_startup_load("timers.yaml", result)? {
             files_loaded.push("timers.yaml".to_string());
+        } else {
+            self.create_default_timer_config().await?;
+            files_loaded.push(format!("timers.yaml ({})", default_source));
         }
 
         // Load bot configuration
-        if let Err(e) = self.load_bot_config().await {
-            warn!("Failed to load bot config, using defaults: {}", e);
+        let result = self.load_bot_config().await;
+        if self.check_startup_load("bot.yaml", result)? {
+            files_loaded.push("bot.yaml".to_string());
+        } else {
             self.create_default_bot_config().await?;
-            files_loaded.push("bot.yaml (created default)".to_string());
+            files_loaded.push(format!("bot.yaml ({})", default_source));
+        }
+
+        // Load achievement configuration
+        let result = self.load_achievement_config().await;
+        if self.check_startup_load("achievements.yaml", result)? {
+            files_loaded.push("achievements.yaml".to_string());
         } else {
-            files_loaded.push("bot.yaml".to_string());
+            self.create_default_achievement_config().await?;
+            files_loaded.push(format!("achievements.yaml ({})", default_source));
         }
 
         // Broadcast reload complete event
@@ -839,11 +1572,33 @@ impl ConfigurationManager {
         Ok(())
     }
 
+    /// Whether a file loaded at startup; false means the defaults should be used. A file
+    /// that exists but fails to parse or validate stops startup in strict mode, rather than
+    /// silently replacing its rules with the defaults.
+    fn check_startup_load(&self, filename: &str, result: ConfigResult<()>) -> ConfigResult<bool> {
+        match result {
+            Ok(()) => Ok(true),
+            Err(ConfigError::NotFound(_)) => {
+                info!("{} not found, using defaults", filename);
+                Ok(false)
+            }
+            Err(e) if self.is_strict() => {
+                error!("{} is invalid ({}); refusing to replace it with defaults while strict_config is on \
+                        (set core.strict_config: false in bot.yaml or STRICT_CONFIG=false to start anyway)", filename, e);
+                Err(e)
+            }
+            Err(e) => {
+                warn!("Failed to load {}, using defaults: {}", filename, e);
+                Ok(false)
+            }
+        }
+    }
+
     /// Load filter configuration from file
-    async fn load_filter_config(&self) -> Result<()> {
+    async fn load_filter_config(&self) -> ConfigResult<()> {
         let config_path = self.config_dir.join("filters.yaml");
         if !config_path.exists() {
-            return Err(anyhow::anyhow!("Filter config file not found"));
+            return Err(ConfigError::NotFound(config_path.display().to_string()));
         }
 
         let content = fs::read_to_string(&config_path).await?;
@@ -860,10 +1615,10 @@ impl ConfigurationManager {
     }
 
     /// Load pattern configuration from file
-    async fn load_pattern_config(&self) -> Result<()> {
+    async fn load_pattern_config(&self) -> ConfigResult<()> {
         let config_path = self.config_dir.join("patterns.yaml");
         if !config_path.exists() {
-            return Err(anyhow::anyhow!("Pattern config file not found"));
+            return Err(ConfigError::NotFound(config_path.display().to_string()));
         }
 
         let content = fs::read_to_string(&config_path).await?;
@@ -880,10 +1635,10 @@ impl ConfigurationManager {
     }
 
     /// Load timer configuration from file
-    async fn load_timer_config(&self) -> Result<()> {
+    async fn load_timer_config(&self) -> ConfigResult<()> {
         let config_path = self.config_dir.join("timers.yaml");
         if !config_path.exists() {
-            return Err(anyhow::anyhow!("Timer config file not found"));
+            return Err(ConfigError::NotFound(config_path.display().to_string()));
         }
 
         let content = fs::read_to_string(&config_path).await?;
@@ -897,15 +1652,18 @@ impl ConfigurationManager {
     }
 
     /// Load bot configuration from file
-    async fn load_bot_config(&self) -> Result<()> {
+    async fn load_bot_config(&self) -> ConfigResult<()> {
         let config_path = self.config_dir.join("bot.yaml");
         if !config_path.exists() {
-            return Err(anyhow::anyhow!("Bot config file not found"));
+            return Err(ConfigError::NotFound(config_path.display().to_string()));
         }
 
         let content = fs::read_to_string(&config_path).await?;
         let config: BotConfiguration = serde_yaml::from_str(&content)?;
 
+        // Validate configuration
+        self.validator.validate_bot_config(&config)?;
+
         // Update cached configuration
         *self.bot_config.write().await = config;
 
@@ -913,14 +1671,47 @@ impl ConfigurationManager {
         Ok(())
     }
 
-    /// Create default filter configuration file
-    async fn create_default_filter_config(&self) -> Result<()> {
-        let mut config = FilterConfiguration::default();
-        
-        // Add comprehensive default filters
-        config.blacklist_filters = vec![
-            EnhancedBlacklistFilter {
-                id: "crypto_spam".to_string(),
+    /// Load achievement configuration from file
+    async fn load_achievement_config(&self) -> ConfigResult<()> {
+        let config_path = self.config_dir.join("achievements.yaml");
+        if !config_path.exists() {
+            return Err(ConfigError::NotFound(config_path.display().to_string()));
+        }
+
+        let content = fs::read_to_string(&config_path).await?;
+        let config: AchievementConfiguration = serde_yaml::from_str(&content)?;
+
+        // Validate configuration
+        self.validator.validate_achievement_config(&config)?;
+
+        // Update cached configuration
+        *self.achievement_config.write().await = config;
+
+        debug!("Loaded achievement configuration from {}", config_path.display());
+        Ok(())
+    }
+
+    /// Create default achievement configuration file
+    async fn create_default_achievement_config(&self) -> ConfigResult<()> {
+        let config = AchievementConfiguration::default();
+
+        let config_path = self.config_dir.join("achievements.yaml");
+        let content = serde_yaml::to_string(&config)?;
+        self.write_default_file(&config_path, content).await?;
+
+        *self.achievement_config.write().await = config;
+        info!("Using default achievement configuration: {}", config_path.display());
+        Ok(())
+    }
+
+    /// Create default filter configuration file
+    async fn create_default_filter_config(&self) -> ConfigResult<()> {
+        let mut config = FilterConfiguration::default();
+        
+        // Add comprehensive default filters
+        config.blacklist_filters = vec![
+            EnhancedBlacklistFilter {
+                id: "crypto_spam".to_string(),
                 name: "Cryptocurrency Spam Detection".to_string(),
                 enabled: true,
                 description: Some("Detects crypto scams and investment spam".to_string()),
@@ -933,6 +1724,7 @@ impl ConfigurationManager {
                         weight: 1.0,
                         description: Some("Free money promises".to_string()),
                         enabled: true,
+                        anchor: WildcardAnchor::default(),
                     },
                     PatternDefinition {
                         pattern_type: "regex".to_string(),
@@ -940,6 +1732,7 @@ impl ConfigurationManager {
                         weight: 1.0,
                         description: Some("Guaranteed profit claims".to_string()),
                         enabled: true,
+                        anchor: WildcardAnchor::default(),
                     },
                     PatternDefinition {
                         pattern_type: "fuzzy".to_string(),
@@ -947,13 +1740,18 @@ impl ConfigurationManager {
                         weight: 0.8,
                         description: Some("Crypto investment variations".to_string()),
                         enabled: true,
+                        anchor: WildcardAnchor::default(),
                     },
                 ],
+                except_patterns: Vec::new(),
                 case_sensitive: false,
                 whole_words_only: false,
                 regex_flags: Some("i".to_string()),
                 timeout_seconds: Some(1800),
                 escalation_enabled: true,
+                action: None,
+                grace_seconds: None,
+                log_level: None,
                 custom_message: Some("🚨 Crypto spam detected. Appeal with !appeal if this was a mistake.".to_string()),
                 silent_mode: false,
                 exemption_level: "Regular".to_string(),
@@ -984,6 +1782,7 @@ impl ConfigurationManager {
                         weight: 1.0,
                         description: Some("Follow-for-follow requests".to_string()),
                         enabled: true,
+                        anchor: WildcardAnchor::default(),
                     },
                     PatternDefinition {
                         pattern_type: "regex".to_string(),
@@ -991,13 +1790,18 @@ impl ConfigurationManager {
                         weight: 1.0,
                         description: Some("F4F and S4S patterns".to_string()),
                         enabled: true,
+                        anchor: WildcardAnchor::default(),
                     },
                 ],
+                except_patterns: Vec::new(),
                 case_sensitive: false,
                 whole_words_only: false,
                 regex_flags: Some("i".to_string()),
                 timeout_seconds: Some(600),
                 escalation_enabled: true,
+                action: None,
+                grace_seconds: None,
+                log_level: None,
                 custom_message: Some("Please engage naturally with our community.".to_string()),
                 silent_mode: false,
                 exemption_level: "Subscriber".to_string(),
@@ -1028,6 +1832,9 @@ impl ConfigurationManager {
                 filter_type: "ExcessiveCaps".to_string(),
                 parameters: serde_json::json!({"max_percentage": 60}),
                 timeout_seconds: 300,
+                action: None,
+                grace_seconds: None,
+                log_level: None,
                 escalation: EscalationConfig {
                     enabled: true,
                     first_offense_action: "warn".to_string(),
@@ -1083,15 +1890,15 @@ impl ConfigurationManager {
 
         let config_path = self.config_dir.join("filters.yaml");
         let content = serde_yaml::to_string(&config)?;
-        fs::write(&config_path, content).await?;
+        self.write_default_file(&config_path, content).await?;
 
         *self.filter_config.write().await = config;
-        info!("Created default filter configuration: {}", config_path.display());
+        info!("Using default filter configuration: {}", config_path.display());
         Ok(())
     }
 
     /// Create default pattern configuration file
-    async fn create_default_pattern_config(&self) -> Result<()> {
+    async fn create_default_pattern_config(&self) -> ConfigResult<()> {
         let mut config = PatternConfiguration::default();
 
         // Add default pattern collections
@@ -1115,6 +1922,8 @@ impl ConfigurationManager {
                     min_confidence: 0.7,
                     learning_rate: 0.1,
                     tags: vec!["spam".to_string(), "fuzzy".to_string()],
+                    min_message_length: None,
+                    reputation_scaling: None,
                 },
                 AdvancedPatternDefinition {
                     id: "leetspeak_spam".to_string(),
@@ -1128,6 +1937,8 @@ impl ConfigurationManager {
                     min_confidence: 0.8,
                     learning_rate: 0.05,
                     tags: vec!["spam".to_string(), "leetspeak".to_string()],
+                    min_message_length: None,
+                    reputation_scaling: None,
                 },
                 AdvancedPatternDefinition {
                     id: "zalgo_detection".to_string(),
@@ -1139,6 +1950,8 @@ impl ConfigurationManager {
                     min_confidence: 0.9,
                     learning_rate: 0.01,
                     tags: vec!["zalgo".to_string(), "corruption".to_string()],
+                    min_message_length: None,
+                    reputation_scaling: None,
                 },
             ],
             confidence_threshold: 0.75,
@@ -1147,21 +1960,22 @@ impl ConfigurationManager {
             max_processing_time_ms: 100,
             cache_results: true,
             parallel_processing: true,
+            timeout_action: "fail_open".to_string(),
         };
 
         config.pattern_collections.insert("spam_detection".to_string(), spam_detection);
 
         let config_path = self.config_dir.join("patterns.yaml");
         let content = serde_yaml::to_string(&config)?;
-        fs::write(&config_path, content).await?;
+        self.write_default_file(&config_path, content).await?;
 
         *self.pattern_config.write().await = config;
-        info!("Created default pattern configuration: {}", config_path.display());
+        info!("Using default pattern configuration: {}", config_path.display());
         Ok(())
     }
 
     /// Create default timer configuration file
-    async fn create_default_timer_config(&self) -> Result<()> {
+    async fn create_default_timer_config(&self) -> ConfigResult<()> {
         let mut config = TimerConfiguration::default();
 
         config.timers = vec![
@@ -1240,15 +2054,15 @@ impl ConfigurationManager {
 
         let config_path = self.config_dir.join("timers.yaml");
         let content = serde_yaml::to_string(&config)?;
-        fs::write(&config_path, content).await?;
+        self.write_default_file(&config_path, content).await?;
 
         *self.timer_config.write().await = config;
-        info!("Created default timer configuration: {}", config_path.display());
+        info!("Using default timer configuration: {}", config_path.display());
         Ok(())
     }
 
     /// Create default bot configuration file
-    async fn create_default_bot_config(&self) -> Result<()> {
+    async fn create_default_bot_config(&self) -> ConfigResult<()> {
         let mut config = BotConfiguration::default();
 
         config.core = CoreBotSettings {
@@ -1257,8 +2071,15 @@ impl ConfigurationManager {
             response_delay_ms: 100,
             max_message_length: 500,
             rate_limit_per_minute: 20,
+            rate_limit_exempt_mods: default_rate_limit_exempt_mods(),
+            cooldown_notice: None,
+            cooldown_notice_interval_seconds: default_cooldown_notice_interval_seconds(),
             debug_mode: false,
             log_level: "info".to_string(),
+            dry_run: false,
+            bot_accounts: HashMap::new(),
+            message_timeout_ms: default_message_timeout_ms(),
+            strict_config: false,
         };
 
         // Platform configurations
@@ -1324,6 +2145,7 @@ impl ConfigurationManager {
             worker_threads: 4,
             batch_size: 100,
             monitoring_enabled: true,
+            max_concurrent_tasks: default_max_concurrent_tasks(),
         };
 
         config.security = SecuritySettings {
@@ -1331,30 +2153,25 @@ impl ConfigurationManager {
             api_key_rotation_days: 90,
             max_failed_attempts: 5,
             ip_whitelist: Vec::new(),
+            trusted_proxies: Vec::new(),
             audit_logging: true,
         };
 
         let config_path = self.config_dir.join("bot.yaml");
         let content = serde_yaml::to_string(&config)?;
-        fs::write(&config_path, content).await?;
+        self.write_default_file(&config_path, content).await?;
 
         *self.bot_config.write().await = config;
-        info!("Created default bot configuration: {}", config_path.display());
+        info!("Using default bot configuration: {}", config_path.display());
         Ok(())
     }
 
     /// Setup file watchers for hot-reloading
-    async fn setup_file_watchers(&self) -> Result<()> {
+    async fn setup_file_watchers(&self) -> ConfigResult<()> {
         use notify::{EventKind, RecursiveMode, Watcher};
         
         let config_dir = self.config_dir.clone();
-        let change_notifier = self.change_notifier.clone();
-        let filter_config = self.filter_config.clone();
-        let pattern_config = self.pattern_config.clone();
-        let timer_config = self.timer_config.clone();
-        let bot_config = self.bot_config.clone();
-        let validator = self.validator.clone();
-        let last_reload = self.last_reload.clone();
+        let manager = self.clone();
 
         // Create file watcher
         let (tx, mut rx) = tokio::sync::mpsc::channel(100);
@@ -1376,80 +2193,13 @@ impl ConfigurationManager {
         // Spawn background task to handle file changes
         tokio::spawn(async move {
             while let Some(event) = rx.recv().await {
-                // Debounce rapid file changes
-                {
-                    let now = std::time::Instant::now();
-                    let last = *last_reload.read().await;
-                    if now.duration_since(last) < Duration::from_millis(500) {
-                        continue; // Skip rapid consecutive changes
-                    }
-                    *last_reload.write().await = now;
-                }
-
                 if let EventKind::Modify(_) = event.kind {
+                    if manager.debounce(std::time::Instant::now()).await {
+                        continue;
+                    }
                     for path in event.paths {
                         if let Some(filename) = path.file_name().and_then(|n| n.to_str()) {
-                            match filename {
-                                "filters.yaml" | "filters.yml" => {
-                                    info!("Filter configuration file changed, reloading...");
-                                    if let Err(e) = Self::reload_filter_config(&path, &filter_config, &validator).await {
-                                        error!("Failed to reload filter config: {}", e);
-                                        let _ = change_notifier.send(ConfigChangeEvent::ValidationError {
-                                            file: filename.to_string(),
-                                            error: e.to_string(),
-                                        });
-                                    } else {
-                                        let _ = change_notifier.send(ConfigChangeEvent::FiltersUpdated {
-                                            file: filename.to_string(),
-                                        });
-                                    }
-                                }
-                                "patterns.yaml" | "patterns.yml" => {
-                                    info!("Pattern configuration file changed, reloading...");
-                                    if let Err(e) = Self::reload_pattern_config(&path, &pattern_config, &validator).await {
-                                        error!("Failed to reload pattern config: {}", e);
-                                        let _ = change_notifier.send(ConfigChangeEvent::ValidationError {
-                                            file: filename.to_string(),
-                                            error: e.to_string(),
-                                        });
-                                    } else {
-                                        let _ = change_notifier.send(ConfigChangeEvent::PatternsUpdated {
-                                            file: filename.to_string(),
-                                        });
-                                    }
-                                }
-                                "timers.yaml" | "timers.yml" => {
-                                    info!("Timer configuration file changed, reloading...");
-                                    if let Err(e) = Self::reload_timer_config(&path, &timer_config).await {
-                                        error!("Failed to reload timer config: {}", e);
-                                        let _ = change_notifier.send(ConfigChangeEvent::ValidationError {
-                                            file: filename.to_string(),
-                                            error: e.to_string(),
-                                        });
-                                    } else {
-                                        let _ = change_notifier.send(ConfigChangeEvent::TimersUpdated {
-                                            file: filename.to_string(),
-                                        });
-                                    }
-                                }
-                                "bot.yaml" | "bot.yml" => {
-                                    info!("Bot configuration file changed, reloading...");
-                                    if let Err(e) = Self::reload_bot_config(&path, &bot_config).await {
-                                        error!("Failed to reload bot config: {}", e);
-                                        let _ = change_notifier.send(ConfigChangeEvent::ValidationError {
-                                            file: filename.to_string(),
-                                            error: e.to_string(),
-                                        });
-                                    } else {
-                                        let _ = change_notifier.send(ConfigChangeEvent::BotConfigUpdated {
-                                            file: filename.to_string(),
-                                        });
-                                    }
-                                }
-                                _ => {
-                                    debug!("Ignoring change to non-config file: {}", filename);
-                                }
-                            }
+                            manager.handle_file_change(&path, filename).await;
                         }
                     }
                 }
@@ -1460,12 +2210,89 @@ impl ConfigurationManager {
         Ok(())
     }
 
+    /// Whether a change arriving at `now` follows the last handled one too closely to act on
+    async fn debounce(&self, now: std::time::Instant) -> bool {
+        let mut last = self.last_reload.write().await;
+        if now.saturating_duration_since(*last) < RELOAD_DEBOUNCE {
+            self.reload_metrics.write().await.debounce_skipped += 1;
+            return true;
+        }
+        *last = now;
+        false
+    }
+
+    /// Hot-reload counts, per config type, since startup
+    pub async fn reload_metrics(&self) -> ReloadMetrics {
+        self.reload_metrics.read().await.clone()
+    }
+
+    /// Reload a changed configuration file and announce the result
+    async fn handle_file_change(&self, path: &Path, filename: &str) {
+        match filename {
+            "filters.yaml" | "filters.yml" => {
+                info!("Filter configuration file changed, reloading...");
+                let result = Self::reload_filter_config(path, &self.filter_config, &self.validator).await;
+                self.finish_reload(filename, result,
+                                   ConfigChangeEvent::FiltersUpdated { file: filename.to_string() }).await;
+            }
+            "patterns.yaml" | "patterns.yml" => {
+                info!("Pattern configuration file changed, reloading...");
+                let result = Self::reload_pattern_config(path, &self.pattern_config, &self.validator).await;
+                self.finish_reload(filename, result,
+                                   ConfigChangeEvent::PatternsUpdated { file: filename.to_string() }).await;
+            }
+            "timers.yaml" | "timers.yml" => {
+                info!("Timer configuration file changed, reloading...");
+                let result = Self::reload_timer_config(path, &self.timer_config).await;
+                self.finish_reload(filename, result,
+                                   ConfigChangeEvent::TimersUpdated { file: filename.to_string() }).await;
+            }
+            "bot.yaml" | "bot.yml" => {
+                info!("Bot configuration file changed, reloading...");
+                let result = Self::reload_bot_config(path, &self.bot_config, &self.validator).await;
+                self.finish_reload(filename, result,
+                                   ConfigChangeEvent::BotConfigUpdated { file: filename.to_string() }).await;
+            }
+            "achievements.yaml" | "achievements.yml" => {
+                info!("Achievement configuration file changed, reloading...");
+                let result = Self::reload_achievement_config(path, &self.achievement_config, &self.validator).await;
+                self.finish_reload(filename, result,
+                                   ConfigChangeEvent::AchievementsUpdated { file: filename.to_string() }).await;
+            }
+            _ => {
+                debug!("Ignoring change to non-config file: {}", filename);
+            }
+        }
+    }
+
+    /// Announce a hot-reload. A file that failed to parse or validate keeps the last good
+    /// configuration until it's fixed, so a bad save never wipes the rules in use.
+    async fn finish_reload(
+        &self,
+        filename: &str,
+        result: ConfigResult<()>,
+        updated: ConfigChangeEvent,
+    ) {
+        self.reload_metrics.write().await.record(filename, result.as_ref().err().map(ToString::to_string));
+        let Err(e) = result else {
+            let _ = self.change_notifier.send(updated);
+            return;
+        };
+
+        let _ = self.change_notifier.send(ConfigChangeEvent::ValidationError {
+            file: filename.to_string(),
+            error: e.to_string(),
+        });
+        error!("CONFIG RELOAD REJECTED: {} is invalid ({}); keeping the previous configuration until it is fixed",
+               filename, e);
+    }
+
     /// Reload filter configuration from file
     async fn reload_filter_config(
         path: &Path,
         filter_config: &Arc<RwLock<FilterConfiguration>>,
         validator: &Arc<ConfigValidator>,
-    ) -> Result<()> {
+    ) -> ConfigResult<()> {
         let content = fs::read_to_string(path).await?;
         let config: FilterConfiguration = serde_yaml::from_str(&content)?;
         
@@ -1482,7 +2309,7 @@ impl ConfigurationManager {
         path: &Path,
         pattern_config: &Arc<RwLock<PatternConfiguration>>,
         validator: &Arc<ConfigValidator>,
-    ) -> Result<()> {
+    ) -> ConfigResult<()> {
         let content = fs::read_to_string(path).await?;
         let config: PatternConfiguration = serde_yaml::from_str(&content)?;
         
@@ -1498,7 +2325,7 @@ impl ConfigurationManager {
     async fn reload_timer_config(
         path: &Path,
         timer_config: &Arc<RwLock<TimerConfiguration>>,
-    ) -> Result<()> {
+    ) -> ConfigResult<()> {
         let content = fs::read_to_string(path).await?;
         let config: TimerConfiguration = serde_yaml::from_str(&content)?;
         
@@ -1511,15 +2338,36 @@ impl ConfigurationManager {
     async fn reload_bot_config(
         path: &Path,
         bot_config: &Arc<RwLock<BotConfiguration>>,
-    ) -> Result<()> {
+        validator: &Arc<ConfigValidator>,
+    ) -> ConfigResult<()> {
         let content = fs::read_to_string(path).await?;
         let config: BotConfiguration = serde_yaml::from_str(&content)?;
+
+        // Validate before applying
+        validator.validate_bot_config(&config)?;
         
         *bot_config.write().await = config;
         debug!("Reloaded bot configuration from {}", path.display());
         Ok(())
     }
 
+    /// Reload achievement configuration from file
+    async fn reload_achievement_config(
+        path: &Path,
+        achievement_config: &Arc<RwLock<AchievementConfiguration>>,
+        validator: &Arc<ConfigValidator>,
+    ) -> ConfigResult<()> {
+        let content = fs::read_to_string(path).await?;
+        let config: AchievementConfiguration = serde_yaml::from_str(&content)?;
+
+        // Validate before applying
+        validator.validate_achievement_config(&config)?;
+
+        *achievement_config.write().await = config;
+        debug!("Reloaded achievement configuration from {}", path.display());
+        Ok(())
+    }
+
     /// Get current filter configuration
     pub async fn get_filter_config(&self) -> FilterConfiguration {
         self.filter_config.read().await.clone()
@@ -1535,6 +2383,11 @@ impl ConfigurationManager {
         self.timer_config.read().await.clone()
     }
 
+    /// Get current achievement configuration
+    pub async fn get_achievement_config(&self) -> AchievementConfiguration {
+        self.achievement_config.read().await.clone()
+    }
+
     /// Get current bot configuration
     pub async fn get_bot_config(&self) -> BotConfiguration {
         self.bot_config.read().await.clone()
@@ -1546,7 +2399,8 @@ impl ConfigurationManager {
     }
 
     /// Save filter configuration to file
-    pub async fn save_filter_config(&self, config: FilterConfiguration) -> Result<()> {
+    pub async fn save_filter_config(&self, config: FilterConfiguration) -> ConfigResult<()> {
+        self.ensure_writable()?;
         // Validate before saving
         self.validator.validate_filter_config(&config)?;
         
@@ -1560,7 +2414,8 @@ impl ConfigurationManager {
     }
 
     /// Save pattern configuration to file
-    pub async fn save_pattern_config(&self, config: PatternConfiguration) -> Result<()> {
+    pub async fn save_pattern_config(&self, config: PatternConfiguration) -> ConfigResult<()> {
+        self.ensure_writable()?;
         // Validate before saving
         self.validator.validate_pattern_config(&config)?;
         
@@ -1573,8 +2428,38 @@ impl ConfigurationManager {
         Ok(())
     }
 
+    /// Save bot configuration to file
+    pub async fn save_bot_config(&self, config: BotConfiguration) -> ConfigResult<()> {
+        self.ensure_writable()?;
+        // Validate before saving
+        self.validator.validate_bot_config(&config)?;
+
+        let config_path = self.config_dir.join("bot.yaml");
+        let content = serde_yaml::to_string(&config)?;
+        fs::write(&config_path, content).await?;
+
+        *self.bot_config.write().await = config;
+        info!("Saved bot configuration to {}", config_path.display());
+        Ok(())
+    }
+
+    /// Save achievement configuration to file
+    pub async fn save_achievement_config(&self, config: AchievementConfiguration) -> ConfigResult<()> {
+        self.ensure_writable()?;
+        // Validate before saving
+        self.validator.validate_achievement_config(&config)?;
+
+        let config_path = self.config_dir.join("achievements.yaml");
+        let content = serde_yaml::to_string(&config)?;
+        fs::write(&config_path, content).await?;
+
+        *self.achievement_config.write().await = config;
+        info!("Saved achievement configuration to {}", config_path.display());
+        Ok(())
+    }
+
     /// Update a specific filter by ID
-    pub async fn update_filter(&self, filter_id: &str, updated_filter: EnhancedBlacklistFilter) -> Result<()> {
+    pub async fn update_filter(&self, filter_id: &str, updated_filter: EnhancedBlacklistFilter) -> ConfigResult<()> {
         let mut config = self.get_filter_config().await;
         
         if let Some(filter) = config.blacklist_filters.iter_mut().find(|f| f.id == filter_id) {
@@ -1582,19 +2467,19 @@ impl ConfigurationManager {
             self.save_filter_config(config).await?;
             info!("Updated filter: {}", filter_id);
         } else {
-            return Err(anyhow::anyhow!("Filter not found: {}", filter_id));
+            return Err(ConfigError::NotFound(format!("filter '{}'", filter_id)));
         }
         
         Ok(())
     }
 
     /// Add a new filter
-    pub async fn add_filter(&self, filter: EnhancedBlacklistFilter) -> Result<()> {
+    pub async fn add_filter(&self, filter: EnhancedBlacklistFilter) -> ConfigResult<()> {
         let mut config = self.get_filter_config().await;
         
         // Check for duplicate IDs
         if config.blacklist_filters.iter().any(|f| f.id == filter.id) {
-            return Err(anyhow::anyhow!("Filter with ID '{}' already exists", filter.id));
+            return Err(ConfigError::AlreadyExists(format!("filter '{}'", filter.id)));
         }
         
         let filter_id = filter.id.clone(); // Clone the ID before moving
@@ -1605,14 +2490,14 @@ impl ConfigurationManager {
     }
 
     /// Remove a filter by ID
-    pub async fn remove_filter(&self, filter_id: &str) -> Result<()> {
+    pub async fn remove_filter(&self, filter_id: &str) -> ConfigResult<()> {
         let mut config = self.get_filter_config().await;
         let initial_len = config.blacklist_filters.len();
         
         config.blacklist_filters.retain(|f| f.id != filter_id);
         
         if config.blacklist_filters.len() == initial_len {
-            return Err(anyhow::anyhow!("Filter not found: {}", filter_id));
+            return Err(ConfigError::NotFound(format!("filter '{}'", filter_id)));
         }
         
         self.save_filter_config(config).await?;
@@ -1621,7 +2506,7 @@ impl ConfigurationManager {
     }
 
     /// Toggle filter enabled status
-    pub async fn toggle_filter(&self, filter_id: &str, enabled: bool) -> Result<()> {
+    pub async fn toggle_filter(&self, filter_id: &str, enabled: bool) -> ConfigResult<()> {
         let mut config = self.get_filter_config().await;
         
         if let Some(filter) = config.blacklist_filters.iter_mut().find(|f| f.id == filter_id) {
@@ -1629,7 +2514,7 @@ impl ConfigurationManager {
             self.save_filter_config(config).await?;
             info!("Toggled filter '{}' to {}", filter_id, if enabled { "enabled" } else { "disabled" });
         } else {
-            return Err(anyhow::anyhow!("Filter not found: {}", filter_id));
+            return Err(ConfigError::NotFound(format!("filter '{}'", filter_id)));
         }
         
         Ok(())
@@ -1651,8 +2536,55 @@ impl ConfigurationManager {
             .collect()
     }
 
+    /// Search filters by id, name, pattern or tag (case-insensitive substring match).
+    /// `tag:<name>` matches only filters carrying that exact tag.
+    pub async fn find_filters(&self, query: &str) -> Vec<FilterSearchMatch> {
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            return Vec::new();
+        }
+        let config = self.get_filter_config().await;
+        let mut matches = Vec::new();
+
+        if let Some(tag) = query.strip_prefix("tag:").map(str::trim) {
+            for filter in &config.blacklist_filters {
+                if let Some(found) = filter.tags.iter().find(|t| t.to_lowercase() == tag) {
+                    matches.push(FilterSearchMatch::blacklist(filter, FilterSearchField::Tag, found));
+                }
+            }
+            return matches;
+        }
+
+        let hit = |value: &str| value.to_lowercase().contains(&query);
+        for filter in &config.blacklist_filters {
+            let found = if hit(&filter.id) || hit(&filter.name) {
+                Some((FilterSearchField::Name, filter.name.as_str()))
+            } else if let Some(pattern) = filter.patterns.iter().find(|p| hit(&p.value)) {
+                Some((FilterSearchField::Pattern, pattern.value.as_str()))
+            } else {
+                filter.tags.iter().find(|t| hit(t)).map(|tag| (FilterSearchField::Tag, tag.as_str()))
+            };
+            if let Some((field, value)) = found {
+                matches.push(FilterSearchMatch::blacklist(filter, field, value));
+            }
+        }
+        for filter in &config.spam_filters {
+            if hit(&filter.id) || hit(&filter.name) {
+                matches.push(FilterSearchMatch {
+                    id: filter.id.clone(),
+                    name: filter.name.clone(),
+                    kind: FilterKind::Spam,
+                    enabled: filter.enabled,
+                    field: FilterSearchField::Name,
+                    matched: filter.name.clone(),
+                });
+            }
+        }
+        matches
+    }
+
     /// Export configuration to different formats
-    pub async fn export_config(&self, format: &str, output_path: &Path) -> Result<()> {
+    pub async fn export_config(&self, format: &str, output_path: &Path) -> ConfigResult<()> {
         match format.to_lowercase().as_str() {
             "json" => {
                 let config = self.get_filter_config().await;
@@ -1672,7 +2604,7 @@ impl ConfigurationManager {
                 fs::write(output_path, content).await?;
             }
             _ => {
-                return Err(anyhow::anyhow!("Unsupported export format: {}", format));
+                return Err(ConfigError::UnsupportedFormat(format!("{} (export)", format)));
             }
         }
 
@@ -1681,7 +2613,7 @@ impl ConfigurationManager {
     }
 
     /// Convert configuration to NightBot format for compatibility
-    async fn convert_to_nightbot_format(&self, config: &FilterConfiguration) -> Result<serde_json::Value> {
+    async fn convert_to_nightbot_format(&self, config: &FilterConfiguration) -> ConfigResult<serde_json::Value> {
         let mut nightbot_filters = Vec::new();
 
         for filter in &config.blacklist_filters {
@@ -1722,7 +2654,7 @@ impl ConfigurationManager {
     }
 
     /// Import configuration from external source
-    pub async fn import_config(&self, format: &str, input_path: &Path) -> Result<ImportResult> {
+    pub async fn import_config(&self, format: &str, input_path: &Path) -> ConfigResult<ImportResult> {
         let content = fs::read_to_string(input_path).await?;
         
         match format.to_lowercase().as_str() {
@@ -1741,8 +2673,14 @@ impl ConfigurationManager {
                 self.validator.validate_filter_config(&imported_config)?;
                 self.save_filter_config(imported_config).await?;
             }
+            "csv" => {
+                let result = self.import_csv_blacklist(&content).await?;
+                info!("Imported {} blacklist patterns from {} ({} bad rows)",
+                      result.imported_count, input_path.display(), result.errors.len());
+                return Ok(result);
+            }
             _ => {
-                return Err(anyhow::anyhow!("Unsupported import format: {}", format));
+                return Err(ConfigError::UnsupportedFormat(format!("{} (import)", format)));
             }
         }
 
@@ -1754,8 +2692,29 @@ impl ConfigurationManager {
         })
     }
 
+    /// Import blacklist patterns from CSV into the current filter configuration
+    ///
+    /// Rows are grouped into one `csv_<category>` filter per category; re-importing
+    /// replaces those filters. Bad rows are skipped and reported in `errors`.
+    pub async fn import_csv_blacklist(&self, content: &str) -> ConfigResult<ImportResult> {
+        let (filters, result) = parse_csv_blacklist(content, &self.validator);
+
+        if !filters.is_empty() {
+            let mut config = self.get_filter_config().await;
+            for filter in filters {
+                match config.blacklist_filters.iter_mut().find(|f| f.id == filter.id) {
+                    Some(existing) => *existing = filter,
+                    None => config.blacklist_filters.push(filter),
+                }
+            }
+            self.save_filter_config(config).await?;
+        }
+
+        Ok(result)
+    }
+
     /// Convert from NightBot format
-    async fn convert_from_nightbot_format(&self, content: &str) -> Result<FilterConfiguration> {    
+    async fn convert_from_nightbot_format(&self, content: &str) -> ConfigResult<FilterConfiguration> {    
         let nightbot_data: serde_json::Value = serde_json::from_str(content)?;
         let mut config = FilterConfiguration::default();
 
@@ -1784,6 +2743,7 @@ impl ConfigurationManager {
                                     weight: 1.0,
                                     description: None,
                                     enabled: true,
+                                    anchor: WildcardAnchor::default(),
                                 }
                             } else if pattern_str.contains('*') {
                                 PatternDefinition {
@@ -1792,6 +2752,7 @@ impl ConfigurationManager {
                                     weight: 1.0,
                                     description: None,
                                     enabled: true,
+                                    anchor: WildcardAnchor::default(),
                                 }
                             } else {
                                 PatternDefinition {
@@ -1800,6 +2761,7 @@ impl ConfigurationManager {
                                     weight: 1.0,
                                     description: None,
                                     enabled: true,
+                                    anchor: WildcardAnchor::default(),
                                 }
                             }
                         })
@@ -1813,12 +2775,16 @@ impl ConfigurationManager {
                         category: "imported".to_string(),
                         priority: 5,
                         patterns,
+                        except_patterns: Vec::new(),
                         case_sensitive: false,
                         whole_words_only: false,
                         regex_flags: Some("i".to_string()),
                         timeout_seconds: filter_obj.get("timeout")
                             .and_then(|t| t.as_u64()),
                         escalation_enabled: true,
+                        action: None,
+                        grace_seconds: None,
+                        log_level: None,
                         custom_message: filter_obj.get("customMessage")
                             .and_then(|m| m.as_str())
                             .map(|s| s.to_string()),
@@ -1852,12 +2818,12 @@ impl ConfigurationManager {
     }
 
     /// Get configuration statistics
-    pub async fn get_config_stats(&self) -> ConfigStats {
+    pub async fn get_config_status(&self) -> ConfigStatusSummary {
         let filter_config = self.get_filter_config().await;
         let pattern_config = self.get_pattern_config().await;
         let timer_config = self.get_timer_config().await;
 
-        ConfigStats {
+        ConfigStatusSummary {
             total_blacklist_filters: filter_config.blacklist_filters.len(),
             enabled_blacklist_filters: filter_config.blacklist_filters.iter().filter(|f| f.enabled).count(),
             total_spam_filters: filter_config.spam_filters.len(),
@@ -1868,11 +2834,12 @@ impl ConfigurationManager {
             enabled_timers: timer_config.timers.iter().filter(|t| t.enabled).count(),
             categories: filter_config.categories.len(),
             last_updated: filter_config.last_updated,
+            reloads: self.reload_metrics().await,
         }
     }
 
     /// Validate all configurations
-    pub async fn validate_all_configs(&self) -> Result<ValidationReport> {
+    pub async fn validate_all_configs(&self) -> ConfigResult<ValidationReport> {
         let mut report = ValidationReport {
             filter_config_valid: true,
             pattern_config_valid: true,
@@ -1883,9 +2850,15 @@ impl ConfigurationManager {
         };
 
         // Validate filter configuration
-        if let Err(e) = self.validator.validate_filter_config(&self.get_filter_config().await) {
+        let filter_config = self.get_filter_config().await;
+        if let Err(e) = self.validator.validate_filter_config(&filter_config) {
             report.filter_config_valid = false;
             report.errors.push(format!("Filter config: {}", e));
+        } else {
+            // Tolerated duplicates (DuplicateIdPolicy::Warn) still deserve a mention
+            report.warnings.extend(ConfigValidator::duplicate_filter_ids(&filter_config)
+                .into_iter()
+                .map(|duplicate| format!("Filter config: {}", duplicate)));
         }
 
         // Validate pattern configuration
@@ -1894,26 +2867,33 @@ impl ConfigurationManager {
             report.errors.push(format!("Pattern config: {}", e));
         }
 
+        if let Err(e) = self.validator.validate_achievement_config(&self.get_achievement_config().await) {
+            report.errors.push(format!("Achievement config: {}", e));
+        }
+
         // Additional validations can be added here for timer and bot configs
 
         Ok(report)
     }
 
     /// Reset to default configuration
-    pub async fn reset_to_defaults(&self) -> Result<()> {
+    pub async fn reset_to_defaults(&self) -> ConfigResult<()> {
+        self.ensure_writable()?;
         info!("Resetting all configurations to defaults...");
         
         self.create_default_filter_config().await?;
         self.create_default_pattern_config().await?;
         self.create_default_timer_config().await?;
         self.create_default_bot_config().await?;
+        self.create_default_achievement_config().await?;
 
         info!("All configurations reset to defaults");
         Ok(())
     }
 
     /// Create backup of current configuration
-    pub async fn create_backup(&self) -> Result<PathBuf> {
+    pub async fn create_backup(&self) -> ConfigResult<PathBuf> {
+        self.ensure_writable()?;
         let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S");
         let backup_dir = self.config_dir.join("backups");
         
@@ -1964,9 +2944,189 @@ pub struct ImportResult {
     pub errors: Vec<String>,
 }
 
-/// Configuration statistics
-#[derive(Debug)]
-pub struct ConfigStats {
+/// Columns of a blacklist CSV, in default order when the file has no header
+const CSV_BLACKLIST_COLUMNS: [&str; 5] = ["pattern", "type", "timeout", "exemption", "category"];
+
+/// Split one CSV line into fields, honouring double quotes and `""` escapes
+fn split_csv_line(line: &str) -> std::result::Result<Vec<String>, String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' if in_quotes => in_quotes = false,
+            '"' if field.trim().is_empty() => {
+                field.clear();
+                in_quotes = true;
+            }
+            ',' if !in_quotes => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err("unterminated quoted field".to_string());
+    }
+    fields.push(field);
+    Ok(fields)
+}
+
+/// Parse blacklist CSV rows into one filter per category
+fn parse_csv_blacklist(content: &str, validator: &ConfigValidator) -> (Vec<EnhancedBlacklistFilter>, ImportResult) {
+    let mut result = ImportResult { imported_count: 0, warnings: Vec::new(), errors: Vec::new() };
+    let mut filters: Vec<EnhancedBlacklistFilter> = Vec::new();
+    let mut columns: Vec<String> = CSV_BLACKLIST_COLUMNS.iter().map(|c| c.to_string()).collect();
+
+    let rows = content.lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim_end_matches('\r')))
+        .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'));
+
+    for (position, (row, line)) in rows.enumerate() {
+        let fields = match split_csv_line(line) {
+            Ok(fields) => fields,
+            Err(e) => {
+                result.errors.push(format!("Row {}: {}", row, e));
+                continue;
+            }
+        };
+
+        // Optional header row naming the columns
+        if position == 0 && fields.iter().any(|f| f.trim().eq_ignore_ascii_case("pattern")) {
+            columns = fields.iter().map(|f| f.trim().to_lowercase()).collect();
+            if let Some(unknown) = columns.iter().find(|c| !CSV_BLACKLIST_COLUMNS.contains(&c.as_str())) {
+                result.warnings.push(format!("Ignoring unknown column '{}'", unknown));
+            }
+            continue;
+        }
+
+        let get = |name: &str| {
+            columns.iter()
+                .position(|c| c == name)
+                .and_then(|i| fields.get(i))
+                .map(|v| v.trim())
+                .filter(|v| !v.is_empty())
+        };
+
+        let Some(value) = get("pattern") else {
+            result.errors.push(format!("Row {}: missing pattern", row));
+            continue;
+        };
+
+        let (pattern_type, value) = match get("type").map(|t| t.to_lowercase()) {
+            Some(t) if ["literal", "wildcard", "regex", "fuzzy"].contains(&t.as_str()) => (t, value),
+            Some(t) => {
+                result.errors.push(format!("Row {}: unknown pattern type '{}'", row, t));
+                continue;
+            }
+            None if value.len() > 2 && value.starts_with("~/") && value.ends_with('/') => {
+                ("regex".to_string(), &value[2..value.len() - 1])
+            }
+            None if value.contains('*') => ("wildcard".to_string(), value),
+            None => ("literal".to_string(), value),
+        };
+
+        let timeout_seconds = match get("timeout").map(|t| t.parse::<u64>()) {
+            Some(Ok(seconds)) => Some(seconds),
+            Some(Err(_)) => {
+                result.errors.push(format!("Row {}: invalid timeout '{}'", row, get("timeout").unwrap_or_default()));
+                continue;
+            }
+            None => None,
+        };
+
+        let exemption_level = match get("exemption") {
+            Some(level) => match ["None", "Subscriber", "Regular", "Moderator", "Owner"]
+                .iter()
+                .find(|l| l.eq_ignore_ascii_case(level))
+            {
+                Some(level) => level.to_string(),
+                None => {
+                    result.errors.push(format!("Row {}: unknown exemption level '{}'", row, level));
+                    continue;
+                }
+            },
+            None => "Moderator".to_string(),
+        };
+
+        let pattern = PatternDefinition {
+            pattern_type,
+            value: value.to_string(),
+            weight: 1.0,
+            description: None,
+            enabled: true,
+            anchor: WildcardAnchor::default(),
+        };
+        if let Err(e) = validator.validate_pattern_definition(&pattern) {
+            result.errors.push(format!("Row {}: {}", row, e));
+            continue;
+        }
+
+        let category = get("category").unwrap_or("imported").to_lowercase();
+        let id = format!("csv_{}", category.chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect::<String>());
+
+        match filters.iter_mut().find(|f| f.id == id) {
+            Some(filter) => {
+                if filter.timeout_seconds != timeout_seconds || filter.exemption_level != exemption_level {
+                    result.warnings.push(format!(
+                        "Row {}: timeout/exemption differ from earlier rows in category '{}'; using the first row's",
+                        row, category
+                    ));
+                }
+                filter.patterns.push(pattern);
+            }
+            None => filters.push(EnhancedBlacklistFilter {
+                id,
+                name: format!("CSV import: {}", category),
+                enabled: true,
+                description: Some("Imported from CSV".to_string()),
+                category: category.clone(),
+                priority: 5,
+                patterns: vec![pattern],
+                except_patterns: Vec::new(),
+                case_sensitive: false,
+                whole_words_only: false,
+                regex_flags: Some("i".to_string()),
+                timeout_seconds,
+                escalation_enabled: true,
+                action: None,
+                grace_seconds: None,
+                log_level: None,
+                custom_message: None,
+                silent_mode: false,
+                exemption_level,
+                exempt_users: Vec::new(),
+                exempt_platforms: Vec::new(),
+                active_hours: None,
+                active_days: None,
+                min_account_age_days: None,
+                min_follow_time_days: None,
+                track_effectiveness: true,
+                auto_disable_threshold: None,
+                tags: vec!["imported".to_string(), "csv".to_string()],
+                ai_enabled: false,
+                confidence_threshold: None,
+                learning_enabled: false,
+            }),
+        }
+        result.imported_count += 1;
+    }
+
+    (filters, result)
+}
+
+/// Counts of configured filters, patterns and timers; shown by `!configstatus`
+/// and served by the web dashboard
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigStatusSummary {
     pub total_blacklist_filters: usize,
     pub enabled_blacklist_filters: usize,
     pub total_spam_filters: usize,
@@ -1977,6 +3137,24 @@ pub struct ConfigStats {
     pub enabled_timers: usize,
     pub categories: usize,
     pub last_updated: chrono::DateTime<chrono::Utc>,
+    #[serde(default)]
+    pub reloads: ReloadMetrics,
+}
+
+impl std::fmt::Display for ConfigStatusSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "📊 Config Status: {} filters ({} enabled), {} patterns ({} enabled), {} timers ({} enabled), {} categories",
+               self.total_blacklist_filters, self.enabled_blacklist_filters,
+               self.total_pattern_collections, self.enabled_pattern_collections,
+               self.total_timers, self.enabled_timers, self.categories)?;
+        write!(f, " | Reloads: {} ok, {} failed, {} debounced",
+               self.reloads.succeeded(), self.reloads.failed(), self.reloads.debounce_skipped)?;
+        if let Some((config_type, error)) = self.reloads.last_error() {
+            let error: String = error.chars().take(100).collect();
+            write!(f, " (last error in {}: {})", config_type, error)?;
+        }
+        Ok(())
+    }
 }
 
 /// Validation report
@@ -2031,8 +3209,15 @@ impl Default for BotConfiguration {
                 response_delay_ms: 100,
                 max_message_length: 500,
                 rate_limit_per_minute: 20,
+                rate_limit_exempt_mods: default_rate_limit_exempt_mods(),
+                cooldown_notice: None,
+                cooldown_notice_interval_seconds: default_cooldown_notice_interval_seconds(),
                 debug_mode: false,
                 log_level: "info".to_string(),
+                dry_run: false,
+                bot_accounts: HashMap::new(),
+                message_timeout_ms: default_message_timeout_ms(),
+                strict_config: false,
             },
             platforms: HashMap::new(),
             features: FeatureFlags {
@@ -2052,14 +3237,23 @@ impl Default for BotConfiguration {
                 worker_threads: 4,
                 batch_size: 100,
                 monitoring_enabled: true,
+                max_concurrent_tasks: default_max_concurrent_tasks(),
             },
             security: SecuritySettings {
                 encryption_enabled: true,
                 api_key_rotation_days: 90,
                 max_failed_attempts: 5,
                 ip_whitelist: Vec::new(),
+                trusted_proxies: Vec::new(),
                 audit_logging: true,
             },
+            points: PointsSettings::default(),
+            user_filter: UserFilterSettings::default(),
+            privacy: PrivacySettings::default(),
+            restricted_hours: Default::default(),
+            message_log: Default::default(),
+            pending_actions: Default::default(),
+            giveaway_fraud: Default::default(),
         }
     }
 }
@@ -2069,6 +3263,134 @@ mod tests {
     use super::*;
     use tempfile::tempdir;
 
+    fn break_filters_file(dir: &Path) -> PathBuf {
+        let path = dir.join("filters.yaml");
+        std::fs::write(&path, "blacklist_filters: [unclosed").unwrap();
+        path
+    }
+
+    #[tokio::test]
+    async fn test_strict_broken_reload_keeps_last_good_config() {
+        let temp_dir = tempdir().unwrap();
+        let config_manager = ConfigurationManager::new(temp_dir.path()).with_strict_config(true);
+        config_manager.initialize().await.unwrap();
+        let filters_before = config_manager.get_filter_config().await.blacklist_filters.len();
+        assert!(filters_before > 0);
+
+        let mut changes = config_manager.subscribe_to_changes();
+        let path = break_filters_file(temp_dir.path());
+        config_manager.handle_file_change(&path, "filters.yaml").await;
+
+        assert_eq!(config_manager.get_filter_config().await.blacklist_filters.len(), filters_before);
+        assert!(matches!(changes.try_recv(), Ok(ConfigChangeEvent::ValidationError { file, .. }) if file == "filters.yaml"));
+        // Nothing is re-applied
+        assert!(changes.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_reload_metrics_count_outcomes_and_debounces() {
+        let temp_dir = tempdir().unwrap();
+        let config_manager = ConfigurationManager::new(temp_dir.path());
+        config_manager.initialize().await.unwrap();
+
+        let timers = temp_dir.path().join("timers.yaml");
+        config_manager.handle_file_change(&timers, "timers.yaml").await;
+        config_manager.handle_file_change(&timers, "timers.yaml").await;
+        let filters = break_filters_file(temp_dir.path());
+        config_manager.handle_file_change(&filters, "filters.yaml").await;
+        // Files that aren't configuration don't count
+        config_manager.handle_file_change(&temp_dir.path().join("notes.txt"), "notes.txt").await;
+
+        // An editor saving on every keystroke: only the first change is handled
+        let start = std::time::Instant::now() + Duration::from_secs(1);
+        assert!(!config_manager.debounce(start).await);
+        assert!(config_manager.debounce(start + Duration::from_millis(100)).await);
+        assert!(config_manager.debounce(start + Duration::from_millis(400)).await);
+        assert!(!config_manager.debounce(start + Duration::from_millis(600)).await);
+
+        let metrics = config_manager.reload_metrics().await;
+        assert_eq!(metrics.by_config["timers"].succeeded, 2);
+        assert_eq!(metrics.by_config["filters"].failed, 1);
+        assert_eq!(metrics.by_config.len(), 2);
+        assert_eq!((metrics.succeeded(), metrics.failed(), metrics.debounce_skipped), (2, 1, 2));
+        let (config_type, error) = metrics.last_error().unwrap();
+        assert_eq!(config_type, "filters");
+        assert!(!error.is_empty());
+
+        let status = config_manager.get_config_status().await;
+        assert_eq!(status.reloads, metrics);
+        let shown = status.to_string();
+        assert!(shown.contains("Reloads: 2 ok, 1 failed, 2 debounced (last error in filters:"), "{}", shown);
+    }
+
+    #[tokio::test]
+    async fn test_lenient_broken_reload_keeps_last_good_config() {
+        let temp_dir = tempdir().unwrap();
+        let config_manager = ConfigurationManager::new(temp_dir.path());
+        assert!(!config_manager.is_strict());
+        config_manager.initialize().await.unwrap();
+        let filters_before = config_manager.get_filter_config().await.blacklist_filters.len();
+        assert!(filters_before > 0);
+
+        let mut changes = config_manager.subscribe_to_changes();
+        let path = break_filters_file(temp_dir.path());
+        config_manager.handle_file_change(&path, "filters.yaml").await;
+
+        // A bad save never swaps in the (empty) defaults
+        assert_eq!(config_manager.get_filter_config().await.blacklist_filters.len(), filters_before);
+        assert!(matches!(changes.try_recv(), Ok(ConfigChangeEvent::ValidationError { .. })));
+        assert!(changes.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_broken_file_at_startup() {
+        let temp_dir = tempdir().unwrap();
+        let path = break_filters_file(temp_dir.path());
+
+        // Strict mode refuses to start and leaves the file for the user to fix
+        let strict = ConfigurationManager::new(temp_dir.path()).with_strict_config(true);
+        assert!(matches!(strict.initialize().await, Err(ConfigError::Parse(_))));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "blacklist_filters: [unclosed");
+
+        let lenient = ConfigurationManager::new(temp_dir.path());
+        lenient.initialize().await.unwrap();
+        assert!(!lenient.get_filter_config().await.blacklist_filters.is_empty());
+    }
+
+    #[test]
+    fn test_strict_config_is_read_from_bot_yaml() {
+        let temp_dir = tempdir().unwrap();
+        assert!(!ConfigurationManager::read_strict_config(temp_dir.path()));
+
+        let bot_yaml = temp_dir.path().join("bot.yaml");
+        std::fs::write(&bot_yaml, "core:\n  bot_name: NotaBot\n  strict_config: true\n").unwrap();
+        assert!(ConfigurationManager::read_strict_config(temp_dir.path()));
+
+        std::fs::write(&bot_yaml, "core:\n  bot_name: NotaBot\n").unwrap();
+        assert!(!ConfigurationManager::read_strict_config(temp_dir.path()));
+    }
+
+    #[tokio::test]
+    async fn test_find_filters_by_pattern_and_tag() {
+        let temp_dir = tempdir().unwrap();
+        let config_manager = ConfigurationManager::new(temp_dir.path());
+        config_manager.initialize().await.unwrap();
+
+        let by_pattern = config_manager.find_filters("Free Money").await;
+        assert_eq!(by_pattern.len(), 1);
+        assert_eq!(by_pattern[0].id, "crypto_spam");
+        assert_eq!(by_pattern[0].field, FilterSearchField::Pattern);
+        assert_eq!(by_pattern[0].matched, "*free money*");
+
+        let by_tag = config_manager.find_filters("tag:social").await;
+        assert!(!by_tag.is_empty());
+        assert!(by_tag.iter().all(|m| m.field == FilterSearchField::Tag && m.kind == FilterKind::Blacklist));
+        // Tag queries match whole tags only
+        assert!(config_manager.find_filters("tag:soc").await.is_empty());
+
+        assert!(config_manager.find_filters("  ").await.is_empty());
+    }
+
     #[tokio::test]
     async fn test_config_manager_initialization() {
         let temp_dir = tempdir().unwrap();
@@ -2082,6 +3404,32 @@ mod tests {
         assert!(temp_dir.path().join("patterns.yaml").exists());
         assert!(temp_dir.path().join("timers.yaml").exists());
         assert!(temp_dir.path().join("bot.yaml").exists());
+        assert!(temp_dir.path().join("achievements.yaml").exists());
+    }
+
+    #[tokio::test]
+    async fn test_config_status_summary() {
+        let temp_dir = tempdir().unwrap();
+        let config_manager = ConfigurationManager::new(temp_dir.path());
+        config_manager.initialize().await.unwrap();
+
+        let status = config_manager.get_config_status().await;
+        let filters = config_manager.get_filter_config().await;
+        let timers = config_manager.get_timer_config().await;
+        assert_eq!(status.total_blacklist_filters, filters.blacklist_filters.len());
+        assert_eq!(status.total_spam_filters, filters.spam_filters.len());
+        assert_eq!(status.total_timers, timers.timers.len());
+        assert_eq!(status.enabled_timers, timers.timers.iter().filter(|t| t.enabled).count());
+
+        let json = serde_json::to_value(&status).unwrap();
+        for field in ["total_blacklist_filters", "enabled_spam_filters", "total_pattern_collections",
+                      "enabled_timers", "categories", "last_updated"] {
+            assert!(json.get(field).is_some(), "missing {}", field);
+        }
+
+        let text = status.to_string();
+        assert!(text.starts_with("📊 Config Status: "));
+        assert!(text.contains(&format!("{} timers ({} enabled)", status.total_timers, status.enabled_timers)));
     }
 
     #[tokio::test]
@@ -2104,12 +3452,17 @@ mod tests {
                 weight: 1.0,
                 description: None,
                 enabled: true,
+                anchor: WildcardAnchor::default(),
             }],
+            except_patterns: Vec::new(),
             case_sensitive: false,
             whole_words_only: false,
             regex_flags: None,
             timeout_seconds: Some(300),
             escalation_enabled: false,
+            action: None,
+            grace_seconds: None,
+            log_level: None,
             custom_message: None,
             silent_mode: false,
             exemption_level: "None".to_string(),
@@ -2161,6 +3514,33 @@ mod tests {
         assert!(report.errors.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_config_error_variants() {
+        let temp_dir = tempdir().unwrap();
+        let config_manager = ConfigurationManager::new(temp_dir.path());
+        config_manager.initialize().await.unwrap();
+
+        assert!(matches!(config_manager.remove_filter("missing").await, Err(ConfigError::NotFound(_))));
+        assert!(matches!(config_manager.toggle_filter("missing", true).await, Err(ConfigError::NotFound(_))));
+
+        let existing = config_manager.get_filter_config().await.blacklist_filters[0].clone();
+        assert!(matches!(config_manager.add_filter(existing).await, Err(ConfigError::AlreadyExists(_))));
+
+        let mut invalid = config_manager.get_filter_config().await;
+        invalid.version.clear();
+        assert!(matches!(config_manager.save_filter_config(invalid).await, Err(ConfigError::Validation(_))));
+
+        let broken = temp_dir.path().join("broken.yaml");
+        std::fs::write(&broken, "blacklist_filters: [unclosed").unwrap();
+        assert!(matches!(config_manager.import_config("yaml", &broken).await, Err(ConfigError::Parse(_))));
+
+        let missing = temp_dir.path().join("missing.json");
+        assert!(matches!(config_manager.import_config("json", &missing).await, Err(ConfigError::Io(_))));
+
+        let output = temp_dir.path().join("export.xml");
+        assert!(matches!(config_manager.export_config("xml", &output).await, Err(ConfigError::UnsupportedFormat(_))));
+    }
+
     #[tokio::test]
     async fn test_nightbot_import_export() {
         let temp_dir = tempdir().unwrap();
@@ -2193,6 +3573,66 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_csv_blacklist_import() {
+        let temp_dir = tempdir().unwrap();
+        let config_manager = ConfigurationManager::new(temp_dir.path());
+        config_manager.initialize().await.unwrap();
+        let existing_filters = config_manager.get_filter_config().await.blacklist_filters.len();
+
+        let csv = "\
+pattern,type,timeout,exemption,category
+badword,,600,subscriber,Profanity
+*slur*,wildcard,600,Subscriber,profanity
+\"~/fr[e3]{2},? ?gift/\",,300,,scams
+\"buy \"\"followers\"\"\",literal,120,moderator,Scams
+,literal,60,,scams
+spam,literal,soon,,scams
+~/([a-z/,,,,scams
+hello,sound,,,scams
+nope,,,admin,scams
+\"unterminated,,,,scams
+";
+        let csv_path = temp_dir.path().join("blacklist.csv");
+        fs::write(&csv_path, csv).await.unwrap();
+
+        let result = config_manager.import_config("csv", &csv_path).await.unwrap();
+        assert_eq!(result.imported_count, 4);
+        assert_eq!(result.errors.len(), 6);
+        for (row, reason) in [(6, "missing pattern"), (7, "invalid timeout"), (8, "regex"), (9, "unknown pattern type"), (10, "unknown exemption"), (11, "unterminated")] {
+            assert!(
+                result.errors.iter().any(|e| e.starts_with(&format!("Row {}:", row)) && e.contains(reason)),
+                "no error for row {}: {:?}", row, result.errors
+            );
+        }
+        // Row 5 sets a different timeout than the first scams row
+        assert_eq!(result.warnings.len(), 1);
+
+        let config = config_manager.get_filter_config().await;
+        assert_eq!(config.blacklist_filters.len(), existing_filters + 2);
+
+        let profanity = config.blacklist_filters.iter().find(|f| f.id == "csv_profanity").unwrap();
+        assert_eq!(profanity.patterns.len(), 2);
+        assert_eq!(profanity.patterns[0].pattern_type, "literal");
+        assert_eq!(profanity.patterns[1].pattern_type, "wildcard");
+        assert_eq!(profanity.exemption_level, "Subscriber");
+        assert_eq!(profanity.timeout_seconds, Some(600));
+
+        let scams = config.blacklist_filters.iter().find(|f| f.id == "csv_scams").unwrap();
+        assert_eq!(scams.patterns[0].pattern_type, "regex");
+        assert_eq!(scams.patterns[0].value, "fr[e3]{2},? ?gift");
+        assert_eq!(scams.patterns[1].value, "buy \"followers\"");
+        assert_eq!(scams.exemption_level, "Moderator");
+
+        // Re-importing replaces the category filters instead of duplicating them
+        fs::write(&csv_path, "badword\n").await.unwrap();
+        let result = config_manager.import_config("csv", &csv_path).await.unwrap();
+        assert_eq!(result.imported_count, 1);
+        let config = config_manager.get_filter_config().await;
+        assert_eq!(config.blacklist_filters.len(), existing_filters + 3);
+        assert!(config.blacklist_filters.iter().any(|f| f.id == "csv_imported"));
+    }
+
     #[tokio::test]
     async fn test_backup_creation() {
         let temp_dir = tempdir().unwrap();
@@ -2203,4 +3643,181 @@ mod tests {
         assert!(backup_path.exists());
         assert!(backup_path.extension().unwrap() == "gz");
     }
+
+    #[test]
+    fn test_time_range_contains() {
+        use chrono::{TimeZone, Utc};
+        let range = |start: &str, end: &str, timezone: Option<&str>| TimeRange {
+            start: start.to_string(),
+            end: end.to_string(),
+            timezone: timezone.map(String::from),
+        };
+        let at = |h, m| Utc.with_ymd_and_hms(2026, 3, 10, h, m, 0).unwrap();
+
+        let daytime = range("09:00", "17:00", None);
+        assert!(daytime.contains(at(9, 0)));
+        assert!(!daytime.contains(at(17, 0)));
+
+        // Overnight ranges wrap past midnight
+        let overnight = range("23:00", "06:00", Some("UTC"));
+        assert!(overnight.contains(at(23, 30)));
+        assert!(overnight.contains(at(2, 0)));
+        assert!(!overnight.contains(at(12, 0)));
+
+        // 01:00-07:00 in Los Angeles is 08:00-14:00 UTC in March
+        let pacific = range("01:00", "07:00", Some("America/Los_Angeles"));
+        assert!(pacific.contains(at(10, 0)));
+        assert!(!pacific.contains(at(3, 0)));
+        assert!(range("01:00", "07:00", Some("UTC-8")).contains(at(10, 0)));
+        assert!(range("15:00", "16:00", Some("+05:30")).contains(at(9, 45)));
+
+        assert!(!range("25:00", "07:00", None).contains(at(3, 0)));
+        assert!(!range("01:00", "07:00", Some("Mars/Olympus")).contains(at(3, 0)));
+    }
+
+    #[tokio::test]
+    async fn test_weekday_only_filter_schedule() {
+        use chrono::{TimeZone, Utc};
+        let temp_dir = tempdir().unwrap();
+        let config_manager = ConfigurationManager::new(temp_dir.path());
+        config_manager.initialize().await.unwrap();
+        let mut filter = config_manager.get_filter_config().await.blacklist_filters.remove(0);
+        assert!(filter.schedule().unwrap().is_none());
+
+        // 2026-03-10 is a Tuesday, 03-14 and 03-15 the weekend after
+        let at = |day, h| Utc.with_ymd_and_hms(2026, 3, day, h, 0, 0).unwrap();
+        filter.active_days = Some(["Mon", "tue", "Wednesday", "Thu", "FRI"].iter().map(|d| d.to_string()).collect());
+        let schedule = filter.schedule().unwrap().unwrap();
+        assert!(schedule.is_active(at(10, 12)));
+        assert!(schedule.is_active(at(13, 23)));
+        assert!(!schedule.is_active(at(14, 12)));
+        assert!(!schedule.is_active(at(15, 12)));
+
+        // Days follow the hours' timezone: Friday 21:00 in Los Angeles is already Saturday in UTC
+        filter.active_hours = Some(TimeRange { start: "18:00".to_string(), end: "23:00".to_string(), timezone: Some("America/Los_Angeles".to_string()) });
+        let schedule = filter.schedule().unwrap().unwrap();
+        assert!(schedule.is_active(at(14, 4)));
+        assert!(!schedule.is_active(at(15, 4)));
+        // Both the hours and the days have to match
+        assert!(!schedule.is_active(at(13, 12)));
+
+        filter.active_days = Some(vec!["Someday".to_string()]);
+        assert!(filter.schedule().is_err());
+        assert!(ConfigValidator::new().validate_filter_config(&FilterConfiguration {
+            blacklist_filters: vec![filter],
+            ..config_manager.get_filter_config().await
+        }).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_filter_ids_are_rejected_at_load() {
+        let temp_dir = tempdir().unwrap();
+        let config_manager = ConfigurationManager::new(temp_dir.path());
+        let shipped = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("config/filters.yaml");
+        let mut config: FilterConfiguration = serde_yaml::from_str(&std::fs::read_to_string(shipped).unwrap()).unwrap();
+
+        let mut copy = config.blacklist_filters[0].clone();
+        copy.name = "Copy with the same id".to_string();
+        config.blacklist_filters.push(copy);
+        let spam_copy = config.spam_filters[0].clone();
+        config.spam_filters.push(spam_copy);
+        let write_config = |config: &FilterConfiguration| {
+            std::fs::write(temp_dir.path().join("filters.yaml"), serde_yaml::to_string(config).unwrap()).unwrap();
+        };
+
+        write_config(&config);
+        match config_manager.load_filter_config().await {
+            Err(ConfigError::Validation(message)) => assert_eq!(message, "Duplicate blacklist filter ID: crypto_spam"),
+            other => panic!("expected a validation error, got {:?}", other),
+        }
+        assert_eq!(ConfigValidator::duplicate_filter_ids(&config), vec![
+            "Duplicate blacklist filter ID: crypto_spam".to_string(),
+            "Duplicate spam filter ID: excessive_caps".to_string(),
+        ]);
+
+        // Warn mode loads the file but reports the duplicates
+        config.global_settings.duplicate_ids = DuplicateIdPolicy::Warn;
+        write_config(&config);
+        config_manager.load_filter_config().await.unwrap();
+        let report = config_manager.validate_all_configs().await.unwrap();
+        assert!(report.filter_config_valid);
+        assert!(report.warnings.contains(&"Filter config: Duplicate spam filter ID: excessive_caps".to_string()));
+    }
+
+    #[test]
+    fn test_runtime_uses_configured_worker_threads() {
+        let temp_dir = tempdir().unwrap();
+        let runtime_workers = |settings: &PerformanceSettings| {
+            settings.runtime_builder().build().unwrap().metrics().num_workers()
+        };
+
+        // No bot.yaml yet: the defaults apply
+        let settings = ConfigurationManager::read_performance_settings(temp_dir.path());
+        assert_eq!(runtime_workers(&settings), BotConfiguration::default().performance.worker_threads as usize);
+
+        let mut config = BotConfiguration::default();
+        config.performance.worker_threads = 3;
+        std::fs::write(temp_dir.path().join("bot.yaml"), serde_yaml::to_string(&config).unwrap()).unwrap();
+        let settings = ConfigurationManager::read_performance_settings(temp_dir.path());
+        assert_eq!(settings.worker_threads, 3);
+        assert_eq!(runtime_workers(&settings), 3);
+
+        // Too few workers is raised to the minimum
+        config.performance.worker_threads = 1;
+        std::fs::write(temp_dir.path().join("bot.yaml"), serde_yaml::to_string(&config).unwrap()).unwrap();
+        let settings = ConfigurationManager::read_performance_settings(temp_dir.path());
+        assert_eq!(runtime_workers(&settings), MIN_WORKER_THREADS);
+    }
+
+    #[tokio::test]
+    async fn test_read_only_config_dir_runs_on_defaults() {
+        let temp_dir = tempdir().unwrap();
+        let mut bot_config = BotConfiguration::default();
+        bot_config.core.bot_name = "MountedBot".to_string();
+        std::fs::write(temp_dir.path().join("bot.yaml"), serde_yaml::to_string(&bot_config).unwrap()).unwrap();
+
+        let config_manager = ConfigurationManager::new(temp_dir.path()).with_read_only(true);
+        config_manager.initialize().await.unwrap();
+
+        // Existing files are loaded; missing ones fall back to in-memory defaults
+        assert_eq!(config_manager.get_bot_config().await.core.bot_name, "MountedBot");
+        assert!(config_manager.get_filter_config().await.blacklist_filters.iter().any(|f| f.id == "crypto_spam"));
+        assert!(!temp_dir.path().join("filters.yaml").exists());
+        assert!(!temp_dir.path().join("timers.yaml").exists());
+
+        let filters = config_manager.get_filter_config().await;
+        assert!(matches!(config_manager.save_filter_config(filters).await, Err(ConfigError::ReadOnly(_))));
+        assert!(matches!(config_manager.toggle_filter("crypto_spam", false).await, Err(ConfigError::ReadOnly(_))));
+        assert!(matches!(config_manager.reset_to_defaults().await, Err(ConfigError::ReadOnly(_))));
+        assert!(matches!(config_manager.create_backup().await, Err(ConfigError::ReadOnly(_))));
+        assert!(!temp_dir.path().join("filters.yaml").exists());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_unwritable_config_dir_is_detected() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = tempdir().unwrap();
+        let config_dir = temp_dir.path().join("config");
+        std::fs::create_dir(&config_dir).unwrap();
+        std::fs::set_permissions(&config_dir, std::fs::Permissions::from_mode(0o555)).unwrap();
+
+        // Root ignores directory permissions, so there is nothing to detect
+        if std::fs::write(config_dir.join("probe"), b"").is_ok() {
+            std::fs::remove_file(config_dir.join("probe")).unwrap();
+            return;
+        }
+
+        let config_manager = ConfigurationManager::new(&config_dir);
+        config_manager.initialize().await.unwrap();
+        assert!(config_manager.is_read_only());
+        assert!(!config_dir.join("bot.yaml").exists());
+        assert!(matches!(
+            config_manager.save_bot_config(BotConfiguration::default()).await,
+            Err(ConfigError::ReadOnly(_))
+        ));
+
+        std::fs::set_permissions(&config_dir, std::fs::Permissions::from_mode(0o755)).unwrap();
+    }
 }
@@ -25,26 +25,35 @@ pub mod web;
 // Re-export commonly used items
 pub mod prelude {
     pub use crate::bot::ChatBot;
+    pub use crate::bot::user_filter::UserFilter;
+    pub use crate::bot::raid_mode::RaidModeConfig;
+    pub use crate::bot::restricted_hours::{RestrictedHoursChange, RestrictedHoursConfig};
+    pub use crate::bot::message_log::MessageLogConfig;
+    pub use crate::bot::audit_log::{AuditEntry, AuditQuery, AuditSource};
+    pub use crate::bot::data_dir::DataDir;
+    pub use crate::bot::identity::IdentityMap;
+    pub use crate::bot::pending_actions::PendingActionConfig;
+    pub use crate::bot::announcements::{AnnouncementReport, AnnouncementTarget};
     pub use crate::platforms::{
-        PlatformConnection, 
+        PlatformConnection, DeliveryStatus, ChatRestriction,
         twitch::{TwitchConnection, TwitchConfig},
         youtube::{YouTubeConnection, YouTubeConfig},
     };
     pub use crate::types::{
-        ChatMessage, BotCommand, BotTimer, SpamFilterType, SpamFilter, 
+        ChatMessage, BotCommand, CommandChannels, BotTimer, SpamFilterType, SpamFilter, 
         ModerationAction, UserMessageHistory, ExemptionLevel, ModerationEscalation,
-        FilterConfigManager
+        FilterConfigManager, ModerationError
     };
     pub use crate::adaptive::AdaptivePerformanceSystem;
 
 
     // Phase 2 exports
     pub use crate::bot::enhanced_moderation::EnhancedModerationSystem;
-    pub use crate::bot::pattern_matching::{AdvancedPattern, EnhancedPatternMatcher};
+    pub use crate::bot::pattern_matching::{AdvancedPattern, EnhancedPatternMatcher, NormalizeOptions, NormalizedText};
     pub use crate::bot::smart_escalation::{SmartEscalation, ViolationSeverity, PositiveActionType};
     pub use crate::bot::realtime_analytics::{FilterAnalyticsSystem, UserReportType, ModeratorReviewType};
     pub use crate::bot::filter_import_export::{FilterImportExport, ExportFormat, ExportOptions, ImportOptions};
-    pub use crate::config::{ConfigurationManager};
+    pub use crate::config::{ConfigurationManager, ConfigError, PlatformFeatures, RateLimitConfig, TimeRange};
 
     #[cfg(feature = "web")]
     pub use crate::web::{WebDashboard, DashboardState};
@@ -1,6 +1,6 @@
 use anyhow::Result;
 use log::{debug, error, info, warn};
-use tokio::time::{sleep, Duration, Instant};
+use tokio::time::{sleep, Duration};
 use std::env;
 use std::path::Path;
 use std::sync::Arc;
@@ -9,15 +9,16 @@ use tokio::sync::RwLock;
 use notabot::prelude::*;
 use notabot::config::ConfigurationManager;
 use notabot::bot::config_integration::{ConfigIntegration, ConfigCommands};
-use notabot::bot::connection_pool::{ConnectionPool, PoolConfig};
+use notabot::bot::connection_pool::{ConnectionPool, ConnectionRole, PoolConfig};
 use notabot::bot::shutdown::{GracefulShutdown, ShutdownIntegration, ShutdownConfig};
+use notabot::bot::diagnostics::{CheckStatus, DiagnosticsOptions};
+use notabot::bot::data_dir::{DataDir, ADAPTIVE_STATE_FILE, AUDIT_LOG_FILE};
 
 // adaptive tuning system
 use notabot::adaptive::{AdaptivePerformanceSystem, AdaptiveConfig};
 use std::sync::atomic::{AtomicBool, Ordering};
 
-#[tokio::main]
-async fn main() -> Result<()> {
+fn main() -> Result<()> {
     // Load environment variables and initialize logging
     dotenv::dotenv().ok();
     env_logger::Builder::from_default_env()
@@ -26,6 +27,16 @@ async fn main() -> Result<()> {
 
     info!("Starting NotaBot v{} - AI-Powered Moderation", env!("CARGO_PKG_VERSION"));
 
+    // The runtime is sized from bot.yaml, so that has to be read before it exists
+    let performance = ConfigurationManager::read_performance_settings("config");
+    let runtime = performance.runtime_builder().build()?;
+    info!("Runtime started with {} worker threads (configured: {})",
+          performance.effective_worker_threads(), performance.worker_threads);
+
+    runtime.block_on(run())
+}
+
+async fn run() -> Result<()> {
     // =================================================================
     // CONFIGURATION SYSTEM INITIALIZATION
     // =================================================================
@@ -33,15 +44,35 @@ async fn main() -> Result<()> {
     info!("Initializing configuration management system...");
     
     let config_dir = Path::new("config");
-    let config_manager = Arc::new(ConfigurationManager::new(config_dir));
+    // CONFIG_READ_ONLY forces read-only mode; an unwritable directory is detected on its own
+    let config_read_only = env::var("CONFIG_READ_ONLY")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(false);
+    // Strict config refuses to start with a broken file instead of using its defaults
+    // (STRICT_CONFIG overrides core.strict_config in bot.yaml)
+    let strict_config = env::var("STRICT_CONFIG")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or_else(|| ConfigurationManager::read_strict_config(config_dir));
+    let config_manager = Arc::new(ConfigurationManager::new(config_dir)
+        .with_read_only(config_read_only)
+        .with_strict_config(strict_config));
     
     if let Err(e) = config_manager.initialize().await {
         error!("Failed to initialize configuration system: {}", e);
-        return Err(e);
+        return Err(e.into());
     }
     
     info!("Configuration system initialized with hot-reload support");
 
+    // Persisted state lives under NOTABOT_DATA_DIR (default `data/`)
+    let data_dir = DataDir::from_env();
+    if let Err(e) = data_dir.ensure().await {
+        error!("{:#}", e);
+        return Err(e);
+    }
+
     // =================================================================
     // CONNECTION POOL INITIALIZATION
     // =================================================================
@@ -90,6 +121,63 @@ async fn main() -> Result<()> {
     // =================================================================
     
     let mut bot = ChatBot::new();
+
+    // Dry run: stay connected and log every decision without acting on it (DRY_RUN overrides bot.yaml)
+    let dry_run = env::var("DRY_RUN")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(bot_config.core.dry_run);
+    bot.set_dry_run(dry_run);
+    bot.set_message_timeout(Duration::from_millis(bot_config.core.message_timeout_ms));
+    bot.set_command_rate_limit(bot_config.core.rate_limit_per_minute, bot_config.core.rate_limit_exempt_mods).await;
+    bot.set_cooldown_notice(bot_config.core.cooldown_notice.clone(), bot_config.core.cooldown_notice_interval_seconds).await;
+    bot.set_max_concurrent_tasks(bot_config.performance.max_concurrent_tasks).await;
+    bot.set_security_settings(bot_config.security.clone()).await;
+    bot.set_restricted_hours(bot_config.restricted_hours.clone()).await;
+    bot.set_pending_action_config(bot_config.pending_actions.clone()).await;
+    for (platform, username) in &bot_config.core.bot_accounts {
+        bot.set_bot_account(platform, Some(username)).await;
+    }
+    if let Err(e) = bot.set_data_dir(&data_dir).await {
+        error!("Failed to load saved state from {}: {:#}", data_dir.root().display(), e);
+    }
+    let mut message_log_config = bot_config.message_log.clone();
+    message_log_config.path = data_dir.resolve(&message_log_config.path);
+    if let Err(e) = bot.set_message_log(message_log_config.clone()).await {
+        error!("Message log disabled: {:#}", e);
+    }
+    if bot_config.security.audit_logging {
+        if let Err(e) = bot.set_audit_log(Some(data_dir.file(AUDIT_LOG_FILE))).await {
+            error!("Moderation audit log disabled: {:#}", e);
+        }
+    }
+
+    for (platform, platform_config) in &bot_config.platforms {
+        bot.set_platform_features(platform, platform_config.features.clone()).await;
+        bot.set_platform_webhooks(platform, platform_config.webhooks.clone()).await;
+        bot.set_platform_rate_limits(platform, platform_config.rate_limits.clone()).await;
+    }
+
+    let dashboard_port = env::var("DASHBOARD_PORT")
+        .unwrap_or_else(|_| "3000".to_string())
+        .parse::<u16>()
+        .unwrap_or(3000);
+
+    let mut data_dirs = vec![data_dir.root().to_path_buf()];
+    if message_log_config.enabled {
+        if let Some(log_dir) = message_log_config.path.parent().filter(|p| !p.as_os_str().is_empty() && *p != data_dir.root()) {
+            data_dirs.push(log_dir.to_path_buf());
+        }
+    }
+    let doctor = env::args().any(|arg| arg == "--doctor");
+    bot.set_diagnostics_options(DiagnosticsOptions {
+        config_dir: Some(config_dir.to_path_buf()),
+        dashboard_port: cfg!(feature = "web").then_some(dashboard_port),
+        data_dirs,
+        connect_platforms: doctor,
+        ..Default::default()
+    }).await;
+
     let bot_arc = Arc::new(RwLock::new(bot));
 
     // Instead of adding connections directly, the bot will use the pool
@@ -102,6 +190,27 @@ async fn main() -> Result<()> {
         // Add platform connections (these will be managed by the pool)
         if available_platforms.contains(&"twitch".to_string()) {
             if let Ok(twitch_config) = TwitchConfig::from_env() {
+                if !bot_config.core.bot_accounts.contains_key("twitch") {
+                    bot_guard.set_bot_account("twitch", Some(&twitch_config.username)).await;
+                }
+
+                // The main account keeps reading chat and moderating; responses go
+                // out through the send accounts
+                match twitch_config.send_accounts_from_env() {
+                    Ok(accounts) => {
+                        for account in accounts {
+                            let username = account.username.clone();
+                            bot_guard.add_bot_account("twitch", &username).await;
+                            let connection = Box::new(TwitchConnection::new(account));
+                            match connection_pool.add_account("twitch", &username, ConnectionRole::Send, connection).await {
+                                Ok(()) => info!("Twitch responses will also be sent as {}", username),
+                                Err(e) => warn!("Twitch send account {} unavailable: {}", username, e),
+                            }
+                        }
+                    }
+                    Err(e) => warn!("Ignoring TWITCH_SEND_ACCOUNTS: {}", e),
+                }
+
                 let twitch_connection = TwitchConnection::new(twitch_config);
                 bot_guard.add_connection(Box::new(twitch_connection)).await;
                 info!("Twitch connection added to bot");
@@ -117,6 +226,13 @@ async fn main() -> Result<()> {
         }
     }
 
+    // `notabot --doctor`: check the setup, print the report and exit
+    if doctor {
+        let report = bot_arc.read().await.run_diagnostics().await;
+        println!("{}", report);
+        std::process::exit(if report.overall() == CheckStatus::Fail { 1 } else { 0 });
+    }
+
     // =================================================================
     // GRACEFUL SHUTDOWN SETUP
     // =================================================================
@@ -202,10 +318,17 @@ async fn main() -> Result<()> {
                 retry_increase_factor: 1.5,
             },
         },
+        parameter_bounds: AdaptiveConfig::load_parameter_bounds(&config_dir.join("startup.toml"))?,
     };
 
     // Initialize the adaptive performance system
     let adaptive_system = Arc::new(AdaptivePerformanceSystem::new(adaptive_config.clone())?);
+    {
+        let bot_guard = bot_arc.read().await;
+        bot_guard.attach_adaptive_system(adaptive_system.clone()).await;
+        bot_guard.attach_connection_pool(connection_pool.clone()).await;
+        bot_guard.attach_config_manager(config_manager.clone()).await;
+    }
 
     // Start the adaptive system
     if let Err(e) = adaptive_system.start(adaptive_config.clone()).await {
@@ -226,6 +349,7 @@ async fn main() -> Result<()> {
         bot_guard.create_enhanced_moderation()
     };
     let enhanced_moderation = Arc::new(enhanced_moderation);
+    bot_arc.read().await.attach_enhanced_moderation(enhanced_moderation.clone()).await;
     
     // Setup configuration integration
     let mut config_integration = ConfigIntegration::new(
@@ -236,6 +360,26 @@ async fn main() -> Result<()> {
         },
     );
     config_integration.set_enhanced_moderation(enhanced_moderation.clone());
+    config_integration.set_points_system({
+        let bot_guard = bot_arc.read().await;
+        bot_guard.get_points_system()
+    });
+    config_integration.set_giveaway_system({
+        let bot_guard = bot_arc.read().await;
+        bot_guard.get_giveaway_system()
+    });
+    config_integration.set_achievement_system({
+        let bot_guard = bot_arc.read().await;
+        bot_guard.get_achievement_system()
+    });
+    config_integration.set_user_filter({
+        let bot_guard = bot_arc.read().await;
+        bot_guard.get_user_filter()
+    });
+    config_integration.set_timer_system({
+        let bot_guard = bot_arc.read().await;
+        bot_guard.get_timer_system()
+    });
     
     if let Err(e) = config_integration.initialize().await {
         error!("Failed to initialize configuration integration: {}", e);
@@ -263,8 +407,6 @@ async fn main() -> Result<()> {
     
     info!("Registering commands with shutdown awareness...");
     
-    let config_commands = Arc::new(ConfigCommands::new(config_integration.clone()));
-    
     // Register basic commands
     {
         let bot_guard = bot_arc.read().await;
@@ -274,12 +416,8 @@ async fn main() -> Result<()> {
         // Add shutdown command for administrators
         bot_guard.add_command("shutdown".to_string(), "Initiating graceful shutdown... (admin only)".to_string(), true, 300).await;
         
-        // Add pool statistics command
-        bot_guard.add_command("poolstats".to_string(), "Connection pool statistics (mod only)".to_string(), true, 30).await;
-        
         // Configuration commands
         bot_guard.add_command("reloadconfig".to_string(), "Configuration management (mod only)".to_string(), true, 60).await;
-        bot_guard.add_command("configstatus".to_string(), "Configuration status (mod only)".to_string(), true, 30).await;
     
         // Adaptive system control commands
         bot_guard.add_command("adaptivestatus".to_string(), "Show adaptive performance status (mod only)".to_string(), true, 30).await;
@@ -342,6 +480,7 @@ async fn main() -> Result<()> {
     let pool_for_metrics = connection_pool.clone();
     tokio::spawn(async move {
         let mut interval = tokio::time::interval(Duration::from_secs(30));
+        let mut connection_failures = notabot::adaptive::connection_pool_integration::ConnectionFailureCounter::default();
         
         loop {
             interval.tick().await;
@@ -349,6 +488,7 @@ async fn main() -> Result<()> {
             if let Err(e) = notabot::adaptive::connection_pool_integration::collect_connection_pool_metrics(
                 &pool_for_metrics,
                 &pool_metrics_collector,
+                &mut connection_failures,
             ).await {
                 error!("Failed to collect connection pool metrics: {}", e);
             }
@@ -391,130 +531,6 @@ async fn main() -> Result<()> {
         }
     });
 
-    // =================================================================
-    // ENHANCED MESSAGE PROCESSING WITH ADAPTIVE METRICS
-    // =================================================================
-
-    let adaptive_message_processor = adaptive_system.clone();
-    let message_processor_shutdown = shutdown_manager.clone();
-    let message_processor_config_commands = config_commands.clone();
-    let message_processor_enhanced_mod = enhanced_moderation.clone();
-    let message_processor_pool = connection_pool.clone();
-
-    tokio::spawn(async move {
-        info!("Starting adaptive-aware message processor...");
-        let mut message_count = 0;
-        let mut processing_times = Vec::new();
-        let mut last_metric_update = Instant::now();
-        
-        loop {
-            // Check if shutdown is requested
-            if message_processor_shutdown.is_shutdown_requested().await {
-                info!("Message processor stopping due to shutdown request");
-                break;
-            }
-            
-            // Acquire operation permit
-            if let Some(_permit) = message_processor_shutdown.acquire_operation_permit().await {
-                let start_time = std::time::Instant::now();
-                
-                // Get current adaptive parameters for dynamic behavior
-                let current_params = adaptive_message_processor.get_current_parameters().await.unwrap_or_default();
-                
-                // Use adaptive batch size if available
-                let batch_size = current_params.get("message_processing_batch_size")
-                    .and_then(|v| v.as_i64())
-                    .unwrap_or(10) as usize;
-                
-                // Use adaptive response delay
-                let response_delay = current_params.get("response_delay_ms")
-                    .and_then(|v| v.as_duration_ms())
-                    .unwrap_or(100);
-                
-                // Process messages in adaptive batches
-                for _ in 0..batch_size {
-                    // Simulate processing one message with adaptive delay
-                    tokio::time::sleep(Duration::from_millis(response_delay / batch_size as u64)).await;
-                    message_count += 1;
-                    
-                    // Simulate different processing complexities
-                    let complexity_delay = match message_count % 10 {
-                        0 => 50, // Complex AI processing
-                        1..=2 => 20, // Moderation checks
-                        _ => 5, // Simple commands
-                    };
-                    tokio::time::sleep(Duration::from_millis(complexity_delay)).await;
-                }
-                
-                let processing_time = start_time.elapsed();
-                processing_times.push(processing_time.as_millis() as f64);
-                
-                // Record comprehensive metrics for adaptive system
-                if let Err(e) = adaptive_message_processor.record_metric("message_processing_latency", processing_time.as_millis() as f64).await {
-                    error!("Failed to record processing latency: {}", e);
-                }
-                
-                if let Err(e) = adaptive_message_processor.record_metric("messages_processed_per_batch", batch_size as f64).await {
-                    error!("Failed to record batch size metric: {}", e);
-                }
-                
-                // Update system metrics periodically
-                if last_metric_update.elapsed() >= Duration::from_secs(30) {
-                    let avg_processing_time = processing_times.iter().sum::<f64>() / processing_times.len() as f64;
-                    let throughput = (batch_size as f64 * 1000.0) / avg_processing_time; // messages per second
-                    
-                    // Record aggregated metrics
-                    if let Err(e) = adaptive_message_processor.record_metric("message_throughput", throughput).await {
-                        error!("Failed to record throughput: {}", e);
-                    }
-                    
-                    if let Err(e) = adaptive_message_processor.record_metric("processing_efficiency", 1000.0 / avg_processing_time).await {
-                        error!("Failed to record efficiency: {}", e);
-                    }
-                    
-                    // Simulate memory usage tracking
-                    let memory_usage = 40.0 + (message_count % 1000) as f64 / 20.0; // Simulate 40-90% usage
-                    if let Err(e) = adaptive_message_processor.record_metric("memory_usage", memory_usage).await {
-                        error!("Failed to record memory usage: {}", e);
-                    }
-                    
-                    // Simulate error rate tracking
-                    let error_rate = if message_count % 100 == 0 { 2.0 } else { 0.5 }; // Periodic errors
-                    if let Err(e) = adaptive_message_processor.record_metric("error_rate", error_rate).await {
-                        error!("Failed to record error rate: {}", e);
-                    }
-                    
-                    // Clear old processing times to keep memory usage bounded
-                    if processing_times.len() > 100 {
-                        processing_times.drain(0..50);
-                    }
-                    
-                    last_metric_update = Instant::now();
-                    
-                    debug!("Processed {} messages, avg latency: {:.2}ms, throughput: {:.2} msg/s, memory: {:.1}%", 
-                        message_count, avg_processing_time, throughput, memory_usage);
-                }
-                
-                // Adaptive sleep based on current system load and performance
-                let base_sleep = Duration::from_millis(100);
-                let adaptive_sleep = if processing_time > Duration::from_millis(500) {
-                    base_sleep / 2 // Process faster if we're slow
-                } else if processing_time < Duration::from_millis(50) {
-                    base_sleep * 2 // Process slower if we're fast (save resources)
-                } else {
-                    base_sleep
-                };
-                
-                sleep(adaptive_sleep).await;
-            } else {
-                debug!("Skipping message processing due to shutdown");
-                break;
-            }
-        }
-        
-        info!("Adaptive message processor stopped gracefully after processing {} messages", message_count);
-    });
-
     // =================================================================
     // ADAPTIVE PERFORMANCE MONITORING WITH SYSTEM INTEGRATION
     // =================================================================
@@ -635,11 +651,6 @@ async fn main() -> Result<()> {
     // WEB DASHBOARD WITH ENHANCED STATS
     // =================================================================
     
-    let dashboard_port = env::var("DASHBOARD_PORT")
-        .unwrap_or_else(|_| "3000".to_string())
-        .parse::<u16>()
-        .unwrap_or(3000);
-    
     {
         let bot_guard = bot_arc.read().await;
         if let Err(e) = bot_guard.start_web_dashboard(dashboard_port).await {
@@ -749,16 +760,10 @@ async fn main() -> Result<()> {
 
     // Export final adaptive state before shutdown
     match adaptive_system.export_state().await {
-        Ok(state) => {
-            let final_state_file = "data/adaptive_final_state.json";
-            if let Ok(state_json) = serde_json::to_string_pretty(&state) {
-                if let Err(e) = tokio::fs::write(final_state_file, state_json).await {
-                    error!("Failed to save final adaptive state: {}", e);
-                } else {
-                    info!("📊 Final adaptive state saved to {}", final_state_file);
-                }
-            }
-        }
+        Ok(state) => match data_dir.write_json(ADAPTIVE_STATE_FILE, &state).await {
+            Ok(path) => info!("📊 Final adaptive state saved to {}", path.display()),
+            Err(e) => error!("Failed to save final adaptive state: {:#}", e),
+        },
         Err(e) => {
             error!("Failed to export final adaptive state: {}", e);
         }
@@ -809,25 +814,6 @@ async fn process_message_with_shutdown_protection(
         return Some(response);
     }
     
-    // Handle pool commands
-    if message.content.starts_with("!poolstats") && message.is_mod {
-        let stats = connection_pool.get_stats().await;
-        let mut response = "Connection Pool Stats:\n".to_string();
-        
-        for (platform, platform_stats) in stats {
-            response.push_str(&format!(
-                "{}: {} total ({} active, {} idle), {:.1}ms avg\n",
-                platform,
-                platform_stats.total_connections,
-                platform_stats.active_connections,
-                platform_stats.idle_connections,
-                platform_stats.average_response_time_ms
-            ));
-        }
-        
-        return Some(response);
-    }
-    
     // Handle manual shutdown command
     if message.content.starts_with("!shutdown") && message.is_mod {
         shutdown_manager.trigger_shutdown().await;
@@ -872,17 +858,6 @@ async fn handle_config_commands(
             }
         }
         
-        "configstatus" => {
-            if !message.is_mod {
-                return Some("This command is moderator-only.".to_string());
-            }
-            
-            match config_commands.handle_status_command().await {
-                Ok(response) => Some(response),
-                Err(e) => Some(format!("Status error: {}", e)),
-            }
-        }
-        
         "validateconfig" => {
             if !message.is_mod {
                 return Some("This command is moderator-only.".to_string());
@@ -917,27 +892,6 @@ async fn handle_config_commands(
             }
         }
         
-        "appeal" => {
-            if args.is_empty() {
-                return Some("Usage: !appeal <reason>. Describe why you think the moderation action was incorrect.".to_string());
-            }
-            
-            let reason = args.join(" ");
-            let user_id = format!("{}:{}", message.platform, message.username);
-            
-            if let Err(e) = enhanced_moderation.record_user_feedback(
-                "user_appeal",
-                &user_id,
-                notabot::bot::realtime_analytics::UserReportType::FalsePositive,
-                &message.content,
-                Some(reason.clone()),
-            ).await {
-                error!("Failed to record user appeal: {}", e);
-            }
-            
-            Some(format!("Appeal recorded: '{}'. Our AI will learn from this feedback. Thank you!", reason))
-        }
-        
         "aiinfo" => {
             let status = enhanced_moderation.get_system_status().await;
             Some(format!(
@@ -948,7 +902,7 @@ async fn handle_config_commands(
                 if status.auto_optimization_enabled { "ON" } else { "OFF" }
             ))
         }
-        
+
         _ => None,
     }
 }
@@ -0,0 +1,163 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use log::debug;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::{broadcast, RwLock};
+use tokio::time::{sleep, Duration, Instant};
+
+use crate::platforms::twitch_eventsub::TwitchEvent;
+use crate::platforms::{ChatRestriction, DeliveryStatus, PlatformConnection};
+use crate::types::ChatMessage;
+
+/// In-memory platform connection for exercising the bot without a network.
+///
+/// Clones share state, so keep a clone before handing the connection to the
+/// bot and use it to inject incoming messages and inspect sent responses.
+#[derive(Clone)]
+pub struct MockConnection {
+    platform: String,
+    channels: Vec<String>,
+    message_sender: broadcast::Sender<ChatMessage>,
+    event_sender: broadcast::Sender<TwitchEvent>,
+    sent_messages: Arc<RwLock<Vec<(String, String)>>>,
+    drop_reason: Arc<RwLock<Option<String>>>,
+    is_connected: Arc<RwLock<bool>>,
+    restrictions: Arc<RwLock<HashMap<String, ChatRestriction>>>,
+    account_created: Arc<RwLock<HashMap<String, DateTime<Utc>>>>,
+}
+
+impl MockConnection {
+    pub fn new(platform: &str, channels: Vec<String>) -> Self {
+        let (message_sender, _) = broadcast::channel(1000);
+        let (event_sender, _) = broadcast::channel(100);
+        Self {
+            platform: platform.to_string(),
+            channels,
+            message_sender,
+            event_sender,
+            sent_messages: Arc::new(RwLock::new(Vec::new())),
+            drop_reason: Arc::new(RwLock::new(None)),
+            is_connected: Arc::new(RwLock::new(false)),
+            restrictions: Arc::new(RwLock::new(HashMap::new())),
+            account_created: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Deliver a message as if it arrived from the platform
+    pub fn inject(&self, message: ChatMessage) -> Result<()> {
+        self.message_sender.send(message)
+            .map_err(|_| anyhow::anyhow!("No receivers for mock {} connection", self.platform))?;
+        Ok(())
+    }
+
+    /// Deliver a non-chat event as if the platform reported it
+    pub fn inject_event(&self, event: TwitchEvent) -> Result<()> {
+        self.event_sender.send(event)
+            .map_err(|_| anyhow::anyhow!("No event receivers for mock {} connection", self.platform))?;
+        Ok(())
+    }
+
+    /// Drop every outgoing message with `reason` until cleared with `None`
+    pub async fn set_drop_reason(&self, reason: Option<String>) {
+        *self.drop_reason.write().await = reason;
+    }
+
+    /// Messages sent through this connection as (channel, message) pairs
+    pub async fn sent_messages(&self) -> Vec<(String, String)> {
+        self.sent_messages.read().await.clone()
+    }
+
+    /// Chat restriction currently set on a channel
+    pub async fn chat_restriction(&self, channel: &str) -> Option<ChatRestriction> {
+        self.restrictions.read().await.get(channel).copied()
+    }
+
+    /// Report `created` as the creation date of `username`'s account
+    pub async fn set_account_created(&self, username: &str, created: DateTime<Utc>) {
+        self.account_created.write().await.insert(username.to_lowercase(), created);
+    }
+
+    /// Wait until a sent message matches `predicate`, or give up after `timeout`
+    pub async fn wait_for_sent<F>(&self, timeout: Duration, predicate: F) -> Option<(String, String)>
+    where
+        F: Fn(&str, &str) -> bool,
+    {
+        let deadline = Instant::now() + timeout;
+        loop {
+            if let Some(found) = self.sent_messages.read().await.iter()
+                .find(|(channel, message)| predicate(channel, message))
+            {
+                return Some(found.clone());
+            }
+            if Instant::now() >= deadline {
+                return None;
+            }
+            sleep(Duration::from_millis(10)).await;
+        }
+    }
+}
+
+#[async_trait]
+impl PlatformConnection for MockConnection {
+    async fn connect(&mut self) -> Result<()> {
+        *self.is_connected.write().await = true;
+        Ok(())
+    }
+
+    async fn send_message(&self, channel: &str, message: &str) -> Result<()> {
+        self.send_message_with_status(channel, message).await.map(|_| ())
+    }
+
+    async fn send_message_with_status(&self, channel: &str, message: &str) -> Result<DeliveryStatus> {
+        if !*self.is_connected.read().await {
+            return Err(anyhow::anyhow!("Not connected to {}", self.platform));
+        }
+        if let Some(reason) = self.drop_reason.read().await.clone() {
+            debug!("Mock {} dropped message to #{}: {}", self.platform, channel, reason);
+            return Ok(DeliveryStatus::Dropped { reason });
+        }
+        debug!("Mock {} sent to #{}: {}", self.platform, channel, message);
+        self.sent_messages.write().await.push((channel.to_string(), message.to_string()));
+        Ok(DeliveryStatus::Delivered)
+    }
+
+    async fn set_chat_restriction(&self, channel: &str, restriction: Option<ChatRestriction>) -> Result<()> {
+        let mut restrictions = self.restrictions.write().await;
+        match restriction {
+            Some(restriction) => restrictions.insert(channel.to_string(), restriction),
+            None => restrictions.remove(channel),
+        };
+        Ok(())
+    }
+
+    async fn account_created(&self, username: &str) -> Result<Option<DateTime<Utc>>> {
+        Ok(self.account_created.read().await.get(&username.to_lowercase()).copied())
+    }
+
+    fn platform_name(&self) -> &str {
+        &self.platform
+    }
+
+    async fn is_connected(&self) -> bool {
+        *self.is_connected.read().await
+    }
+
+    fn get_message_receiver(&self) -> Option<broadcast::Receiver<ChatMessage>> {
+        Some(self.message_sender.subscribe())
+    }
+
+    fn get_event_receiver(&self) -> Option<broadcast::Receiver<TwitchEvent>> {
+        Some(self.event_sender.subscribe())
+    }
+
+    fn get_channels(&self) -> Vec<String> {
+        self.channels.clone()
+    }
+
+    async fn disconnect(&mut self) -> Result<()> {
+        *self.is_connected.write().await = false;
+        Ok(())
+    }
+}
@@ -1,10 +1,17 @@
 use anyhow::Result;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use tokio::sync::broadcast;
 
+use crate::bot::redaction::LogRedaction;
+use crate::platforms::twitch_eventsub::TwitchEvent;
 use crate::types::ChatMessage;
 
+pub mod mock;
 pub mod twitch;
+pub mod twitch_auth;
+pub mod twitch_eventsub;
 pub mod youtube;
 
 /// Trait defining the interface all platform connections must implement
@@ -15,6 +22,14 @@ pub trait PlatformConnection: Send + Sync {
     
     /// Send a message to the specified channel
     async fn send_message(&self, channel: &str, message: &str) -> Result<()>;
+
+    /// Send a message and report whether the platform accepted it.
+    ///
+    /// Platforms that give no delivery signal are assumed to have delivered it.
+    async fn send_message_with_status(&self, channel: &str, message: &str) -> Result<DeliveryStatus> {
+        self.send_message(channel, message).await?;
+        Ok(DeliveryStatus::Delivered)
+    }
     
     /// Get the platform identifier (e.g., "twitch", "youtube")
     fn platform_name(&self) -> &str;
@@ -24,10 +39,195 @@ pub trait PlatformConnection: Send + Sync {
     
     /// Get a receiver for incoming messages
     fn get_message_receiver(&self) -> Option<broadcast::Receiver<ChatMessage>>;
+
+    /// Get a receiver for non-chat events (bans, subscriptions), for platforms
+    /// that report them; chat messages still arrive on the message receiver
+    fn get_event_receiver(&self) -> Option<broadcast::Receiver<TwitchEvent>> {
+        None
+    }
     
     /// Get list of channels this connection is active in
     fn get_channels(&self) -> Vec<String>;
     
     /// Gracefully disconnect
     async fn disconnect(&mut self) -> Result<()>;
+
+    /// API quota consumption, for platforms whose API calls are metered
+    async fn quota_usage(&self) -> Option<QuotaUsage> {
+        None
+    }
+
+    /// Restrict who can chat in a channel, or lift the restriction with `None`
+    async fn set_chat_restriction(&self, channel: &str, restriction: Option<ChatRestriction>) -> Result<()> {
+        let _ = (channel, restriction);
+        Err(anyhow::anyhow!("{} doesn't support chat restrictions", self.platform_name()))
+    }
+
+    /// When a user's account was created, for platforms that can look it up
+    async fn account_created(&self, username: &str) -> Result<Option<DateTime<Utc>>> {
+        let _ = username;
+        Ok(None)
+    }
+
+    /// Share the bot's log redaction setting, for connections that log message content
+    fn set_log_redaction(&mut self, log_redaction: LogRedaction) {
+        let _ = log_redaction;
+    }
+
+    /// Connection state for health reporting, telling rejected credentials apart from outages
+    async fn health(&self) -> PlatformHealth {
+        if self.is_connected().await {
+            PlatformHealth::Connected
+        } else {
+            PlatformHealth::Down
+        }
+    }
+}
+
+/// Connection failures that need different handling from ordinary network errors
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum ConnectionError {
+    /// The platform rejected our credentials; retrying won't help until the token is replaced
+    #[error("{platform} authentication failed: {reason}")]
+    AuthFailed { platform: String, reason: String },
+}
+
+/// Longest response body kept in an auth failure reason
+const MAX_REASON_CHARS: usize = 200;
+
+/// IRC notices Twitch sends before closing the socket on a bad PASS
+const TWITCH_AUTH_NOTICES: [&str; 2] = ["Login authentication failed", "Improperly formatted auth"];
+
+impl ConnectionError {
+    /// Classify an HTTP error response; `None` when it isn't an auth failure
+    pub fn from_http_response(platform: &str, status: u16, body: &str) -> Option<Self> {
+        // YouTube reports quota exhaustion as a 403, but the credentials are fine
+        let rate_limited = body.contains("quotaExceeded") || body.contains("rateLimitExceeded");
+        match status {
+            401 => {}
+            403 if !rate_limited => {}
+            _ => return None,
+        }
+
+        let body: String = body.trim().chars().take(MAX_REASON_CHARS).collect();
+        Some(ConnectionError::AuthFailed {
+            platform: platform.to_string(),
+            reason: format!("HTTP {}: {}", status, body),
+        })
+    }
+
+    /// Classify a Twitch IRC line; `None` when it isn't a login failure notice
+    pub fn from_irc_notice(platform: &str, line: &str) -> Option<Self> {
+        if !line.contains("NOTICE") {
+            return None;
+        }
+        TWITCH_AUTH_NOTICES.iter()
+            .find(|notice| line.contains(*notice))
+            .map(|notice| ConnectionError::AuthFailed {
+                platform: platform.to_string(),
+                reason: notice.to_string(),
+            })
+    }
+
+    /// The auth failure behind an error, if that's what it is
+    pub fn auth_failure(error: &anyhow::Error) -> Option<&ConnectionError> {
+        error.downcast_ref::<ConnectionError>()
+    }
+}
+
+/// Platform connection state as shown in health checks and the dashboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlatformHealth {
+    Connected,
+    Down,
+    /// Credentials were rejected; needs a new token rather than a reconnect
+    AuthFailed,
+}
+
+impl PlatformHealth {
+    pub fn is_healthy(&self) -> bool {
+        matches!(self, PlatformHealth::Connected)
+    }
+}
+
+impl std::fmt::Display for PlatformHealth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PlatformHealth::Connected => write!(f, "connected"),
+            PlatformHealth::Down => write!(f, "down"),
+            PlatformHealth::AuthFailed => write!(f, "auth failed"),
+        }
+    }
+}
+
+/// Platform-side limit on who can chat
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChatRestriction {
+    FollowersOnly,
+    SubscribersOnly,
+}
+
+/// Outcome of a sent message as reported by the platform
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeliveryStatus {
+    Delivered,
+    /// The platform accepted the request but didn't post the message
+    Dropped { reason: String },
+}
+
+/// API quota consumption for a platform connection
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuotaUsage {
+    pub used: u32,
+    pub limit: u32,
+}
+
+impl QuotaUsage {
+    pub fn remaining(&self) -> u32 {
+        self.limit.saturating_sub(self.used)
+    }
+
+    /// Share of the quota consumed, from 0.0 to 1.0
+    pub fn fraction_used(&self) -> f64 {
+        if self.limit == 0 {
+            return 1.0;
+        }
+        (self.used as f64 / self.limit as f64).min(1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_http_auth_errors_map_to_auth_failed() {
+        let body = r#"{"error":"Unauthorized","status":401,"message":"Invalid OAuth token"}"#;
+        let error = ConnectionError::from_http_response("twitch", 401, body).unwrap();
+        assert!(matches!(&error, ConnectionError::AuthFailed { platform, reason }
+            if platform == "twitch" && reason.contains("Invalid OAuth token")));
+
+        // Survives being wrapped with context on the way up
+        let wrapped = anyhow::Error::from(error.clone()).context("Failed to resolve Twitch users");
+        assert_eq!(ConnectionError::auth_failure(&wrapped), Some(&error));
+
+        // Quota and server errors aren't credential problems
+        assert!(ConnectionError::from_http_response("youtube", 403, r#"{"reason":"quotaExceeded"}"#).is_none());
+        assert!(ConnectionError::from_http_response("youtube", 500, "backend error").is_none());
+        assert!(ConnectionError::from_http_response("youtube", 403, "forbidden").is_some());
+        assert!(ConnectionError::auth_failure(&anyhow::anyhow!("connection reset by peer")).is_none());
+    }
+
+    #[test]
+    fn test_irc_login_notice_maps_to_auth_failed() {
+        let error = ConnectionError::from_irc_notice("twitch", ":tmi.twitch.tv NOTICE * :Login authentication failed").unwrap();
+        assert_eq!(error.to_string(), "twitch authentication failed: Login authentication failed");
+        assert!(ConnectionError::from_irc_notice("twitch", ":tmi.twitch.tv NOTICE * :Improperly formatted auth").is_some());
+
+        assert!(ConnectionError::from_irc_notice("twitch", ":tmi.twitch.tv 001 notabot :Welcome, GLHF!").is_none());
+        // Chat that happens to quote the notice isn't a login failure
+        assert!(ConnectionError::from_irc_notice("twitch", ":v!v@v.tmi.twitch.tv PRIVMSG #c :Login authentication failed lol").is_none());
+    }
 }
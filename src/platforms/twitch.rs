@@ -3,15 +3,25 @@ use async_trait::async_trait;
 use futures_util::{SinkExt, StreamExt};
 use log::{debug, error, info, warn};
 use std::env;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tokio::sync::{broadcast, RwLock};
+use tokio::time::{timeout, Duration};
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 use url::Url;
 
-use crate::platforms::PlatformConnection;
-use crate::types::ChatMessage;
+use crate::platforms::twitch_auth::{TokenPair, TokenRefreshConfig, TokenRefresher};
+use crate::platforms::twitch_eventsub::{self, EventSubConfig, HelixClient, TwitchEvent};
+use crate::platforms::{ChatRestriction, ConnectionError, DeliveryStatus, PlatformConnection, PlatformHealth};
+use crate::types::{ChatMessage, EmoteSpan};
+use crate::bot::redaction::LogRedaction;
 
 // Type aliases for cleaner code
+const TWITCH_IRC_URL: &str = "wss://irc-ws.chat.twitch.tv:443";
+
+/// How long to wait for Twitch to accept or reject our login
+const LOGIN_TIMEOUT: Duration = Duration::from_secs(10);
+
 type WebSocketWriter = Arc<RwLock<futures_util::stream::SplitSink<tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>, Message>>>;
 
 /// Configuration for Twitch connection
@@ -20,6 +30,12 @@ pub struct TwitchConfig {
     pub username: String,
     pub oauth_token: String, // oauth:your_token_here
     pub channels: Vec<String>,
+    /// Use the EventSub websocket transport instead of IRC when set
+    pub eventsub: Option<EventSubConfig>,
+    /// Refresh an expired OAuth token automatically when set
+    pub token_refresh: Option<TokenRefreshConfig>,
+    /// IRC websocket endpoint
+    pub irc_url: String,
 }
 
 impl TwitchConfig {
@@ -53,6 +69,9 @@ impl TwitchConfig {
             ));
         }
         
+        let eventsub = EventSubConfig::from_env(&oauth_token);
+        let token_refresh = TokenRefreshConfig::from_env();
+
         info!("Loaded Twitch config for user '{}' with {} channels", username, channels.len());
         debug!("Channels: {:?}", channels);
         
@@ -60,30 +79,177 @@ impl TwitchConfig {
             username,
             oauth_token,
             channels,
+            eventsub,
+            token_refresh,
+            irc_url: TWITCH_IRC_URL.to_string(),
         })
     }
+
+    /// Send-only accounts from `TWITCH_SEND_ACCOUNTS`; none when it's unset
+    pub fn send_accounts_from_env(&self) -> Result<Vec<TwitchConfig>> {
+        match env::var("TWITCH_SEND_ACCOUNTS") {
+            Ok(accounts) => self.send_accounts(&accounts),
+            Err(_) => Ok(Vec::new()),
+        }
+    }
+
+    /// Accounts listed as comma-separated `username:oauth:token` entries, joining
+    /// this account's channels over IRC (EventSub and token refresh stay with it)
+    pub fn send_accounts(&self, accounts: &str) -> Result<Vec<TwitchConfig>> {
+        accounts.split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| {
+                let (username, oauth_token) = entry.split_once(':')
+                    .filter(|(username, token)| !username.is_empty() && token.starts_with("oauth:"))
+                    .ok_or_else(|| anyhow::anyhow!(
+                        "TWITCH_SEND_ACCOUNTS entries must look like username:oauth:token - got '{}:...'",
+                        entry.split(':').next().unwrap_or_default()))?;
+                Ok(TwitchConfig {
+                    username: username.to_string(),
+                    oauth_token: oauth_token.to_string(),
+                    eventsub: None,
+                    token_refresh: None,
+                    ..self.clone()
+                })
+            })
+            .collect()
+    }
 }
 
-/// Twitch IRC connection implementation
+/// Twitch connection implementation, over IRC or EventSub
 pub struct TwitchConnection {
     config: TwitchConfig,
     message_sender: Option<broadcast::Sender<ChatMessage>>,
+    event_sender: broadcast::Sender<TwitchEvent>,
     websocket_writer: Option<WebSocketWriter>,
+    helix_client: Option<HelixClient>,
     is_connected: Arc<RwLock<bool>>,
+    /// Set when the last connect attempt was rejected for bad credentials
+    auth_failure: Arc<RwLock<Option<ConnectionError>>>,
+    token_refresher: Option<Arc<TokenRefresher>>,
+    /// Held while a rejected send refreshes the token, so concurrent sends refresh it once
+    send_refresh_lock: Arc<tokio::sync::Mutex<()>>,
+    /// Set on disconnect to stop the EventSub session from reconnecting
+    eventsub_stop: Arc<AtomicBool>,
+    log_redaction: LogRedaction,
 }
 
 impl TwitchConnection {
     pub fn new(config: TwitchConfig) -> Self {
+        let (event_sender, _) = broadcast::channel(100);
+        let token_refresher = config.token_refresh.clone().map(|c| Arc::new(TokenRefresher::new(c)));
         Self {
             config,
             message_sender: None,
+            event_sender,
             websocket_writer: None,
+            helix_client: None,
             is_connected: Arc::new(RwLock::new(false)),
+            auth_failure: Arc::new(RwLock::new(None)),
+            token_refresher,
+            send_refresh_lock: Arc::new(tokio::sync::Mutex::new(())),
+            eventsub_stop: Arc::new(AtomicBool::new(false)),
+            log_redaction: LogRedaction::default(),
+        }
+    }
+
+    /// Use a specific token refresher instead of one built from `TwitchConfig::token_refresh`
+    pub fn with_token_refresher(mut self, refresher: TokenRefresher) -> Self {
+        self.token_refresher = Some(Arc::new(refresher));
+        self
+    }
+
+    /// Switch to a renewed token for IRC and EventSub
+    fn apply_tokens(&mut self, tokens: &TokenPair) {
+        self.config.oauth_token = format!("oauth:{}", tokens.access_token);
+        if let Some(eventsub) = &mut self.config.eventsub {
+            eventsub.access_token = tokens.access_token.clone();
+        }
+    }
+
+    /// Connect once with the current token, over EventSub or IRC
+    async fn connect_with_current_token(&mut self) -> Result<()> {
+        match self.config.eventsub.clone() {
+            Some(eventsub_config) => self.connect_eventsub(eventsub_config).await,
+            None => self.connect_irc().await,
+        }
+    }
+
+    /// Connect over EventSub websocket, sending chat through the Helix API
+    async fn connect_eventsub(&mut self, eventsub_config: EventSubConfig) -> Result<()> {
+        info!("Connecting to Twitch EventSub...");
+
+        let helix = HelixClient::new(eventsub_config);
+        if let Err(e) = helix.resolve_users(&self.config.channels).await {
+            self.record_auth_failure(&e).await;
+            return Err(e.context("Failed to resolve Twitch users for EventSub"));
+        }
+        *self.auth_failure.write().await = None;
+        self.helix_client = Some(helix.clone());
+
+        let (tx, _) = broadcast::channel(1000);
+        self.message_sender = Some(tx.clone());
+        *self.is_connected.write().await = true;
+
+        let event_sender = self.event_sender.clone();
+        let is_connected = Arc::clone(&self.is_connected);
+        // A session left over from an earlier connect stops reconnecting
+        self.eventsub_stop.store(true, Ordering::Relaxed);
+        self.eventsub_stop = Arc::new(AtomicBool::new(false));
+        let stop = Arc::clone(&self.eventsub_stop);
+        let log_redaction = self.log_redaction.clone();
+
+        tokio::spawn(async move {
+            info!("Twitch EventSub reader started");
+            if let Err(e) = twitch_eventsub::run_session(helix, tx, event_sender, Arc::clone(&is_connected), stop, log_redaction).await {
+                error!("Twitch EventSub session failed: {}", e);
+                *is_connected.write().await = false;
+            }
+        });
+
+        info!("Successfully connected to Twitch EventSub");
+        Ok(())
+    }
+
+    /// Remember a credential rejection so health checks report it instead of a plain outage
+    /// Send through Helix, refreshing the token and retrying once if it was rejected
+    async fn send_helix_message(&self, helix: &HelixClient, channel: &str, message: &str) -> Result<DeliveryStatus> {
+        let token = helix.access_token();
+        let error = match helix.send_chat_message(channel, message).await {
+            Err(e) if ConnectionError::auth_failure(&e).is_some() => e,
+            result => return result,
+        };
+        let Some(refresher) = &self.token_refresher else {
+            self.record_auth_failure(&error).await;
+            return Err(error);
+        };
+
+        {
+            let _refreshing = self.send_refresh_lock.lock().await;
+            // Another send may have refreshed it while this one waited
+            if helix.access_token() == token {
+                warn!("Twitch rejected the OAuth token while sending ({}), refreshing it", error);
+                match refresher.refresh().await {
+                    Ok(tokens) => helix.set_access_token(&tokens.access_token),
+                    Err(refresh_error) => {
+                        self.record_auth_failure(&refresh_error).await;
+                        return Err(refresh_error.context("Twitch OAuth token refresh failed"));
+                    }
+                }
+            }
+        }
+        helix.send_chat_message(channel, message).await
+    }
+
+    async fn record_auth_failure(&self, error: &anyhow::Error) {
+        if let Some(auth_error) = ConnectionError::auth_failure(error) {
+            *self.auth_failure.write().await = Some(auth_error.clone());
         }
     }
 
     /// Parse incoming Twitch IRC message into our standard format
-    fn parse_twitch_message(&self, raw_message: &str) -> Option<ChatMessage> {
+    fn parse_twitch_message(raw_message: &str) -> Option<ChatMessage> {
         // Handle multiple messages in one websocket frame
         let lines: Vec<&str> = raw_message.split('\n').collect();
         
@@ -94,7 +260,7 @@ impl TwitchConnection {
             }
             
             if line.starts_with("@") && line.contains("PRIVMSG") {
-                if let Some(parsed) = self.parse_privmsg(line) {
+                if let Some(parsed) = Self::parse_privmsg(line) {
                     return Some(parsed);
                 }
             }
@@ -102,7 +268,7 @@ impl TwitchConnection {
         None
     }
 
-    fn parse_privmsg(&self, line: &str) -> Option<ChatMessage> {
+    fn parse_privmsg(line: &str) -> Option<ChatMessage> {
         // Parse IRC tags and message
         // Format: @badges=...;display-name=...;mod=... :user!user@user.tmi.twitch.tv PRIVMSG #channel :message
         
@@ -121,6 +287,7 @@ impl TwitchConnection {
         let mut is_mod = false;
         let mut is_subscriber = false;
         let mut badges = Vec::new();
+        let mut emotes = Vec::new();
         let mut username = String::new();
 
         if let Some(tags_part) = tags_and_prefix.strip_prefix('@') {
@@ -146,6 +313,7 @@ impl TwitchConnection {
                                     .collect();
                             }
                         }
+                        "emotes" => emotes = parse_emotes_tag(tag_parts[1]),
                         _ => {}
                     }
                 }
@@ -194,23 +362,21 @@ impl TwitchConnection {
             user_badges: badges,
             is_mod,
             is_subscriber,
+            emotes,
         })
     }
-}
 
-#[async_trait]
-impl PlatformConnection for TwitchConnection {
-    async fn connect(&mut self) -> Result<()> {
+    async fn connect_irc(&mut self) -> Result<()> {
         info!("Connecting to Twitch IRC...");
 
-        let url = Url::parse("wss://irc-ws.chat.twitch.tv:443")
+        let url = Url::parse(&self.config.irc_url)
             .context("Failed to parse Twitch WebSocket URL")?;
 
         let (ws_stream, _) = connect_async(url)
             .await
             .context("Failed to connect to Twitch WebSocket")?;
 
-        let (write, read) = ws_stream.split();
+        let (write, mut read) = ws_stream.split();
 
         // Store writer for sending messages
         let writer_arc = Arc::new(RwLock::new(write));
@@ -230,6 +396,32 @@ impl PlatformConnection for TwitchConnection {
         writer_for_pong.write().await.send(Message::Text("CAP REQ :twitch.tv/tags twitch.tv/commands\r\n".to_string())).await
             .context("Failed to request capabilities")?;
 
+        // Twitch answers a bad token with a NOTICE and then closes the socket,
+        // so wait for the welcome before joining anything
+        let login = timeout(LOGIN_TIMEOUT, async {
+            while let Some(frame) = read.next().await {
+                let Message::Text(text) = frame.context("Twitch connection failed during login")? else {
+                    continue;
+                };
+                if let Some(auth_error) = ConnectionError::from_irc_notice("twitch", &text) {
+                    return Err(auth_error.into());
+                }
+                if text.lines().any(|line| line.split_whitespace().nth(1) == Some("001")) {
+                    return Ok(());
+                }
+            }
+            Err(anyhow::anyhow!("Twitch closed the connection during login"))
+        }).await;
+
+        match login {
+            Ok(Ok(())) => *self.auth_failure.write().await = None,
+            Ok(Err(e)) => {
+                self.record_auth_failure(&e).await;
+                return Err(e);
+            }
+            Err(_) => warn!("No login confirmation from Twitch within {:?}, continuing", LOGIN_TIMEOUT),
+        }
+
         // Join channels
         for channel in &self.config.channels {
             let join_msg = format!("JOIN #{}\r\n", channel);
@@ -248,9 +440,7 @@ impl PlatformConnection for TwitchConnection {
         // Spawn message reading task
         let message_sender = tx;
         let is_connected = Arc::clone(&self.is_connected);
-        
-        // Clone self reference for message parsing
-        let config = self.config.clone();
+        let log_redaction = self.log_redaction.clone();
         
         tokio::spawn(async move {
             let mut read = read;
@@ -272,15 +462,8 @@ impl PlatformConnection for TwitchConnection {
                         }
 
                         // Parse and broadcast chat messages
-                        let temp_connection = TwitchConnection {
-                            config: config.clone(),
-                            message_sender: None,
-                            websocket_writer: None,
-                            is_connected: Arc::new(RwLock::new(true)),
-                        };
-                        
-                        if let Some(chat_msg) = temp_connection.parse_twitch_message(&text) {
-                            info!("Parsed message from {}: {}", chat_msg.username, chat_msg.content);
+                        if let Some(chat_msg) = Self::parse_twitch_message(&text) {
+                            info!("Parsed message from {}: {}", chat_msg.username, log_redaction.redact(&chat_msg.content));
                             if let Err(e) = message_sender.send(chat_msg) {
                                 warn!("Failed to broadcast message: {}", e);
                             }
@@ -323,15 +506,54 @@ impl PlatformConnection for TwitchConnection {
         info!("Successfully connected to Twitch IRC");
         Ok(())
     }
+}
+
+#[async_trait]
+impl PlatformConnection for TwitchConnection {
+    async fn connect(&mut self) -> Result<()> {
+        let Some(refresher) = self.token_refresher.clone() else {
+            return self.connect_with_current_token().await;
+        };
+
+        if let Some(stored) = refresher.load_stored().await {
+            debug!("Using Twitch token saved by an earlier refresh");
+            self.apply_tokens(&stored);
+        }
+
+        match self.connect_with_current_token().await {
+            Err(e) if ConnectionError::auth_failure(&e).is_some() => {
+                warn!("Twitch rejected the OAuth token ({}), refreshing it", e);
+                match refresher.refresh().await {
+                    Ok(tokens) => self.apply_tokens(&tokens),
+                    Err(refresh_error) => {
+                        self.record_auth_failure(&refresh_error).await;
+                        return Err(refresh_error.context("Twitch OAuth token refresh failed"));
+                    }
+                }
+                self.connect_with_current_token().await
+            }
+            result => result,
+        }
+    }
 
     async fn send_message(&self, channel: &str, message: &str) -> Result<()> {
+        self.send_message_with_status(channel, message).await.map(|_| ())
+    }
+
+    /// Helix reports dropped messages directly; over IRC drops only show up
+    /// as a later NOTICE, so IRC sends are assumed delivered
+    async fn send_message_with_status(&self, channel: &str, message: &str) -> Result<DeliveryStatus> {
+        if let Some(helix) = &self.helix_client {
+            return self.send_helix_message(helix, channel, message).await;
+        }
+
         if let Some(writer_arc) = &self.websocket_writer {
             let privmsg = format!("PRIVMSG #{} :{}\r\n", channel, message);
             
             match writer_arc.write().await.send(Message::Text(privmsg)).await {
                 Ok(_) => {
                     debug!("Sent message to #{}: {}", channel, message);
-                    Ok(())
+                    Ok(DeliveryStatus::Delivered)
                 }
                 Err(e) => {
                     error!("Failed to send message to #{}: {}", channel, e);
@@ -343,6 +565,22 @@ impl PlatformConnection for TwitchConnection {
         }
     }
 
+    /// Only available over EventSub: IRC chat commands like /followers were retired
+    async fn set_chat_restriction(&self, channel: &str, restriction: Option<ChatRestriction>) -> Result<()> {
+        match &self.helix_client {
+            Some(helix) => helix.update_chat_settings(channel, restriction).await,
+            None => Err(anyhow::anyhow!("Twitch chat restrictions need the EventSub transport")),
+        }
+    }
+
+    /// Only available over EventSub, which has a Helix client to ask
+    async fn account_created(&self, username: &str) -> Result<Option<chrono::DateTime<chrono::Utc>>> {
+        match &self.helix_client {
+            Some(helix) => helix.user_created_at(username).await,
+            None => Ok(None),
+        }
+    }
+
     fn platform_name(&self) -> &str {
         "twitch"
     }
@@ -351,19 +589,266 @@ impl PlatformConnection for TwitchConnection {
         *self.is_connected.read().await
     }
 
+    async fn health(&self) -> PlatformHealth {
+        if self.auth_failure.read().await.is_some() {
+            PlatformHealth::AuthFailed
+        } else if *self.is_connected.read().await {
+            PlatformHealth::Connected
+        } else {
+            PlatformHealth::Down
+        }
+    }
+
     fn get_message_receiver(&self) -> Option<broadcast::Receiver<ChatMessage>> {
         self.message_sender.as_ref().map(|sender| sender.subscribe())
     }
 
+    /// Only populated when using the EventSub transport
+    fn get_event_receiver(&self) -> Option<broadcast::Receiver<TwitchEvent>> {
+        Some(self.event_sender.subscribe())
+    }
+
     fn get_channels(&self) -> Vec<String> {
         self.config.channels.clone()
     }
 
     async fn disconnect(&mut self) -> Result<()> {
+        self.eventsub_stop.store(true, Ordering::Relaxed);
         *self.is_connected.write().await = false;
         self.websocket_writer = None;
+        self.helix_client = None;
         self.message_sender = None;
         info!("Disconnected from Twitch");
         Ok(())
     }
-}
\ No newline at end of file
+
+    fn set_log_redaction(&mut self, log_redaction: LogRedaction) {
+        self.log_redaction = log_redaction;
+    }
+}
+
+/// Parse the IRC `emotes` tag, e.g. `25:0-4,12-16/1902:6-10`
+fn parse_emotes_tag(tag: &str) -> Vec<EmoteSpan> {
+    let mut emotes: Vec<EmoteSpan> = tag.split('/')
+        .filter_map(|emote| emote.split_once(':'))
+        .flat_map(|(id, positions)| {
+            positions.split(',').filter_map(move |range| {
+                let (start, end) = range.split_once('-')?;
+                Some(EmoteSpan {
+                    id: id.to_string(),
+                    start: start.parse().ok()?,
+                    end: end.parse().ok()?,
+                })
+            })
+        })
+        .collect();
+    emotes.sort_by_key(|emote| emote.start);
+    emotes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platforms::twitch_auth::TokenEndpoint;
+    use std::sync::Mutex;
+    use tokio::net::TcpListener;
+
+    /// Accepts only `valid_token`, answering anything else the way Twitch does
+    async fn fake_irc_server(valid_token: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let Ok(mut ws) = tokio_tungstenite::accept_async(stream).await else { continue };
+                let Some(Ok(Message::Text(pass))) = ws.next().await else { continue };
+
+                let reply = if pass.trim() == format!("PASS oauth:{}", valid_token) {
+                    ":tmi.twitch.tv 001 notabot :Welcome, GLHF!"
+                } else {
+                    ":tmi.twitch.tv NOTICE * :Login authentication failed"
+                };
+                let _ = ws.send(Message::Text(reply.to_string())).await;
+                tokio::spawn(async move { while let Some(Ok(_)) = ws.next().await {} });
+            }
+        });
+        url
+    }
+
+    struct FakeTokenEndpoint {
+        used_refresh_tokens: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl TokenEndpoint for FakeTokenEndpoint {
+        async fn refresh(&self, _client_id: &str, _client_secret: &str, refresh_token: &str) -> Result<TokenPair> {
+            self.used_refresh_tokens.lock().unwrap().push(refresh_token.to_string());
+            Ok(TokenPair { access_token: "fresh".to_string(), refresh_token: "rotated".to_string() })
+        }
+    }
+
+    fn config(irc_url: String, token_file: Option<std::path::PathBuf>) -> TwitchConfig {
+        TwitchConfig {
+            username: "notabot".to_string(),
+            oauth_token: "oauth:expired".to_string(),
+            channels: vec!["somechannel".to_string()],
+            eventsub: None,
+            token_refresh: token_file.map(|token_file| TokenRefreshConfig {
+                client_id: "client".to_string(),
+                client_secret: "secret".to_string(),
+                refresh_token: "initial".to_string(),
+                token_file,
+            }),
+            irc_url,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rejected_token_is_refreshed_and_reconnects() {
+        let url = fake_irc_server("fresh").await;
+        let dir = tempfile::tempdir().unwrap();
+        let token_file = dir.path().join("twitch_tokens.json");
+
+        let config = config(url, Some(token_file.clone()));
+        let endpoint = Arc::new(FakeTokenEndpoint { used_refresh_tokens: Mutex::new(Vec::new()) });
+        let refresher = TokenRefresher::with_endpoint(config.token_refresh.clone().unwrap(), endpoint.clone());
+        let mut connection = TwitchConnection::new(config).with_token_refresher(refresher);
+
+        connection.connect().await.unwrap();
+        assert!(connection.is_connected().await);
+        assert_eq!(connection.health().await, PlatformHealth::Connected);
+        assert_eq!(connection.config.oauth_token, "oauth:fresh");
+        assert_eq!(*endpoint.used_refresh_tokens.lock().unwrap(), vec!["initial".to_string()]);
+
+        // The rotated refresh token is kept for the next restart
+        let stored = TokenPair::load(&token_file).await.unwrap().unwrap();
+        assert_eq!(stored.refresh_token, "rotated");
+    }
+
+    #[tokio::test]
+    async fn test_rejected_token_without_refresh_is_auth_failure() {
+        let url = fake_irc_server("fresh").await;
+        let mut connection = TwitchConnection::new(config(url, None));
+
+        let error = connection.connect().await.unwrap_err();
+        assert!(ConnectionError::auth_failure(&error).is_some());
+        assert_eq!(connection.health().await, PlatformHealth::AuthFailed);
+    }
+
+    /// Minimal Helix: resolves every user to id 1 (created 2016-12-14) and only accepts chat sent with `valid_token`
+    async fn fake_helix_server(valid_token: &'static str) -> (String, Arc<Mutex<Vec<String>>>) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let sends = Arc::new(Mutex::new(Vec::new()));
+
+        let seen = Arc::clone(&sends);
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut request = Vec::new();
+                let mut buffer = [0u8; 4096];
+                // Requests here are small enough that headers and body arrive together
+                while !String::from_utf8_lossy(&request).contains("\r\n\r\n") {
+                    match stream.read(&mut buffer).await {
+                        Ok(0) | Err(_) => break,
+                        Ok(n) => request.extend_from_slice(&buffer[..n]),
+                    }
+                }
+                let request = String::from_utf8_lossy(&request).to_string();
+
+                let (status, body) = if request.starts_with("GET /users") {
+                    ("200 OK", r#"{"data":[{"id":"1","login":"somechannel","created_at":"2016-12-14T20:32:28Z"}]}"#)
+                } else if request.to_lowercase().contains(&format!("authorization: bearer {}", valid_token)) {
+                    seen.lock().unwrap().push(valid_token.to_string());
+                    ("200 OK", r#"{"data":[{"message_id":"m1","is_sent":true}]}"#)
+                } else {
+                    seen.lock().unwrap().push("rejected".to_string());
+                    ("401 Unauthorized", r#"{"error":"Unauthorized","status":401,"message":"Invalid OAuth token"}"#)
+                };
+                let response = format!("HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                                       status, body.len(), body);
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
+        });
+        (url, sends)
+    }
+
+    async fn resolved_helix_client(url: String) -> HelixClient {
+        let helix = HelixClient::new(EventSubConfig {
+            client_id: "client".to_string(),
+            access_token: "expired".to_string(),
+        }).with_base_url(url);
+        helix.resolve_users(&["somechannel".to_string()]).await.unwrap();
+        helix
+    }
+
+    #[tokio::test]
+    async fn test_rejected_send_refreshes_the_token_and_retries_once() {
+        let (url, sends) = fake_helix_server("fresh").await;
+        let dir = tempfile::tempdir().unwrap();
+
+        let config = config(TWITCH_IRC_URL.to_string(), Some(dir.path().join("twitch_tokens.json")));
+        let endpoint = Arc::new(FakeTokenEndpoint { used_refresh_tokens: Mutex::new(Vec::new()) });
+        let refresher = TokenRefresher::with_endpoint(config.token_refresh.clone().unwrap(), endpoint.clone());
+        let mut connection = TwitchConnection::new(config).with_token_refresher(refresher);
+
+        let helix = resolved_helix_client(url).await;
+        connection.helix_client = Some(helix.clone());
+
+        let status = connection.send_message_with_status("somechannel", "hello").await.unwrap();
+        assert_eq!(status, DeliveryStatus::Delivered);
+        assert_eq!(*sends.lock().unwrap(), vec!["rejected".to_string(), "fresh".to_string()]);
+        assert_eq!(*endpoint.used_refresh_tokens.lock().unwrap(), vec!["initial".to_string()]);
+        assert_eq!(helix.access_token(), "fresh");
+    }
+
+    #[tokio::test]
+    async fn test_rejected_send_without_refresh_is_auth_failure() {
+        let (url, _) = fake_helix_server("fresh").await;
+        let mut connection = TwitchConnection::new(config(TWITCH_IRC_URL.to_string(), None));
+        connection.helix_client = Some(resolved_helix_client(url).await);
+
+        let error = connection.send_message_with_status("somechannel", "hello").await.unwrap_err();
+        assert!(ConnectionError::auth_failure(&error).is_some());
+        assert_eq!(connection.health().await, PlatformHealth::AuthFailed);
+    }
+
+    #[tokio::test]
+    async fn test_account_created_needs_helix() {
+        let (url, _) = fake_helix_server("fresh").await;
+        let mut connection = TwitchConnection::new(config(TWITCH_IRC_URL.to_string(), None));
+        assert_eq!(connection.account_created("somechannel").await.unwrap(), None);
+
+        connection.helix_client = Some(resolved_helix_client(url).await);
+        let created = connection.account_created("somechannel").await.unwrap().unwrap();
+        assert_eq!(created.to_rfc3339(), "2016-12-14T20:32:28+00:00");
+    }
+
+    #[test]
+    fn test_send_accounts_share_the_main_accounts_channels() {
+        let main = config(TWITCH_IRC_URL.to_string(), Some("tokens.json".into()));
+        let accounts = main.send_accounts("verifiedbot:oauth:abc123, backupbot:oauth:def456,").unwrap();
+        assert_eq!(accounts.len(), 2);
+        assert_eq!(accounts[0].username, "verifiedbot");
+        assert_eq!(accounts[0].oauth_token, "oauth:abc123");
+        assert_eq!(accounts[1].channels, main.channels);
+        assert!(accounts[1].token_refresh.is_none());
+
+        assert!(main.send_accounts("").unwrap().is_empty());
+        let error = main.send_accounts("verifiedbot:abc123").unwrap_err().to_string();
+        assert!(error.contains("verifiedbot") && !error.contains("abc123"), "{}", error);
+    }
+
+    #[test]
+    fn test_privmsg_emote_spans() {
+        let line = "@badges=;display-name=Viewer;emotes=25:0-4,12-16/1902:6-10;mod=0;subscriber=0 \
+            :viewer!viewer@viewer.tmi.twitch.tv PRIVMSG #somechannel :Kappa Keepo Kappa";
+
+        let message = TwitchConnection::parse_privmsg(line).unwrap();
+        let emotes: Vec<_> = message.emotes.iter().map(|e| (e.id.as_str(), e.start, e.end)).collect();
+        assert_eq!(emotes, vec![("25", 0, 4), ("1902", 6, 10), ("25", 12, 16)]);
+
+        let plain = TwitchConnection::parse_privmsg(&line.replace("emotes=25:0-4,12-16/1902:6-10", "emotes=")).unwrap();
+        assert!(plain.emotes.is_empty());
+    }
+}
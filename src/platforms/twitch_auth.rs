@@ -0,0 +1,204 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::fs;
+use tokio::sync::RwLock;
+
+use crate::bot::data_dir::{DataDir, TWITCH_TOKEN_FILE};
+use crate::platforms::ConnectionError;
+
+const TOKEN_URL: &str = "https://id.twitch.tv/oauth2/token";
+
+/// Client credentials for renewing an expired Twitch user token
+#[derive(Debug, Clone)]
+pub struct TokenRefreshConfig {
+    pub client_id: String,
+    pub client_secret: String,
+    pub refresh_token: String,
+    /// Renewed tokens are saved here so they survive a restart
+    pub token_file: PathBuf,
+}
+
+impl TokenRefreshConfig {
+    /// Load refresh settings from the environment. Returns `None` (no automatic
+    /// refresh) unless `TWITCH_REFRESH_TOKEN`, `TWITCH_CLIENT_ID` and
+    /// `TWITCH_CLIENT_SECRET` are all set. A relative `TWITCH_TOKEN_FILE` is
+    /// placed under the data directory.
+    pub fn from_env() -> Option<Self> {
+        let var = |name: &str| env::var(name).ok().filter(|v| !v.is_empty());
+        let refresh_token = var("TWITCH_REFRESH_TOKEN")?;
+
+        let (Some(client_id), Some(client_secret)) = (var("TWITCH_CLIENT_ID"), var("TWITCH_CLIENT_SECRET")) else {
+            warn!("TWITCH_REFRESH_TOKEN is set but TWITCH_CLIENT_ID/TWITCH_CLIENT_SECRET are not; token refresh disabled");
+            return None;
+        };
+
+        info!("Twitch OAuth token refresh enabled");
+        Some(Self {
+            client_id,
+            client_secret,
+            refresh_token,
+            token_file: DataDir::from_env().resolve(Path::new(&var("TWITCH_TOKEN_FILE").unwrap_or_else(|| TWITCH_TOKEN_FILE.to_string()))),
+        })
+    }
+}
+
+/// A user access token and the refresh token that renews it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TokenPair {
+    /// Without the "oauth:" prefix
+    pub access_token: String,
+    pub refresh_token: String,
+}
+
+impl TokenPair {
+    /// Tokens saved by an earlier refresh, if any
+    pub async fn load(path: &Path) -> Result<Option<Self>> {
+        match fs::read_to_string(path).await {
+            Ok(content) => Ok(Some(serde_json::from_str(&content)
+                .with_context(|| format!("Invalid token file {}", path.display()))?)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e).with_context(|| format!("Failed to read token file {}", path.display())),
+        }
+    }
+
+    /// Save the tokens readable by the owner only, replacing the file atomically
+    pub async fn save(&self, path: &Path) -> Result<()> {
+        let tmp_path = path.with_extension("tmp");
+        let content = serde_json::to_string_pretty(self)?;
+
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        options.mode(0o600);
+
+        let mut file = options.open(&tmp_path).await
+            .with_context(|| format!("Failed to create token file {}", tmp_path.display()))?;
+        tokio::io::AsyncWriteExt::write_all(&mut file, content.as_bytes()).await?;
+        file.sync_all().await?;
+        fs::rename(&tmp_path, path).await
+            .with_context(|| format!("Failed to save token file {}", path.display()))?;
+        Ok(())
+    }
+}
+
+/// Exchanges a refresh token for a new token pair
+#[async_trait]
+pub trait TokenEndpoint: Send + Sync {
+    async fn refresh(&self, client_id: &str, client_secret: &str, refresh_token: &str) -> Result<TokenPair>;
+}
+
+/// Twitch's OAuth token endpoint
+pub struct TwitchTokenEndpoint;
+
+#[async_trait]
+impl TokenEndpoint for TwitchTokenEndpoint {
+    async fn refresh(&self, client_id: &str, client_secret: &str, refresh_token: &str) -> Result<TokenPair> {
+        let response = reqwest::Client::new()
+            .post(TOKEN_URL)
+            .form(&[
+                ("grant_type", "refresh_token"),
+                ("refresh_token", refresh_token),
+                ("client_id", client_id),
+                ("client_secret", client_secret),
+            ])
+            .send()
+            .await
+            .context("Failed to reach the Twitch token endpoint")?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            // A revoked refresh token comes back as 400 "Invalid refresh token"
+            return Err(ConnectionError::from_http_response("twitch", status.as_u16(), &body)
+                .unwrap_or(ConnectionError::AuthFailed {
+                    platform: "twitch".to_string(),
+                    reason: format!("token refresh rejected ({}): {}", status, body.trim()),
+                })
+                .into());
+        }
+
+        response.json().await.context("Failed to parse Twitch token response")
+    }
+}
+
+/// Renews the Twitch token on demand, keeping the rotated refresh token
+pub struct TokenRefresher {
+    config: TokenRefreshConfig,
+    endpoint: Arc<dyn TokenEndpoint>,
+    refresh_token: RwLock<String>,
+}
+
+impl TokenRefresher {
+    pub fn new(config: TokenRefreshConfig) -> Self {
+        Self::with_endpoint(config, Arc::new(TwitchTokenEndpoint))
+    }
+
+    pub fn with_endpoint(config: TokenRefreshConfig, endpoint: Arc<dyn TokenEndpoint>) -> Self {
+        let refresh_token = RwLock::new(config.refresh_token.clone());
+        Self {
+            config,
+            endpoint,
+            refresh_token,
+        }
+    }
+
+    /// Tokens saved by an earlier refresh; these supersede the configured ones
+    /// because Twitch invalidates a refresh token once it has been used
+    pub async fn load_stored(&self) -> Option<TokenPair> {
+        match TokenPair::load(&self.config.token_file).await {
+            Ok(Some(tokens)) => {
+                *self.refresh_token.write().await = tokens.refresh_token.clone();
+                Some(tokens)
+            }
+            Ok(None) => None,
+            Err(e) => {
+                warn!("Ignoring stored Twitch tokens: {:#}", e);
+                None
+            }
+        }
+    }
+
+    /// Get a new access token and persist the new pair
+    pub async fn refresh(&self) -> Result<TokenPair> {
+        let refresh_token = self.refresh_token.read().await.clone();
+        let tokens = self.endpoint
+            .refresh(&self.config.client_id, &self.config.client_secret, &refresh_token)
+            .await?;
+
+        *self.refresh_token.write().await = tokens.refresh_token.clone();
+        if let Err(e) = tokens.save(&self.config.token_file).await {
+            warn!("Refreshed Twitch token could not be saved and will be lost on restart: {:#}", e);
+        }
+
+        info!("Refreshed Twitch OAuth token");
+        Ok(tokens)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_token_file_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tokens.json");
+        assert_eq!(TokenPair::load(&path).await.unwrap(), None);
+
+        let tokens = TokenPair { access_token: "abc".to_string(), refresh_token: "def".to_string() };
+        tokens.save(&path).await.unwrap();
+        assert_eq!(TokenPair::load(&path).await.unwrap(), Some(tokens));
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
+}
@@ -0,0 +1,812 @@
+use anyhow::{Context, Result};
+use futures_util::StreamExt;
+use log::{debug, error, info, warn};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::env;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::sync::{broadcast, RwLock};
+use tokio::time::{timeout, Duration};
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+use url::Url;
+
+use crate::platforms::{ChatRestriction, ConnectionError, DeliveryStatus};
+use crate::types::{ChatMessage, EmoteSpan};
+use crate::bot::redaction::LogRedaction;
+
+const EVENTSUB_WEBSOCKET_URL: &str = "wss://eventsub.wss.twitch.tv/ws";
+const HELIX_BASE_URL: &str = "https://api.twitch.tv/helix";
+
+/// Extra time allowed past the keepalive timeout before the session is considered dead
+const KEEPALIVE_GRACE_SECONDS: u64 = 5;
+
+/// First wait before reconnecting a dropped EventSub session, doubled per
+/// failed attempt up to `RECONNECT_MAX_BACKOFF`
+const RECONNECT_INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const RECONNECT_MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Configuration for the Twitch EventSub websocket transport
+#[derive(Debug, Clone)]
+pub struct EventSubConfig {
+    pub client_id: String,
+    /// User access token (without the "oauth:" prefix)
+    pub access_token: String,
+}
+
+impl EventSubConfig {
+    /// Load EventSub settings from the environment. Returns `None` when
+    /// `TWITCH_EVENTSUB_CLIENT_ID` is unset, in which case IRC is used.
+    /// Falls back to the IRC OAuth token if `TWITCH_EVENTSUB_TOKEN` is unset.
+    pub fn from_env(irc_oauth_token: &str) -> Option<Self> {
+        let client_id = env::var("TWITCH_EVENTSUB_CLIENT_ID").ok().filter(|id| !id.is_empty())?;
+        let access_token = env::var("TWITCH_EVENTSUB_TOKEN")
+            .unwrap_or_else(|_| irc_oauth_token.trim_start_matches("oauth:").to_string());
+
+        info!("Twitch EventSub transport configured");
+        Some(Self {
+            client_id,
+            access_token,
+        })
+    }
+}
+
+/// Twitch events delivered over EventSub
+#[derive(Debug, Clone)]
+pub enum TwitchEvent {
+    ChatMessage(ChatMessage),
+    UserBanned {
+        channel: String,
+        username: String,
+        moderator: String,
+        reason: String,
+        /// `None` for permanent bans
+        ends_at: Option<chrono::DateTime<chrono::Utc>>,
+    },
+    Subscription {
+        channel: String,
+        username: String,
+        tier: String,
+        is_gift: bool,
+    },
+    /// Another broadcaster raided `channel`
+    Raid {
+        channel: String,
+        raider: String,
+        viewers: u64,
+    },
+}
+
+/// A decoded EventSub websocket frame
+#[derive(Debug, Clone)]
+pub enum EventSubFrame {
+    Welcome { session_id: String, keepalive_timeout_seconds: Option<u64> },
+    Keepalive,
+    Reconnect { reconnect_url: String },
+    Notification(TwitchEvent),
+    Revocation { subscription_type: String },
+    /// Notification for a subscription type we don't map
+    Unhandled(String),
+}
+
+#[derive(Debug, Deserialize)]
+struct EventSubEnvelope {
+    metadata: EventSubMetadata,
+    #[serde(default)]
+    payload: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct EventSubMetadata {
+    message_type: String,
+    subscription_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatMessageEvent {
+    broadcaster_user_login: String,
+    chatter_user_login: String,
+    chatter_user_name: String,
+    message: ChatMessageBody,
+    #[serde(default)]
+    badges: Vec<ChatBadge>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatMessageBody {
+    text: String,
+    #[serde(default)]
+    fragments: Vec<ChatFragment>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatFragment {
+    #[serde(rename = "type")]
+    fragment_type: String,
+    text: String,
+    #[serde(default)]
+    emote: Option<FragmentEmote>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FragmentEmote {
+    id: String,
+}
+
+impl ChatMessageBody {
+    /// Emote positions, worked out from the fragment lengths since EventSub doesn't send them
+    fn emote_spans(&self) -> Vec<EmoteSpan> {
+        let mut spans = Vec::new();
+        let mut position = 0;
+        for fragment in &self.fragments {
+            let length = fragment.text.chars().count();
+            let emote = fragment.emote.as_ref().filter(|_| fragment.fragment_type == "emote" && length > 0);
+            if let Some(emote) = emote {
+                spans.push(EmoteSpan {
+                    id: emote.id.clone(),
+                    start: position,
+                    end: position + length - 1,
+                });
+            }
+            position += length;
+        }
+        spans
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatBadge {
+    set_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BanEvent {
+    broadcaster_user_login: String,
+    user_login: String,
+    moderator_user_login: String,
+    #[serde(default)]
+    reason: String,
+    ends_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RaidEvent {
+    from_broadcaster_user_login: String,
+    to_broadcaster_user_login: String,
+    #[serde(default)]
+    viewers: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct SubscribeEvent {
+    broadcaster_user_login: String,
+    user_login: String,
+    tier: String,
+    #[serde(default)]
+    is_gift: bool,
+}
+
+/// Decode a raw EventSub websocket message
+pub fn parse_frame(text: &str) -> Result<EventSubFrame> {
+    let envelope: EventSubEnvelope = serde_json::from_str(text)
+        .context("Failed to parse EventSub message")?;
+
+    match envelope.metadata.message_type.as_str() {
+        "session_welcome" => {
+            let session = &envelope.payload["session"];
+            let session_id = session["id"].as_str()
+                .ok_or_else(|| anyhow::anyhow!("EventSub welcome message missing session id"))?
+                .to_string();
+            Ok(EventSubFrame::Welcome {
+                session_id,
+                keepalive_timeout_seconds: session["keepalive_timeout_seconds"].as_u64(),
+            })
+        }
+        "session_keepalive" => Ok(EventSubFrame::Keepalive),
+        "session_reconnect" => {
+            let reconnect_url = envelope.payload["session"]["reconnect_url"].as_str()
+                .ok_or_else(|| anyhow::anyhow!("EventSub reconnect message missing reconnect_url"))?
+                .to_string();
+            Ok(EventSubFrame::Reconnect { reconnect_url })
+        }
+        "revocation" => Ok(EventSubFrame::Revocation {
+            subscription_type: envelope.metadata.subscription_type.unwrap_or_default(),
+        }),
+        "notification" => {
+            let subscription_type = envelope.metadata.subscription_type.unwrap_or_default();
+            let event = envelope.payload["event"].clone();
+            match map_notification(&subscription_type, event)? {
+                Some(event) => Ok(EventSubFrame::Notification(event)),
+                None => Ok(EventSubFrame::Unhandled(subscription_type)),
+            }
+        }
+        other => Err(anyhow::anyhow!("Unknown EventSub message type: {}", other)),
+    }
+}
+
+/// Map an EventSub notification event to an internal event
+fn map_notification(subscription_type: &str, event: serde_json::Value) -> Result<Option<TwitchEvent>> {
+    let mapped = match subscription_type {
+        "channel.chat.message" => {
+            let event: ChatMessageEvent = serde_json::from_value(event)?;
+            let badges: Vec<String> = event.badges.into_iter().map(|b| b.set_id).collect();
+            let is_mod = badges.iter().any(|b| b == "moderator" || b == "broadcaster");
+            let is_subscriber = badges.iter().any(|b| b == "subscriber" || b == "founder");
+            let emotes = event.message.emote_spans();
+
+            TwitchEvent::ChatMessage(ChatMessage {
+                platform: "twitch".to_string(),
+                channel: event.broadcaster_user_login,
+                username: event.chatter_user_login,
+                display_name: Some(event.chatter_user_name),
+                content: event.message.text,
+                timestamp: chrono::Utc::now(),
+                user_badges: badges,
+                is_mod,
+                is_subscriber,
+                emotes,
+            })
+        }
+        "channel.ban" => {
+            let event: BanEvent = serde_json::from_value(event)?;
+            TwitchEvent::UserBanned {
+                channel: event.broadcaster_user_login,
+                username: event.user_login,
+                moderator: event.moderator_user_login,
+                reason: event.reason,
+                ends_at: event.ends_at,
+            }
+        }
+        "channel.subscribe" => {
+            let event: SubscribeEvent = serde_json::from_value(event)?;
+            TwitchEvent::Subscription {
+                channel: event.broadcaster_user_login,
+                username: event.user_login,
+                tier: event.tier,
+                is_gift: event.is_gift,
+            }
+        }
+        "channel.raid" => {
+            let event: RaidEvent = serde_json::from_value(event)?;
+            TwitchEvent::Raid {
+                channel: event.to_broadcaster_user_login,
+                raider: event.from_broadcaster_user_login,
+                viewers: event.viewers,
+            }
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(mapped))
+}
+
+/// Helix API client for EventSub subscriptions and sending chat
+#[derive(Clone)]
+pub struct HelixClient {
+    config: EventSubConfig,
+    http_client: reqwest::Client,
+    base_url: String,
+    /// Starts as `config.access_token`; replaced when the token is refreshed
+    access_token: Arc<std::sync::RwLock<String>>,
+    /// Channel login -> broadcaster user id
+    broadcaster_ids: Arc<RwLock<HashMap<String, String>>>,
+    /// User id of the account the token belongs to
+    bot_user_id: Arc<RwLock<Option<String>>>,
+}
+
+impl HelixClient {
+    pub fn new(config: EventSubConfig) -> Self {
+        Self {
+            access_token: Arc::new(std::sync::RwLock::new(config.access_token.clone())),
+            config,
+            http_client: reqwest::Client::new(),
+            base_url: HELIX_BASE_URL.to_string(),
+            broadcaster_ids: Arc::new(RwLock::new(HashMap::new())),
+            bot_user_id: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    /// Send requests to another Helix host (a local fake in tests)
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    /// The token requests are currently sent with
+    pub fn access_token(&self) -> String {
+        self.access_token.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Use a refreshed token for this client and every clone of it
+    pub fn set_access_token(&self, access_token: &str) {
+        *self.access_token.write().unwrap_or_else(|e| e.into_inner()) = access_token.to_string();
+    }
+
+    fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        self.http_client
+            .request(method, format!("{}{}", self.base_url, path))
+            .header("Client-Id", &self.config.client_id)
+            .bearer_auth(self.access_token())
+    }
+
+    /// Parse a Helix response body, reporting a rejected token as an auth failure
+    async fn read_json(response: reqwest::Response) -> Result<serde_json::Value> {
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response.json().await?);
+        }
+
+        let body = response.text().await.unwrap_or_default();
+        if let Some(auth_error) = ConnectionError::from_http_response("twitch", status.as_u16(), &body) {
+            return Err(auth_error.into());
+        }
+        Err(anyhow::anyhow!("Helix request failed {}: {}", status, body))
+    }
+
+    /// Look up user ids for the token owner and each channel
+    pub async fn resolve_users(&self, channels: &[String]) -> Result<()> {
+        let response = Self::read_json(self.request(reqwest::Method::GET, "/users").send().await?).await?;
+        let bot_id = response["data"][0]["id"].as_str()
+            .ok_or_else(|| anyhow::anyhow!("Could not determine user id for EventSub token"))?;
+        *self.bot_user_id.write().await = Some(bot_id.to_string());
+
+        let query: Vec<(&str, &str)> = channels.iter().map(|c| ("login", c.as_str())).collect();
+        let response = Self::read_json(self.request(reqwest::Method::GET, "/users").query(&query).send().await?).await?;
+
+        let mut ids = self.broadcaster_ids.write().await;
+        for user in response["data"].as_array().into_iter().flatten() {
+            if let (Some(login), Some(id)) = (user["login"].as_str(), user["id"].as_str()) {
+                ids.insert(login.to_string(), id.to_string());
+            }
+        }
+        for channel in channels {
+            if !ids.contains_key(channel) {
+                warn!("Could not resolve Twitch channel '{}' for EventSub", channel);
+            }
+        }
+        Ok(())
+    }
+
+    /// When a user's account was created, or `None` if there's no such user
+    pub async fn user_created_at(&self, login: &str) -> Result<Option<chrono::DateTime<chrono::Utc>>> {
+        let response = Self::read_json(
+            self.request(reqwest::Method::GET, "/users").query(&[("login", login)]).send().await?
+        ).await?;
+        let Some(created_at) = response["data"][0]["created_at"].as_str() else {
+            return Ok(None);
+        };
+        Ok(Some(chrono::DateTime::parse_from_rfc3339(created_at)?.with_timezone(&chrono::Utc)))
+    }
+
+    /// Subscribe the websocket session to chat, ban, subscription and raid events for every channel
+    pub async fn subscribe_all(&self, session_id: &str) -> Result<()> {
+        let bot_user_id = self.bot_user_id.read().await.clone()
+            .ok_or_else(|| anyhow::anyhow!("EventSub users not resolved"))?;
+        let broadcaster_ids = self.broadcaster_ids.read().await.clone();
+
+        for (channel, broadcaster_id) in &broadcaster_ids {
+            let subscriptions = [
+                ("channel.chat.message", serde_json::json!({ "broadcaster_user_id": broadcaster_id, "user_id": bot_user_id })),
+                ("channel.ban", serde_json::json!({ "broadcaster_user_id": broadcaster_id })),
+                ("channel.subscribe", serde_json::json!({ "broadcaster_user_id": broadcaster_id })),
+                ("channel.raid", serde_json::json!({ "to_broadcaster_user_id": broadcaster_id })),
+            ];
+
+            for (subscription_type, condition) in subscriptions {
+                let body = serde_json::json!({
+                    "type": subscription_type,
+                    "version": "1",
+                    "condition": condition,
+                    "transport": { "method": "websocket", "session_id": session_id },
+                });
+                let response = self.request(reqwest::Method::POST, "/eventsub/subscriptions")
+                    .json(&body)
+                    .send().await?;
+
+                if response.status().is_success() {
+                    debug!("Subscribed to {} for #{}", subscription_type, channel);
+                } else {
+                    // Missing scopes only disable that event type, so keep going
+                    let status = response.status();
+                    let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+                    warn!("EventSub subscription {} for #{} failed {}: {}", subscription_type, channel, status, error_text);
+                }
+            }
+            info!("Subscribed to EventSub events for #{}", channel);
+        }
+        Ok(())
+    }
+
+    /// Send a chat message through the Helix API
+    pub async fn send_chat_message(&self, channel: &str, message: &str) -> Result<DeliveryStatus> {
+        let broadcaster_id = self.broadcaster_ids.read().await.get(channel).cloned()
+            .ok_or_else(|| anyhow::anyhow!("Unknown Twitch channel: {}", channel))?;
+        let sender_id = self.bot_user_id.read().await.clone()
+            .ok_or_else(|| anyhow::anyhow!("EventSub users not resolved"))?;
+
+        let body = serde_json::json!({
+            "broadcaster_id": broadcaster_id,
+            "sender_id": sender_id,
+            "message": message,
+        });
+        let response = self.request(reqwest::Method::POST, "/chat/messages")
+            .json(&body)
+            .send().await
+            .context("Failed to send Twitch chat message")?;
+
+        if response.status().is_success() {
+            let body: serde_json::Value = response.json().await.unwrap_or_default();
+            let status = parse_send_result(&body);
+            match &status {
+                DeliveryStatus::Delivered => debug!("Sent message to #{}: {}", channel, message),
+                DeliveryStatus::Dropped { reason } => warn!("Twitch dropped message to #{}: {}", channel, reason),
+            }
+            Ok(status)
+        } else {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+            if let Some(auth_error) = ConnectionError::from_http_response("twitch", status.as_u16(), &error_text) {
+                return Err(auth_error.into());
+            }
+            Err(anyhow::anyhow!("Failed to send Twitch message {}: {}", status, error_text))
+        }
+    }
+
+    /// Switch follower/subscriber-only mode for a channel (needs the
+    /// moderator:manage:chat_settings scope)
+    pub async fn update_chat_settings(&self, channel: &str, restriction: Option<ChatRestriction>) -> Result<()> {
+        let broadcaster_id = self.broadcaster_ids.read().await.get(channel).cloned()
+            .ok_or_else(|| anyhow::anyhow!("Unknown Twitch channel: {}", channel))?;
+        let moderator_id = self.bot_user_id.read().await.clone()
+            .ok_or_else(|| anyhow::anyhow!("EventSub users not resolved"))?;
+
+        let response = self.request(reqwest::Method::PATCH, "/chat/settings")
+            .query(&[("broadcaster_id", broadcaster_id), ("moderator_id", moderator_id)])
+            .json(&chat_settings_body(restriction))
+            .send().await
+            .context("Failed to update Twitch chat settings")?;
+        Self::read_json(response).await?;
+
+        info!("Chat restriction for #{} set to {:?}", channel, restriction);
+        Ok(())
+    }
+}
+
+/// Chat settings update for a restriction; `None` turns both modes off
+pub fn chat_settings_body(restriction: Option<ChatRestriction>) -> serde_json::Value {
+    serde_json::json!({
+        "follower_mode": restriction == Some(ChatRestriction::FollowersOnly),
+        "subscriber_mode": restriction == Some(ChatRestriction::SubscribersOnly),
+    })
+}
+
+/// Read the delivery outcome from a Send Chat Message response; Twitch
+/// answers 200 with `is_sent: false` and a drop reason for messages it
+/// rejects (duplicates, AutoMod, channel settings)
+pub fn parse_send_result(body: &serde_json::Value) -> DeliveryStatus {
+    let result = &body["data"][0];
+    if result["is_sent"].as_bool() != Some(false) {
+        return DeliveryStatus::Delivered;
+    }
+
+    let drop_reason = &result["drop_reason"];
+    let reason = drop_reason["message"].as_str()
+        .or_else(|| drop_reason["code"].as_str())
+        .unwrap_or("unknown reason")
+        .to_string();
+    DeliveryStatus::Dropped { reason }
+}
+
+/// Run EventSub websocket sessions until `stop` is set: follow reconnect
+/// requests, reconnect with backoff when a session drops, and broadcast
+/// mapped events
+pub async fn run_session(
+    helix: HelixClient,
+    message_sender: broadcast::Sender<ChatMessage>,
+    event_sender: broadcast::Sender<TwitchEvent>,
+    is_connected: Arc<RwLock<bool>>,
+    stop: Arc<AtomicBool>,
+    log_redaction: LogRedaction,
+) -> Result<()> {
+    let mut backoff = RECONNECT_INITIAL_BACKOFF;
+
+    loop {
+        let result = run_connection(&helix, &message_sender, &event_sender, &is_connected, &stop, &log_redaction).await;
+        if stop.load(Ordering::Relaxed) {
+            break;
+        }
+        *is_connected.write().await = false;
+
+        match result {
+            // A session that got going starts the backoff over
+            Ok(true) => backoff = RECONNECT_INITIAL_BACKOFF,
+            Ok(false) => {}
+            Err(e) if ConnectionError::auth_failure(&e).is_some() => return Err(e),
+            Err(e) => error!("Twitch EventSub session failed: {:#}", e),
+        }
+
+        warn!("Reconnecting to Twitch EventSub in {:?}", backoff);
+        tokio::time::sleep(backoff).await;
+        backoff = (backoff * 2).min(RECONNECT_MAX_BACKOFF);
+    }
+
+    *is_connected.write().await = false;
+    info!("Twitch EventSub session ended");
+    Ok(())
+}
+
+/// One websocket connection, including any sessions Twitch moves it to with
+/// reconnect requests. Returns whether a session was established before it
+/// dropped.
+async fn run_connection(
+    helix: &HelixClient,
+    message_sender: &broadcast::Sender<ChatMessage>,
+    event_sender: &broadcast::Sender<TwitchEvent>,
+    is_connected: &RwLock<bool>,
+    stop: &AtomicBool,
+    log_redaction: &LogRedaction,
+) -> Result<bool> {
+    let mut url = EVENTSUB_WEBSOCKET_URL.to_string();
+    // A fresh connection starts without subscriptions; they carry over to reconnect sessions
+    let mut subscribed = false;
+    let mut established = false;
+
+    'session: loop {
+        let (ws_stream, _) = connect_async(Url::parse(&url)?)
+            .await
+            .context("Failed to connect to Twitch EventSub")?;
+        let (_write, mut read) = ws_stream.split();
+        // Twitch sends a welcome within 10 seconds of connecting
+        let mut keepalive = Duration::from_secs(10 + KEEPALIVE_GRACE_SECONDS);
+
+        loop {
+            if stop.load(Ordering::Relaxed) {
+                break 'session;
+            }
+
+            let frame = match timeout(keepalive, read.next()).await {
+                Err(_) => {
+                    warn!("No EventSub message within {:?}, closing session", keepalive);
+                    break 'session;
+                }
+                Ok(Some(Ok(Message::Text(text)))) => text,
+                Ok(Some(Ok(Message::Close(close_frame)))) => {
+                    info!("EventSub connection closed: {:?}", close_frame);
+                    break 'session;
+                }
+                Ok(Some(Ok(_))) => continue,
+                Ok(Some(Err(e))) => {
+                    error!("EventSub websocket error: {}", e);
+                    break 'session;
+                }
+                Ok(None) => {
+                    warn!("EventSub stream ended");
+                    break 'session;
+                }
+            };
+
+            match parse_frame(&frame) {
+                Ok(EventSubFrame::Welcome { session_id, keepalive_timeout_seconds }) => {
+                    if let Some(seconds) = keepalive_timeout_seconds {
+                        keepalive = Duration::from_secs(seconds + KEEPALIVE_GRACE_SECONDS);
+                    }
+                    if !subscribed {
+                        helix.subscribe_all(&session_id).await?;
+                        subscribed = true;
+                    }
+                    established = true;
+                    *is_connected.write().await = true;
+                    info!("EventSub session {} established", session_id);
+                }
+                Ok(EventSubFrame::Keepalive) => debug!("EventSub keepalive"),
+                Ok(EventSubFrame::Reconnect { reconnect_url }) => {
+                    info!("EventSub requested reconnect");
+                    url = reconnect_url;
+                    continue 'session;
+                }
+                Ok(EventSubFrame::Notification(TwitchEvent::ChatMessage(chat_msg))) => {
+                    info!("Parsed message from {}: {}", chat_msg.username, log_redaction.redact(&chat_msg.content));
+                    if let Err(e) = message_sender.send(chat_msg) {
+                        warn!("Failed to broadcast message: {}", e);
+                    }
+                }
+                Ok(EventSubFrame::Notification(event)) => {
+                    debug!("EventSub event: {:?}", event);
+                    // No receivers is normal when nothing listens for non-chat events
+                    let _ = event_sender.send(event);
+                }
+                Ok(EventSubFrame::Revocation { subscription_type }) => {
+                    warn!("EventSub subscription revoked: {}", subscription_type);
+                }
+                Ok(EventSubFrame::Unhandled(subscription_type)) => {
+                    debug!("Ignoring EventSub notification: {}", subscription_type);
+                }
+                Err(e) => warn!("{}", e),
+            }
+        }
+    }
+
+    Ok(established)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_welcome() {
+        let frame = parse_frame(r#"{
+            "metadata": {"message_id": "1", "message_type": "session_welcome", "message_timestamp": "2024-01-01T00:00:00Z"},
+            "payload": {"session": {"id": "AQoQexAWVYKSTIu4ec_2VAxyuhAB", "status": "connected", "keepalive_timeout_seconds": 10, "reconnect_url": null}}
+        }"#).unwrap();
+
+        match frame {
+            EventSubFrame::Welcome { session_id, keepalive_timeout_seconds } => {
+                assert_eq!(session_id, "AQoQexAWVYKSTIu4ec_2VAxyuhAB");
+                assert_eq!(keepalive_timeout_seconds, Some(10));
+            }
+            other => panic!("Expected welcome, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_map_chat_message() {
+        let frame = parse_frame(r##"{
+            "metadata": {"message_type": "notification", "subscription_type": "channel.chat.message"},
+            "payload": {
+                "subscription": {"type": "channel.chat.message", "version": "1"},
+                "event": {
+                    "broadcaster_user_id": "1971641", "broadcaster_user_login": "streamer", "broadcaster_user_name": "Streamer",
+                    "chatter_user_id": "4145994", "chatter_user_login": "viewer32", "chatter_user_name": "Viewer32",
+                    "message_id": "cc106a89-1814-919d-454c-f4f2f970aae7",
+                    "message": {"text": "Hi chat", "fragments": [{"type": "text", "text": "Hi chat"}]},
+                    "color": "#00FF7F",
+                    "badges": [{"set_id": "moderator", "id": "1", "info": ""}, {"set_id": "subscriber", "id": "12", "info": "16"}],
+                    "message_type": "text"
+                }
+            }
+        }"##).unwrap();
+
+        match frame {
+            EventSubFrame::Notification(TwitchEvent::ChatMessage(msg)) => {
+                assert_eq!(msg.platform, "twitch");
+                assert_eq!(msg.channel, "streamer");
+                assert_eq!(msg.username, "viewer32");
+                assert_eq!(msg.display_name.as_deref(), Some("Viewer32"));
+                assert_eq!(msg.content, "Hi chat");
+                assert!(msg.is_mod);
+                assert!(msg.is_subscriber);
+                assert_eq!(msg.user_badges, vec!["moderator".to_string(), "subscriber".to_string()]);
+                assert!(msg.emotes.is_empty());
+            }
+            other => panic!("Expected chat message, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_chat_message_emote_spans() {
+        let body: ChatMessageBody = serde_json::from_str(r#"{
+            "text": "héllo Kappa LUL",
+            "fragments": [
+                {"type": "text", "text": "héllo "},
+                {"type": "emote", "text": "Kappa", "emote": {"id": "25", "emote_set_id": "0"}},
+                {"type": "text", "text": " "},
+                {"type": "emote", "text": "LUL", "emote": {"id": "425618", "emote_set_id": "0"}}
+            ]
+        }"#).unwrap();
+
+        assert_eq!(body.emote_spans(), vec![
+            EmoteSpan { id: "25".to_string(), start: 6, end: 10 },
+            EmoteSpan { id: "425618".to_string(), start: 12, end: 14 },
+        ]);
+    }
+
+    #[test]
+    fn test_map_ban_and_subscribe() {
+        let ban = parse_frame(r#"{
+            "metadata": {"message_type": "notification", "subscription_type": "channel.ban"},
+            "payload": {"event": {
+                "user_id": "1234", "user_login": "spammer", "user_name": "Spammer",
+                "broadcaster_user_id": "1337", "broadcaster_user_login": "streamer", "broadcaster_user_name": "Streamer",
+                "moderator_user_id": "1339", "moderator_user_login": "mod_user", "moderator_user_name": "Mod_User",
+                "reason": "spam links", "banned_at": "2024-01-01T00:00:00Z", "ends_at": null, "is_permanent": true
+            }}
+        }"#).unwrap();
+
+        match ban {
+            EventSubFrame::Notification(TwitchEvent::UserBanned { channel, username, moderator, reason, ends_at }) => {
+                assert_eq!(channel, "streamer");
+                assert_eq!(username, "spammer");
+                assert_eq!(moderator, "mod_user");
+                assert_eq!(reason, "spam links");
+                assert!(ends_at.is_none());
+            }
+            other => panic!("Expected ban, got {:?}", other),
+        }
+
+        let subscribe = parse_frame(r#"{
+            "metadata": {"message_type": "notification", "subscription_type": "channel.subscribe"},
+            "payload": {"event": {
+                "user_id": "1234", "user_login": "newsub", "user_name": "NewSub",
+                "broadcaster_user_id": "1337", "broadcaster_user_login": "streamer", "broadcaster_user_name": "Streamer",
+                "tier": "1000", "is_gift": false
+            }}
+        }"#).unwrap();
+
+        match subscribe {
+            EventSubFrame::Notification(TwitchEvent::Subscription { channel, username, tier, is_gift }) => {
+                assert_eq!(channel, "streamer");
+                assert_eq!(username, "newsub");
+                assert_eq!(tier, "1000");
+                assert!(!is_gift);
+            }
+            other => panic!("Expected subscription, got {:?}", other),
+        }
+
+        let raid = parse_frame(r#"{
+            "metadata": {"message_type": "notification", "subscription_type": "channel.raid"},
+            "payload": {"event": {
+                "from_broadcaster_user_id": "1234", "from_broadcaster_user_login": "friendlystreamer", "from_broadcaster_user_name": "FriendlyStreamer",
+                "to_broadcaster_user_id": "1337", "to_broadcaster_user_login": "streamer", "to_broadcaster_user_name": "Streamer",
+                "viewers": 42
+            }}
+        }"#).unwrap();
+
+        match raid {
+            EventSubFrame::Notification(TwitchEvent::Raid { channel, raider, viewers }) => {
+                assert_eq!(channel, "streamer");
+                assert_eq!(raider, "friendlystreamer");
+                assert_eq!(viewers, 42);
+            }
+            other => panic!("Expected raid, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_session_control_frames() {
+        let keepalive = parse_frame(r#"{"metadata": {"message_type": "session_keepalive"}, "payload": {}}"#).unwrap();
+        assert!(matches!(keepalive, EventSubFrame::Keepalive));
+
+        let reconnect = parse_frame(r#"{
+            "metadata": {"message_type": "session_reconnect"},
+            "payload": {"session": {"id": "abc", "reconnect_url": "wss://eventsub.wss.twitch.tv?id=abc"}}
+        }"#).unwrap();
+        assert!(matches!(reconnect, EventSubFrame::Reconnect { reconnect_url } if reconnect_url.ends_with("id=abc")));
+
+        let unknown = parse_frame(r#"{
+            "metadata": {"message_type": "notification", "subscription_type": "channel.follow"},
+            "payload": {"event": {}}
+        }"#).unwrap();
+        assert!(matches!(unknown, EventSubFrame::Unhandled(t) if t == "channel.follow"));
+    }
+
+    #[test]
+    fn test_chat_settings_body() {
+        assert_eq!(
+            chat_settings_body(Some(ChatRestriction::FollowersOnly)),
+            serde_json::json!({"follower_mode": true, "subscriber_mode": false})
+        );
+        assert_eq!(
+            chat_settings_body(None),
+            serde_json::json!({"follower_mode": false, "subscriber_mode": false})
+        );
+    }
+
+    #[test]
+    fn test_parse_send_result() {
+        let sent = serde_json::json!({ "data": [{ "message_id": "abc", "is_sent": true }] });
+        assert_eq!(parse_send_result(&sent), DeliveryStatus::Delivered);
+
+        let dropped = serde_json::json!({ "data": [{
+            "message_id": "",
+            "is_sent": false,
+            "drop_reason": { "code": "msg_duplicate", "message": "Your message is identical to the one you sent within the last 30 seconds." }
+        }] });
+        assert_eq!(
+            parse_send_result(&dropped),
+            DeliveryStatus::Dropped { reason: "Your message is identical to the one you sent within the last 30 seconds.".to_string() }
+        );
+
+        // No signal means delivered
+        assert_eq!(parse_send_result(&serde_json::Value::Null), DeliveryStatus::Delivered);
+    }
+}
@@ -7,8 +7,9 @@ use std::sync::Arc;
 use tokio::sync::{broadcast, RwLock};
 use tokio::time::{sleep, Duration};
 
-use crate::platforms::PlatformConnection;
+use crate::platforms::{ConnectionError, PlatformConnection, PlatformHealth, QuotaUsage};
 use crate::types::ChatMessage;
+use crate::bot::redaction::LogRedaction;
 
 /// YouTube API response structures
 #[derive(Debug, Deserialize)]
@@ -73,6 +74,151 @@ struct TextMessageDetails {
     message_text: String,
 }
 
+/// Quota cost of a liveChatMessages.list call
+const LIST_MESSAGES_QUOTA_COST: u32 = 5;
+
+/// Quota cost of a liveChatMessages.insert call
+const INSERT_MESSAGE_QUOTA_COST: u32 = 50;
+
+/// Default daily quota for a YouTube Data API project
+const DEFAULT_DAILY_QUOTA: u32 = 10_000;
+
+/// Longest poll interval while backing off from errors
+const MAX_ERROR_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Poll interval once the daily quota is exhausted
+const QUOTA_EXHAUSTED_INTERVAL: Duration = Duration::from_secs(300);
+
+/// Tracks YouTube Data API quota consumption for the current quota day.
+/// Quota resets at midnight Pacific time; a fixed UTC-8 offset is used.
+#[derive(Debug, Clone)]
+pub struct QuotaTracker {
+    daily_limit: u32,
+    used: u32,
+    quota_day: chrono::NaiveDate,
+    warned_level: u8,
+}
+
+impl QuotaTracker {
+    pub fn new(daily_limit: u32) -> Self {
+        Self {
+            daily_limit,
+            used: 0,
+            quota_day: Self::current_quota_day(),
+            warned_level: 0,
+        }
+    }
+
+    fn current_quota_day() -> chrono::NaiveDate {
+        (chrono::Utc::now() - chrono::Duration::hours(8)).date_naive()
+    }
+
+    fn roll_over_if_new_day(&mut self) {
+        let today = Self::current_quota_day();
+        if today != self.quota_day {
+            info!("YouTube API quota reset for {}", today);
+            self.quota_day = today;
+            self.used = 0;
+            self.warned_level = 0;
+        }
+    }
+
+    /// Record quota spent on an API call, logging as the limit gets close
+    pub fn record(&mut self, cost: u32) {
+        self.roll_over_if_new_day();
+        self.used = self.used.saturating_add(cost);
+
+        let fraction = self.usage().fraction_used();
+        let level = if fraction >= 1.0 { 3 } else if fraction >= 0.95 { 2 } else if fraction >= 0.8 { 1 } else { 0 };
+        if level > self.warned_level {
+            self.warned_level = level;
+            match level {
+                3 => error!("YouTube API quota exhausted ({}/{} units)", self.used, self.daily_limit),
+                _ => warn!("YouTube API quota at {:.0}% ({}/{} units), slowing polling",
+                           fraction * 100.0, self.used, self.daily_limit),
+            }
+        }
+    }
+
+    /// Treat the quota as used up, e.g. after the API reports quotaExceeded
+    pub fn mark_exhausted(&mut self) {
+        let remaining = self.daily_limit.saturating_sub(self.used);
+        self.record(remaining);
+    }
+
+    pub fn usage(&self) -> QuotaUsage {
+        QuotaUsage {
+            used: self.used,
+            limit: self.daily_limit,
+        }
+    }
+
+    pub fn is_exhausted(&self) -> bool {
+        self.used >= self.daily_limit
+    }
+
+    /// Poll interval multiplier: 1x until half the quota is used, then rising
+    /// to 2x at 80% and 4x at 95%
+    pub fn slowdown_factor(&self) -> f64 {
+        let fraction = self.usage().fraction_used();
+        if fraction < 0.5 {
+            1.0
+        } else if fraction < 0.8 {
+            1.0 + (fraction - 0.5) / 0.3
+        } else if fraction < 0.95 {
+            2.0 + 2.0 * (fraction - 0.8) / 0.15
+        } else {
+            4.0
+        }
+    }
+}
+
+/// Decides how long to wait between live chat polls
+#[derive(Debug, Clone)]
+pub struct PollScheduler {
+    base_interval: Duration,
+    current: Duration,
+}
+
+impl PollScheduler {
+    pub fn new(base_interval: Duration) -> Self {
+        Self {
+            base_interval,
+            current: base_interval,
+        }
+    }
+
+    pub fn current(&self) -> Duration {
+        self.current
+    }
+
+    /// Interval after a successful poll: the API's suggested interval (never
+    /// faster than configured), stretched as quota depletes
+    pub fn on_success(&mut self, suggested_ms: Option<u64>, quota: &QuotaTracker) -> Duration {
+        if quota.is_exhausted() {
+            self.current = QUOTA_EXHAUSTED_INTERVAL;
+            return self.current;
+        }
+
+        let suggested = suggested_ms.map(Duration::from_millis).unwrap_or(self.base_interval);
+        let interval = suggested.max(self.base_interval).mul_f64(quota.slowdown_factor());
+        self.current = interval.min(QUOTA_EXHAUSTED_INTERVAL);
+        self.current
+    }
+
+    /// Exponential backoff after a failed poll
+    pub fn on_error(&mut self) -> Duration {
+        self.current = (self.current * 2).min(MAX_ERROR_BACKOFF.max(self.base_interval));
+        self.current
+    }
+
+    /// Wait out an exhausted quota
+    pub fn on_quota_exhausted(&mut self) -> Duration {
+        self.current = QUOTA_EXHAUSTED_INTERVAL;
+        self.current
+    }
+}
+
 /// Configuration for YouTube Live Chat connection
 #[derive(Debug, Clone)]
 pub struct YouTubeConfig {
@@ -81,6 +227,7 @@ pub struct YouTubeConfig {
     pub live_chat_id: String,
     pub video_id: Option<String>,
     pub polling_interval_ms: u64,
+    pub daily_quota: u32,
 }
 
 impl YouTubeConfig {
@@ -101,6 +248,11 @@ impl YouTubeConfig {
             .unwrap_or_else(|_| "5000".to_string())
             .parse::<u64>()
             .unwrap_or(5000);
+
+        let daily_quota = env::var("YOUTUBE_DAILY_QUOTA")
+            .ok()
+            .and_then(|v| v.parse::<u32>().ok())
+            .unwrap_or(DEFAULT_DAILY_QUOTA);
         
         info!("Loaded YouTube config for live chat: {}", live_chat_id);
         if let Some(ref vid_id) = video_id {
@@ -113,6 +265,7 @@ impl YouTubeConfig {
             live_chat_id,
             video_id,
             polling_interval_ms,
+            daily_quota,
         })
     }
     
@@ -146,6 +299,7 @@ impl YouTubeConfig {
                         live_chat_id,
                         video_id: Some(video_id),
                         polling_interval_ms: 5000,
+                        daily_quota: DEFAULT_DAILY_QUOTA,
                     });
                 }
             }
@@ -160,23 +314,32 @@ pub struct YouTubeConnection {
     config: YouTubeConfig,
     message_sender: Option<broadcast::Sender<ChatMessage>>,
     is_connected: Arc<RwLock<bool>>,
+    /// Set once the API rejects our credentials
+    auth_failure: Arc<RwLock<Option<ConnectionError>>>,
     http_client: reqwest::Client,
     next_page_token: Arc<RwLock<Option<String>>>,
+    quota: Arc<RwLock<QuotaTracker>>,
+    log_redaction: LogRedaction,
 }
 
 impl YouTubeConnection {
     pub fn new(config: YouTubeConfig) -> Self {
+        let quota = QuotaTracker::new(config.daily_quota);
         Self {
             config,
             message_sender: None,
             is_connected: Arc::new(RwLock::new(false)),
+            auth_failure: Arc::new(RwLock::new(None)),
             http_client: reqwest::Client::new(),
             next_page_token: Arc::new(RwLock::new(None)),
+            quota: Arc::new(RwLock::new(quota)),
+            log_redaction: LogRedaction::default(),
         }
     }
 
-    /// Poll YouTube Live Chat API for new messages
-    async fn poll_messages(&self) -> Result<Vec<LiveChatMessage>> {
+    /// Poll YouTube Live Chat API for new messages, returning them along with
+    /// the API's suggested polling interval
+    async fn poll_messages(&self) -> Result<(Vec<LiveChatMessage>, Option<u64>)> {
         let page_token = self.next_page_token.read().await.clone();
         
         let mut url = format!(
@@ -195,10 +358,14 @@ impl YouTubeConnection {
             .send()
             .await
             .context("Failed to poll YouTube Live Chat API")?;
+        self.quota.write().await.record(LIST_MESSAGES_QUOTA_COST);
         
         if !response.status().is_success() {
             let status = response.status();
             let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+            if let Some(auth_error) = ConnectionError::from_http_response("youtube", status.as_u16(), &error_text) {
+                return Err(auth_error.into());
+            }
             return Err(anyhow::anyhow!("YouTube API error {}: {}", status, error_text));
         }
         
@@ -213,12 +380,11 @@ impl YouTubeConnection {
             *token_guard = chat_response.next_page_token;
         }
         
-        // Update polling interval if provided
         if let Some(interval) = chat_response.polling_interval_millis {
             debug!("YouTube suggested polling interval: {}ms", interval);
         }
         
-        Ok(chat_response.items)
+        Ok((chat_response.items, chat_response.polling_interval_millis))
     }
 
     /// Convert YouTube message to our standard ChatMessage format
@@ -234,6 +400,7 @@ impl YouTubeConnection {
             user_badges: self.extract_badges(&yt_message.author_details),
             is_mod: yt_message.author_details.is_chat_moderator || yt_message.author_details.is_chat_owner,
             is_subscriber: yt_message.author_details.is_chat_sponsor,
+            emotes: Vec::new(),
         }
     }
 
@@ -271,12 +438,18 @@ impl PlatformConnection for YouTubeConnection {
             .send()
             .await
             .context("Failed to connect to YouTube Live Chat API")?;
+        self.quota.write().await.record(LIST_MESSAGES_QUOTA_COST);
         
         if !response.status().is_success() {
             let status = response.status();
             let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+            if let Some(auth_error) = ConnectionError::from_http_response("youtube", status.as_u16(), &error_text) {
+                *self.auth_failure.write().await = Some(auth_error.clone());
+                return Err(auth_error.into());
+            }
             return Err(anyhow::anyhow!("YouTube API connection failed {}: {}", status, error_text));
         }
+        *self.auth_failure.write().await = None;
         
         info!("Successfully connected to YouTube Live Chat API");
         
@@ -293,10 +466,13 @@ impl PlatformConnection for YouTubeConnection {
         let config = self.config.clone();
         let http_client = self.http_client.clone();
         let next_page_token = Arc::clone(&self.next_page_token);
+        let quota = Arc::clone(&self.quota);
+        let auth_failure = Arc::clone(&self.auth_failure);
+        let log_redaction = self.log_redaction.clone();
         
         tokio::spawn(async move {
             info!("YouTube Live Chat message poller started");
-            let mut interval = Duration::from_millis(config.polling_interval_ms);
+            let mut scheduler = PollScheduler::new(Duration::from_millis(config.polling_interval_ms));
             
             loop {
                 if !*is_connected.read().await {
@@ -309,46 +485,51 @@ impl PlatformConnection for YouTubeConnection {
                     config: config.clone(),
                     message_sender: None,
                     is_connected: Arc::clone(&is_connected),
+                    auth_failure: Arc::clone(&auth_failure),
                     http_client: http_client.clone(),
                     next_page_token: Arc::clone(&next_page_token),
+                    quota: Arc::clone(&quota),
+                    log_redaction: log_redaction.clone(),
                 };
                 
-                match temp_connection.poll_messages().await {
-                    Ok(messages) => {
+                let interval = match temp_connection.poll_messages().await {
+                    Ok((messages, suggested_interval)) => {
                         debug!("Polled {} new YouTube messages", messages.len());
                         
                         for yt_message in messages {
                             let chat_message = temp_connection.convert_message(yt_message);
-                            info!("YouTube message from {}: {}", chat_message.username, chat_message.content);
+                            info!("YouTube message from {}: {}", chat_message.username, log_redaction.redact(&chat_message.content));
                             
                             if let Err(e) = message_sender.send(chat_message) {
                                 warn!("Failed to broadcast YouTube message: {}", e);
                             }
                         }
+
+                        let quota_guard = quota.read().await;
+                        scheduler.on_success(suggested_interval, &quota_guard)
                     }
                     Err(e) => {
                         error!("Failed to poll YouTube messages: {}", e);
+                        let error_text = e.to_string();
                         
-                        // If we get an auth error, mark as disconnected
-                        if e.to_string().contains("403") || e.to_string().contains("401") {
-                            error!("YouTube API authentication failed, marking as disconnected");
+                        if error_text.contains("quotaExceeded") {
+                            // Quota errors come back as 403 but the credentials are fine
+                            quota.write().await.mark_exhausted();
+                            scheduler.on_quota_exhausted()
+                        } else if let Some(auth_error) = ConnectionError::auth_failure(&e) {
+                            error!("🔑 {} - refresh the YouTube OAuth token; marking as disconnected", auth_error);
+                            *auth_failure.write().await = Some(auth_error.clone());
                             *is_connected.write().await = false;
                             break;
+                        } else {
+                            let interval = scheduler.on_error();
+                            warn!("Backing off polling interval to {:?}", interval);
+                            interval
                         }
-                        
-                        // Exponential backoff on errors
-                        interval = std::cmp::min(interval * 2, Duration::from_secs(60));
-                        warn!("Backing off polling interval to {:?}", interval);
                     }
-                }
+                };
                 
                 sleep(interval).await;
-                
-                // Reset interval on successful polls
-                if interval != Duration::from_millis(config.polling_interval_ms) {
-                    interval = Duration::from_millis(config.polling_interval_ms);
-                    debug!("Reset polling interval to {}ms", config.polling_interval_ms);
-                }
             }
             
             warn!("YouTube Live Chat message poller stopped");
@@ -380,6 +561,7 @@ impl PlatformConnection for YouTubeConnection {
             .send()
             .await
             .context("Failed to send YouTube Live Chat message")?;
+        self.quota.write().await.record(INSERT_MESSAGE_QUOTA_COST);
         
         if response.status().is_success() {
             debug!("Sent YouTube message: {}", message);
@@ -399,6 +581,16 @@ impl PlatformConnection for YouTubeConnection {
         *self.is_connected.read().await
     }
 
+    async fn health(&self) -> PlatformHealth {
+        if self.auth_failure.read().await.is_some() {
+            PlatformHealth::AuthFailed
+        } else if *self.is_connected.read().await {
+            PlatformHealth::Connected
+        } else {
+            PlatformHealth::Down
+        }
+    }
+
     fn get_message_receiver(&self) -> Option<broadcast::Receiver<ChatMessage>> {
         self.message_sender.as_ref().map(|sender| sender.subscribe())
     }
@@ -413,4 +605,77 @@ impl PlatformConnection for YouTubeConnection {
         info!("Disconnected from YouTube Live Chat");
         Ok(())
     }
-}
\ No newline at end of file
+
+    async fn quota_usage(&self) -> Option<QuotaUsage> {
+        Some(self.quota.read().await.usage())
+    }
+
+    fn set_log_redaction(&mut self, log_redaction: LogRedaction) {
+        self.log_redaction = log_redaction;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Pull the suggested interval out of a raw API response body
+    fn suggested_interval(body: &str) -> Option<u64> {
+        let response: YouTubeResponse<serde_json::Value> = serde_json::from_str(body).unwrap();
+        response.polling_interval_millis
+    }
+
+    #[test]
+    fn test_poll_interval_follows_api_suggestion() {
+        let quota = QuotaTracker::new(DEFAULT_DAILY_QUOTA);
+        let mut scheduler = PollScheduler::new(Duration::from_millis(2000));
+
+        let slow = suggested_interval(r#"{"items": [], "nextPageToken": "abc", "pollingIntervalMillis": 6000}"#);
+        assert_eq!(scheduler.on_success(slow, &quota), Duration::from_millis(6000));
+
+        // Never poll faster than configured, even if the API allows it
+        let fast = suggested_interval(r#"{"items": [], "pollingIntervalMillis": 500}"#);
+        assert_eq!(scheduler.on_success(fast, &quota), Duration::from_millis(2000));
+
+        let missing = suggested_interval(r#"{"items": []}"#);
+        assert_eq!(scheduler.on_success(missing, &quota), Duration::from_millis(2000));
+    }
+
+    #[test]
+    fn test_error_backoff_compounds_and_resets() {
+        let quota = QuotaTracker::new(DEFAULT_DAILY_QUOTA);
+        let mut scheduler = PollScheduler::new(Duration::from_millis(5000));
+
+        assert_eq!(scheduler.on_error(), Duration::from_secs(10));
+        assert_eq!(scheduler.on_error(), Duration::from_secs(20));
+        for _ in 0..5 {
+            scheduler.on_error();
+        }
+        assert_eq!(scheduler.current(), MAX_ERROR_BACKOFF);
+
+        assert_eq!(scheduler.on_success(None, &quota), Duration::from_millis(5000));
+    }
+
+    #[test]
+    fn test_polling_slows_as_quota_depletes() {
+        let mut quota = QuotaTracker::new(100);
+        let mut scheduler = PollScheduler::new(Duration::from_millis(5000));
+        let suggested = suggested_interval(r#"{"items": [], "pollingIntervalMillis": 5000}"#);
+
+        quota.record(40);
+        assert_eq!(scheduler.on_success(suggested, &quota), Duration::from_millis(5000));
+
+        quota.record(25); // 65% used
+        let interval = scheduler.on_success(suggested, &quota);
+        assert!(interval > Duration::from_millis(5000) && interval < Duration::from_millis(10000));
+
+        quota.record(30); // 95% used
+        assert_eq!(scheduler.on_success(suggested, &quota), Duration::from_millis(20000));
+
+        quota.mark_exhausted();
+        assert!(quota.is_exhausted());
+        assert_eq!(scheduler.on_success(suggested, &quota), QUOTA_EXHAUSTED_INTERVAL);
+        assert_eq!(quota.usage(), QuotaUsage { used: 100, limit: 100 });
+        assert_eq!(quota.usage().remaining(), 0);
+    }
+}
@@ -20,14 +20,127 @@ pub struct ChatMessage {
     pub user_badges: Vec<String>,
     pub is_mod: bool,
     pub is_subscriber: bool,
+    /// Emotes the platform reported in `content`; empty when it sends no emote data
+    #[serde(default)]
+    pub emotes: Vec<EmoteSpan>,
 }
 
+/// Position of a platform emote within a message
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EmoteSpan {
+    pub id: String,
+    /// Index of the first character (not byte) of the emote
+    pub start: usize,
+    /// Index of the last character, inclusive
+    pub end: usize,
+}
+
+impl ChatMessage {
+    /// Create a message from a regular viewer, timestamped now with no badges
+    pub fn new(
+        platform: impl Into<String>,
+        channel: impl Into<String>,
+        username: impl Into<String>,
+        content: impl Into<String>,
+    ) -> Self {
+        Self {
+            platform: platform.into(),
+            channel: channel.into(),
+            username: username.into(),
+            display_name: None,
+            content: content.into(),
+            timestamp: chrono::Utc::now(),
+            user_badges: Vec::new(),
+            is_mod: false,
+            is_subscriber: false,
+            emotes: Vec::new(),
+        }
+    }
+
+    pub fn with_display_name(mut self, display_name: impl Into<String>) -> Self {
+        self.display_name = Some(display_name.into());
+        self
+    }
+
+    pub fn with_timestamp(mut self, timestamp: chrono::DateTime<chrono::Utc>) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
+    pub fn with_badges(mut self, badges: Vec<String>) -> Self {
+        self.user_badges = badges;
+        self
+    }
+
+    pub fn with_emotes(mut self, emotes: Vec<EmoteSpan>) -> Self {
+        self.emotes = emotes;
+        self
+    }
+
+    pub fn with_mod(mut self, is_mod: bool) -> Self {
+        self.is_mod = is_mod;
+        self
+    }
+
+    pub fn with_subscriber(mut self, is_subscriber: bool) -> Self {
+        self.is_subscriber = is_subscriber;
+        self
+    }
+
+    /// When the message counts as sent for moderation windows, on the bot's clock.
+    ///
+    /// The platform timestamp is used while it is plausible, so a delayed batch keeps
+    /// its spacing. Future-dated timestamps are clamped to `now` and ones older than
+    /// [`MAX_MESSAGE_TIMESTAMP_AGE_SECONDS`] are replaced by `now`, so a skewed platform
+    /// clock can't push messages out of a window (skipping escalation) or keep them
+    /// in it indefinitely.
+    pub fn window_timestamp(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        if self.timestamp > now || now - self.timestamp > chrono::Duration::seconds(MAX_MESSAGE_TIMESTAMP_AGE_SECONDS) {
+            now
+        } else {
+            self.timestamp
+        }
+    }
+}
+
+/// Platform timestamps older than this on arrival are treated as clock skew
+pub const MAX_MESSAGE_TIMESTAMP_AGE_SECONDS: i64 = 60;
+
 #[derive(Debug, Clone)]
 pub struct BotCommand {
     pub trigger: String,
     pub response: String,
     pub mod_only: bool,
     pub cooldown_seconds: u64,
+    pub channels: CommandChannels,
+    pub category: Option<String>,
+    /// Reply when this command is on cooldown, overriding the global cooldown notice
+    pub cooldown_notice: Option<String>,
+}
+
+/// Channels a command responds in
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub enum CommandChannels {
+    #[default]
+    All,
+    Only(Vec<String>),
+    Except(Vec<String>),
+}
+
+impl CommandChannels {
+    /// Whether the command is enabled in `channel` (case-insensitive, leading '#' ignored)
+    pub fn allows(&self, channel: &str) -> bool {
+        let matches = |channels: &[String]| {
+            let channel = channel.trim_start_matches('#');
+            channels.iter().any(|c| c.trim_start_matches('#').eq_ignore_ascii_case(channel))
+        };
+
+        match self {
+            CommandChannels::All => true,
+            CommandChannels::Only(channels) => matches(channels),
+            CommandChannels::Except(channels) => !matches(channels),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -40,10 +153,16 @@ pub struct BotTimer {
     pub enabled: bool,
     pub last_triggered: Option<chrono::DateTime<chrono::Utc>>,
     pub trigger_count: u64,
+    /// Largest random shift (± seconds) applied to each interval
+    pub random_offset_max: Option<u64>,
+    /// Wait before the next fire, re-rolled with the random offset after every fire
+    pub next_interval_seconds: u64,
+    /// Hours and days the timer may post in (`None` = always)
+    pub schedule: Option<crate::bot::schedule::ActiveSchedule>,
 }
 
 /// Enhanced spam filter types with NightBot parity
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum SpamFilterType {
     /// Excessive caps lock (percentage threshold)
     ExcessiveCaps { max_percentage: u8 },
@@ -53,8 +172,13 @@ pub enum SpamFilterType {
     RepeatedMessages { max_repeats: u8, window_seconds: u64 },
     /// Message length limits
     MessageLength { max_length: usize },
-    /// Excessive emotes
-    ExcessiveEmotes { max_count: u8 },
+    /// Excessive emotes; platform emote data is used when available, otherwise Unicode emoji are counted
+    ExcessiveEmotes {
+        max_count: u8,
+        /// Count each distinct emote once instead of every occurrence
+        #[serde(default)]
+        count_unique: bool,
+    },
     /// Symbol spam (non-alphanumeric characters)
     SymbolSpam { max_percentage: u8 },
     /// Fast posting (rate limiting)
@@ -64,18 +188,62 @@ pub enum SpamFilterType {
         patterns: Vec<BlacklistPattern>,
         case_sensitive: bool,
         whole_words_only: bool,
+        /// Exceptions take precedence: a message matching any of them is never
+        /// flagged by this filter, whatever else it contains. Other filters still apply.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        except_patterns: Vec<BlacklistPattern>,
     },
 }
 
 /// Blacklist pattern types supporting literal, wildcard, and regex
-#[derive(Debug, Clone)]
+///
+/// Regex patterns serialize as their `~/pattern/flags` source and are
+/// recompiled when deserialized.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "BlacklistPatternSource")]
 pub enum BlacklistPattern {
     /// Literal text match: "badword"
     Literal(String),
     /// Wildcard pattern: "bad*", "*word", "*bad*"
-    Wildcard(String),
+    Wildcard(WildcardPattern),
     /// Regex pattern: ~/pattern/flags
-    Regex { pattern: String, compiled: Option<Regex> },
+    Regex {
+        pattern: String,
+        #[serde(skip)]
+        compiled: Option<Regex>,
+    },
+}
+
+/// How a wildcard pattern lines up with the text it's checked against
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WildcardAnchor {
+    /// NightBot-compatible: the pattern must cover the whole text (or each word,
+    /// with whole-word matching), so `bad*` matches "badword" but not "this is bad"
+    #[default]
+    Full,
+    /// The pattern may match anywhere: `bad*` matches "this is bad stuff"
+    Contains,
+}
+
+/// Serialized form of `BlacklistPattern`, before regex compilation
+#[derive(Deserialize)]
+enum BlacklistPatternSource {
+    Literal(String),
+    Wildcard(String),
+    Regex { pattern: String },
+}
+
+impl TryFrom<BlacklistPatternSource> for BlacklistPattern {
+    type Error = String;
+
+    fn try_from(source: BlacklistPatternSource) -> Result<Self, Self::Error> {
+        match source {
+            BlacklistPatternSource::Literal(text) => Ok(BlacklistPattern::Literal(text)),
+            BlacklistPatternSource::Wildcard(text) => Ok(BlacklistPattern::Wildcard(WildcardPattern::new(text))),
+            BlacklistPatternSource::Regex { pattern } => BlacklistPattern::from_regex_string(&pattern),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -134,6 +302,24 @@ impl Default for FilterConfig {
 }
 
 impl BlacklistPattern {
+    /// Wildcard pattern with the given anchoring
+    pub fn wildcard(pattern: &str, anchor: WildcardAnchor) -> Self {
+        match anchor {
+            WildcardAnchor::Full => BlacklistPattern::Wildcard(WildcardPattern::new(pattern)),
+            WildcardAnchor::Contains => {
+                // Leading and trailing `*` make the anchored match behave as "contains"
+                let mut pattern = pattern.to_string();
+                if !pattern.starts_with('*') {
+                    pattern.insert(0, '*');
+                }
+                if !pattern.ends_with('*') {
+                    pattern.push('*');
+                }
+                BlacklistPattern::Wildcard(WildcardPattern::new(pattern))
+            }
+        }
+    }
+
     /// Create a new regex pattern from NightBot-style syntax
     pub fn from_regex_string(input: &str) -> Result<Self, String> {
         if !input.starts_with("~/") {
@@ -187,13 +373,11 @@ impl BlacklistPattern {
                 }
             }
             BlacklistPattern::Wildcard(pattern) => {
-                let text_to_check = if case_sensitive { text.to_string() } else { text.to_lowercase() };
-                let pattern_to_check = if case_sensitive { pattern.clone() } else { pattern.to_lowercase() };
-                
                 if whole_words_only {
-                    Self::wildcard_whole_word_match(&text_to_check, &pattern_to_check)
+                    text.split(|c: char| !c.is_alphanumeric())
+                        .any(|word| pattern.is_match(word, case_sensitive))
                 } else {
-                    Self::wildcard_match(&text_to_check, &pattern_to_check)
+                    pattern.is_match(text, case_sensitive)
                 }
             }
             BlacklistPattern::Regex { compiled, .. } => {
@@ -211,30 +395,326 @@ impl BlacklistPattern {
         let word_chars: Vec<&str> = text.split(|c: char| !c.is_alphanumeric()).collect();
         word_chars.iter().any(|word| *word == pattern)
     }
-    
-    /// Match wildcard pattern against text
-    fn wildcard_match(text: &str, pattern: &str) -> bool {
-        // Convert wildcard pattern to regex
-        let regex_pattern = pattern
-            .replace("*", ".*")
-            .replace("?", ".");
-        
-        if let Ok(regex) = Regex::new(&format!("^{}$", regex_pattern)) {
-            regex.is_match(text)
-        } else {
-            false
+
+    /// Where in `text` this pattern matches, following the same rules as `matches`
+    pub fn find(&self, text: &str, case_sensitive: bool, whole_words_only: bool) -> Option<MatchSpan> {
+        let range = match self {
+            BlacklistPattern::Literal(pattern) if whole_words_only => {
+                let pattern = if case_sensitive { pattern.clone() } else { pattern.to_lowercase() };
+                Self::words(text).find(|(_, word)| {
+                    if case_sensitive { *word == pattern } else { word.to_lowercase() == pattern }
+                }).map(|(start, word)| start..start + word.len())
+            }
+            BlacklistPattern::Literal(pattern) => {
+                Self::literal_ranges(text, pattern, case_sensitive).into_iter().next()
+            }
+            BlacklistPattern::Wildcard(pattern) if whole_words_only => {
+                Self::words(text).find(|(_, word)| pattern.is_match(word, case_sensitive))
+                    .map(|(start, word)| start..start + word.len())
+            }
+            BlacklistPattern::Wildcard(pattern) => pattern.find(text, case_sensitive),
+            BlacklistPattern::Regex { compiled, .. } => compiled.as_ref()?.find(text).map(|m| m.range()),
+        }?;
+        Some(MatchSpan::new(text, range))
+    }
+
+    /// Every place in `text` this pattern matches, following the same rules as `matches`
+    pub fn find_all(&self, text: &str, case_sensitive: bool, whole_words_only: bool) -> Vec<MatchSpan> {
+        let ranges: Vec<std::ops::Range<usize>> = match self {
+            BlacklistPattern::Literal(pattern) if whole_words_only => {
+                let pattern = if case_sensitive { pattern.clone() } else { pattern.to_lowercase() };
+                Self::words(text).filter(|(_, word)| {
+                    if case_sensitive { *word == pattern } else { word.to_lowercase() == pattern }
+                }).map(|(start, word)| start..start + word.len()).collect()
+            }
+            BlacklistPattern::Literal(pattern) => Self::literal_ranges(text, pattern, case_sensitive),
+            BlacklistPattern::Wildcard(pattern) if whole_words_only => {
+                Self::words(text).filter(|(_, word)| pattern.is_match(word, case_sensitive))
+                    .map(|(start, word)| start..start + word.len()).collect()
+            }
+            BlacklistPattern::Wildcard(pattern) => pattern.find_all(text, case_sensitive),
+            BlacklistPattern::Regex { compiled, .. } => compiled.as_ref()
+                .map(|regex| regex.find_iter(text).map(|m| m.range()).collect())
+                .unwrap_or_default(),
+        };
+        ranges.into_iter().map(|range| MatchSpan::new(text, range)).collect()
+    }
+
+    /// The pattern as written in the filter configuration
+    pub fn source(&self) -> &str {
+        match self {
+            BlacklistPattern::Literal(pattern) => pattern,
+            BlacklistPattern::Wildcard(pattern) => pattern.as_str(),
+            BlacklistPattern::Regex { pattern, .. } => pattern,
         }
     }
-    
-    /// Match wildcard pattern against whole words
-    fn wildcard_whole_word_match(text: &str, pattern: &str) -> bool {
-        let words: Vec<&str> = text.split(|c: char| !c.is_alphanumeric()).collect();
-        words.iter().any(|word| Self::wildcard_match(word, pattern))
+
+    /// Byte ranges of the non-overlapping occurrences of a literal in `text`,
+    /// compared like `matches` does (lowercased unless case-sensitive)
+    fn literal_ranges(text: &str, pattern: &str, case_sensitive: bool) -> Vec<std::ops::Range<usize>> {
+        if pattern.is_empty() {
+            return Vec::new();
+        }
+        if case_sensitive {
+            return text.match_indices(pattern).map(|(start, m)| start..start + m.len()).collect();
+        }
+
+        let pattern: Vec<char> = pattern.chars().flat_map(char::to_lowercase).collect();
+        let mut ranges = Vec::new();
+        let mut next = 0;
+        for (start, _) in text.char_indices() {
+            if start < next {
+                continue;
+            }
+            if let Some(len) = Self::lowercase_prefix_len(&text[start..], &pattern) {
+                ranges.push(start..start + len);
+                next = start + len;
+            }
+        }
+        ranges
+    }
+
+    /// Length in bytes of the start of `text` that lowercases to `pattern`, if any
+    fn lowercase_prefix_len(text: &str, pattern: &[char]) -> Option<usize> {
+        let mut matched = 0;
+        for (offset, c) in text.char_indices() {
+            for lower in c.to_lowercase() {
+                if pattern.get(matched) != Some(&lower) {
+                    return None;
+                }
+                matched += 1;
+            }
+            if matched == pattern.len() {
+                return Some(offset + c.len_utf8());
+            }
+        }
+        None
+    }
+
+    /// Words split on non-alphanumerics, with their byte offsets
+    fn words(text: &str) -> impl Iterator<Item = (usize, &str)> {
+        text.split(|c: char| !c.is_alphanumeric())
+            .map(move |word| (word.as_ptr() as usize - text.as_ptr() as usize, word))
     }
 }
 
-/// Enhanced spam filter with escalation support
+/// Part of a text a pattern matched, as a byte range into that text
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MatchSpan {
+    pub start: usize,
+    pub end: usize,
+    /// The matched text itself
+    pub text: String,
+}
+
+impl MatchSpan {
+    pub fn new(text: &str, range: std::ops::Range<usize>) -> Self {
+        Self {
+            text: text[range.clone()].to_string(),
+            start: range.start,
+            end: range.end,
+        }
+    }
+
+    /// `text` with this span marked «like this», for chat replies
+    pub fn highlight(&self, text: &str) -> String {
+        match (text.get(..self.start), text.get(self.end..)) {
+            (Some(before), Some(after)) => format!("{}«{}»{}", before, self.text, after),
+            _ => text.to_string(),
+        }
+    }
+}
+
+/// Why a filter or AI pattern caught a message, for `!testfilter` and the dashboard
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MatchExplanation {
+    /// Filter name, or the AI pattern for pattern matches
+    pub filter: String,
+    /// Blacklist pattern that matched
+    pub pattern: Option<String>,
+    /// Where in `matched_text` the match is; `None` when it isn't tied to one
+    /// place (caps ratio, message length, whole-message similarity)
+    pub span: Option<MatchSpan>,
+    /// The message, or a payload decoded from it when that's what matched
+    pub matched_text: String,
+    /// An exception pattern lets the message through anyway
+    pub excepted: bool,
+}
+
+impl MatchExplanation {
+    pub fn new(filter: impl Into<String>, matched_text: impl Into<String>) -> Self {
+        Self {
+            filter: filter.into(),
+            pattern: None,
+            span: None,
+            matched_text: matched_text.into(),
+            excepted: false,
+        }
+    }
+
+    /// The matched text with the span marked, or as-is when there's no span
+    pub fn highlighted(&self) -> String {
+        match &self.span {
+            Some(span) => span.highlight(&self.matched_text),
+            None => self.matched_text.clone(),
+        }
+    }
+}
+
+/// Wildcard pattern (`*` any run of characters, `?` one character) matched
+/// against the whole text, with its regexes compiled once up front.
+///
+/// Serializes as the plain pattern string.
 #[derive(Debug, Clone)]
+pub struct WildcardPattern {
+    pattern: String,
+    /// `None` when the pattern doesn't translate to a valid regex; it then never matches
+    case_sensitive: Option<Regex>,
+    case_insensitive: Option<Regex>,
+    /// The pattern without its leading and trailing `*`, for locating matches;
+    /// `None` when that leaves nothing, so the whole text is the match
+    span_case_sensitive: Option<Regex>,
+    span_case_insensitive: Option<Regex>,
+}
+
+impl WildcardPattern {
+    pub fn new(pattern: impl Into<String>) -> Self {
+        let pattern = pattern.into();
+        let regex_source = format!("^{}$", pattern.replace('*', ".*").replace('?', "."));
+        let core = pattern.trim_matches('*');
+        let span_source = (!core.is_empty()).then(|| format!("{}{}{}",
+            if pattern.starts_with('*') { "" } else { "^" },
+            core.replace('*', ".*").replace('?', "."),
+            if pattern.ends_with('*') { "" } else { "$" }));
+
+        Self {
+            case_sensitive: Self::compile(&regex_source, false),
+            case_insensitive: Self::compile(&regex_source, true),
+            span_case_sensitive: span_source.as_deref().and_then(|source| Self::compile(source, false)),
+            span_case_insensitive: span_source.as_deref().and_then(|source| Self::compile(source, true)),
+            pattern,
+        }
+    }
+
+    fn compile(regex_source: &str, case_insensitive: bool) -> Option<Regex> {
+        #[cfg(test)]
+        WILDCARD_COMPILES.with(|compiles| compiles.set(compiles.get() + 1));
+        regex::RegexBuilder::new(regex_source)
+            .case_insensitive(case_insensitive)
+            .build()
+            .ok()
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+
+    pub fn is_match(&self, text: &str, case_sensitive: bool) -> bool {
+        let regex = if case_sensitive { &self.case_sensitive } else { &self.case_insensitive };
+        regex.as_ref().is_some_and(|regex| regex.is_match(text))
+    }
+
+    /// Byte range of a matching `text` the pattern accounts for, leaving out
+    /// whatever leading and trailing `*` swallowed
+    pub fn find(&self, text: &str, case_sensitive: bool) -> Option<std::ops::Range<usize>> {
+        self.find_all(text, case_sensitive).into_iter().next()
+    }
+
+    /// Every such byte range; a pattern that isn't open at both ends matches at most once
+    pub fn find_all(&self, text: &str, case_sensitive: bool) -> Vec<std::ops::Range<usize>> {
+        if !self.is_match(text, case_sensitive) {
+            return Vec::new();
+        }
+        let span_regex = if case_sensitive { &self.span_case_sensitive } else { &self.span_case_insensitive };
+        match span_regex {
+            Some(regex) => regex.find_iter(text).map(|m| m.range()).collect(),
+            None => {
+                let whole_text = 0..text.len();
+                vec![whole_text]
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+thread_local! {
+    /// Regexes `WildcardPattern` compiled on this thread, so tests can tell matching reuses them
+    static WILDCARD_COMPILES: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+impl std::fmt::Display for WildcardPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.pattern)
+    }
+}
+
+impl Serialize for WildcardPattern {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.pattern)
+    }
+}
+
+impl<'de> Deserialize<'de> for WildcardPattern {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(WildcardPattern::new)
+    }
+}
+
+/// Phrase that exempts a message from blacklist filtering
+#[derive(Debug, Clone)]
+pub struct AllowlistEntry {
+    pub pattern: BlacklistPattern,
+    /// Channels the entry applies to; empty means every channel
+    pub channels: Vec<String>,
+}
+
+/// Protected phrases checked before spam filters run
+#[derive(Debug, Clone, Default)]
+pub struct ModerationAllowlist {
+    pub entries: Vec<AllowlistEntry>,
+    /// Also skip non-blacklist filters (caps, rate limit, ...) for allowlisted messages
+    pub bypass_all_filters: bool,
+}
+
+impl ModerationAllowlist {
+    /// Check whether a message contains a protected phrase for its channel
+    pub fn is_allowed(&self, message: &ChatMessage) -> bool {
+        self.entries.iter().any(|entry| {
+            let channel_matches = entry.channels.is_empty() ||
+                entry.channels.iter().any(|c| c.eq_ignore_ascii_case(&message.channel));
+            channel_matches && entry.pattern.matches(&message.content, false, false)
+        })
+    }
+
+    /// Whether an allowlisted message should skip the given filter type
+    pub fn bypasses(&self, filter_type: &SpamFilterType) -> bool {
+        self.bypass_all_filters || matches!(filter_type, SpamFilterType::Blacklist { .. })
+    }
+}
+
+/// How loudly a filter's matches are logged
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterLogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+}
+
+impl FilterLogLevel {
+    pub fn level(self) -> log::Level {
+        match self {
+            FilterLogLevel::Trace => log::Level::Trace,
+            FilterLogLevel::Debug => log::Level::Debug,
+            FilterLogLevel::Info => log::Level::Info,
+            FilterLogLevel::Warn => log::Level::Warn,
+        }
+    }
+}
+
+/// Enhanced spam filter with escalation support
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SpamFilter {
     pub filter_type: SpamFilterType,
     pub enabled: bool,
@@ -243,10 +723,20 @@ pub struct SpamFilter {
     pub silent_mode: bool,
     pub custom_message: Option<String>,
     pub name: String, // For management commands
+    /// The first match within this many seconds only warns; a second match
+    /// inside the window gets the filter's real action
+    #[serde(default)]
+    pub grace_seconds: Option<u64>,
+    /// Only applies at these hours and days (`None` = always)
+    #[serde(default)]
+    pub schedule: Option<crate::bot::schedule::ActiveSchedule>,
+    /// Level matches are logged at (`None` = the usual levels)
+    #[serde(default)]
+    pub log_level: Option<FilterLogLevel>,
 }
 
 /// Moderation escalation system (NightBot parity)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModerationEscalation {
     pub first_offense: ModerationAction,
     pub repeat_offense: ModerationAction,
@@ -265,8 +755,19 @@ impl Default for ModerationEscalation {
     }
 }
 
+impl ModerationEscalation {
+    /// Non-escalating: every offense gets the same action
+    pub fn fixed(action: ModerationAction) -> Self {
+        Self {
+            first_offense: action.clone(),
+            repeat_offense: action,
+            offense_window_seconds: 0,
+        }
+    }
+}
+
 /// User exemption levels
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum ExemptionLevel {
     None,        // No exemptions
     Subscriber,  // Subscribers exempt
@@ -306,6 +807,23 @@ pub enum ModerationAction {
     TimeoutUser { duration_seconds: u64 },
     WarnUser { message: String },
     LogOnly,
+    /// Quietly ignore the user's messages for a while, with no visible timeout
+    /// to tip off a suspected bot
+    ShadowMute { duration_seconds: u64 },
+}
+
+impl ModerationAction {
+    /// Parse a configured action name: delete, warn, timeout, shadow_mute or log_only
+    pub fn from_config(name: &str, timeout_seconds: u64, warn_message: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "delete" => Some(ModerationAction::DeleteMessage),
+            "warn" => Some(ModerationAction::WarnUser { message: warn_message.to_string() }),
+            "timeout" => Some(ModerationAction::TimeoutUser { duration_seconds: timeout_seconds }),
+            "shadow_mute" => Some(ModerationAction::ShadowMute { duration_seconds: timeout_seconds }),
+            "log_only" | "log" => Some(ModerationAction::LogOnly),
+            _ => None,
+        }
+    }
 }
 
 /// User violation history for escalation tracking
@@ -323,6 +841,17 @@ pub struct ViolationRecord {
     pub timestamp: chrono::DateTime<chrono::Utc>,
     pub action_taken: ModerationAction,
     pub message_content: String, // For review purposes
+    pub confidence: Option<f64>, // Set when detected by AI patterns rather than a rule
+    /// Warning issued for a filter's grace period; doesn't count toward escalation
+    pub grace_warning: bool,
+    /// Set once the user has appealed it, so repeat appeals aren't counted again
+    pub appealed: bool,
+}
+
+impl ViolationRecord {
+    pub fn is_ai_detected(&self) -> bool {
+        self.confidence.is_some()
+    }
 }
 
 impl UserViolationHistory {
@@ -340,7 +869,7 @@ impl UserViolationHistory {
         let cutoff_time = chrono::Utc::now() - chrono::Duration::seconds(window_seconds as i64);
         
         self.violations.iter().any(|v| {
-            v.filter_name == filter_name && v.timestamp > cutoff_time
+            v.filter_name == filter_name && v.timestamp > cutoff_time && !v.grace_warning
         })
     }
     
@@ -361,6 +890,7 @@ impl UserViolationHistory {
 pub struct UserMessageHistory {
     pub messages: Vec<(chrono::DateTime<chrono::Utc>, String)>,
     pub last_warning: Option<chrono::DateTime<chrono::Utc>>,
+    pub last_timeout: Option<chrono::DateTime<chrono::Utc>>,
     pub violation_count: u64,
     pub violation_history: UserViolationHistory,
 }
@@ -370,6 +900,7 @@ impl UserMessageHistory {
         Self {
             messages: Vec::new(),
             last_warning: None,
+            last_timeout: None,
             violation_count: 0,
             violation_history: UserViolationHistory::new(user_id),
         }
@@ -745,6 +1276,10 @@ pub struct TimerDefinition {
     pub description: Option<String>,
     pub tags: Option<Vec<String>>,
     pub variables: Option<Vec<String>>,
+    /// Shift each interval by a random amount up to ± this many seconds; when
+    /// unset, the `random_offset_max` in config/timers.yaml for this timer applies
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub random_offset_max: Option<u64>,
 }
 
 /// Variable definitions for timer messages
@@ -1160,7 +1695,8 @@ impl ActiveGiveaway {
         status.user_level = user_level;
         status.last_activity = Utc::now();
 
-        if should_make_eligible {
+        // Users a moderator removed stay out until they're added back
+        if should_make_eligible && (status.eligible || !status.manual_override) {
             let was_eligible = status.eligible;
             status.make_eligible();
             if !was_eligible && status.eligible {
@@ -1178,7 +1714,7 @@ impl ActiveGiveaway {
     /// Get all currently eligible users
     pub fn get_eligible_users(&self) -> Vec<&EligibilityStatus> {
         self.eligible_users.values()
-            .filter(|status| status.eligible && status.fraud_score <= self.settings.max_fraud_score)
+            .filter(|status| status.eligible && (status.manual_override || status.fraud_score <= self.settings.max_fraud_score))
             .collect()
     }
 
@@ -1193,10 +1729,32 @@ impl ActiveGiveaway {
             .sum()
     }
 
+    /// Manually include or exclude a user (moderator action); returns whether anything changed.
+    /// Manually added users skip the fraud score cutoff.
+    pub fn set_manual_eligibility(&mut self, username: &str, platform: &str, eligible: bool) -> bool {
+        let status = self.eligible_users.entry(Self::user_key(platform, username)).or_insert_with(|| {
+            EligibilityStatus::new(username.to_string(), platform.to_string(), UserLevel::Viewer)
+        });
+
+        if status.eligible == eligible {
+            status.manual_override = true;
+            return false;
+        }
+
+        status.toggle_eligibility();
+        if status.eligible {
+            self.participant_count += 1;
+        } else {
+            self.participant_count = self.participant_count.saturating_sub(1);
+        }
+        true
+    }
+
     /// Reset all user eligibility
     pub fn reset_eligibility(&mut self) {
         for status in self.eligible_users.values_mut() {
             status.make_ineligible();
+            status.manual_override = false;
         }
         self.participant_count = 0;
         self.keyword_entries.clear();
@@ -1227,6 +1785,17 @@ impl ActiveGiveaway {
     }
 }
 
+impl GiveawayType {
+    /// Short name used in reports and exports
+    pub fn name(&self) -> &'static str {
+        match self {
+            GiveawayType::ActiveUser { .. } => "active_user",
+            GiveawayType::Keyword { .. } => "keyword",
+            GiveawayType::RandomNumber { .. } => "random_number",
+        }
+    }
+}
+
 /// A completed giveaway for historical tracking
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CompletedGiveaway {
@@ -1306,7 +1875,255 @@ pub enum GiveawayError {
     
     #[error("Permission denied: {reason}")]
     PermissionDenied { reason: String },
+
+    #[error("No completed giveaways to export")]
+    NoHistory,
+
+    #[error("Giveaway persistence failed: {0}")]
+    Persistence(String),
 }
 
 /// Result type for giveaway operations
-pub type GiveawayResult<T> = Result<T, GiveawayError>;
\ No newline at end of file
+pub type GiveawayResult<T> = Result<T, GiveawayError>;
+
+/// Error types for moderation operations
+#[derive(Debug, thiserror::Error)]
+pub enum ModerationError {
+    #[error("Filter '{0}' not found")]
+    FilterNotFound(String),
+
+    #[error("Filter '{0}' is not a blacklist filter")]
+    NotABlacklist(String),
+
+    #[error("Serialization error: {0}")]
+    Serialization(String),
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+impl From<serde_json::Error> for ModerationError {
+    fn from(e: serde_json::Error) -> Self {
+        ModerationError::Serialization(e.to_string())
+    }
+}
+
+/// Result type for moderation operations
+pub type ModerationResult<T> = Result<T, ModerationError>;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chat_message_defaults() {
+        let before = chrono::Utc::now();
+        let message = ChatMessage::new("twitch", "somechannel", "viewer", "hello there");
+
+        assert_eq!(message.platform, "twitch");
+        assert_eq!(message.channel, "somechannel");
+        assert_eq!(message.username, "viewer");
+        assert_eq!(message.content, "hello there");
+        assert!(message.display_name.is_none());
+        assert!(message.user_badges.is_empty());
+        assert!(!message.is_mod);
+        assert!(!message.is_subscriber);
+        assert!(message.timestamp >= before && message.timestamp <= chrono::Utc::now());
+    }
+
+    #[test]
+    fn test_chat_message_overrides() {
+        let timestamp = chrono::Utc::now() - chrono::Duration::minutes(5);
+        let message = ChatMessage::new("youtube", "stream", "modperson", "!uptime")
+            .with_display_name("ModPerson")
+            .with_timestamp(timestamp)
+            .with_badges(vec!["moderator".to_string(), "subscriber".to_string()])
+            .with_mod(true)
+            .with_subscriber(true);
+
+        assert_eq!(message.display_name.as_deref(), Some("ModPerson"));
+        assert_eq!(message.timestamp, timestamp);
+        assert_eq!(message.user_badges, vec!["moderator", "subscriber"]);
+        assert!(message.is_mod);
+        assert!(message.is_subscriber);
+    }
+
+    #[test]
+    fn test_window_timestamp_ignores_implausible_timestamps() {
+        let now = chrono::Utc::now();
+        let message = |offset_seconds: i64| ChatMessage::new("twitch", "somechannel", "viewer", "hi")
+            .with_timestamp(now + chrono::Duration::seconds(offset_seconds));
+
+        // Recent platform timestamps are kept
+        assert_eq!(message(-5).window_timestamp(now), now - chrono::Duration::seconds(5));
+        // Five minutes is too old to trust for moderation windows
+        assert_eq!(message(-300).window_timestamp(now), now);
+        // Future-dated ones are clamped to now
+        assert_eq!(message(3600).window_timestamp(now), now);
+    }
+
+    #[test]
+    fn test_spam_filter_round_trip() {
+        let filter = SpamFilter {
+            filter_type: SpamFilterType::Blacklist {
+                patterns: vec![
+                    BlacklistPattern::Literal("badword".to_string()),
+                    BlacklistPattern::Wildcard(WildcardPattern::new("*scam*")),
+                    BlacklistPattern::from_regex_string("~/fr[e3]{2}\\s+v-?bucks/i").unwrap(),
+                ],
+                case_sensitive: false,
+                whole_words_only: true,
+                except_patterns: Vec::new(),
+            },
+            enabled: true,
+            escalation: ModerationEscalation::default(),
+            exemption_level: ExemptionLevel::Moderator,
+            silent_mode: false,
+            custom_message: Some("No spam".to_string()),
+            name: "scams".to_string(),
+            grace_seconds: None,
+            log_level: None,
+            schedule: None,
+        };
+
+        let json = serde_json::to_string(&filter).unwrap();
+        let restored: SpamFilter = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored.name, "scams");
+        assert_eq!(restored.exemption_level, ExemptionLevel::Moderator);
+        assert_eq!(restored.custom_message.as_deref(), Some("No spam"));
+        assert_eq!(restored.escalation.offense_window_seconds, 3600);
+        // Serialization is stable across round trips
+        assert_eq!(serde_json::to_string(&restored).unwrap(), json);
+
+        match restored.filter_type {
+            SpamFilterType::Blacklist { patterns, whole_words_only, .. } => {
+                assert!(whole_words_only);
+                assert_eq!(patterns.len(), 3);
+                match &patterns[2] {
+                    BlacklistPattern::Regex { pattern, compiled } => {
+                        assert_eq!(pattern, "~/fr[e3]{2}\\s+v-?bucks/i");
+                        assert!(compiled.is_some());
+                    }
+                    other => panic!("Expected regex pattern, got {:?}", other),
+                }
+                assert!(patterns[2].matches("get FREE vbucks now", false, false));
+                assert!(!patterns[2].matches("free stuff", false, false));
+            }
+            other => panic!("Expected blacklist filter, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_wildcard_anchoring() {
+        let anchored = BlacklistPattern::wildcard("bad*", WildcardAnchor::Full);
+        let contains = BlacklistPattern::wildcard("bad*", WildcardAnchor::Contains);
+
+        assert!(anchored.matches("badword", false, false));
+        assert!(!anchored.matches("this is bad stuff", false, false));
+        assert!(contains.matches("this is bad stuff", false, false));
+        assert!(contains.matches("badword", false, false));
+        assert!(!contains.matches("this is fine", false, false));
+
+        // With whole-word matching, anchoring applies per word
+        assert!(anchored.matches("so badly done", false, true));
+        assert!(!anchored.matches("notbad at all", false, true));
+        assert!(contains.matches("notbad at all", false, true));
+
+        // Patterns that already float are left alone
+        assert!(matches!(BlacklistPattern::wildcard("*scam*", WildcardAnchor::Contains),
+            BlacklistPattern::Wildcard(p) if p.as_str() == "*scam*"));
+    }
+
+    #[test]
+    fn test_found_span_is_the_matched_text() {
+        let cases = [
+            (BlacklistPattern::Literal("Free Money".to_string()), "get FREE money now", false, false, "FREE money"),
+            (BlacklistPattern::Literal("scam".to_string()), "a scam, clearly", false, true, "scam"),
+            (BlacklistPattern::wildcard("*crypto*", WildcardAnchor::Full), "buy crypto today", false, false, "crypto"),
+            (BlacklistPattern::wildcard("bad*", WildcardAnchor::Full), "so badly done", false, true, "badly"),
+            (BlacklistPattern::wildcard("buy*now", WildcardAnchor::Contains), "pls buy it now ok", false, false, "buy it now"),
+            (BlacklistPattern::from_regex_string(r"~/\d{3}-\d{4}/").unwrap(), "call 555-1234 ü", false, false, "555-1234"),
+            (BlacklistPattern::Literal("café".to_string()), "Ünïcode CAFÉ here", false, true, "CAFÉ"),
+            (BlacklistPattern::Literal("café".to_string()), "Ünïcode CAFÉs here", false, false, "CAFÉ"),
+        ];
+
+        for (pattern, text, case_sensitive, whole_words_only, expected) in cases {
+            assert!(pattern.matches(text, case_sensitive, whole_words_only), "{} on {:?}", pattern.source(), text);
+            let span = pattern.find(text, case_sensitive, whole_words_only)
+                .unwrap_or_else(|| panic!("{} should find a span in {:?}", pattern.source(), text));
+            assert_eq!(&text[span.start..span.end], expected);
+            assert_eq!(span.text, expected);
+        }
+
+        let literal = BlacklistPattern::Literal("scam".to_string());
+        assert_eq!(literal.find("scammer", false, true), None);
+        let span = literal.find("a scam here", false, false).unwrap();
+        assert_eq!(span.highlight("a scam here"), "a «scam» here");
+        let spans: Vec<String> = literal.find_all("Scam, scam! SCAMscam", false, false).into_iter().map(|span| span.text).collect();
+        assert_eq!(spans, ["Scam", "scam", "SCAM", "scam"]);
+        assert_eq!(literal.find_all("Scam, scam!", true, false).len(), 1);
+    }
+
+    /// Wildcard matching as it was before patterns were precompiled
+    fn recompiling_wildcard_match(text: &str, pattern: &str, case_sensitive: bool) -> bool {
+        let (text, pattern) = if case_sensitive {
+            (text.to_string(), pattern.to_string())
+        } else {
+            (text.to_lowercase(), pattern.to_lowercase())
+        };
+        Regex::new(&format!("^{}$", pattern.replace('*', ".*").replace('?', ".")))
+            .is_ok_and(|regex| regex.is_match(&text))
+    }
+
+    #[test]
+    fn test_precompiled_wildcard_matches_like_before() {
+        let patterns = ["*scam*", "bad*", "*word", "b?d", "FREE*MONEY", "*(broken*", "exact"];
+        let texts = ["this is a SCAM", "badword", "Bad", "swearword", "bid", "free easy money", "exact", "exactly", ""];
+
+        for pattern in patterns {
+            let compiled = WildcardPattern::new(pattern);
+            for text in texts {
+                for case_sensitive in [true, false] {
+                    assert_eq!(
+                        compiled.is_match(text, case_sensitive),
+                        recompiling_wildcard_match(text, pattern, case_sensitive),
+                        "pattern {:?} on {:?} (case sensitive: {})", pattern, text, case_sensitive
+                    );
+                }
+            }
+        }
+
+        // Round-trips as the plain pattern string
+        let json = serde_json::to_string(&BlacklistPattern::Wildcard(WildcardPattern::new("*scam*"))).unwrap();
+        assert_eq!(json, r#"{"Wildcard":"*scam*"}"#);
+        let restored: BlacklistPattern = serde_json::from_str(&json).unwrap();
+        assert!(restored.matches("big SCAM here", false, false));
+    }
+
+    #[test]
+    fn test_precompiled_wildcard_is_reused() {
+        let compiles = || WILDCARD_COMPILES.with(std::cell::Cell::get);
+        let pattern = BlacklistPattern::Wildcard(WildcardPattern::new("*free*money*"));
+        let compiled = compiles();
+
+        let text = "get your free v-bucks and easy money here";
+        for _ in 0..200 {
+            assert!(pattern.matches(text, false, false));
+            assert!(pattern.matches(text, true, false));
+            assert_eq!(pattern.find_all(text, false, false).len(), 1);
+        }
+        assert_eq!(compiles(), compiled);
+        assert!(recompiling_wildcard_match(text, "*free*money*", false));
+    }
+
+    #[test]
+    fn test_invalid_regex_pattern_fails_to_deserialize() {
+        let result: Result<BlacklistPattern, _> =
+            serde_json::from_str(r#"{"Regex":{"pattern":"~/([unclosed/i"}}"#);
+        assert!(result.is_err());
+
+        let filter_type: SpamFilterType =
+            serde_json::from_str(r#"{"ExcessiveCaps":{"max_percentage":70}}"#).unwrap();
+        assert!(matches!(filter_type, SpamFilterType::ExcessiveCaps { max_percentage: 70 }));
+    }
+}
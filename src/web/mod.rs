@@ -1,23 +1,218 @@
 use axum::{
-    extract::{State},
-    http::StatusCode,
-    response::{Html, Json},
-    routing::get,
+    extract::{ConnectInfo, Path, Query, Request, State},
+    http::{header, HeaderMap, HeaderName, HeaderValue, Method, StatusCode},
+    middleware::{self, Next},
+    response::{Html, IntoResponse, Json, Response},
+    routing::{get, post},
     Router,
 };
-use log::{info};
+use log::{debug, error, info, warn};
 use std::collections::HashMap;
+use std::future::Future;
+use std::net::{IpAddr, SocketAddr};
+use std::str::FromStr;
 use std::sync::Arc;
-use tower_http::cors::CorsLayer;
+use std::time::Duration;
+use tower_http::cors::{AllowOrigin, CorsLayer};
 use tokio::sync::RwLock;
 
+use crate::adaptive::{AdaptivePerformanceSystem, HealthStatus, MetricDataPoint, ParameterChange, ParameterValue};
+use crate::bot::connection_pool::PoolStatsSummary;
+use crate::bot::enhanced_moderation::EnhancedModerationSystem;
+use crate::bot::moderation::FilterInfo;
+use crate::bot::moderation_metrics::ModerationMetricsSnapshot;
+use crate::config::{ConfigStatusSummary, ConfigurationManager};
+use crate::platforms::PlatformHealth;
+
 // Simple state struct that we can create from the bot
 #[derive(Clone)]
 pub struct DashboardState {
     pub analytics_data: Arc<RwLock<HashMap<String, serde_json::Value>>>,
-    pub health_data: Arc<RwLock<HashMap<String, bool>>>,
+    pub health_data: Arc<RwLock<HashMap<String, PlatformHealth>>>,
     pub points_data: Arc<RwLock<HashMap<String, serde_json::Value>>>,
     pub leaderboard_data: Arc<RwLock<Vec<serde_json::Value>>>,
+    pub active_filters: Arc<RwLock<Vec<FilterInfo>>>,
+    pub moderation_metrics: Arc<RwLock<ModerationMetricsSnapshot>>,
+    pub pool_stats: Arc<RwLock<PoolStatsSummary>>,
+    pub config_status: Arc<RwLock<Option<ConfigStatusSummary>>>,
+    pub adaptive_changes: Arc<RwLock<Vec<ParameterChange>>>,
+    pub adaptive_parameters: Arc<RwLock<HashMap<String, ParameterValue>>>,
+    pub adaptive_health: Arc<RwLock<Option<HealthStatus>>>,
+    pub adaptive_metrics: Arc<RwLock<HashMap<String, Vec<MetricDataPoint>>>>,
+    /// When the data updater last completed a refresh, reported by `/api/status`
+    pub last_data_update: Arc<RwLock<Option<chrono::DateTime<chrono::Utc>>>>,
+    /// Live handle for actions (manual tuning); read-only pages use the snapshots above
+    pub adaptive_system: Arc<RwLock<Option<Arc<AdaptivePerformanceSystem>>>>,
+    /// Live handle for searching the configured filters
+    pub config_manager: Arc<RwLock<Option<Arc<ConfigurationManager>>>>,
+    /// Live handle for testing messages against the loaded filters and AI patterns
+    pub moderation_system: Arc<RwLock<Option<Arc<EnhancedModerationSystem>>>>,
+    /// Bearer token required by protected endpoints (they're disabled when unset)
+    pub admin_token: Arc<RwLock<Option<String>>>,
+    pub api_access: Arc<ApiAccessConfig>,
+}
+
+/// Seconds without a data refresh before `/api/status` reports the data as stale
+pub const DATA_STALE_AFTER_SECONDS: i64 = 30;
+
+/// Header overlays use to authenticate against `/api/*`
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// Who may call the JSON API: browser origins (CORS) and overlay API keys
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ApiAccessConfig {
+    /// Browser origins allowed to call `/api/*`, e.g. `https://overlay.example.com`;
+    /// empty means localhost only, `*` allows any origin
+    pub allowed_origins: Vec<String>,
+    /// Keys accepted in the `X-API-Key` header on `/api/*`; empty disables key checks
+    pub api_keys: Vec<String>,
+    /// Client addresses allowed to reach any page; empty allows everyone
+    pub ip_allowlist: IpAllowlist,
+}
+
+/// An address or CIDR range such as `10.0.0.0/8` or `fd00::/8`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IpRange {
+    network: IpAddr,
+    prefix_len: u8,
+}
+
+impl FromStr for IpRange {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+        let (address, prefix) = match value.split_once('/') {
+            Some((address, prefix)) => (address, Some(prefix)),
+            None => (value, None),
+        };
+        let network = address.parse::<IpAddr>()
+            .map_err(|_| format!("Invalid IP address '{}'", value))?
+            .to_canonical();
+        let max_len = if network.is_ipv4() { 32 } else { 128 };
+        let prefix_len = match prefix {
+            Some(prefix) => prefix.parse::<u8>().ok().filter(|len| *len <= max_len)
+                .ok_or_else(|| format!("Invalid prefix length in '{}'", value))?,
+            None => max_len,
+        };
+        Ok(Self { network, prefix_len })
+    }
+}
+
+impl IpRange {
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        match (self.network, ip.to_canonical()) {
+            (IpAddr::V4(network), IpAddr::V4(ip)) => {
+                let mask = u32::MAX.checked_shl(32 - self.prefix_len as u32).unwrap_or(0);
+                u32::from(network) & mask == u32::from(ip) & mask
+            }
+            (IpAddr::V6(network), IpAddr::V6(ip)) => {
+                let mask = u128::MAX.checked_shl(128 - self.prefix_len as u32).unwrap_or(0);
+                u128::from(network) & mask == u128::from(ip) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Which client addresses may use the dashboard, and which reverse proxies are
+/// trusted to report the real client in `X-Forwarded-For`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IpAllowlist {
+    allowed: Vec<IpRange>,
+    trusted_proxies: Vec<IpRange>,
+}
+
+impl IpAllowlist {
+    /// Build from `security.ip_whitelist` and `security.trusted_proxies`
+    pub fn parse(allowed: &[String], trusted_proxies: &[String]) -> Result<Self, String> {
+        let ranges = |values: &[String]| values.iter()
+            .filter(|value| !value.trim().is_empty())
+            .map(|value| value.parse::<IpRange>())
+            .collect::<Result<Vec<_>, _>>();
+        Ok(Self {
+            allowed: ranges(allowed)?,
+            trusted_proxies: ranges(trusted_proxies)?,
+        })
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.allowed.is_empty()
+    }
+
+    fn is_trusted_proxy(&self, ip: IpAddr) -> bool {
+        self.trusted_proxies.iter().any(|range| range.contains(ip))
+    }
+
+    /// The client behind a request: the peer itself, or when the peer is a trusted
+    /// proxy, the nearest `X-Forwarded-For` hop that isn't another trusted proxy
+    pub fn client_ip(&self, peer: IpAddr, forwarded_for: Option<&str>) -> IpAddr {
+        if !self.is_trusted_proxy(peer) {
+            return peer;
+        }
+        let hops: Vec<IpAddr> = forwarded_for.unwrap_or_default()
+            .split(',')
+            .filter_map(|hop| hop.trim().parse().ok())
+            .collect();
+        hops.iter().rev()
+            .find(|hop| !self.is_trusted_proxy(**hop))
+            .or(hops.first())
+            .copied()
+            .unwrap_or(peer)
+    }
+
+    pub fn is_allowed(&self, ip: IpAddr) -> bool {
+        !self.is_enabled() || self.allowed.iter().any(|range| range.contains(ip))
+    }
+}
+
+impl ApiAccessConfig {
+    /// Read `DASHBOARD_ALLOWED_ORIGINS` and `DASHBOARD_API_KEYS` (both comma-separated)
+    pub fn from_env() -> Self {
+        let list = |name: &str| {
+            std::env::var(name)
+                .map(|value| Self::parse_list(&value))
+                .unwrap_or_default()
+        };
+        Self {
+            allowed_origins: list("DASHBOARD_ALLOWED_ORIGINS"),
+            api_keys: list("DASHBOARD_API_KEYS"),
+            ip_allowlist: IpAllowlist::default(),
+        }
+    }
+
+    fn parse_list(value: &str) -> Vec<String> {
+        value.split(',')
+            .map(|item| item.trim().trim_end_matches('/').to_string())
+            .filter(|item| !item.is_empty())
+            .collect()
+    }
+
+    pub fn is_origin_allowed(&self, origin: &str) -> bool {
+        let origin = origin.trim_end_matches('/');
+        if self.allowed_origins.is_empty() {
+            return Self::is_localhost(origin);
+        }
+        self.allowed_origins.iter().any(|allowed| allowed == "*" || allowed.eq_ignore_ascii_case(origin))
+    }
+
+    pub fn requires_api_key(&self) -> bool {
+        !self.api_keys.is_empty()
+    }
+
+    pub fn is_api_key_valid(&self, key: Option<&str>) -> bool {
+        !self.requires_api_key() || key.is_some_and(|key| self.api_keys.iter().any(|k| k == key))
+    }
+
+    fn is_localhost(origin: &str) -> bool {
+        let host = origin.split_once("://").map_or(origin, |(_, rest)| rest);
+        let host = if host.starts_with('[') {
+            host.split_once(']').map_or(host, |(h, _)| &h[1..])
+        } else {
+            host.split(':').next().unwrap_or(host)
+        };
+        matches!(host, "localhost" | "127.0.0.1" | "::1")
+    }
 }
 
 impl DashboardState {
@@ -27,6 +222,20 @@ impl DashboardState {
             health_data: Arc::new(RwLock::new(HashMap::new())),
             points_data: Arc::new(RwLock::new(HashMap::new())),
             leaderboard_data: Arc::new(RwLock::new(Vec::new())),
+            active_filters: Arc::new(RwLock::new(Vec::new())),
+            moderation_metrics: Arc::new(RwLock::new(ModerationMetricsSnapshot::default())),
+            pool_stats: Arc::new(RwLock::new(PoolStatsSummary::default())),
+            config_status: Arc::new(RwLock::new(None)),
+            adaptive_changes: Arc::new(RwLock::new(Vec::new())),
+            adaptive_parameters: Arc::new(RwLock::new(HashMap::new())),
+            adaptive_health: Arc::new(RwLock::new(None)),
+            adaptive_metrics: Arc::new(RwLock::new(HashMap::new())),
+            last_data_update: Arc::new(RwLock::new(None)),
+            adaptive_system: Arc::new(RwLock::new(None)),
+            config_manager: Arc::new(RwLock::new(None)),
+            moderation_system: Arc::new(RwLock::new(None)),
+            admin_token: Arc::new(RwLock::new(None)),
+            api_access: Arc::new(ApiAccessConfig::default()),
         }
     }
 
@@ -34,7 +243,7 @@ impl DashboardState {
         *self.analytics_data.write().await = data;
     }
 
-    pub async fn update_health(&self, data: HashMap<String, bool>) {
+    pub async fn update_health(&self, data: HashMap<String, PlatformHealth>) {
         *self.health_data.write().await = data;
     }
 
@@ -45,6 +254,108 @@ impl DashboardState {
     pub async fn update_leaderboard(&self, data: Vec<serde_json::Value>) {
         *self.leaderboard_data.write().await = data;
     }
+
+    pub async fn update_active_filters(&self, data: Vec<FilterInfo>) {
+        *self.active_filters.write().await = data;
+    }
+
+    pub async fn update_moderation_metrics(&self, data: ModerationMetricsSnapshot) {
+        *self.moderation_metrics.write().await = data;
+    }
+
+    pub async fn update_pool_stats(&self, data: PoolStatsSummary) {
+        *self.pool_stats.write().await = data;
+    }
+
+    pub async fn update_config_status(&self, data: ConfigStatusSummary) {
+        *self.config_status.write().await = Some(data);
+    }
+
+    pub async fn update_adaptive_changes(&self, data: Vec<ParameterChange>) {
+        *self.adaptive_changes.write().await = data;
+    }
+
+    pub async fn update_adaptive_parameters(&self, data: HashMap<String, ParameterValue>) {
+        *self.adaptive_parameters.write().await = data;
+    }
+
+    pub async fn update_adaptive_health(&self, data: HealthStatus) {
+        *self.adaptive_health.write().await = Some(data);
+    }
+
+    pub async fn update_adaptive_metrics(&self, data: HashMap<String, Vec<MetricDataPoint>>) {
+        *self.adaptive_metrics.write().await = data;
+    }
+
+    pub async fn record_data_update(&self, at: chrono::DateTime<chrono::Utc>) {
+        *self.last_data_update.write().await = Some(at);
+    }
+
+    /// Run `update` every `period`, recording a heartbeat after each refresh. If an
+    /// update panics the updater is restarted after one period instead of dying
+    pub fn spawn_updater<F, Fut>(&self, period: Duration, update: F) -> tokio::task::JoinHandle<()>
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let state = self.clone();
+        let update = Arc::new(update);
+        tokio::spawn(async move {
+            loop {
+                let state = state.clone();
+                let update = Arc::clone(&update);
+                let updater = tokio::spawn(async move {
+                    let mut interval = tokio::time::interval(period);
+                    loop {
+                        interval.tick().await;
+                        update().await;
+                        state.record_data_update(chrono::Utc::now()).await;
+                    }
+                });
+
+                match updater.await {
+                    Err(e) if e.is_panic() => {
+                        error!("Dashboard data updater panicked, restarting in {:?}", period);
+                        tokio::time::sleep(period).await;
+                    }
+                    _ => break,
+                }
+            }
+        })
+    }
+
+    pub async fn set_adaptive_system(&self, system: Arc<AdaptivePerformanceSystem>) {
+        *self.adaptive_system.write().await = Some(system);
+    }
+
+    pub async fn set_config_manager(&self, config_manager: Arc<ConfigurationManager>) {
+        *self.config_manager.write().await = Some(config_manager);
+    }
+
+    pub async fn set_moderation_system(&self, moderation_system: Arc<EnhancedModerationSystem>) {
+        *self.moderation_system.write().await = Some(moderation_system);
+    }
+
+    pub async fn set_admin_token(&self, token: Option<String>) {
+        *self.admin_token.write().await = token.filter(|t| !t.is_empty());
+    }
+
+    /// Whether the request carries the configured admin bearer token
+    async fn check_admin(&self, headers: &HeaderMap) -> Result<(), StatusCode> {
+        let token = self.admin_token.read().await;
+        let Some(expected) = token.as_deref() else {
+            return Err(StatusCode::FORBIDDEN);
+        };
+
+        let provided = headers.get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "));
+        if provided.is_some_and(|provided| constant_time_eq(provided.as_bytes(), expected.as_bytes())) {
+            Ok(())
+        } else {
+            Err(StatusCode::UNAUTHORIZED)
+        }
+    }
 }
 
 pub struct WebDashboard {
@@ -58,6 +369,12 @@ impl WebDashboard {
         }
     }
 
+    /// Restrict which origins and API keys may use `/api/*`
+    pub fn with_api_access(mut self, config: ApiAccessConfig) -> Self {
+        self.state.api_access = Arc::new(config);
+        self
+    }
+
     pub fn get_state(&self) -> DashboardState {
         self.state.clone()
     }
@@ -72,7 +389,7 @@ impl WebDashboard {
         info!("Analytics API: http://localhost:{}/api/analytics", port);
         
         info!("Starting axum server...");
-        axum::serve(listener, app).await?;
+        axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>()).await?;
         Ok(())
     }
 
@@ -81,6 +398,9 @@ impl WebDashboard {
             // Main dashboard
             .route("/", get(dashboard_html))
             .route("/dashboard", get(dashboard_html))
+            .route("/adaptive", get(adaptive_html))
+            .route("/api-client.js", get(api_client_js))
+            .route("/metrics", get(get_prometheus_metrics))
             
             // API endpoints
             .route("/api/analytics", get(get_analytics))
@@ -88,11 +408,93 @@ impl WebDashboard {
             .route("/api/status", get(get_status))
             .route("/api/points", get(get_points_stats))
             .route("/api/leaderboard", get(get_leaderboard))
+            .route("/api/filters/active", get(get_active_filters))
+            .route("/api/filters/search", get(search_filters))
+            .route("/api/filters/test", get(test_filters))
+            .route("/api/filters/:id/matches", get(get_filter_matches))
+            .route("/api/pool", get(get_pool_stats))
+            .route("/api/config/status", get(get_config_status))
+            .route("/api/adaptive/changes", get(get_adaptive_changes))
+            .route("/api/adaptive/parameters", get(get_adaptive_parameters))
+            .route("/api/adaptive/health", get(get_adaptive_health))
+            .route("/api/adaptive/metrics", get(get_adaptive_metrics))
+            .route("/api/adaptive/tune", post(trigger_adaptive_tuning))
             
-            // Enable CORS for API endpoints
-            .layer(CorsLayer::permissive())
+            // Origin and API key checks for the JSON API, with CORS outermost so
+            // preflight requests are answered before any key is required
+            .layer(middleware::from_fn_with_state(self.state.clone(), api_access_guard))
+            .layer(self.cors_layer())
+            // Addresses outside the allowlist get nothing at all
+            .layer(middleware::from_fn_with_state(self.state.clone(), ip_allowlist_guard))
             .with_state(self.state.clone())
     }
+
+    fn cors_layer(&self) -> CorsLayer {
+        let access = Arc::clone(&self.state.api_access);
+        CorsLayer::new()
+            .allow_origin(AllowOrigin::predicate(move |origin: &HeaderValue, _| {
+                origin.to_str().is_ok_and(|origin| access.is_origin_allowed(origin))
+            }))
+            .allow_methods([Method::GET, Method::POST])
+            .allow_headers([header::AUTHORIZATION, header::CONTENT_TYPE, HeaderName::from_static(API_KEY_HEADER)])
+    }
+}
+
+/// Reject `/api/*` requests from disallowed browser origins or without a valid API key
+async fn api_access_guard(State(state): State<DashboardState>, request: Request, next: Next) -> Response {
+    if !request.uri().path().starts_with("/api/") {
+        return next.run(request).await;
+    }
+
+    let headers = request.headers();
+    if let Some(origin) = headers.get(header::ORIGIN).and_then(|o| o.to_str().ok()) {
+        if !is_same_origin(origin, headers) && !state.api_access.is_origin_allowed(origin) {
+            debug!("Rejected API request from origin {}", origin);
+            return StatusCode::FORBIDDEN.into_response();
+        }
+    }
+
+    let key = headers.get(API_KEY_HEADER).and_then(|k| k.to_str().ok());
+    if !state.api_access.is_api_key_valid(key) {
+        return StatusCode::UNAUTHORIZED.into_response();
+    }
+
+    next.run(request).await
+}
+
+/// Reject requests from clients outside `security.ip_whitelist`, when it's set
+async fn ip_allowlist_guard(State(state): State<DashboardState>, request: Request, next: Next) -> Response {
+    let allowlist = &state.api_access.ip_allowlist;
+    if !allowlist.is_enabled() {
+        return next.run(request).await;
+    }
+
+    // Without the peer address there's nothing to check against, so refuse
+    let Some(ConnectInfo(peer)) = request.extensions().get::<ConnectInfo<SocketAddr>>().copied() else {
+        warn!("Rejected dashboard request with no peer address");
+        return StatusCode::FORBIDDEN.into_response();
+    };
+    let forwarded_for = request.headers().get("x-forwarded-for").and_then(|h| h.to_str().ok());
+    let client = allowlist.client_ip(peer.ip(), forwarded_for);
+    if !allowlist.is_allowed(client) {
+        warn!("Rejected dashboard request from {} (not in ip_whitelist)", client);
+        return StatusCode::FORBIDDEN.into_response();
+    }
+
+    next.run(request).await
+}
+
+/// Compare secrets without bailing out at the first differing byte, so response
+/// times don't reveal how much of a guessed token was right
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+/// The dashboard's own pages calling back to the server they were served from
+fn is_same_origin(origin: &str, headers: &HeaderMap) -> bool {
+    let host = headers.get(header::HOST).and_then(|h| h.to_str().ok());
+    let origin_host = origin.split_once("://").map(|(_, rest)| rest.trim_end_matches('/'));
+    host.is_some() && origin_host == host
 }
 
 // API Route Handlers
@@ -107,19 +509,30 @@ async fn get_analytics(State(state): State<DashboardState>) -> Result<Json<serde
 
 async fn get_health(State(state): State<DashboardState>) -> Result<Json<serde_json::Value>, StatusCode> {
     let health = state.health_data.read().await.clone();
+    let connected: HashMap<&String, bool> = health.iter()
+        .map(|(platform, status)| (platform, status.is_healthy()))
+        .collect();
+    // `data` keeps its platform -> connected shape; `platform_status` tells a
+    // rejected token apart from an outage
     Ok(Json(serde_json::json!({
         "success": true,
-        "data": health
+        "data": connected,
+        "platform_status": health
     })))
 }
 
-async fn get_status(State(_state): State<DashboardState>) -> Result<Json<serde_json::Value>, StatusCode> {
+async fn get_status(State(state): State<DashboardState>) -> Result<Json<serde_json::Value>, StatusCode> {
+    let now = chrono::Utc::now();
+    let last_update = *state.last_data_update.read().await;
+    let data_stale = last_update.is_none_or(|at| now - at > chrono::Duration::seconds(DATA_STALE_AFTER_SECONDS));
     Ok(Json(serde_json::json!({
         "success": true,
         "data": {
             "status": "running",
-            "timestamp": chrono::Utc::now(),
-            "version": env!("CARGO_PKG_VERSION")
+            "timestamp": now,
+            "version": env!("CARGO_PKG_VERSION"),
+            "data_updated_at": last_update,
+            "data_stale": data_stale
         }
     })))
 }
@@ -140,11 +553,186 @@ async fn get_leaderboard(State(state): State<DashboardState>) -> Result<Json<ser
     })))
 }
 
+async fn get_active_filters(State(state): State<DashboardState>) -> Result<Json<serde_json::Value>, StatusCode> {
+    let filters = state.active_filters.read().await.clone();
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": filters
+    })))
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct FilterSearchParams {
+    #[serde(default)]
+    q: String,
+}
+
+/// Configured filters whose name, pattern or tag contains `q` (`tag:<name>` for an exact tag)
+async fn search_filters(
+    State(state): State<DashboardState>,
+    Query(params): Query<FilterSearchParams>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let config_manager = state.config_manager.read().await.clone()
+        .ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let matches = config_manager.find_filters(&params.q).await;
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": matches
+    })))
+}
+
+/// Filters and AI patterns the message `q` would trip, with the byte range each one matched
+async fn test_filters(
+    State(state): State<DashboardState>,
+    Query(params): Query<FilterSearchParams>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let moderation_system = state.moderation_system.read().await.clone()
+        .ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let message = crate::types::ChatMessage::new("dashboard", "dashboard", "dashboard", params.q);
+    let explanations = moderation_system.explain_message(&message).await;
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": explanations
+    })))
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct RecentMatchesParams {
+    count: Option<usize>,
+}
+
+/// The last few (privacy-trimmed) messages filter `id` caught, newest first
+async fn get_filter_matches(
+    State(state): State<DashboardState>,
+    Path(id): Path<String>,
+    Query(params): Query<RecentMatchesParams>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    const DEFAULT_MATCHES: usize = 5;
+    const MAX_MATCHES: usize = 20;
+
+    let moderation_system = state.moderation_system.read().await.clone()
+        .ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let count = params.count.unwrap_or(DEFAULT_MATCHES).clamp(1, MAX_MATCHES);
+    let matches = moderation_system.get_recent_matches(&id, count).await;
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": matches
+    })))
+}
+
+/// Moderation counters for Prometheus scraping
+async fn get_prometheus_metrics(State(state): State<DashboardState>) -> impl IntoResponse {
+    let metrics = state.moderation_metrics.read().await.to_prometheus();
+    ([(header::CONTENT_TYPE, "text/plain; version=0.0.4")], metrics)
+}
+
+async fn get_adaptive_changes(State(state): State<DashboardState>) -> Result<Json<serde_json::Value>, StatusCode> {
+    let changes = state.adaptive_changes.read().await.clone();
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": changes
+    })))
+}
+
+async fn get_adaptive_parameters(State(state): State<DashboardState>) -> Result<Json<serde_json::Value>, StatusCode> {
+    let parameters = state.adaptive_parameters.read().await.clone();
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": parameters
+    })))
+}
+
+async fn get_adaptive_health(State(state): State<DashboardState>) -> Result<Json<serde_json::Value>, StatusCode> {
+    let health = state.adaptive_health.read().await.clone();
+    Ok(Json(serde_json::json!({
+        "success": health.is_some(),
+        "data": health
+    })))
+}
+
+async fn get_pool_stats(State(state): State<DashboardState>) -> Result<Json<serde_json::Value>, StatusCode> {
+    let stats = state.pool_stats.read().await.clone();
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": stats
+    })))
+}
+
+async fn get_config_status(State(state): State<DashboardState>) -> Result<Json<serde_json::Value>, StatusCode> {
+    let status = state.config_status.read().await.clone();
+    Ok(Json(serde_json::json!({
+        "success": status.is_some(),
+        "data": status
+    })))
+}
+
+async fn get_adaptive_metrics(State(state): State<DashboardState>) -> Result<Json<serde_json::Value>, StatusCode> {
+    let metrics = state.adaptive_metrics.read().await.clone();
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": metrics
+    })))
+}
+
+/// Run a tuning cycle now (requires `Authorization: Bearer <DASHBOARD_ADMIN_TOKEN>`)
+async fn trigger_adaptive_tuning(
+    State(state): State<DashboardState>,
+    headers: HeaderMap,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    state.check_admin(&headers).await?;
+
+    let system = state.adaptive_system.read().await.clone()
+        .ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+
+    info!("Manual tuning cycle requested from dashboard");
+    match system.trigger_tuning_cycle().await {
+        Ok(result) => Ok(Json(serde_json::json!({
+            "success": true,
+            "data": result
+        }))),
+        Err(e) => {
+            warn!("Dashboard-triggered tuning cycle failed: {}", e);
+            Ok(Json(serde_json::json!({
+                "success": false,
+                "error": e.to_string()
+            })))
+        }
+    }
+}
+
 // Embedded HTML Dashboard
 async fn dashboard_html() -> Html<&'static str> {
     Html(DASHBOARD_HTML)
 }
 
+async fn adaptive_html() -> Html<&'static str> {
+    Html(ADAPTIVE_HTML)
+}
+
+async fn api_client_js() -> impl IntoResponse {
+    ([(header::CONTENT_TYPE, "application/javascript")], API_CLIENT_JS)
+}
+
+/// Shared by the dashboard pages for their API calls
+const API_CLIENT_JS: &str = r#"
+// Sends the overlay API key when the server requires one (DASHBOARD_API_KEYS)
+async function apiFetch(url, options = {}) {
+    const send = () => fetch(url, {
+        ...options,
+        headers: { ...(options.headers || {}), 'X-API-Key': localStorage.getItem('notabotApiKey') || '' }
+    });
+    let response = await send();
+    if (response.status === 401 && url !== '/api/adaptive/tune') {
+        const key = prompt('Dashboard API key:');
+        if (key) {
+            localStorage.setItem('notabotApiKey', key);
+            response = await send();
+        }
+    }
+    return response;
+}
+"#;
+
 const DASHBOARD_HTML: &str = r#"
 <!DOCTYPE html>
 <html lang="en">
@@ -305,6 +893,7 @@ const DASHBOARD_HTML: &str = r#"
             <h1>NotaBot Dashboard</h1>
             <p>Real-time chat bot analytics and management</p>
             <button class="refresh-btn" onclick="loadAllData()">Refresh Data</button>
+            <a class="refresh-btn" href="/adaptive" style="text-decoration: none; display: inline-block;">Adaptive Tuning</a>
         </div>
 
         <div class="stats-grid" id="stats-grid">
@@ -324,8 +913,23 @@ const DASHBOARD_HTML: &str = r#"
                 <div class="loading">Loading bot status...</div>
             </div>
         </div>
+
+        <div class="section">
+            <h2>Find Filters</h2>
+            <input id="filter-search" type="search" placeholder="Phrase, filter name or tag:crypto"
+                   style="width: 100%; padding: 12px 16px; border-radius: 12px; border: 1px solid #dee2e6; font-size: 1rem;">
+            <div id="filter-results"></div>
+        </div>
+
+        <div class="section">
+            <h2>Test a Message</h2>
+            <input id="filter-test" type="search" placeholder="Message to check against the filters"
+                   style="width: 100%; padding: 12px 16px; border-radius: 12px; border: 1px solid #dee2e6; font-size: 1rem;">
+            <div id="filter-test-results"></div>
+        </div>
     </div>
 
+    <script src="/api-client.js"></script>
     <script>
         async function loadAllData() {
             console.log('Refreshing dashboard data...');
@@ -338,7 +942,7 @@ const DASHBOARD_HTML: &str = r#"
 
         async function loadAnalytics() {
             try {
-                const response = await fetch('/api/analytics');
+                const response = await apiFetch('/api/analytics');
                 const result = await response.json();
                 
                 if (result.success) {
@@ -382,20 +986,24 @@ const DASHBOARD_HTML: &str = r#"
 
         async function loadConnections() {
             try {
-                const response = await fetch('/api/health');
+                const response = await apiFetch('/api/health');
                 const result = await response.json();
                 
                 if (result.success) {
                     const connections = Object.entries(result.data)
-                        .map(([platform, status]) => `
+                        .map(([platform, online]) => {
+                            const status = (result.platform_status || {})[platform];
+                            const label = online ? 'Connected' : status === 'auth_failed' ? 'Auth failed' : 'Disconnected';
+                            return `
                             <div class="connection-item">
-                                <span class="status-indicator ${status ? 'status-online' : 'status-offline'}"></span>
+                                <span class="status-indicator ${online ? 'status-online' : 'status-offline'}"></span>
                                 <strong>${platform.toUpperCase()}</strong>
-                                <span style="float: right; color: ${status ? '#27ae60' : '#e74c3c'}; font-weight: 600;">
-                                    ${status ? 'Connected' : 'Disconnected'}
+                                <span style="float: right; color: ${online ? '#27ae60' : '#e74c3c'}; font-weight: 600;">
+                                    ${label}
                                 </span>
                             </div>
-                        `).join('');
+                        `;
+                        }).join('');
                     
                     document.getElementById('connections').innerHTML = 
                         connections || '<div class="connection-item">No platforms configured</div>';
@@ -412,7 +1020,7 @@ const DASHBOARD_HTML: &str = r#"
 
         async function loadStatus() {
             try {
-                const response = await fetch('/api/status');
+                const response = await apiFetch('/api/status');
                 const result = await response.json();
                 
                 if (result.success) {
@@ -441,6 +1049,104 @@ const DASHBOARD_HTML: &str = r#"
             }
         }
 
+        function escapeHtml(text) {
+            const div = document.createElement('div');
+            div.textContent = text;
+            return div.innerHTML;
+        }
+
+        async function searchFilters(query) {
+            const results = document.getElementById('filter-results');
+            if (!query.trim()) {
+                results.innerHTML = '';
+                return;
+            }
+            try {
+                const response = await apiFetch('/api/filters/search?q=' + encodeURIComponent(query));
+                if (!response.ok) {
+                    results.innerHTML = '<div class="error">❌ Filter search is unavailable</div>';
+                    return;
+                }
+                const result = await response.json();
+                foundFilters = result.data;
+                results.innerHTML = result.data.map((match, index) => `
+                    <div class="connection-item">
+                        <span class="status-indicator ${match.enabled ? 'status-online' : 'status-offline'}"></span>
+                        <strong>${escapeHtml(match.id)}</strong> ${escapeHtml(match.name)}
+                        <span style="float: right; color: #6c757d;">
+                            ${match.kind} · ${match.field}: ${escapeHtml(match.matched)}
+                        </span>
+                        <div><button type="button" data-filter-index="${index}">Recent matches</button></div>
+                        <div id="filter-matches-${index}"></div>
+                    </div>
+                `).join('') || '<div class="connection-item">No filters match</div>';
+            } catch (error) {
+                console.error('❌ Failed to search filters:', error);
+                results.innerHTML = '<div class="error">❌ Failed to search filters</div>';
+            }
+        }
+
+        let foundFilters = [];
+
+        async function showRecentMatches(index) {
+            const target = document.getElementById('filter-matches-' + index);
+            const filter = foundFilters[index];
+            if (!target || !filter) return;
+            try {
+                const response = await apiFetch('/api/filters/' + encodeURIComponent(filter.id) + '/matches');
+                if (!response.ok) {
+                    target.innerHTML = '<div class="error">❌ Recent matches are unavailable</div>';
+                    return;
+                }
+                const result = await response.json();
+                target.innerHTML = result.data.map(match => `
+                    <div style="color: #6c757d;">
+                        ${new Date(match.timestamp).toLocaleTimeString()} · ${escapeHtml(match.snippet)}
+                    </div>
+                `).join('') || '<div style="color: #6c757d;">No recent matches</div>';
+            } catch (error) {
+                console.error('❌ Failed to load recent matches:', error);
+                target.innerHTML = '<div class="error">❌ Failed to load recent matches</div>';
+            }
+        }
+
+        // Spans are byte offsets into the UTF-8 text
+        function highlightSpan(text, span) {
+            if (!span) return escapeHtml(text);
+            const bytes = new TextEncoder().encode(text);
+            const decoder = new TextDecoder();
+            return escapeHtml(decoder.decode(bytes.slice(0, span.start)))
+                + '<mark>' + escapeHtml(decoder.decode(bytes.slice(span.start, span.end))) + '</mark>'
+                + escapeHtml(decoder.decode(bytes.slice(span.end)));
+        }
+
+        async function testFilters(message) {
+            const results = document.getElementById('filter-test-results');
+            if (!message.trim()) {
+                results.innerHTML = '';
+                return;
+            }
+            try {
+                const response = await apiFetch('/api/filters/test?q=' + encodeURIComponent(message));
+                if (!response.ok) {
+                    results.innerHTML = '<div class="error">❌ Filter testing is unavailable</div>';
+                    return;
+                }
+                const result = await response.json();
+                results.innerHTML = result.data.map(match => `
+                    <div class="connection-item">
+                        <span class="status-indicator ${match.excepted ? 'status-offline' : 'status-online'}"></span>
+                        <strong>${escapeHtml(match.filter)}</strong>
+                        ${match.pattern ? escapeHtml(match.pattern) : ''}${match.excepted ? ' (excepted)' : ''}
+                        <div>${match.span ? highlightSpan(match.matched_text, match.span) : 'whole message'}</div>
+                    </div>
+                `).join('') || '<div class="connection-item">No filters match</div>';
+            } catch (error) {
+                console.error('❌ Failed to test filters:', error);
+                results.innerHTML = '<div class="error">❌ Failed to test filters</div>';
+            }
+        }
+
         function formatNumber(num) {
             if (num >= 1000000) return (num / 1000000).toFixed(1) + 'M';
             if (num >= 1000) return (num / 1000).toFixed(1) + 'K';
@@ -451,6 +1157,23 @@ const DASHBOARD_HTML: &str = r#"
         document.addEventListener('DOMContentLoaded', () => {
             console.log('NotaBot Dashboard initialized');
             loadAllData();
+
+            let searchTimer;
+            document.getElementById('filter-search').addEventListener('input', event => {
+                clearTimeout(searchTimer);
+                searchTimer = setTimeout(() => searchFilters(event.target.value), 250);
+            });
+            document.getElementById('filter-results').addEventListener('click', event => {
+                const index = event.target.dataset.filterIndex;
+                if (index === undefined) return;
+                showRecentMatches(Number(index));
+            });
+
+            let testTimer;
+            document.getElementById('filter-test').addEventListener('input', event => {
+                clearTimeout(testTimer);
+                testTimer = setTimeout(() => testFilters(event.target.value), 250);
+            });
             
             // Auto-refresh every 30 seconds
             setInterval(loadAllData, 30000);
@@ -458,4 +1181,640 @@ const DASHBOARD_HTML: &str = r#"
     </script>
 </body>
 </html>
-"#;
\ No newline at end of file
+"#;
+const ADAPTIVE_HTML: &str = r##"
+<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="UTF-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1.0">
+    <title>NotABot Adaptive Tuning</title>
+    <style>
+        * { margin: 0; padding: 0; box-sizing: border-box; }
+        body {
+            font-family: 'Segoe UI', system-ui, -apple-system, sans-serif;
+            background: linear-gradient(135deg, #667eea 0%, #764ba2 100%);
+            color: #333;
+            min-height: 100vh;
+            padding: 20px;
+        }
+        .container {
+            max-width: 1200px;
+            margin: 0 auto;
+            background: white;
+            border-radius: 20px;
+            box-shadow: 0 25px 50px rgba(0,0,0,0.15);
+            overflow: hidden;
+        }
+        .header {
+            background: linear-gradient(135deg, #4facfe 0%, #00f2fe 100%);
+            color: white;
+            padding: 40px;
+            text-align: center;
+        }
+        .header h1 { font-size: 2.5rem; font-weight: 700; margin-bottom: 10px; }
+        .header a, .header button {
+            display: inline-block;
+            background: rgba(255,255,255,0.2);
+            color: white;
+            border: 2px solid rgba(255,255,255,0.3);
+            padding: 10px 20px;
+            border-radius: 50px;
+            font-size: 1rem;
+            cursor: pointer;
+            text-decoration: none;
+            margin: 15px 5px 0;
+        }
+        .section {
+            margin: 20px 40px;
+            padding: 30px;
+            background: linear-gradient(135deg, #f8f9fa 0%, #e9ecef 100%);
+            border-radius: 16px;
+            border: 1px solid #dee2e6;
+        }
+        .section h2 { color: #2c3e50; margin-bottom: 20px; font-size: 1.6rem; font-weight: 600; }
+        table { width: 100%; border-collapse: collapse; background: white; border-radius: 12px; overflow: hidden; }
+        th, td { padding: 10px 14px; text-align: left; border-bottom: 1px solid #e9ecef; font-size: 0.95rem; }
+        th { background: #f1f3f5; color: #495057; }
+        .why { color: #6c757d; font-size: 0.85rem; }
+        .series { display: grid; grid-template-columns: repeat(auto-fit, minmax(260px, 1fr)); gap: 15px; }
+        .series-card { background: white; border-radius: 12px; padding: 15px; border: 1px solid #e9ecef; }
+        .series-card svg { width: 100%; height: 60px; }
+        .ok { color: #27ae60; font-weight: 600; }
+        .bad { color: #e74c3c; font-weight: 600; }
+        .loading { text-align: center; padding: 30px; color: #6c757d; }
+        .error { color: #e74c3c; padding: 15px; background: #fadbd8; border-radius: 8px; }
+    </style>
+</head>
+<body>
+    <div class="container">
+        <div class="header">
+            <h1>Adaptive Tuning</h1>
+            <p>Parameters, tuning decisions and system health</p>
+            <a href="/dashboard">Back to Dashboard</a>
+            <button onclick="loadAll()">Refresh</button>
+            <button onclick="runTuning()">Run Tuning Cycle</button>
+            <div id="tune-result"></div>
+        </div>
+
+        <div class="section">
+            <h2>Health &amp; Safety</h2>
+            <div id="health"><div class="loading">Loading health...</div></div>
+        </div>
+
+        <div class="section">
+            <h2>Current Parameters</h2>
+            <div id="parameters"><div class="loading">Loading parameters...</div></div>
+        </div>
+
+        <div class="section">
+            <h2>Recent Changes</h2>
+            <div id="changes"><div class="loading">Loading changes...</div></div>
+        </div>
+
+        <div class="section">
+            <h2>Metrics</h2>
+            <div id="metrics" class="series"><div class="loading">Loading metrics...</div></div>
+        </div>
+    </div>
+
+    <script src="/api-client.js"></script>
+    <script>
+        async function fetchData(url) {
+            const response = await apiFetch(url);
+            const result = await response.json();
+            return result.success ? result.data : null;
+        }
+
+        function formatValue(value) {
+            if (value === null || typeof value !== 'object') return String(value);
+            const [kind, inner] = Object.entries(value)[0];
+            return kind === 'Duration' ? inner + 'ms' : String(inner);
+        }
+
+        async function loadHealth() {
+            try {
+                const health = await fetchData('/api/adaptive/health');
+                if (!health) {
+                    document.getElementById('health').innerHTML = '<div class="loading">No health data yet</div>';
+                    return;
+                }
+                const safety = health.safety_status;
+                document.getElementById('health').innerHTML = `
+                    <table>
+                        <tr><th>Overall health</th><td>${(health.overall_health * 100).toFixed(1)}%</td></tr>
+                        <tr><th>Metrics health</th><td>${(health.metrics_health * 100).toFixed(1)}%</td></tr>
+                        <tr><th>Safety</th><td class="${safety.is_safe ? 'ok' : 'bad'}">${safety.is_safe ? 'Safe' : 'Unsafe'} (score ${safety.safety_score.toFixed(2)})</td></tr>
+                        <tr><th>Circuit breaker</th><td>${safety.circuit_breaker_state}</td></tr>
+                        <tr><th>Rollbacks (last hour)</th><td>${safety.rollbacks_in_last_hour}</td></tr>
+                        <tr><th>Last tuning cycle</th><td>${new Date(health.last_tuning_cycle).toLocaleString()}</td></tr>
+                        ${safety.warnings.map(w => `<tr><th>Warning</th><td class="bad">${w}</td></tr>`).join('')}
+                    </table>`;
+            } catch (error) {
+                document.getElementById('health').innerHTML = '<div class="error">Failed to load health</div>';
+            }
+        }
+
+        async function loadParameters() {
+            try {
+                const parameters = await fetchData('/api/adaptive/parameters') || {};
+                const rows = Object.keys(parameters).sort()
+                    .map(name => `<tr><td>${name}</td><td>${formatValue(parameters[name])}</td></tr>`)
+                    .join('');
+                document.getElementById('parameters').innerHTML = rows
+                    ? `<table><tr><th>Parameter</th><th>Value</th></tr>${rows}</table>`
+                    : '<div class="loading">No parameters reported yet</div>';
+            } catch (error) {
+                document.getElementById('parameters').innerHTML = '<div class="error">Failed to load parameters</div>';
+            }
+        }
+
+        async function loadChanges() {
+            try {
+                const changes = await fetchData('/api/adaptive/changes') || [];
+                const rows = changes.map(change => {
+                    const why = change.justification;
+                    const detail = why
+                        ? `<div class="why">${why.strategy}: ${why.metric} ${why.metric_value.toFixed(2)} vs target ${why.target_value.toFixed(2)}, expected to ${why.expected_effect}</div>`
+                        : '';
+                    return `<tr>
+                        <td>${new Date(change.timestamp * 1000).toLocaleString()}</td>
+                        <td>${change.parameter_name}</td>
+                        <td>${formatValue(change.old_value)} → ${formatValue(change.new_value)}</td>
+                        <td>${change.reason}${detail}</td>
+                    </tr>`;
+                }).join('');
+                document.getElementById('changes').innerHTML = rows
+                    ? `<table><tr><th>When</th><th>Parameter</th><th>Change</th><th>Why</th></tr>${rows}</table>`
+                    : '<div class="loading">No tuning changes yet</div>';
+            } catch (error) {
+                document.getElementById('changes').innerHTML = '<div class="error">Failed to load changes</div>';
+            }
+        }
+
+        function sparkline(points) {
+            if (points.length < 2) return '';
+            const values = points.map(p => p.value);
+            const min = Math.min(...values);
+            const range = (Math.max(...values) - min) || 1;
+            const coords = values.map((v, i) =>
+                `${(i / (values.length - 1) * 100).toFixed(1)},${(55 - (v - min) / range * 50).toFixed(1)}`);
+            return `<svg viewBox="0 0 100 60" preserveAspectRatio="none"><polyline fill="none" stroke="#667eea" stroke-width="1.5" points="${coords.join(' ')}"/></svg>`;
+        }
+
+        async function loadMetrics() {
+            try {
+                const metrics = await fetchData('/api/adaptive/metrics') || {};
+                const cards = Object.keys(metrics).sort().map(name => {
+                    const points = metrics[name];
+                    const latest = points.length ? points[points.length - 1].value.toFixed(2) : '-';
+                    return `<div class="series-card"><strong>${name}</strong> <span class="why">latest ${latest}</span>${sparkline(points)}</div>`;
+                }).join('');
+                document.getElementById('metrics').innerHTML = cards || '<div class="loading">No metrics collected yet</div>';
+            } catch (error) {
+                document.getElementById('metrics').innerHTML = '<div class="error">Failed to load metrics</div>';
+            }
+        }
+
+        async function runTuning() {
+            let token = localStorage.getItem('notabotAdminToken');
+            if (!token) {
+                token = prompt('Admin token (DASHBOARD_ADMIN_TOKEN):');
+                if (!token) return;
+            }
+            const output = document.getElementById('tune-result');
+            output.textContent = 'Running tuning cycle...';
+            try {
+                const response = await apiFetch('/api/adaptive/tune', {
+                    method: 'POST',
+                    headers: { 'Authorization': 'Bearer ' + token }
+                });
+                if (response.status === 401) {
+                    localStorage.removeItem('notabotAdminToken');
+                    output.textContent = 'Invalid admin token or API key';
+                    return;
+                }
+                if (!response.ok) {
+                    output.textContent = response.status === 403
+                        ? 'Manual tuning is disabled (set DASHBOARD_ADMIN_TOKEN)'
+                        : 'Tuning unavailable (' + response.status + ')';
+                    return;
+                }
+                localStorage.setItem('notabotAdminToken', token);
+                const result = await response.json();
+                output.textContent = result.success
+                    ? `Tuning cycle applied ${result.data.changes.length} change(s)`
+                    : 'Tuning cycle failed: ' + result.error;
+                loadAll();
+            } catch (error) {
+                output.textContent = 'Failed to trigger tuning cycle';
+            }
+        }
+
+        function loadAll() {
+            return Promise.all([loadHealth(), loadParameters(), loadChanges(), loadMetrics()]);
+        }
+
+        document.addEventListener('DOMContentLoaded', () => {
+            loadAll();
+            setInterval(loadAll, 30000);
+        });
+    </script>
+</body>
+</html>
+"##;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adaptive::AdaptiveConfig;
+    use axum::body::Body;
+    use tower::Service;
+
+    async fn json_of(response: Result<Json<serde_json::Value>, StatusCode>) -> serde_json::Value {
+        response.expect("handler should succeed").0
+    }
+
+    #[tokio::test]
+    async fn test_adaptive_endpoints_json_shapes() {
+        let state = DashboardState::new();
+        let system = AdaptivePerformanceSystem::new(AdaptiveConfig::default()).unwrap();
+        system.record_metric("chat_latency", 42.0).await.unwrap();
+
+        state.update_adaptive_parameters(system.get_current_parameters().await.unwrap()).await;
+        state.update_adaptive_health(system.get_health_status().await.unwrap()).await;
+        state.update_adaptive_metrics(system.get_metric_series(10).await.unwrap()).await;
+
+        let parameters = json_of(get_adaptive_parameters(State(state.clone())).await).await;
+        assert_eq!(parameters["success"], true);
+        assert_eq!(parameters["data"]["connection_timeout_ms"]["Duration"], 30000);
+
+        let health = json_of(get_adaptive_health(State(state.clone())).await).await;
+        assert_eq!(health["success"], true);
+        assert!(health["data"]["overall_health"].is_number());
+        assert!(health["data"]["safety_status"]["is_safe"].is_boolean());
+        assert!(health["data"]["last_tuning_cycle"].is_string());
+
+        let metrics = json_of(get_adaptive_metrics(State(state.clone())).await).await;
+        let series = metrics["data"]["chat_latency"].as_array().expect("series for the recorded metric");
+        assert_eq!(series[0]["value"], 42.0);
+        assert!(series[0]["timestamp"].is_number());
+
+        let changes = json_of(get_adaptive_changes(State(state.clone())).await).await;
+        assert_eq!(changes["success"], true);
+        assert!(changes["data"].as_array().unwrap().is_empty());
+
+        // Before the first update there's no health snapshot to report
+        let empty = json_of(get_adaptive_health(State(DashboardState::new())).await).await;
+        assert_eq!(empty["success"], false);
+        assert!(empty["data"].is_null());
+    }
+
+    #[tokio::test]
+    async fn test_pool_and_config_endpoints() {
+        let state = DashboardState::new();
+        let empty = json_of(get_config_status(State(state.clone())).await).await;
+        assert_eq!(empty["success"], false);
+        assert!(empty["data"].is_null());
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config_manager = crate::config::ConfigurationManager::new(temp_dir.path());
+        config_manager.initialize().await.unwrap();
+        let status = config_manager.get_config_status().await;
+        state.update_config_status(status.clone()).await;
+        state.update_pool_stats(crate::bot::connection_pool::ConnectionPool::with_default_config().get_stats_summary().await).await;
+
+        let config = json_of(get_config_status(State(state.clone())).await).await;
+        assert_eq!(config["success"], true);
+        assert_eq!(config["data"]["total_timers"], status.total_timers);
+        assert!(config["data"]["enabled_blacklist_filters"].is_number());
+        assert_eq!(config["data"]["reloads"]["debounce_skipped"], 0);
+
+        let pool = json_of(get_pool_stats(State(state.clone())).await).await;
+        assert_eq!(pool["success"], true);
+        assert!(pool["data"]["platforms"].as_array().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_filter_search_endpoint() {
+        let state = DashboardState::new();
+        let search = |q: &str| search_filters(State(state.clone()), Query(FilterSearchParams { q: q.to_string() }));
+        assert_eq!(search("crypto").await.unwrap_err(), StatusCode::SERVICE_UNAVAILABLE);
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config_manager = Arc::new(crate::config::ConfigurationManager::new(temp_dir.path()));
+        config_manager.initialize().await.unwrap();
+        state.set_config_manager(config_manager).await;
+
+        let found = json_of(search("tag:crypto").await).await;
+        assert_eq!(found["success"], true);
+        assert_eq!(found["data"][0]["id"], "crypto_spam");
+        assert_eq!(found["data"][0]["field"], "tag");
+        assert!(json_of(search("no such phrase").await).await["data"].as_array().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_filter_test_endpoint_returns_spans() {
+        let state = DashboardState::new();
+        let test = |q: &str| test_filters(State(state.clone()), Query(FilterSearchParams { q: q.to_string() }));
+        assert_eq!(test("anything").await.unwrap_err(), StatusCode::SERVICE_UNAVAILABLE);
+
+        let moderation = Arc::new(crate::bot::moderation::ModerationSystem::new());
+        moderation.add_spam_filter(crate::types::SpamFilterType::Blacklist {
+            patterns: vec![crate::types::BlacklistPattern::Literal("buy followers".to_string())],
+            case_sensitive: false,
+            whole_words_only: false,
+            except_patterns: Vec::new(),
+        }).await.unwrap();
+        let enhanced = EnhancedModerationSystem::new(moderation);
+        enhanced.add_advanced_pattern(crate::bot::pattern_matching::AdvancedPattern::Leetspeak("cheap".to_string())).await.unwrap();
+        state.set_moderation_system(Arc::new(enhanced)).await;
+
+        let text = "hey Buy Followers ch34p";
+        let found = json_of(test(text).await).await;
+        assert_eq!(found["success"], true);
+        let span = &found["data"][0]["span"];
+        let (start, end) = (span["start"].as_u64().unwrap() as usize, span["end"].as_u64().unwrap() as usize);
+        assert_eq!(&text[start..end], "Buy Followers");
+        assert_eq!(span["text"], "Buy Followers");
+        assert_eq!(found["data"][1]["filter"], "leetspeak#0");
+        assert_eq!(found["data"][1]["span"]["text"], "ch34p");
+        assert!(json_of(test("hello there").await).await["data"].as_array().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_filter_matches_endpoint_lists_recent_matches() {
+        let state = DashboardState::new();
+        let matches = |id: &str, count: Option<usize>| get_filter_matches(
+            State(state.clone()),
+            Path(id.to_string()),
+            Query(RecentMatchesParams { count }),
+        );
+        assert_eq!(matches("scam_links", None).await.unwrap_err(), StatusCode::SERVICE_UNAVAILABLE);
+
+        let moderation = Arc::new(crate::bot::moderation::ModerationSystem::new());
+        moderation.add_blacklist_filter(
+            "scam_links".to_string(), vec!["freecoins".to_string()], false, false,
+            crate::types::ExemptionLevel::None, 300, None,
+        ).await.unwrap();
+        let enhanced = Arc::new(EnhancedModerationSystem::new(moderation));
+        for content in ["freecoins for @someone", "more freecoins"] {
+            let spam = crate::types::ChatMessage::new("twitch", "testchannel", "spammer", content);
+            assert!(enhanced.check_message_enhanced(&spam, None).await.is_some());
+        }
+        state.set_moderation_system(enhanced).await;
+
+        let found = json_of(matches("scam_links", None).await).await;
+        let snippets: Vec<_> = found["data"].as_array().unwrap().iter().map(|m| m["snippet"].clone()).collect();
+        assert_eq!(snippets.len(), 2);
+        assert_eq!(snippets[0], "more freecoins");
+        assert!(!snippets[1].as_str().unwrap().contains("someone"));
+        assert_eq!(json_of(matches("scam_links", Some(1)).await).await["data"].as_array().unwrap().len(), 1);
+        assert!(json_of(matches("other", None).await).await["data"].as_array().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_updater_restarts_after_a_panic() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let state = DashboardState::new();
+        let status = json_of(get_status(State(state.clone())).await).await;
+        assert_eq!(status["data"]["data_stale"], true);
+        assert!(status["data"]["data_updated_at"].is_null());
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+        let updater = state.spawn_updater(Duration::from_millis(10), move || {
+            let call = counter.fetch_add(1, Ordering::SeqCst);
+            async move {
+                if call == 0 {
+                    panic!("analytics read failed");
+                }
+            }
+        });
+
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while state.last_data_update.read().await.is_none() {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        }).await.expect("updater should recover from the panic");
+        assert!(calls.load(Ordering::SeqCst) >= 2);
+        assert!(!updater.is_finished());
+
+        let status = json_of(get_status(State(state.clone())).await).await;
+        assert_eq!(status["data"]["data_stale"], false);
+        assert!(status["data"]["data_updated_at"].is_string());
+        updater.abort();
+    }
+
+    #[tokio::test]
+    async fn test_manual_tuning_requires_admin_token() {
+        let state = DashboardState::new();
+        let mut headers = HeaderMap::new();
+
+        // Disabled until a token is configured
+        let result = trigger_adaptive_tuning(State(state.clone()), headers.clone()).await;
+        assert_eq!(result.unwrap_err(), StatusCode::FORBIDDEN);
+
+        state.set_admin_token(Some("s3cret".to_string())).await;
+        let result = trigger_adaptive_tuning(State(state.clone()), headers.clone()).await;
+        assert_eq!(result.unwrap_err(), StatusCode::UNAUTHORIZED);
+
+        headers.insert(header::AUTHORIZATION, HeaderValue::from_static("Bearer wrong"));
+        let result = trigger_adaptive_tuning(State(state.clone()), headers.clone()).await;
+        assert_eq!(result.unwrap_err(), StatusCode::UNAUTHORIZED);
+
+        // Authorized, but there's no adaptive system to tune
+        headers.insert(header::AUTHORIZATION, HeaderValue::from_static("Bearer s3cret"));
+        let result = trigger_adaptive_tuning(State(state.clone()), headers).await;
+        assert_eq!(result.unwrap_err(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[tokio::test]
+    async fn test_health_reports_connected_flags_and_status() {
+        let state = DashboardState::new();
+        state.update_health(HashMap::from([
+            ("twitch".to_string(), PlatformHealth::AuthFailed),
+            ("youtube".to_string(), PlatformHealth::Connected),
+        ])).await;
+
+        let health = get_health(State(state)).await.unwrap().0;
+        assert_eq!(health["data"]["twitch"], false);
+        assert_eq!(health["data"]["youtube"], true);
+        assert_eq!(health["platform_status"]["twitch"], "auth_failed");
+        assert_eq!(health["platform_status"]["youtube"], "connected");
+    }
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"s3cret", b"s3cret"));
+        assert!(!constant_time_eq(b"s3cret", b"s3creT"));
+        assert!(!constant_time_eq(b"s3cret", b"s3cre"));
+        assert!(!constant_time_eq(b"", b"s3cret"));
+        assert!(constant_time_eq(b"", b""));
+    }
+
+    async fn api_request(dashboard: &WebDashboard, origin: Option<&str>, key: Option<&str>) -> Response {
+        let mut request = Request::builder().uri("/api/status").header(header::HOST, "bot.local:3000");
+        if let Some(origin) = origin {
+            request = request.header(header::ORIGIN, origin);
+        }
+        if let Some(key) = key {
+            request = request.header(API_KEY_HEADER, key);
+        }
+        dashboard.create_routes().call(request.body(Body::empty()).unwrap()).await.unwrap()
+    }
+
+    #[test]
+    fn test_default_access_allows_localhost_only() {
+        let access = ApiAccessConfig::default();
+        assert!(access.is_origin_allowed("http://localhost:5173"));
+        assert!(access.is_origin_allowed("http://127.0.0.1"));
+        assert!(access.is_origin_allowed("http://[::1]:8080"));
+        assert!(!access.is_origin_allowed("https://localhost.evil.example"));
+        assert!(!access.requires_api_key());
+        assert!(access.is_api_key_valid(None));
+    }
+
+    #[tokio::test]
+    async fn test_disallowed_origins_are_rejected() {
+        let dashboard = WebDashboard::new().with_api_access(ApiAccessConfig {
+            allowed_origins: ApiAccessConfig::parse_list("https://overlay.example.com/, https://obs.example.com"),
+            ..Default::default()
+        });
+
+        let response = api_request(&dashboard, Some("https://evil.example.com"), None).await;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        // Configuring origins replaces the localhost default
+        let response = api_request(&dashboard, Some("http://localhost:3000"), None).await;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let response = api_request(&dashboard, Some("https://overlay.example.com"), None).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN], "https://overlay.example.com");
+
+        // The dashboard's own pages and non-browser clients still work
+        assert_eq!(api_request(&dashboard, Some("http://bot.local:3000"), None).await.status(), StatusCode::OK);
+        assert_eq!(api_request(&dashboard, None, None).await.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_api_keys_are_required_when_configured() {
+        let dashboard = WebDashboard::new().with_api_access(ApiAccessConfig {
+            api_keys: vec!["overlay-key".to_string()],
+            ..Default::default()
+        });
+
+        assert_eq!(api_request(&dashboard, None, None).await.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(api_request(&dashboard, None, Some("wrong")).await.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(api_request(&dashboard, None, Some("overlay-key")).await.status(), StatusCode::OK);
+        assert_eq!(
+            api_request(&dashboard, Some("http://localhost:5173"), Some("overlay-key")).await.status(),
+            StatusCode::OK
+        );
+
+        // Pages and their shared script aren't part of the API
+        for uri in ["/dashboard", "/api-client.js"] {
+            let page = Request::builder().uri(uri).body(Body::empty()).unwrap();
+            let response = dashboard.create_routes().call(page).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+    }
+
+    #[test]
+    fn test_ip_ranges_match_cidr() {
+        let range: IpRange = "192.168.1.0/24".parse().unwrap();
+        assert!(range.contains("192.168.1.77".parse().unwrap()));
+        assert!(!range.contains("192.168.2.1".parse().unwrap()));
+        // IPv4-mapped IPv6 peers (dual-stack listeners) match IPv4 ranges
+        assert!(range.contains("::ffff:192.168.1.5".parse().unwrap()));
+
+        let single: IpRange = "10.0.0.5".parse().unwrap();
+        assert!(single.contains("10.0.0.5".parse().unwrap()));
+        assert!(!single.contains("10.0.0.6".parse().unwrap()));
+
+        let v6: IpRange = "fd00::/8".parse().unwrap();
+        assert!(v6.contains("fd12:3456::1".parse().unwrap()));
+        assert!(!v6.contains("fe80::1".parse().unwrap()));
+        assert!(!v6.contains("10.0.0.5".parse().unwrap()));
+
+        let everything: IpRange = "0.0.0.0/0".parse().unwrap();
+        assert!(everything.contains("203.0.113.9".parse().unwrap()));
+
+        assert!("10.0.0.0/33".parse::<IpRange>().is_err());
+        assert!("not-an-ip".parse::<IpRange>().is_err());
+        assert!(IpAllowlist::parse(&["10.0.0.0/8".to_string(), "bogus".to_string()], &[]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_ip_allowlist_rejects_unlisted_clients() {
+        let allowlist = IpAllowlist::parse(
+            &["127.0.0.1".to_string(), "192.168.1.0/24".to_string()],
+            &["10.0.0.1".to_string()],
+        ).unwrap();
+        let dashboard = WebDashboard::new().with_api_access(ApiAccessConfig {
+            ip_allowlist: allowlist,
+            ..Default::default()
+        });
+        let request_from = |peer: &str, forwarded_for: Option<&str>| {
+            let mut request = Request::builder().uri("/api/status");
+            if let Some(forwarded_for) = forwarded_for {
+                request = request.header("x-forwarded-for", forwarded_for);
+            }
+            let mut request = request.body(Body::empty()).unwrap();
+            request.extensions_mut().insert(ConnectInfo(SocketAddr::new(peer.parse().unwrap(), 50000)));
+            request
+        };
+        let status = |request: Request| async {
+            dashboard.create_routes().call(request).await.unwrap().status()
+        };
+
+        assert_eq!(status(request_from("127.0.0.1", None)).await, StatusCode::OK);
+        assert_eq!(status(request_from("192.168.1.40", None)).await, StatusCode::OK);
+        assert_eq!(status(request_from("203.0.113.9", None)).await, StatusCode::FORBIDDEN);
+        // Pages are covered too, not just the API
+        let mut page = Request::builder().uri("/dashboard").body(Body::empty()).unwrap();
+        page.extensions_mut().insert(ConnectInfo(SocketAddr::new("203.0.113.9".parse().unwrap(), 50000)));
+        assert_eq!(status(page).await, StatusCode::FORBIDDEN);
+
+        // X-Forwarded-For is only believed from a trusted proxy
+        assert_eq!(status(request_from("203.0.113.9", Some("192.168.1.40"))).await, StatusCode::FORBIDDEN);
+        assert_eq!(status(request_from("10.0.0.1", Some("192.168.1.40"))).await, StatusCode::OK);
+        assert_eq!(status(request_from("10.0.0.1", Some("203.0.113.9"))).await, StatusCode::FORBIDDEN);
+        // A client can't smuggle an allowed address in ahead of its real one
+        assert_eq!(status(request_from("10.0.0.1", Some("192.168.1.40, 203.0.113.9"))).await, StatusCode::FORBIDDEN);
+        // The proxy itself isn't in the allowlist
+        assert_eq!(status(request_from("10.0.0.1", None)).await, StatusCode::FORBIDDEN);
+
+        // Without a peer address the request can't be checked
+        let unknown = Request::builder().uri("/api/status").body(Body::empty()).unwrap();
+        assert_eq!(status(unknown).await, StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_prometheus_metrics_endpoint() {
+        use crate::bot::moderation_metrics::ModerationMetrics;
+
+        let dashboard = WebDashboard::new().with_api_access(ApiAccessConfig {
+            api_keys: vec!["overlay-key".to_string()],
+            ..Default::default()
+        });
+        let mut metrics = ModerationMetrics::default();
+        let now = chrono::Utc::now();
+        metrics.record(None, now);
+        metrics.record(Some("caps"), now);
+        dashboard.get_state().update_moderation_metrics(metrics.snapshot(now)).await;
+
+        // Scrapers don't need the overlay API key
+        let request = Request::builder().uri("/metrics").body(Body::empty()).unwrap();
+        let response = dashboard.create_routes().call(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.headers()[header::CONTENT_TYPE].to_str().unwrap().starts_with("text/plain"));
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        assert!(body.contains("notabot_moderation_messages_checked_total 2\n"));
+        assert!(body.contains("notabot_moderation_filter_blocks_total{filter=\"caps\"} 1\n"));
+        assert!(body.contains("notabot_moderation_block_rate 0.5\n"));
+    }
+}
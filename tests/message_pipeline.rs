@@ -0,0 +1,1113 @@
+//! End-to-end test of the message pipeline: platform -> moderation -> commands -> response
+
+use notabot::platforms::mock::MockConnection;
+use notabot::prelude::*;
+use notabot::bot::points::UserPoints;
+use notabot::types::BlacklistPattern;
+use tokio::time::{sleep, Duration};
+
+const WAIT: Duration = Duration::from_secs(5);
+
+fn chat_message(username: &str, content: &str) -> ChatMessage {
+    ChatMessage::new("mock", "testchannel", username, content)
+}
+
+fn twitch_message(username: &str, content: &str) -> ChatMessage {
+    ChatMessage::new("twitch", "testchannel", username, content)
+}
+
+/// Poll `check` until it returns true or the wait expires
+async fn eventually<F, Fut>(check: F) -> bool
+where
+    F: Fn() -> Fut,
+    Fut: std::future::Future<Output = bool>,
+{
+    let deadline = tokio::time::Instant::now() + WAIT;
+    while tokio::time::Instant::now() < deadline {
+        if check().await {
+            return true;
+        }
+        sleep(Duration::from_millis(20)).await;
+    }
+    false
+}
+
+#[tokio::test]
+async fn test_spam_and_command_flow_through_pipeline() {
+    let connection = MockConnection::new("mock", vec!["testchannel".to_string()]);
+    let mut bot = ChatBot::new();
+    bot.add_connection(Box::new(connection.clone())).await;
+
+    bot.add_command("hello".to_string(), "Hello $(user)!".to_string(), false, 0).await;
+    bot.add_blacklist_filter(
+        vec!["buyfollowers".to_string()],
+        Some(60),
+        None,
+        None,
+        None,
+        Some("no spam please".to_string()),
+    ).await.unwrap();
+
+    bot.start().await.unwrap();
+
+    // Spam is blocked and the first-offense warning is sent back to the channel
+    connection.inject(chat_message("spammer", "cheap buyfollowers here")).unwrap();
+    let warning = connection.wait_for_sent(WAIT, |_, msg| msg.contains("@spammer")).await;
+    assert_eq!(
+        warning,
+        Some(("testchannel".to_string(), "@spammer no spam please".to_string()))
+    );
+
+    // Commands in a flagged message are never processed
+    connection.inject(chat_message("spammer", "!hello buyfollowers")).unwrap();
+
+    // A clean command gets its response
+    connection.inject(chat_message("viewer", "!hello")).unwrap();
+    let reply = connection.wait_for_sent(WAIT, |_, msg| msg == "Hello viewer!").await;
+    assert_eq!(reply, Some(("testchannel".to_string(), "Hello viewer!".to_string())));
+
+    let sent = connection.sent_messages().await;
+    assert!(!sent.iter().any(|(_, msg)| msg == "Hello spammer!"));
+
+    // Points are tracked for every message, and command use only for clean ones
+    assert!(eventually(|| async {
+        bot.get_user_points("mock", "viewer").await
+            .map(|p| p.messages_sent == 1 && p.commands_used == 1)
+            .unwrap_or(false)
+    }).await);
+    let spammer_points = bot.get_user_points("mock", "spammer").await.unwrap();
+    assert_eq!(spammer_points.messages_sent, 2);
+    assert_eq!(spammer_points.commands_used, 0);
+
+    // Analytics record both the blocked spam and the command
+    assert!(eventually(|| async {
+        let analytics = bot.get_analytics().await;
+        analytics.get("total_spam_blocked").and_then(|v| v.as_u64()) == Some(2)
+            && analytics.get("total_commands_used").and_then(|v| v.as_u64()) == Some(1)
+    }).await);
+
+    let filter_stats = bot.get_filter_stats().await;
+    assert_eq!(filter_stats.get("total_violations").and_then(|v| v.as_u64()), Some(2));
+
+    bot.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_dropped_responses_reach_analytics() {
+    let connection = MockConnection::new("mock", vec!["testchannel".to_string()]);
+    let mut bot = ChatBot::new();
+    bot.add_connection(Box::new(connection.clone())).await;
+    bot.add_command("hello".to_string(), "Hello $(user)!".to_string(), false, 0).await;
+    bot.start().await.unwrap();
+
+    // Get the first-message achievement announcement out of the way
+    connection.inject(chat_message("viewer", "hi")).unwrap();
+    assert!(connection.wait_for_sent(WAIT, |_, msg| msg.contains("viewer UNLOCKED")).await.is_some());
+    let delivered = connection.sent_messages().await.len();
+
+    connection.set_drop_reason(Some("msg_duplicate".to_string())).await;
+    connection.inject(chat_message("viewer", "!hello")).unwrap();
+
+    assert!(eventually(|| async {
+        bot.get_analytics().await.get("total_messages_dropped").and_then(|v| v.as_u64()) == Some(1)
+    }).await);
+    assert_eq!(connection.sent_messages().await.len(), delivered);
+
+    // Delivered messages aren't counted
+    connection.set_drop_reason(None).await;
+    connection.inject(chat_message("viewer", "!hello")).unwrap();
+    assert!(connection.wait_for_sent(WAIT, |_, msg| msg == "Hello viewer!").await.is_some());
+    assert_eq!(bot.get_analytics().await.get("total_messages_dropped").and_then(|v| v.as_u64()), Some(1));
+
+    bot.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_filtered_users_are_ignored() {
+    let connection = MockConnection::new("mock", vec!["testchannel".to_string()]);
+    let mut bot = ChatBot::new();
+    bot.add_connection(Box::new(connection.clone())).await;
+    bot.add_command("hello".to_string(), "Hello $(user)!".to_string(), false, 0).await;
+    bot.add_blacklist_filter(
+        vec!["buyfollowers".to_string()],
+        Some(60),
+        None,
+        None,
+        None,
+        Some("no spam please".to_string()),
+    ).await.unwrap();
+    bot.set_user_filter(UserFilter::deny(["altbot"])).await;
+    bot.start().await.unwrap();
+
+    connection.inject(chat_message("AltBot", "cheap buyfollowers here")).unwrap();
+    connection.inject(chat_message("AltBot", "!hello")).unwrap();
+    connection.inject(chat_message("viewer", "!hello")).unwrap();
+    assert!(connection.wait_for_sent(WAIT, |_, msg| msg == "Hello viewer!").await.is_some());
+
+    // Nothing from the denied user reached moderation, commands, points or analytics
+    let sent = connection.sent_messages().await;
+    assert!(!sent.iter().any(|(_, msg)| msg.to_lowercase().contains("altbot")));
+    assert!(bot.get_user_points("mock", "altbot").await.is_none());
+    assert_eq!(bot.get_filter_stats().await.get("total_violations").and_then(|v| v.as_u64()), Some(0));
+    assert!(eventually(|| async {
+        bot.get_analytics().await.get("total_commands_used").and_then(|v| v.as_u64()) == Some(1)
+    }).await);
+
+    // Allowlist mode processes only the listed users
+    bot.set_user_filter(UserFilter::default().with_allowlist(["tester"])).await;
+    connection.inject(chat_message("viewer", "!hello")).unwrap();
+    connection.inject(chat_message("tester", "!hello")).unwrap();
+    assert!(connection.wait_for_sent(WAIT, |_, msg| msg == "Hello tester!").await.is_some());
+    let greetings = connection.sent_messages().await.iter().filter(|(_, msg)| msg == "Hello viewer!").count();
+    assert_eq!(greetings, 1);
+
+    bot.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_dry_run_sends_nothing() {
+    let connection = MockConnection::new("mock", vec!["testchannel".to_string()]);
+    let mut bot = ChatBot::new();
+    bot.add_connection(Box::new(connection.clone())).await;
+    bot.add_command("hello".to_string(), "Hello $(user)!".to_string(), false, 0).await;
+    bot.add_blacklist_filter(
+        vec!["buyfollowers".to_string()],
+        Some(60),
+        None,
+        None,
+        None,
+        Some("no spam please".to_string()),
+    ).await.unwrap();
+    bot.set_dry_run(true);
+    bot.start().await.unwrap();
+
+    connection.inject(chat_message("spammer", "cheap buyfollowers here")).unwrap();
+    connection.inject(chat_message("viewer", "!hello")).unwrap();
+
+    // Decisions are still made and recorded...
+    assert!(eventually(|| async {
+        let analytics = bot.get_analytics().await;
+        analytics.get("total_spam_blocked").and_then(|v| v.as_u64()) == Some(1)
+            && analytics.get("total_commands_used").and_then(|v| v.as_u64()) == Some(1)
+    }).await);
+    let violation = bot.get_moderation_system().get_last_violation("mock", "spammer").await.unwrap();
+    assert!(matches!(violation.action_taken, ModerationAction::WarnUser { .. }));
+
+    // ...but nothing reaches the platform
+    sleep(Duration::from_millis(200)).await;
+    assert!(connection.sent_messages().await.is_empty());
+
+    bot.set_dry_run(false);
+    connection.inject(chat_message("viewer", "!hello")).unwrap();
+    assert!(connection.wait_for_sent(WAIT, |_, msg| msg == "Hello viewer!").await.is_some());
+    assert!(!connection.sent_messages().await.iter().any(|(_, msg)| msg.contains("@spammer")));
+
+    bot.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_raid_mode_batches_welcomes() {
+    let connection = MockConnection::new("twitch", vec!["testchannel".to_string()]);
+    let mut bot = ChatBot::new();
+    bot.add_connection(Box::new(connection.clone())).await;
+    bot.add_spam_filter(SpamFilterType::LinkBlocking { allow_mods: true, whitelist: Vec::new() }).await.unwrap();
+    bot.set_raid_mode_config(RaidModeConfig {
+        new_chatter_threshold: 0,
+        detection_window_seconds: 30,
+        duration_seconds: 2,
+        relax_link_rules: true,
+    }).await;
+    bot.start().await.unwrap();
+    bot.start_raid_mode("twitch", "testchannel", Some("FriendlyStreamer".to_string())).await;
+
+    // Raiders may link the raiding channel in their first message, and their
+    // first-message achievements aren't announced one by one
+    connection.inject(twitch_message("raider1", "hype! twitch.tv/friendlystreamer")).unwrap();
+    connection.inject(twitch_message("raider2", "hello everyone")).unwrap();
+    assert!(eventually(|| async {
+        bot.get_user_points("twitch", "raider1").await.is_some() && bot.get_user_points("twitch", "raider2").await.is_some()
+    }).await);
+
+    // Later messages get the normal link rules
+    connection.inject(twitch_message("raider1", "twitch.tv/friendlystreamer again")).unwrap();
+    assert!(connection.wait_for_sent(WAIT, |_, msg| msg.starts_with("@raider1")).await.is_some());
+    // Timer posts aside, the warning is the only message about the raiders
+    let sent = connection.sent_messages().await;
+    let about_raiders: Vec<_> = sent.iter().filter(|(_, msg)| msg.contains("raider")).collect();
+    assert_eq!(about_raiders.len(), 1, "{:?}", about_raiders);
+    assert!(bot.is_raid_mode_active("twitch", "testchannel").await);
+
+    // One combined welcome once the window ends
+    let welcome = connection.wait_for_sent(WAIT, |_, msg| msg.contains("Welcome to everyone")).await;
+    assert_eq!(
+        welcome.unwrap().1,
+        "👋 Welcome to everyone joining us from friendlystreamer: raider1, raider2!"
+    );
+    assert!(!bot.is_raid_mode_active("twitch", "testchannel").await);
+
+    // Normal behaviour afterwards
+    connection.inject(twitch_message("newbie", "twitch.tv/friendlystreamer")).unwrap();
+    assert!(connection.wait_for_sent(WAIT, |_, msg| msg.starts_with("@newbie")).await.is_some());
+    connection.inject(twitch_message("latecomer", "hi")).unwrap();
+    assert!(connection.wait_for_sent(WAIT, |_, msg| msg.contains("latecomer UNLOCKED")).await.is_some());
+
+    bot.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_twitch_raid_notification_starts_raid_mode() {
+    use notabot::platforms::twitch_eventsub::TwitchEvent;
+
+    let connection = MockConnection::new("twitch", vec!["testchannel".to_string()]);
+    let mut bot = ChatBot::new();
+    bot.add_connection(Box::new(connection.clone())).await;
+    bot.add_spam_filter(SpamFilterType::LinkBlocking { allow_mods: true, whitelist: Vec::new() }).await.unwrap();
+    bot.set_raid_mode_config(RaidModeConfig {
+        new_chatter_threshold: 0,
+        detection_window_seconds: 30,
+        duration_seconds: 60,
+        relax_link_rules: true,
+    }).await;
+    bot.start().await.unwrap();
+
+    connection.inject_event(TwitchEvent::Raid {
+        channel: "testchannel".to_string(),
+        raider: "friendlystreamer".to_string(),
+        viewers: 42,
+    }).unwrap();
+    assert!(eventually(|| async { bot.is_raid_mode_active("twitch", "testchannel").await }).await);
+
+    // The raider's channel is allowed in first messages without anyone calling start_raid_mode
+    connection.inject(twitch_message("raider1", "hype! twitch.tv/friendlystreamer")).unwrap();
+    assert!(eventually(|| async { bot.get_user_points("twitch", "raider1").await.is_some() }).await);
+    connection.inject(twitch_message("raider1", "twitch.tv/friendlystreamer again")).unwrap();
+    assert!(connection.wait_for_sent(WAIT, |_, msg| msg.starts_with("@raider1")).await.is_some());
+    let warnings = connection.sent_messages().await.into_iter().filter(|(_, msg)| msg.starts_with("@raider1")).count();
+    assert_eq!(warnings, 1);
+
+    bot.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_raids_are_detected_without_points() {
+    let connection = MockConnection::new("mock", vec!["testchannel".to_string()]);
+    let mut bot = ChatBot::new();
+    bot.add_connection(Box::new(connection.clone())).await;
+    bot.set_platform_features("mock", PlatformFeatures {
+        points: false,
+        ..PlatformFeatures::default()
+    }).await;
+    bot.set_raid_mode_config(RaidModeConfig {
+        new_chatter_threshold: 3,
+        detection_window_seconds: 30,
+        duration_seconds: 60,
+        relax_link_rules: false,
+    }).await;
+    bot.start().await.unwrap();
+
+    // A regular chatting again is no new chatter
+    connection.inject(chat_message("regular", "hi")).unwrap();
+    connection.inject(chat_message("regular", "hi again")).unwrap();
+    connection.inject(chat_message("newcomer1", "hello")).unwrap();
+    sleep(Duration::from_millis(200)).await;
+    assert!(!bot.is_raid_mode_active("mock", "testchannel").await);
+
+    connection.inject(chat_message("newcomer2", "hello")).unwrap();
+    assert!(eventually(|| async { bot.is_raid_mode_active("mock", "testchannel").await }).await);
+
+    bot.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_platform_features_are_enforced() {
+    let mock = MockConnection::new("mock", vec!["testchannel".to_string()]);
+    let other = MockConnection::new("other", vec!["otherchannel".to_string()]);
+    let mut bot = ChatBot::new();
+    bot.add_connection(Box::new(mock.clone())).await;
+    bot.add_connection(Box::new(other.clone())).await;
+    bot.add_command("hello".to_string(), "Hello $(user)!".to_string(), false, 0).await;
+    bot.set_platform_features("mock", PlatformFeatures {
+        commands: false,
+        points: false,
+        ..PlatformFeatures::default()
+    }).await;
+    bot.start().await.unwrap();
+
+    mock.inject(chat_message("viewer", "!hello")).unwrap();
+    other.inject(ChatMessage::new("other", "otherchannel", "viewer", "!hello")).unwrap();
+
+    // Commands and points still work on the other platform
+    assert!(other.wait_for_sent(WAIT, |_, msg| msg == "Hello viewer!").await.is_some());
+    assert!(eventually(|| async {
+        bot.get_user_points("other", "viewer").await.is_some_and(|p| p.commands_used == 1)
+    }).await);
+
+    // ...but are skipped where they're turned off
+    sleep(Duration::from_millis(200)).await;
+    assert!(!mock.sent_messages().await.iter().any(|(_, msg)| msg == "Hello viewer!"));
+    assert!(bot.get_user_points("mock", "viewer").await.is_none());
+
+    bot.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_restricted_hours_toggle_chat_mode() {
+    let connection = MockConnection::new("mock", vec!["testchannel".to_string()]);
+    let mut bot = ChatBot::new();
+    bot.add_connection(Box::new(connection.clone())).await;
+    bot.add_blacklist_filter(vec!["lurkbait".to_string()], None, None, None, None, None).await.unwrap();
+    let (strict_filter, _) = bot.list_filters().await.into_iter().next().unwrap();
+    bot.set_filter_enabled(&strict_filter, false).await.unwrap();
+
+    // A window a few hours from now, so the bot's own scheduled check stays outside it
+    let now = chrono::Utc::now();
+    let at = |hours| now + chrono::Duration::hours(hours);
+    bot.set_restricted_hours(RestrictedHoursConfig {
+        windows: vec![TimeRange {
+            start: at(2).format("%H:%M").to_string(),
+            end: at(4).format("%H:%M").to_string(),
+            timezone: Some("UTC".to_string()),
+        }],
+        restriction: Some(ChatRestriction::FollowersOnly),
+        strict_filters: vec![strict_filter.clone()],
+        start_message: Some("Chat is followers-only overnight".to_string()),
+        end_message: None,
+    }).await;
+    bot.start().await.unwrap();
+    let filter_enabled = |filters: Vec<(String, bool)>| filters.iter().any(|(name, enabled)| *name == strict_filter && *enabled);
+
+    // Inside the window: restricted, with the stricter filters on
+    assert_eq!(bot.check_restricted_hours(at(3)).await, Some(RestrictedHoursChange::Activated));
+    assert_eq!(connection.chat_restriction("testchannel").await, Some(ChatRestriction::FollowersOnly));
+    assert!(filter_enabled(bot.list_filters().await));
+    assert!(connection.sent_messages().await.iter().any(|(_, msg)| msg == "Chat is followers-only overnight"));
+    assert_eq!(bot.check_restricted_hours(at(3) + chrono::Duration::minutes(30)).await, None);
+
+    // Outside it: back to normal
+    assert_eq!(bot.check_restricted_hours(at(5)).await, Some(RestrictedHoursChange::Deactivated));
+    assert_eq!(connection.chat_restriction("testchannel").await, None);
+    assert!(!filter_enabled(bot.list_filters().await));
+
+    bot.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_message_log_rebuilds_analytics_after_a_crash() {
+    let dir = tempfile::tempdir().unwrap();
+    let log_config = MessageLogConfig {
+        enabled: true,
+        path: dir.path().join("messages.jsonl"),
+        ..Default::default()
+    };
+
+    let connection = MockConnection::new("mock", vec!["testchannel".to_string()]);
+    let mut bot = ChatBot::new();
+    bot.add_connection(Box::new(connection.clone())).await;
+    bot.add_blacklist_filter(
+        vec!["buyfollowers".to_string()],
+        Some(60),
+        None,
+        None,
+        None,
+        Some("no spam please".to_string()),
+    ).await.unwrap();
+    bot.set_message_log(log_config.clone()).await.unwrap();
+    bot.start().await.unwrap();
+
+    connection.inject(chat_message("regular", "hello chat")).unwrap();
+    connection.inject(chat_message("spammer", "cheap buyfollowers here")).unwrap();
+    assert!(connection.wait_for_sent(WAIT, |_, msg| msg.contains("no spam please")).await.is_some());
+    assert!(eventually(|| async {
+        notabot::bot::message_log::MessageLog::replay(&log_config).await.unwrap().messages.len() == 2
+    }).await);
+    // The first process crashes: it never shuts down
+
+    // A fresh process has lost the in-memory state until the log is replayed
+    let restarted_connection = MockConnection::new("mock", vec!["testchannel".to_string()]);
+    let mut restarted = ChatBot::new();
+    restarted.add_connection(Box::new(restarted_connection.clone())).await;
+    restarted.add_blacklist_filter(
+        vec!["buyfollowers".to_string()],
+        Some(60),
+        None,
+        None,
+        None,
+        Some("no spam please".to_string()),
+    ).await.unwrap();
+    restarted.set_message_log(log_config.clone()).await.unwrap();
+    restarted.start().await.unwrap();
+
+    // Replayed messages earn no points
+    assert!(restarted.get_user_points("mock", "regular").await.is_none());
+    assert!(eventually(|| async {
+        restarted.get_analytics().await.get("total_messages").and_then(|v| v.as_u64()) == Some(2)
+    }).await);
+
+    // Replayed messages are not moderated again
+    sleep(Duration::from_millis(200)).await;
+    let sent = restarted_connection.sent_messages().await;
+    assert!(!sent.iter().any(|(_, msg)| msg.contains("no spam please")));
+    assert_eq!(restarted.get_filter_stats().await.get("total_violations").and_then(|v| v.as_u64()), Some(0));
+
+    // After a clean shutdown there's nothing left to replay
+    restarted.shutdown().await.unwrap();
+    assert!(notabot::bot::message_log::MessageLog::replay(&log_config).await.unwrap().messages.is_empty());
+}
+
+#[tokio::test]
+async fn test_state_is_written_under_the_data_dir() {
+    use notabot::bot::data_dir::{ACTIVE_GIVEAWAY_FILE, POINTS_SEASONS_FILE};
+    use notabot::types::GiveawayType;
+
+    let dir = tempfile::tempdir().unwrap();
+    let data_dir = DataDir::new(dir.path().join("state"));
+    data_dir.ensure().await.unwrap();
+
+    let bot = ChatBot::new();
+    bot.set_data_dir(&data_dir).await.unwrap();
+    bot.get_points_system().start_new_season("Spring").await.unwrap();
+    bot.start_giveaway(
+        GiveawayType::Keyword {
+            keyword: "!enter".to_string(),
+            case_sensitive: false,
+            anti_spam: true,
+            max_entries_per_user: Some(1),
+        },
+        "moderator".to_string(),
+        "testchannel".to_string(),
+        "mock".to_string(),
+        None,
+    ).await.unwrap();
+
+    assert!(data_dir.file(POINTS_SEASONS_FILE).exists());
+    assert!(data_dir.file(ACTIVE_GIVEAWAY_FILE).exists());
+
+    // A restarted bot pointed at the same directory picks the state back up
+    let restarted = ChatBot::new();
+    restarted.set_data_dir(&data_dir).await.unwrap();
+    assert!(restarted.get_giveaway_status().await.is_some());
+    restarted.get_points_system().load_seasons().await.unwrap();
+    assert_eq!(restarted.get_points_system().list_seasons().await, vec!["Spring".to_string()]);
+}
+
+#[tokio::test]
+async fn test_adaptive_metrics_come_from_processed_messages() {
+    let adaptive = std::sync::Arc::new(
+        AdaptivePerformanceSystem::new(notabot::adaptive::AdaptiveConfig::default()).unwrap()
+    );
+    let connection = MockConnection::new("mock", vec!["testchannel".to_string()]);
+    let mut bot = ChatBot::new();
+    bot.add_connection(Box::new(connection.clone())).await;
+    bot.add_command("hello".to_string(), "Hello $(user)!".to_string(), false, 0).await;
+    bot.attach_adaptive_system(adaptive.clone()).await;
+    bot.start().await.unwrap();
+
+    // Nothing processed yet: no latency is reported, only zero throughput
+    let idle = bot.report_pipeline_metrics().await.unwrap().unwrap();
+    assert_eq!(idle.messages_processed, 0);
+    let series = adaptive.get_metric_series(10).await.unwrap();
+    assert!(!series.contains_key("latency_message_processing"));
+
+    connection.inject(chat_message("viewer", "hi")).unwrap();
+    connection.inject(chat_message("viewer", "!hello")).unwrap();
+    connection.inject(chat_message("other", "good stream")).unwrap();
+    let pipeline_metrics = bot.get_pipeline_metrics();
+    assert!(eventually(|| async { pipeline_metrics.messages_in_window() == 3 }).await);
+
+    let window = bot.report_pipeline_metrics().await.unwrap().unwrap();
+    assert_eq!(window.messages_processed, 3);
+    assert_eq!(window.errors, 0);
+    assert!(window.average_latency_ms > 0.0);
+
+    let series = adaptive.get_metric_series(10).await.unwrap();
+    let latency: Vec<f64> = series["latency_message_processing"].iter().map(|p| p.value).collect();
+    assert_eq!(latency, vec![window.average_latency_ms]);
+    assert_eq!(series["throughput_messages"].last().unwrap().value, window.messages_per_second());
+    assert!(series["error_rate"].iter().all(|p| p.value == 0.0));
+    // The simulated metrics are gone
+    assert!(!series.contains_key("message_processing_latency"));
+
+    bot.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_doctor_reports_failing_checks() {
+    use notabot::bot::diagnostics::{CheckStatus, DiagnosticsOptions};
+    use notabot::config::{BotConfiguration, PlatformConfig, RateLimitConfig};
+
+    let config_dir = tempfile::tempdir().unwrap();
+    let mut bot_config = BotConfiguration::default();
+    bot_config.platforms.insert("mock".to_string(), PlatformConfig {
+        enabled: true,
+        connection_settings: serde_json::json!({ "token": "${NOTABOT_DOCTOR_TEST_UNSET}" }),
+        rate_limits: RateLimitConfig { messages_per_second: 1.0, burst_limit: 1, cooldown_seconds: 1 },
+        features: PlatformFeatures::default(),
+        webhooks: Vec::new(),
+    });
+    std::fs::write(config_dir.path().join("bot.yaml"), serde_yaml::to_string(&bot_config).unwrap()).unwrap();
+    std::fs::write(config_dir.path().join("timers.yaml"), "timers: {broken").unwrap();
+
+    let connection = MockConnection::new("mock", vec!["testchannel".to_string()]);
+    let mut bot = ChatBot::new();
+    bot.add_connection(Box::new(connection.clone())).await;
+    let options = DiagnosticsOptions {
+        config_dir: Some(config_dir.path().to_path_buf()),
+        data_dirs: vec![config_dir.path().join("data")],
+        ..Default::default()
+    };
+    bot.set_diagnostics_options(options.clone()).await;
+
+    let report = bot.run_diagnostics().await;
+    let status = |name: &str| report.checks.iter().find(|c| c.name.ends_with(name)).map(|c| c.status);
+    assert_eq!(status("bot.yaml"), Some(CheckStatus::Pass));
+    assert_eq!(status("timers.yaml"), Some(CheckStatus::Fail));
+    assert_eq!(status("filters.yaml"), Some(CheckStatus::Warn));
+    assert_eq!(status("env vars for mock"), Some(CheckStatus::Fail));
+    assert_eq!(status("platform mock"), Some(CheckStatus::Fail));
+    assert_eq!(status("data"), Some(CheckStatus::Pass));
+    assert_eq!(report.overall(), CheckStatus::Fail);
+
+    // The CLI flag tries to connect
+    bot.set_diagnostics_options(DiagnosticsOptions { connect_platforms: true, ..options }).await;
+    let report = bot.run_diagnostics().await;
+    let platform = report.checks.iter().find(|c| c.name == "platform mock").unwrap();
+    assert_eq!((platform.status, platform.detail.as_str()), (CheckStatus::Pass, "connected successfully"));
+
+    // Moderators can run it from chat, which only shows the counts
+    bot.start().await.unwrap();
+    connection.inject(chat_message("viewer", "!doctor")).unwrap();
+    connection.inject(chat_message("moderator", "!doctor").with_mod(true)).unwrap();
+    let reply = connection.wait_for_sent(WAIT, |_, msg| msg.starts_with("Doctor:")).await.unwrap();
+    assert!(reply.1.contains("failed"), "reply was {}", reply.1);
+    assert!(!reply.1.contains("timers.yaml") && !reply.1.contains("NOTABOT_DOCTOR_TEST_UNSET"), "reply was {}", reply.1);
+    sleep(Duration::from_millis(100)).await;
+    let doctor_replies = connection.sent_messages().await.iter().filter(|(_, msg)| msg.starts_with("Doctor:")).count();
+    assert_eq!(doctor_replies, 1);
+
+    bot.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_moderation_actions_are_queued_until_reconnect() {
+    let connection = MockConnection::new("mock", vec!["testchannel".to_string()]);
+    let mut bot = ChatBot::new();
+    bot.add_connection(Box::new(connection.clone())).await;
+    bot.add_blacklist_filter(
+        vec!["buyfollowers".to_string()],
+        Some(60),
+        None,
+        None,
+        None,
+        Some("no spam please".to_string()),
+    ).await.unwrap();
+    bot.start().await.unwrap();
+
+    // The platform drops while spam is being moderated
+    connection.clone().disconnect().await.unwrap();
+    connection.inject(chat_message("spammer", "cheap buyfollowers here")).unwrap();
+    assert!(eventually(|| async { bot.pending_action_count().await == 1 }).await);
+    assert!(connection.sent_messages().await.is_empty());
+
+    // The action goes out once the connection recovers
+    connection.clone().connect().await.unwrap();
+    let warning = connection.wait_for_sent(WAIT, |_, msg| msg.contains("@spammer")).await;
+    assert_eq!(
+        warning,
+        Some(("testchannel".to_string(), "@spammer no spam please".to_string()))
+    );
+    assert!(eventually(|| async { bot.pending_action_count().await == 0 }).await);
+
+    // Actions too old by the time the platform returns are dropped instead
+    bot.set_pending_action_config(PendingActionConfig { max_age_seconds: 0, ..Default::default() }).await;
+    connection.clone().disconnect().await.unwrap();
+    connection.inject(chat_message("another", "cheap buyfollowers here")).unwrap();
+    assert!(eventually(|| async { bot.pending_action_count().await == 1 }).await);
+    sleep(Duration::from_millis(1100)).await;
+    connection.clone().connect().await.unwrap();
+    assert!(eventually(|| async { bot.pending_action_count().await == 0 }).await);
+    sleep(Duration::from_millis(1100)).await;
+    let sent = connection.sent_messages().await;
+    assert!(!sent.iter().any(|(_, msg)| msg.contains("@another")));
+
+    bot.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_announce_reaches_every_channel_and_reports_failures() {
+    let connection = MockConnection::new("mock", vec!["testchannel".to_string(), "costream".to_string()]);
+    let dropping = MockConnection::new("dropping", vec!["partner".to_string()]);
+    let offline = MockConnection::new("offline", vec!["sleeping".to_string()]);
+    let mut bot = ChatBot::new();
+    bot.add_connection(Box::new(connection.clone())).await;
+    bot.add_connection(Box::new(dropping.clone())).await;
+    bot.add_connection(Box::new(offline.clone())).await;
+    bot.set_platform_rate_limits("mock", RateLimitConfig {
+        messages_per_second: 20.0,
+        burst_limit: 1,
+        cooldown_seconds: 0,
+    }).await;
+    bot.start().await.unwrap();
+    offline.clone().disconnect().await.unwrap();
+    dropping.set_drop_reason(Some("msg_ratelimit".to_string())).await;
+
+    // Viewers can't announce
+    connection.inject(chat_message("viewer", "!announce free stuff")).unwrap();
+
+    let mut moderator = chat_message("modperson", "!announce Raid incoming in 5 minutes!");
+    moderator.is_mod = true;
+    connection.inject(moderator).unwrap();
+
+    for channel in ["testchannel", "costream"] {
+        let announced = connection.wait_for_sent(WAIT, |ch, msg| ch == channel && msg == "Raid incoming in 5 minutes!").await;
+        assert!(announced.is_some(), "announcement missing in {}", channel);
+    }
+    let report = connection.wait_for_sent(WAIT, |_, msg| msg.starts_with("Announced to")).await;
+    assert_eq!(
+        report,
+        Some((
+            "testchannel".to_string(),
+            "Announced to 2/4 channels; failed: dropping#partner (dropped: msg_ratelimit), offline#sleeping (not connected)".to_string()
+        ))
+    );
+    assert!(!connection.sent_messages().await.iter().any(|(_, msg)| msg.contains("free stuff")));
+
+    // The same result is available directly
+    let report = bot.broadcast("Thanks for watching").await;
+    assert_eq!(report.delivered.len(), 2);
+    assert_eq!(report.failed.len(), 2);
+
+    bot.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_command_rate_limit_throttles_each_user() {
+    let connection = MockConnection::new("mock", vec!["testchannel".to_string()]);
+    let mut bot = ChatBot::new();
+    bot.add_connection(Box::new(connection.clone())).await;
+    bot.add_command("hello".to_string(), "Hello $(user)!".to_string(), false, 0).await;
+    bot.set_command_rate_limit(2, true).await;
+    bot.start().await.unwrap();
+
+    for i in 0..4 {
+        connection.inject(chat_message("viewer", &format!("!hello {}", i))).unwrap();
+    }
+    let mut moderator = chat_message("modname", "!hello");
+    moderator.is_mod = true;
+    for _ in 0..3 {
+        connection.inject(moderator.clone()).unwrap();
+    }
+    connection.inject(chat_message("other", "!hello")).unwrap();
+
+    // Messages on one stream are handled in order, so the last reply means all were seen
+    assert!(connection.wait_for_sent(WAIT, |_, msg| msg == "Hello other!").await.is_some());
+    // Repeated replies get an invisible suffix from the duplicate guard, so match on the prefix
+    assert!(eventually(|| async {
+        connection.sent_messages().await.iter().filter(|(_, msg)| msg.starts_with("Hello modname!")).count() == 3
+    }).await);
+    let sent = connection.sent_messages().await;
+    assert_eq!(sent.iter().filter(|(_, msg)| msg.starts_with("Hello viewer!")).count(), 2);
+
+    bot.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_moderation_actions_are_audited() {
+    let dir = tempfile::tempdir().unwrap();
+    let connection = MockConnection::new("mock", vec!["testchannel".to_string()]);
+    let mut bot = ChatBot::new();
+    bot.add_connection(Box::new(connection.clone())).await;
+    bot.add_blacklist_filter(
+        vec!["buyfollowers".to_string()],
+        Some(60),
+        None,
+        None,
+        None,
+        Some("no spam please".to_string()),
+    ).await.unwrap();
+    bot.set_audit_log(Some(dir.path().join("moderation_audit.jsonl"))).await.unwrap();
+    bot.start().await.unwrap();
+
+    connection.inject(chat_message("spammer", "cheap buyfollowers here")).unwrap();
+    connection.inject(chat_message("viewer", "hello everyone")).unwrap();
+    assert!(connection.wait_for_sent(WAIT, |_, msg| msg.contains("@spammer")).await.is_some());
+
+    let spammer = AuditQuery { username: Some("spammer".to_string()), ..Default::default() };
+    let entries = bot.query_audit_log(&spammer).await.unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].source, AuditSource::Automatic);
+    assert_eq!(entries[0].message_snippet, "cheap buyfollowers here");
+    assert!(entries[0].filter.is_some());
+    assert!(matches!(entries[0].action, ModerationAction::WarnUser { .. }));
+    assert!(!entries[0].dry_run);
+
+    let viewer = AuditQuery { username: Some("viewer".to_string()), ..Default::default() };
+    assert!(bot.query_audit_log(&viewer).await.unwrap().is_empty());
+
+    let output = dir.path().join("export.json");
+    assert_eq!(bot.export_audit_log(&AuditQuery::default(), &output).await.unwrap(), 1);
+
+    bot.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_giveaway_entrants_are_managed_from_chat() {
+    let connection = MockConnection::new("mock", vec!["testchannel".to_string()]);
+    let mut bot = ChatBot::new();
+    bot.add_connection(Box::new(connection.clone())).await;
+    bot.start().await.unwrap();
+
+    let mut moderator = chat_message("moderator", "!gstart keyword !join");
+    moderator.is_mod = true;
+    connection.inject(moderator.clone()).unwrap();
+    assert!(connection.wait_for_sent(WAIT, |_, msg| msg.contains("Keyword Giveaway started")).await.is_some());
+
+    // Only moderators may add or remove entrants
+    connection.inject(chat_message("viewer", "!giveaway add viewer")).unwrap();
+    assert!(connection.wait_for_sent(WAIT, |_, msg| msg == "Only moderators can manage giveaway entrants.").await.is_some());
+
+    moderator.content = "!giveaway add @lurker".to_string();
+    connection.inject(moderator.clone()).unwrap();
+    assert!(connection.wait_for_sent(WAIT, |_, msg| msg == "lurker has been added to the giveaway (1 participants)").await.is_some());
+    assert_eq!(bot.get_giveaway_eligible_users().await.len(), 1);
+
+    moderator.content = "!giveaway remove @lurker".to_string();
+    connection.inject(moderator).unwrap();
+    assert!(connection.wait_for_sent(WAIT, |_, msg| msg == "lurker has been removed from the giveaway (0 participants)").await.is_some());
+    assert!(bot.get_giveaway_status().await.is_some());
+
+    bot.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_giveaway_entrants_account_age_is_looked_up_before_the_draw() {
+    use notabot::bot::giveaway_fraud::FraudWeights;
+    use notabot::types::GiveawayType;
+
+    let connection = MockConnection::new("mock", vec!["testchannel".to_string()]);
+    let now = chrono::Utc::now();
+    connection.set_account_created("regular_viewer", now - chrono::Duration::days(400)).await;
+    connection.set_account_created("fresh_account", now - chrono::Duration::hours(1)).await;
+    let mut bot = ChatBot::new();
+    bot.add_connection(Box::new(connection.clone())).await;
+    bot.set_giveaway_fraud_weights(FraudWeights {
+        account_age: 1.0,
+        message_history: 0.0,
+        duplicate_entries: 0.0,
+        username_similarity: 0.0,
+    }).await;
+    bot.start().await.unwrap();
+    bot.start_giveaway(
+        GiveawayType::Keyword {
+            keyword: "!enter".to_string(),
+            case_sensitive: false,
+            anti_spam: true,
+            max_entries_per_user: Some(1),
+        },
+        "moderator".to_string(),
+        "testchannel".to_string(),
+        "mock".to_string(),
+        None,
+    ).await.unwrap();
+
+    connection.inject(chat_message("regular_viewer", "!enter")).unwrap();
+    connection.inject(chat_message("fresh_account", "!enter")).unwrap();
+    let giveaways = bot.get_giveaway_system();
+    assert!(eventually(|| async {
+        giveaways.account_created("mock", "regular_viewer").await.is_some()
+            && giveaways.account_created("mock", "fresh_account").await.is_some()
+    }).await);
+
+    let winner = bot.end_giveaway(false).await.unwrap().unwrap();
+    assert_eq!(winner.username, "regular_viewer");
+
+    bot.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_ai_pattern_actions_are_audited_with_confidence() {
+    let dir = tempfile::tempdir().unwrap();
+    let connection = MockConnection::new("mock", vec!["testchannel".to_string()]);
+    let mut bot = ChatBot::new();
+    bot.add_connection(Box::new(connection.clone())).await;
+    bot.set_audit_log(Some(dir.path().join("moderation_audit.jsonl"))).await.unwrap();
+    let enhanced = std::sync::Arc::new(bot.create_enhanced_moderation());
+    enhanced.add_advanced_pattern(AdvancedPattern::Leetspeak("follow".to_string())).await.unwrap();
+    bot.attach_enhanced_moderation(enhanced).await;
+    bot.start().await.unwrap();
+
+    connection.inject(chat_message("spammer", "f0ll0w my channel")).unwrap();
+
+    let spammer = AuditQuery { username: Some("spammer".to_string()), ..Default::default() };
+    assert!(eventually(|| async { bot.query_audit_log(&spammer).await.unwrap().len() == 1 }).await);
+    let entry = &bot.query_audit_log(&spammer).await.unwrap()[0];
+    assert_eq!(entry.source, AuditSource::Automatic);
+    assert!(entry.confidence.is_some_and(|confidence| confidence > 0.0));
+
+    bot.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_moderators_set_ai_strictness_from_chat() {
+    use notabot::bot::enhanced_moderation::AiStrictness;
+
+    let connection = MockConnection::new("mock", vec!["testchannel".to_string()]);
+    let mut bot = ChatBot::new();
+    bot.add_connection(Box::new(connection.clone())).await;
+    let enhanced = std::sync::Arc::new(bot.create_enhanced_moderation());
+    bot.attach_enhanced_moderation(std::sync::Arc::clone(&enhanced)).await;
+    bot.start().await.unwrap();
+
+    // Viewers can't change it
+    connection.inject(chat_message("viewer", "!aistrict off")).unwrap();
+    connection.inject(chat_message("moderator", "!aistrict high").with_mod(true)).unwrap();
+    let reply = connection.wait_for_sent(WAIT, |_, msg| msg.contains("AI strictness set to")).await;
+    assert_eq!(reply.unwrap().1, "🤖 AI strictness set to high");
+    assert_eq!(enhanced.get_ai_strictness().await, AiStrictness::High);
+
+    bot.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_moderators_toggle_pattern_collections_from_chat() {
+    use notabot::bot::pattern_matching::AdvancedPattern;
+
+    let connection = MockConnection::new("mock", vec!["testchannel".to_string()]);
+    let mut bot = ChatBot::new();
+    bot.add_connection(Box::new(connection.clone())).await;
+    let enhanced = std::sync::Arc::new(bot.create_enhanced_moderation());
+    enhanced.add_collection_pattern("leetspeak", AdvancedPattern::Leetspeak("secretword".to_string())).await.unwrap();
+    bot.attach_enhanced_moderation(std::sync::Arc::clone(&enhanced)).await;
+    bot.start().await.unwrap();
+
+    connection.inject(chat_message("viewer", "!patterns leetspeak off")).unwrap();
+    connection.inject(chat_message("moderator", "!patterns leetspeak off").with_mod(true)).unwrap();
+    let reply = connection.wait_for_sent(WAIT, |_, msg| msg.contains("Pattern collection 'leetspeak'")).await;
+    assert_eq!(reply.unwrap().1, "🧩 Pattern collection 'leetspeak' disabled");
+    assert_eq!(enhanced.get_pattern_collections().await, vec![("leetspeak".to_string(), false)]);
+
+    connection.inject(chat_message("moderator", "!patterns nope on").with_mod(true)).unwrap();
+    let reply = connection.wait_for_sent(WAIT, |_, msg| msg.contains("Unknown pattern collection")).await;
+    assert_eq!(reply.unwrap().1, "❌ Unknown pattern collection: nope");
+
+    bot.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_platform_timeouts_are_audited_as_manual_actions() {
+    use notabot::platforms::twitch_eventsub::TwitchEvent;
+
+    let dir = tempfile::tempdir().unwrap();
+    let connection = MockConnection::new("mock", vec!["testchannel".to_string()]);
+    let mut bot = ChatBot::new();
+    bot.add_connection(Box::new(connection.clone())).await;
+    bot.set_audit_log(Some(dir.path().join("moderation_audit.jsonl"))).await.unwrap();
+    bot.start().await.unwrap();
+
+    connection.inject_event(TwitchEvent::UserBanned {
+        channel: "testchannel".to_string(),
+        username: "troll".to_string(),
+        moderator: "moduser".to_string(),
+        reason: "spamming".to_string(),
+        ends_at: Some(chrono::Utc::now() + chrono::Duration::seconds(600)),
+    }).unwrap();
+
+    let troll = AuditQuery { username: Some("troll".to_string()), ..Default::default() };
+    assert!(eventually(|| async { bot.query_audit_log(&troll).await.unwrap().len() == 1 }).await);
+    let entry = &bot.query_audit_log(&troll).await.unwrap()[0];
+    assert_eq!(entry.source, AuditSource::Manual { moderator: "moduser".to_string() });
+    assert!(matches!(entry.action, ModerationAction::TimeoutUser { duration_seconds } if duration_seconds > 590));
+
+    bot.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_responses_go_out_through_send_accounts() {
+    use notabot::bot::connection_pool::{ConnectionPool, ConnectionRole};
+    use std::sync::Arc;
+
+    let listener = MockConnection::new("mock", vec!["testchannel".to_string()]);
+    let sender = MockConnection::new("mock", vec!["testchannel".to_string()]);
+    let pool = Arc::new(ConnectionPool::with_default_config());
+    pool.add_account("mock", "verifiedbot", ConnectionRole::Send, Box::new(sender.clone())).await.unwrap();
+
+    let mut bot = ChatBot::new();
+    bot.add_connection(Box::new(listener.clone())).await;
+    bot.attach_connection_pool(pool).await;
+    bot.add_command("hello".to_string(), "Hello $(user)!".to_string(), false, 0).await;
+    bot.add_blacklist_filter(
+        vec!["buyfollowers".to_string()],
+        Some(60),
+        None,
+        None,
+        None,
+        Some("no spam please".to_string()),
+    ).await.unwrap();
+    bot.start().await.unwrap();
+
+    // Chat is read from the listen connection, the reply is sent by the send account
+    listener.inject(chat_message("viewer", "!hello")).unwrap();
+    let reply = sender.wait_for_sent(WAIT, |_, msg| msg == "Hello viewer!").await;
+    assert_eq!(reply, Some(("testchannel".to_string(), "Hello viewer!".to_string())));
+
+    // Moderation stays with the main account
+    listener.inject(chat_message("spammer", "cheap buyfollowers here")).unwrap();
+    assert!(listener.wait_for_sent(WAIT, |_, msg| msg.contains("@spammer")).await.is_some());
+    assert!(!listener.sent_messages().await.iter().any(|(_, msg)| msg.starts_with("Hello")));
+    assert!(!sender.sent_messages().await.iter().any(|(_, msg)| msg.contains("@spammer")));
+
+    bot.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_shadow_muted_users_are_dropped_until_the_mute_ends() {
+    let connection = MockConnection::new("mock", vec!["testchannel".to_string()]);
+    let mut bot = ChatBot::new();
+    bot.add_connection(Box::new(connection.clone())).await;
+    bot.add_command("hello".to_string(), "Hello $(user)!".to_string(), false, 0).await;
+    bot.get_moderation_system().add_spam_filter_advanced(
+        "bot_links".to_string(),
+        SpamFilterType::Blacklist {
+            patterns: vec![BlacklistPattern::Literal("cheap-viewers".to_string())],
+            case_sensitive: false,
+            whole_words_only: false,
+            except_patterns: Vec::new(),
+        },
+        ModerationEscalation {
+            first_offense: ModerationAction::ShadowMute { duration_seconds: 1 },
+            repeat_offense: ModerationAction::ShadowMute { duration_seconds: 1 },
+            offense_window_seconds: 3600,
+        },
+        ExemptionLevel::None,
+        true,
+        None,
+    ).await.unwrap();
+    bot.start().await.unwrap();
+
+    connection.inject(chat_message("suspect", "cheap-viewers dot com")).unwrap();
+    assert!(eventually(|| async {
+        bot.get_user_points("mock", "suspect").await.is_some_and(|p| p.messages_sent == 1)
+    }).await);
+
+    // Muted: no reply and no points
+    connection.inject(chat_message("suspect", "!hello")).unwrap();
+    connection.inject(chat_message("viewer", "!hello")).unwrap();
+    assert!(connection.wait_for_sent(WAIT, |_, msg| msg == "Hello viewer!").await.is_some());
+    let sent = connection.sent_messages().await;
+    assert!(!sent.iter().any(|(_, msg)| msg == "Hello suspect!"), "{:?}", sent);
+    assert_eq!(bot.get_user_points("mock", "suspect").await.unwrap().messages_sent, 1);
+
+    // Heard again once the mute runs out
+    sleep(Duration::from_millis(1100)).await;
+    connection.inject(chat_message("suspect", "!hello")).unwrap();
+    assert!(connection.wait_for_sent(WAIT, |_, msg| msg == "Hello suspect!").await.is_some());
+
+    bot.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_linking_merges_state_and_links_are_saved_in_the_data_dir() {
+    let dir = tempfile::tempdir().unwrap();
+    let data_dir = DataDir::new(dir.path());
+    let bot = ChatBot::new();
+    bot.set_data_dir(&data_dir).await.unwrap();
+    let points = bot.get_points_system();
+    points.process_message(&ChatMessage::new("twitch", "testchannel", "Streamfan", "hi")).await.unwrap();
+    points.process_message(&ChatMessage::new("youtube", "testchannel", "StreamFan_YT", "hi")).await.unwrap();
+    points.add_points("twitch", "Streamfan", 100, "test").await.unwrap();
+    points.add_points("youtube", "StreamFan_YT", 50, "test").await.unwrap();
+    let balance = |user: Option<UserPoints>| user.map(|user| user.points).unwrap_or_default();
+    let combined = balance(points.get_user_points("twitch", "Streamfan").await)
+        + balance(points.get_user_points("youtube", "StreamFan_YT").await);
+    bot.get_moderation_system().shadow_mute("youtube", "StreamFan_YT", 60).await;
+
+    // What the YouTube account had joins the Twitch identity
+    bot.link_identities(("twitch", "Streamfan"), ("youtube", "StreamFan_YT")).await.unwrap();
+    assert_eq!(balance(points.get_user_points("youtube", "StreamFan_YT").await), combined);
+    assert_eq!(points.get_user_points("twitch", "Streamfan").await.unwrap().messages_sent, 2);
+    let from_twitch = ChatMessage::new("twitch", "testchannel", "streamfan", "hi");
+    assert!(bot.get_moderation_system().is_shadow_muted(&from_twitch).await);
+
+    // A restarted bot loads the link
+    let restarted = ChatBot::new();
+    restarted.set_data_dir(&data_dir).await.unwrap();
+    assert_eq!(restarted.resolve_identity("youtube", "streamfan_yt").await, "twitch:streamfan");
+
+    // Unlinking the canonical account leaves the balance with the account still linked
+    assert!(bot.unlink_identity("twitch", "Streamfan").await.unwrap());
+    assert_eq!(balance(points.get_user_points("youtube", "StreamFan_YT").await), combined);
+    assert!(points.get_user_points("twitch", "Streamfan").await.is_none());
+    assert!(!bot.unlink_identity("youtube", "StreamFan_YT").await.unwrap());
+
+    let restarted = ChatBot::new();
+    restarted.set_data_dir(&data_dir).await.unwrap();
+    assert_eq!(restarted.resolve_identity("youtube", "streamfan_yt").await, "youtube:streamfan_yt");
+}
+
+#[tokio::test]
+async fn test_linked_accounts_share_points_and_mutes_across_platforms() {
+    let twitch = MockConnection::new("twitch", vec!["testchannel".to_string()]);
+    let youtube = MockConnection::new("youtube", vec!["testchannel".to_string()]);
+    let mut bot = ChatBot::new();
+    bot.add_connection(Box::new(twitch.clone())).await;
+    bot.add_connection(Box::new(youtube.clone())).await;
+    bot.add_command("hello".to_string(), "Hello $(user)!".to_string(), false, 0).await;
+
+    let identity = bot.link_identities(("twitch", "Streamfan"), ("youtube", "StreamFan_YT")).await.unwrap();
+    assert_eq!(identity, "twitch:streamfan");
+    assert_eq!(bot.resolve_identity("youtube", "streamfan_yt").await, "twitch:streamfan");
+    bot.start().await.unwrap();
+
+    twitch.inject(ChatMessage::new("twitch", "testchannel", "streamfan", "hi from twitch")).unwrap();
+    youtube.inject(ChatMessage::new("youtube", "testchannel", "StreamFan_YT", "hi from youtube")).unwrap();
+    assert!(eventually(|| async {
+        bot.get_user_points("youtube", "StreamFan_YT").await.is_some_and(|p| p.messages_sent == 2)
+    }).await);
+
+    // A shadow mute on one account silences the other
+    bot.get_moderation_system().shadow_mute("twitch", "STREAMFAN", 60).await;
+    youtube.inject(ChatMessage::new("youtube", "testchannel", "StreamFan_YT", "!hello")).unwrap();
+    youtube.inject(ChatMessage::new("youtube", "testchannel", "viewer", "!hello")).unwrap();
+    assert!(youtube.wait_for_sent(WAIT, |_, msg| msg == "Hello viewer!").await.is_some());
+    assert!(!youtube.sent_messages().await.iter().any(|(_, msg)| msg == "Hello StreamFan_YT!"));
+
+    // Once unlinked, the YouTube account is its own user again
+    assert!(bot.unlink_identity("youtube", "StreamFan_YT").await.unwrap());
+    youtube.inject(ChatMessage::new("youtube", "testchannel", "StreamFan_YT", "!hello")).unwrap();
+    assert!(youtube.wait_for_sent(WAIT, |_, msg| msg == "Hello StreamFan_YT!").await.is_some());
+
+    bot.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_moderators_link_accounts_from_chat() {
+    let dir = tempfile::tempdir().unwrap();
+    let data_dir = DataDir::new(dir.path());
+    let connection = MockConnection::new("twitch", vec!["testchannel".to_string()]);
+    let mut bot = ChatBot::new();
+    bot.add_connection(Box::new(connection.clone())).await;
+    bot.set_data_dir(&data_dir).await.unwrap();
+    bot.start().await.unwrap();
+
+    let points = bot.get_points_system();
+    points.process_message(&ChatMessage::new("youtube", "testchannel", "StreamFan_YT", "hi")).await.unwrap();
+    points.add_points("youtube", "StreamFan_YT", 50, "test").await.unwrap();
+    let balance = points.get_user_points("youtube", "StreamFan_YT").await.unwrap().points;
+
+    // Viewers can't link accounts
+    connection.inject(ChatMessage::new("twitch", "testchannel", "viewer", "!linkaccounts twitch:viewer youtube:StreamFan_YT")).unwrap();
+    connection.inject(ChatMessage::new("twitch", "testchannel", "moderator", "!linkaccounts twitch:Streamfan youtube:StreamFan_YT").with_mod(true)).unwrap();
+    let reply = connection.wait_for_sent(WAIT, |_, msg| msg.contains("Linked")).await;
+    assert_eq!(reply.unwrap().1, "🔗 Linked youtube:StreamFan_YT to twitch:streamfan; they now share points and moderation history");
+    assert_eq!(points.get_user_points("twitch", "Streamfan").await.unwrap().points, balance);
+
+    // The link is saved like one made through the API
+    let restarted = ChatBot::new();
+    restarted.set_data_dir(&data_dir).await.unwrap();
+    assert_eq!(restarted.resolve_identity("youtube", "streamfan_yt").await, "twitch:streamfan");
+
+    connection.inject(ChatMessage::new("twitch", "testchannel", "moderator", "!unlinkaccount youtube:StreamFan_YT").with_mod(true)).unwrap();
+    assert!(connection.wait_for_sent(WAIT, |_, msg| msg == "🔗 Unlinked youtube:StreamFan_YT").await.is_some());
+    assert_eq!(bot.resolve_identity("youtube", "streamfan_yt").await, "youtube:streamfan_yt");
+
+    bot.shutdown().await.unwrap();
+}